@@ -10,15 +10,13 @@
 //! - Stable ID operations
 //! - Command buffer operations
 //! - Persistence operations (save/load)
+//! - Query iteration over 1, 2 and 4 components
+//! - Archetype transitions (insert/remove)
+//! - Queries over fragmented archetypes
+//! - Change detection
 //!
-//! ## Missing Benchmarks (requires API implementation)
-//!
-//! - Component access (get, get_mut) - requires World::get/get_mut
-//! - Component insertion/removal - requires World::insert/remove
-//! - Query iteration - requires World::query integration
-//! - Filtered queries - requires World::query_filtered
-//!
-//! See docs/dev/API_GAPS.md for details on missing APIs.
+//! The query scenarios come from [`pecs::bench_util`], which also backs the
+//! timing assertions in `tests/bench_regression_tests.rs`.
 //!
 //! ## Performance Targets
 //!
@@ -28,6 +26,7 @@
 //! - Persistence: < 1ms per 1000 entities (target: < 0.5ms per 1000 entities)
 
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use pecs::bench_util;
 use pecs::prelude::*;
 use std::hint::black_box;
 
@@ -417,7 +416,7 @@ fn bench_persistence_roundtrip_binary(c: &mut Criterion) {
                     }
                     world
                 },
-                |world| {
+                |mut world| {
                     let mut buffer = Vec::new();
                     world.save_binary(&mut buffer).unwrap();
                     let mut cursor = std::io::Cursor::new(&buffer);
@@ -514,6 +513,75 @@ fn bench_persistence_file_size_json(c: &mut Criterion) {
     group.finish();
 }
 
+// ============================================================================
+// Query Benchmarks
+// ============================================================================
+
+fn bench_query_iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("query_iterate");
+
+    for size in [1000, 10000].iter() {
+        group.throughput(Throughput::Elements(*size as u64));
+        let mut world = World::new();
+        bench_util::spawn_moving(&mut world, *size);
+
+        group.bench_with_input(BenchmarkId::new("one", size), size, |b, _| {
+            b.iter(|| black_box(bench_util::iterate_one(&mut world)));
+        });
+        group.bench_with_input(BenchmarkId::new("two", size), size, |b, _| {
+            b.iter(|| black_box(bench_util::iterate_two(&mut world)));
+        });
+        group.bench_with_input(BenchmarkId::new("four", size), size, |b, _| {
+            b.iter(|| black_box(bench_util::iterate_four(&mut world)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_query_fragmented(c: &mut Criterion) {
+    let mut group = c.benchmark_group("query_fragmented");
+
+    for size in [1000, 10000].iter() {
+        group.throughput(Throughput::Elements(*size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let mut world = World::new();
+            bench_util::spawn_fragmented(&mut world, size);
+
+            b.iter(|| black_box(bench_util::iterate_two(&mut world)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_archetype_transition(c: &mut Criterion) {
+    let mut group = c.benchmark_group("archetype_transition");
+
+    for size in [100, 1000].iter() {
+        group.throughput(Throughput::Elements(*size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let mut world = World::new();
+            let entities = bench_util::spawn_fragmented(&mut world, size);
+
+            b.iter(|| bench_util::toggle_scale(&mut world, &entities));
+        });
+    }
+    group.finish();
+}
+
+fn bench_change_detection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("change_detection");
+
+    for stride in [1, 10, 100].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(stride), stride, |b, &stride| {
+            let mut world = World::new();
+            let entities = bench_util::spawn_moving(&mut world, 10000);
+
+            b.iter(|| black_box(bench_util::modify_and_detect(&mut world, &entities, stride)));
+        });
+    }
+    group.finish();
+}
+
 // ============================================================================
 // Criterion Configuration
 // ============================================================================
@@ -559,10 +627,19 @@ criterion_group!(
     bench_persistence_file_size_json
 );
 
+criterion_group!(
+    query_benches,
+    bench_query_iterate,
+    bench_query_fragmented,
+    bench_archetype_transition,
+    bench_change_detection
+);
+
 criterion_main!(
     entity_benches,
     stable_id_benches,
     command_benches,
     world_benches,
-    persistence_benches
+    persistence_benches,
+    query_benches
 );
@@ -0,0 +1,33 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Feeds arbitrary bytes to the binary save loader.
+//!
+//! Run with `cargo +nightly fuzz run binary_deserializer` from the
+//! repository root. Any panic, abort or out-of-memory is a bug: corrupt
+//! files must be rejected with an error.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pecs::persistence::LoadOptions;
+use pecs::persistence::binary::BinaryDeserializer;
+
+fuzz_target!(|data: &[u8]| {
+    // Lenient loading gets past checksum failures into entity reconstruction
+    let _ = BinaryDeserializer::with_options(LoadOptions::lenient()).deserialize(&mut &data[..]);
+    let _ = BinaryDeserializer::new().read_info(&mut &data[..]);
+});
@@ -0,0 +1,401 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Command-line tool for PECS save files.
+//!
+//! Built only on the public persistence API, so anything this tool can do a
+//! downstream application can do too.
+//!
+//! ```text
+//! pecs-cli inspect world.pecs --entities
+//! pecs-cli convert world.pecs world.json --from binary --to json
+//! pecs-cli diff before.pecs after.pecs
+//! pecs-cli validate world.pecs --checksum
+//! pecs-cli extract world.pecs --stable-id <UUID> --output entity.json
+//! ```
+
+use clap::{Parser, Subcommand, ValueEnum};
+use pecs::StableId;
+use pecs::World;
+use pecs::persistence::binary::format::{Footer, Header, MAGIC_BYTES, calculate_checksum};
+use pecs::persistence::{BinaryPlugin, JsonPlugin, PersistenceError, PersistenceManager, Result};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "pecs-cli",
+    version,
+    about = "Inspect and convert PECS save files"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print a summary of a save file
+    Inspect {
+        /// Save file to inspect
+        file: PathBuf,
+
+        /// Input format (detected from the file contents by default)
+        #[arg(long)]
+        from: Option<Format>,
+
+        /// List the stable ID of every entity
+        #[arg(long)]
+        entities: bool,
+    },
+
+    /// Convert a save file between formats
+    Convert {
+        /// Save file to read
+        input: PathBuf,
+
+        /// File to write
+        output: PathBuf,
+
+        /// Input format (detected from the file contents by default)
+        #[arg(long)]
+        from: Option<Format>,
+
+        /// Output format
+        #[arg(long)]
+        to: Format,
+    },
+
+    /// Compare the entities of two save files
+    Diff {
+        /// Original save file
+        old: PathBuf,
+
+        /// Updated save file
+        new: PathBuf,
+    },
+
+    /// Check that a save file loads cleanly
+    Validate {
+        /// Save file to validate
+        file: PathBuf,
+
+        /// Input format (detected from the file contents by default)
+        #[arg(long)]
+        from: Option<Format>,
+
+        /// Also verify the binary footer checksum and report both values
+        #[arg(long)]
+        checksum: bool,
+    },
+
+    /// Write a single entity to a new save file
+    Extract {
+        /// Save file to read
+        file: PathBuf,
+
+        /// Stable ID of the entity to extract
+        #[arg(long)]
+        stable_id: StableId,
+
+        /// File to write
+        #[arg(long, short)]
+        output: PathBuf,
+
+        /// Input format (detected from the file contents by default)
+        #[arg(long)]
+        from: Option<Format>,
+
+        /// Output format
+        #[arg(long, default_value = "json")]
+        to: Format,
+    },
+}
+
+/// On-disk save formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Binary,
+    Json,
+}
+
+impl Format {
+    /// Name of the plugin registered for this format in [`manager`].
+    fn plugin(self) -> &'static str {
+        match self {
+            Format::Binary => "binary",
+            Format::Json => "json",
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode> {
+    match command {
+        Command::Inspect {
+            file,
+            from,
+            entities,
+        } => inspect(&file, from, entities),
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+        } => {
+            let from = resolve_format(&input, from)?;
+            manager().convert(&input, from.plugin(), &output, to.plugin())?;
+            println!("Converted {} to {}", input.display(), output.display());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Diff { old, new } => diff(&old, &new),
+        Command::Validate {
+            file,
+            from,
+            checksum,
+        } => validate(&file, from, checksum),
+        Command::Extract {
+            file,
+            stable_id,
+            output,
+            from,
+            to,
+        } => {
+            let world = load(&file, from)?;
+            let mut extracted = extract(&world, stable_id)?;
+            save(&mut extracted, &output, to)?;
+            println!("Extracted {} to {}", stable_id, output.display());
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn inspect(path: &Path, from: Option<Format>, list_entities: bool) -> Result<ExitCode> {
+    let format = resolve_format(path, from)?;
+    println!("File:     {}", path.display());
+    println!("Format:   {:?}", format);
+
+    match format {
+        Format::Binary => {
+            let header = Header::read(&mut open(path)?).map_err(PersistenceError::Io)?;
+            println!("Version:  {}", header.version);
+            println!("Flags:    {:#x}", header.flags.bits());
+            println!("Types:    {}", header.component_type_count);
+        }
+        Format::Json => {
+            let value: serde_json::Value = serde_json::from_reader(open(path)?)
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            if let Some(version) = value.get("version") {
+                println!("Version:  {}", version);
+            }
+            if let Some(timestamp) = value.get("timestamp").and_then(|t| t.as_str()) {
+                println!("Saved:    {}", timestamp);
+            }
+        }
+    }
+
+    let world = load(path, Some(format))?;
+    println!("Entities: {}", world.len());
+    if list_entities {
+        for stable_id in stable_ids(&world) {
+            println!("  {}", stable_id);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(old: &Path, new: &Path) -> Result<ExitCode> {
+    let old_ids = stable_ids(&load(old, None)?);
+    let new_ids = stable_ids(&load(new, None)?);
+
+    let removed: Vec<_> = old_ids.difference(&new_ids).collect();
+    let added: Vec<_> = new_ids.difference(&old_ids).collect();
+    for id in &removed {
+        println!("- {}", id);
+    }
+    for id in &added {
+        println!("+ {}", id);
+    }
+    println!(
+        "{} removed, {} added, {} unchanged",
+        removed.len(),
+        added.len(),
+        old_ids.intersection(&new_ids).count()
+    );
+
+    Ok(if removed.is_empty() && added.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    })
+}
+
+fn validate(path: &Path, from: Option<Format>, checksum: bool) -> Result<ExitCode> {
+    let format = resolve_format(path, from)?;
+    if checksum {
+        if format != Format::Binary {
+            return Err(PersistenceError::Custom(
+                "only binary saves carry a checksum".to_string(),
+            ));
+        }
+        let (stored, computed) = checksums(path)?;
+        println!("Stored checksum:   {:#018x}", stored);
+        println!("Computed checksum: {:#018x}", computed);
+        if stored != computed {
+            println!("INVALID: checksum mismatch");
+            return Ok(ExitCode::from(1));
+        }
+    }
+
+    let world = load(path, Some(format))?;
+    println!("OK: {} entities", world.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the checksum stored in a binary save's footer and the checksum
+/// of the data preceding it.
+fn checksums(path: &Path) -> Result<(u64, u64)> {
+    let bytes = std::fs::read(path).map_err(PersistenceError::Io)?;
+    let data_len = bytes
+        .len()
+        .checked_sub(8)
+        .ok_or_else(|| PersistenceError::InvalidFormat("file too short".to_string()))?;
+    let footer = Footer::read(&mut &bytes[data_len..]).map_err(PersistenceError::Io)?;
+    Ok((footer.checksum, calculate_checksum(&bytes[..data_len])))
+}
+
+/// Builds a world containing only the entity with `stable_id`.
+fn extract(world: &World, stable_id: StableId) -> Result<World> {
+    if world.get_entity_id(stable_id).is_none() {
+        return Err(PersistenceError::Custom(format!(
+            "no entity with stable ID {}",
+            stable_id
+        )));
+    }
+    let mut extracted = World::new();
+    extracted
+        .spawn_empty_with_stable_id(stable_id)
+        .map_err(|e| PersistenceError::Custom(e.to_string()))?;
+    Ok(extracted)
+}
+
+fn stable_ids(world: &World) -> BTreeSet<String> {
+    world
+        .iter_entities()
+        .map(|(_, stable_id)| stable_id.to_string())
+        .collect()
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(PersistenceError::Io)
+}
+
+/// Uses the explicit format if given, otherwise sniffs the magic bytes.
+fn resolve_format(path: &Path, from: Option<Format>) -> Result<Format> {
+    if let Some(format) = from {
+        return Ok(format);
+    }
+    let mut magic = [0u8; 4];
+    let read = open(path)?.read(&mut magic).map_err(PersistenceError::Io)?;
+    Ok(detect_format(&magic[..read]))
+}
+
+fn detect_format(prefix: &[u8]) -> Format {
+    if prefix == MAGIC_BYTES {
+        Format::Binary
+    } else {
+        Format::Json
+    }
+}
+
+/// A manager with a plugin registered for every [`Format`].
+fn manager() -> PersistenceManager {
+    let mut manager = PersistenceManager::new();
+    manager.register_plugin(Format::Binary.plugin(), Box::new(BinaryPlugin::new()));
+    manager.register_plugin(Format::Json.plugin(), Box::new(JsonPlugin::new()));
+    manager
+}
+
+fn load(path: &Path, from: Option<Format>) -> Result<World> {
+    let format = resolve_format(path, from)?;
+    let mut reader = open(path)?;
+    match format {
+        Format::Binary => World::load_binary(&mut reader),
+        Format::Json => World::load_json(&mut reader),
+    }
+}
+
+fn save(world: &mut World, path: &Path, format: Format) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path).map_err(PersistenceError::Io)?);
+    match format {
+        Format::Binary => world.save_binary(&mut writer)?,
+        Format::Json => world.save_json(&mut writer)?,
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_format_from_magic_bytes() {
+        assert_eq!(detect_format(b"PECS"), Format::Binary);
+        assert_eq!(detect_format(b"{\n  "), Format::Json);
+        assert_eq!(detect_format(b""), Format::Json);
+    }
+
+    #[test]
+    fn extract_keeps_only_the_requested_entity() {
+        let mut world = World::new();
+        world.spawn_empty();
+        let entity = world.spawn_empty();
+        let stable_id = world.get_stable_id(entity).unwrap();
+
+        let extracted = extract(&world, stable_id).unwrap();
+        assert_eq!(extracted.len(), 1);
+        assert!(extracted.get_entity_id(stable_id).is_some());
+        assert!(extract(&world, StableId::from_raw(1)).is_err());
+    }
+
+    #[test]
+    fn checksum_of_saved_file_matches() {
+        let path = std::env::temp_dir().join("pecs_cli_checksum_test.pecs");
+        let mut world = World::new();
+        world.spawn_empty();
+        save(&mut world, &path, Format::Binary).unwrap();
+
+        let (stored, computed) = checksums(&path).unwrap();
+        assert_eq!(stored, computed);
+        assert_eq!(resolve_format(&path, None).unwrap(), Format::Binary);
+        let _ = std::fs::remove_file(path);
+    }
+}
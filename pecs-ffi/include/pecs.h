@@ -0,0 +1,50 @@
+/*
+ * Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* C API of the pecs-ffi library. See the crate documentation for details. */
+
+#ifndef PECS_H
+#define PECS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct PecsWorld PecsWorld;
+typedef void (*pecs_query_fn)(void *user_data, const uint64_t *entities,
+                              size_t count, void *const *columns);
+
+PecsWorld *pecs_world_new(void);
+void pecs_world_free(PecsWorld *world);
+uint64_t pecs_spawn(PecsWorld *world);
+bool pecs_despawn(PecsWorld *world, uint64_t entity);
+bool pecs_is_alive(const PecsWorld *world, uint64_t entity);
+bool pecs_insert(PecsWorld *world, uint64_t entity, const char *name,
+                 const void *data, size_t size);
+int64_t pecs_query(PecsWorld *world, const char *const *names, size_t count,
+                   pecs_query_fn callback, void *user_data);
+bool pecs_world_save(PecsWorld *world, const char *path);
+PecsWorld *pecs_world_load(const char *path);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PECS_H */
@@ -0,0 +1,407 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! C API for PECS.
+//!
+//! Exposes a small `extern "C"` API so engines written in C or C++ can embed
+//! PECS. The crate builds as both a shared and a static library:
+//!
+//! ```text
+//! cargo build --release -p pecs-ffi
+//! ```
+//!
+//! and `include/pecs.h` declares the API for C and C++.
+//!
+//! Components are addressed by the names registered through
+//! [`ComponentRegistry::register_name`], so the Rust side must register every
+//! type the foreign side uses, and both sides must agree on its layout
+//! (typically a `#[repr(C)]` struct).
+//!
+//! Entities cross the boundary as the `u64` produced by
+//! [`EntityId::to_raw`]; `0` is never a valid entity.
+//!
+//! The declarations in `include/pecs.h`:
+//!
+//! ```c
+//! typedef struct PecsWorld PecsWorld;
+//! typedef void (*pecs_query_fn)(void *user_data, const uint64_t *entities,
+//!                               size_t count, void *const *columns);
+//!
+//! PecsWorld *pecs_world_new(void);
+//! void pecs_world_free(PecsWorld *world);
+//! uint64_t pecs_spawn(PecsWorld *world);
+//! bool pecs_despawn(PecsWorld *world, uint64_t entity);
+//! bool pecs_is_alive(const PecsWorld *world, uint64_t entity);
+//! bool pecs_insert(PecsWorld *world, uint64_t entity, const char *name,
+//!                  const void *data, size_t size);
+//! int64_t pecs_query(PecsWorld *world, const char *const *names, size_t count,
+//!                    pecs_query_fn callback, void *user_data);
+//! bool pecs_world_save(PecsWorld *world, const char *path);
+//! PecsWorld *pecs_world_load(const char *path);
+//! ```
+
+use pecs::World;
+use pecs::component::ComponentTypeId;
+use pecs::component::registry::ComponentRegistry;
+use pecs::entity::EntityId;
+use std::ffi::{CStr, c_char, c_void};
+
+/// Callback invoked once per matching archetype by [`pecs_query`].
+///
+/// Receives the caller's `user_data`, the entities of the archetype, the row
+/// count, and one column pointer per requested component name.
+pub type QueryCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    entities: *const u64,
+    count: usize,
+    columns: *const *mut c_void,
+);
+
+/// Converts a raw entity handle, rejecting the invalid value `0`.
+fn entity_from_raw(raw: u64) -> Option<EntityId> {
+    // SAFETY: The value is non-zero
+    (raw != 0).then(|| unsafe { EntityId::from_raw(raw) })
+}
+
+/// Reads a NUL-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn str_from_ptr<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: Guaranteed by the caller
+    unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+}
+
+/// Creates a new, empty world.
+///
+/// The world must be released with [`pecs_world_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn pecs_world_new() -> *mut World {
+    Box::into_raw(Box::new(World::new()))
+}
+
+/// Destroys a world created by [`pecs_world_new`] or [`pecs_world_load`].
+///
+/// # Safety
+///
+/// `world` must be null or a pointer returned by this API that has not been
+/// freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_world_free(world: *mut World) {
+    if !world.is_null() {
+        // SAFETY: Guaranteed by the caller
+        drop(unsafe { Box::from_raw(world) });
+    }
+}
+
+/// Spawns an empty entity and returns its handle, or `0` if `world` is null.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_spawn(world: *mut World) -> u64 {
+    // SAFETY: Guaranteed by the caller
+    match unsafe { world.as_mut() } {
+        Some(world) => world.spawn_empty().to_raw(),
+        None => 0,
+    }
+}
+
+/// Despawns an entity. Returns `true` if it was alive.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_despawn(world: *mut World, entity: u64) -> bool {
+    // SAFETY: Guaranteed by the caller
+    match (unsafe { world.as_mut() }, entity_from_raw(entity)) {
+        (Some(world), Some(entity)) => world.despawn(entity),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the entity is alive.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_is_alive(world: *const World, entity: u64) -> bool {
+    // SAFETY: Guaranteed by the caller
+    match (unsafe { world.as_ref() }, entity_from_raw(entity)) {
+        (Some(world), Some(entity)) => world.is_alive(entity),
+        _ => false,
+    }
+}
+
+/// Inserts a component, identified by its registered name, into an entity.
+///
+/// `size` must equal the size of the registered type; the bytes are copied
+/// into the world. Returns `false` if the name is unknown, the size does not
+/// match, or the entity is not alive.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer, `name` must be null or a
+/// NUL-terminated string, and `data` must point to `size` bytes forming a
+/// valid value of the named component type.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_insert(
+    world: *mut World,
+    entity: u64,
+    name: *const c_char,
+    data: *const c_void,
+    size: usize,
+) -> bool {
+    // SAFETY: Guaranteed by the caller
+    let (Some(world), Some(entity), Some(name)) =
+        (unsafe { world.as_mut() }, entity_from_raw(entity), unsafe {
+            str_from_ptr(name)
+        })
+    else {
+        return false;
+    };
+    let Some(info) = ComponentRegistry::global().info_by_name(name) else {
+        return false;
+    };
+    if data.is_null() || size != info.size() {
+        return false;
+    }
+
+    // Copy into an aligned buffer, since foreign data may be unaligned
+    let layout = std::alloc::Layout::from_size_align(info.size(), info.alignment())
+        .expect("component layout is valid");
+    let mut buffer = vec![0u8; layout.size() + layout.align()];
+    let offset = buffer.as_ptr().align_offset(layout.align());
+    let aligned = buffer[offset..].as_mut_ptr();
+    // SAFETY: The buffer holds `size` bytes after `offset`, and the caller
+    // guarantees the bytes form a valid value
+    unsafe {
+        std::ptr::copy_nonoverlapping(data as *const u8, aligned, size);
+        world.insert_raw(entity, &info, aligned)
+    }
+}
+
+/// Visits every archetype containing all of the named components.
+///
+/// `callback` is called once per non-empty archetype with one column pointer
+/// per name, in the order given. Returns the total number of rows visited,
+/// or `-1` if an argument is invalid or a name is not registered.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer, and `names` must point to
+/// `count` NUL-terminated strings. `callback` must not call back into this
+/// API with the same world.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_query(
+    world: *mut World,
+    names: *const *const c_char,
+    count: usize,
+    callback: Option<QueryCallback>,
+    user_data: *mut c_void,
+) -> i64 {
+    // SAFETY: Guaranteed by the caller
+    let (Some(world), Some(callback)) = (unsafe { world.as_mut() }, callback) else {
+        return -1;
+    };
+    if names.is_null() && count > 0 {
+        return -1;
+    }
+
+    let mut types = Vec::with_capacity(count);
+    for i in 0..count {
+        // SAFETY: The caller guarantees `names` holds `count` strings
+        let name = unsafe { str_from_ptr(*names.add(i)) };
+        match name.and_then(|name| ComponentRegistry::global().info_by_name(name)) {
+            Some(info) => types.push(info.type_id()),
+            None => return -1,
+        }
+    }
+
+    visit_columns(world, &types, |entities, columns| {
+        // SAFETY: Both slices outlive the call and hold `entities.len()` rows
+        unsafe {
+            callback(
+                user_data,
+                entities.as_ptr(),
+                entities.len(),
+                columns.as_ptr(),
+            )
+        }
+    })
+}
+
+/// Runs `visit` for each archetype column set and returns the rows visited.
+fn visit_columns(
+    world: &mut World,
+    types: &[ComponentTypeId],
+    mut visit: impl FnMut(&[u64], &[*mut c_void]),
+) -> i64 {
+    let mut rows = 0;
+    let mut entities = Vec::new();
+    let mut columns = Vec::new();
+    for slices in world.iter_archetype_columns(types) {
+        entities.clear();
+        entities.extend(slices.entities().iter().map(|entity| entity.to_raw()));
+        columns.clear();
+        columns.extend(
+            slices
+                .columns()
+                .iter()
+                .map(|column| column.ptr as *mut c_void),
+        );
+        visit(&entities, &columns);
+        rows += slices.len() as i64;
+    }
+    rows
+}
+
+/// Saves a world to `path` in the binary format. Returns `true` on success.
+///
+/// # Safety
+///
+/// `world` must be null or a valid world pointer and `path` must be null or
+/// a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_world_save(world: *mut World, path: *const c_char) -> bool {
+    // SAFETY: Guaranteed by the caller
+    let (Some(world), Some(path)) = (unsafe { world.as_mut() }, unsafe { str_from_ptr(path) })
+    else {
+        return false;
+    };
+    std::fs::File::create(path)
+        .map_err(pecs::persistence::PersistenceError::Io)
+        .and_then(|mut file| world.save_binary(&mut file))
+        .is_ok()
+}
+
+/// Loads a world saved by [`pecs_world_save`]. Returns null on failure.
+///
+/// The world must be released with [`pecs_world_free`].
+///
+/// # Safety
+///
+/// `path` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pecs_world_load(path: *const c_char) -> *mut World {
+    // SAFETY: Guaranteed by the caller
+    let Some(path) = (unsafe { str_from_ptr(path) }) else {
+        return std::ptr::null_mut();
+    };
+    std::fs::File::open(path)
+        .map_err(pecs::persistence::PersistenceError::Io)
+        .and_then(|mut file| World::load_binary(&mut file))
+        .map(|world| Box::into_raw(Box::new(world)))
+        .unwrap_or(std::ptr::null_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pecs::component::Component;
+    use std::ffi::CString;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct FfiPoint {
+        x: f32,
+        y: f32,
+    }
+    impl Component for FfiPoint {}
+
+    unsafe extern "C" fn sum_x(
+        user_data: *mut c_void,
+        _entities: *const u64,
+        count: usize,
+        columns: *const *mut c_void,
+    ) {
+        unsafe {
+            let total = &mut *(user_data as *mut f32);
+            let points = *columns as *const FfiPoint;
+            for i in 0..count {
+                *total += (*points.add(i)).x;
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_insert_and_query() {
+        ComponentRegistry::global().register_name::<FfiPoint>("FfiPoint");
+        let name = CString::new("FfiPoint").unwrap();
+
+        unsafe {
+            let world = pecs_world_new();
+            let a = pecs_spawn(world);
+            let b = pecs_spawn(world);
+            assert!(pecs_is_alive(world, a));
+
+            for (entity, x) in [(a, 1.5f32), (b, 2.0)] {
+                let point = FfiPoint { x, y: 0.0 };
+                let data = &point as *const FfiPoint as *const c_void;
+                assert!(pecs_insert(world, entity, name.as_ptr(), data, 8));
+            }
+            assert!(!pecs_insert(world, a, name.as_ptr(), std::ptr::null(), 8));
+
+            let names = [name.as_ptr()];
+            let mut total = 0.0f32;
+            let rows = pecs_query(
+                world,
+                names.as_ptr(),
+                1,
+                Some(sum_x),
+                &mut total as *mut f32 as *mut c_void,
+            );
+            assert_eq!(rows, 2);
+            assert_eq!(total, 3.5);
+
+            let unknown = CString::new("Unknown").unwrap();
+            let names = [unknown.as_ptr()];
+            let rows = pecs_query(world, names.as_ptr(), 1, Some(sum_x), std::ptr::null_mut());
+            assert_eq!(rows, -1);
+
+            assert!(pecs_despawn(world, a));
+            assert!(!pecs_is_alive(world, a));
+            assert!(!pecs_is_alive(world, 0));
+            pecs_world_free(world);
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("pecs_ffi_save_and_load.pecs");
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let world = pecs_world_new();
+            pecs_spawn(world);
+            assert!(pecs_world_save(world, c_path.as_ptr()));
+            pecs_world_free(world);
+
+            let loaded = pecs_world_load(c_path.as_ptr());
+            assert!(!loaded.is_null());
+            assert_eq!((*loaded).len(), 1);
+            pecs_world_free(loaded);
+        }
+        let _ = std::fs::remove_file(path);
+    }
+}
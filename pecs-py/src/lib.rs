@@ -0,0 +1,324 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Python bindings for PECS.
+//!
+//! Exposes `World`, `EntityId` and `StableId` as Python classes. Components
+//! cross the boundary as plain Python values (usually dicts) and are looked up
+//! by the names registered with
+//! [`ComponentRegistry::register_json`](pecs::component::registry::ComponentRegistry::register_json).
+//!
+//! The stock extension module only knows components registered before it is
+//! initialized. Games with their own component types build a small extension
+//! crate that registers them and then calls [`init_module`]:
+//!
+//! ```ignore
+//! use pyo3::prelude::*;
+//!
+//! #[pymodule]
+//! fn my_game(m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     ComponentRegistry::global().register_json::<Position>("Position");
+//!     pecs_py::init_module(m)
+//! }
+//! ```
+//!
+//! From Python:
+//!
+//! ```python
+//! import pecs
+//!
+//! world = pecs.World.load("save.pecs")
+//! for entity in world.entities():
+//!     print(entity, world.components(entity))
+//! world.insert(entity, "Position", {"x": 1.0, "y": 2.0})
+//! world.save("save.json")
+//! ```
+
+use pecs::component::registry::ComponentRegistry;
+use pecs::persistence::PersistenceError;
+use pyo3::exceptions::{PyIOError, PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use std::fs::File;
+use std::path::Path;
+
+/// An ephemeral entity handle.
+#[pyclass(name = "EntityId", frozen, eq, hash, from_py_object)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PyEntityId(pecs::EntityId);
+
+#[pymethods]
+impl PyEntityId {
+    /// Slot index of the entity.
+    #[getter]
+    fn index(&self) -> u32 {
+        self.0.index()
+    }
+
+    /// Recycling generation of the entity.
+    #[getter]
+    fn generation(&self) -> u32 {
+        self.0.generation()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EntityId({}v{})", self.0.index(), self.0.generation())
+    }
+}
+
+/// A persistent entity identifier.
+#[pyclass(name = "StableId", frozen, eq, hash, from_py_object)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PyStableId(pecs::StableId);
+
+#[pymethods]
+impl PyStableId {
+    /// Parses a stable ID from its UUID string form.
+    #[new]
+    fn new(value: &str) -> PyResult<Self> {
+        value
+            .parse()
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StableId('{}')", self.0)
+    }
+}
+
+/// An ECS world.
+#[pyclass(name = "World", unsendable)]
+pub struct PyWorld(pecs::World);
+
+#[pymethods]
+impl PyWorld {
+    #[new]
+    fn new() -> Self {
+        Self(pecs::World::new())
+    }
+
+    /// Loads a world from a `.json` file or a binary save file.
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        let mut file = File::open(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+        let world = if is_json(path) {
+            pecs::World::load_json(&mut file)
+        } else {
+            pecs::World::load_binary(&mut file)
+        };
+        world.map(Self).map_err(to_py_err)
+    }
+
+    /// Saves the world to a `.json` file or a binary save file.
+    fn save(&mut self, path: &str) -> PyResult<()> {
+        let mut file = File::create(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+        let result = if is_json(path) {
+            self.0.save_json(&mut file)
+        } else {
+            self.0.save_binary(&mut file)
+        };
+        result.map(drop).map_err(to_py_err)
+    }
+
+    /// Spawns an empty entity.
+    fn spawn(&mut self) -> PyEntityId {
+        PyEntityId(self.0.spawn_empty())
+    }
+
+    /// Despawns an entity. Returns `True` if it was alive.
+    fn despawn(&mut self, entity: PyEntityId) -> bool {
+        self.0.despawn(entity.0)
+    }
+
+    fn is_alive(&self, entity: PyEntityId) -> bool {
+        self.0.is_alive(entity.0)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns all live entities.
+    fn entities(&self) -> Vec<PyEntityId> {
+        self.0
+            .iter_entities()
+            .map(|(id, _)| PyEntityId(id))
+            .collect()
+    }
+
+    /// Returns the stable ID of an entity.
+    fn stable_id(&self, entity: PyEntityId) -> Option<PyStableId> {
+        self.0.get_stable_id(entity.0).map(PyStableId)
+    }
+
+    /// Looks up an entity by stable ID.
+    fn entity(&self, stable_id: PyStableId) -> Option<PyEntityId> {
+        self.0.get_entity_id(stable_id.0).map(PyEntityId)
+    }
+
+    /// Returns the named component of an entity, or `None`.
+    fn get(&self, py: Python<'_>, entity: PyEntityId, name: &str) -> PyResult<Option<Py<PyAny>>> {
+        ComponentRegistry::global()
+            .get_json(name, &self.0, entity.0)
+            .map(|value| json_to_py(py, &value))
+            .transpose()
+    }
+
+    /// Inserts or replaces the named component of an entity.
+    fn insert(
+        &mut self,
+        entity: PyEntityId,
+        name: &str,
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<bool> {
+        let value = py_to_json(value)?;
+        ComponentRegistry::global()
+            .insert_json(name, &mut self.0, entity.0, value)
+            .map_err(to_py_err)
+    }
+
+    /// Returns every registered component of an entity as a dict keyed by name.
+    fn components<'py>(&self, py: Python<'py>, entity: PyEntityId) -> PyResult<Bound<'py, PyDict>> {
+        let registry = ComponentRegistry::global();
+        let dict = PyDict::new(py);
+        for name in registry.json_names() {
+            if let Some(value) = registry.get_json(&name, &self.0, entity.0) {
+                dict.set_item(name, json_to_py(py, &value)?)?;
+            }
+        }
+        Ok(dict)
+    }
+}
+
+/// Returns the names of all components usable from Python.
+#[pyfunction]
+fn component_names() -> Vec<String> {
+    ComponentRegistry::global().json_names()
+}
+
+/// Adds the PECS classes to a Python module.
+pub fn init_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyWorld>()?;
+    m.add_class::<PyEntityId>()?;
+    m.add_class::<PyStableId>()?;
+    m.add_function(wrap_pyfunction!(component_names, m)?)?;
+    Ok(())
+}
+
+#[pymodule]
+#[pyo3(name = "pecs")]
+fn pecs_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    init_module(m)
+}
+
+fn is_json(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn to_py_err(error: PersistenceError) -> PyErr {
+    match error {
+        PersistenceError::Io(e) => PyIOError::new_err(e.to_string()),
+        PersistenceError::UnknownComponentType(name) => PyKeyError::new_err(name),
+        other => PyValueError::new_err(other.to_string()),
+    }
+}
+
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<Py<PyAny>> {
+    use serde_json::Value;
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any().unbind(),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_pyobject(py)?.into_any().unbind(),
+            (None, Some(u)) => u.into_pyobject(py)?.into_any().unbind(),
+            _ => n
+                .as_f64()
+                .unwrap_or(f64::NAN)
+                .into_pyobject(py)?
+                .into_any()
+                .unbind(),
+        },
+        Value::String(s) => PyString::new(py, s).into_any().unbind(),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| json_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    use serde_json::Value;
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    // bool is a subclass of int in Python, so it must be checked first
+    if let Ok(b) = value.cast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+    if value.is_instance_of::<PyInt>() {
+        if let Ok(i) = value.extract::<i64>() {
+            return Ok(Value::from(i));
+        }
+        return Ok(Value::from(value.extract::<u64>()?));
+    }
+    if value.is_instance_of::<PyFloat>() {
+        let f = value.extract::<f64>()?;
+        return serde_json::Number::from_f64(f)
+            .map(Value::Number)
+            .ok_or_else(|| PyValueError::new_err("NaN and infinity are not supported"));
+    }
+    if let Ok(s) = value.cast::<PyString>() {
+        return Ok(Value::String(s.to_str()?.to_string()));
+    }
+    if let Ok(dict) = value.cast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (key, item) in dict.iter() {
+            let key = key
+                .cast::<PyString>()
+                .map_err(|_| PyTypeError::new_err("component dict keys must be strings"))?;
+            map.insert(key.to_str()?.to_string(), py_to_json(&item)?);
+        }
+        return Ok(Value::Object(map));
+    }
+    if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        let items = value
+            .try_iter()?
+            .map(|item| py_to_json(&item?))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(Value::Array(items));
+    }
+    Err(PyTypeError::new_err(format!(
+        "unsupported component value type: {}",
+        value.get_type().name()?
+    )))
+}
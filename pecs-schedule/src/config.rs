@@ -0,0 +1,204 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Labels, sets and ordering constraints.
+//!
+//! Systems are named by labels. [`before`](IntoSystemConfig::before) and
+//! [`after`](IntoSystemConfig::after) order a system relative to every
+//! system carrying a label. A set is a label that can carry ordering of its
+//! own, configured with [`SystemSet`]; every member inherits it. Constraints
+//! naming labels no system carries are ignored.
+
+use crate::criteria::{RunCriteria, ShouldRun};
+use crate::system::{IntoSystem, System};
+use pecs::World;
+
+/// A system together with its labels, ordering and run criteria.
+pub struct SystemConfig {
+    pub(crate) system: Box<dyn System>,
+    pub(crate) criteria: Option<Box<dyn RunCriteria>>,
+    pub(crate) labels: Vec<String>,
+    pub(crate) before: Vec<String>,
+    pub(crate) after: Vec<String>,
+}
+
+impl SystemConfig {
+    /// Runs the system as often as its criteria allow.
+    pub(crate) fn run(&mut self, world: &mut World) {
+        let Some(criteria) = &mut self.criteria else {
+            self.system.run(world);
+            return;
+        };
+        loop {
+            match criteria.should_run(world) {
+                ShouldRun::No => break,
+                ShouldRun::Yes => {
+                    self.system.run(world);
+                    break;
+                }
+                ShouldRun::YesAndCheckAgain => self.system.run(world),
+            }
+        }
+    }
+}
+
+/// Conversion into a [`SystemConfig`], with builder methods for labels and
+/// ordering.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs_schedule::{IntoSystemConfig, ResMut, Schedule};
+///
+/// struct Log(Vec<&'static str>);
+///
+/// fn input(mut log: ResMut<Log>) {
+///     log.0.push("input");
+/// }
+///
+/// fn physics(mut log: ResMut<Log>) {
+///     log.0.push("physics");
+/// }
+///
+/// fn render(mut log: ResMut<Log>) {
+///     log.0.push("render");
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Log(Vec::new()));
+///
+/// let mut schedule = Schedule::new();
+/// schedule
+///     .add_system(render.label("render"))
+///     .add_system(physics.after("input").before("render"))
+///     .add_system(input.label("input"));
+/// schedule.run(&mut world);
+///
+/// assert_eq!(world.resource::<Log>().unwrap().0, vec!["input", "physics", "render"]);
+/// ```
+pub trait IntoSystemConfig<Marker>: Sized {
+    /// Builds the configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system's parameters conflict with each other.
+    fn into_config(self) -> SystemConfig;
+
+    /// Adds a label other systems can order against.
+    fn label(self, label: impl Into<String>) -> SystemConfig {
+        let mut config = self.into_config();
+        config.labels.push(label.into());
+        config
+    }
+
+    /// Adds the system to a set. Sets are labels whose ordering is
+    /// configured with [`SystemSet`].
+    fn in_set(self, set: impl Into<String>) -> SystemConfig {
+        self.label(set)
+    }
+
+    /// Runs the system before every system with `label`.
+    fn before(self, label: impl Into<String>) -> SystemConfig {
+        let mut config = self.into_config();
+        config.before.push(label.into());
+        config
+    }
+
+    /// Runs the system after every system with `label`.
+    fn after(self, label: impl Into<String>) -> SystemConfig {
+        let mut config = self.into_config();
+        config.after.push(label.into());
+        config
+    }
+
+    /// Only runs the system when `criteria` allows.
+    fn run_if(self, criteria: impl RunCriteria) -> SystemConfig {
+        let mut config = self.into_config();
+        config.criteria = Some(Box::new(criteria));
+        config
+    }
+}
+
+impl IntoSystemConfig<()> for SystemConfig {
+    fn into_config(self) -> SystemConfig {
+        self
+    }
+}
+
+/// Marker for the [`IntoSystemConfig`] implementation on systems.
+#[doc(hidden)]
+pub struct IsSystem;
+
+impl<S, Marker> IntoSystemConfig<(IsSystem, Marker)> for S
+where
+    S: IntoSystem<Marker>,
+{
+    fn into_config(self) -> SystemConfig {
+        SystemConfig {
+            system: Box::new(self.into_system()),
+            criteria: None,
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+}
+
+/// Ordering shared by every system in a set.
+///
+/// # Examples
+///
+/// ```
+/// use pecs_schedule::{Schedule, SystemSet};
+///
+/// let mut schedule = Schedule::new();
+/// schedule.configure_set(SystemSet::new("physics").after("input").before("render"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemSet {
+    pub(crate) name: String,
+    pub(crate) before: Vec<String>,
+    pub(crate) after: Vec<String>,
+}
+
+impl SystemSet {
+    /// Creates a set with no ordering.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+
+    /// Runs the set's systems before every system with `label`.
+    pub fn before(mut self, label: impl Into<String>) -> Self {
+        self.before.push(label.into());
+        self
+    }
+
+    /// Runs the set's systems after every system with `label`.
+    pub fn after(mut self, label: impl Into<String>) -> Self {
+        self.after.push(label.into());
+        self
+    }
+
+    /// The set's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,319 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Conditions that decide when a system runs.
+//!
+//! A [`RunCriteria`] is checked each time the schedule reaches its system.
+//! It can skip the system, run it once, or run it and be checked again, which
+//! is how [`FixedTimestep`] catches up on several steps in one frame.
+//! Closures of the form `FnMut(&World) -> bool` are criteria too.
+
+use crate::event::Events;
+use crate::time::Time;
+use pecs::World;
+use pecs::resource::Resource;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// What a [`RunCriteria`] decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShouldRun {
+    /// Skip the system this time.
+    No,
+    /// Run the system once.
+    Yes,
+    /// Run the system, then check the criteria again.
+    YesAndCheckAgain,
+}
+
+impl From<bool> for ShouldRun {
+    fn from(run: bool) -> Self {
+        if run { ShouldRun::Yes } else { ShouldRun::No }
+    }
+}
+
+/// A condition gating a system.
+pub trait RunCriteria: Send + Sync + 'static {
+    /// Decides whether the system runs.
+    fn should_run(&mut self, world: &mut World) -> ShouldRun;
+}
+
+impl<F> RunCriteria for F
+where
+    F: FnMut(&World) -> bool + Send + Sync + 'static,
+{
+    fn should_run(&mut self, world: &mut World) -> ShouldRun {
+        self(world).into()
+    }
+}
+
+/// Runs a system at a fixed rate, independent of the frame rate.
+///
+/// Each frame the [`Time`] delta is added to an accumulator, and the system
+/// runs once for every whole step the accumulator holds. The accumulator is
+/// kept in the [`FixedTimesteps`] resource, so systems can read how far the
+/// simulation is into the next step, e.g. to interpolate rendering.
+///
+/// # Panics
+///
+/// Checking the criteria panics if the world has no [`Time`] resource.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs_schedule::criteria::FixedTimestep;
+/// use pecs_schedule::{ResMut, Schedule, Time};
+/// use std::time::Duration;
+///
+/// struct Steps(u32);
+///
+/// fn physics(mut steps: ResMut<Steps>) {
+///     steps.0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Time::new());
+/// world.insert_resource(Steps(0));
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system_with(physics, FixedTimestep::hz(10.0));
+///
+/// world.resource_mut::<Time>().unwrap().advance(Duration::from_millis(250));
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Steps>().unwrap().0, 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step: Duration,
+    label: String,
+    looping: bool,
+}
+
+impl FixedTimestep {
+    /// Runs `rate` times per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not positive and finite.
+    pub fn hz(rate: f64) -> Self {
+        assert!(
+            rate > 0.0 && rate.is_finite(),
+            "fixed timestep rate must be positive, got {rate}"
+        );
+        Self::step(Duration::from_secs_f64(1.0 / rate))
+    }
+
+    /// Runs once every `step`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn step(step: Duration) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        assert!(!step.is_zero(), "fixed timestep step must be non-zero");
+        Self {
+            step,
+            label: format!("fixed_timestep_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            looping: false,
+        }
+    }
+
+    /// Sets the key this timestep's state is stored under in
+    /// [`FixedTimesteps`].
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
+impl RunCriteria for FixedTimestep {
+    fn should_run(&mut self, world: &mut World) -> ShouldRun {
+        let delta = if self.looping {
+            Duration::ZERO
+        } else {
+            world
+                .resource::<Time>()
+                .expect("FixedTimestep requires a Time resource")
+                .delta()
+        };
+        let state = world
+            .resource_or_insert_with(FixedTimesteps::default)
+            .states
+            .entry(self.label.clone())
+            .or_insert(FixedTimestepState {
+                step: self.step,
+                accumulator: Duration::ZERO,
+            });
+        state.step = self.step;
+        state.accumulator += delta;
+
+        if state.accumulator >= state.step {
+            state.accumulator -= state.step;
+            self.looping = true;
+            ShouldRun::YesAndCheckAgain
+        } else {
+            self.looping = false;
+            ShouldRun::No
+        }
+    }
+}
+
+/// Resource holding the accumulated time of every [`FixedTimestep`].
+#[derive(Debug, Clone, Default)]
+pub struct FixedTimesteps {
+    states: HashMap<String, FixedTimestepState>,
+}
+
+impl FixedTimesteps {
+    /// Returns the state of the timestep with `label`.
+    pub fn get(&self, label: &str) -> Option<&FixedTimestepState> {
+        self.states.get(label)
+    }
+}
+
+/// Accumulated time of one [`FixedTimestep`].
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestepState {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestepState {
+    /// Length of one step.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Time accumulated towards the next step.
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// How far into the next step the accumulator is, from 0.0 to 1.0.
+    pub fn overstep_fraction(&self) -> f64 {
+        self.accumulator.as_secs_f64() / self.step.as_secs_f64()
+    }
+}
+
+/// Runs a system only while events of type `T` are queued.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs_schedule::criteria::on_event;
+/// use pecs_schedule::{Events, ResMut, Schedule};
+///
+/// struct Explosion;
+/// struct Explosions(usize);
+///
+/// fn count(mut events: ResMut<Events<Explosion>>, mut total: ResMut<Explosions>) {
+///     total.0 += events.drain().count();
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Events::<Explosion>::new());
+/// world.insert_resource(Explosions(0));
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system_with(count, on_event::<Explosion>());
+///
+/// world.resource_mut::<Events<Explosion>>().unwrap().send(Explosion);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Explosions>().unwrap().0, 1);
+/// ```
+pub fn on_event<T: Resource>() -> OnEvent<T> {
+    OnEvent {
+        marker: PhantomData,
+    }
+}
+
+/// Criteria returned by [`on_event`].
+pub struct OnEvent<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Resource> RunCriteria for OnEvent<T> {
+    fn should_run(&mut self, world: &mut World) -> ShouldRun {
+        world
+            .resource::<Events<T>>()
+            .is_some_and(|events| !events.is_empty())
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(criteria: &mut impl RunCriteria, world: &mut World) -> u32 {
+        let mut count = 0;
+        loop {
+            match criteria.should_run(world) {
+                ShouldRun::No => return count,
+                ShouldRun::Yes => return count + 1,
+                ShouldRun::YesAndCheckAgain => count += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_timestep_accumulates() {
+        let mut world = World::new();
+        world.insert_resource(Time::new());
+        let mut criteria = FixedTimestep::hz(10.0).with_label("physics");
+
+        let mut frame = |world: &mut World, millis| {
+            world
+                .resource_mut::<Time>()
+                .unwrap()
+                .advance(Duration::from_millis(millis));
+            runs(&mut criteria, world)
+        };
+
+        assert_eq!(frame(&mut world, 250), 2);
+        assert_eq!(frame(&mut world, 40), 0);
+        assert_eq!(frame(&mut world, 10), 1);
+
+        let state = *world
+            .resource::<FixedTimesteps>()
+            .unwrap()
+            .get("physics")
+            .unwrap();
+        assert_eq!(state.step(), Duration::from_millis(100));
+        assert_eq!(state.accumulator(), Duration::ZERO);
+    }
+
+    #[test]
+    fn closures_and_events() {
+        struct Ping;
+
+        let mut world = World::new();
+        let mut on_ping = on_event::<Ping>();
+        assert_eq!(on_ping.should_run(&mut world), ShouldRun::No);
+
+        world.insert_resource(Events::<Ping>::new());
+        world.resource_mut::<Events<Ping>>().unwrap().send(Ping);
+        assert_eq!(on_ping.should_run(&mut world), ShouldRun::Yes);
+
+        let mut has_pings = |world: &World| world.contains_resource::<Events<Ping>>();
+        assert_eq!(runs(&mut has_pings, &mut world), 1);
+    }
+}
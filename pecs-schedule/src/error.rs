@@ -0,0 +1,41 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Scheduling errors.
+
+use std::fmt;
+
+/// Errors raised while ordering a schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The ordering constraints form a cycle. The systems are listed in
+    /// constraint order, with the first repeated at the end.
+    OrderingCycle(Vec<String>),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OrderingCycle(systems) => write!(
+                f,
+                "Ordering constraints form a cycle: {}\nSuggestion: remove one of the before/after constraints between these systems",
+                systems.join(" -> ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
@@ -0,0 +1,87 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Event queues.
+
+use pecs::resource::Resource;
+
+/// A queue of events of type `T`, stored as a world resource.
+///
+/// Events stay queued until a system drains or clears them, so a reader
+/// gated by [`on_event`](crate::criteria::on_event) sees each event once.
+///
+/// # Examples
+///
+/// ```
+/// use pecs_schedule::Events;
+///
+/// struct Collision(u32);
+///
+/// let mut events = Events::new();
+/// events.send(Collision(1));
+/// events.send(Collision(2));
+///
+/// let ids: Vec<u32> = events.drain().map(|collision| collision.0).collect();
+/// assert_eq!(ids, vec![1, 2]);
+/// assert!(events.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Events<T> {
+    queue: Vec<T>,
+}
+
+impl<T: Resource> Events<T> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Self { queue: Vec::new() }
+    }
+
+    /// Queues an event.
+    pub fn send(&mut self, event: T) {
+        self.queue.push(event);
+    }
+
+    /// Iterates over queued events without removing them.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.queue.iter()
+    }
+
+    /// Removes and returns all queued events, oldest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.queue.drain(..)
+    }
+
+    /// Discards all queued events.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Returns the number of queued events.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl<T: Resource> Default for Events<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
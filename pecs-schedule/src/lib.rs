@@ -0,0 +1,93 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! System scheduling for PECS.
+//!
+//! PECS itself is a library for storing and persisting entities; it does not
+//! decide when game logic runs. This crate adds that layer: plain functions
+//! become [`System`]s whose arguments are [`SystemParam`]s, and a
+//! [`Schedule`] runs them against a [`World`](pecs::World).
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::{Component, World};
+//! use pecs_schedule::{Query, Res, Schedule};
+//!
+//! #[derive(Component)]
+//! struct Position(f32);
+//!
+//! #[derive(Component)]
+//! struct Velocity(f32);
+//!
+//! struct Time {
+//!     delta: f32,
+//! }
+//!
+//! fn movement(mut query: Query<(&mut Position, &Velocity)>, time: Res<Time>) {
+//!     for (position, velocity) in &mut query {
+//!         position.0 += velocity.0 * time.delta;
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! world.insert_resource(Time { delta: 0.5 });
+//! let entity = world.spawn().with(Position(0.0)).with(Velocity(2.0)).id();
+//!
+//! let mut schedule = Schedule::new();
+//! schedule.add_system(movement);
+//! schedule.run(&mut world);
+//!
+//! assert_eq!(world.get::<Position>(entity).unwrap().0, 1.0);
+//! ```
+//!
+//! # Modules
+//!
+//! - [`config`]: Labels, sets and ordering constraints
+//! - [`criteria`]: Conditions that decide when a system runs
+//! - [`error`]: Scheduling errors
+//! - [`event`]: Event queues
+//! - [`param`]: Values a system can take as arguments
+//! - [`schedule`]: Ordered collections of systems
+//! - [`system`]: The system trait and function systems
+//! - [`time`]: Frame timing
+
+pub mod config;
+pub mod criteria;
+pub mod error;
+pub mod event;
+pub mod param;
+pub mod schedule;
+pub mod system;
+pub mod time;
+
+pub use config::{IntoSystemConfig, SystemConfig, SystemSet};
+pub use criteria::{FixedTimestep, RunCriteria, ShouldRun, on_event};
+pub use error::ScheduleError;
+pub use event::Events;
+pub use param::{Query, Res, ResMut, SystemAccess, SystemParam, SystemParamItem};
+pub use pecs_derive::SystemParam;
+pub use schedule::Schedule;
+pub use system::{ExclusiveSystem, FunctionSystem, IntoSystem, System, SystemParamFunction};
+pub use time::Time;
+
+/// Commonly used scheduling types.
+pub mod prelude {
+    pub use crate::{
+        Events, FixedTimestep, IntoSystem, IntoSystemConfig, Query, Res, ResMut, Schedule, System,
+        SystemParam, SystemSet, Time, on_event,
+    };
+}
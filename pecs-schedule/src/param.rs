@@ -0,0 +1,351 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Values a system can take as arguments.
+//!
+//! A [`SystemParam`] describes what it accesses and how to fetch itself from
+//! a [`World`]. The built-in parameters are [`Query`], [`Res`] and
+//! [`ResMut`]; optional resources are taken as `Option<Res<R>>` or
+//! `Option<ResMut<R>>`, and tuples of parameters are parameters too. Custom
+//! parameter structs can be built with `#[derive(SystemParam)]`.
+
+use pecs::World;
+use pecs::query::iter::QueryIter;
+use pecs::query::{Access, Filter};
+use pecs::resource::Resource;
+use std::any::{TypeId, type_name};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// A value that can be fetched from a world as a system argument.
+pub trait SystemParam {
+    /// The parameter with its world borrow set to `'w`.
+    type Item<'w>;
+
+    /// Records the components and resources this parameter accesses.
+    fn access(access: &mut SystemAccess);
+
+    /// Fetches the parameter from the world.
+    ///
+    /// # Safety
+    ///
+    /// For as long as the returned item is alive, nothing else may access
+    /// what [`access`](Self::access) declared in a conflicting way, and the
+    /// world may not be changed structurally.
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w>;
+}
+
+/// The item of parameter `P` for world borrow `'w`.
+pub type SystemParamItem<'w, P> = <P as SystemParam>::Item<'w>;
+
+/// Everything a system accesses, and any conflicts between its own
+/// parameters.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAccess {
+    components: Access,
+    resource_reads: Vec<(TypeId, &'static str)>,
+    resource_writes: Vec<(TypeId, &'static str)>,
+    exclusive: bool,
+    conflicts: Vec<String>,
+}
+
+impl SystemAccess {
+    /// Creates an empty access set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the component access of a query.
+    pub fn add_query(&mut self, access: &Access) {
+        for name in self.components.conflicts(access) {
+            self.conflicts.push(format!("component `{name}`"));
+        }
+        self.components.extend(access);
+    }
+
+    /// Records a shared read of resource `R`.
+    pub fn add_resource_read<R: Resource>(&mut self) {
+        let entry = (TypeId::of::<R>(), type_name::<R>());
+        if self.resource_writes.contains(&entry) {
+            self.conflicts.push(format!("resource `{}`", entry.1));
+        }
+        if !self.resource_reads.contains(&entry) {
+            self.resource_reads.push(entry);
+        }
+    }
+
+    /// Records an exclusive write of resource `R`.
+    pub fn add_resource_write<R: Resource>(&mut self) {
+        let entry = (TypeId::of::<R>(), type_name::<R>());
+        if self.resource_writes.contains(&entry) || self.resource_reads.contains(&entry) {
+            self.conflicts.push(format!("resource `{}`", entry.1));
+        }
+        if !self.resource_writes.contains(&entry) {
+            self.resource_writes.push(entry);
+        }
+    }
+
+    /// Records access to the entire world, as exclusive systems have.
+    pub fn set_exclusive(&mut self) {
+        self.exclusive = true;
+    }
+
+    /// Returns true if the whole world is accessed.
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// The combined component access of every query.
+    pub fn components(&self) -> &Access {
+        &self.components
+    }
+
+    /// Descriptions of everything accessed in conflicting ways by more than
+    /// one parameter.
+    pub fn conflicts(&self) -> &[String] {
+        &self.conflicts
+    }
+
+    /// Returns true if systems with these two access sets could run at the
+    /// same time.
+    pub fn is_compatible(&self, other: &SystemAccess) -> bool {
+        let resources_conflict = |writes: &[(TypeId, &str)], other: &SystemAccess| {
+            writes.iter().any(|write| {
+                other.resource_reads.contains(write) || other.resource_writes.contains(write)
+            })
+        };
+        !self.exclusive
+            && !other.exclusive
+            && self.components.is_compatible(&other.components)
+            && !resources_conflict(&self.resource_writes, other)
+            && !resources_conflict(&other.resource_writes, self)
+    }
+}
+
+/// A query over the world's entities.
+///
+/// `Q` is any [`pecs::Query`], such as `(&mut Position, &Velocity)`, and `F`
+/// is an optional filter.
+pub struct Query<'w, Q, F = ()> {
+    world: &'w World,
+    marker: PhantomData<fn() -> (Q, F)>,
+}
+
+impl<'w, Q, F> Query<'w, Q, F>
+where
+    Q: pecs::Query,
+    F: for<'a> Filter<'a>,
+{
+    /// Iterates over the matching entities.
+    pub fn iter(&mut self) -> QueryIter<'_, Q::Fetch, F> {
+        // SAFETY: The system's access was checked for conflicts when it was
+        // built, and `&mut self` prevents two live iterators from this query
+        unsafe { self.world.query_unchecked::<Q, F>() }
+    }
+}
+
+impl<'a, 'w, Q, F> IntoIterator for &'a mut Query<'w, Q, F>
+where
+    Q: pecs::Query,
+    F: for<'f> Filter<'f>,
+{
+    type Item = <QueryIter<'a, Q::Fetch, F> as Iterator>::Item;
+    type IntoIter = QueryIter<'a, Q::Fetch, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<Q, F> SystemParam for Query<'_, Q, F>
+where
+    Q: pecs::Query + 'static,
+    F: for<'a> Filter<'a> + 'static,
+{
+    type Item<'w> = Query<'w, Q, F>;
+
+    fn access(access: &mut SystemAccess) {
+        access.add_query(&Access::of::<Q>());
+    }
+
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w> {
+        Query {
+            world,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Shared access to resource `R`.
+///
+/// Running a system that takes `Res<R>` panics if the world has no `R`; use
+/// `Option<Res<R>>` for resources that may be missing.
+pub struct Res<'w, R: Resource> {
+    value: &'w R,
+}
+
+impl<R: Resource> Deref for Res<'_, R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        self.value
+    }
+}
+
+impl<R: Resource> SystemParam for Res<'_, R> {
+    type Item<'w> = Res<'w, R>;
+
+    fn access(access: &mut SystemAccess) {
+        access.add_resource_read::<R>();
+    }
+
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w> {
+        // SAFETY: Forwarded from caller
+        unsafe { <Option<Res<R>> as SystemParam>::fetch(world) }
+            .unwrap_or_else(|| panic!("resource `{}` does not exist", type_name::<R>()))
+    }
+}
+
+impl<R: Resource> SystemParam for Option<Res<'_, R>> {
+    type Item<'w> = Option<Res<'w, R>>;
+
+    fn access(access: &mut SystemAccess) {
+        access.add_resource_read::<R>();
+    }
+
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w> {
+        world.resource::<R>().map(|value| Res { value })
+    }
+}
+
+/// Exclusive access to resource `R`.
+///
+/// Running a system that takes `ResMut<R>` panics if the world has no `R`;
+/// use `Option<ResMut<R>>` for resources that may be missing.
+pub struct ResMut<'w, R: Resource> {
+    value: &'w mut R,
+}
+
+impl<R: Resource> Deref for ResMut<'_, R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        self.value
+    }
+}
+
+impl<R: Resource> DerefMut for ResMut<'_, R> {
+    fn deref_mut(&mut self) -> &mut R {
+        self.value
+    }
+}
+
+impl<R: Resource> SystemParam for ResMut<'_, R> {
+    type Item<'w> = ResMut<'w, R>;
+
+    fn access(access: &mut SystemAccess) {
+        access.add_resource_write::<R>();
+    }
+
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w> {
+        // SAFETY: Forwarded from caller
+        unsafe { <Option<ResMut<R>> as SystemParam>::fetch(world) }
+            .unwrap_or_else(|| panic!("resource `{}` does not exist", type_name::<R>()))
+    }
+}
+
+impl<R: Resource> SystemParam for Option<ResMut<'_, R>> {
+    type Item<'w> = Option<ResMut<'w, R>>;
+
+    fn access(access: &mut SystemAccess) {
+        access.add_resource_write::<R>();
+    }
+
+    unsafe fn fetch<'w>(world: &'w World) -> Self::Item<'w> {
+        // SAFETY: The caller guarantees no other access to `R`
+        unsafe { world.resource_unchecked_mut::<R>() }.map(|value| ResMut { value })
+    }
+}
+
+macro_rules! impl_system_param_tuple {
+    ($($P:ident),*) => {
+        impl<$($P: SystemParam),*> SystemParam for ($($P,)*) {
+            type Item<'w> = ($($P::Item<'w>,)*);
+
+            fn access(_access: &mut SystemAccess) {
+                $($P::access(_access);)*
+            }
+
+            #[allow(clippy::unused_unit)]
+            unsafe fn fetch<'w>(_world: &'w World) -> Self::Item<'w> {
+                // SAFETY: Forwarded from caller
+                ($(unsafe { $P::fetch(_world) },)*)
+            }
+        }
+    };
+}
+
+impl_system_param_tuple!();
+impl_system_param_tuple!(P0);
+impl_system_param_tuple!(P0, P1);
+impl_system_param_tuple!(P0, P1, P2);
+impl_system_param_tuple!(P0, P1, P2, P3);
+impl_system_param_tuple!(P0, P1, P2, P3, P4);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5, P6);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pecs::Component;
+
+    struct Position;
+    impl Component for Position {}
+    struct Velocity;
+    impl Component for Velocity {}
+    struct Time;
+
+    fn access_of<P: SystemParam>() -> SystemAccess {
+        let mut access = SystemAccess::new();
+        P::access(&mut access);
+        access
+    }
+
+    #[test]
+    fn conflicts_between_parameters() {
+        let ok = access_of::<(Query<&mut Position>, Query<&Velocity>, Res<Time>, Res<Time>)>();
+        assert!(ok.conflicts().is_empty());
+
+        let queries = access_of::<(Query<&mut Position>, Query<&Position>)>();
+        assert_eq!(queries.conflicts().len(), 1);
+        assert!(queries.conflicts()[0].contains("Position"));
+
+        let resources = access_of::<(Res<Time>, ResMut<Time>)>();
+        assert_eq!(resources.conflicts().len(), 1);
+    }
+
+    #[test]
+    fn compatibility_between_systems() {
+        let writer = access_of::<(Query<&mut Position>, ResMut<Time>)>();
+        let reader = access_of::<(Query<&Velocity>, Option<Res<Time>>)>();
+        let other = access_of::<Query<(&Velocity, Option<&Position>)>>();
+
+        assert!(!writer.is_compatible(&reader));
+        assert!(!writer.is_compatible(&other));
+        assert!(reader.is_compatible(&other));
+    }
+}
@@ -0,0 +1,364 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Ordered collections of systems.
+
+use crate::config::{IntoSystemConfig, SystemConfig, SystemSet};
+use crate::criteria::RunCriteria;
+use crate::error::ScheduleError;
+use pecs::World;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+
+/// A collection of systems run one after another.
+///
+/// Systems run in the order they were added unless
+/// [`before`](IntoSystemConfig::before) and
+/// [`after`](IntoSystemConfig::after) constraints say otherwise. Systems
+/// with [`RunCriteria`] only run when their criteria allow.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs_schedule::{ResMut, Schedule};
+///
+/// struct Frame(u64);
+///
+/// fn advance(mut frame: ResMut<Frame>) {
+///     frame.0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Frame(0));
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system(advance);
+/// schedule.run(&mut world);
+/// schedule.run(&mut world);
+///
+/// assert_eq!(world.resource::<Frame>().unwrap().0, 2);
+/// ```
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<SystemConfig>,
+    sets: HashMap<String, SystemSet>,
+    /// Run order as indices into `systems`; `None` when it must be rebuilt.
+    order: Option<Vec<usize>>,
+}
+
+impl Schedule {
+    /// Creates an empty schedule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a system, optionally configured with labels, ordering and
+    /// criteria.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system's parameters conflict with each other.
+    pub fn add_system<Marker>(&mut self, system: impl IntoSystemConfig<Marker>) -> &mut Self {
+        self.systems.push(system.into_config());
+        self.order = None;
+        self
+    }
+
+    /// Adds a system that only runs when `criteria` allows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system's parameters conflict with each other.
+    pub fn add_system_with<Marker>(
+        &mut self,
+        system: impl IntoSystemConfig<Marker>,
+        criteria: impl RunCriteria,
+    ) -> &mut Self {
+        self.add_system(system.run_if(criteria))
+    }
+
+    /// Sets the ordering of a set, replacing any earlier configuration of
+    /// the same set.
+    pub fn configure_set(&mut self, set: SystemSet) -> &mut Self {
+        self.sets.insert(set.name.clone(), set);
+        self.order = None;
+        self
+    }
+
+    /// Resolves the ordering constraints into a run order.
+    ///
+    /// [`run`](Self::run) does this on demand; call it directly to handle
+    /// an unsatisfiable ordering without panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::OrderingCycle`] if the constraints form a
+    /// cycle.
+    pub fn initialize(&mut self) -> Result<(), ScheduleError> {
+        if self.order.is_none() {
+            self.order = Some(self.sort()?);
+        }
+        Ok(())
+    }
+
+    /// Runs every system once, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ordering constraints form a cycle.
+    pub fn run(&mut self, world: &mut World) {
+        if let Err(error) = self.initialize() {
+            panic!("{error}");
+        }
+        let order = self.order.as_ref().expect("order was just built");
+        for &index in order {
+            self.systems[index].run(world);
+        }
+    }
+
+    /// Returns the names of the systems in run order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::OrderingCycle`] if the constraints form a
+    /// cycle.
+    pub fn system_names(&mut self) -> Result<Vec<&str>, ScheduleError> {
+        self.initialize()?;
+        let order = self.order.as_ref().expect("order was just built");
+        Ok(order
+            .iter()
+            .map(|&index| self.systems[index].system.name())
+            .collect())
+    }
+
+    /// Returns the number of systems.
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Returns true if the schedule has no systems.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// Topologically sorts the systems, keeping insertion order where the
+    /// constraints allow.
+    fn sort(&self) -> Result<Vec<usize>, ScheduleError> {
+        let mut labelled: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, config) in self.systems.iter().enumerate() {
+            for label in &config.labels {
+                labelled.entry(label).or_default().push(index);
+            }
+        }
+
+        // successors[i] must run after i
+        let mut successors = vec![Vec::new(); self.systems.len()];
+        let mut add_edges = |index: usize, before: &[String], after: &[String]| {
+            for label in before {
+                for &other in labelled.get(label.as_str()).into_iter().flatten() {
+                    successors[index].push(other);
+                }
+            }
+            for label in after {
+                for &other in labelled.get(label.as_str()).into_iter().flatten() {
+                    successors[other].push(index);
+                }
+            }
+        };
+        for (index, config) in self.systems.iter().enumerate() {
+            add_edges(index, &config.before, &config.after);
+            for set in config
+                .labels
+                .iter()
+                .filter_map(|label| self.sets.get(label))
+            {
+                add_edges(index, &set.before, &set.after);
+            }
+        }
+
+        let mut in_degree = vec![0usize; self.systems.len()];
+        for edges in &mut successors {
+            edges.sort_unstable();
+            edges.dedup();
+            for &next in edges.iter() {
+                in_degree[next] += 1;
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..self.systems.len())
+            .filter(|&index| in_degree[index] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.systems.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            order.push(index);
+            for &next in &successors[index] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if order.len() < self.systems.len() {
+            return Err(ScheduleError::OrderingCycle(
+                self.find_cycle(&successors, &in_degree),
+            ));
+        }
+        Ok(order)
+    }
+
+    /// Finds one cycle among the systems left unsorted.
+    fn find_cycle(&self, successors: &[Vec<usize>], in_degree: &[usize]) -> Vec<String> {
+        // Every unsorted system has an unsorted predecessor, so walking
+        // backwards must revisit a system
+        let mut predecessor = vec![None; successors.len()];
+        for (index, edges) in successors.iter().enumerate() {
+            for &next in edges {
+                if in_degree[index] > 0 && in_degree[next] > 0 {
+                    predecessor[next] = Some(index);
+                }
+            }
+        }
+
+        let start = in_degree
+            .iter()
+            .position(|&degree| degree > 0)
+            .expect("a cycle exists");
+        let mut seen = vec![false; successors.len()];
+        let mut current = start;
+        while !seen[current] {
+            seen[current] = true;
+            current = predecessor[current].expect("unsorted systems have predecessors");
+        }
+
+        let mut cycle = vec![current];
+        let mut node = predecessor[current].expect("unsorted systems have predecessors");
+        while node != current {
+            cycle.push(node);
+            node = predecessor[node].expect("unsorted systems have predecessors");
+        }
+        cycle.push(current);
+        cycle.reverse();
+        cycle
+            .into_iter()
+            .map(|index| self.systems[index].system.name().to_string())
+            .collect()
+    }
+}
+
+impl fmt::Debug for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Schedule")
+            .field(
+                "systems",
+                &self
+                    .systems
+                    .iter()
+                    .map(|config| config.system.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("sets", &self.sets.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::param::ResMut;
+
+    struct Log(Vec<&'static str>);
+
+    fn first(mut log: ResMut<Log>) {
+        log.0.push("first");
+    }
+
+    fn second(mut log: ResMut<Log>) {
+        log.0.push("second");
+    }
+
+    fn third(mut log: ResMut<Log>) {
+        log.0.push("third");
+    }
+
+    fn log_of(schedule: &mut Schedule) -> Vec<&'static str> {
+        let mut world = World::new();
+        world.insert_resource(Log(Vec::new()));
+        schedule.run(&mut world);
+        world.remove_resource::<Log>().unwrap().0
+    }
+
+    #[test]
+    fn runs_in_insertion_order() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(second).add_system(first);
+        assert_eq!(schedule.len(), 2);
+
+        assert_eq!(log_of(&mut schedule), vec!["second", "first"]);
+    }
+
+    #[test]
+    fn criteria_gate_systems() {
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system_with(first, |_: &World| false)
+            .add_system_with(second, |world: &World| world.resource_count() == 1);
+
+        assert_eq!(log_of(&mut schedule), vec!["second"]);
+    }
+
+    #[test]
+    fn labels_order_systems() {
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(third.label("third"))
+            .add_system(first.before("second"))
+            .add_system(second.label("second").before("third"));
+
+        assert_eq!(log_of(&mut schedule), vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn sets_order_members() {
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(SystemSet::new("late").after("early"))
+            .add_system(third.in_set("late"))
+            .add_system(second.in_set("late"))
+            .add_system(first.in_set("early"));
+
+        assert_eq!(log_of(&mut schedule), vec!["first", "third", "second"]);
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(third)
+            .add_system(first.label("a").after("b"))
+            .add_system(second.label("b").after("a"));
+
+        let Err(ScheduleError::OrderingCycle(cycle)) = schedule.initialize() else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.iter().all(|name| !name.ends_with("third")));
+    }
+}
@@ -0,0 +1,320 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! The system trait and function systems.
+//!
+//! Any function whose arguments are all [`SystemParam`]s can be turned into
+//! a [`System`] with [`IntoSystem`]. The parameters' access is checked once,
+//! when the system is built: a function that takes two parameters accessing
+//! the same component or resource in conflicting ways panics there instead of
+//! aliasing at run time.
+//!
+//! Functions taking `&mut World` as their only argument become exclusive
+//! systems. They see the whole world, so they can spawn, despawn, apply
+//! command buffers or save, at a defined point relative to other systems.
+
+use crate::param::{SystemAccess, SystemParam, SystemParamItem};
+use pecs::World;
+use std::marker::PhantomData;
+
+/// A unit of logic that runs against a world.
+pub trait System: Send + Sync + 'static {
+    /// The system's name, used in diagnostics.
+    fn name(&self) -> &str;
+
+    /// Everything the system accesses.
+    fn access(&self) -> &SystemAccess;
+
+    /// Runs the system once.
+    fn run(&mut self, world: &mut World);
+}
+
+/// Conversion into a [`System`].
+///
+/// `Marker` only exists to keep the implementations for systems and for
+/// functions apart; callers never name it.
+pub trait IntoSystem<Marker> {
+    /// The system produced.
+    type System: System;
+
+    /// Builds the system.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two of the system's parameters access the same component or
+    /// resource in conflicting ways.
+    fn into_system(self) -> Self::System;
+}
+
+impl<S: System> IntoSystem<()> for S {
+    type System = S;
+
+    fn into_system(self) -> S {
+        self
+    }
+}
+
+/// Marker for the [`IntoSystem`] implementation on functions.
+#[doc(hidden)]
+pub struct IsFunctionSystem;
+
+impl<F, Marker> IntoSystem<(IsFunctionSystem, Marker)> for F
+where
+    F: SystemParamFunction<Marker>,
+    Marker: 'static,
+{
+    type System = FunctionSystem<F, Marker>;
+
+    fn into_system(self) -> Self::System {
+        FunctionSystem::new(self)
+    }
+}
+
+/// Marker for the [`IntoSystem`] implementation on exclusive functions.
+#[doc(hidden)]
+pub struct IsExclusiveSystem;
+
+impl<F> IntoSystem<IsExclusiveSystem> for F
+where
+    F: FnMut(&mut World) + Send + Sync + 'static,
+{
+    type System = ExclusiveSystem<F>;
+
+    fn into_system(self) -> Self::System {
+        let mut access = SystemAccess::new();
+        access.set_exclusive();
+        ExclusiveSystem { func: self, access }
+    }
+}
+
+/// A [`System`] with exclusive access to the world.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::{Component, World};
+/// use pecs_schedule::{IntoSystemConfig, Query, Schedule};
+///
+/// #[derive(Component)]
+/// struct Marker;
+///
+/// fn spawn_marker(world: &mut World) {
+///     let entity = world.spawn_empty();
+///     world.commands().insert(entity, Marker);
+/// }
+///
+/// fn count(mut markers: Query<&Marker>) {
+///     assert_eq!(markers.iter().count(), 1);
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::new();
+/// schedule
+///     .add_system(count.after("apply_commands"))
+///     .add_system(World::apply_commands.label("apply_commands"))
+///     .add_system(spawn_marker.before("apply_commands"));
+/// schedule.run(&mut world);
+/// ```
+pub struct ExclusiveSystem<F> {
+    func: F,
+    access: SystemAccess,
+}
+
+impl<F> System for ExclusiveSystem<F>
+where
+    F: FnMut(&mut World) + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        std::any::type_name::<F>()
+    }
+
+    fn access(&self) -> &SystemAccess {
+        &self.access
+    }
+
+    fn run(&mut self, world: &mut World) {
+        (self.func)(world);
+    }
+}
+
+/// A function that can run as a system.
+///
+/// Implemented for functions of up to eight [`SystemParam`] arguments.
+pub trait SystemParamFunction<Marker>: Send + Sync + 'static {
+    /// The function's arguments as one tuple parameter.
+    type Param: SystemParam;
+
+    /// Calls the function.
+    fn run(&mut self, param: SystemParamItem<'_, Self::Param>);
+}
+
+/// A [`System`] built from a function.
+pub struct FunctionSystem<F, Marker> {
+    func: F,
+    access: SystemAccess,
+    marker: PhantomData<fn() -> Marker>,
+}
+
+impl<F, Marker> FunctionSystem<F, Marker>
+where
+    F: SystemParamFunction<Marker>,
+{
+    fn new(func: F) -> Self {
+        let mut access = SystemAccess::new();
+        F::Param::access(&mut access);
+        if !access.conflicts().is_empty() {
+            panic!(
+                "system `{}` has conflicting parameters: {}",
+                std::any::type_name::<F>(),
+                access.conflicts().join(", ")
+            );
+        }
+        Self {
+            func,
+            access,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F, Marker> System for FunctionSystem<F, Marker>
+where
+    F: SystemParamFunction<Marker>,
+    Marker: 'static,
+{
+    fn name(&self) -> &str {
+        std::any::type_name::<F>()
+    }
+
+    fn access(&self) -> &SystemAccess {
+        &self.access
+    }
+
+    fn run(&mut self, world: &mut World) {
+        // SAFETY: The world is borrowed exclusively for the whole call and the
+        // parameters were checked not to conflict with each other
+        let param = unsafe { F::Param::fetch(world) };
+        self.func.run(param);
+    }
+}
+
+macro_rules! impl_system_param_function {
+    ($($P:ident),*) => {
+        impl<Func, $($P: SystemParam),*> SystemParamFunction<fn($($P,)*)> for Func
+        where
+            Func: Send + Sync + 'static,
+            for<'a> &'a mut Func: FnMut($($P),*) + FnMut($(SystemParamItem<'_, $P>),*),
+        {
+            type Param = ($($P,)*);
+
+            #[allow(non_snake_case)]
+            fn run(&mut self, param: SystemParamItem<'_, ($($P,)*)>) {
+                // Naming the argument types lets the compiler pick the
+                // `FnMut(SystemParamItem<..>)` implementation
+                #[allow(clippy::too_many_arguments)]
+                fn call_inner<$($P),*>(mut func: impl FnMut($($P),*), $($P: $P),*) {
+                    func($($P),*)
+                }
+                let ($($P,)*) = param;
+                call_inner(self, $($P),*);
+            }
+        }
+    };
+}
+
+impl_system_param_function!();
+impl_system_param_function!(P0);
+impl_system_param_function!(P0, P1);
+impl_system_param_function!(P0, P1, P2);
+impl_system_param_function!(P0, P1, P2, P3);
+impl_system_param_function!(P0, P1, P2, P3, P4);
+impl_system_param_function!(P0, P1, P2, P3, P4, P5);
+impl_system_param_function!(P0, P1, P2, P3, P4, P5, P6);
+impl_system_param_function!(P0, P1, P2, P3, P4, P5, P6, P7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::param::{Query, Res, ResMut};
+    use pecs::Component;
+
+    struct Health(u32);
+    impl Component for Health {}
+    struct Ticks(u32);
+
+    fn heal(mut query: Query<&mut Health>, ticks: Res<Ticks>) {
+        for health in &mut query {
+            health.0 += ticks.0;
+        }
+    }
+
+    #[test]
+    fn function_system_runs() {
+        let mut world = World::new();
+        world.insert_resource(Ticks(5));
+        let entity = world.spawn().with(Health(10)).id();
+
+        let mut system = heal.into_system();
+        assert!(system.name().ends_with("heal"));
+        assert_eq!(system.access().components().writes().count(), 1);
+        system.run(&mut world);
+        system.run(&mut world);
+
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 20);
+    }
+
+    #[test]
+    fn closures_are_systems() {
+        let mut world = World::new();
+        world.insert_resource(Ticks(0));
+
+        let mut system = (|mut ticks: ResMut<Ticks>| ticks.0 += 1).into_system();
+        system.run(&mut world);
+
+        assert_eq!(world.resource::<Ticks>().unwrap().0, 1);
+    }
+
+    #[test]
+    fn exclusive_systems_take_the_world() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Health(1)).id();
+
+        let mut system = (|world: &mut World| {
+            world.insert_resource(Ticks(3));
+        })
+        .into_system();
+        let mut healing = heal.into_system();
+        assert!(system.access().is_exclusive());
+        assert!(!system.access().is_compatible(healing.access()));
+        system.run(&mut world);
+        healing.run(&mut world);
+
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting parameters: resource")]
+    fn conflicting_parameters_panic() {
+        fn bad(_read: Res<Ticks>, _write: ResMut<Ticks>) {}
+        bad.into_system();
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn missing_resource_panics() {
+        heal.into_system().run(&mut World::new());
+    }
+}
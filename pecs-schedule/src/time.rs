@@ -0,0 +1,93 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Frame timing.
+
+use std::time::{Duration, Instant};
+
+/// Frame timing resource.
+///
+/// The schedule does not advance time on its own. Call [`update`](Self::update)
+/// once per frame to follow the wall clock, or [`advance`](Self::advance) to
+/// step by a fixed amount, e.g. in tests or replays.
+///
+/// # Examples
+///
+/// ```
+/// use pecs_schedule::Time;
+/// use std::time::Duration;
+///
+/// let mut time = Time::new();
+/// time.advance(Duration::from_millis(16));
+/// time.advance(Duration::from_millis(17));
+///
+/// assert_eq!(time.delta(), Duration::from_millis(17));
+/// assert_eq!(time.elapsed(), Duration::from_millis(33));
+/// assert_eq!(time.frame(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Time {
+    delta: Duration,
+    elapsed: Duration,
+    frame: u64,
+    last_update: Option<Instant>,
+}
+
+impl Time {
+    /// Creates a clock at time zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the clock by `delta`.
+    pub fn advance(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+        self.frame += 1;
+    }
+
+    /// Advances the clock by the wall-clock time since the previous update.
+    ///
+    /// The first update has a delta of zero.
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let delta = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+        self.advance(delta);
+    }
+
+    /// Time between the last two updates.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Time between the last two updates, in seconds.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Total time advanced.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Number of updates so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+}
@@ -0,0 +1,80 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Tests for `#[derive(SystemParam)]`.
+
+use pecs::{Component, World};
+use pecs_schedule::{IntoSystem, Query, Res, ResMut, Schedule, System, SystemParam};
+
+#[derive(Component)]
+struct Body {
+    speed: f32,
+}
+
+struct Gravity(f32);
+
+struct Steps(u32);
+
+#[derive(SystemParam)]
+struct Physics<'w> {
+    bodies: Query<'w, &'static mut Body>,
+    gravity: Res<'w, Gravity>,
+}
+
+#[derive(SystemParam)]
+struct Counters<'w>(ResMut<'w, Steps>);
+
+#[derive(SystemParam)]
+struct Nothing;
+
+fn fall(mut physics: Physics, mut counters: Counters, _nothing: Nothing) {
+    let gravity = physics.gravity.0;
+    for body in &mut physics.bodies {
+        body.speed += gravity;
+    }
+    counters.0.0 += 1;
+}
+
+#[test]
+fn derived_params_run() {
+    let mut world = World::new();
+    world.insert_resource(Gravity(2.0));
+    world.insert_resource(Steps(0));
+    let entity = world.spawn().with(Body { speed: 1.0 }).id();
+
+    let mut schedule = Schedule::new();
+    schedule.add_system(fall);
+    schedule.run(&mut world);
+
+    assert_eq!(world.get::<Body>(entity).unwrap().speed, 3.0);
+    assert_eq!(world.resource::<Steps>().unwrap().0, 1);
+}
+
+#[test]
+fn derived_params_report_access() {
+    let system = fall.into_system();
+    let access = system.access();
+
+    assert_eq!(access.components().writes().count(), 1);
+    assert!(access.conflicts().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "conflicting parameters")]
+fn derived_params_conflict_with_siblings() {
+    fn bad(_physics: Physics, _bodies: Query<&Body>) {}
+    bad.into_system();
+}
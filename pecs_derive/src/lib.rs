@@ -32,7 +32,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{DeriveInput, parse_macro_input};
+use syn::{DeriveInput, LitInt, LitStr, parse_macro_input};
 
 /// Derives the `Component` trait for a type.
 ///
@@ -57,6 +57,44 @@ use syn::{DeriveInput, parse_macro_input};
 /// }
 /// ```
 ///
+/// # Schema Attributes
+///
+/// The optional `#[component(...)]` attribute declares the persistence
+/// schema, which is registered with the global `ComponentRegistry` on first
+/// use without any manual registration call:
+///
+/// - `name = "..."`: stable name written to save files (defaults to the
+///   module path and type name, e.g. `game::physics::Position`)
+/// - `version = N`: schema version (defaults to 1)
+/// - `transient`: the component is never saved
+/// - `clone`: register the type's `Clone` impl, so type-erased code can
+///   duplicate it through `ComponentInfo::clone_into`
+/// - `debug`: register the type's `Debug` impl for `ComponentInfo::debug`
+/// - `align = N`: start storage columns at a multiple of `N` bytes, for
+///   aligned SIMD loads
+/// - `requires(A, B, ...)`: inserting this component also inserts the
+///   registered defaults of `A`, `B`, ... if the entity lacks them
+///
+/// ```ignore
+/// #[derive(Component)]
+/// #[component(name = "game::Position", version = 3)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// #[component(transient)]
+/// struct RenderCache(Vec<u8>);
+///
+/// #[derive(Component)]
+/// #[component(requires(Position))]
+/// struct Sprite(u32);
+/// ```
+///
+/// Generic types cannot be registered automatically; register their schema
+/// with `ComponentRegistry::register_schema` for each instantiation.
+///
 /// # Requirements
 ///
 /// The type must satisfy the following bounds:
@@ -65,11 +103,16 @@ use syn::{DeriveInput, parse_macro_input};
 /// - `Sync`: Can be shared between threads
 ///
 /// These bounds are automatically checked by the compiler when the macro is applied.
-#[proc_macro_derive(Component)]
+#[proc_macro_derive(Component, attributes(component))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
+    let schema = match ComponentAttributes::parse(&input.attrs) {
+        Ok(schema) => schema,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     // Build where clause with Component bounds
     let generics = &input.generics;
     let (_impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
@@ -90,12 +133,494 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let (impl_generics_with_bounds, _, where_clause_with_bounds) =
         generics_with_bounds.split_for_impl();
 
+    // Submit the schema for automatic registration (non-generic types only)
+    let registration = if generics.params.is_empty() {
+        let schema_name = match &schema.name {
+            Some(schema_name) => quote! { #schema_name },
+            None => {
+                quote! { ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#name)) }
+            }
+        };
+        let version = schema.version;
+        let transient = schema.transient;
+        let fields = field_layouts(name, &input.data);
+        let clone = schema
+            .clone
+            .then(|| quote! { registry.register_clone::<#name>(); });
+        let debug = schema
+            .debug
+            .then(|| quote! { registry.register_debug::<#name>(); });
+        let align = schema
+            .align
+            .map(|align| quote! { registry.register_alignment::<#name>(#align); });
+        let requires = &schema.requires;
+        quote! {
+            const _: () = {
+                fn register(registry: &::pecs::component::registry::ComponentRegistry) {
+                    registry.register_schema::<#name>(
+                        ::pecs::component::registry::ComponentSchema::new(#schema_name, #version)
+                            .with_transient(#transient)
+                            .with_fields(::std::vec![#(#fields),*]),
+                    );
+                    #clone
+                    #debug
+                    #align
+                    #(registry.register_requires::<#name, #requires>();)*
+                }
+
+                ::pecs::__private::inventory::submit! {
+                    ::pecs::component::registry::ComponentRegistration::new(register)
+                }
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    // Cache the registry lookup in a static (non-generic types only, as a
+    // static cannot depend on generic parameters)
+    let cached_type_id = if generics.params.is_empty() {
+        quote! {
+            #[inline]
+            fn component_type_id() -> ::pecs::component::ComponentTypeId {
+                static TYPE_ID: ::std::sync::OnceLock<::pecs::component::ComponentTypeId> =
+                    ::std::sync::OnceLock::new();
+                *TYPE_ID.get_or_init(::pecs::component::ComponentTypeId::lookup::<Self>)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     // Generate the Component trait implementation
     let expanded = quote! {
-        impl #impl_generics_with_bounds ::pecs::Component for #name #ty_generics #where_clause_with_bounds {}
+        impl #impl_generics_with_bounds ::pecs::Component for #name #ty_generics #where_clause_with_bounds {
+            #cached_type_id
+        }
+
+        #registration
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derives the `Bundle` trait for a struct whose fields are all components
+/// or nested bundles.
+///
+/// The bundle's component set is the union of its fields' sets, so nested
+/// bundles are flattened. Each component type may appear only once.
+///
+/// # Examples
+///
+/// ```ignore
+/// use pecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position { x: f32, y: f32 }
+///
+/// #[derive(Component)]
+/// struct Velocity { x: f32, y: f32 }
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Bundle)]
+/// struct MovementBundle {
+///     position: Position,
+///     velocity: Velocity,
+/// }
+///
+/// #[derive(Bundle)]
+/// struct PlayerBundle {
+///     movement: MovementBundle,
+///     health: Health,
+/// }
+///
+/// let mut world = World::new();
+/// let player = world.spawn_bundle(PlayerBundle {
+///     movement: MovementBundle {
+///         position: Position { x: 0.0, y: 0.0 },
+///         velocity: Velocity { x: 1.0, y: 0.0 },
+///     },
+///     health: Health(100),
+/// });
+/// ```
+#[proc_macro_derive(Bundle)]
+pub fn derive_bundle(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(&input.ident, "Bundle can only be derived for structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let members: Vec<syn::Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        })
+        .collect();
+
+    // Every field must itself be a bundle (components are bundles of one)
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in &types {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ty: ::pecs::bundle::Bundle));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::pecs::bundle::Bundle for #name #ty_generics #where_clause {
+            fn component_types(&self) -> ::pecs::component::ComponentSet {
+                let mut set = ::pecs::component::ComponentSet::new();
+                #(
+                    for component_type in ::pecs::bundle::Bundle::component_types(&self.#members).iter() {
+                        set.insert(component_type);
+                    }
+                )*
+                set
+            }
+
+            fn component_info() -> ::std::vec::Vec<::pecs::component::ComponentInfo> {
+                let mut info = ::std::vec::Vec::new();
+                #(
+                    info.extend(<#types as ::pecs::bundle::Bundle>::component_info());
+                )*
+                info
+            }
+
+            unsafe fn insert_into_world(self, world: &mut ::pecs::World, entity: ::pecs::EntityId) {
+                #(
+                    // SAFETY: Forwarded from caller
+                    unsafe { ::pecs::bundle::Bundle::insert_into_world(self.#members, world, entity) };
+                )*
+            }
+
+            unsafe fn take_from_archetype(
+                archetype: &::pecs::component::archetype::Archetype,
+                row: usize,
+            ) -> Self {
+                Self {
+                    #(
+                        // SAFETY: Forwarded from caller
+                        #members: unsafe {
+                            <#types as ::pecs::bundle::Bundle>::take_from_archetype(archetype, row)
+                        },
+                    )*
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derives `SerializableComponent` using the component's serde
+/// implementation.
+///
+/// The type must implement `Component`, `serde::Serialize` and
+/// `serde::Deserialize`. The `name`, `version` and `transient` settings are
+/// read from the same `#[component(...)]` attribute used by
+/// `#[derive(Component)]`.
+///
+/// Non-generic types are registered automatically with
+/// `ComponentRegistry::register_json` under their component name, so world
+/// saves in every format include them. Generic types must be registered
+/// for each instantiation.
+///
+/// # Examples
+///
+/// ```ignore
+/// use pecs::{Component, SerializableComponent};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Component, SerializableComponent, Serialize, Deserialize)]
+/// #[component(name = "game::Health", version = 2)]
+/// struct Health {
+///     current: u32,
+///     max: u32,
+/// }
+/// ```
+#[proc_macro_derive(SerializableComponent, attributes(component))]
+pub fn derive_serializable_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let schema = match ComponentAttributes::parse(&input.attrs) {
+        Ok(schema) => schema,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let version = schema.version;
+    let transient = schema.transient;
+
+    // Register the JSON codec the world savers use (non-generic types only)
+    let registration = if input.generics.params.is_empty() {
+        let json_name = match &schema.name {
+            Some(json_name) => quote! { #json_name },
+            None => {
+                quote! { ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#name)) }
+            }
+        };
+        quote! {
+            const _: () = {
+                fn register(registry: &::pecs::component::registry::ComponentRegistry) {
+                    registry.register_json::<#name>(#json_name);
+                }
+
+                ::pecs::__private::inventory::submit! {
+                    ::pecs::component::registry::ComponentRegistration::new(register)
+                }
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(Self: ::pecs::__private::serde::Serialize + ::pecs::__private::serde::de::DeserializeOwned));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::pecs::persistence::SerializableComponent for #name #ty_generics #where_clause {
+            const VERSION: u32 = #version;
+
+            fn serialize(&self, writer: &mut dyn ::std::io::Write) -> ::pecs::persistence::Result<()> {
+                ::pecs::persistence::plugin::serialize_serde(self, writer)
+            }
+
+            fn deserialize(reader: &mut dyn ::std::io::Read) -> ::pecs::persistence::Result<Self> {
+                ::pecs::persistence::plugin::deserialize_serde(reader)
+            }
+
+            fn is_transient(&self) -> bool {
+                #transient
+            }
+        }
+
+        #registration
     };
 
     TokenStream::from(expanded)
 }
 
+/// Derives `SystemParam` for a struct whose fields are all system
+/// parameters, so a group of queries and resources can be passed to a system
+/// as one argument.
+///
+/// The struct may have at most one lifetime parameter, which is the world
+/// borrow, and no type parameters.
+///
+/// # Examples
+///
+/// ```ignore
+/// use pecs::Component;
+/// use pecs_schedule::{Query, Res, SystemParam};
+///
+/// #[derive(Component)]
+/// struct Body { mass: f32 }
+///
+/// struct Gravity(f32);
+///
+/// #[derive(SystemParam)]
+/// struct Physics<'w> {
+///     bodies: Query<'w, &'static mut Body>,
+///     gravity: Res<'w, Gravity>,
+/// }
+///
+/// fn fall(mut physics: Physics) {
+///     let gravity = physics.gravity.0;
+///     for body in &mut physics.bodies {
+///         body.mass += gravity;
+///     }
+/// }
+/// ```
+#[proc_macro_derive(SystemParam)]
+pub fn derive_system_param(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "SystemParam can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let mut lifetimes = input.generics.lifetimes();
+    let lifetime = lifetimes.next();
+    if let Some(extra) = lifetimes.next() {
+        return syn::Error::new_spanned(
+            extra,
+            "SystemParam structs may have at most one lifetime parameter",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if let Some(param) = input
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+    {
+        return syn::Error::new_spanned(
+            param,
+            "SystemParam structs may not have type or const parameters",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let members: Vec<syn::Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        })
+        .collect();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let item = match lifetime {
+        Some(_) => quote! { #name<'__w> },
+        None => quote! { #name },
+    };
+
+    let expanded = quote! {
+        impl #impl_generics ::pecs_schedule::SystemParam for #name #ty_generics #where_clause {
+            type Item<'__w> = #item;
+
+            fn access(access: &mut ::pecs_schedule::SystemAccess) {
+                #(
+                    <#types as ::pecs_schedule::SystemParam>::access(access);
+                )*
+            }
+
+            unsafe fn fetch<'__w>(world: &'__w ::pecs::World) -> Self::Item<'__w> {
+                #name {
+                    #(
+                        // SAFETY: Forwarded from caller
+                        #members: unsafe { <#types as ::pecs_schedule::SystemParam>::fetch(world) },
+                    )*
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Builds a `FieldLayout` expression for each field of a struct.
+///
+/// Enums and unions have no fixed field layout and produce none.
+fn field_layouts(name: &syn::Ident, data: &syn::Data) -> Vec<proc_macro2::TokenStream> {
+    let syn::Data::Struct(data) = data else {
+        return Vec::new();
+    };
+    data.fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let ty = &field.ty;
+            let (member, field_name) = match &field.ident {
+                Some(ident) => (quote! { #ident }, ident.to_string()),
+                None => {
+                    let index = syn::Index::from(index);
+                    (quote! { #index }, index.index.to_string())
+                }
+            };
+            quote! {
+                ::pecs::component::registry::FieldLayout::new(
+                    #field_name,
+                    ::std::any::type_name::<#ty>(),
+                    ::std::mem::offset_of!(#name, #member),
+                    ::std::mem::size_of::<#ty>(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Parsed `#[component(...)]` attributes.
+struct ComponentAttributes {
+    name: Option<LitStr>,
+    version: u32,
+    transient: bool,
+    clone: bool,
+    debug: bool,
+    align: Option<usize>,
+    requires: Vec<syn::Path>,
+}
+
+impl ComponentAttributes {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self {
+            name: None,
+            version: 1,
+            transient: false,
+            clone: false,
+            debug: false,
+            align: None,
+            requires: Vec::new(),
+        };
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("component"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    parsed.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("version") {
+                    parsed.version = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else if meta.path.is_ident("transient") {
+                    parsed.transient = true;
+                } else if meta.path.is_ident("clone") {
+                    parsed.clone = true;
+                } else if meta.path.is_ident("debug") {
+                    parsed.debug = true;
+                } else if meta.path.is_ident("align") {
+                    let lit = meta.value()?.parse::<LitInt>()?;
+                    let align: usize = lit.base10_parse()?;
+                    if !align.is_power_of_two() {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "`align` must be a power of two",
+                        ));
+                    }
+                    parsed.align = Some(align);
+                } else if meta.path.is_ident("requires") {
+                    meta.parse_nested_meta(|required| {
+                        parsed.requires.push(required.path);
+                        Ok(())
+                    })?;
+                } else {
+                    return Err(meta.error(
+                        "expected `name`, `version`, `transient`, `clone`, `debug`, `align` or `requires`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
 // Made with Bob
@@ -0,0 +1,251 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Shared benchmark scenarios and timing helpers.
+//!
+//! The Criterion suite in `benches/benchmarks.rs` is built from the
+//! scenarios here, so the same workloads can be timed as plain assertions
+//! where Criterion is not available, e.g. from an ignored test run with
+//! `cargo test --release -- --ignored`.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//! use pecs::bench_util::{self, measure};
+//! use std::time::Duration;
+//!
+//! let mut world = World::new();
+//! bench_util::spawn_moving(&mut world, 1_000);
+//!
+//! let timing = measure("iterate_two", 10, || bench_util::iterate_two(&mut world));
+//! timing.assert_at_most(Duration::from_secs(1));
+//! ```
+
+use crate::World;
+use crate::component::Component;
+use crate::entity::EntityId;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Position of a benchmark entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+impl Component for Position {}
+
+/// Velocity of a benchmark entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+impl Component for Velocity {}
+
+/// Rotation of a benchmark entity, in radians.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rotation(pub f32);
+impl Component for Rotation {}
+
+/// Uniform scale of a benchmark entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Scale(pub f32);
+impl Component for Scale {}
+
+/// Marker that splits entities into distinct archetypes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Fragment<const N: usize>;
+impl<const N: usize> Component for Fragment<N> {}
+
+/// Number of archetypes [`spawn_fragmented`] spreads entities over.
+pub const FRAGMENTS: usize = 16;
+
+/// Spawns `count` entities with all four benchmark components.
+pub fn spawn_moving(world: &mut World, count: usize) -> Vec<EntityId> {
+    (0..count)
+        .map(|i| {
+            world.spawn_bundle((
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+                Velocity { x: 1.0, y: 0.5 },
+                Rotation(0.0),
+                Scale(1.0),
+            ))
+        })
+        .collect()
+}
+
+/// Spawns `count` entities with [`Position`] and [`Velocity`], spread
+/// round-robin over [`FRAGMENTS`] archetypes by a [`Fragment`] marker.
+pub fn spawn_fragmented(world: &mut World, count: usize) -> Vec<EntityId> {
+    macro_rules! spawn_fragment {
+        ($world:expr, $i:expr, $($n:literal)*) => {
+            match $i % FRAGMENTS {
+                $($n => $world.spawn_bundle((
+                    Position { x: $i as f32, y: 0.0 },
+                    Velocity { x: 1.0, y: 0.5 },
+                    Fragment::<$n>,
+                )),)*
+                _ => unreachable!(),
+            }
+        };
+    }
+
+    (0..count)
+        .map(|i| spawn_fragment!(world, i, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15))
+        .collect()
+}
+
+/// Reads one component of every entity, returning the sum of positions.
+pub fn iterate_one(world: &mut World) -> f32 {
+    world.query::<&Position>().map(|position| position.x).sum()
+}
+
+/// Integrates velocity into position, returning the number of entities
+/// visited.
+pub fn iterate_two(world: &mut World) -> usize {
+    let mut count = 0;
+    for (position, velocity) in world.query::<(&mut Position, &Velocity)>() {
+        position.x += velocity.x;
+        position.y += velocity.y;
+        count += 1;
+    }
+    count
+}
+
+/// Updates all four components, returning the number of entities visited.
+pub fn iterate_four(world: &mut World) -> usize {
+    let mut count = 0;
+    for (position, velocity, rotation, scale) in
+        world.query::<(&mut Position, &Velocity, &mut Rotation, &Scale)>()
+    {
+        position.x += velocity.x * scale.0;
+        position.y += velocity.y * scale.0;
+        rotation.0 += 0.1;
+        count += 1;
+    }
+    count
+}
+
+/// Moves every entity to a new archetype and back by inserting and then
+/// removing a [`Scale`].
+///
+/// Entities that already have a `Scale` lose it; entities without gain and
+/// lose one, so the world ends as it started for those.
+pub fn toggle_scale(world: &mut World, entities: &[EntityId]) {
+    for &entity in entities {
+        world.insert(entity, Scale(2.0));
+    }
+    for &entity in entities {
+        black_box(world.remove::<Scale>(entity));
+    }
+}
+
+/// Modifies every `stride`-th entity through `get_mut` and returns how many
+/// modified entities the change tracker reports since before the writes.
+///
+/// # Panics
+///
+/// Panics if `stride` is zero.
+pub fn modify_and_detect(world: &mut World, entities: &[EntityId], stride: usize) -> usize {
+    let epoch = world.persistence().change_tracker_mut().advance();
+    for &entity in entities.iter().step_by(stride) {
+        if let Some(position) = world.get_mut::<Position>(entity) {
+            position.x += 1.0;
+        }
+    }
+    world
+        .persistence()
+        .change_tracker()
+        .changes_since(epoch)
+        .modified
+        .len()
+}
+
+/// Timing of a scenario, as returned by [`measure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    /// Name of the scenario
+    pub name: &'static str,
+    /// Number of timed runs
+    pub iterations: u32,
+    /// Fastest run
+    pub min: Duration,
+    /// Median run
+    pub median: Duration,
+}
+
+impl Measurement {
+    /// Panics if the median run took longer than `budget`.
+    ///
+    /// The median keeps one slow run from failing the check.
+    #[track_caller]
+    pub fn assert_at_most(&self, budget: Duration) {
+        assert!(
+            self.median <= budget,
+            "{} regressed: median {:?} over budget {:?}",
+            self.name,
+            self.median,
+            budget
+        );
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: median {:?}, min {:?} over {} runs",
+            self.name, self.median, self.min, self.iterations
+        )
+    }
+}
+
+/// Runs `scenario` once to warm up, then `iterations` more times, timing
+/// each run.
+///
+/// # Panics
+///
+/// Panics if `iterations` is zero.
+pub fn measure<R>(
+    name: &'static str,
+    iterations: u32,
+    mut scenario: impl FnMut() -> R,
+) -> Measurement {
+    assert!(iterations > 0, "measure needs at least one iteration");
+    black_box(scenario());
+
+    let mut runs: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(scenario());
+            start.elapsed()
+        })
+        .collect();
+    runs.sort_unstable();
+
+    Measurement {
+        name,
+        iterations,
+        min: runs[0],
+        median: runs[runs.len() / 2],
+    }
+}
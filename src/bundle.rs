@@ -40,13 +40,16 @@
 //! ```
 
 use crate::World;
-use crate::component::{Component, ComponentInfo, ComponentSet, ComponentTypeId};
+use crate::component::archetype::Archetype;
+use crate::component::registry::InsertRequirementFn;
+use crate::component::{Component, ComponentInfo, ComponentSet, ComponentTypeId, InsertError};
 use crate::entity::EntityId;
 
 /// A bundle of components that can be inserted into an entity.
 ///
 /// Bundles provide an ergonomic way to spawn entities with multiple components
-/// at once. Any tuple of components automatically implements `Bundle`.
+/// at once. Any tuple of components automatically implements `Bundle`, and
+/// structs of components or nested bundles can use `#[derive(Bundle)]`.
 ///
 /// # Examples
 ///
@@ -84,6 +87,31 @@ pub trait Bundle: 'static {
     /// The caller must ensure the entity exists and the archetype has been
     /// properly set up with the correct component types.
     unsafe fn insert_into_world(self, world: &mut World, entity: EntityId);
+
+    /// Reads this bundle's components out of an archetype row by value.
+    ///
+    /// # Safety
+    ///
+    /// - `row` must be a valid, initialized row of `archetype`
+    /// - The archetype must contain every component type in the bundle
+    /// - The caller takes ownership of the values and must ensure the
+    ///   originals in the archetype are never dropped or read again
+    unsafe fn take_from_archetype(archetype: &Archetype, row: usize) -> Self
+    where
+        Self: Sized;
+}
+
+/// Reads a single component out of an archetype row by value.
+///
+/// # Safety
+///
+/// Same requirements as [`Bundle::take_from_archetype`].
+unsafe fn take_component<T: Component>(archetype: &Archetype, row: usize) -> T {
+    let storage = archetype
+        .get_storage(ComponentTypeId::of::<T>())
+        .expect("bundle component missing from archetype");
+    // SAFETY: Caller guarantees the row is initialized and ownership moves out
+    unsafe { std::ptr::read(storage.get(row) as *const T) }
 }
 
 // Implement Bundle for single components
@@ -99,7 +127,12 @@ impl<T: Component> Bundle for T {
     }
 
     unsafe fn insert_into_world(self, world: &mut World, entity: EntityId) {
-        world.insert(entity, self);
+        world.insert_bundle_component(entity, self);
+    }
+
+    unsafe fn take_from_archetype(archetype: &Archetype, row: usize) -> Self {
+        // SAFETY: Forwarded from caller
+        unsafe { take_component::<T>(archetype, row) }
     }
 }
 
@@ -125,9 +158,14 @@ macro_rules! impl_bundle_tuple {
             unsafe fn insert_into_world(self, world: &mut World, entity: EntityId) {
                 let ($($T,)*) = self;
                 $(
-                    world.insert(entity, $T);
+                    world.insert_bundle_component(entity, $T);
                 )*
             }
+
+            unsafe fn take_from_archetype(archetype: &Archetype, row: usize) -> Self {
+                // SAFETY: Forwarded from caller
+                unsafe { ($(take_component::<$T>(archetype, row),)*) }
+            }
         }
     };
 }
@@ -159,6 +197,13 @@ impl World {
     ///
     /// This is a more ergonomic alternative to using the builder pattern
     /// when you want to spawn an entity with multiple components at once.
+    /// [Required components](crate::component::registry::ComponentRegistry::register_requires)
+    /// missing from the bundle are filled in with their registered defaults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required component is missing from the bundle and has no
+    /// registered default.
     ///
     /// # Examples
     ///
@@ -184,12 +229,53 @@ impl World {
     /// ```
     pub fn spawn_bundle<B: Bundle>(&mut self, bundle: B) -> EntityId {
         let entity = self.spawn_empty();
+        let required = self
+            .bundle_requirements::<B>(entity)
+            .unwrap_or_else(|error| panic!("{}", error));
         unsafe {
             bundle.insert_into_world(self, entity);
         }
+        self.insert_bundle_requirements(entity, required);
         entity
     }
 
+    /// Spawns an entity for each bundle.
+    ///
+    /// Equivalent to calling [`spawn_bundle`](Self::spawn_bundle) for each
+    /// bundle, but reserves entity slots and the bundle archetype's columns
+    /// once up front, using the iterator's lower size bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required component is missing from the bundles and has
+    /// no registered default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position { x: f32, y: f32 }
+    ///
+    /// let mut world = World::new();
+    /// let entities = world.spawn_batch((0..100).map(|i| Position { x: i as f32, y: 0.0 }));
+    /// assert_eq!(entities.len(), 100);
+    /// assert_eq!(world.len(), 100);
+    /// ```
+    pub fn spawn_batch<B: Bundle>(
+        &mut self,
+        bundles: impl IntoIterator<Item = B>,
+    ) -> Vec<EntityId> {
+        let bundles = bundles.into_iter();
+        let (count, _) = bundles.size_hint();
+        self.entities_mut().reserve(count);
+        // The reservation is only a hint; running out of budget surfaces
+        // when the components are inserted
+        let _ = self.try_reserve::<B>(count);
+        bundles.map(|bundle| self.spawn_bundle(bundle)).collect()
+    }
+
     /// Inserts a bundle of components into an existing entity.
     ///
     /// If the entity already has any of the component types in the bundle,
@@ -203,7 +289,9 @@ impl World {
     ///
     /// # Returns
     ///
-    /// `true` if successful, `false` if the entity doesn't exist.
+    /// `true` if successful, `false` if the entity doesn't exist or a
+    /// required component is missing and has no registered default, in
+    /// which case nothing is inserted.
     ///
     /// # Examples
     ///
@@ -229,11 +317,43 @@ impl World {
         if !self.is_alive(entity) {
             return false;
         }
+        let Ok(required) = self.bundle_requirements::<B>(entity) else {
+            return false;
+        };
         unsafe {
             bundle.insert_into_world(self, entity);
         }
+        self.insert_bundle_requirements(entity, required);
         true
     }
+
+    /// Inserts the defaults of a bundle's missing requirements, panicking
+    /// like [`insert_bundle_component`](Self::insert_bundle_component) if
+    /// the memory budget is exceeded.
+    pub(crate) fn insert_bundle_requirements(
+        &mut self,
+        entity: EntityId,
+        required: Vec<InsertRequirementFn>,
+    ) {
+        for insert in required {
+            if let Err(InsertError::OverBudget(error)) = insert(self, entity) {
+                panic!("{}", error);
+            }
+        }
+    }
+
+    /// Resolves the requirements of a bundle's components as a group, so
+    /// a bundle may carry both a component and what it requires.
+    fn bundle_requirements<B: Bundle>(
+        &self,
+        entity: EntityId,
+    ) -> Result<Vec<InsertRequirementFn>, InsertError> {
+        let added: Vec<_> = B::component_info()
+            .iter()
+            .map(|info| (info.type_id().type_id(), info.type_name()))
+            .collect();
+        self.missing_requirements(entity, &added)
+    }
 }
 
 #[cfg(test)]
@@ -261,6 +381,56 @@ mod tests {
     }
     impl Component for Health {}
 
+    #[test]
+    fn test_register_archetype_creates_spawn_path() {
+        let mut world = World::new();
+        let archetype = world.register_archetype::<(Position, Velocity, Health)>();
+        let count = world.archetypes().count();
+        assert_eq!(world.archetype(archetype).unwrap().len(), 0);
+        assert_eq!(
+            world.register_archetype::<(Position, Velocity, Health)>(),
+            archetype
+        );
+
+        let entity = world.spawn_bundle((
+            Position { x: 1.0, y: 2.0 },
+            Velocity { x: 0.5, y: 0.5 },
+            Health {
+                current: 10,
+                max: 10,
+            },
+        ));
+        assert_eq!(world.archetype_of(entity), Some(archetype));
+        assert_eq!(world.archetypes().count(), count);
+    }
+
+    #[test]
+    fn test_spawn_batch_reserves_columns_up_front() {
+        let mut world = World::new();
+        let entities = world.spawn_batch((0..1000).map(|i| {
+            (
+                Position {
+                    x: i as f32,
+                    y: 0.0,
+                },
+                Velocity { x: 1.0, y: 1.0 },
+            )
+        }));
+        assert_eq!(entities.len(), 1000);
+
+        let archetype = world
+            .archetype(world.archetype_of(entities[0]).unwrap())
+            .unwrap();
+        let storage = archetype
+            .get_storage(ComponentTypeId::of::<Position>())
+            .unwrap();
+        assert_eq!(storage.capacity(), 1000);
+        assert_eq!(
+            world.get::<Position>(entities[999]),
+            Some(&Position { x: 999.0, y: 0.0 })
+        );
+    }
+
     #[test]
     fn test_spawn_bundle_single_component() {
         let mut world = World::new();
@@ -373,6 +543,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_take_bundle() {
+        let mut world = World::new();
+        let entity = world.spawn_bundle((
+            Position { x: 1.0, y: 2.0 },
+            Velocity { x: 0.5, y: 0.5 },
+            Health {
+                current: 50,
+                max: 100,
+            },
+        ));
+
+        let (vel, pos) = world.take::<(Velocity, Position)>(entity).unwrap();
+        assert_eq!(pos, Position { x: 1.0, y: 2.0 });
+        assert_eq!(vel, Velocity { x: 0.5, y: 0.5 });
+
+        assert!(!world.has::<Position>(entity));
+        assert!(!world.has::<Velocity>(entity));
+        assert_eq!(world.get::<Health>(entity).unwrap().current, 50);
+    }
+
+    #[test]
+    fn test_take_bundle_missing_component() {
+        let mut world = World::new();
+        let entity = world.spawn_bundle(Position { x: 1.0, y: 2.0 });
+
+        assert!(world.take::<(Position, Velocity)>(entity).is_none());
+        assert!(world.has::<Position>(entity));
+
+        assert_eq!(
+            world.take::<Position>(entity),
+            Some(Position { x: 1.0, y: 2.0 })
+        );
+        assert!(world.take::<Position>(entity).is_none());
+    }
+
     #[test]
     fn test_query_after_bundle_spawn() {
         let mut world = World::new();
@@ -27,6 +27,14 @@
 //! - [`Command`]: A trait for operations that can be applied to the world
 //! - [`CommandBuffer`]: A buffer that records commands for later execution
 //! - Built-in commands for common operations (spawn, despawn, insert, remove)
+//!   and for deferred persistence (saving entities or the whole world)
+//!
+//! Recorded commands are stored by value in an arena inside the buffer
+//! rather than boxed one by one. When the buffer is applied, runs of
+//! consecutive built-in commands of the same kind are applied together:
+//! spawns become one batch spawn, and inserts of one component type become
+//! one batch grouped by archetype. Commands still take effect in the order
+//! they were recorded.
 //!
 //! # Examples
 //!
@@ -45,8 +53,13 @@
 //! assert_eq!(world.len(), 2);
 //! ```
 
+mod arena;
+
 use crate::component::Component;
 use crate::entity::EntityId;
+use crate::replay::RecordingWorld;
+use arena::{ArenaCommand, CommandArena};
+use std::marker::PhantomData;
 
 /// A command that can be applied to the ECS world.
 ///
@@ -74,6 +87,17 @@ pub trait Command: Send {
     /// The caller must ensure the world pointer is valid and that no other
     /// references to the world exist during command application.
     unsafe fn apply(self: Box<Self>, world: *mut crate::World);
+
+    /// Applies this command through a replay recording layer.
+    ///
+    /// The default applies the command directly and notes it in the log as
+    /// unrecorded, which makes the log fail to replay. Override it to route
+    /// the command's mutations through `recording`.
+    fn apply_recorded(self: Box<Self>, recording: &mut RecordingWorld<'_>) {
+        recording.note_unrecorded(std::any::type_name::<Self>().to_string());
+        // SAFETY: The recording layer holds the only borrow of the world
+        unsafe { self.apply(recording.world_mut()) };
+    }
 }
 
 /// A buffer for recording commands to be applied later.
@@ -86,8 +110,9 @@ pub trait Command: Send {
 ///
 /// # Thread Safety
 ///
-/// `CommandBuffer` is `Send` but not `Sync`, meaning it can be moved between
-/// threads but not shared. Each thread should have its own command buffer.
+/// `CommandBuffer` is `Send` and `Sync`. Recording needs `&mut self`, so each
+/// thread should still have its own command buffer; sharing one by reference
+/// only exposes its length.
 ///
 /// # Examples
 ///
@@ -95,18 +120,19 @@ pub trait Command: Send {
 /// use pecs::prelude::*;
 ///
 /// let mut world = World::new();
+/// let entity = world.reserve_entity();
 /// let commands = world.commands();
 ///
 /// // Record some commands
-/// let entity = commands.spawn();
+/// commands.spawn();
 /// commands.despawn(entity);
 ///
 /// // Apply all commands at once
 /// world.apply_commands();
 /// ```
 pub struct CommandBuffer {
-    /// The list of commands to be executed
-    commands: Vec<Box<dyn Command>>,
+    /// The commands to be executed, in recording order
+    commands: CommandArena,
 
     /// Entities spawned by this buffer (for tracking)
     spawned_entities: Vec<EntityId>,
@@ -124,7 +150,7 @@ impl CommandBuffer {
     /// ```
     pub fn new() -> Self {
         Self {
-            commands: Vec::new(),
+            commands: CommandArena::default(),
             spawned_entities: Vec::new(),
         }
     }
@@ -144,33 +170,36 @@ impl CommandBuffer {
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            commands: Vec::with_capacity(capacity),
+            commands: CommandArena::with_capacity(capacity),
             spawned_entities: Vec::new(),
         }
     }
 
     /// Records a command to spawn a new entity.
     ///
-    /// Returns a placeholder `EntityId` that will be valid after the buffer
-    /// is applied. Note that this ID is temporary and may not match the actual
-    /// ID assigned when the command is executed.
+    /// Returns [`EntityId::PLACEHOLDER`], since the actual ID is only assigned
+    /// when the command is executed. Every call returns the same placeholder,
+    /// so it cannot be passed to follow-up commands such as
+    /// [`insert`](Self::insert); use
+    /// [`World::reserve_entity`](crate::World::reserve_entity) instead when a
+    /// real ID is needed up front.
     ///
     /// # Examples
     ///
     /// ```
     /// use pecs::command::CommandBuffer;
+    /// use pecs::EntityId;
     ///
     /// let mut buffer = CommandBuffer::new();
     /// let entity = buffer.spawn();
+    /// assert_eq!(entity, EntityId::PLACEHOLDER);
     /// ```
     pub fn spawn(&mut self) -> EntityId {
-        // Create a placeholder entity ID
         // The actual ID will be assigned when the command is applied
-        // Using index as placeholder, generation 1
-        let placeholder = EntityId::new(self.spawned_entities.len() as u32, 1);
+        let placeholder = EntityId::PLACEHOLDER;
         self.spawned_entities.push(placeholder);
 
-        self.commands.push(Box::new(SpawnCommand));
+        self.commands.push(SpawnCommand);
         placeholder
     }
 
@@ -194,7 +223,95 @@ impl CommandBuffer {
     /// assert!(!world.is_alive(entity));
     /// ```
     pub fn despawn(&mut self, entity: EntityId) {
-        self.commands.push(Box::new(DespawnCommand { entity }));
+        debug_assert_placeholder_unused(entity);
+        self.commands.push(DespawnCommand { entity });
+    }
+
+    /// Records commands to despawn many entities.
+    ///
+    /// Consecutive despawns are applied together through
+    /// [`World::despawn_many`](crate::World::despawn_many).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..3).map(|_| world.spawn_empty()).collect();
+    ///
+    /// world.commands().despawn_many(entities.iter().copied());
+    /// world.apply_commands();
+    /// assert!(world.is_empty());
+    /// ```
+    pub fn despawn_many(&mut self, entities: impl IntoIterator<Item = EntityId>) {
+        for entity in entities {
+            self.despawn(entity);
+        }
+    }
+
+    /// Records a command to clone an entity with all of its cloneable
+    /// components.
+    ///
+    /// See [`World::clone_entity`](crate::World::clone_entity). Like
+    /// [`spawn`](Self::spawn), the new entity's ID is only known once the
+    /// command is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// #[component(clone)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let template = world.spawn().with(Health(30)).id();
+    ///
+    /// world.commands().clone_entity(template);
+    /// world.apply_commands();
+    /// assert_eq!(world.len(), 2);
+    /// ```
+    pub fn clone_entity(&mut self, entity: EntityId) {
+        self.commands.push(CloneEntityCommand { entity });
+    }
+
+    /// Records a command to save an entity through the named entity
+    /// persistence plugin.
+    ///
+    /// The save runs when the buffer is applied, after the structural
+    /// changes recorded before it. Failures are collected in the
+    /// [`PersistenceErrors`](crate::persistence::PersistenceErrors) resource.
+    pub fn save_entity(&mut self, entity: EntityId, plugin_name: impl Into<String>) {
+        self.commands.push(SaveEntityCommand {
+            entity,
+            plugin_name: plugin_name.into(),
+        });
+    }
+
+    /// Records a command to save the whole world to a file, choosing the
+    /// plugin by file extension as [`World::save`](crate::World::save)
+    /// does.
+    ///
+    /// The save runs when the buffer is applied, after the structural
+    /// changes recorded before it. Failures are collected in the
+    /// [`PersistenceErrors`](crate::persistence::PersistenceErrors) resource.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pecs::prelude::*;
+    ///
+    /// let mut world = World::new();
+    /// let commands = world.commands();
+    /// commands.spawn();
+    /// commands.save_world("autosave.pecs");
+    /// world.apply_commands();
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save_world(&mut self, path: impl Into<std::path::PathBuf>) {
+        self.commands.push(SaveWorldCommand { path: path.into() });
     }
 
     /// Records a command to insert a component on an entity.
@@ -214,13 +331,15 @@ impl CommandBuffer {
     /// struct Position { x: f32, y: f32 }
     /// impl Component for Position {}
     ///
+    /// let world = pecs::World::new();
+    /// let entity = world.reserve_entity();
+    ///
     /// let mut buffer = CommandBuffer::new();
-    /// let entity = buffer.spawn();
     /// buffer.insert(entity, Position { x: 0.0, y: 0.0 });
     /// ```
     pub fn insert<T: Component>(&mut self, entity: EntityId, component: T) {
-        self.commands
-            .push(Box::new(InsertCommand { entity, component }));
+        debug_assert_placeholder_unused(entity);
+        self.commands.push(InsertCommand { entity, component });
     }
 
     /// Records a command to remove a component from an entity.
@@ -239,15 +358,47 @@ impl CommandBuffer {
     /// struct Position { x: f32, y: f32 }
     /// impl Component for Position {}
     ///
+    /// let mut world = pecs::World::new();
+    /// let entity = world.spawn_empty();
+    ///
     /// let mut buffer = CommandBuffer::new();
-    /// let entity = buffer.spawn();
     /// buffer.remove::<Position>(entity);
     /// ```
     pub fn remove<T: Component>(&mut self, entity: EntityId) {
-        self.commands.push(Box::new(RemoveCommand::<T> {
+        debug_assert_placeholder_unused(entity);
+        self.commands.push(RemoveCommand::<T> {
             entity,
-            _phantom: std::marker::PhantomData,
-        }));
+            _phantom: PhantomData,
+        });
+    }
+
+    /// Records a custom command.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::command::{Command, CommandBuffer};
+    /// use pecs::World;
+    ///
+    /// struct SpawnTwo;
+    ///
+    /// impl Command for SpawnTwo {
+    ///     unsafe fn apply(self: Box<Self>, world: *mut World) {
+    ///         // SAFETY: Command buffers pass a valid, exclusive world pointer
+    ///         let world = unsafe { &mut *world };
+    ///         world.spawn_empty();
+    ///         world.spawn_empty();
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let mut buffer = CommandBuffer::new();
+    /// buffer.push(SpawnTwo);
+    /// buffer.apply(&mut world);
+    /// assert_eq!(world.len(), 2);
+    /// ```
+    pub fn push(&mut self, command: impl Command + 'static) {
+        self.commands.push(CustomCommand(Box::new(command)));
     }
 
     /// Returns the number of commands in the buffer.
@@ -277,7 +428,7 @@ impl CommandBuffer {
     /// assert!(buffer.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool {
-        self.commands.is_empty()
+        self.commands.len() == 0
     }
 
     /// Clears all commands from the buffer without executing them.
@@ -321,20 +472,27 @@ impl CommandBuffer {
     /// assert_eq!(world.len(), 2);
     /// ```
     pub fn apply(&mut self, world: &mut crate::World) {
-        // Take ownership of commands to execute them
-        let commands = std::mem::take(&mut self.commands);
-
-        for command in commands {
-            unsafe {
-                command.apply(world as *mut crate::World);
-            }
-        }
+        self.commands.apply(world);
 
         // Clear spawned entities tracking
         self.spawned_entities.clear();
     }
+
+    /// Applies all commands in the buffer through a replay recording layer.
+    ///
+    /// Built-in commands are recorded as the mutations they make; other
+    /// commands are applied and noted in the log as unrecorded.
+    pub fn apply_recorded(&mut self, recording: &mut RecordingWorld<'_>) {
+        self.commands.apply_recorded(recording);
+        self.spawned_entities.clear();
+    }
 }
 
+// SAFETY: Commands are only `Send`, but a shared `CommandBuffer` never hands
+// out or touches them: every method reaching the commands takes `&mut self`
+// or `self`, and `&self` methods only read the length of the list.
+unsafe impl Sync for CommandBuffer {}
+
 impl Default for CommandBuffer {
     fn default() -> Self {
         Self::new()
@@ -346,12 +504,13 @@ impl Default for CommandBuffer {
 /// Command to spawn a new entity.
 struct SpawnCommand;
 
-impl Command for SpawnCommand {
-    unsafe fn apply(self: Box<Self>, world: *mut crate::World) {
-        // SAFETY: Caller ensures world pointer is valid
-        unsafe {
-            (*world).spawn_empty();
-        }
+impl ArenaCommand for SpawnCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        world.spawn_empty_batch(run.count());
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.spawn();
     }
 }
 
@@ -360,12 +519,13 @@ struct DespawnCommand {
     entity: EntityId,
 }
 
-impl Command for DespawnCommand {
-    unsafe fn apply(self: Box<Self>, world: *mut crate::World) {
-        // SAFETY: Caller ensures world pointer is valid
-        unsafe {
-            (*world).despawn(self.entity);
-        }
+impl ArenaCommand for DespawnCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        world.despawn_many(run.map(|command| command.entity));
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.despawn(self.entity);
     }
 }
 
@@ -375,28 +535,134 @@ struct InsertCommand<T: Component> {
     component: T,
 }
 
-impl<T: Component> Command for InsertCommand<T> {
-    unsafe fn apply(self: Box<Self>, world: *mut crate::World) {
-        // SAFETY: Caller ensures world pointer is valid
-        unsafe {
-            (*world).insert(self.entity, self.component);
-        }
+impl<T: Component> ArenaCommand for InsertCommand<T> {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        // Grouped by source archetype so each transition is resolved once
+        world.insert_batch(run.map(|command| (command.entity, command.component)));
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.insert(self.entity, self.component);
     }
 }
 
 /// Command to remove a component from an entity.
 struct RemoveCommand<T: Component> {
     entity: EntityId,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: PhantomData<fn() -> T>,
 }
 
-impl<T: Component> Command for RemoveCommand<T> {
-    unsafe fn apply(self: Box<Self>, world: *mut crate::World) {
-        // SAFETY: Caller ensures world pointer is valid
-        unsafe {
-            (*world).remove::<T>(self.entity);
+impl<T: Component> ArenaCommand for RemoveCommand<T> {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        for command in run {
+            world.remove::<T>(command.entity);
         }
     }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.remove::<T>(self.entity);
+    }
+}
+
+/// Command to clone an entity.
+struct CloneEntityCommand {
+    entity: EntityId,
+}
+
+impl ArenaCommand for CloneEntityCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        for command in run {
+            world.clone_entity(command.entity);
+        }
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.note_unrecorded(std::any::type_name::<Self>().to_string());
+        recording.world_mut().clone_entity(self.entity);
+    }
+}
+
+/// Command to save an entity through an entity persistence plugin.
+struct SaveEntityCommand {
+    entity: EntityId,
+    plugin_name: String,
+}
+
+impl SaveEntityCommand {
+    fn run(self, world: &mut crate::World) {
+        if let Err(error) = world.save_entity(self.entity, &self.plugin_name) {
+            world.report_persistence_error(error);
+        }
+    }
+}
+
+impl ArenaCommand for SaveEntityCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        for command in run {
+            command.run(world);
+        }
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.note_unrecorded(std::any::type_name::<Self>().to_string());
+        self.run(recording.world_mut());
+    }
+}
+
+/// Command to save the world to a file.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+struct SaveWorldCommand {
+    path: std::path::PathBuf,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl SaveWorldCommand {
+    fn run(self, world: &mut crate::World) {
+        if let Err(error) = world.save(&self.path) {
+            world.report_persistence_error(error);
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl ArenaCommand for SaveWorldCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        for command in run {
+            command.run(world);
+        }
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        recording.note_unrecorded(std::any::type_name::<Self>().to_string());
+        self.run(recording.world_mut());
+    }
+}
+
+/// A command recorded with [`CommandBuffer::push`].
+struct CustomCommand(Box<dyn Command>);
+
+impl ArenaCommand for CustomCommand {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut crate::World) {
+        for command in run {
+            // SAFETY: The world is exclusively borrowed for the whole run
+            unsafe { command.0.apply(world as *mut crate::World) };
+        }
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        self.0.apply_recorded(recording);
+    }
+}
+
+/// Catches the shared spawn placeholder being used as a command target.
+#[inline]
+fn debug_assert_placeholder_unused(entity: EntityId) {
+    debug_assert_ne!(
+        entity,
+        EntityId::PLACEHOLDER,
+        "the placeholder returned by CommandBuffer::spawn does not name an entity; \
+         use World::reserve_entity for an ID usable in later commands"
+    );
 }
 
 #[cfg(test)]
@@ -513,18 +779,131 @@ mod tests {
     #[test]
     fn insert_command_recording() {
         let mut buffer = CommandBuffer::new();
-        let entity = buffer.spawn();
+        buffer.spawn();
+        let entity = crate::World::new().reserve_entity();
 
         buffer.insert(entity, TestComponent { value: 42 });
         assert_eq!(buffer.len(), 2); // spawn + insert
     }
 
     #[test]
-    fn remove_command_recording() {
+    fn consecutive_commands_are_coalesced_in_order() {
+        let mut world = crate::World::new();
+        let entities: Vec<_> = (0..4).map(|_| world.spawn_empty()).collect();
+
+        let mut buffer = CommandBuffer::new();
+        for _ in 0..3 {
+            buffer.spawn();
+        }
+        for (i, &entity) in entities.iter().enumerate() {
+            buffer.insert(entity, TestComponent { value: i as i32 });
+        }
+        buffer.remove::<TestComponent>(entities[0]);
+        buffer.insert(entities[0], TestComponent { value: 99 });
+        buffer.despawn(entities[3]);
+        buffer.despawn(entities[2]);
+
+        assert_eq!(buffer.len(), 11);
+
+        buffer.apply(&mut world);
+        assert!(buffer.is_empty());
+        assert_eq!(world.len(), 5);
+        assert_eq!(world.get::<TestComponent>(entities[0]).unwrap().value, 99);
+        assert_eq!(world.get::<TestComponent>(entities[1]).unwrap().value, 1);
+        assert!(!world.is_alive(entities[2]));
+    }
+
+    #[test]
+    fn unapplied_commands_are_dropped_once() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Tracked(#[allow(dead_code)] [u64; 3], Arc<AtomicUsize>);
+        impl Component for Tracked {}
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.1.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut world = crate::World::new();
+        let entity = world.spawn_empty();
+
+        let mut buffer = CommandBuffer::new();
+        for _ in 0..5 {
+            buffer.insert(entity, Tracked([0; 3], drops.clone()));
+            buffer.insert(entity, TestComponent { value: 7 });
+        }
+        buffer.clear();
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+
+        buffer.insert(entity, Tracked([0; 3], drops.clone()));
+        drop(buffer);
+        assert_eq!(drops.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not name an entity")]
+    fn spawn_placeholder_is_rejected_as_target() {
         let mut buffer = CommandBuffer::new();
         let entity = buffer.spawn();
+        buffer.insert(entity, TestComponent { value: 1 });
+    }
+
+    #[test]
+    fn remove_command_recording() {
+        let mut buffer = CommandBuffer::new();
+        buffer.spawn();
+        let entity = crate::World::new().reserve_entity();
 
         buffer.remove::<TestComponent>(entity);
         assert_eq!(buffer.len(), 2); // spawn + remove
     }
+
+    #[test]
+    fn persistence_commands_run_at_apply_point() {
+        use crate::persistence::{KeyValueEntityPlugin, PersistenceErrors};
+
+        let mut world = crate::World::new();
+        world
+            .persistence()
+            .register_entity_plugin("kv", Box::new(KeyValueEntityPlugin::new()));
+        let entity = world.spawn_empty();
+        let stable_id = world.get_stable_id(entity).unwrap();
+
+        let path = std::env::temp_dir().join("pecs_command_save_world.pecs");
+        let commands = world.commands();
+        commands.save_entity(entity, "kv");
+        commands.save_entity(entity, "missing");
+        commands.spawn();
+        commands.save_world(&path);
+        assert!(
+            !world
+                .persistence()
+                .entity_exists_with(stable_id, "kv")
+                .unwrap()
+        );
+
+        world.apply_commands();
+        assert!(
+            world
+                .persistence()
+                .entity_exists_with(stable_id, "kv")
+                .unwrap()
+        );
+        let errors = world.resource_mut::<PersistenceErrors>().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors
+                .drain()
+                .all(|e| matches!(e, crate::persistence::PersistenceError::PluginNotFound(_)))
+        );
+
+        // The save saw the entity spawned before it in the same buffer
+        let loaded = crate::World::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.len(), 2);
+    }
 }
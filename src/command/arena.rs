@@ -0,0 +1,275 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Type-erased bump storage for recorded commands.
+//!
+//! Commands are written by value into one growable buffer of aligned blocks,
+//! next to a small per-kind vtable, so recording does not allocate per
+//! command. When applied, runs of consecutive commands of the same kind are
+//! handed to their kind's [`ArenaCommand::apply_run`] together.
+
+use crate::World;
+use crate::replay::RecordingWorld;
+use std::any::TypeId;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::{MaybeUninit, align_of, size_of};
+
+/// Unit of arena storage; its alignment is the largest a command can have
+/// without being boxed.
+#[derive(Clone, Copy)]
+#[repr(C, align(16))]
+struct Block([u8; 16]);
+
+/// A command kind that can be stored in a [`CommandArena`].
+pub(super) trait ArenaCommand: Send + Sized + 'static {
+    /// Applies a run of consecutive commands of this kind, in order.
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut World);
+
+    /// Applies one command through a replay recording layer.
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>);
+}
+
+/// Functions for one command kind, operating on type-erased payloads.
+struct Vtable {
+    kind: fn() -> TypeId,
+    apply_run: unsafe fn(&mut CommandArena, usize, &mut World),
+    apply_recorded: unsafe fn(*mut u8, &mut RecordingWorld<'_>),
+    drop: unsafe fn(*mut u8),
+}
+
+impl Vtable {
+    fn of<T: ArenaCommand>() -> &'static Self {
+        const {
+            &Vtable {
+                kind: TypeId::of::<T>,
+                apply_run: apply_run::<T>,
+                apply_recorded: apply_recorded::<T>,
+                drop: drop_payload::<T>,
+            }
+        }
+    }
+}
+
+/// # Safety
+///
+/// The next `count` unconsumed entries of `arena` must hold `T`s.
+unsafe fn apply_run<T: ArenaCommand>(arena: &mut CommandArena, count: usize, world: &mut World) {
+    T::apply_run(
+        Run {
+            arena,
+            remaining: count,
+            _phantom: PhantomData,
+        },
+        world,
+    );
+}
+
+/// # Safety
+///
+/// `payload` must hold a `T`, which is moved out.
+unsafe fn apply_recorded<T: ArenaCommand>(payload: *mut u8, recording: &mut RecordingWorld<'_>) {
+    unsafe { payload.cast::<T>().read() }.apply_recorded(recording);
+}
+
+/// # Safety
+///
+/// `payload` must hold a `T`, which is dropped in place.
+unsafe fn drop_payload<T>(payload: *mut u8) {
+    unsafe { payload.cast::<T>().drop_in_place() }
+}
+
+/// Where a command's payload starts and how to handle it.
+#[derive(Clone, Copy)]
+struct Entry {
+    /// Offset of the payload, in blocks
+    offset: usize,
+    vtable: &'static Vtable,
+}
+
+/// A command whose alignment exceeds a block's, stored behind a box.
+struct Overaligned<T>(Box<T>);
+
+impl<T: ArenaCommand> ArenaCommand for Overaligned<T> {
+    fn apply_run(run: impl Iterator<Item = Self>, world: &mut World) {
+        T::apply_run(run.map(|command| *command.0), world);
+    }
+
+    fn apply_recorded(self, recording: &mut RecordingWorld<'_>) {
+        self.0.apply_recorded(recording);
+    }
+}
+
+/// Bump storage for recorded commands.
+///
+/// Entries before `consumed` have been moved out; the rest are owned and
+/// dropped with the arena.
+///
+/// The arena is `Send` like its payloads, but not `Sync`: commands are only
+/// required to be `Send`, so shared access to them must not be assumed.
+#[derive(Default)]
+pub(super) struct CommandArena {
+    blocks: Vec<MaybeUninit<Block>>,
+    entries: Vec<Entry>,
+    consumed: usize,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl CommandArena {
+    /// Creates an arena with room for about `capacity` small commands.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            blocks: Vec::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            consumed: 0,
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Returns the number of commands not yet applied.
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.consumed
+    }
+
+    /// Records a command.
+    pub fn push<T: ArenaCommand>(&mut self, command: T) {
+        if align_of::<T>() > align_of::<Block>() {
+            self.push_inline(Overaligned(Box::new(command)));
+        } else {
+            self.push_inline(command);
+        }
+    }
+
+    fn push_inline<T: ArenaCommand>(&mut self, command: T) {
+        debug_assert!(align_of::<T>() <= align_of::<Block>());
+        let offset = self.blocks.len();
+        let blocks = size_of::<T>().div_ceil(size_of::<Block>());
+        self.blocks.reserve(blocks);
+        // SAFETY: the reserved blocks are aligned for T and large enough to
+        // hold it; MaybeUninit blocks need no initialization
+        unsafe {
+            self.blocks
+                .as_mut_ptr()
+                .add(offset)
+                .cast::<T>()
+                .write(command);
+            self.blocks.set_len(offset + blocks);
+        }
+        self.entries.push(Entry {
+            offset,
+            vtable: Vtable::of::<T>(),
+        });
+    }
+
+    /// Drops every command without applying it.
+    pub fn clear(&mut self) {
+        self.drop_unconsumed();
+        self.blocks.clear();
+        self.entries.clear();
+        self.consumed = 0;
+    }
+
+    /// Applies every command in order, grouping runs of the same kind.
+    pub fn apply(&mut self, world: &mut World) {
+        while self.consumed < self.entries.len() {
+            let vtable = self.entries[self.consumed].vtable;
+            let kind = (vtable.kind)();
+            let count = self.entries[self.consumed..]
+                .iter()
+                .take_while(|entry| (entry.vtable.kind)() == kind)
+                .count();
+            // SAFETY: the run's entries all hold the vtable's type
+            unsafe { (vtable.apply_run)(self, count, world) };
+        }
+        self.clear();
+    }
+
+    /// Applies every command in order through a replay recording layer.
+    pub fn apply_recorded(&mut self, recording: &mut RecordingWorld<'_>) {
+        while self.consumed < self.entries.len() {
+            let entry = self.entries[self.consumed];
+            self.consumed += 1;
+            // SAFETY: the entry holds the vtable's type and is now consumed
+            unsafe { (entry.vtable.apply_recorded)(self.payload(entry), recording) };
+        }
+        self.clear();
+    }
+
+    fn payload(&mut self, entry: Entry) -> *mut u8 {
+        // SAFETY: offsets are within the block buffer
+        unsafe { self.blocks.as_mut_ptr().add(entry.offset).cast() }
+    }
+
+    /// Moves the next unconsumed command out of the arena.
+    ///
+    /// # Safety
+    ///
+    /// There must be an unconsumed command and it must be a `T`.
+    unsafe fn take<T>(&mut self) -> T {
+        let entry = self.entries[self.consumed];
+        self.consumed += 1;
+        unsafe { self.payload(entry).cast::<T>().read() }
+    }
+
+    fn drop_unconsumed(&mut self) {
+        while self.consumed < self.entries.len() {
+            let entry = self.entries[self.consumed];
+            self.consumed += 1;
+            // SAFETY: the entry holds the vtable's type and is now consumed
+            unsafe { (entry.vtable.drop)(self.payload(entry)) };
+        }
+    }
+}
+
+impl Drop for CommandArena {
+    fn drop(&mut self) {
+        self.drop_unconsumed();
+    }
+}
+
+/// A run of consecutive commands of one kind, yielded by value.
+///
+/// Commands the consumer does not take are dropped with the run.
+struct Run<'a, T> {
+    arena: &'a mut CommandArena,
+    remaining: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Iterator for Run<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        // SAFETY: the run covers the next `remaining` entries, all `T`s
+        Some(unsafe { self.arena.take() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Run<'_, T> {}
+
+impl<T> Drop for Run<'_, T> {
+    fn drop(&mut self) {
+        for _ in self.by_ref() {}
+    }
+}
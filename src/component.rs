@@ -50,10 +50,17 @@
 //! ```
 
 pub mod archetype;
+pub mod index;
+pub mod memory;
+pub mod registry;
 pub mod storage;
 
+use registry::ComponentRegistry;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use std::any::TypeId;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// A component that can be attached to entities.
 ///
@@ -77,14 +84,107 @@ use std::fmt;
 ///
 /// impl Component for Health {}
 /// ```
-pub trait Component: 'static + Send + Sync {}
+pub trait Component: 'static + Send + Sync {
+    /// Returns this type's [`ComponentTypeId`].
+    ///
+    /// The default looks the type up in the [`ComponentRegistry`] on every
+    /// call. `#[derive(Component)]` caches the result in a per-type static,
+    /// so presence checks such as [`World::has`](crate::World::has) come
+    /// down to an atomic load and a bit test.
+    #[doc(hidden)]
+    fn component_type_id() -> ComponentTypeId
+    where
+        Self: Sized,
+    {
+        ComponentTypeId::lookup::<Self>()
+    }
+}
+
+/// Marks an entity as disabled.
+///
+/// Queries skip disabled entities unless their filter includes them with
+/// [`Including<Disabled>`](crate::query::filter::Including), so an entity
+/// can be switched off without removing its components. Toggle it with
+/// [`World::set_enabled`](crate::World::set_enabled). Direct access such as
+/// [`World::get`](crate::World::get) is unaffected.
+///
+/// Like any component, it is only saved once registered for persistence,
+/// e.g. with
+/// [`register_json`](registry::ComponentRegistry::register_json).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Disabled;
+
+impl Component for Disabled {
+    #[inline]
+    fn component_type_id() -> ComponentTypeId {
+        static TYPE_ID: std::sync::OnceLock<ComponentTypeId> = std::sync::OnceLock::new();
+        *TYPE_ID.get_or_init(ComponentTypeId::lookup::<Self>)
+    }
+}
+
+/// An error from inserting a component into an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertError {
+    /// The entity does not exist.
+    InvalidEntity,
+    /// The component requires a type the entity lacks, and no default is
+    /// registered for it.
+    MissingRequirement {
+        /// Name of the inserted component type
+        component: &'static str,
+        /// Name of the required component type
+        required: &'static str,
+    },
+    /// The component storage could not grow, usually because it would
+    /// exceed the world's memory budget. The entity is left unchanged.
+    OverBudget(memory::MemoryError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::InvalidEntity => write!(f, "Invalid entity"),
+            InsertError::MissingRequirement {
+                component,
+                required,
+            } => write!(
+                f,
+                "Component {} requires {}, which has no registered default",
+                component, required
+            ),
+            InsertError::OverBudget(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for InsertError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InsertError::OverBudget(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<memory::MemoryError> for InsertError {
+    fn from(error: memory::MemoryError) -> Self {
+        InsertError::OverBudget(error)
+    }
+}
 
 /// A unique identifier for a component type.
 ///
-/// This is a wrapper around `TypeId` that provides additional functionality
-/// for component type management.
+/// This is a wrapper around `TypeId` that also carries the dense index
+/// assigned by the [`ComponentRegistry`], which is what [`ComponentSet`] is
+/// keyed by. Ordering follows the registry index.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct ComponentTypeId(TypeId);
+pub struct ComponentTypeId {
+    /// Dense index assigned by the component registry
+    index: u32,
+
+    /// The underlying Rust type ID
+    type_id: TypeId,
+}
 
 impl ComponentTypeId {
     /// Creates a new `ComponentTypeId` for a component type.
@@ -100,26 +200,63 @@ impl ComponentTypeId {
     ///
     /// let type_id = ComponentTypeId::of::<Position>();
     /// ```
+    #[inline]
     pub fn of<T: Component>() -> Self {
-        Self(TypeId::of::<T>())
+        T::component_type_id()
+    }
+
+    /// Looks up the `ComponentTypeId` of `T` in the registry, bypassing any
+    /// cache. Used by generated code.
+    #[doc(hidden)]
+    pub fn lookup<T: Component>() -> Self {
+        let type_id = TypeId::of::<T>();
+        Self {
+            index: ComponentRegistry::global().index_of(type_id),
+            type_id,
+        }
     }
 
     /// Returns the underlying `TypeId`.
     pub fn type_id(self) -> TypeId {
-        self.0
+        self.type_id
+    }
+
+    /// Returns the dense registry index of this component type.
+    pub fn index(self) -> u32 {
+        self.index
     }
 }
 
 impl fmt::Display for ComponentTypeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "ComponentType({:?})", self.0)
+        write!(f, "ComponentType({:?})", self.type_id)
     }
 }
 
+/// Clones the value at the first pointer into uninitialized memory at the second.
+pub type CloneFn = unsafe fn(*const u8, *mut u8);
+
+/// Formats the value at the pointer with its `Debug` implementation.
+pub type DebugFn = unsafe fn(*const u8, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+/// Serializes the value at the pointer to JSON.
+pub type SerializeFn = unsafe fn(*const u8) -> Result<serde_json::Value, serde_json::Error>;
+
+/// Deserializes a JSON value into uninitialized memory at the pointer.
+pub type DeserializeFn = unsafe fn(serde_json::Value, *mut u8) -> Result<(), serde_json::Error>;
+
 /// Information about a component type.
 ///
 /// This stores metadata needed for component storage and manipulation,
-/// including size, alignment, and drop behavior.
+/// including size, alignment, and drop behavior. It doubles as a vtable for
+/// working with components whose type is only known at runtime: cloning,
+/// `Debug` formatting and JSON conversion are available when the type has
+/// registered them with the [`ComponentRegistry`] (see
+/// [`register_clone`](ComponentRegistry::register_clone),
+/// [`register_debug`](ComponentRegistry::register_debug) and
+/// [`register_json`](ComponentRegistry::register_json)). Operations
+/// registered after the info was created are looked up when first used, so
+/// existing archetypes pick them up too.
 #[derive(Debug, Clone)]
 pub struct ComponentInfo {
     /// The type ID of the component
@@ -134,11 +271,26 @@ pub struct ComponentInfo {
     /// Alignment requirement of the component
     alignment: usize,
 
+    /// Alignment of the start of storage columns, at least `alignment`
+    column_alignment: usize,
+
     /// Whether the component needs to be dropped
     needs_drop: bool,
 
     /// Function to drop a component in place
     drop_fn: unsafe fn(*mut u8),
+
+    /// Function to clone a component, if the type is cloneable
+    clone_fn: Option<CloneFn>,
+
+    /// Function to format a component, if the type is `Debug`
+    debug_fn: Option<DebugFn>,
+
+    /// Function to serialize a component, if the type is serializable
+    serialize_fn: Option<SerializeFn>,
+
+    /// Function to deserialize a component, if the type is serializable
+    deserialize_fn: Option<DeserializeFn>,
 }
 
 impl ComponentInfo {
@@ -157,18 +309,77 @@ impl ComponentInfo {
     /// assert_eq!(info.size(), std::mem::size_of::<Position>());
     /// ```
     pub fn of<T: Component>() -> Self {
+        Self::with_vtable::<T>(
+            ComponentTypeId::of::<T>(),
+            ComponentRegistry::global().vtable(TypeId::of::<T>()),
+        )
+    }
+
+    /// Creates component info for `T` through `registry` alone, which
+    /// registrations run while the global registry initializes must use.
+    pub(crate) fn of_in<T: Component>(registry: &ComponentRegistry) -> Self {
+        let type_id = TypeId::of::<T>();
+        let component_type_id = ComponentTypeId {
+            index: registry.index_of(type_id),
+            type_id,
+        };
+        Self::with_vtable::<T>(component_type_id, registry.vtable(type_id))
+    }
+
+    fn with_vtable<T: Component>(
+        type_id: ComponentTypeId,
+        vtable: registry::ComponentVtable,
+    ) -> Self {
         Self {
-            type_id: ComponentTypeId::of::<T>(),
+            type_id,
             type_name: std::any::type_name::<T>(),
             size: std::mem::size_of::<T>(),
             alignment: std::mem::align_of::<T>(),
+            column_alignment: vtable.column_alignment.max(std::mem::align_of::<T>()),
             needs_drop: std::mem::needs_drop::<T>(),
             drop_fn: |ptr| unsafe {
                 std::ptr::drop_in_place(ptr as *mut T);
             },
+            clone_fn: vtable.clone,
+            debug_fn: vtable.debug,
+            serialize_fn: vtable.serialize,
+            deserialize_fn: vtable.deserialize,
         }
     }
 
+    /// Creates component info whose storage columns start at an
+    /// over-aligned address.
+    ///
+    /// Storage built from this info allocates its column at an address that
+    /// is a multiple of `align`, so math code can use aligned SIMD loads on
+    /// [`ComponentStorage::as_slice`](storage::ComponentStorage::as_slice).
+    /// An `align` below the type's own alignment has no effect. To apply it
+    /// to the archetypes a [`World`](crate::World) creates, register it with
+    /// [`ComponentRegistry::register_alignment`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::{Component, ComponentInfo, storage::ComponentStorage};
+    ///
+    /// struct Mass(f32);
+    /// impl Component for Mass {}
+    ///
+    /// let mut storage = ComponentStorage::new(ComponentInfo::of_aligned::<Mass>(32));
+    /// unsafe { storage.push(&Mass(1.0) as *const Mass as *const u8) };
+    /// assert_eq!(storage.as_slice::<Mass>().as_ptr() as usize % 32, 0);
+    /// ```
+    pub fn of_aligned<T: Component>(align: usize) -> Self {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let mut info = Self::of::<T>();
+        info.column_alignment = info.column_alignment.max(align);
+        info
+    }
+
     /// Returns the component type ID.
     pub fn type_id(&self) -> ComponentTypeId {
         self.type_id
@@ -189,6 +400,15 @@ impl ComponentInfo {
         self.alignment
     }
 
+    /// Returns the alignment of the start of storage columns.
+    ///
+    /// Equal to [`alignment`](Self::alignment) unless the info was created
+    /// with [`of_aligned`](Self::of_aligned) or the type registered a larger
+    /// alignment.
+    pub fn column_alignment(&self) -> usize {
+        self.column_alignment
+    }
+
     /// Returns whether the component needs to be dropped.
     pub fn needs_drop(&self) -> bool {
         self.needs_drop
@@ -207,62 +427,228 @@ impl ComponentInfo {
             }
         }
     }
+
+    /// Returns `true` if components of this type can be cloned.
+    pub fn is_cloneable(&self) -> bool {
+        self.clone_fn().is_some()
+    }
+
+    /// Returns `true` if components of this type can be converted to and
+    /// from JSON.
+    pub fn is_serializable(&self) -> bool {
+        self.serialize_fn().is_some() && self.deserialize_fn().is_some()
+    }
+
+    /// Clones the component at `src` into `dst`.
+    ///
+    /// Returns `false`, leaving `dst` untouched, if the type is not cloneable.
+    ///
+    /// # Safety
+    ///
+    /// `src` must point to a valid instance of this component type, and
+    /// `dst` to uninitialized memory of the right size and alignment.
+    pub unsafe fn clone_into(&self, src: *const u8, dst: *mut u8) -> bool {
+        match self.clone_fn() {
+            Some(clone_fn) => {
+                // SAFETY: Caller ensures both pointers are valid for the type
+                unsafe { clone_fn(src, dst) };
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns a `Debug` view of the component at `ptr`.
+    ///
+    /// Types without a registered formatter print as their type name.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid instance of this component type and stay
+    /// valid for as long as the returned value is used.
+    pub unsafe fn debug(&self, ptr: *const u8) -> impl fmt::Debug + '_ {
+        ComponentDebug { info: self, ptr }
+    }
+
+    /// Serializes the component at `ptr` to JSON.
+    ///
+    /// Returns `None` if the type is not serializable.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid instance of this component type.
+    pub unsafe fn serialize(
+        &self,
+        ptr: *const u8,
+    ) -> Option<serde_json::Result<serde_json::Value>> {
+        // SAFETY: Caller ensures ptr points to a valid component instance
+        self.serialize_fn()
+            .map(|serialize| unsafe { serialize(ptr) })
+    }
+
+    /// Deserializes a JSON value into `dst`.
+    ///
+    /// Returns `None` if the type is not serializable. On error `dst` is
+    /// left uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// `dst` must point to uninitialized memory of the right size and
+    /// alignment for this component type.
+    pub unsafe fn deserialize_into(
+        &self,
+        value: serde_json::Value,
+        dst: *mut u8,
+    ) -> Option<serde_json::Result<()>> {
+        // SAFETY: Caller ensures dst is valid for writes of this type
+        self.deserialize_fn()
+            .map(|deserialize| unsafe { deserialize(value, dst) })
+    }
+
+    /// Returns the operations registered for the type since this info was
+    /// created.
+    fn registered(&self) -> registry::ComponentVtable {
+        ComponentRegistry::global().vtable(self.type_id.type_id())
+    }
+
+    fn clone_fn(&self) -> Option<CloneFn> {
+        self.clone_fn.or_else(|| self.registered().clone)
+    }
+
+    fn debug_fn(&self) -> Option<DebugFn> {
+        self.debug_fn.or_else(|| self.registered().debug)
+    }
+
+    fn serialize_fn(&self) -> Option<SerializeFn> {
+        self.serialize_fn.or_else(|| self.registered().serialize)
+    }
+
+    fn deserialize_fn(&self) -> Option<DeserializeFn> {
+        self.deserialize_fn
+            .or_else(|| self.registered().deserialize)
+    }
+}
+
+/// `Debug` adapter returned by [`ComponentInfo::debug`].
+struct ComponentDebug<'a> {
+    info: &'a ComponentInfo,
+    ptr: *const u8,
+}
+
+impl fmt::Debug for ComponentDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.info.debug_fn() {
+            // SAFETY: `ComponentInfo::debug` requires ptr to stay valid
+            Some(debug_fn) => unsafe { debug_fn(self.ptr, f) },
+            None => write!(f, "{} {{ .. }}", self.info.type_name),
+        }
+    }
 }
 
 /// A set of component types, used to identify archetypes.
 ///
-/// Component sets are ordered by type ID to ensure consistent archetype
-/// identification regardless of insertion order.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The set is a bitset over registry indices (see [`ComponentTypeId::index`]),
+/// so membership, subset, and equality checks are word-wise bit operations.
+/// A sorted list of the contained types is kept alongside for iteration.
+///
+/// Both are stored inline for up to [`INLINE_TYPES`](Self::INLINE_TYPES)
+/// types with registry indices below 128, so building a set to look up an
+/// archetype does not allocate in the common case.
+#[derive(Debug, Clone)]
 pub struct ComponentSet {
-    /// Sorted list of component type IDs
-    types: Vec<ComponentTypeId>,
+    /// Bit `i` is set when the type with registry index `i` is present.
+    /// Trailing zero words are trimmed so equal sets compare equal.
+    bits: SmallVec<[u64; 2]>,
+
+    /// Component type IDs sorted by registry index
+    types: SmallVec<[ComponentTypeId; ComponentSet::INLINE_TYPES]>,
 }
 
 impl ComponentSet {
+    /// Number of types a set holds without allocating.
+    pub const INLINE_TYPES: usize = 8;
+
     /// Creates a new empty component set.
     pub fn new() -> Self {
-        Self { types: Vec::new() }
+        Self {
+            bits: SmallVec::new(),
+            types: SmallVec::new(),
+        }
     }
 
     /// Creates a component set from a list of component types.
     ///
     /// The types are automatically sorted for consistent identification.
-    pub fn from_types(mut types: Vec<ComponentTypeId>) -> Self {
-        types.sort_unstable();
-        types.dedup();
-        Self { types }
+    pub fn from_types(types: Vec<ComponentTypeId>) -> Self {
+        let mut set = Self::new();
+        for type_id in types {
+            set.insert(type_id);
+        }
+        set
+    }
+
+    /// Splits a registry index into a word position and bit mask.
+    #[inline]
+    fn bit(type_id: ComponentTypeId) -> (usize, u64) {
+        let index = type_id.index() as usize;
+        (index / 64, 1u64 << (index % 64))
     }
 
     /// Adds a component type to the set.
     ///
     /// Returns `true` if the type was added, `false` if it was already present.
     pub fn insert(&mut self, type_id: ComponentTypeId) -> bool {
-        match self.types.binary_search(&type_id) {
-            Ok(_) => false, // Already present
-            Err(pos) => {
-                self.types.insert(pos, type_id);
-                true
-            }
+        if self.contains(type_id) {
+            return false;
         }
+
+        let (word, mask) = Self::bit(type_id);
+        if word >= self.bits.len() {
+            self.bits.resize(word + 1, 0);
+        }
+        self.bits[word] |= mask;
+
+        let pos = self.types.partition_point(|t| *t < type_id);
+        self.types.insert(pos, type_id);
+        true
     }
 
     /// Removes a component type from the set.
     ///
     /// Returns `true` if the type was removed, `false` if it wasn't present.
     pub fn remove(&mut self, type_id: ComponentTypeId) -> bool {
-        match self.types.binary_search(&type_id) {
-            Ok(pos) => {
-                self.types.remove(pos);
-                true
-            }
-            Err(_) => false,
+        if !self.contains(type_id) {
+            return false;
         }
+
+        let (word, mask) = Self::bit(type_id);
+        self.bits[word] &= !mask;
+        while self.bits.last() == Some(&0) {
+            self.bits.pop();
+        }
+
+        if let Ok(pos) = self.types.binary_search(&type_id) {
+            self.types.remove(pos);
+        }
+        true
     }
 
     /// Checks if the set contains a component type.
+    #[inline]
     pub fn contains(&self, type_id: ComponentTypeId) -> bool {
-        self.types.binary_search(&type_id).is_ok()
+        let (word, mask) = Self::bit(type_id);
+        self.bits.get(word).is_some_and(|bits| bits & mask != 0)
+    }
+
+    /// Returns `true` if every type in `self` is also in `other`.
+    pub fn is_subset(&self, other: &ComponentSet) -> bool {
+        self.bits.len() <= other.bits.len()
+            && self.bits.iter().zip(&other.bits).all(|(a, b)| a & !b == 0)
+    }
+
+    /// Returns `true` if `self` and `other` have no types in common.
+    pub fn is_disjoint(&self, other: &ComponentSet) -> bool {
+        self.bits.iter().zip(&other.bits).all(|(a, b)| a & b == 0)
     }
 
     /// Returns the number of component types in the set.
@@ -286,6 +672,20 @@ impl ComponentSet {
     }
 }
 
+impl PartialEq for ComponentSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl Eq for ComponentSet {}
+
+impl Hash for ComponentSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
 impl Default for ComponentSet {
     fn default() -> Self {
         Self::new()
@@ -294,8 +694,11 @@ impl Default for ComponentSet {
 
 impl FromIterator<ComponentTypeId> for ComponentSet {
     fn from_iter<T: IntoIterator<Item = ComponentTypeId>>(iter: T) -> Self {
-        let types: Vec<_> = iter.into_iter().collect();
-        Self::from_types(types)
+        let mut set = Self::new();
+        for type_id in iter {
+            set.insert(type_id);
+        }
+        set
     }
 }
 
@@ -352,6 +755,76 @@ mod tests {
         assert!(info3.needs_drop()); // Vec<u8> needs drop
     }
 
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Label(String);
+    impl Component for Label {}
+
+    #[test]
+    fn operations_registered_later_are_found() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Late(u8);
+        impl Component for Late {}
+
+        let info = ComponentInfo::of::<Late>();
+        assert!(!info.is_cloneable());
+
+        ComponentRegistry::global().register_clone::<Late>();
+        ComponentRegistry::global().register_debug::<Late>();
+        assert!(info.is_cloneable());
+
+        let late = Late(3);
+        let src = &late as *const Late as *const u8;
+        let mut copy = std::mem::MaybeUninit::<Late>::uninit();
+        unsafe {
+            assert!(info.clone_into(src, copy.as_mut_ptr() as *mut u8));
+            assert_eq!(copy.assume_init(), late);
+        }
+        assert_eq!(format!("{:?}", unsafe { info.debug(src) }), "Late(3)");
+    }
+
+    #[test]
+    fn component_info_vtable() {
+        let registry = ComponentRegistry::global();
+        registry.register_clone::<Label>();
+        registry.register_debug::<Label>();
+        registry.register_json::<Label>("component_info_vtable::Label");
+        let info = ComponentInfo::of::<Label>();
+        assert!(info.is_cloneable());
+        assert!(info.is_serializable());
+
+        let label = Label("hero".to_string());
+        let src = &label as *const Label as *const u8;
+        let mut copy = std::mem::MaybeUninit::<Label>::uninit();
+        unsafe {
+            assert!(info.clone_into(src, copy.as_mut_ptr() as *mut u8));
+            assert_eq!(copy.assume_init_ref(), &label);
+            info.drop(copy.as_mut_ptr() as *mut u8);
+        }
+
+        assert_eq!(
+            format!("{:?}", unsafe { info.debug(src) }),
+            "Label(\"hero\")"
+        );
+        let value = unsafe { info.serialize(src) }.unwrap().unwrap();
+        assert_eq!(value, serde_json::json!("hero"));
+
+        let mut restored = std::mem::MaybeUninit::<Label>::uninit();
+        unsafe {
+            info.deserialize_into(value, restored.as_mut_ptr() as *mut u8)
+                .unwrap()
+                .unwrap();
+            assert_eq!(restored.assume_init(), label);
+        }
+
+        let plain = ComponentInfo::of::<TestComponent3>();
+        assert!(!plain.is_cloneable());
+        assert!(!plain.is_serializable());
+        assert!(
+            format!("{:?}", unsafe { plain.debug(std::ptr::null()) })
+                .ends_with("TestComponent3 { .. }")
+        );
+    }
+
     #[test]
     fn component_set_creation() {
         let set = ComponentSet::new();
@@ -435,4 +908,45 @@ mod tests {
         let collected: Vec<_> = set.iter().collect();
         assert_eq!(collected.len(), 2);
     }
+
+    #[test]
+    fn component_set_subset_and_disjoint() {
+        let id1 = ComponentTypeId::of::<TestComponent1>();
+        let id2 = ComponentTypeId::of::<TestComponent2>();
+        let id3 = ComponentTypeId::of::<TestComponent3>();
+
+        let small = ComponentSet::from_types(vec![id1]);
+        let large = ComponentSet::from_types(vec![id1, id2]);
+        let other = ComponentSet::from_types(vec![id3]);
+
+        assert!(small.is_subset(&large));
+        assert!(!large.is_subset(&small));
+        assert!(ComponentSet::new().is_subset(&small));
+        assert!(large.is_disjoint(&other));
+        assert!(!small.is_disjoint(&large));
+    }
+
+    #[test]
+    fn component_set_remove_restores_equality() {
+        let id1 = ComponentTypeId::of::<TestComponent1>();
+        let id3 = ComponentTypeId::of::<TestComponent3>();
+
+        let mut set = ComponentSet::from_types(vec![id1, id3]);
+        set.remove(id3);
+
+        assert_eq!(set, ComponentSet::from_types(vec![id1]));
+    }
+
+    #[test]
+    fn small_component_sets_stay_inline() {
+        let set: ComponentSet = [
+            ComponentTypeId::of::<TestComponent1>(),
+            ComponentTypeId::of::<TestComponent2>(),
+            ComponentTypeId::of::<TestComponent3>(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 3);
+        assert!(!set.types.spilled());
+    }
 }
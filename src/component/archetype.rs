@@ -20,10 +20,13 @@
 //! with the same set of components belong to the same archetype, enabling
 //! cache-friendly iteration and efficient queries.
 
+use super::memory::{Alloc, Global, MemoryError, MemoryTracker};
 use super::storage::ComponentStorage;
 use super::{ComponentInfo, ComponentSet, ComponentTypeId};
 use crate::entity::EntityId;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// A unique identifier for an archetype.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -55,48 +58,72 @@ pub struct EntityLocation {
 ///
 /// When a component is added or removed from an entity, it moves to a different
 /// archetype. These edges cache the target archetype IDs to avoid repeated lookups.
-#[derive(Debug)]
+/// Edges are indexed by component registry index, so following one is a single
+/// array access.
+#[derive(Debug, Default)]
 pub struct ArchetypeEdges {
-    /// Map from added component type to target archetype
-    add_edges: HashMap<ComponentTypeId, ArchetypeId>,
-
-    /// Map from removed component type to target archetype
-    remove_edges: HashMap<ComponentTypeId, ArchetypeId>,
-}
+    /// Target archetype when adding a component, indexed by registry index
+    add_edges: Vec<Option<ArchetypeId>>,
 
-impl Default for ArchetypeEdges {
-    fn default() -> Self {
-        Self::new()
-    }
+    /// Target archetype when removing a component, indexed by registry index
+    remove_edges: Vec<Option<ArchetypeId>>,
 }
 
 impl ArchetypeEdges {
-    /// Creates new empty archetype edges with pre-allocated capacity.
+    /// Creates new empty archetype edges.
     pub fn new() -> Self {
-        Self {
-            add_edges: HashMap::with_capacity(8),
-            remove_edges: HashMap::with_capacity(8),
-        }
+        Self::default()
     }
 
     /// Gets the target archetype when adding a component.
+    #[inline]
     pub fn get_add(&self, component_type: ComponentTypeId) -> Option<ArchetypeId> {
-        self.add_edges.get(&component_type).copied()
+        Self::get(&self.add_edges, component_type)
     }
 
     /// Gets the target archetype when removing a component.
+    #[inline]
     pub fn get_remove(&self, component_type: ComponentTypeId) -> Option<ArchetypeId> {
-        self.remove_edges.get(&component_type).copied()
+        Self::get(&self.remove_edges, component_type)
     }
 
     /// Sets the target archetype when adding a component.
     pub fn set_add(&mut self, component_type: ComponentTypeId, target: ArchetypeId) {
-        self.add_edges.insert(component_type, target);
+        Self::set(&mut self.add_edges, component_type, target);
     }
 
     /// Sets the target archetype when removing a component.
     pub fn set_remove(&mut self, component_type: ComponentTypeId, target: ArchetypeId) {
-        self.remove_edges.insert(component_type, target);
+        Self::set(&mut self.remove_edges, component_type, target);
+    }
+
+    /// Returns the cached targets of add transitions.
+    pub fn add_targets(&self) -> impl Iterator<Item = ArchetypeId> + '_ {
+        self.add_edges.iter().flatten().copied()
+    }
+
+    /// Returns the cached targets of remove transitions.
+    pub fn remove_targets(&self) -> impl Iterator<Item = ArchetypeId> + '_ {
+        self.remove_edges.iter().flatten().copied()
+    }
+
+    fn get(edges: &[Option<ArchetypeId>], component_type: ComponentTypeId) -> Option<ArchetypeId> {
+        edges
+            .get(component_type.index() as usize)
+            .copied()
+            .flatten()
+    }
+
+    fn set(
+        edges: &mut Vec<Option<ArchetypeId>>,
+        component_type: ComponentTypeId,
+        target: ArchetypeId,
+    ) {
+        let index = component_type.index() as usize;
+        if index >= edges.len() {
+            edges.resize(index + 1, None);
+        }
+        edges[index] = Some(target);
     }
 }
 
@@ -111,11 +138,11 @@ pub struct Archetype {
     /// The set of component types in this archetype
     component_types: ComponentSet,
 
-    /// Storage for each component type
-    component_storage: HashMap<ComponentTypeId, ComponentStorage>,
+    /// Storage for each component type, ordered by type so that walks over
+    /// the columns visit them in the same order on every run
+    component_storage: BTreeMap<ComponentTypeId, ComponentStorage>,
 
-    /// Component metadata in the same order as component_types
-    #[allow(dead_code)]
+    /// Component metadata for every type in component_types
     component_info: Vec<ComponentInfo>,
 
     /// List of entities in this archetype
@@ -141,17 +168,61 @@ impl Archetype {
         component_types: ComponentSet,
         component_info: Vec<ComponentInfo>,
     ) -> Self {
-        // Pre-allocate HashMap with capacity to avoid rehashing
-        let mut component_storage = HashMap::with_capacity(component_info.len());
+        Self::new_in(id, component_types, component_info, &Global::shared())
+    }
+
+    /// Creates a new archetype whose component columns allocate from
+    /// `allocator`.
+    pub fn new_in(
+        id: ArchetypeId,
+        component_types: ComponentSet,
+        component_info: Vec<ComponentInfo>,
+        allocator: &Arc<dyn Alloc>,
+    ) -> Self {
+        let mut component_storage = BTreeMap::new();
 
         for info in &component_info {
-            // Pre-allocate component storage with reasonable initial capacity
             component_storage.insert(
                 info.type_id(),
-                ComponentStorage::with_capacity(info.clone(), 16),
+                ComponentStorage::with_capacity_in(info.clone(), 16, Arc::clone(allocator)),
             );
         }
 
+        Self::from_columns(id, component_types, component_info, component_storage)
+    }
+
+    /// Creates a new archetype whose component columns allocate from
+    /// `allocator` and report to `tracker`.
+    ///
+    /// Columns reserve the same initial capacity as [`new_in`](Self::new_in)
+    /// when it fits the tracker's budget. Otherwise they start empty, and
+    /// the budget is checked again when the first row is added.
+    pub fn new_tracked(
+        id: ArchetypeId,
+        component_types: ComponentSet,
+        component_info: Vec<ComponentInfo>,
+        allocator: &Arc<dyn Alloc>,
+        tracker: &Arc<MemoryTracker>,
+    ) -> Self {
+        let mut component_storage = BTreeMap::new();
+
+        for info in &component_info {
+            let mut storage = ComponentStorage::new_in(info.clone(), Arc::clone(allocator));
+            storage.set_tracker(Arc::clone(tracker));
+            // Over budget the column stays empty until a row needs it
+            let _ = storage.try_reserve(16);
+            component_storage.insert(info.type_id(), storage);
+        }
+
+        Self::from_columns(id, component_types, component_info, component_storage)
+    }
+
+    fn from_columns(
+        id: ArchetypeId,
+        component_types: ComponentSet,
+        component_info: Vec<ComponentInfo>,
+        component_storage: BTreeMap<ComponentTypeId, ComponentStorage>,
+    ) -> Self {
         Self {
             id,
             component_types,
@@ -173,6 +244,11 @@ impl Archetype {
         &self.component_types
     }
 
+    /// Returns the metadata for every component type in this archetype.
+    pub fn component_info(&self) -> &[ComponentInfo] {
+        &self.component_info
+    }
+
     /// Returns the number of entities in this archetype.
     pub fn len(&self) -> usize {
         self.entities.len()
@@ -188,6 +264,74 @@ impl Archetype {
         &self.entities
     }
 
+    /// Iterates a read-only query over the entities of this archetype, in
+    /// row order.
+    ///
+    /// Yields nothing if the archetype does not have the components `Q`
+    /// needs. Use [`World::query_archetype`](crate::World::query_archetype)
+    /// for queries that write or fetch `&StableId`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Mass(f32);
+    /// impl Component for Mass {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Mass(1.0)).id();
+    /// world.spawn().with(Mass(2.0)).id();
+    ///
+    /// for archetype in world.archetypes() {
+    ///     let total: f32 = archetype.iter::<&Mass>().map(|mass| mass.0).sum();
+    ///     if !archetype.is_empty() && archetype.has_component::<Mass>() {
+    ///         assert_eq!(total, 3.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn iter<Q>(&self) -> crate::query::iter::ArchetypeIter<'_, Q::Fetch, Q::Filter>
+    where
+        Q: crate::query::Query,
+        Q::Fetch: crate::query::ReadOnlyFetch,
+    {
+        // SAFETY: The fetch is read-only and `self` is borrowed shared
+        unsafe {
+            crate::query::iter::ArchetypeIter::new(self, crate::query::FetchContext::default())
+        }
+    }
+
+    /// Returns the number of bytes allocated for component columns.
+    pub fn allocated_bytes(&self) -> usize {
+        self.component_storage
+            .values()
+            .map(ComponentStorage::allocated_bytes)
+            .sum()
+    }
+
+    /// Reports the allocations of every column to a memory tracker.
+    pub fn set_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        for storage in self.component_storage.values_mut() {
+            storage.set_tracker(Arc::clone(tracker));
+        }
+    }
+
+    /// Reserves room for at least `additional` more rows in every column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a column cannot grow, e.g. because it would exceed
+    /// the memory budget. Columns reserved before the failure keep their
+    /// capacity.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), MemoryError> {
+        for storage in self.component_storage.values_mut() {
+            storage.try_reserve(additional)?;
+        }
+        self.entities.reserve(additional);
+        self.entity_index.reserve(additional);
+        Ok(())
+    }
+
     /// Gets the row index for an entity.
     pub fn get_entity_row(&self, entity: EntityId) -> Option<usize> {
         self.entity_index.get(&entity).copied()
@@ -303,38 +447,13 @@ impl Archetype {
         component: *const u8,
     ) {
         if let Some(storage) = self.component_storage.get_mut(&component_type) {
-            // Ensure storage has capacity for this row
+            // Grow the column up to this row in one step; the new row is
+            // written below
             if storage.len() <= row {
-                // Reserve capacity if needed
-                storage.reserve(row + 1 - storage.len());
-
-                // Fill gaps with uninitialized memory (will be overwritten)
-                // This is more efficient than creating Vec<u8> for each dummy value
-                let component_size = storage.info().size();
-                while storage.len() <= row {
-                    // For zero-sized types, just increment length
-                    if component_size == 0 {
-                        // SAFETY: Zero-sized types don't need actual memory
-                        unsafe {
-                            storage.push(std::ptr::null());
-                        }
-                    } else {
-                        // Allocate uninitialized memory on stack (faster than heap)
-                        let mut uninit = std::mem::MaybeUninit::<[u8; 256]>::uninit();
-                        let dummy_ptr = if component_size <= 256 {
-                            uninit.as_mut_ptr() as *const u8
-                        } else {
-                            // For large components, use heap allocation
-                            let dummy = vec![0u8; component_size];
-                            let ptr = dummy.as_ptr();
-                            std::mem::forget(dummy);
-                            ptr
-                        };
-                        // SAFETY: dummy_ptr points to valid memory
-                        unsafe {
-                            storage.push(dummy_ptr);
-                        }
-                    }
+                // SAFETY: Rows up to `row` are allocated rows whose
+                // components are written by the caller, this one included
+                unsafe {
+                    storage.extend_uninit(row + 1 - storage.len());
                 }
             }
 
@@ -354,9 +473,28 @@ impl Archetype {
     ///
     /// Returns the entity that was moved (if any).
     pub fn remove_entity(&mut self, entity: EntityId) -> Option<EntityId> {
+        let (row, moved_entity) = self.remove_entity_slot(entity)?;
+
+        // Swap-remove components from all storages
+        for storage in self.component_storage.values_mut() {
+            if row < storage.len() {
+                unsafe {
+                    let mut temp = vec![0u8; storage.info().size()];
+                    storage.swap_remove(row, temp.as_mut_ptr());
+                }
+            }
+        }
+
+        moved_entity
+    }
+
+    /// Swap-removes an entity from the entity list, leaving the columns
+    /// alone.
+    ///
+    /// Returns the entity's row and the entity moved into it, if any.
+    fn remove_entity_slot(&mut self, entity: EntityId) -> Option<(usize, Option<EntityId>)> {
         let row = self.entity_index.remove(&entity)?;
 
-        // Swap-remove the entity
         let last_entity = self.entities.pop()?;
         let moved_entity = if row < self.entities.len() {
             self.entities[row] = last_entity;
@@ -366,17 +504,53 @@ impl Archetype {
             None
         };
 
-        // Swap-remove components from all storages
+        Some((row, moved_entity))
+    }
+
+    /// Removes many entities from the archetype at once.
+    ///
+    /// Rows are swap-removed from the back forward, one column at a time,
+    /// so each column is walked once no matter how many entities go.
+    /// Entities not in the archetype are ignored.
+    ///
+    /// Returns the entities that were moved into vacated rows, paired with
+    /// their final row. Each moved entity appears once.
+    pub fn remove_entities(&mut self, entities: &[EntityId]) -> Vec<(EntityId, usize)> {
+        let mut rows: Vec<usize> = entities
+            .iter()
+            .filter_map(|entity| self.entity_index.remove(entity))
+            .collect();
+        // Removing the highest rows first means the row swapped in from the
+        // back is never one that is still waiting to be removed
+        rows.sort_unstable_by(|a, b| b.cmp(a));
+
+        for &row in &rows {
+            let Some(last_entity) = self.entities.pop() else {
+                break;
+            };
+            if row < self.entities.len() {
+                self.entities[row] = last_entity;
+                self.entity_index.insert(last_entity, row);
+            }
+        }
+
         for storage in self.component_storage.values_mut() {
-            if row < storage.len() {
-                unsafe {
-                    let mut temp = vec![0u8; storage.info().size()];
-                    storage.swap_remove(row, temp.as_mut_ptr());
+            let mut temp = vec![0u8; storage.info().size()];
+            for &row in &rows {
+                if row < storage.len() {
+                    unsafe {
+                        storage.swap_remove(row, temp.as_mut_ptr());
+                    }
                 }
             }
         }
 
-        moved_entity
+        // A vacated row below the new length holds whichever entity ended
+        // up there last
+        rows.into_iter()
+            .filter(|&row| row < self.entities.len())
+            .map(|row| (self.entities[row], row))
+            .collect()
     }
 
     /// Moves an entity's components to another archetype.
@@ -395,17 +569,39 @@ impl Archetype {
     ) -> Option<usize> {
         let row = self.get_entity_row(entity)?;
 
-        // Allocate row in target archetype
-        let target_row = target.allocate_row(entity);
+        // Grow every target column before anything is moved: this is the
+        // only step that can fail (e.g. over the memory budget), and failing
+        // here leaves both archetypes as they were
+        let target_row = target.len();
+        for storage in target.component_storage.values_mut() {
+            storage.reserve((target_row + 1).saturating_sub(storage.len()));
+        }
+
+        // Nothing below allocates column memory, so the move runs to
+        // completion once started
+        target.allocate_row(entity);
 
-        // Copy shared components
-        for component_type in self.component_types.iter() {
-            if target.has_component_by_id(component_type) {
-                let src_storage = self.get_storage(component_type)?;
+        // Move shared components straight across, and drop the source's
+        // row from the columns the target doesn't have
+        for (&component_type, src_storage) in self.component_storage.iter_mut() {
+            if row >= src_storage.len() {
+                continue;
+            }
+            if let Some(dst_storage) = target.component_storage.get_mut(&component_type) {
+                // SAFETY: row is valid for this archetype, both columns store
+                // component_type, and the padded rows are written by the
+                // caller
+                unsafe {
+                    if dst_storage.len() < target_row {
+                        dst_storage.extend_uninit(target_row - dst_storage.len());
+                    }
+                    src_storage.transfer(dst_storage, row);
+                }
+            } else {
+                let mut temp = vec![0u8; src_storage.info().size()];
                 // SAFETY: row is valid for this archetype
                 unsafe {
-                    let src_ptr = src_storage.get(row);
-                    target.set_component(target_row, component_type, src_ptr);
+                    src_storage.swap_remove(row, temp.as_mut_ptr());
                 }
             }
         }
@@ -420,8 +616,8 @@ impl Archetype {
             }
         }
 
-        // Remove entity from source archetype
-        self.remove_entity(entity);
+        // Its columns were emptied above
+        self.remove_entity_slot(entity);
 
         Some(target_row)
     }
@@ -436,6 +632,30 @@ impl Archetype {
         &mut self.edges
     }
 
+    /// Shrinks entity and column storage as close as possible to the number
+    /// of entities.
+    ///
+    /// Returns the approximate number of bytes released.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        let mut released = 0;
+
+        for storage in self.component_storage.values_mut() {
+            released += storage.shrink_to_fit();
+        }
+
+        let entities_capacity = self.entities.capacity();
+        self.entities.shrink_to_fit();
+        released +=
+            (entities_capacity - self.entities.capacity()) * std::mem::size_of::<EntityId>();
+
+        let index_capacity = self.entity_index.capacity();
+        self.entity_index.shrink_to_fit();
+        released += index_capacity.saturating_sub(self.entity_index.capacity())
+            * std::mem::size_of::<(EntityId, usize)>();
+
+        released
+    }
+
     /// Clears all entities from the archetype.
     pub fn clear(&mut self) {
         self.entities.clear();
@@ -444,6 +664,97 @@ impl Archetype {
             storage.clear();
         }
     }
+
+    /// Returns raw column pointers for the requested component types.
+    ///
+    /// Columns are returned in the order of `component_types`. Returns `None`
+    /// if the archetype lacks any of the requested types.
+    pub fn column_slices(
+        &mut self,
+        component_types: &[ComponentTypeId],
+    ) -> Option<ColumnSlices<'_>> {
+        let mut columns = Vec::with_capacity(component_types.len());
+        for &component_type in component_types {
+            let storage = self.component_storage.get_mut(&component_type)?;
+            columns.push(RawColumn {
+                component_type,
+                ptr: storage.as_mut_ptr(),
+                size: storage.info().size(),
+                alignment: storage.info().alignment(),
+                column_alignment: storage.alignment(),
+            });
+        }
+
+        Some(ColumnSlices {
+            archetype_id: self.id,
+            entities: &self.entities,
+            columns,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// A type-erased pointer to one component column.
+///
+/// Element `i` lives at `ptr + i * size` and belongs to the entity at row
+/// `i` of the owning [`ColumnSlices`].
+#[derive(Debug, Clone, Copy)]
+pub struct RawColumn {
+    /// Component type stored in the column
+    pub component_type: ComponentTypeId,
+
+    /// Pointer to the first element
+    pub ptr: *mut u8,
+
+    /// Size of one element in bytes
+    pub size: usize,
+
+    /// Alignment of the elements in bytes
+    pub alignment: usize,
+
+    /// Guaranteed alignment of `ptr` in bytes, at least `alignment`
+    pub column_alignment: usize,
+}
+
+/// Raw component columns of one archetype.
+///
+/// Intended for FFI and scripting bindings that process a whole archetype per
+/// call instead of crossing the language boundary once per entity. The
+/// pointers stay valid for the lifetime `'a`, during which the world is
+/// mutably borrowed and cannot move or resize the columns.
+#[derive(Debug)]
+pub struct ColumnSlices<'a> {
+    archetype_id: ArchetypeId,
+    entities: &'a [EntityId],
+    columns: Vec<RawColumn>,
+    _marker: PhantomData<&'a mut Archetype>,
+}
+
+impl<'a> ColumnSlices<'a> {
+    /// Returns the archetype the columns belong to.
+    pub fn archetype_id(&self) -> ArchetypeId {
+        self.archetype_id
+    }
+
+    /// Returns the number of rows in every column.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if the archetype has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the entity stored at each row.
+    pub fn entities(&self) -> &'a [EntityId] {
+        self.entities
+    }
+
+    /// Returns the columns, in the order they were requested.
+    pub fn columns(&self) -> &[RawColumn] {
+        &self.columns
+    }
 }
 
 /// Manages all archetypes in the world.
@@ -454,18 +765,32 @@ pub struct ArchetypeManager {
     /// Map from component set to archetype ID
     archetype_index: HashMap<ComponentSet, ArchetypeId>,
 
-    /// Map from entity to its location (using Vec for O(1) access by entity index)
-    /// This is more cache-friendly than HashMap for entity lookups
-    entity_locations: Vec<Option<EntityLocation>>,
+    /// Entity locations indexed by entity index. The stored `EntityId` is
+    /// compared on lookup so stale IDs sharing a recycled index miss.
+    entity_locations: Vec<Option<(EntityId, EntityLocation)>>,
+
+    /// Tracker shared by the columns of every archetype
+    memory: Arc<MemoryTracker>,
+
+    /// Allocator shared by the columns of every archetype
+    allocator: Arc<dyn Alloc>,
 }
 
 impl ArchetypeManager {
     /// Creates a new archetype manager.
     pub fn new() -> Self {
+        Self::with_allocator(Global::shared())
+    }
+
+    /// Creates a new archetype manager whose component columns allocate
+    /// from `allocator`.
+    pub fn with_allocator(allocator: Arc<dyn Alloc>) -> Self {
         let mut manager = Self {
             archetypes: Vec::new(),
             archetype_index: HashMap::new(),
             entity_locations: Vec::with_capacity(1024), // Pre-allocate for common case
+            memory: Arc::new(MemoryTracker::new()),
+            allocator,
         };
 
         // Create the empty archetype (archetype 0)
@@ -489,12 +814,94 @@ impl ArchetypeManager {
         }
 
         let id = ArchetypeId::new(self.archetypes.len());
-        let archetype = Archetype::new(id, component_types.clone(), component_info);
+        let archetype = Archetype::new_tracked(
+            id,
+            component_types.clone(),
+            component_info,
+            &self.allocator,
+            &self.memory,
+        );
         self.archetypes.push(archetype);
         self.archetype_index.insert(component_types, id);
         id
     }
 
+    /// Returns the archetype reached by adding a component type to `source`.
+    ///
+    /// The source archetype's add edge is consulted first; on a miss the target
+    /// is looked up or created and both the add edge and the target's reverse
+    /// remove edge are recorded, so later transitions are a single array index.
+    /// Returns `source` itself if it already contains the type.
+    pub fn get_or_create_add_target(
+        &mut self,
+        source: ArchetypeId,
+        info: &ComponentInfo,
+    ) -> ArchetypeId {
+        let component_type = info.type_id();
+        let source_archetype = &self.archetypes[source.index()];
+        if let Some(target) = source_archetype.edges.get_add(component_type) {
+            return target;
+        }
+        if source_archetype.has_component_by_id(component_type) {
+            return source;
+        }
+
+        let mut types = source_archetype.component_types.clone();
+        types.insert(component_type);
+        let mut infos = source_archetype.component_info.clone();
+        infos.push(info.clone());
+
+        let target = self.get_or_create_archetype(types, infos);
+        self.archetypes[source.index()]
+            .edges
+            .set_add(component_type, target);
+        self.archetypes[target.index()]
+            .edges
+            .set_remove(component_type, source);
+        target
+    }
+
+    /// Returns the archetype reached by removing a component type from `source`.
+    ///
+    /// Mirrors [`get_or_create_add_target`](Self::get_or_create_add_target)
+    /// using remove edges. Returns `source` itself if it lacks the type.
+    pub fn get_or_create_remove_target(
+        &mut self,
+        source: ArchetypeId,
+        component_type: ComponentTypeId,
+    ) -> ArchetypeId {
+        let source_archetype = &self.archetypes[source.index()];
+        if let Some(target) = source_archetype.edges.get_remove(component_type) {
+            return target;
+        }
+        if !source_archetype.has_component_by_id(component_type) {
+            return source;
+        }
+
+        let mut types = source_archetype.component_types.clone();
+        types.remove(component_type);
+        let infos = source_archetype
+            .component_info
+            .iter()
+            .filter(|info| info.type_id() != component_type)
+            .cloned()
+            .collect();
+
+        let target = self.get_or_create_archetype(types, infos);
+        self.archetypes[source.index()]
+            .edges
+            .set_remove(component_type, target);
+        self.archetypes[target.index()]
+            .edges
+            .set_add(component_type, source);
+        target
+    }
+
+    /// Returns the tracker the columns of every archetype report to.
+    pub fn memory(&self) -> &Arc<MemoryTracker> {
+        &self.memory
+    }
+
     /// Gets an archetype by ID.
     pub fn get_archetype(&self, id: ArchetypeId) -> Option<&Archetype> {
         self.archetypes.get(id.index())
@@ -510,10 +917,17 @@ impl ArchetypeManager {
         self.archetypes.iter()
     }
 
+    /// Returns a mutable iterator over all archetypes.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Archetype> {
+        self.archetypes.iter_mut()
+    }
+
     /// Gets the location of an entity.
     pub fn get_entity_location(&self, entity: EntityId) -> Option<EntityLocation> {
-        let index = entity.index() as usize;
-        self.entity_locations.get(index).and_then(|loc| *loc)
+        match self.entity_locations.get(entity.index() as usize) {
+            Some(&Some((owner, location))) if owner == entity => Some(location),
+            _ => None,
+        }
     }
 
     /// Sets the location of an entity.
@@ -523,16 +937,18 @@ impl ArchetypeManager {
         if index >= self.entity_locations.len() {
             self.entity_locations.resize(index + 1, None);
         }
-        self.entity_locations[index] = Some(location);
+        self.entity_locations[index] = Some((entity, location));
     }
 
     /// Removes an entity's location.
     pub fn remove_entity_location(&mut self, entity: EntityId) -> Option<EntityLocation> {
-        let index = entity.index() as usize;
-        if index < self.entity_locations.len() {
-            self.entity_locations[index].take()
-        } else {
-            None
+        let slot = self.entity_locations.get_mut(entity.index() as usize)?;
+        match *slot {
+            Some((owner, location)) if owner == entity => {
+                *slot = None;
+                Some(location)
+            }
+            _ => None,
         }
     }
 
@@ -546,6 +962,71 @@ impl ArchetypeManager {
         self.archetypes.is_empty()
     }
 
+    /// Removes stale rows, repairs entity locations, and releases excess memory.
+    ///
+    /// A row is stale if its entity is no longer alive according to `is_alive`,
+    /// or if the entity's recorded location points at a different archetype.
+    /// Every remaining entity's location is rewritten to its actual row.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of `(rows_removed, bytes_released)`.
+    pub fn compact(&mut self, is_alive: impl Fn(EntityId) -> bool) -> (usize, usize) {
+        let mut rows_removed = 0;
+        let mut released = 0;
+
+        for archetype_index in 0..self.archetypes.len() {
+            let archetype_id = ArchetypeId::new(archetype_index);
+
+            let stale: Vec<EntityId> = self.archetypes[archetype_index]
+                .entities()
+                .iter()
+                .copied()
+                .filter(|&entity| {
+                    !is_alive(entity)
+                        || self
+                            .get_entity_location(entity)
+                            .is_some_and(|location| location.archetype_id != archetype_id)
+                })
+                .collect();
+
+            let archetype = &mut self.archetypes[archetype_index];
+            for entity in stale {
+                archetype.remove_entity(entity);
+                rows_removed += 1;
+            }
+            released += archetype.shrink_to_fit();
+
+            let entities = self.archetypes[archetype_index].entities().to_vec();
+            for (row, entity) in entities.into_iter().enumerate() {
+                self.set_entity_location(entity, EntityLocation { archetype_id, row });
+            }
+        }
+
+        // Trailing slots without a location can be dropped entirely
+        while matches!(self.entity_locations.last(), Some(None)) {
+            self.entity_locations.pop();
+        }
+        let locations_capacity = self.entity_locations.capacity();
+        self.entity_locations.shrink_to_fit();
+        released += (locations_capacity - self.entity_locations.capacity())
+            * std::mem::size_of::<Option<(EntityId, EntityLocation)>>();
+
+        (rows_removed, released)
+    }
+
+    /// Removes all entities while keeping every registered archetype.
+    ///
+    /// Archetype IDs, component metadata, edges, and allocated column capacity
+    /// are retained so a cleared world can be refilled without re-creating its
+    /// archetypes. All entity locations are forgotten.
+    pub fn clear(&mut self) {
+        for archetype in &mut self.archetypes {
+            archetype.clear();
+        }
+        self.entity_locations.clear();
+    }
+
     /// Moves an entity from one archetype to another with additional component data.
     ///
     /// This is a helper method that handles the borrow checker complexity of
@@ -625,6 +1106,30 @@ mod tests {
     }
     impl Component for Velocity {}
 
+    #[test]
+    fn tracked_archetypes_reserve_within_budget() {
+        let info = vec![ComponentInfo::of::<Position>()];
+        let types = ComponentSet::from_types(vec![info[0].type_id()]);
+        let allocator = Global::shared();
+        let tracker = Arc::new(MemoryTracker::new());
+
+        let roomy = Archetype::new_tracked(
+            ArchetypeId::new(0),
+            types.clone(),
+            info.clone(),
+            &allocator,
+            &tracker,
+        );
+        let reserved = 16 * std::mem::size_of::<Position>();
+        assert_eq!(roomy.allocated_bytes(), reserved);
+        assert_eq!(tracker.allocated(), reserved);
+
+        tracker.set_budget(Some(reserved + 8));
+        let tight = Archetype::new_tracked(ArchetypeId::new(1), types, info, &allocator, &tracker);
+        assert_eq!(tight.allocated_bytes(), 0);
+        assert_eq!(tracker.allocated(), reserved);
+    }
+
     #[test]
     fn archetype_creation() {
         let component_types = ComponentSet::new();
@@ -673,6 +1178,23 @@ mod tests {
         assert_eq!(manager.get_entity_location(entity), None);
     }
 
+    #[test]
+    fn entity_location_rejects_stale_generation() {
+        let mut manager = ArchetypeManager::new();
+        let entity = EntityId::new(3, 1);
+        let location = EntityLocation {
+            archetype_id: ArchetypeId::new(0),
+            row: 0,
+        };
+
+        manager.set_entity_location(entity, location);
+
+        let stale = EntityId::new(3, 2);
+        assert_eq!(manager.get_entity_location(stale), None);
+        assert_eq!(manager.remove_entity_location(stale), None);
+        assert_eq!(manager.get_entity_location(entity), Some(location));
+    }
+
     #[test]
     fn archetype_edges() {
         let mut edges = ArchetypeEdges::new();
@@ -685,4 +1207,100 @@ mod tests {
         edges.set_remove(component_type, target);
         assert_eq!(edges.get_remove(component_type), Some(target));
     }
+
+    #[test]
+    fn archetype_transition_edges_are_cached() {
+        let mut manager = ArchetypeManager::new();
+        let empty = ArchetypeId::new(0);
+        let position = ComponentInfo::of::<Position>();
+        let velocity = ComponentInfo::of::<Velocity>();
+
+        let with_position = manager.get_or_create_add_target(empty, &position);
+        let both = manager.get_or_create_add_target(with_position, &velocity);
+        assert_eq!(manager.len(), 3);
+
+        let empty_archetype = manager.get_archetype(empty).unwrap();
+        assert_eq!(
+            empty_archetype.edges().get_add(position.type_id()),
+            Some(with_position)
+        );
+        let both_archetype = manager.get_archetype(both).unwrap();
+        assert_eq!(
+            both_archetype.edges().get_remove(velocity.type_id()),
+            Some(with_position)
+        );
+
+        // Removing Position from {Position, Velocity} creates {Velocity}
+        let with_velocity = manager.get_or_create_remove_target(both, position.type_id());
+        assert_eq!(manager.len(), 4);
+        assert!(
+            manager
+                .get_archetype(with_velocity)
+                .unwrap()
+                .has_component::<Velocity>()
+        );
+
+        // Repeated transitions reuse the cached targets
+        assert_eq!(
+            manager.get_or_create_add_target(empty, &position),
+            with_position
+        );
+        assert_eq!(
+            manager.get_or_create_add_target(with_velocity, &position),
+            both
+        );
+        assert_eq!(manager.get_or_create_add_target(both, &position), both);
+        assert_eq!(manager.len(), 4);
+    }
+
+    #[test]
+    fn archetype_manager_compact_removes_stale_rows() {
+        let mut manager = ArchetypeManager::new();
+        let empty = ArchetypeId::new(0);
+        let alive = EntityId::new(0, 1);
+        let dead = EntityId::new(1, 1);
+
+        let archetype = manager.get_archetype_mut(empty).unwrap();
+        archetype.allocate_row(dead);
+        archetype.allocate_row(alive);
+
+        let (rows_removed, _) = manager.compact(|entity| entity == alive);
+
+        assert_eq!(rows_removed, 1);
+        assert_eq!(manager.get_archetype(empty).unwrap().entities(), &[alive]);
+        assert_eq!(
+            manager.get_entity_location(alive),
+            Some(EntityLocation {
+                archetype_id: empty,
+                row: 0
+            })
+        );
+    }
+
+    #[test]
+    fn archetype_manager_clear_retains_archetypes() {
+        let mut manager = ArchetypeManager::new();
+
+        let mut types = ComponentSet::new();
+        types.insert(ComponentTypeId::of::<Position>());
+        let id =
+            manager.get_or_create_archetype(types.clone(), vec![ComponentInfo::of::<Position>()]);
+
+        let entity = EntityId::new(0, 1);
+        let row = manager.get_archetype_mut(id).unwrap().allocate_row(entity);
+        manager.set_entity_location(
+            entity,
+            EntityLocation {
+                archetype_id: id,
+                row,
+            },
+        );
+
+        manager.clear();
+
+        assert_eq!(manager.len(), 2);
+        assert!(manager.get_archetype(id).unwrap().is_empty());
+        assert_eq!(manager.get_entity_location(entity), None);
+        assert_eq!(manager.get_or_create_archetype(types, Vec::new()), id);
+    }
 }
@@ -0,0 +1,275 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Secondary indexes over component values.
+//!
+//! A [`ComponentIndex`] maps a key extracted from a component to the entities
+//! whose component produces that key. It turns questions such as "which
+//! entities are in chunk (3, 4)?" into a single hash lookup instead of a full
+//! query.
+//!
+//! Indexes are registered on the [`World`](crate::World) with an extractor
+//! function. The world marks an entity as changed whenever it inserts,
+//! removes or hands out mutable access to a component, and the index
+//! re-extracts keys for changed entities the next time it is read.
+//! Mutations made through query iterators are not observed; call
+//! [`World::rebuild_index`](crate::World::rebuild_index) after such bulk
+//! updates.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::prelude::*;
+//!
+//! #[derive(Debug)]
+//! struct Position { x: f32, y: f32 }
+//! impl Component for Position {}
+//!
+//! fn chunk(pos: &Position) -> (i32, i32) {
+//!     ((pos.x / 16.0).floor() as i32, (pos.y / 16.0).floor() as i32)
+//! }
+//!
+//! let mut world = World::new();
+//! world.register_index(chunk);
+//!
+//! let a = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
+//! let b = world.spawn().with(Position { x: 20.0, y: 2.0 }).id();
+//!
+//! let chunks = world.index::<Position, (i32, i32)>();
+//! assert_eq!(chunks.get(&(0, 0)), &[a]);
+//! assert_eq!(chunks.get(&(1, 0)), &[b]);
+//! ```
+
+use super::Component;
+use super::archetype::ArchetypeManager;
+use crate::entity::EntityId;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A multimap from extracted component keys to entities.
+pub struct ComponentIndex<T, K> {
+    /// Computes the key for a component value
+    extractor: fn(&T) -> K,
+
+    /// Entities grouped by key
+    buckets: HashMap<K, Vec<EntityId>>,
+
+    /// Current key of every indexed entity
+    keys: HashMap<EntityId, K>,
+
+    /// Entities whose key must be recomputed
+    dirty: HashSet<EntityId>,
+}
+
+impl<T, K> ComponentIndex<T, K>
+where
+    T: Component,
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+{
+    /// Creates an empty index using `extractor` to compute keys.
+    pub(crate) fn new(extractor: fn(&T) -> K) -> Self {
+        Self {
+            extractor,
+            buckets: HashMap::new(),
+            keys: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Returns the entities whose component currently maps to `key`.
+    pub fn get(&self, key: &K) -> &[EntityId] {
+        self.buckets.get(key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the key an entity is indexed under, if any.
+    pub fn key_of(&self, entity: EntityId) -> Option<&K> {
+        self.keys.get(&entity)
+    }
+
+    /// Returns an iterator over all keys with at least one entity.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.buckets.keys()
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no entities are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Re-indexes every entity that has the component.
+    pub(crate) fn rebuild(&mut self, archetypes: &ArchetypeManager) {
+        self.buckets.clear();
+        self.keys.clear();
+        self.dirty.clear();
+
+        for archetype in archetypes.iter() {
+            if !archetype.has_component::<T>() {
+                continue;
+            }
+            for &entity in archetype.entities() {
+                // SAFETY: The entity is stored in this archetype, which has T
+                if let Some(component) = unsafe { archetype.get_component::<T>(entity) } {
+                    let key = (self.extractor)(component);
+                    self.insert_key(entity, key);
+                }
+            }
+        }
+    }
+
+    fn refresh_entity(&mut self, archetypes: &ArchetypeManager, entity: EntityId) {
+        let key = archetypes
+            .get_entity_location(entity)
+            .and_then(|location| archetypes.get_archetype(location.archetype_id))
+            // SAFETY: get_component checks the entity's row and the column type
+            .and_then(|archetype| unsafe { archetype.get_component::<T>(entity) })
+            .map(self.extractor);
+
+        if self.keys.get(&entity) == key.as_ref() {
+            return;
+        }
+        self.remove_key(entity);
+        if let Some(key) = key {
+            self.insert_key(entity, key);
+        }
+    }
+
+    fn insert_key(&mut self, entity: EntityId, key: K) {
+        self.buckets.entry(key.clone()).or_default().push(entity);
+        self.keys.insert(entity, key);
+    }
+
+    fn remove_key(&mut self, entity: EntityId) {
+        let Some(key) = self.keys.remove(&entity) else {
+            return;
+        };
+        if let Some(bucket) = self.buckets.get_mut(&key) {
+            bucket.retain(|&e| e != entity);
+            if bucket.is_empty() {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+}
+
+/// Type-erased interface the world uses to notify indexes of changes.
+pub(crate) trait AnyComponentIndex: Send + Sync {
+    /// Records that an entity's components may have changed.
+    fn mark_dirty(&mut self, entity: EntityId);
+
+    /// Recomputes keys for all entities marked dirty.
+    fn refresh(&mut self, archetypes: &ArchetypeManager);
+
+    /// Removes every entry.
+    fn clear(&mut self);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T, K> AnyComponentIndex for ComponentIndex<T, K>
+where
+    T: Component,
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+{
+    fn mark_dirty(&mut self, entity: EntityId) {
+        self.dirty.insert(entity);
+    }
+
+    fn refresh(&mut self, archetypes: &ArchetypeManager) {
+        for entity in std::mem::take(&mut self.dirty) {
+            self.refresh_entity(archetypes, entity);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.buckets.clear();
+        self.keys.clear();
+        self.dirty.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::World;
+    use crate::component::Component;
+
+    #[derive(Debug)]
+    struct Cell(i32);
+    impl Component for Cell {}
+
+    fn cell(c: &Cell) -> i32 {
+        c.0
+    }
+
+    #[test]
+    fn index_tracks_world_changes() {
+        let mut world = World::new();
+        let a = world.spawn().with(Cell(1)).id();
+        world.register_index(cell);
+        let b = world.spawn_empty();
+        world.insert(b, Cell(1));
+
+        let index = world.index::<Cell, i32>();
+        assert_eq!(index.len(), 2);
+        assert!(index.get(&1).contains(&a) && index.get(&1).contains(&b));
+
+        world.get_mut::<Cell>(a).unwrap().0 = 2;
+        world.remove::<Cell>(b);
+        let index = world.index::<Cell, i32>();
+        assert_eq!(index.get(&1), &[]);
+        assert_eq!(index.get(&2), &[a]);
+        assert_eq!(index.key_of(b), None);
+
+        world.despawn(a);
+        assert!(world.index::<Cell, i32>().is_empty());
+    }
+
+    #[test]
+    fn rebuild_picks_up_query_mutations() {
+        let mut world = World::new();
+        world.register_index(cell);
+        let a = world.spawn().with(Cell(0)).id();
+        assert_eq!(world.index::<Cell, i32>().get(&0), &[a]);
+
+        for c in world.query::<&mut Cell>() {
+            c.0 = 7;
+        }
+        world.rebuild_index::<Cell, i32>();
+        assert_eq!(world.index::<Cell, i32>().get(&7), &[a]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no index registered")]
+    fn unregistered_index_panics() {
+        let mut world = World::new();
+        world.index::<Cell, u8>();
+    }
+}
@@ -0,0 +1,387 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Memory accounting for component storage.
+//!
+//! Every [`World`](crate::World) owns a [`MemoryTracker`] that its component
+//! columns report their allocations to. The tracker keeps a running total
+//! per component type and can enforce a hard budget: a column that would
+//! grow past the budget fails with [`MemoryError::BudgetExceeded`] instead
+//! of allocating.
+//!
+//! Inserting a component never goes over the budget: the entity is left
+//! unchanged and [`World::insert`](crate::World::insert) returns `false`,
+//! while [`World::try_insert`](crate::World::try_insert) and
+//! [`World::try_insert_batch`](crate::World::try_insert_batch) return
+//! [`InsertError::OverBudget`](crate::component::InsertError::OverBudget).
+//! Operations that cannot report errors, such as spawning and removing
+//! components, panic when they hit the budget. Reserve ahead of time with
+//! [`World::try_reserve`](crate::World::try_reserve) or spawn with
+//! [`World::try_spawn_bundle`](crate::World::try_spawn_bundle) to handle it
+//! as an error.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::prelude::*;
+//! use pecs::component::memory::MemoryError;
+//!
+//! struct Chunk([u8; 1024]);
+//! impl Component for Chunk {}
+//!
+//! let mut world = World::new();
+//! world.memory().set_budget(Some(64 * 1024));
+//!
+//! assert!(world.try_reserve::<Chunk>(16).is_ok());
+//! assert!(matches!(
+//!     world.try_reserve::<Chunk>(1024),
+//!     Err(MemoryError::BudgetExceeded { .. })
+//! ));
+//! ```
+//!
+//! # Custom Allocators
+//!
+//! Component columns get their memory from an [`Alloc`] implementation,
+//! [`Global`] by default. Pass another one to
+//! [`World::with_allocator`](crate::World::with_allocator) to place a
+//! world's component data in an arena or a tracked heap. Entity tables and
+//! indexes still use the global allocator.
+
+use super::ComponentTypeId;
+use std::alloc::Layout;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Sentinel budget value meaning "no budget".
+const UNLIMITED: usize = usize::MAX;
+
+/// An error from a component storage allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryError {
+    /// The allocation would take the tracked total past the budget.
+    BudgetExceeded {
+        /// Additional bytes the allocation needed
+        requested: usize,
+        /// Bytes allocated before the request
+        allocated: usize,
+        /// The budget in bytes
+        budget: usize,
+    },
+    /// The allocator could not provide the memory.
+    AllocationFailed {
+        /// Size of the failed allocation in bytes
+        bytes: usize,
+    },
+}
+
+impl fmt::Display for MemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryError::BudgetExceeded {
+                requested,
+                allocated,
+                budget,
+            } => write!(
+                f,
+                "Memory budget exceeded: {} bytes requested with {} of {} bytes allocated",
+                requested, allocated, budget
+            ),
+            MemoryError::AllocationFailed { bytes } => {
+                write!(f, "Failed to allocate {} bytes", bytes)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemoryError {}
+
+/// Tracks the bytes allocated by component columns.
+///
+/// Shared between the columns of one world. Counts column allocations
+/// only; entity lists and lookup tables are small by comparison and are
+/// not tracked.
+#[derive(Debug)]
+pub struct MemoryTracker {
+    /// Total bytes allocated
+    allocated: AtomicUsize,
+
+    /// Budget in bytes, or `UNLIMITED`
+    budget: AtomicUsize,
+
+    /// Bytes allocated per component type
+    by_type: Mutex<HashMap<ComponentTypeId, usize>>,
+}
+
+impl MemoryTracker {
+    /// Creates a tracker without a budget.
+    pub fn new() -> Self {
+        Self {
+            allocated: AtomicUsize::new(0),
+            budget: AtomicUsize::new(UNLIMITED),
+            by_type: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the total bytes allocated by tracked columns.
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    /// Returns the bytes allocated for one component type.
+    pub fn allocated_for(&self, component_type: ComponentTypeId) -> usize {
+        self.by_type()
+            .get(&component_type)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the bytes allocated per component type.
+    pub fn allocated_by_type(&self) -> HashMap<ComponentTypeId, usize> {
+        self.by_type().clone()
+    }
+
+    /// Returns the budget in bytes, if one is set.
+    pub fn budget(&self) -> Option<usize> {
+        match self.budget.load(Ordering::Relaxed) {
+            UNLIMITED => None,
+            budget => Some(budget),
+        }
+    }
+
+    /// Sets or clears the budget.
+    ///
+    /// Lowering the budget below the current total does not free anything;
+    /// it only makes further growth fail.
+    pub fn set_budget(&self, budget: Option<usize>) {
+        self.budget
+            .store(budget.unwrap_or(UNLIMITED), Ordering::Relaxed);
+    }
+
+    /// Records a column resize from `old` to `new` bytes, failing if growth
+    /// would exceed the budget.
+    pub(crate) fn try_resize(
+        &self,
+        component_type: ComponentTypeId,
+        old: usize,
+        new: usize,
+    ) -> Result<(), MemoryError> {
+        if new > old {
+            let requested = new - old;
+            let budget = self.budget.load(Ordering::Relaxed);
+            self.allocated
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |allocated| {
+                    allocated
+                        .checked_add(requested)
+                        .filter(|&total| budget == UNLIMITED || total <= budget)
+                })
+                .map_err(|allocated| MemoryError::BudgetExceeded {
+                    requested,
+                    allocated,
+                    budget,
+                })?;
+        } else {
+            self.allocated.fetch_sub(old - new, Ordering::Relaxed);
+        }
+
+        let mut by_type = self.by_type();
+        let bytes = by_type.entry(component_type).or_default();
+        *bytes = *bytes + new - old;
+        if *bytes == 0 {
+            by_type.remove(&component_type);
+        }
+        Ok(())
+    }
+
+    /// Records bytes that were allocated before the column was tracked,
+    /// bypassing the budget.
+    pub(crate) fn adopt(&self, component_type: ComponentTypeId, bytes: usize) {
+        if bytes == 0 {
+            return;
+        }
+        self.allocated.fetch_add(bytes, Ordering::Relaxed);
+        *self.by_type().entry(component_type).or_default() += bytes;
+    }
+
+    fn by_type(&self) -> std::sync::MutexGuard<'_, HashMap<ComponentTypeId, usize>> {
+        self.by_type.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for MemoryTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An allocator for component columns.
+///
+/// Columns only request blocks with a non-zero size, aligned to the
+/// column's [alignment](crate::component::ComponentInfo::column_alignment).
+///
+/// # Safety
+///
+/// `allocate` and `reallocate` must return either null or a pointer to a
+/// block that fits the requested layout and stays valid until it is passed
+/// to `deallocate` or `reallocate`. Blocks are freed on whichever thread
+/// drops the world, so the allocator must be usable from any thread.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::component::memory::{Alloc, Global};
+/// use std::alloc::Layout;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// #[derive(Default)]
+/// struct Counting(AtomicUsize);
+///
+/// unsafe impl Alloc for Counting {
+///     fn allocate(&self, layout: Layout) -> *mut u8 {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         Global.allocate(layout)
+///     }
+///
+///     unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+///         unsafe { Global.deallocate(ptr, layout) }
+///     }
+/// }
+/// ```
+pub unsafe trait Alloc: Send + Sync {
+    /// Allocates a block for `layout`, returning null on failure.
+    fn allocate(&self, layout: Layout) -> *mut u8;
+
+    /// Frees a block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this allocator for `layout`.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+
+    /// Resizes a block to `new_size` bytes with the same alignment,
+    /// returning null on failure and leaving the old block intact.
+    ///
+    /// The default allocates a new block, copies and frees the old one.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this allocator for `layout`, and
+    /// `new_size` must be non-zero and valid for `layout`'s alignment.
+    unsafe fn reallocate(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller guarantees new_size is valid for the alignment
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+        let new_ptr = self.allocate(new_layout);
+        if !new_ptr.is_null() {
+            // SAFETY: both blocks are valid for the smaller of the two sizes
+            unsafe {
+                std::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                self.deallocate(ptr, layout);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// The process-wide global allocator, used by columns by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Global;
+
+impl Global {
+    /// Returns a shared handle to the global allocator.
+    pub fn shared() -> Arc<dyn Alloc> {
+        static SHARED: LazyLock<Arc<dyn Alloc>> = LazyLock::new(|| Arc::new(Global));
+        Arc::clone(&SHARED)
+    }
+}
+
+// SAFETY: forwards to the global allocator
+unsafe impl Alloc for Global {
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: columns never request zero-sized blocks
+        unsafe { std::alloc::alloc(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded from the caller
+        unsafe { std::alloc::dealloc(ptr, layout) }
+    }
+
+    unsafe fn reallocate(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: forwarded from the caller
+        unsafe { std::alloc::realloc(ptr, layout, new_size) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+
+    struct A;
+    impl Component for A {}
+
+    #[test]
+    fn budget_limits_growth_but_not_shrinking() {
+        let tracker = MemoryTracker::new();
+        let a = ComponentTypeId::of::<A>();
+        tracker.set_budget(Some(100));
+
+        assert!(tracker.try_resize(a, 0, 60).is_ok());
+        assert_eq!(
+            tracker.try_resize(a, 60, 120),
+            Err(MemoryError::BudgetExceeded {
+                requested: 60,
+                allocated: 60,
+                budget: 100
+            })
+        );
+        assert_eq!(tracker.allocated(), 60);
+
+        tracker.adopt(a, 80);
+        assert_eq!(tracker.allocated_for(a), 140);
+        assert!(tracker.try_resize(a, 80, 0).is_ok());
+        assert_eq!(tracker.allocated(), 60);
+
+        tracker.set_budget(None);
+        assert!(tracker.try_resize(a, 60, 1000).is_ok());
+        assert_eq!(tracker.allocated_by_type()[&a], 1000);
+    }
+
+    #[test]
+    fn default_reallocate_preserves_contents() {
+        struct Copying;
+        // SAFETY: forwards to the global allocator
+        unsafe impl Alloc for Copying {
+            fn allocate(&self, layout: Layout) -> *mut u8 {
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let layout = Layout::from_size_align(4, 4).unwrap();
+        unsafe {
+            let ptr = Copying.allocate(layout);
+            ptr.cast::<u32>().write(0xDEAD_BEEF);
+            let ptr = Copying.reallocate(ptr, layout, 16);
+            assert_eq!(ptr.cast::<u32>().read(), 0xDEAD_BEEF);
+            Copying.deallocate(ptr, Layout::from_size_align(16, 4).unwrap());
+        }
+    }
+}
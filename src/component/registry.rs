@@ -0,0 +1,1123 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Process-wide component type registry.
+//!
+//! The registry assigns every component type a dense, stable index the first
+//! time the type is seen. These indices let [`ComponentSet`](super::ComponentSet)
+//! be represented as a small bitset, turning archetype lookup and query
+//! matching into plain bit operations.
+//!
+//! Indices are assigned in first-use order and are only stable for the
+//! lifetime of the process; they must never be persisted.
+//!
+//! Component types can also be given a name, which lets bindings such as the
+//! C FFI layer refer to them without knowing the Rust type. Types registered
+//! with [`ComponentRegistry::register_json`] can additionally be read and
+//! written as JSON values by name, which is what scripting bindings use.
+//!
+//! Component types can carry a persistence schema (a stable name, a version
+//! and a transient flag). `#[derive(Component)]` submits the schema at
+//! compile time, and it is registered automatically when the registry is
+//! first used, so persistence sees names and versions without any manual
+//! registration calls.
+//!
+//! The registry also holds optional default constructors, which back
+//! [`World::insert_default`](crate::World::insert_default) and let the JSON
+//! and binary loaders fill in required components that an older save file
+//! does not contain.
+//!
+//! Component types can require companions: with
+//! [`ComponentRegistry::register_requires`] (or `#[component(requires(...))]`),
+//! inserting the component also inserts the registered default of every
+//! required type the entity is missing, so an entity never ends up with,
+//! say, a sprite but no transform.
+//!
+//! Component types can also form exclusive groups with
+//! [`ComponentRegistry::register_exclusive`]: inserting one member removes
+//! the others in the same archetype move, which makes marker components a
+//! cheap per-entity state machine.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::component::{Component, ComponentTypeId};
+//! use pecs::component::registry::ComponentRegistry;
+//!
+//! #[derive(Debug)]
+//! struct Position { x: f32, y: f32 }
+//! impl Component for Position {}
+//!
+//! let type_id = ComponentTypeId::of::<Position>();
+//! let registry = ComponentRegistry::global();
+//! assert_eq!(registry.type_at(type_id.index()), Some(type_id.type_id()));
+//! ```
+
+use super::{
+    CloneFn, Component, ComponentInfo, ComponentSet, ComponentTypeId, DebugFn, DeserializeFn,
+    InsertError, SerializeFn,
+};
+use crate::bundle::Bundle;
+use crate::entity::EntityId;
+use crate::persistence::PersistenceError;
+use crate::world::World;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Assigns dense indices to component types.
+///
+/// There is a single registry per process, obtained through
+/// [`ComponentRegistry::global`].
+#[derive(Debug, Default)]
+pub struct ComponentRegistry {
+    inner: RwLock<RegistryInner>,
+}
+
+#[derive(Debug, Default)]
+struct RegistryInner {
+    /// Map from type ID to dense index
+    indices: HashMap<TypeId, u32>,
+
+    /// Type IDs ordered by index
+    types: Vec<TypeId>,
+
+    /// Registered default constructors
+    defaults: HashMap<TypeId, DefaultEntry>,
+
+    /// Component metadata by registered name
+    names: HashMap<String, ComponentInfo>,
+
+    /// JSON conversions by registered name
+    json: HashMap<String, JsonCodec>,
+
+    /// Delta codecs by registered name
+    deltas: HashMap<String, DeltaOps>,
+
+    /// Persistence schemas by type
+    schemas: HashMap<TypeId, ComponentSchema>,
+
+    /// Types by schema name
+    schema_names: HashMap<String, TypeId>,
+
+    /// Optional type-erased operations by type
+    vtables: HashMap<TypeId, ComponentVtable>,
+
+    /// Required companion types by type
+    requires: HashMap<TypeId, Vec<Requirement>>,
+
+    /// Mutually exclusive types by type, not including the type itself
+    exclusive: HashMap<TypeId, ComponentSet>,
+}
+
+/// A component type that must accompany another, declared with
+/// [`ComponentRegistry::register_requires`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Requirement {
+    /// The required type
+    pub type_id: TypeId,
+    /// The required type's name, for error messages
+    pub type_name: &'static str,
+}
+
+/// Optional type-erased operations of a component type, copied into its
+/// [`ComponentInfo`] and looked up again by infos created before an
+/// operation was registered.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ComponentVtable {
+    pub(crate) clone: Option<CloneFn>,
+    pub(crate) debug: Option<DebugFn>,
+    pub(crate) serialize: Option<SerializeFn>,
+    pub(crate) deserialize: Option<DeserializeFn>,
+    pub(crate) column_alignment: usize,
+}
+
+/// Persistence metadata for a component type.
+///
+/// Usually declared with `#[component(...)]` on `#[derive(Component)]`:
+///
+/// ```
+/// use pecs::Component;
+/// use pecs::component::registry::ComponentRegistry;
+///
+/// #[derive(Component)]
+/// #[component(name = "game::Position", version = 3)]
+/// struct Position { x: f32, y: f32 }
+///
+/// let schema = ComponentRegistry::global().schema::<Position>().unwrap();
+/// assert_eq!(schema.name, "game::Position");
+/// assert_eq!(schema.version, 3);
+/// assert!(!schema.transient);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentSchema {
+    /// Stable name written to save files instead of the Rust type name
+    pub name: String,
+    /// Schema version, for migrations
+    pub version: u32,
+    /// Whether the component is skipped when saving
+    pub transient: bool,
+    /// In-memory layout of the component's fields, in declaration order
+    pub fields: Vec<FieldLayout>,
+}
+
+/// The in-memory layout of one component field.
+///
+/// `#[derive(Component)]` records the layout of every field of a
+/// non-generic struct, and binary saves can embed it as a description of
+/// their component types. Saved payloads are the component's serde JSON,
+/// so only the field names and types describe them; the offset and size
+/// describe the Rust type in memory.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FieldLayout {
+    /// Field name, or its index for tuple structs
+    pub name: String,
+    /// Rust type name of the field, as reported by [`std::any::type_name`]
+    pub type_name: String,
+    /// Byte offset of the field within the component
+    pub offset: usize,
+    /// Size of the field in bytes
+    pub size: usize,
+}
+
+impl FieldLayout {
+    /// Creates a field layout.
+    pub fn new(
+        name: impl Into<String>,
+        type_name: impl Into<String>,
+        offset: usize,
+        size: usize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            type_name: type_name.into(),
+            offset,
+            size,
+        }
+    }
+}
+
+impl ComponentSchema {
+    /// Creates a persistent schema.
+    pub fn new(name: impl Into<String>, version: u32) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            transient: false,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets whether the component is transient.
+    pub fn with_transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
+
+    /// Sets the field layouts.
+    pub fn with_fields(mut self, fields: Vec<FieldLayout>) -> Self {
+        self.fields = fields;
+        self
+    }
+}
+
+/// A schema registration submitted by `#[derive(Component)]`.
+///
+/// Registrations are collected at link time and run when
+/// [`ComponentRegistry::global`] is first called. Not intended to be
+/// constructed by hand; call [`ComponentRegistry::register_schema`] instead.
+#[doc(hidden)]
+pub struct ComponentRegistration {
+    register: fn(&ComponentRegistry),
+}
+
+impl ComponentRegistration {
+    /// Wraps a registration function.
+    pub const fn new(register: fn(&ComponentRegistry)) -> Self {
+        Self { register }
+    }
+}
+
+inventory::collect!(ComponentRegistration);
+
+/// JSON conversions for one component type.
+#[derive(Debug, Clone, Copy)]
+struct JsonCodec {
+    get: fn(&World, EntityId) -> Option<serde_json::Value>,
+    insert: fn(&mut World, EntityId, serde_json::Value) -> Result<bool, serde_json::Error>,
+    remove: fn(&mut World, EntityId) -> bool,
+}
+
+/// Encodes changes to large components as patches instead of full values.
+///
+/// Implement it for big, array-like components such as terrain chunks, and
+/// register it with [`ComponentRegistry::register_delta`]. Delta saves and
+/// [`DeltaEncoder`](crate::persistence::delta::DeltaEncoder) then send a
+/// patch describing what changed since the last encoded value, so editing
+/// one cell of a 64KB chunk does not re-send the whole chunk.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::component::Component;
+/// use pecs::component::registry::DeltaCodec;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Terrain { heights: Vec<u8> }
+/// impl Component for Terrain {}
+///
+/// impl DeltaCodec for Terrain {
+///     /// Changed cells as (index, height)
+///     type Patch = Vec<(usize, u8)>;
+///
+///     fn diff(old: &Self, new: &Self) -> Option<Self::Patch> {
+///         if old.heights.len() != new.heights.len() {
+///             return None;
+///         }
+///         Some(
+///             old.heights.iter().zip(&new.heights).enumerate()
+///                 .filter(|(_, (a, b))| a != b)
+///                 .map(|(i, (_, &b))| (i, b))
+///                 .collect(),
+///         )
+///     }
+///
+///     fn apply(base: &mut Self, patch: Self::Patch) {
+///         for (i, height) in patch {
+///             base.heights[i] = height;
+///         }
+///     }
+/// }
+/// ```
+pub trait DeltaCodec: Component + Clone + Serialize + DeserializeOwned {
+    /// Serialized description of a change
+    type Patch: Serialize + DeserializeOwned;
+
+    /// Describes how to turn `old` into `new`.
+    ///
+    /// Returns `None` if no patch can express the change, in which case the
+    /// full value is sent instead.
+    fn diff(old: &Self, new: &Self) -> Option<Self::Patch>;
+
+    /// Applies a patch produced by [`diff`](Self::diff) to the old value.
+    fn apply(base: &mut Self, patch: Self::Patch);
+}
+
+/// A copy of a component kept as the base for the next diff.
+pub(crate) type DeltaBase = Box<dyn Any + Send + Sync>;
+
+/// Type-erased operations of a [`DeltaCodec`].
+#[derive(Debug, Clone, Copy)]
+struct DeltaOps {
+    snapshot: fn(&World, EntityId) -> Option<DeltaBase>,
+    diff: fn(&DeltaBase, &World, EntityId) -> Option<Result<serde_json::Value, serde_json::Error>>,
+    apply: fn(&mut World, EntityId, serde_json::Value) -> Result<bool, serde_json::Error>,
+}
+
+/// Signature of a type-erased default insertion.
+pub(crate) type InsertDefaultFn = fn(&mut World, EntityId) -> bool;
+
+/// Signature of a type-erased default insertion that fills in a required
+/// component, reporting why it failed.
+pub(crate) type InsertRequirementFn = fn(&mut World, EntityId) -> Result<(), InsertError>;
+
+/// A registered default constructor.
+#[derive(Clone)]
+struct DefaultEntry {
+    /// Boxed `Fn() -> T`, downcast by the typed accessors
+    construct: Arc<dyn Any + Send + Sync>,
+
+    /// Inserts a default value without knowing `T` statically
+    insert: InsertDefaultFn,
+
+    /// As `insert`, but without resolving the requirements of `T`
+    insert_component: InsertRequirementFn,
+}
+
+impl fmt::Debug for DefaultEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultEntry").finish_non_exhaustive()
+    }
+}
+
+type Constructor<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+impl ComponentRegistry {
+    /// Returns the process-wide registry.
+    pub fn global() -> &'static ComponentRegistry {
+        static REGISTRY: OnceLock<ComponentRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| {
+            let registry = ComponentRegistry::default();
+            for registration in inventory::iter::<ComponentRegistration> {
+                (registration.register)(&registry);
+            }
+            registry
+        })
+    }
+
+    /// Returns the dense index for a type, registering it if necessary.
+    pub fn index_of(&self, type_id: TypeId) -> u32 {
+        if let Some(&index) = self.read().indices.get(&type_id) {
+            return index;
+        }
+
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have registered the type in the meantime
+        if let Some(&index) = inner.indices.get(&type_id) {
+            return index;
+        }
+        let index = u32::try_from(inner.types.len()).expect("too many component types");
+        inner.types.push(type_id);
+        inner.indices.insert(type_id, index);
+        index
+    }
+
+    /// Returns the type registered at `index`, if any.
+    pub fn type_at(&self, index: u32) -> Option<TypeId> {
+        self.read().types.get(index as usize).copied()
+    }
+
+    /// Returns the number of registered component types.
+    pub fn len(&self) -> usize {
+        self.read().types.len()
+    }
+
+    /// Returns `true` if no component types have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.read().types.is_empty()
+    }
+
+    /// Registers `name` as the external name of component type `T`.
+    ///
+    /// Registering a name again replaces the type it refers to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::Component;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Debug)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let registry = ComponentRegistry::global();
+    /// registry.register_name::<Position>("Position");
+    /// assert_eq!(registry.info_by_name("Position").unwrap().size(), 8);
+    /// ```
+    pub fn register_name<T: Component>(&self, name: impl Into<String>) {
+        let info = ComponentInfo::of::<T>();
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.names.insert(name.into(), info);
+    }
+
+    /// Returns the metadata of the component type registered under `name`.
+    pub fn info_by_name(&self, name: &str) -> Option<ComponentInfo> {
+        self.read().names.get(name).cloned()
+    }
+
+    /// Registers `name` for component type `T` and enables reading and
+    /// writing it as JSON through [`get_json`](Self::get_json) and
+    /// [`insert_json`](Self::insert_json).
+    ///
+    /// `#[derive(SerializableComponent)]` calls this for non-generic types,
+    /// under their component name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Serialize, Deserialize)]
+    /// struct Health { current: u32 }
+    /// impl Component for Health {}
+    ///
+    /// let registry = ComponentRegistry::global();
+    /// registry.register_json::<Health>("Health");
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    /// let value = serde_json::json!({ "current": 7 });
+    /// assert!(registry.insert_json("Health", &mut world, entity, value.clone()).unwrap());
+    /// assert_eq!(registry.get_json("Health", &world, entity), Some(value));
+    /// ```
+    pub fn register_json<T>(&self, name: impl Into<String>)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        let name = name.into();
+        let codec = JsonCodec {
+            get: |world, entity| {
+                world
+                    .get::<T>(entity)
+                    .and_then(|component| serde_json::to_value(component).ok())
+            },
+            insert: |world, entity, value| {
+                let component: T = serde_json::from_value(value)?;
+                Ok(world.insert_component(entity, component))
+            },
+            remove: |world, entity| world.remove::<T>(entity).is_some(),
+        };
+        self.update_vtable::<T>(|vtable| {
+            vtable.serialize = Some(|ptr| {
+                // SAFETY: `ComponentInfo::serialize` requires a valid `T`
+                serde_json::to_value(unsafe { &*(ptr as *const T) })
+            });
+            vtable.deserialize = Some(|value, dst| {
+                let component: T = serde_json::from_value(value)?;
+                // SAFETY: `ComponentInfo::deserialize_into` requires dst to
+                // be valid for writes of `T`
+                unsafe { std::ptr::write(dst as *mut T, component) };
+                Ok(())
+            });
+        });
+        // Resolve the type's index before taking the write lock. This may
+        // run while `global` is still initializing, so it must not go
+        // through the global registry
+        let info = ComponentInfo::of_in::<T>(self);
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.names.insert(name.clone(), info);
+        inner.json.insert(name, codec);
+    }
+
+    /// Registers `name` for component type `T` as with
+    /// [`register_json`](Self::register_json), and enables sending changes
+    /// to it as patches through its [`DeltaCodec`].
+    pub fn register_delta<T: DeltaCodec>(&self, name: impl Into<String>) {
+        let name = name.into();
+        self.register_json::<T>(name.clone());
+        let ops = DeltaOps {
+            snapshot: |world, entity| {
+                world
+                    .get::<T>(entity)
+                    .map(|component| Box::new(component.clone()) as DeltaBase)
+            },
+            diff: |base, world, entity| {
+                let old = base.downcast_ref::<T>()?;
+                let patch = T::diff(old, world.get::<T>(entity)?)?;
+                Some(serde_json::to_value(patch))
+            },
+            apply: |world, entity, patch| {
+                let patch: T::Patch = serde_json::from_value(patch)?;
+                Ok(world
+                    .get_mut::<T>(entity)
+                    .map(|base| T::apply(base, patch))
+                    .is_some())
+            },
+        };
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.deltas.insert(name, ops);
+    }
+
+    /// Returns true if a JSON codec is registered under `name`.
+    pub fn has_json(&self, name: &str) -> bool {
+        self.read().json.contains_key(name)
+    }
+
+    /// Returns true if a [`DeltaCodec`] is registered under `name`.
+    pub fn has_delta(&self, name: &str) -> bool {
+        self.read().deltas.contains_key(name)
+    }
+
+    /// Copies the named component of an entity as the base for a later
+    /// [`diff_delta`](Self::diff_delta).
+    pub(crate) fn delta_snapshot(
+        &self,
+        name: &str,
+        world: &World,
+        entity: EntityId,
+    ) -> Option<DeltaBase> {
+        let ops = *self.read().deltas.get(name)?;
+        (ops.snapshot)(world, entity)
+    }
+
+    /// Diffs the named component of an entity against a base from
+    /// [`delta_snapshot`](Self::delta_snapshot).
+    ///
+    /// Returns `None` if there is no codec, the entity lacks the component,
+    /// or the codec cannot express the change.
+    pub(crate) fn diff_delta(
+        &self,
+        name: &str,
+        base: &DeltaBase,
+        world: &World,
+        entity: EntityId,
+    ) -> Option<Result<serde_json::Value, serde_json::Error>> {
+        let ops = *self.read().deltas.get(name)?;
+        (ops.diff)(base, world, entity)
+    }
+
+    /// Applies a patch to the named component of an entity.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if applied, `Ok(false)` if the entity doesn't have the
+    /// component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::UnknownComponentType`] if no codec is
+    /// registered under `name`, or [`PersistenceError::Deserialization`] if
+    /// the patch does not match the codec.
+    pub fn apply_delta(
+        &self,
+        name: &str,
+        world: &mut World,
+        entity: EntityId,
+        patch: serde_json::Value,
+    ) -> Result<bool, PersistenceError> {
+        let ops = *self
+            .read()
+            .deltas
+            .get(name)
+            .ok_or_else(|| PersistenceError::UnknownComponentType(name.to_string()))?;
+        (ops.apply)(world, entity, patch)
+            .map_err(|e| PersistenceError::Deserialization(format!("{}: {}", name, e)))
+    }
+
+    /// Registers `Clone` for component type `T`, making it cloneable
+    /// through [`ComponentInfo::clone_into`].
+    ///
+    /// `#[derive(Component)]` does this for `#[component(clone)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::{Component, ComponentInfo};
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Clone)]
+    /// struct Name(String);
+    /// impl Component for Name {}
+    ///
+    /// ComponentRegistry::global().register_clone::<Name>();
+    /// assert!(ComponentInfo::of::<Name>().is_cloneable());
+    /// ```
+    pub fn register_clone<T: Component + Clone>(&self) {
+        self.update_vtable::<T>(|vtable| {
+            vtable.clone = Some(|src, dst| {
+                // SAFETY: `ComponentInfo::clone_into` requires a valid `T`
+                // at src and memory valid for writes of `T` at dst
+                unsafe {
+                    let component = (*(src as *const T)).clone();
+                    std::ptr::write(dst as *mut T, component);
+                }
+            });
+        });
+    }
+
+    /// Registers `Debug` for component type `T`, used by
+    /// [`ComponentInfo::debug`].
+    ///
+    /// `#[derive(Component)]` does this for `#[component(debug)]`.
+    pub fn register_debug<T: Component + fmt::Debug>(&self) {
+        self.update_vtable::<T>(|vtable| {
+            vtable.debug = Some(|ptr, f| {
+                // SAFETY: `ComponentInfo::debug` requires a valid `T`
+                fmt::Debug::fmt(unsafe { &*(ptr as *const T) }, f)
+            });
+        });
+    }
+
+    /// Registers an over-alignment for the storage columns of component
+    /// type `T`, used by [`ComponentInfo::of`].
+    ///
+    /// Columns created afterwards start at a multiple of `align`, see
+    /// [`ComponentInfo::of_aligned`]. Register before spawning any `T`, as
+    /// existing archetypes keep their columns. `#[derive(Component)]` does
+    /// this for `#[component(align = N)]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn register_alignment<T: Component>(&self, align: usize) {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self.update_vtable::<T>(|vtable| vtable.column_alignment = align);
+    }
+
+    /// Returns the optional operations registered for a type.
+    pub(crate) fn vtable(&self, type_id: TypeId) -> ComponentVtable {
+        self.read()
+            .vtables
+            .get(&type_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn update_vtable<T: Component>(&self, update: impl FnOnce(&mut ComponentVtable)) {
+        // Must not touch `ComponentTypeId`: this runs while `global` is
+        // still initializing
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        update(inner.vtables.entry(TypeId::of::<T>()).or_default());
+    }
+
+    /// Returns the names of all types registered with
+    /// [`register_json`](Self::register_json), sorted.
+    pub fn json_names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.read().json.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Returns the name component type `type_id` was registered under with
+    /// [`register_json`](Self::register_json). If there are several, the
+    /// first in sorted order is returned.
+    pub fn json_name_of(&self, type_id: ComponentTypeId) -> Option<String> {
+        let inner = self.read();
+        inner
+            .json
+            .keys()
+            .filter(|name| {
+                inner
+                    .names
+                    .get(name.as_str())
+                    .is_some_and(|info| info.type_id() == type_id)
+            })
+            .min()
+            .cloned()
+    }
+
+    /// Reads the named component of an entity as JSON.
+    ///
+    /// Returns `None` if the name is not registered for JSON, the entity
+    /// doesn't have the component, or it fails to serialize.
+    pub fn get_json(
+        &self,
+        name: &str,
+        world: &World,
+        entity: EntityId,
+    ) -> Option<serde_json::Value> {
+        let codec = *self.read().json.get(name)?;
+        (codec.get)(world, entity)
+    }
+
+    /// Inserts the named component into an entity from a JSON value.
+    ///
+    /// Loaders restore entities one component at a time through this, so
+    /// [requirements](Self::register_requires) are not resolved: a saved
+    /// entity already has what its components require.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if inserted, `Ok(false)` if the entity doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::UnknownComponentType`] if the name is not
+    /// registered for JSON, or [`PersistenceError::Deserialization`] if the
+    /// value does not match the component type.
+    pub fn insert_json(
+        &self,
+        name: &str,
+        world: &mut World,
+        entity: EntityId,
+        value: serde_json::Value,
+    ) -> Result<bool, PersistenceError> {
+        let codec = *self
+            .read()
+            .json
+            .get(name)
+            .ok_or_else(|| PersistenceError::UnknownComponentType(name.to_string()))?;
+        (codec.insert)(world, entity, value)
+            .map_err(|e| PersistenceError::Deserialization(format!("{}: {}", name, e)))
+    }
+
+    /// Removes the named component from an entity.
+    ///
+    /// Returns `Ok(true)` if the component was removed, `Ok(false)` if the
+    /// entity doesn't exist or doesn't have it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::UnknownComponentType`] if the name is not
+    /// registered for JSON.
+    pub fn remove_json(
+        &self,
+        name: &str,
+        world: &mut World,
+        entity: EntityId,
+    ) -> Result<bool, PersistenceError> {
+        let codec = *self
+            .read()
+            .json
+            .get(name)
+            .ok_or_else(|| PersistenceError::UnknownComponentType(name.to_string()))?;
+        Ok((codec.remove)(world, entity))
+    }
+
+    /// Registers a default constructor for component type `T`.
+    ///
+    /// Registering again replaces the previous constructor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::Component;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Debug, Default, PartialEq)]
+    /// struct Health(u32);
+    /// impl Component for Health {}
+    ///
+    /// let registry = ComponentRegistry::global();
+    /// registry.register_default(|| Health(100));
+    /// assert_eq!(registry.default_value::<Health>(), Some(Health(100)));
+    /// ```
+    pub fn register_default<T: Component>(
+        &self,
+        constructor: impl Fn() -> T + Send + Sync + 'static,
+    ) {
+        let constructor: Constructor<T> = Box::new(constructor);
+        let entry = DefaultEntry {
+            construct: Arc::new(constructor),
+            insert: |world, entity| world.insert_default::<T>(entity),
+            insert_component: |world, entity| {
+                // Only reached through the global registry's entry for `T`
+                let component = ComponentRegistry::global()
+                    .default_value::<T>()
+                    .expect("default is registered");
+                world.try_insert_component(entity, component)
+            },
+        };
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.defaults.insert(TypeId::of::<T>(), entry);
+    }
+
+    /// Returns `true` if a default constructor is registered for the type.
+    pub fn has_default(&self, type_id: ComponentTypeId) -> bool {
+        self.read().defaults.contains_key(&type_id.type_id())
+    }
+
+    /// Constructs the registered default value for `T`.
+    ///
+    /// Returns `None` if no default constructor is registered.
+    pub fn default_value<T: Component>(&self) -> Option<T> {
+        // Release the lock before running user code
+        let construct = self
+            .read()
+            .defaults
+            .get(&TypeId::of::<T>())?
+            .construct
+            .clone();
+        construct
+            .downcast_ref::<Constructor<T>>()
+            .map(|constructor| constructor())
+    }
+
+    /// Returns the type-erased insertion function for a registered default.
+    pub(crate) fn default_inserter(&self, type_id: ComponentTypeId) -> Option<InsertDefaultFn> {
+        self.read()
+            .defaults
+            .get(&type_id.type_id())
+            .map(|entry| entry.insert)
+    }
+
+    /// Returns the type-erased insertion function for a registered default,
+    /// looked up by Rust type.
+    pub(crate) fn default_inserter_for(&self, type_id: TypeId) -> Option<InsertDefaultFn> {
+        self.read().defaults.get(&type_id).map(|entry| entry.insert)
+    }
+
+    /// Returns the insertion function for a registered default that does
+    /// not resolve the requirements of the inserted type.
+    pub(crate) fn requirement_inserter(&self, type_id: TypeId) -> Option<InsertRequirementFn> {
+        self.read()
+            .defaults
+            .get(&type_id)
+            .map(|entry| entry.insert_component)
+    }
+
+    /// Declares that component type `T` requires component type `R`.
+    ///
+    /// [`World::insert`](crate::World::insert) and entity builders then add
+    /// the [registered default](Self::register_default) of `R` to any
+    /// entity that gets a `T` without an `R`. If `R` has no default, the
+    /// insertion fails instead, see
+    /// [`World::try_insert`](crate::World::try_insert). Requirements are
+    /// transitive. `#[derive(Component)]` does this for
+    /// `#[component(requires(R))]`.
+    ///
+    /// Like everything in the registry, requirements are process-wide: they
+    /// hold for every [`World`](crate::World), including worlds that already
+    /// exist, and cannot be withdrawn. Declare them for the type itself, not
+    /// for one world's use of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Transform { x: f32, y: f32 }
+    /// impl Component for Transform {}
+    ///
+    /// struct Sprite;
+    /// impl Component for Sprite {}
+    ///
+    /// let registry = ComponentRegistry::global();
+    /// registry.register_default(|| Transform { x: 0.0, y: 0.0 });
+    /// registry.register_requires::<Sprite, Transform>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    /// world.insert(entity, Sprite);
+    /// assert_eq!(world.get::<Transform>(entity), Some(&Transform { x: 0.0, y: 0.0 }));
+    /// ```
+    pub fn register_requires<T: Component, R: Component>(&self) {
+        // Must not touch `ComponentTypeId`: this runs while `global` is
+        // still initializing
+        let requirement = Requirement {
+            type_id: TypeId::of::<R>(),
+            type_name: std::any::type_name::<R>(),
+        };
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        let requires = inner.requires.entry(TypeId::of::<T>()).or_default();
+        if !requires.contains(&requirement) {
+            requires.push(requirement);
+        }
+    }
+
+    /// Returns the component types directly required by type `T`.
+    pub fn requirements<T: Component>(&self) -> Vec<Requirement> {
+        self.requirements_of(TypeId::of::<T>())
+    }
+
+    /// Returns the component types directly required by a type.
+    pub fn requirements_of(&self, type_id: TypeId) -> Vec<Requirement> {
+        self.read()
+            .requires
+            .get(&type_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Declares the component types of bundle `B` mutually exclusive.
+    ///
+    /// Inserting one of them into an entity removes (and drops) any of the
+    /// others it has, in the same archetype move as the insertion, so an
+    /// entity is in at most one state of the group at a time. A type may
+    /// belong to several groups. Register groups before inserting their
+    /// members.
+    ///
+    /// Groups are process-wide, like [requirements](Self::register_requires):
+    /// they apply to every [`World`](crate::World) and cannot be withdrawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Component)]
+    /// struct Idle;
+    /// #[derive(Component)]
+    /// struct Walking { speed: f32 }
+    /// #[derive(Component)]
+    /// struct Attacking { target: u32 }
+    ///
+    /// ComponentRegistry::global().register_exclusive::<(Idle, Walking, Attacking)>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Idle).id();
+    ///
+    /// world.insert(entity, Walking { speed: 2.0 });
+    /// assert!(!world.has::<Idle>(entity));
+    ///
+    /// world.insert(entity, Attacking { target: 7 });
+    /// assert!(!world.has::<Walking>(entity));
+    /// assert!(world.has::<Attacking>(entity));
+    /// ```
+    pub fn register_exclusive<B: Bundle>(&self) {
+        // Resolve the type indices before taking the write lock
+        let members = B::component_info();
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        for member in &members {
+            let rivals = inner
+                .exclusive
+                .entry(member.type_id().type_id())
+                .or_default();
+            for rival in &members {
+                if rival.type_id() != member.type_id() {
+                    rivals.insert(rival.type_id());
+                }
+            }
+        }
+    }
+
+    /// Returns the component types that are mutually exclusive with a type.
+    ///
+    /// The set is empty if the type belongs to no
+    /// [exclusive group](Self::register_exclusive).
+    pub fn exclusive_with(&self, type_id: ComponentTypeId) -> ComponentSet {
+        self.read()
+            .exclusive
+            .get(&type_id.type_id())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Registers the persistence schema of component type `T`.
+    ///
+    /// Replaces any earlier schema for `T`. Derived components register
+    /// their schema automatically; this is for manual `Component` impls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::Component;
+    /// use pecs::component::registry::{ComponentRegistry, ComponentSchema};
+    ///
+    /// struct Cache(Vec<u8>);
+    /// impl Component for Cache {}
+    ///
+    /// let registry = ComponentRegistry::global();
+    /// registry.register_schema::<Cache>(ComponentSchema::new("Cache", 1).with_transient(true));
+    /// assert!(registry.schema::<Cache>().unwrap().transient);
+    /// ```
+    pub fn register_schema<T: Component>(&self, schema: ComponentSchema) {
+        // Must not touch `ComponentTypeId`: this runs while `global` is
+        // still initializing
+        let type_id = TypeId::of::<T>();
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = inner.schemas.remove(&type_id) {
+            inner.schema_names.remove(&old.name);
+        }
+        inner.schema_names.insert(schema.name.clone(), type_id);
+        inner.schemas.insert(type_id, schema);
+    }
+
+    /// Returns the persistence schema of component type `T`, if registered.
+    pub fn schema<T: Component>(&self) -> Option<ComponentSchema> {
+        self.schema_of(TypeId::of::<T>())
+    }
+
+    /// Returns the persistence schema registered for a type.
+    pub fn schema_of(&self, type_id: TypeId) -> Option<ComponentSchema> {
+        self.read().schemas.get(&type_id).cloned()
+    }
+
+    /// Returns the type whose schema is registered under `name`.
+    pub fn type_by_schema_name(&self, name: &str) -> Option<TypeId> {
+        self.read().schema_names.get(name).copied()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, RegistryInner> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct A;
+    struct B;
+
+    #[test]
+    fn indices_are_dense_and_stable() {
+        let registry = ComponentRegistry::default();
+        let a = registry.index_of(TypeId::of::<A>());
+        let b = registry.index_of(TypeId::of::<B>());
+
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(registry.index_of(TypeId::of::<A>()), a);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.type_at(b), Some(TypeId::of::<B>()));
+        assert_eq!(registry.type_at(2), None);
+    }
+
+    #[test]
+    fn named_types() {
+        let registry = ComponentRegistry::default();
+        assert!(registry.info_by_name("Level").is_none());
+
+        registry.register_name::<Level>("Level");
+        let info = registry.info_by_name("Level").unwrap();
+        assert_eq!(info.type_id(), ComponentTypeId::of::<Level>());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let registry = ComponentRegistry::default();
+        registry.register_json::<Level>("Level");
+        assert_eq!(registry.json_names(), vec!["Level".to_string()]);
+
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        assert_eq!(registry.get_json("Level", &world, entity), None);
+
+        let inserted = registry.insert_json("Level", &mut world, entity, serde_json::json!(3));
+        assert!(inserted.unwrap());
+        assert_eq!(world.get::<Level>(entity), Some(&Level(3)));
+        assert_eq!(
+            registry.get_json("Level", &world, entity),
+            Some(serde_json::json!(3))
+        );
+
+        assert!(matches!(
+            registry.insert_json("Level", &mut world, entity, serde_json::json!("x")),
+            Err(PersistenceError::Deserialization(_))
+        ));
+        assert!(matches!(
+            registry.insert_json("Missing", &mut world, entity, serde_json::json!(1)),
+            Err(PersistenceError::UnknownComponentType(_))
+        ));
+    }
+
+    #[test]
+    fn schemas() {
+        let registry = ComponentRegistry::default();
+        assert_eq!(registry.schema::<Level>(), None);
+
+        registry.register_schema::<Level>(ComponentSchema::new("game::Level", 2));
+        assert_eq!(registry.schema::<Level>().unwrap().version, 2);
+        assert_eq!(
+            registry.type_by_schema_name("game::Level"),
+            Some(TypeId::of::<Level>())
+        );
+
+        registry.register_schema::<Level>(ComponentSchema::new("Level", 3).with_transient(true));
+        assert_eq!(registry.type_by_schema_name("game::Level"), None);
+        assert!(registry.schema_of(TypeId::of::<Level>()).unwrap().transient);
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Level(u8);
+    impl Component for Level {}
+
+    #[test]
+    fn default_constructors() {
+        let registry = ComponentRegistry::default();
+        let type_id = ComponentTypeId::of::<Level>();
+        assert!(!registry.has_default(type_id));
+        assert_eq!(registry.default_value::<Level>(), None);
+
+        registry.register_default(|| Level(1));
+        assert!(registry.has_default(type_id));
+        assert_eq!(registry.default_value::<Level>(), Some(Level(1)));
+        assert!(registry.default_inserter(type_id).is_some());
+
+        registry.register_default(|| Level(5));
+        assert_eq!(registry.default_value::<Level>(), Some(Level(5)));
+    }
+}
@@ -19,13 +19,16 @@
 //! This module provides the low-level storage mechanisms for components,
 //! including type-erased storage and safe access patterns.
 
+use super::memory::{Alloc, Global, MemoryError, MemoryTracker};
 use super::{Component, ComponentInfo};
-use std::alloc::{self, Layout};
+use std::alloc::Layout;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 /// A type-erased storage for a single component type.
 ///
-/// This stores components in a contiguous array with proper alignment,
+/// This stores components in a contiguous array with proper alignment
+/// (optionally over-aligned at the start, see [`ComponentInfo::of_aligned`]),
 /// allowing for cache-friendly iteration while maintaining type safety
 /// through the component info.
 pub struct ComponentStorage {
@@ -40,6 +43,12 @@ pub struct ComponentStorage {
 
     /// Capacity of the allocated memory
     capacity: usize,
+
+    /// Tracker the allocation is reported to, if any
+    tracker: Option<Arc<MemoryTracker>>,
+
+    /// Allocator the column's memory comes from
+    allocator: Arc<dyn Alloc>,
 }
 
 impl ComponentStorage {
@@ -59,17 +68,41 @@ impl ComponentStorage {
     /// assert_eq!(storage.len(), 0);
     /// ```
     pub fn new(info: ComponentInfo) -> Self {
+        Self::new_in(info, Global::shared())
+    }
+
+    /// Creates a new empty component storage that allocates from
+    /// `allocator`.
+    pub fn new_in(info: ComponentInfo, allocator: Arc<dyn Alloc>) -> Self {
         Self {
+            data: Self::dangling(&info),
             info,
-            data: NonNull::dangling(),
             len: 0,
             capacity: 0,
+            tracker: None,
+            allocator,
         }
     }
 
+    /// Returns a non-null pointer aligned for the column, for empty storage.
+    fn dangling(info: &ComponentInfo) -> NonNull<u8> {
+        NonNull::new(std::ptr::without_provenance_mut(info.column_alignment()))
+            .expect("alignment is never zero")
+    }
+
     /// Creates a component storage with pre-allocated capacity.
     pub fn with_capacity(info: ComponentInfo, capacity: usize) -> Self {
-        let mut storage = Self::new(info);
+        Self::with_capacity_in(info, capacity, Global::shared())
+    }
+
+    /// Creates a component storage with pre-allocated capacity that
+    /// allocates from `allocator`.
+    pub fn with_capacity_in(
+        info: ComponentInfo,
+        capacity: usize,
+        allocator: Arc<dyn Alloc>,
+    ) -> Self {
+        let mut storage = Self::new_in(info, allocator);
         if capacity > 0 {
             storage.reserve(capacity);
         }
@@ -81,6 +114,48 @@ impl ComponentStorage {
         &self.info
     }
 
+    /// Returns the guaranteed alignment of the start of the column.
+    ///
+    /// This is the component's [`column_alignment`](ComponentInfo::column_alignment).
+    pub fn alignment(&self) -> usize {
+        self.info.column_alignment()
+    }
+
+    /// Returns the stored components as a slice.
+    ///
+    /// The slice starts at a multiple of [`alignment`](Self::alignment).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not the stored component type.
+    pub fn as_slice<T: Component>(&self) -> &[T] {
+        self.assert_type::<T>();
+        // SAFETY: The column holds `len` initialized values of `T`, and the
+        // pointer is aligned and non-null even when empty
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+    }
+
+    /// Returns the stored components as a mutable slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not the stored component type.
+    pub fn as_mut_slice<T: Component>(&mut self) -> &mut [T] {
+        self.assert_type::<T>();
+        // SAFETY: As in `as_slice`, and `self` is borrowed mutably
+        unsafe { std::slice::from_raw_parts_mut(self.data.as_ptr() as *mut T, self.len) }
+    }
+
+    fn assert_type<T: Component>(&self) {
+        assert_eq!(
+            self.info.type_id(),
+            super::ComponentTypeId::of::<T>(),
+            "storage holds {}, not {}",
+            self.info.type_name(),
+            std::any::type_name::<T>()
+        );
+    }
+
     /// Returns the number of components stored.
     pub fn len(&self) -> usize {
         self.len
@@ -96,49 +171,134 @@ impl ComponentStorage {
         self.capacity
     }
 
+    /// Returns the number of bytes allocated for the column.
+    pub fn allocated_bytes(&self) -> usize {
+        self.capacity * self.info.size()
+    }
+
+    /// Reports this storage's allocations to a memory tracker.
+    ///
+    /// The current allocation is recorded without checking the budget;
+    /// later growth is checked. Any previous tracker is released.
+    pub fn set_tracker(&mut self, tracker: Arc<MemoryTracker>) {
+        let bytes = self.allocated_bytes();
+        if let Some(previous) = &self.tracker {
+            let _ = previous.try_resize(self.info.type_id(), bytes, 0);
+        }
+        tracker.adopt(self.info.type_id(), bytes);
+        self.tracker = Some(tracker);
+    }
+
     /// Reserves capacity for at least `additional` more components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocation fails or exceeds the tracker's budget. Use
+    /// [`try_reserve`](Self::try_reserve) to handle that as an error.
     pub fn reserve(&mut self, additional: usize) {
+        if let Err(error) = self.try_reserve(additional) {
+            panic!("{}: {}", self.info.type_name(), error);
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more components,
+    /// returning an error instead of panicking if the allocation fails or
+    /// exceeds the tracker's budget.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), MemoryError> {
         let required = self.len.checked_add(additional).expect("capacity overflow");
         if required <= self.capacity {
-            return;
+            return Ok(());
         }
 
         // Use a more aggressive growth strategy for better amortized performance
         // Growth factor of 1.5x is optimal for memory reuse while minimizing reallocations
         let new_capacity = required.max((self.capacity * 3) / 2).max(16); // Start with 16 instead of 4 to reduce early reallocations
-        self.realloc(new_capacity);
+        self.try_realloc(new_capacity)
+    }
+
+    /// Shrinks the capacity of the storage as close as possible to its length.
+    ///
+    /// Returns the number of bytes released.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        if self.capacity == self.len {
+            return 0;
+        }
+
+        let component_size = self.info.size();
+        let released = (self.capacity - self.len) * component_size;
+
+        if component_size > 0 && self.len == 0 {
+            // Nothing left to keep, release the allocation entirely
+            let layout = Layout::from_size_align(
+                component_size * self.capacity,
+                self.info.column_alignment(),
+            )
+            .expect("invalid layout");
+            unsafe {
+                self.allocator.deallocate(self.data.as_ptr(), layout);
+            }
+            self.track_resize(released, 0)
+                .expect("shrinking is never over budget");
+            self.data = Self::dangling(&self.info);
+            self.capacity = 0;
+        } else if let Err(error) = self.try_realloc(self.len) {
+            panic!("{}: {}", self.info.type_name(), error);
+        }
+
+        released
+    }
+
+    /// Reports a resize of the allocation to the tracker, if any.
+    fn track_resize(&self, old: usize, new: usize) -> Result<(), MemoryError> {
+        match &self.tracker {
+            Some(tracker) => tracker.try_resize(self.info.type_id(), old, new),
+            None => Ok(()),
+        }
     }
 
     /// Reallocates the storage to a new capacity.
-    fn realloc(&mut self, new_capacity: usize) {
+    fn try_realloc(&mut self, new_capacity: usize) -> Result<(), MemoryError> {
         assert!(new_capacity >= self.len);
 
         let component_size = self.info.size();
-        let component_align = self.info.alignment();
+        let component_align = self.info.column_alignment();
 
         if component_size == 0 {
             // Zero-sized types don't need allocation
             self.capacity = new_capacity;
-            return;
+            return Ok(());
         }
 
         let new_layout = Layout::from_size_align(component_size * new_capacity, component_align)
             .expect("invalid layout");
+        let old_bytes = self.allocated_bytes();
+        self.track_resize(old_bytes, new_layout.size())?;
 
         let new_ptr = if self.capacity == 0 {
             // Initial allocation
-            unsafe { alloc::alloc(new_layout) }
+            self.allocator.allocate(new_layout)
         } else {
             // Reallocation
             let old_layout =
                 Layout::from_size_align(component_size * self.capacity, component_align)
                     .expect("invalid layout");
 
-            unsafe { alloc::realloc(self.data.as_ptr(), old_layout, new_layout.size()) }
+            unsafe {
+                self.allocator
+                    .reallocate(self.data.as_ptr(), old_layout, new_layout.size())
+            }
         };
 
-        self.data = NonNull::new(new_ptr).expect("allocation failed");
+        let Some(data) = NonNull::new(new_ptr) else {
+            self.track_resize(new_layout.size(), old_bytes)
+                .expect("reverting a resize is never over budget");
+            return Err(MemoryError::AllocationFailed {
+                bytes: new_layout.size(),
+            });
+        };
+        self.data = data;
         self.capacity = new_capacity;
+        Ok(())
     }
 
     /// Pushes a component to the end of the storage.
@@ -161,6 +321,20 @@ impl ComponentStorage {
         self.len += 1;
     }
 
+    /// Grows the storage by `additional` uninitialized rows.
+    ///
+    /// Capacity is reserved once for all rows, so filling a gap of several
+    /// rows costs at most one reallocation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must write a valid component into every new row before it
+    /// is read, moved out or dropped.
+    pub unsafe fn extend_uninit(&mut self, additional: usize) {
+        self.reserve(additional);
+        self.len += additional;
+    }
+
     /// Removes and returns the component at the given index.
     ///
     /// This performs a swap-remove operation, moving the last component
@@ -191,6 +365,43 @@ impl ComponentStorage {
         self.len -= 1;
     }
 
+    /// Moves the component at `src_row` to the end of `dst`.
+    ///
+    /// The component's bytes are copied straight into `dst`, and the last
+    /// component of this storage is swapped into the vacated row. The
+    /// moved-from slot is no longer part of this storage, so the component
+    /// is dropped exactly once, by `dst`.
+    ///
+    /// # Safety
+    ///
+    /// - `src_row` must be less than `len()`
+    /// - `dst` must store the same component type as this storage
+    pub unsafe fn transfer(&mut self, dst: &mut ComponentStorage, src_row: usize) {
+        assert!(src_row < self.len);
+        debug_assert_eq!(self.info.type_id(), dst.info.type_id());
+
+        if dst.len == dst.capacity {
+            dst.reserve(1);
+        }
+
+        let component_size = self.info.size();
+        // SAFETY: src_row is in bounds, dst has room for one more component
+        // and both storages hold the same type
+        unsafe {
+            let src = self.data.as_ptr().add(src_row * component_size);
+            let target = dst.data.as_ptr().add(dst.len * component_size);
+            std::ptr::copy_nonoverlapping(src, target, component_size);
+
+            if src_row != self.len - 1 {
+                let last = self.data.as_ptr().add((self.len - 1) * component_size);
+                std::ptr::copy_nonoverlapping(last, src, component_size);
+            }
+        }
+
+        dst.len += 1;
+        self.len -= 1;
+    }
+
     /// Gets a pointer to the component at the given index.
     ///
     /// # Safety
@@ -245,20 +456,24 @@ impl Drop for ComponentStorage {
 
         // Deallocate memory
         if self.capacity > 0 && self.info.size() > 0 {
-            let layout =
-                Layout::from_size_align(self.info.size() * self.capacity, self.info.alignment())
-                    .expect("invalid layout");
+            let layout = Layout::from_size_align(
+                self.info.size() * self.capacity,
+                self.info.column_alignment(),
+            )
+            .expect("invalid layout");
 
             unsafe {
-                alloc::dealloc(self.data.as_ptr(), layout);
+                self.allocator.deallocate(self.data.as_ptr(), layout);
             }
+            let _ = self.track_resize(self.allocated_bytes(), 0);
         }
     }
 }
 
-// Safety: ComponentStorage can be sent between threads if the component type is Send
+// Safety: ComponentStorage only holds `Component` values, which are always Send
 unsafe impl Send for ComponentStorage {}
-// Safety: ComponentStorage can be shared between threads if the component type is Sync
+// Safety: ComponentStorage only holds `Component` values, which are always Sync,
+// and its `&self` methods never mutate
 unsafe impl Sync for ComponentStorage {}
 
 /// A typed wrapper around ComponentStorage for safe access.
@@ -394,6 +609,123 @@ mod tests {
         assert!(storage.is_empty());
     }
 
+    #[test]
+    fn component_storage_shrink_to_fit() {
+        let mut storage = ComponentStorage::with_capacity(ComponentInfo::of::<Position>(), 32);
+        let position = Position { x: 1.0, y: 2.0 };
+        unsafe {
+            storage.push(&position as *const Position as *const u8);
+        }
+
+        let element = std::mem::size_of::<Position>();
+        assert_eq!(storage.shrink_to_fit(), 31 * element);
+        assert_eq!(storage.capacity(), 1);
+        assert_eq!(unsafe { *(storage.get(0) as *const Position) }, position);
+        assert_eq!(storage.shrink_to_fit(), 0);
+
+        storage.clear();
+        assert_eq!(storage.shrink_to_fit(), element);
+        assert_eq!(storage.capacity(), 0);
+    }
+
+    #[test]
+    fn component_storage_transfer_drops_once() {
+        #[derive(Debug)]
+        struct Shared(Arc<u32>);
+        impl Component for Shared {}
+
+        let counter = Arc::new(0);
+        let mut src = ComponentStorage::new(ComponentInfo::of::<Shared>());
+        let mut dst = ComponentStorage::new(ComponentInfo::of::<Shared>());
+        for _ in 0..3 {
+            let shared = std::mem::ManuallyDrop::new(Shared(counter.clone()));
+            unsafe { src.push(&*shared as *const Shared as *const u8) };
+        }
+        assert_eq!(Arc::strong_count(&counter), 4);
+
+        unsafe { src.transfer(&mut dst, 0) };
+        assert_eq!((src.len(), dst.len()), (2, 1));
+        let moved = unsafe { &*(dst.get(0) as *const Shared) };
+        assert!(Arc::ptr_eq(&moved.0, &counter));
+        assert_eq!(Arc::strong_count(&counter), 4);
+
+        drop(src);
+        assert_eq!(Arc::strong_count(&counter), 2);
+        drop(dst);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn component_storage_over_aligned_columns() {
+        let mut storage = ComponentStorage::new(ComponentInfo::of_aligned::<Position>(64));
+        assert_eq!(storage.alignment(), 64);
+        assert_eq!(storage.as_slice::<Position>().as_ptr() as usize % 64, 0);
+
+        for i in 0..100 {
+            let position = Position {
+                x: i as f32,
+                y: 0.0,
+            };
+            unsafe {
+                storage.push(&position as *const Position as *const u8);
+            }
+            assert_eq!(storage.as_ptr() as usize % 64, 0);
+        }
+        storage.as_mut_slice::<Position>()[99].y = 1.0;
+        assert_eq!(storage.as_slice::<Position>()[99].y, 1.0);
+
+        storage.shrink_to_fit();
+        assert_eq!(storage.as_ptr() as usize % 64, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "storage holds")]
+    fn component_storage_as_slice_checks_type() {
+        let storage = ComponentStorage::new(ComponentInfo::of::<Position>());
+        let _ = storage.as_slice::<Name>();
+    }
+
+    #[test]
+    fn component_storage_uses_custom_allocator() {
+        use std::sync::atomic::{AtomicIsize, Ordering};
+
+        #[derive(Default)]
+        struct Counting(AtomicIsize);
+        // SAFETY: forwards to the global allocator
+        unsafe impl Alloc for Counting {
+            fn allocate(&self, layout: Layout) -> *mut u8 {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+                self.0.fetch_sub(1, Ordering::Relaxed);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let allocator = Arc::new(Counting::default());
+        let mut storage = ComponentStorage::with_capacity_in(
+            ComponentInfo::of::<Position>(),
+            4,
+            allocator.clone(),
+        );
+        assert_eq!(allocator.0.load(Ordering::Relaxed), 1);
+
+        for i in 0..100 {
+            let position = Position {
+                x: i as f32,
+                y: 0.0,
+            };
+            unsafe { storage.push(&position as *const Position as *const u8) };
+        }
+        assert_eq!(storage.as_slice::<Position>()[99].x, 99.0);
+        assert_eq!(allocator.0.load(Ordering::Relaxed), 1);
+
+        drop(storage);
+        assert_eq!(allocator.0.load(Ordering::Relaxed), 0);
+    }
+
     #[test]
     fn typed_storage_push_and_get() {
         let mut storage = TypedComponentStorage::<Position>::new();
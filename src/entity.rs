@@ -24,6 +24,7 @@
 //! The entity system uses a dual-ID approach:
 //! - **Ephemeral IDs** ([`EntityId`]): Fast, 64-bit IDs for runtime operations
 //! - **Stable IDs** ([`StableId`]): Persistent, 128-bit UUIDs for serialization
+//! - **Weak handles** ([`WeakEntity`]): Both, resolved through whichever is valid
 //!
 //! # Examples
 //!
@@ -45,10 +46,14 @@
 //! ```
 
 pub mod allocator;
+pub mod generator;
 pub mod id;
+pub mod weak;
 
 pub use allocator::EntityAllocator;
+pub use generator::StableIdGenerator;
 pub use id::{EntityId, StableId};
+pub use weak::WeakEntity;
 
 /// Error type for entity operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,6 +62,8 @@ pub enum EntityError {
     InvalidEntity,
     /// The stable ID is already in use.
     DuplicateStableId,
+    /// A string could not be parsed as a stable ID.
+    InvalidStableId,
 }
 
 impl std::fmt::Display for EntityError {
@@ -64,12 +71,32 @@ impl std::fmt::Display for EntityError {
         match self {
             EntityError::InvalidEntity => write!(f, "Invalid entity"),
             EntityError::DuplicateStableId => write!(f, "Stable ID already in use"),
+            EntityError::InvalidStableId => write!(f, "Invalid stable ID format"),
         }
     }
 }
 
 impl std::error::Error for EntityError {}
 
+/// A stable ID change, passed to remap observers.
+///
+/// See [`EntityManager::on_stable_id_remap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableIdRemap {
+    /// The entity whose stable ID changed
+    pub entity: EntityId,
+    /// The stable ID the entity had before
+    pub old: StableId,
+    /// The stable ID the entity has now
+    pub new: StableId,
+}
+
+/// Identifies a registered remap observer, for removing it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RemapObserverId(u64);
+
+type RemapObserver = Box<dyn FnMut(&StableIdRemap) + Send + Sync>;
+
 /// High-level entity manager that coordinates entity lifecycle operations.
 ///
 /// The `EntityManager` provides a convenient interface for:
@@ -85,10 +112,24 @@ impl std::error::Error for EntityError {}
 /// - Despawn: O(1)
 /// - Lookup: O(1)
 /// - Iteration: O(n) where n is the number of alive entities
-#[derive(Debug)]
 pub struct EntityManager {
     /// The underlying allocator that manages entity IDs
     allocator: EntityAllocator,
+
+    /// Callbacks notified when a stable ID is remapped
+    remap_observers: Vec<(RemapObserverId, RemapObserver)>,
+
+    /// Next remap observer ID to hand out
+    next_observer_id: u64,
+}
+
+impl std::fmt::Debug for EntityManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EntityManager")
+            .field("allocator", &self.allocator)
+            .field("remap_observers", &self.remap_observers.len())
+            .finish()
+    }
 }
 
 impl EntityManager {
@@ -104,6 +145,8 @@ impl EntityManager {
     pub fn new() -> Self {
         Self {
             allocator: EntityAllocator::new(),
+            remap_observers: Vec::new(),
+            next_observer_id: 0,
         }
     }
 
@@ -123,6 +166,8 @@ impl EntityManager {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             allocator: EntityAllocator::with_capacity(capacity),
+            remap_observers: Vec::new(),
+            next_observer_id: 0,
         }
     }
 
@@ -247,6 +292,13 @@ impl EntityManager {
         self.allocator.get_stable_id(entity)
     }
 
+    /// Borrows the stable ID of an entity.
+    ///
+    /// See [`EntityAllocator::stable_id_ref`].
+    pub fn stable_id_ref(&self, entity: EntityId) -> Option<&StableId> {
+        self.allocator.stable_id_ref(entity)
+    }
+
     /// Gets the ephemeral ID for a stable ID.
     ///
     /// This is useful when loading entities from persistence, where you have
@@ -273,6 +325,33 @@ impl EntityManager {
         self.allocator.get_entity_id(stable_id)
     }
 
+    /// Gets the ephemeral IDs for many stable IDs at once.
+    ///
+    /// One entry per input, in input order, with `None` for unknown stable
+    /// IDs. See [`EntityAllocator::get_entity_ids`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::EntityManager;
+    ///
+    /// let mut manager = EntityManager::new();
+    /// let (a, a_stable) = manager.spawn_with_stable_id();
+    /// let (b, b_stable) = manager.spawn_with_stable_id();
+    /// assert_eq!(manager.get_entity_ids(&[b_stable, a_stable]), vec![Some(b), Some(a)]);
+    /// ```
+    pub fn get_entity_ids(&self, stable_ids: &[StableId]) -> Vec<Option<EntityId>> {
+        self.allocator.get_entity_ids(stable_ids)
+    }
+
+    /// Gets the stable IDs for many entities at once.
+    ///
+    /// One entry per input, in input order, with `None` for entities that
+    /// are not alive. See [`EntityAllocator::get_stable_ids`].
+    pub fn get_stable_ids(&self, entities: &[EntityId]) -> Vec<Option<StableId>> {
+        self.allocator.get_stable_ids(entities)
+    }
+
     /// Returns the number of currently alive entities.
     ///
     /// # Examples
@@ -355,11 +434,86 @@ impl EntityManager {
         self.allocator.reserve(additional);
     }
 
+    /// Releases spare capacity held by the entity allocator.
+    ///
+    /// Returns the approximate number of bytes released.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        self.allocator.shrink_to_fit()
+    }
+
+    /// Replaces the source of stable IDs for entities spawned from now on.
+    ///
+    /// See [`EntityAllocator::set_id_generator`] for details.
+    pub fn set_id_generator(&mut self, generator: Box<dyn StableIdGenerator>) {
+        self.allocator.set_id_generator(generator);
+    }
+
+    /// Returns the number of entity slots retired after exhausting their
+    /// generation counter.
+    ///
+    /// See [`EntityAllocator::retired_slots`] for details.
+    pub fn retired_slots(&self) -> usize {
+        self.allocator.retired_slots()
+    }
+
+    /// Sets the number of freed slots kept aging before any is recycled.
+    ///
+    /// See [`EntityAllocator::set_min_free_slots`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::EntityManager;
+    ///
+    /// let mut manager = EntityManager::new();
+    /// manager.set_min_free_slots(1024);
+    /// ```
+    pub fn set_min_free_slots(&mut self, min_free_slots: usize) {
+        self.allocator.set_min_free_slots(min_free_slots);
+    }
+
+    /// Reserves an entity ID through a shared reference.
+    ///
+    /// The entity becomes alive once [`flush_reserved`](Self::flush_reserved)
+    /// is called. This lets code without exclusive access (for example systems
+    /// running in parallel) hand out real entity IDs immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::EntityManager;
+    ///
+    /// let mut manager = EntityManager::new();
+    /// let entity = manager.reserve_entity();
+    /// assert!(!manager.is_alive(entity));
+    ///
+    /// manager.flush_reserved();
+    /// assert!(manager.is_alive(entity));
+    /// ```
+    pub fn reserve_entity(&self) -> EntityId {
+        self.allocator.reserve_entity()
+    }
+
+    /// Returns the number of reserved entities that have not been flushed.
+    pub fn pending_reservations(&self) -> usize {
+        self.allocator.pending_reservations()
+    }
+
+    /// Turns all outstanding reservations into alive entities.
+    ///
+    /// # Returns
+    ///
+    /// The `(EntityId, StableId)` pairs of the entities that were flushed.
+    pub fn flush_reserved(&mut self) -> Vec<(EntityId, StableId)> {
+        self.allocator.flush_reserved()
+    }
+
     /// Spawns an entity with a specific stable ID.
     ///
     /// This is used during deserialization to restore entities with their
-    /// original stable IDs. If the stable ID already exists, this returns
-    /// an error to prevent ID conflicts.
+    /// original stable IDs, and is the insertion point for deterministic IDs
+    /// produced by [`StableId::derive`]. If the stable ID already exists, this
+    /// returns an error to prevent ID conflicts.
     ///
     /// # Arguments
     ///
@@ -378,6 +532,10 @@ impl EntityManager {
     /// let stable_id = StableId::from_raw(12345);
     /// let entity_id = manager.spawn_with_id(stable_id).unwrap();
     /// assert_eq!(manager.get_stable_id(entity_id), Some(stable_id));
+    ///
+    /// let scene = StableId::derive(StableId::from_raw(0), "scenes/intro");
+    /// let authored = manager.spawn_with_id(StableId::derive(scene, "camera")).unwrap();
+    /// assert!(manager.is_alive(authored));
     /// ```
     pub fn spawn_with_id(&mut self, stable_id: StableId) -> Result<EntityId, EntityError> {
         self.allocator.allocate_with_stable_id(stable_id)
@@ -386,7 +544,9 @@ impl EntityManager {
     /// Remaps an existing entity to a new stable ID.
     ///
     /// This is useful for resolving ID conflicts during load operations.
-    /// The old stable ID mapping is removed and replaced with the new one.
+    /// The old stable ID mapping is removed and replaced with the new one,
+    /// and every observer registered with
+    /// [`on_stable_id_remap`](Self::on_stable_id_remap) is notified.
     ///
     /// # Arguments
     ///
@@ -416,7 +576,59 @@ impl EntityManager {
         entity_id: EntityId,
         new_stable_id: StableId,
     ) -> Result<StableId, EntityError> {
-        self.allocator.remap_stable_id(entity_id, new_stable_id)
+        let old = self.allocator.remap_stable_id(entity_id, new_stable_id)?;
+        let remap = StableIdRemap {
+            entity: entity_id,
+            old,
+            new: new_stable_id,
+        };
+        for (_, observer) in &mut self.remap_observers {
+            observer(&remap);
+        }
+        Ok(old)
+    }
+
+    /// Registers a callback that is called with the old and new stable ID
+    /// whenever an entity's stable ID is remapped.
+    ///
+    /// Use it to keep external references keyed by stable ID, such as
+    /// database rows or network session maps, in step with remaps done
+    /// while loading or merging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::{EntityManager, StableId};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut manager = EntityManager::new();
+    /// let remaps = Arc::new(Mutex::new(Vec::new()));
+    /// let log = Arc::clone(&remaps);
+    /// manager.on_stable_id_remap(move |remap| log.lock().unwrap().push((remap.old, remap.new)));
+    ///
+    /// let (entity, old) = manager.spawn_with_stable_id();
+    /// let new = StableId::from_raw(7);
+    /// manager.remap_stable_id(entity, new).unwrap();
+    /// assert_eq!(*remaps.lock().unwrap(), vec![(old, new)]);
+    /// ```
+    pub fn on_stable_id_remap(
+        &mut self,
+        observer: impl FnMut(&StableIdRemap) + Send + Sync + 'static,
+    ) -> RemapObserverId {
+        let id = RemapObserverId(self.next_observer_id);
+        self.next_observer_id += 1;
+        self.remap_observers.push((id, Box::new(observer)));
+        id
+    }
+
+    /// Unregisters a remap observer.
+    ///
+    /// Returns `false` if the observer was already removed.
+    pub fn remove_remap_observer(&mut self, id: RemapObserverId) -> bool {
+        let len = self.remap_observers.len();
+        self.remap_observers
+            .retain(|(observer_id, _)| *observer_id != id);
+        self.remap_observers.len() != len
     }
 
     /// Returns an iterator over all alive entities and their stable IDs.
@@ -506,6 +718,36 @@ mod tests {
         assert_eq!(manager.get_entity_id(old_stable_id), None);
     }
 
+    #[test]
+    fn remap_observers_are_notified() {
+        use std::sync::{Arc, Mutex};
+
+        let mut manager = EntityManager::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let observer = manager.on_stable_id_remap(move |remap| log.lock().unwrap().push(*remap));
+
+        let (entity, old) = manager.spawn_with_stable_id();
+        let new = StableId::from_raw(1234);
+        manager.remap_stable_id(entity, new).unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![StableIdRemap { entity, old, new }]
+        );
+
+        // Failed remaps are not reported
+        let (other, _) = manager.spawn_with_stable_id();
+        assert!(manager.remap_stable_id(other, new).is_err());
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        assert!(manager.remove_remap_observer(observer));
+        assert!(!manager.remove_remap_observer(observer));
+        manager
+            .remap_stable_id(entity, StableId::from_raw(5678))
+            .unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
     #[test]
     fn iter_manager_entities() {
         let mut manager = EntityManager::new();
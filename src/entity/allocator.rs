@@ -35,24 +35,78 @@
 //! ```
 
 use super::EntityError;
+use super::generator::{RandomIds, StableIdGenerator};
 use super::id::{EntityId, StableId};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+/// Number of consecutive in-use IDs a generator may return before allocation
+/// gives up.
+const MAX_GENERATOR_RETRIES: usize = 64;
 
 /// Metadata for an entity slot in the allocator.
+///
+/// The stable ID is stored bare next to an occupancy flag rather than as an
+/// `Option<StableId>`, which would pad a slot from 32 to 48 bytes. Two slots
+/// share a cache line, which is what batched lookups walk.
 #[derive(Debug, Clone)]
 struct EntityMeta {
+    /// The stable ID of the entity, or of the last one in a free slot
+    stable_id: StableId,
     /// The current generation for this slot
     generation: u32,
-    /// The stable ID associated with this entity (if allocated)
-    stable_id: Option<StableId>,
+    /// Whether an entity is allocated in this slot
+    occupied: bool,
+}
+
+impl EntityMeta {
+    /// Creates the metadata of a fresh slot holding an entity.
+    fn new(stable_id: StableId) -> Self {
+        Self {
+            stable_id,
+            generation: 1,
+            occupied: true,
+        }
+    }
+
+    /// Returns the stable ID if an entity is allocated in this slot.
+    #[inline]
+    fn stable_id(&self) -> Option<&StableId> {
+        self.occupied.then_some(&self.stable_id)
+    }
+
+    /// Records an entity allocated in this slot.
+    fn occupy(&mut self, stable_id: StableId) {
+        self.stable_id = stable_id;
+        self.occupied = true;
+    }
+
+    /// Marks this slot free.
+    fn vacate(&mut self) {
+        self.occupied = false;
+    }
 }
 
 /// Manages allocation and recycling of entity IDs.
 ///
 /// The allocator maintains:
-/// - A list of entity metadata (generation counters)
+/// - A list of entity metadata (generation counters and stable IDs), indexed
+///   directly by entity index so ephemeral to stable lookups never hash
 /// - A free list of recyclable entity indices
-/// - Bidirectional mapping between ephemeral and stable IDs
+/// - A hashed mapping from stable IDs back to ephemeral IDs
+///
+/// # Slot Aging
+///
+/// Freed slots are recycled in FIFO order, and only once more than
+/// [`min_free_slots`](Self::min_free_slots) slots are waiting. Spreading reuse
+/// across many slots keeps a hot spawn/despawn loop from cycling a single
+/// slot's 32-bit generation until stale references alias live entities.
+///
+/// A slot whose generation reaches `u32::MAX - 1` is retired when freed
+/// instead of wrapping back to an earlier generation, so a stale `EntityId`
+/// can never match a later occupant. Generation `u32::MAX` is never issued,
+/// which keeps [`EntityId::PLACEHOLDER`] distinct from every real entity. Retired slots are counted by
+/// [`retired_slots`](Self::retired_slots).
 ///
 /// # Performance
 ///
@@ -64,14 +118,29 @@ pub struct EntityAllocator {
     /// Metadata for all entity slots (allocated and free)
     meta: Vec<EntityMeta>,
 
-    /// Indices of free entity slots available for recycling
-    free_list: Vec<u32>,
+    /// Indices of free entity slots, oldest first
+    free_list: VecDeque<u32>,
+
+    /// Number of free slots kept aging before any of them is recycled
+    min_free_slots: usize,
 
-    /// Map from ephemeral ID to stable ID
-    ephemeral_to_stable: HashMap<EntityId, StableId>,
+    /// Number of slots whose generation is exhausted and never reused
+    retired_slots: usize,
 
-    /// Map from stable ID to ephemeral ID
+    /// Source of stable IDs for new entities
+    generator: Box<dyn StableIdGenerator>,
+
+    /// Map from stable ID to ephemeral ID (the ephemeral to stable direction
+    /// is served by `meta`, indexed by entity index)
     stable_to_ephemeral: HashMap<StableId, EntityId>,
+
+    /// Cursor for lock-free reservations made through `&self`.
+    ///
+    /// Equal to the number of recyclable free slots when nothing is reserved.
+    /// Each reservation decrements it: positive values hand out recyclable
+    /// slots from the front of the free list, negative values count fresh
+    /// slots reserved past the end of `meta`.
+    reserve_cursor: AtomicIsize,
 }
 
 impl EntityAllocator {
@@ -106,9 +175,93 @@ impl EntityAllocator {
         let initial_capacity = if capacity == 0 { 16 } else { capacity };
         Self {
             meta: Vec::with_capacity(initial_capacity),
-            free_list: Vec::new(),
-            ephemeral_to_stable: HashMap::with_capacity(initial_capacity),
+            free_list: VecDeque::new(),
+            min_free_slots: 0,
+            retired_slots: 0,
+            generator: Box::new(RandomIds),
             stable_to_ephemeral: HashMap::with_capacity(initial_capacity),
+            reserve_cursor: AtomicIsize::new(0),
+        }
+    }
+
+    /// Replaces the source of stable IDs for entities allocated from now on.
+    ///
+    /// Outstanding reservations are flushed first, so they keep IDs from
+    /// the previous generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    /// use pecs::entity::generator::SequentialIds;
+    /// use pecs::entity::id::StableId;
+    ///
+    /// let mut allocator = EntityAllocator::new();
+    /// allocator.set_id_generator(Box::new(SequentialIds::starting_at(7)));
+    /// let (_, stable_id) = allocator.allocate();
+    /// assert_eq!(stable_id, StableId::from_raw(7));
+    /// ```
+    pub fn set_id_generator(&mut self, generator: Box<dyn StableIdGenerator>) {
+        self.flush_reserved();
+        self.generator = generator;
+    }
+
+    /// Returns the number of free slots kept aging before recycling starts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let allocator = EntityAllocator::new();
+    /// assert_eq!(allocator.min_free_slots(), 0);
+    /// ```
+    pub fn min_free_slots(&self) -> usize {
+        self.min_free_slots
+    }
+
+    /// Sets the number of free slots kept aging before recycling starts.
+    ///
+    /// While the free list holds `min_free_slots` or fewer entries, new
+    /// entities get fresh slots instead. Larger values trade memory for more
+    /// time between reuses of any single slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_free_slots` - Minimum free-list length before a slot is reused
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let mut allocator = EntityAllocator::new();
+    /// allocator.set_min_free_slots(1);
+    ///
+    /// let (first, _) = allocator.allocate();
+    /// allocator.free(first);
+    ///
+    /// // Only one free slot is waiting, so a fresh slot is used
+    /// let (second, _) = allocator.allocate();
+    /// assert_ne!(second.index(), first.index());
+    /// ```
+    pub fn set_min_free_slots(&mut self, min_free_slots: usize) {
+        self.flush_reserved();
+        self.min_free_slots = min_free_slots;
+        self.sync_reserve_cursor();
+    }
+
+    /// Number of free slots currently eligible for recycling.
+    fn recyclable_slots(&self) -> usize {
+        self.free_list.len().saturating_sub(self.min_free_slots)
+    }
+
+    /// Takes the oldest free slot if enough slots are aging.
+    fn pop_recyclable(&mut self) -> Option<u32> {
+        if self.recyclable_slots() > 0 {
+            self.free_list.pop_front()
+        } else {
+            None
         }
     }
 
@@ -133,32 +286,138 @@ impl EntityAllocator {
     /// assert_eq!(entity_id.generation(), 1);
     /// ```
     pub fn allocate(&mut self) -> (EntityId, StableId) {
-        let stable_id = StableId::new();
+        self.flush_reserved();
+        let stable_id = self.next_stable_id();
 
-        let entity_id = if let Some(index) = self.free_list.pop() {
+        let entity_id = if let Some(index) = self.pop_recyclable() {
             // Recycle a free slot
             let meta = &mut self.meta[index as usize];
-            meta.generation = meta.generation.wrapping_add(1).max(1);
-            meta.stable_id = Some(stable_id);
+            meta.generation += 1;
+            meta.occupy(stable_id);
             EntityId::new(index, meta.generation)
         } else {
             // Allocate a new slot
             let index = self.meta.len() as u32;
-            self.meta.push(EntityMeta {
-                generation: 1,
-                stable_id: Some(stable_id),
-            });
+            self.meta.push(EntityMeta::new(stable_id));
             EntityId::new(index, 1)
         };
 
         // Update bidirectional mapping
-        // Using insert is fine here as we know these are new entries
-        self.ephemeral_to_stable.insert(entity_id, stable_id);
         self.stable_to_ephemeral.insert(stable_id, entity_id);
+        self.sync_reserve_cursor();
 
         (entity_id, stable_id)
     }
 
+    /// Reserves an entity ID without exclusive access to the allocator.
+    ///
+    /// The returned ID is final: it will be the ID of the entity once the
+    /// reservation is made real by [`flush_reserved`](Self::flush_reserved).
+    /// Until then the entity is not considered alive. Reservations can be
+    /// made concurrently from multiple threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let mut allocator = EntityAllocator::new();
+    /// let reserved = allocator.reserve_entity();
+    /// assert!(!allocator.is_alive(reserved));
+    ///
+    /// let flushed = allocator.flush_reserved();
+    /// assert_eq!(flushed[0].0, reserved);
+    /// assert!(allocator.is_alive(reserved));
+    /// ```
+    pub fn reserve_entity(&self) -> EntityId {
+        let n = self.reserve_cursor.fetch_sub(1, Ordering::Relaxed);
+        if n > 0 {
+            // Hand out a recycled slot, oldest first
+            let index = self.free_list[self.recyclable_slots() - n as usize];
+            let generation = self.meta[index as usize].generation + 1;
+            EntityId::new(index, generation)
+        } else {
+            // Hand out a fresh slot past the end of the metadata
+            let index = self.meta.len() + (-n) as usize;
+            EntityId::new(index as u32, 1)
+        }
+    }
+
+    /// Returns the number of reserved entities that have not been flushed.
+    pub fn pending_reservations(&self) -> usize {
+        let cursor = self.reserve_cursor.load(Ordering::Relaxed);
+        (self.recyclable_slots() as isize - cursor).max(0) as usize
+    }
+
+    /// Turns all outstanding reservations into allocated entities.
+    ///
+    /// Each reserved entity receives a freshly generated stable ID.
+    ///
+    /// # Returns
+    ///
+    /// The `(EntityId, StableId)` pairs of the entities that were flushed.
+    pub fn flush_reserved(&mut self) -> Vec<(EntityId, StableId)> {
+        let cursor = *self.reserve_cursor.get_mut();
+        let recyclable = self.recyclable_slots() as isize;
+        if cursor == recyclable {
+            return Vec::new();
+        }
+
+        let mut flushed = Vec::with_capacity((recyclable - cursor) as usize);
+
+        // Recycled slots were handed out from the front of the free list
+        let recycled_count = (recyclable - cursor.max(0)) as usize;
+        let recycled: Vec<u32> = self.free_list.drain(..recycled_count).collect();
+        for index in recycled {
+            let stable_id = self.next_stable_id();
+            let meta = &mut self.meta[index as usize];
+            meta.generation += 1;
+            meta.occupy(stable_id);
+            let entity_id = EntityId::new(index, meta.generation);
+            self.stable_to_ephemeral.insert(stable_id, entity_id);
+            flushed.push((entity_id, stable_id));
+        }
+
+        // Fresh slots follow the existing metadata
+        if cursor < 0 {
+            for _ in 0..(-cursor) {
+                let stable_id = self.next_stable_id();
+                let index = self.meta.len() as u32;
+                self.meta.push(EntityMeta::new(stable_id));
+                let entity_id = EntityId::new(index, 1);
+                self.stable_to_ephemeral.insert(stable_id, entity_id);
+                flushed.push((entity_id, stable_id));
+            }
+        }
+
+        self.sync_reserve_cursor();
+
+        flushed
+    }
+
+    /// Draws a stable ID from the generator that no live entity is using.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator returns IDs already in use
+    /// [`MAX_GENERATOR_RETRIES`] times in a row.
+    fn next_stable_id(&mut self) -> StableId {
+        for _ in 0..MAX_GENERATOR_RETRIES {
+            let stable_id = self.generator.generate();
+            if !self.stable_to_ephemeral.contains_key(&stable_id) {
+                return stable_id;
+            }
+        }
+        panic!(
+            "stable ID generator returned {MAX_GENERATOR_RETRIES} IDs in a row that are already in use"
+        );
+    }
+
+    /// Resets the reservation cursor after the free list changed.
+    fn sync_reserve_cursor(&mut self) {
+        *self.reserve_cursor.get_mut() = self.recyclable_slots() as isize;
+    }
+
     /// Reserves capacity for at least `additional` more entities.
     ///
     /// This can improve performance by reducing allocations when spawning
@@ -178,14 +437,51 @@ impl EntityAllocator {
     /// ```
     pub fn reserve(&mut self, additional: usize) {
         self.meta.reserve(additional);
-        self.ephemeral_to_stable.reserve(additional);
         self.stable_to_ephemeral.reserve(additional);
     }
 
+    /// Shrinks internal storage as close as possible to the live entity count.
+    ///
+    /// Slot metadata is kept for every slot ever handed out so that stale
+    /// `EntityId`s stay detectable; only spare capacity is released.
+    ///
+    /// # Returns
+    ///
+    /// The approximate number of bytes released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let mut allocator = EntityAllocator::with_capacity(1000);
+    /// allocator.allocate();
+    /// assert!(allocator.shrink_to_fit() > 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) -> usize {
+        self.flush_reserved();
+
+        let before = self.allocated_bytes();
+        self.meta.shrink_to_fit();
+        self.free_list.shrink_to_fit();
+        self.stable_to_ephemeral.shrink_to_fit();
+        before.saturating_sub(self.allocated_bytes())
+    }
+
+    /// Approximate heap bytes held by the allocator's collections.
+    fn allocated_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        self.meta.capacity() * size_of::<EntityMeta>()
+            + self.free_list.capacity() * size_of::<u32>()
+            + self.stable_to_ephemeral.capacity() * size_of::<(StableId, EntityId)>()
+    }
+
     /// Frees an entity, making its slot available for recycling.
     ///
-    /// The entity's generation is incremented to invalidate any existing
-    /// references to this entity.
+    /// The slot's generation is incremented when it is reused, invalidating
+    /// any existing references to this entity. A slot already at the last
+    /// generation is retired instead.
     ///
     /// # Arguments
     ///
@@ -206,6 +502,7 @@ impl EntityAllocator {
     /// assert!(!allocator.free(entity_id)); // Already freed
     /// ```
     pub fn free(&mut self, entity_id: EntityId) -> bool {
+        self.flush_reserved();
         let index = entity_id.index() as usize;
 
         // Validate the entity exists and matches generation
@@ -218,19 +515,23 @@ impl EntityAllocator {
             return false; // Stale reference
         }
 
-        if meta.stable_id.is_none() {
+        if meta.stable_id().is_none() {
             return false; // Already freed
         }
 
         // Remove from mappings
-        if let Some(stable_id) = meta.stable_id {
-            self.ephemeral_to_stable.remove(&entity_id);
+        if let Some(&stable_id) = meta.stable_id() {
             self.stable_to_ephemeral.remove(&stable_id);
         }
 
-        // Mark as free
-        self.meta[index].stable_id = None;
-        self.free_list.push(index as u32);
+        // Mark as free, retiring the slot if its generation is exhausted
+        self.meta[index].vacate();
+        if self.meta[index].generation >= u32::MAX - 1 {
+            self.retired_slots += 1;
+        } else {
+            self.free_list.push_back(index as u32);
+        }
+        self.sync_reserve_cursor();
 
         true
     }
@@ -264,7 +565,7 @@ impl EntityAllocator {
         }
 
         let meta = &self.meta[index];
-        meta.generation == entity_id.generation() && meta.stable_id.is_some()
+        meta.generation == entity_id.generation() && meta.occupied
     }
 
     /// Gets the stable ID for an entity.
@@ -287,7 +588,20 @@ impl EntityAllocator {
     /// assert_eq!(allocator.get_stable_id(entity_id), Some(stable_id));
     /// ```
     pub fn get_stable_id(&self, entity_id: EntityId) -> Option<StableId> {
-        self.ephemeral_to_stable.get(&entity_id).copied()
+        self.stable_id_ref(entity_id).copied()
+    }
+
+    /// Borrows the stable ID of an entity from the allocator's mapping.
+    ///
+    /// Same as [`get_stable_id`](Self::get_stable_id), but the reference
+    /// can outlive the lookup, which is what `&StableId` queries yield.
+    pub fn stable_id_ref(&self, entity_id: EntityId) -> Option<&StableId> {
+        let meta = self.meta.get(entity_id.index() as usize)?;
+        if meta.generation == entity_id.generation() {
+            meta.stable_id()
+        } else {
+            None
+        }
     }
 
     /// Gets the ephemeral ID for a stable ID.
@@ -313,6 +627,64 @@ impl EntityAllocator {
         self.stable_to_ephemeral.get(&stable_id).copied()
     }
 
+    /// Gets the ephemeral IDs for many stable IDs at once.
+    ///
+    /// The result holds one entry per input, in input order, with `None`
+    /// for stable IDs that belong to no live entity. The output is
+    /// allocated once and the mapping walked in a single pass, which suits
+    /// resolving the thousands of stable IDs in a replication packet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::StableId;
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let mut allocator = EntityAllocator::new();
+    /// let (a, a_stable) = allocator.allocate();
+    /// let (b, b_stable) = allocator.allocate();
+    ///
+    /// let ids = allocator.get_entity_ids(&[b_stable, StableId::new(), a_stable]);
+    /// assert_eq!(ids, vec![Some(b), None, Some(a)]);
+    /// ```
+    pub fn get_entity_ids(&self, stable_ids: &[StableId]) -> Vec<Option<EntityId>> {
+        let mut entity_ids = Vec::with_capacity(stable_ids.len());
+        entity_ids.extend(
+            stable_ids
+                .iter()
+                .map(|stable_id| self.stable_to_ephemeral.get(stable_id).copied()),
+        );
+        entity_ids
+    }
+
+    /// Gets the stable IDs for many entities at once.
+    ///
+    /// The result holds one entry per input, in input order, with `None`
+    /// for entities that are not alive. Lookups index the slot metadata
+    /// directly, without hashing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let mut allocator = EntityAllocator::new();
+    /// let (a, a_stable) = allocator.allocate();
+    /// let (b, _) = allocator.allocate();
+    /// allocator.free(b);
+    ///
+    /// assert_eq!(allocator.get_stable_ids(&[a, b]), vec![Some(a_stable), None]);
+    /// ```
+    pub fn get_stable_ids(&self, entity_ids: &[EntityId]) -> Vec<Option<StableId>> {
+        let mut stable_ids = Vec::with_capacity(entity_ids.len());
+        stable_ids.extend(
+            entity_ids
+                .iter()
+                .map(|&entity_id| self.get_stable_id(entity_id)),
+        );
+        stable_ids
+    }
+
     /// Returns the total number of allocated entities.
     ///
     /// # Examples
@@ -326,7 +698,7 @@ impl EntityAllocator {
     /// assert_eq!(allocator.len(), 1);
     /// ```
     pub fn len(&self) -> usize {
-        self.ephemeral_to_stable.len()
+        self.stable_to_ephemeral.len()
     }
 
     /// Returns `true` if no entities are allocated.
@@ -340,7 +712,7 @@ impl EntityAllocator {
     /// assert!(allocator.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool {
-        self.ephemeral_to_stable.is_empty()
+        self.stable_to_ephemeral.is_empty()
     }
 
     /// Returns the total capacity (allocated + free slots).
@@ -358,6 +730,26 @@ impl EntityAllocator {
         self.meta.len()
     }
 
+    /// Returns the number of slots retired because their generation counter
+    /// was exhausted.
+    ///
+    /// Each retired slot permanently costs one metadata entry. A steadily
+    /// growing count means some slot is being recycled far more often than
+    /// the rest; raising [`min_free_slots`](Self::min_free_slots) spreads
+    /// reuse out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::allocator::EntityAllocator;
+    ///
+    /// let allocator = EntityAllocator::new();
+    /// assert_eq!(allocator.retired_slots(), 0);
+    /// ```
+    pub fn retired_slots(&self) -> usize {
+        self.retired_slots
+    }
+
     /// Clears all entities, resetting the allocator to empty state.
     ///
     /// # Examples
@@ -373,8 +765,9 @@ impl EntityAllocator {
     pub fn clear(&mut self) {
         self.meta.clear();
         self.free_list.clear();
-        self.ephemeral_to_stable.clear();
+        self.retired_slots = 0;
         self.stable_to_ephemeral.clear();
+        self.sync_reserve_cursor();
     }
 
     /// Allocates an entity with a specific stable ID.
@@ -406,30 +799,29 @@ impl EntityAllocator {
         &mut self,
         stable_id: StableId,
     ) -> Result<EntityId, EntityError> {
+        self.flush_reserved();
+
         // Check if stable ID already exists
         if self.stable_to_ephemeral.contains_key(&stable_id) {
             return Err(EntityError::DuplicateStableId);
         }
 
-        let entity_id = if let Some(index) = self.free_list.pop() {
+        let entity_id = if let Some(index) = self.pop_recyclable() {
             // Recycle a free slot
             let meta = &mut self.meta[index as usize];
-            meta.generation = meta.generation.wrapping_add(1).max(1);
-            meta.stable_id = Some(stable_id);
+            meta.generation += 1;
+            meta.occupy(stable_id);
             EntityId::new(index, meta.generation)
         } else {
             // Allocate a new slot
             let index = self.meta.len() as u32;
-            self.meta.push(EntityMeta {
-                generation: 1,
-                stable_id: Some(stable_id),
-            });
+            self.meta.push(EntityMeta::new(stable_id));
             EntityId::new(index, 1)
         };
 
         // Update bidirectional mapping
-        self.ephemeral_to_stable.insert(entity_id, stable_id);
         self.stable_to_ephemeral.insert(stable_id, entity_id);
+        self.sync_reserve_cursor();
 
         Ok(entity_id)
     }
@@ -480,7 +872,10 @@ impl EntityAllocator {
             return Err(EntityError::InvalidEntity); // Stale reference
         }
 
-        let old_stable_id = meta.stable_id.ok_or(EntityError::InvalidEntity)?;
+        let old_stable_id = meta
+            .stable_id()
+            .copied()
+            .ok_or(EntityError::InvalidEntity)?;
 
         // Check if new stable ID is already in use
         if self.stable_to_ephemeral.contains_key(&new_stable_id) {
@@ -488,12 +883,10 @@ impl EntityAllocator {
         }
 
         // Remove old mapping
-        self.ephemeral_to_stable.remove(&entity_id);
         self.stable_to_ephemeral.remove(&old_stable_id);
 
         // Add new mapping
-        self.meta[index].stable_id = Some(new_stable_id);
-        self.ephemeral_to_stable.insert(entity_id, new_stable_id);
+        self.meta[index].occupy(new_stable_id);
         self.stable_to_ephemeral.insert(new_stable_id, entity_id);
 
         Ok(old_stable_id)
@@ -502,7 +895,7 @@ impl EntityAllocator {
     /// Returns an iterator over all alive entities and their stable IDs.
     ///
     /// This is useful for persistence operations that need to iterate
-    /// over all entities.
+    /// over all entities. Entities are yielded in ascending index order.
     ///
     /// # Examples
     ///
@@ -517,9 +910,10 @@ impl EntityAllocator {
     /// assert_eq!(entities.len(), 2);
     /// ```
     pub fn iter(&self) -> impl Iterator<Item = (EntityId, StableId)> + '_ {
-        self.ephemeral_to_stable
-            .iter()
-            .map(|(&entity_id, &stable_id)| (entity_id, stable_id))
+        self.meta.iter().enumerate().filter_map(|(index, meta)| {
+            meta.stable_id()
+                .map(|&stable_id| (EntityId::new(index as u32, meta.generation), stable_id))
+        })
     }
 }
 
@@ -533,6 +927,114 @@ impl Default for EntityAllocator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn generator_supplies_reserved_and_allocated_ids() {
+        use crate::entity::generator::SequentialIds;
+
+        let mut allocator = EntityAllocator::new();
+        allocator.set_id_generator(Box::new(SequentialIds::new()));
+        let reserved = allocator.reserve_entity();
+        let (_, allocated) = allocator.allocate();
+
+        assert_eq!(
+            allocator.get_stable_id(reserved),
+            Some(StableId::from_raw(1))
+        );
+        assert_eq!(allocated, StableId::from_raw(2));
+    }
+
+    /// Cycles through a fixed list of IDs, repeating them forever.
+    struct RepeatingIds(Vec<u128>, usize);
+
+    impl StableIdGenerator for RepeatingIds {
+        fn generate(&mut self) -> StableId {
+            self.1 += 1;
+            StableId::from_raw(self.0[(self.1 - 1) % self.0.len()])
+        }
+    }
+
+    #[test]
+    fn repeated_generator_ids_are_skipped() {
+        let mut allocator = EntityAllocator::new();
+        allocator.set_id_generator(Box::new(RepeatingIds(vec![1, 1, 2, 1, 2, 3], 0)));
+
+        let (a, first) = allocator.allocate();
+        let (b, second) = allocator.allocate();
+        let reserved = allocator.reserve_entity();
+        let flushed = allocator.flush_reserved();
+
+        assert_eq!(first, StableId::from_raw(1));
+        assert_eq!(second, StableId::from_raw(2));
+        assert_eq!(flushed, vec![(reserved, StableId::from_raw(3))]);
+        assert_eq!(allocator.len(), 3);
+        assert_eq!(allocator.get_entity_id(first), Some(a));
+        assert_eq!(allocator.get_entity_id(second), Some(b));
+    }
+
+    #[test]
+    #[should_panic(expected = "already in use")]
+    fn generator_stuck_on_one_id_panics() {
+        let mut allocator = EntityAllocator::new();
+        allocator.set_id_generator(Box::new(RepeatingIds(vec![5], 0)));
+        allocator.allocate();
+        allocator.allocate();
+    }
+
+    #[test]
+    fn exhausted_slot_is_retired() {
+        let mut allocator = EntityAllocator::new();
+        let (first, _) = allocator.allocate();
+        allocator.free(first);
+        allocator.meta[0].generation = u32::MAX - 2;
+
+        let (last, _) = allocator.allocate();
+        assert_eq!((last.index(), last.generation()), (0, u32::MAX - 1));
+        assert_ne!(last, EntityId::PLACEHOLDER);
+        assert!(allocator.free(last));
+        assert_eq!(allocator.retired_slots(), 1);
+
+        // The retired slot is skipped by both allocation paths
+        let reserved = allocator.reserve_entity();
+        assert_eq!(reserved.index(), 1);
+        allocator.flush_reserved();
+        let (fresh, _) = allocator.allocate();
+        assert_eq!(fresh.index(), 2);
+        assert!(!allocator.is_alive(first));
+        assert!(!allocator.is_alive(last));
+    }
+
+    #[test]
+    fn slot_metadata_fits_half_a_cache_line() {
+        assert_eq!(std::mem::size_of::<EntityMeta>(), 32);
+    }
+
+    #[test]
+    fn batched_lookups_match_single_lookups() {
+        let mut allocator = EntityAllocator::new();
+        let entities: Vec<_> = (0..64).map(|_| allocator.allocate()).collect();
+        for &(entity_id, _) in entities.iter().step_by(3) {
+            allocator.free(entity_id);
+        }
+        let (recycled, recycled_stable) = allocator.allocate();
+
+        let mut entity_ids: Vec<_> = entities.iter().map(|&(entity_id, _)| entity_id).collect();
+        let mut stable_ids: Vec<_> = entities.iter().map(|&(_, stable_id)| stable_id).collect();
+        entity_ids.push(recycled);
+        stable_ids.push(recycled_stable);
+
+        let expected: Vec<_> = stable_ids
+            .iter()
+            .map(|&stable_id| allocator.get_entity_id(stable_id))
+            .collect();
+        assert_eq!(allocator.get_entity_ids(&stable_ids), expected);
+        let expected: Vec<_> = entity_ids
+            .iter()
+            .map(|&entity_id| allocator.get_stable_id(entity_id))
+            .collect();
+        assert_eq!(allocator.get_stable_ids(&entity_ids), expected);
+        assert_eq!(expected.iter().filter(|id| id.is_none()).count(), 22);
+    }
+
     #[test]
     fn allocate_single_entity() {
         let mut allocator = EntityAllocator::new();
@@ -740,4 +1242,114 @@ mod tests {
         assert_eq!(entities.len(), 1);
         assert_eq!(entities[0], (e2, s2));
     }
+
+    #[test]
+    fn recycle_slots_in_fifo_order() {
+        let mut allocator = EntityAllocator::new();
+        let (e1, _) = allocator.allocate();
+        let (e2, _) = allocator.allocate();
+        allocator.free(e1);
+        allocator.free(e2);
+
+        let (r1, _) = allocator.allocate();
+        let (r2, _) = allocator.allocate();
+        assert_eq!(r1.index(), e1.index());
+        assert_eq!(r2.index(), e2.index());
+    }
+
+    #[test]
+    fn min_free_slots_delays_recycling() {
+        let mut allocator = EntityAllocator::new();
+        allocator.set_min_free_slots(2);
+
+        // Churn a single entity; slots age in the free list before reuse
+        let mut indices = Vec::new();
+        for _ in 0..6 {
+            let (entity, _) = allocator.allocate();
+            indices.push(entity.index());
+            allocator.free(entity);
+        }
+
+        assert_eq!(indices, vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(allocator.capacity(), 3);
+    }
+
+    #[test]
+    fn reserve_respects_min_free_slots() {
+        let mut allocator = EntityAllocator::new();
+        allocator.set_min_free_slots(1);
+        let (e1, _) = allocator.allocate();
+        let (e2, _) = allocator.allocate();
+        allocator.free(e1);
+        allocator.free(e2);
+
+        let recycled = allocator.reserve_entity();
+        let fresh = allocator.reserve_entity();
+        assert_eq!(recycled.index(), e1.index());
+        assert_eq!(fresh.index(), 2);
+
+        let flushed: Vec<_> = allocator
+            .flush_reserved()
+            .into_iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(flushed, vec![recycled, fresh]);
+        assert_eq!(allocator.pending_reservations(), 0);
+    }
+
+    #[test]
+    fn reserve_fresh_entities() {
+        let mut allocator = EntityAllocator::new();
+        allocator.allocate();
+
+        let r1 = allocator.reserve_entity();
+        let r2 = allocator.reserve_entity();
+        assert_eq!((r1.index(), r1.generation()), (1, 1));
+        assert_eq!((r2.index(), r2.generation()), (2, 1));
+        assert_eq!(allocator.pending_reservations(), 2);
+        assert!(!allocator.is_alive(r1));
+
+        let flushed: Vec<_> = allocator
+            .flush_reserved()
+            .into_iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(flushed, vec![r1, r2]);
+        assert!(allocator.is_alive(r1));
+        assert!(allocator.is_alive(r2));
+        assert_eq!(allocator.pending_reservations(), 0);
+        assert_eq!(allocator.len(), 3);
+    }
+
+    #[test]
+    fn reserve_recycled_entities() {
+        let mut allocator = EntityAllocator::new();
+        let (e1, _) = allocator.allocate();
+        allocator.free(e1);
+
+        let recycled = allocator.reserve_entity();
+        let fresh = allocator.reserve_entity();
+        assert_eq!((recycled.index(), recycled.generation()), (0, 2));
+        assert_eq!((fresh.index(), fresh.generation()), (1, 1));
+
+        let flushed: Vec<_> = allocator
+            .flush_reserved()
+            .into_iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(flushed, vec![recycled, fresh]);
+        assert!(!allocator.is_alive(e1));
+        assert!(allocator.is_alive(recycled));
+    }
+
+    #[test]
+    fn allocate_flushes_reservations() {
+        let mut allocator = EntityAllocator::new();
+        let reserved = allocator.reserve_entity();
+        let (allocated, _) = allocator.allocate();
+
+        assert!(allocator.is_alive(reserved));
+        assert_ne!(reserved, allocated);
+        assert_eq!(allocator.len(), 2);
+    }
 }
@@ -0,0 +1,260 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Stable ID generation schemes.
+//!
+//! The entity allocator draws the [`StableId`] of every new entity from a
+//! [`StableIdGenerator`]. The default, [`RandomIds`], matches
+//! [`StableId::new`]; [`SequentialIds`] produces small predictable IDs for
+//! tests and fixtures, and [`TimeOrderedIds`] produces UUIDv7 keys that sort
+//! by creation time. Install a generator with
+//! [`World::set_id_generator`](crate::World::set_id_generator) or
+//! [`WorldBuilder::id_generator`](crate::world::WorldBuilder::id_generator)
+//! to match the key scheme of a backing store.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//! use pecs::entity::generator::SequentialIds;
+//! use pecs::entity::id::StableId;
+//!
+//! let mut world = World::builder().id_generator(SequentialIds::new()).build();
+//! let entity = world.spawn_empty();
+//! assert_eq!(world.get_stable_id(entity), Some(StableId::from_raw(1)));
+//! ```
+
+use super::id::StableId;
+use crate::resource::Rng;
+use std::fmt;
+
+/// A source of stable IDs for newly allocated entities.
+///
+/// Generators must never return [`StableId::nil`] and should not repeat
+/// IDs. An ID already held by a live entity is discarded and the generator
+/// is asked again; allocation panics if it keeps repeating itself. IDs given
+/// explicitly, as when loading a save, bypass the generator.
+pub trait StableIdGenerator: Send + Sync {
+    /// Returns the stable ID for the next entity.
+    fn generate(&mut self) -> StableId;
+}
+
+impl fmt::Debug for dyn StableIdGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StableIdGenerator")
+    }
+}
+
+/// Process-unique IDs with a random prefix, as [`StableId::new`] creates.
+///
+/// This is the default generator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIds;
+
+impl StableIdGenerator for RandomIds {
+    fn generate(&mut self) -> StableId {
+        StableId::new()
+    }
+}
+
+/// Consecutive IDs counting up from a starting value.
+///
+/// Useful for tests and golden files, where IDs should not change between
+/// runs. Two worlds using sequential IDs will collide if their entities are
+/// ever merged.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::entity::generator::{SequentialIds, StableIdGenerator};
+/// use pecs::entity::id::StableId;
+///
+/// let mut ids = SequentialIds::starting_at(100);
+/// assert_eq!(ids.generate(), StableId::from_raw(100));
+/// assert_eq!(ids.generate(), StableId::from_raw(101));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SequentialIds {
+    next: u128,
+}
+
+impl SequentialIds {
+    /// Creates a generator whose first ID is `1`.
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Creates a generator whose first ID is `first`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `first` is zero, the nil ID.
+    pub fn starting_at(first: u128) -> Self {
+        assert!(first != 0, "sequential stable IDs cannot start at nil");
+        Self { next: first }
+    }
+}
+
+impl Default for SequentialIds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StableIdGenerator for SequentialIds {
+    fn generate(&mut self) -> StableId {
+        let id = StableId::from_raw(self.next);
+        // Skip nil when the counter wraps
+        self.next = self.next.wrapping_add(1).max(1);
+        id
+    }
+}
+
+/// Time-ordered UUIDv7 IDs, for database-backed entity stores.
+///
+/// Each ID starts with the creation time in Unix milliseconds, so IDs sort
+/// roughly by creation and new keys land at the end of B-tree indexes
+/// instead of scattering across them. Within one generator, IDs are
+/// strictly increasing: a 12-bit counter orders IDs created in the same
+/// millisecond, and the timestamp is advanced if the clock stalls or steps
+/// backwards. The remaining 62 bits are pseudo-random.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs::entity::generator::TimeOrderedIds;
+///
+/// let mut world = World::builder().id_generator(TimeOrderedIds::new()).build();
+/// let first = world.spawn_empty();
+/// let second = world.spawn_empty();
+///
+/// let first = world.get_stable_id(first).unwrap();
+/// let second = world.get_stable_id(second).unwrap();
+/// assert_eq!(first.as_uuid().get_version_num(), 7);
+/// assert!(first < second);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeOrderedIds {
+    rng: Rng,
+    clock: fn() -> u64,
+    /// Timestamp of the last ID, in Unix milliseconds
+    last_millis: u64,
+    /// Counter of the last ID within its millisecond
+    counter: u16,
+}
+
+/// Largest value of the 12-bit counter field.
+const MAX_COUNTER: u16 = 0xFFF;
+
+impl TimeOrderedIds {
+    /// Creates a generator seeded from the current time and thread.
+    pub fn new() -> Self {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hash, Hasher};
+
+        let mut hasher = RandomState::new().build_hasher();
+        std::thread::current().id().hash(&mut hasher);
+        chrono::Utc::now().timestamp_nanos_opt().hash(&mut hasher);
+        Self::seeded(hasher.finish())
+    }
+
+    /// Creates a generator whose random bits come from `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng: Rng::seed_from_u64(seed),
+            clock: unix_millis,
+            last_millis: 0,
+            counter: 0,
+        }
+    }
+
+    /// Reads the timestamp from `clock` instead of the system time.
+    ///
+    /// `clock` returns Unix milliseconds; only the low 48 bits are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::generator::{StableIdGenerator, TimeOrderedIds};
+    ///
+    /// let mut ids = TimeOrderedIds::seeded(1).with_clock(|| 1_700_000_000_000);
+    /// let id = ids.generate().as_uuid();
+    /// assert_eq!(id.get_timestamp().unwrap().to_unix().0, 1_700_000_000);
+    /// ```
+    pub fn with_clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Default for TimeOrderedIds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The current time in Unix milliseconds.
+fn unix_millis() -> u64 {
+    // chrono rather than SystemTime, which panics on wasm32-unknown-unknown
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+impl StableIdGenerator for TimeOrderedIds {
+    fn generate(&mut self) -> StableId {
+        let now = (self.clock)() & 0xFFFF_FFFF_FFFF;
+        if now > self.last_millis {
+            self.last_millis = now;
+            // Start low in the range to leave room for IDs in this millisecond
+            self.counter = (self.rng.next_u64() as u16) & (MAX_COUNTER >> 1);
+        } else if self.counter < MAX_COUNTER {
+            self.counter += 1;
+        } else {
+            self.last_millis += 1;
+            self.counter = 0;
+        }
+
+        let random = self.rng.next_u64() & ((1 << 62) - 1);
+        let value = ((self.last_millis as u128) << 80)
+            | (0x7 << 76)
+            | ((self.counter as u128) << 64)
+            | (0b10 << 62)
+            | random as u128;
+        StableId::from_raw(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_ordered_ids_stay_increasing_when_the_clock_stalls() {
+        let mut ids = TimeOrderedIds::seeded(42).with_clock(|| 5);
+        let generated: Vec<_> = (0..10_000).map(|_| ids.generate()).collect();
+
+        assert!(generated.windows(2).all(|pair| pair[0] < pair[1]));
+        for id in &generated {
+            let uuid = id.as_uuid();
+            assert_eq!(uuid.get_version_num(), 7);
+            assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+        }
+        // The counter overflowed into later milliseconds
+        let last = generated.last().unwrap().as_uuid();
+        assert!(last.get_timestamp().is_some());
+        assert!(generated.last().unwrap().as_u128() >> 80 > 5);
+    }
+}
@@ -34,8 +34,11 @@
 //! let stable_id = StableId::new();
 //! ```
 
+use super::EntityError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::num::NonZeroU64;
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// A fast, ephemeral entity identifier optimized for runtime operations.
@@ -54,10 +57,31 @@ use uuid::Uuid;
 /// - Size: 8 bytes
 /// - Copy: Yes (trivial copy)
 /// - Lookup: O(1)
+///
+/// # Niche
+///
+/// Generation 0 is never issued, so the raw value is never zero. The compiler
+/// uses that as a niche, making `Option<EntityId>` the same 8 bytes as
+/// `EntityId` itself. Components holding optional entity references should
+/// prefer `Option<EntityId>` over a separate flag.
+///
+/// ```
+/// use pecs::entity::id::EntityId;
+///
+/// assert_eq!(std::mem::size_of::<Option<EntityId>>(), 8);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EntityId(NonZeroU64);
 
 impl EntityId {
+    /// An `EntityId` that never refers to a live entity.
+    ///
+    /// Used as a stand-in where an ID must be returned before the real one is
+    /// known, such as entities spawned through a command buffer. It uses the
+    /// maximum generation, which the allocator never hands out: slots are
+    /// retired one generation earlier.
+    pub const PLACEHOLDER: Self = Self(NonZeroU64::MAX);
+
     /// Creates a new `EntityId` from an index and generation.
     ///
     /// # Arguments
@@ -178,8 +202,12 @@ impl fmt::Display for EntityId {
 ///
 /// # Format
 ///
-/// Uses UUID v4 (random) format for maximum uniqueness guarantees.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Uses UUID v4 (random) format for maximum uniqueness guarantees. The
+/// string form is the canonical hyphenated UUID
+/// (`550e8400-e29b-41d4-a716-446655440000`), produced by `Display` and
+/// accepted by `FromStr`. With serde, human-readable formats use the string
+/// form and binary formats use the raw `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StableId(u128);
 
 impl StableId {
@@ -215,7 +243,8 @@ impl StableId {
             let random_state = RandomState::new();
             let mut hasher = random_state.build_hasher();
             std::thread::current().id().hash(&mut hasher);
-            std::time::SystemTime::now().hash(&mut hasher);
+            // chrono rather than SystemTime, which panics on wasm32-unknown-unknown
+            chrono::Utc::now().timestamp_nanos_opt().hash(&mut hasher);
             let new_seed = hasher.finish();
 
             // Try to set the seed (only first thread succeeds)
@@ -232,6 +261,82 @@ impl StableId {
         Self(value)
     }
 
+    /// Returns the nil `StableId` (all bits zero).
+    ///
+    /// The nil ID is never produced by [`new`](Self::new) and can be used as a
+    /// sentinel for "no entity" in external systems.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::id::StableId;
+    ///
+    /// assert!(StableId::nil().is_nil());
+    /// assert_eq!(StableId::nil().to_string(), "00000000-0000-0000-0000-000000000000");
+    /// ```
+    #[inline]
+    pub const fn nil() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if this is the nil `StableId`.
+    #[inline]
+    pub const fn is_nil(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Derives a deterministic `StableId` from a namespace and a name.
+    ///
+    /// The same `namespace` and `name` always produce the same ID (UUID v5,
+    /// SHA-1 based), which lets content pipelines assign reproducible stable
+    /// IDs to authored entities. Use a distinct namespace per asset or scene
+    /// to keep names from colliding.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace the name is scoped to
+    /// * `name` - The name to derive the ID from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::id::StableId;
+    ///
+    /// let level = StableId::derive(StableId::from_raw(0), "levels/forest");
+    /// let door = StableId::derive(level, "north_door");
+    ///
+    /// assert_eq!(door, StableId::derive(level, "north_door"));
+    /// assert_ne!(door, StableId::derive(level, "south_door"));
+    /// ```
+    pub fn derive(namespace: StableId, name: &str) -> Self {
+        Self::from_uuid(Uuid::new_v5(&namespace.as_uuid(), name.as_bytes()))
+    }
+
+    /// Derives the stable ID of a parent's sub-entity from its ordinal.
+    ///
+    /// Procedurally spawned sub-entities (the turrets of a ship) get the same
+    /// IDs every time their parent is rebuilt, so references to them survive
+    /// save and load. The ID is a UUID v5 in the parent's namespace and never
+    /// collides with the IDs [`derive`](Self::derive) produces from names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::entity::id::StableId;
+    ///
+    /// let ship = StableId::derive(StableId::nil(), "ships/frigate");
+    /// let turret = StableId::child_of(ship, 0);
+    ///
+    /// assert_eq!(turret, StableId::child_of(ship, 0));
+    /// assert_ne!(turret, StableId::child_of(ship, 1));
+    /// ```
+    pub fn child_of(parent: StableId, ordinal: u64) -> Self {
+        // Names from `derive` are UTF-8, which never starts with 0xFF
+        let mut name = [0xFF; 9];
+        name[1..].copy_from_slice(&ordinal.to_be_bytes());
+        Self::from_uuid(Uuid::new_v5(&parent.as_uuid(), &name))
+    }
+
     /// Creates a `StableId` from a raw 128-bit value.
     ///
     /// Useful for deserialization or testing.
@@ -332,7 +437,42 @@ impl Default for StableId {
 
 impl fmt::Display for StableId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:032x}", self.0)
+        fmt::Display::fmt(&self.as_uuid().hyphenated(), f)
+    }
+}
+
+impl FromStr for StableId {
+    type Err = EntityError;
+
+    /// Parses a `StableId` from a UUID string.
+    ///
+    /// Accepts the canonical hyphenated form as well as the simple (32 hex
+    /// digits), braced, and URN forms.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s)
+            .map(Self::from_uuid)
+            .map_err(|_| EntityError::InvalidStableId)
+    }
+}
+
+impl Serialize for StableId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u128(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StableId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(serde::de::Error::custom)
+        } else {
+            u128::deserialize(deserializer).map(Self)
+        }
     }
 }
 
@@ -382,6 +522,19 @@ mod tests {
         EntityId::new(0, 0);
     }
 
+    #[test]
+    fn entity_id_option_is_niche_packed() {
+        assert_eq!(std::mem::size_of::<EntityId>(), 8);
+        assert_eq!(std::mem::size_of::<Option<EntityId>>(), 8);
+    }
+
+    #[test]
+    fn entity_id_placeholder() {
+        assert_eq!(EntityId::PLACEHOLDER.index(), u32::MAX);
+        assert_eq!(EntityId::PLACEHOLDER.generation(), u32::MAX);
+        assert_ne!(EntityId::PLACEHOLDER, EntityId::new(0, 1));
+    }
+
     #[test]
     fn stable_id_creation() {
         let id = StableId::new();
@@ -414,7 +567,38 @@ mod tests {
     fn stable_id_display() {
         let id = StableId::from_raw(0x12345678_90abcdef_12345678_90abcdef);
         let display = format!("{}", id);
-        assert_eq!(display.len(), 32); // 32 hex characters
+        assert_eq!(display, "12345678-90ab-cdef-1234-567890abcdef");
+    }
+
+    #[test]
+    fn stable_id_from_str() {
+        let id = StableId::from_raw(0x12345678_90abcdef_12345678_90abcdef);
+
+        assert_eq!("12345678-90ab-cdef-1234-567890abcdef".parse(), Ok(id));
+        assert_eq!("1234567890abcdef1234567890abcdef".parse(), Ok(id));
+        assert_eq!(id.to_string().parse(), Ok(id));
+        assert_eq!(
+            "not-a-uuid".parse::<StableId>(),
+            Err(EntityError::InvalidStableId)
+        );
+    }
+
+    #[test]
+    fn stable_id_nil() {
+        assert_eq!(StableId::nil().to_raw(), 0);
+        assert!(StableId::nil().is_nil());
+        assert!(!StableId::new().is_nil());
+    }
+
+    #[test]
+    fn stable_id_serde_json() {
+        let id = StableId::from_raw(0x550e8400_e29b_41d4_a716_446655440000);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"550e8400-e29b-41d4-a716-446655440000\"");
+
+        let restored: StableId = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, id);
+        assert!(serde_json::from_str::<StableId>("\"bogus\"").is_err());
     }
 
     #[test]
@@ -426,6 +610,30 @@ mod tests {
         assert_eq!(uuid.as_u128(), id.as_u128());
     }
 
+    #[test]
+    fn stable_id_derive_is_deterministic() {
+        let namespace = StableId::from_raw(0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+        let a = StableId::derive(namespace, "player");
+        let b = StableId::derive(namespace, "player");
+
+        assert_eq!(a, b);
+        assert_eq!(a.as_uuid().get_version_num(), 5);
+        assert_ne!(a, StableId::derive(namespace, "enemy"));
+        assert_ne!(a, StableId::derive(a, "player"));
+    }
+
+    #[test]
+    fn stable_id_child_of_is_deterministic() {
+        let parent = StableId::from_raw(0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+        let child = StableId::child_of(parent, 3);
+
+        assert_eq!(child, StableId::child_of(parent, 3));
+        assert_eq!(child.as_uuid().get_version_num(), 5);
+        assert_ne!(child, StableId::child_of(parent, 4));
+        assert_ne!(child, StableId::child_of(StableId::child_of(parent, 0), 3));
+        assert_ne!(child, StableId::derive(parent, "3"));
+    }
+
     #[test]
     fn stable_id_from_uuid() {
         use uuid::Uuid;
@@ -0,0 +1,192 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Weak entity handles that survive save and load.
+//!
+//! An [`EntityId`] is only meaningful within the session that issued it,
+//! while a [`StableId`] survives persistence but costs a hash lookup to
+//! resolve. [`WeakEntity`] carries both: it resolves through the entity ID
+//! while that is still valid, and falls back to the stable ID after the
+//! world was reloaded or the entity was remapped.
+
+use super::id::{EntityId, StableId};
+use crate::World;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A generation-checked handle to an entity that may no longer exist.
+///
+/// Gameplay code can hold a `WeakEntity` (in a component, a resource or
+/// elsewhere) without caring which ID space is currently valid.
+/// [`resolve`](Self::resolve) first checks the cached entity ID: it is used
+/// if that entity is alive, with the same generation, and still has the
+/// handle's stable ID. Otherwise the stable ID is looked up, which finds
+/// the entity again after save and load.
+///
+/// A `WeakEntity` serializes as its stable ID alone, so components holding
+/// one persist like any other. A deserialized handle has no cached entity
+/// ID until it is [refreshed](Self::refresh).
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+///
+/// let mut world = World::new();
+/// let target = world.spawn_empty();
+/// let handle = world.weak(target).unwrap();
+/// assert_eq!(handle.resolve(&world), Some(target));
+///
+/// // The handle outlives the world it was taken from
+/// let mut bytes = Vec::new();
+/// world.save_json(&mut bytes).unwrap();
+/// let loaded = World::load_json(&mut bytes.as_slice()).unwrap();
+/// let reloaded = handle.resolve(&loaded).unwrap();
+/// assert_eq!(loaded.get_stable_id(reloaded), Some(handle.stable_id()));
+///
+/// world.despawn(target);
+/// assert_eq!(handle.resolve(&world), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeakEntity {
+    /// Entity ID last seen for the stable ID, or the placeholder if unknown
+    entity: EntityId,
+
+    /// Stable ID of the entity
+    stable_id: StableId,
+}
+
+impl WeakEntity {
+    /// Creates a handle from an entity ID and its stable ID.
+    ///
+    /// Prefer [`World::weak`], which looks the stable ID up. A mismatched
+    /// pair is harmless: the entity ID is only used while it still maps to
+    /// the stable ID.
+    pub fn new(entity: EntityId, stable_id: StableId) -> Self {
+        Self { entity, stable_id }
+    }
+
+    /// Creates a handle from a stable ID alone.
+    ///
+    /// The first [`resolve`](Self::resolve) looks the stable ID up.
+    pub fn from_stable_id(stable_id: StableId) -> Self {
+        Self::new(EntityId::PLACEHOLDER, stable_id)
+    }
+
+    /// Returns the stable ID this handle refers to.
+    pub fn stable_id(&self) -> StableId {
+        self.stable_id
+    }
+
+    /// Returns the cached entity ID, if any.
+    ///
+    /// The ID may be stale; use [`resolve`](Self::resolve) to get a live one.
+    pub fn entity_hint(&self) -> Option<EntityId> {
+        (self.entity != EntityId::PLACEHOLDER).then_some(self.entity)
+    }
+
+    /// Returns the live entity this handle refers to in `world`.
+    ///
+    /// Returns `None` if no entity in `world` has the handle's stable ID.
+    pub fn resolve(&self, world: &World) -> Option<EntityId> {
+        if world.get_stable_id(self.entity) == Some(self.stable_id) {
+            return Some(self.entity);
+        }
+        world.get_entity_id(self.stable_id)
+    }
+
+    /// Resolves the handle like [`resolve`](Self::resolve) and caches the
+    /// result, so later calls take the fast path again.
+    pub fn refresh(&mut self, world: &World) -> Option<EntityId> {
+        let entity = self.resolve(world);
+        if let Some(entity) = entity {
+            self.entity = entity;
+        }
+        entity
+    }
+
+    /// Returns `true` if the handle resolves to a live entity in `world`.
+    pub fn is_alive(&self, world: &World) -> bool {
+        self.resolve(world).is_some()
+    }
+}
+
+impl From<StableId> for WeakEntity {
+    fn from(stable_id: StableId) -> Self {
+        Self::from_stable_id(stable_id)
+    }
+}
+
+impl Serialize for WeakEntity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.stable_id.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for WeakEntity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StableId::deserialize(deserializer).map(Self::from_stable_id)
+    }
+}
+
+impl World {
+    /// Creates a [`WeakEntity`] handle to an entity.
+    ///
+    /// Returns `None` if the entity doesn't exist.
+    pub fn weak(&self, entity: EntityId) -> Option<WeakEntity> {
+        self.get_stable_id(entity)
+            .map(|stable_id| WeakEntity::new(entity, stable_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_generation_falls_back_to_stable_id() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        let mut handle = world.weak(entity).unwrap();
+
+        // A recycled slot with a new generation is not the same entity
+        world.despawn(entity);
+        let other = world.spawn_empty();
+        assert_eq!(handle.resolve(&world), None);
+
+        // Respawning under the old stable ID is found through the lookup
+        world.despawn(other);
+        let respawned = world
+            .spawn_empty_with_stable_id(handle.stable_id())
+            .unwrap();
+        assert_eq!(handle.entity_hint(), Some(entity));
+        assert_eq!(handle.refresh(&world), Some(respawned));
+        assert_eq!(handle.entity_hint(), Some(respawned));
+    }
+
+    #[test]
+    fn serializes_as_stable_id() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        let handle = world.weak(entity).unwrap();
+
+        let json = serde_json::to_value(handle).unwrap();
+        assert_eq!(json, serde_json::to_value(handle.stable_id()).unwrap());
+
+        let restored: WeakEntity = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.entity_hint(), None);
+        assert_eq!(restored.resolve(&world), Some(entity));
+    }
+}
@@ -0,0 +1,278 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Entity inspection for debug tools.
+//!
+//! This module is available with the `inspector` feature. An [`Inspector`]
+//! lists the entities of a world by label or [`StableId`], shows each
+//! component through its registered debug and JSON hooks, and applies live
+//! edits by re-inserting a component from JSON. It holds no UI code, so the
+//! same model can back an egui panel, a terminal view or a remote debugger.
+//! The `pecs-inspector` feature adds `InspectorPanel`, a ready-made egui
+//! panel over it.
+//!
+//! Components show their `Debug` output when registered with
+//! [`register_debug`](ComponentRegistry::register_debug) (or
+//! `#[component(debug)]`), and are editable when registered with
+//! [`register_json`](ComponentRegistry::register_json).
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::prelude::*;
+//! use pecs::component::registry::ComponentRegistry;
+//! use pecs::inspector::Inspector;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Health { current: u32 }
+//! impl Component for Health {}
+//!
+//! let registry = ComponentRegistry::global();
+//! registry.register_debug::<Health>();
+//! registry.register_json::<Health>("Health");
+//!
+//! let mut world = World::new();
+//! let entity = world.spawn().with(Health { current: 3 }).id();
+//!
+//! let inspector = Inspector::new();
+//! let row = inspector.inspect(&world, entity).unwrap();
+//! assert_eq!(row.components[0].debug, "Health { current: 3 }");
+//!
+//! inspector.edit(&mut world, entity, "Health", r#"{ "current": 9 }"#).unwrap();
+//! assert_eq!(world.get::<Health>(entity), Some(&Health { current: 9 }));
+//! ```
+
+#[cfg(feature = "pecs-inspector")]
+pub mod panel;
+
+#[cfg(feature = "pecs-inspector")]
+pub use panel::InspectorPanel;
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::{EntityId, StableId};
+use crate::persistence::{PersistenceError, Result};
+use crate::query::plan::short_type_name;
+
+/// Returns a display label for an entity, or `None` to fall back to its
+/// stable ID.
+pub type LabelFn = fn(&World, EntityId) -> Option<String>;
+
+/// One entity as shown by an [`Inspector`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityInspection {
+    /// The entity
+    pub entity: EntityId,
+    /// The entity's stable ID
+    pub stable_id: StableId,
+    /// Display label, the stable ID unless a label function names it
+    pub label: String,
+    /// The entity's components, sorted by name
+    pub components: Vec<ComponentInspection>,
+}
+
+/// One component as shown by an [`Inspector`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentInspection {
+    /// Short Rust type name
+    pub type_name: String,
+    /// Registered JSON name, `None` if the component can't be edited
+    pub name: Option<String>,
+    /// `Debug` output, or the type name if no debug hook is registered
+    pub debug: String,
+    /// Current value as JSON, if the component is JSON-registered
+    pub value: Option<serde_json::Value>,
+}
+
+impl ComponentInspection {
+    /// Returns whether the component can be edited with
+    /// [`Inspector::edit`].
+    pub fn is_editable(&self) -> bool {
+        self.name.is_some()
+    }
+
+    /// Returns the name shown for the component.
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.type_name)
+    }
+}
+
+/// A UI-agnostic entity inspector.
+#[derive(Debug, Clone, Default)]
+pub struct Inspector {
+    /// Case-insensitive text entities must contain in their label, stable
+    /// ID or a component name
+    filter: String,
+    /// Names entities for display
+    label: Option<LabelFn>,
+}
+
+impl Inspector {
+    /// Creates an inspector that shows every entity by stable ID.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names entities with `label`, for example from a `Name` component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::inspector::Inspector;
+    ///
+    /// struct Name(String);
+    /// impl Component for Name {}
+    ///
+    /// let inspector = Inspector::new().with_label(|world, entity| {
+    ///     world.get::<Name>(entity).map(|name| name.0.clone())
+    /// });
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Name("player".into())).id();
+    /// assert_eq!(inspector.inspect(&world, entity).unwrap().label, "player");
+    /// ```
+    pub fn with_label(mut self, label: LabelFn) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Sets the text listed entities must contain, ignoring case, in their
+    /// label, stable ID or a component name. An empty filter lists all.
+    pub fn set_filter(&mut self, filter: impl Into<String>) {
+        self.filter = filter.into().to_lowercase();
+    }
+
+    /// Returns the current filter text.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Lists the entities matching the filter, sorted by label.
+    pub fn entities(&self, world: &World) -> Vec<EntityInspection> {
+        let mut rows: Vec<_> = world
+            .iter_entities()
+            .filter_map(|(entity, _)| self.inspect(world, entity))
+            .filter(|row| self.matches(row))
+            .collect();
+        rows.sort_by(|a, b| a.label.cmp(&b.label).then(a.stable_id.cmp(&b.stable_id)));
+        rows
+    }
+
+    /// Inspects one entity, or returns `None` if it is not alive.
+    pub fn inspect(&self, world: &World, entity: EntityId) -> Option<EntityInspection> {
+        let view = world.entity_view(entity)?;
+        let stable_id = world.get_stable_id(entity)?;
+        let registry = ComponentRegistry::global();
+
+        let mut components = Vec::new();
+        view.for_each_component(|info, ptr| {
+            let name = registry.json_name_of(info.type_id());
+            let value = name
+                .as_deref()
+                .and_then(|name| registry.get_json(name, world, entity));
+            components.push(ComponentInspection {
+                type_name: short_type_name(info.type_name()),
+                name,
+                // SAFETY: `ptr` points to the entity's live component
+                debug: format!("{:?}", unsafe { info.debug(ptr) }),
+                value,
+            });
+        });
+        components.sort_by(|a, b| a.display_name().cmp(b.display_name()));
+
+        let label = self
+            .label
+            .and_then(|label| label(world, entity))
+            .unwrap_or_else(|| stable_id.to_string());
+        Some(EntityInspection {
+            entity,
+            stable_id,
+            label,
+            components,
+        })
+    }
+
+    /// Replaces a component of an entity with a value parsed from JSON
+    /// text, as typed into an edit field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::EntityNotFound`] if the entity is not
+    /// alive, [`PersistenceError::UnknownComponentType`] if `name` is not
+    /// JSON-registered, or a deserialization error if the text is not valid
+    /// JSON for the component.
+    pub fn edit(&self, world: &mut World, entity: EntityId, name: &str, text: &str) -> Result<()> {
+        let value = serde_json::from_str(text)
+            .map_err(|e| PersistenceError::from_source("Invalid component JSON", e))?;
+        let inserted = ComponentRegistry::global()
+            .insert_json(name, world, entity, value)
+            .map_err(|e| e.in_component(name))?;
+        if inserted {
+            Ok(())
+        } else {
+            Err(PersistenceError::EntityNotFound(entity))
+        }
+    }
+
+    fn matches(&self, row: &EntityInspection) -> bool {
+        self.filter.is_empty()
+            || row.label.to_lowercase().contains(&self.filter)
+            || row.stable_id.to_string().contains(&self.filter)
+            || row.components.iter().any(|component| {
+                component
+                    .display_name()
+                    .to_lowercase()
+                    .contains(&self.filter)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+
+    #[derive(Debug)]
+    struct Marker;
+    impl Component for Marker {}
+
+    #[test]
+    fn filter_matches_labels_and_components() {
+        let mut world = World::new();
+        let tagged = world.spawn().with(Marker).id();
+        world.spawn_empty();
+
+        let mut inspector = Inspector::new();
+        assert_eq!(inspector.entities(&world).len(), 2);
+
+        inspector.set_filter("MARKER");
+        let rows = inspector.entities(&world);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, tagged);
+        assert!(!rows[0].components[0].is_editable());
+        assert_eq!(rows[0].components[0].display_name(), "Marker");
+
+        let error = inspector
+            .edit(&mut world, tagged, "inspector::Missing", "{}")
+            .unwrap_err();
+        assert!(matches!(
+            error.root(),
+            PersistenceError::UnknownComponentType(_)
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! An egui panel over an [`Inspector`].
+//!
+//! Available with the `pecs-inspector` feature. [`InspectorPanel::show`]
+//! draws a filterable entity list next to the components of the selected
+//! entity. JSON-registered components get an edit field whose text is
+//! applied with [`Inspector::edit`] when the user clicks *Apply*.
+
+use super::{EntityInspection, Inspector};
+use crate::World;
+use crate::entity::EntityId;
+use std::collections::HashMap;
+
+/// An entity inspector panel for egui.
+///
+/// Keeps the filter text, the selected entity and unapplied edits between
+/// frames, so one panel should live as long as the window showing it.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::inspector::{Inspector, InspectorPanel};
+///
+/// let mut world = World::new();
+/// world.spawn_empty();
+///
+/// let mut panel = InspectorPanel::new(Inspector::new());
+/// let ctx = egui::Context::default();
+/// let _ = ctx.run(Default::default(), |ctx| {
+///     egui::CentralPanel::default().show(ctx, |ui| panel.show(ui, &mut world));
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InspectorPanel {
+    /// Lists and edits the entities
+    inspector: Inspector,
+    /// Filter text as typed
+    filter: String,
+    /// The entity whose components are shown
+    selected: Option<EntityId>,
+    /// Edited JSON text by component name, for the selected entity
+    drafts: HashMap<String, String>,
+    /// Outcome of the last edit, shown under the components
+    status: Option<Result<String, String>>,
+}
+
+impl InspectorPanel {
+    /// Creates a panel showing the entities `inspector` lists.
+    pub fn new(inspector: Inspector) -> Self {
+        let filter = inspector.filter().to_string();
+        Self {
+            inspector,
+            filter,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the selected entity.
+    pub fn selected(&self) -> Option<EntityId> {
+        self.selected
+    }
+
+    /// Selects the entity whose components are shown, discarding
+    /// unapplied edits.
+    pub fn select(&mut self, entity: Option<EntityId>) {
+        if self.selected != entity {
+            self.selected = entity;
+            self.drafts.clear();
+            self.status = None;
+        }
+    }
+
+    /// Draws the panel and applies any edit the user confirmed this frame.
+    pub fn show(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        ui.horizontal(|ui| {
+            ui.label("Filter");
+            if ui.text_edit_singleline(&mut self.filter).changed() {
+                self.inspector.set_filter(self.filter.as_str());
+            }
+        });
+        ui.separator();
+
+        let rows = self.inspector.entities(world);
+        if self.selected.is_some_and(|entity| !world.is_alive(entity)) {
+            self.select(None);
+        }
+
+        ui.columns(2, |columns| {
+            self.entity_list(&mut columns[0], &rows);
+            self.component_view(&mut columns[1], world);
+        });
+    }
+
+    /// Lists the entities, selecting one on click.
+    fn entity_list(&mut self, ui: &mut egui::Ui, rows: &[EntityInspection]) {
+        ui.weak(format!("{} entities", rows.len()));
+        egui::ScrollArea::vertical()
+            .id_salt("pecs_inspector_entities")
+            .show(ui, |ui| {
+                for row in rows {
+                    let selected = self.selected == Some(row.entity);
+                    if ui.selectable_label(selected, row.label.as_str()).clicked() {
+                        self.select(Some(row.entity));
+                    }
+                }
+            });
+    }
+
+    /// Shows the selected entity's components, with edit fields for the
+    /// JSON-registered ones.
+    fn component_view(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let Some(row) = self
+            .selected
+            .and_then(|entity| self.inspector.inspect(world, entity))
+        else {
+            ui.weak("No entity selected");
+            return;
+        };
+        ui.heading(row.label.as_str());
+        ui.weak(format!("{} / {}", row.stable_id, row.entity));
+
+        let mut apply = None;
+        egui::ScrollArea::vertical()
+            .id_salt("pecs_inspector_components")
+            .show(ui, |ui| {
+                for component in &row.components {
+                    egui::CollapsingHeader::new(component.display_name())
+                        .id_salt((row.entity, component.display_name()))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.monospace(component.debug.as_str());
+                            let (Some(name), Some(value)) = (&component.name, &component.value)
+                            else {
+                                return;
+                            };
+                            let draft = self.drafts.entry(name.clone()).or_insert_with(|| {
+                                serde_json::to_string_pretty(value).unwrap_or_default()
+                            });
+                            ui.code_editor(draft);
+                            ui.horizontal(|ui| {
+                                if ui.button("Apply").clicked() {
+                                    apply = Some((name.clone(), draft.clone()));
+                                }
+                                if ui.button("Reset").clicked() {
+                                    *draft =
+                                        serde_json::to_string_pretty(value).unwrap_or_default();
+                                }
+                            });
+                        });
+                }
+            });
+
+        if let Some((name, text)) = apply {
+            self.status = Some(match self.inspector.edit(world, row.entity, &name, &text) {
+                Ok(()) => {
+                    // Show the stored value, which may normalize the text
+                    self.drafts.remove(&name);
+                    Ok(format!("Applied {}", name))
+                }
+                Err(error) => Err(error.to_string()),
+            });
+        }
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.label(message.as_str());
+            }
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message.as_str());
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::component::registry::ComponentRegistry;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Charge(u32);
+    impl Component for Charge {}
+
+    fn frame(panel: &mut InspectorPanel, world: &mut World) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| panel.show(ui, world));
+        });
+    }
+
+    #[test]
+    fn selection_survives_frames_and_clears_on_despawn() {
+        ComponentRegistry::global().register_json::<Charge>("panel::Charge");
+        let mut world = World::new();
+        let entity = world.spawn().with(Charge(1)).id();
+
+        let mut panel = InspectorPanel::new(Inspector::new());
+        frame(&mut panel, &mut world);
+        assert_eq!(panel.selected(), None);
+
+        panel.select(Some(entity));
+        frame(&mut panel, &mut world);
+        assert_eq!(panel.selected(), Some(entity));
+        assert_eq!(
+            panel.drafts.get("panel::Charge").map(String::as_str),
+            Some("1")
+        );
+
+        world.despawn(entity);
+        frame(&mut panel, &mut world);
+        assert_eq!(panel.selected(), None);
+        assert!(panel.drafts.is_empty());
+    }
+}
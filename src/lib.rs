@@ -67,20 +67,38 @@
 //! - [`entity`]: Entity lifecycle management with dual ID system
 //! - [`component`]: Component storage and management
 //! - [`query`]: Type-safe component queries
+//! - [`resource`]: World-level singleton values
 //! - [`command`]: Thread-safe command buffers
+//! - [`replay`]: Deterministic replay recording
 //! - [`world`]: Top-level ECS world
 //! - [`persistence`]: Pluggable persistence system
+//! - [`bench_util`]: Benchmark scenarios usable as timing assertions
+//! - `spatial`: Uniform-grid spatial queries (requires the `spatial` feature)
 
+pub mod bench_util;
 pub mod bundle;
 pub mod command;
 pub mod component;
 pub mod entity;
+#[cfg(feature = "inspector")]
+pub mod inspector;
 pub mod persistence;
 pub mod query;
+pub mod replay;
+pub mod resource;
+#[cfg(feature = "spatial")]
+pub mod spatial;
 pub mod world;
 
-// Re-export the derive macro
-pub use pecs_derive::Component;
+// Re-export the derive macros
+pub use pecs_derive::{Bundle, Component, SerializableComponent};
+
+/// Items used by generated code. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
+    pub use serde;
+}
 
 /// Convenient re-exports for common types.
 ///
@@ -89,19 +107,20 @@ pub mod prelude {
     pub use crate::bundle::Bundle;
     pub use crate::command::{Command, CommandBuffer};
     pub use crate::component::Component;
-    pub use crate::entity::{EntityId, StableId};
+    pub use crate::entity::{EntityId, StableId, WeakEntity};
     pub use crate::world::World;
 
-    // Re-export derive macro
-    pub use pecs_derive::Component;
+    // Re-export derive macros
+    pub use pecs_derive::{Bundle, Component, SerializableComponent};
 }
 
 // Re-export commonly used types
 pub use bundle::Bundle;
 pub use command::{Command, CommandBuffer};
 pub use component::Component;
-pub use entity::{EntityId, EntityManager, StableId};
+pub use entity::{EntityId, EntityManager, StableId, WeakEntity};
 pub use query::{Fetch, Filter, Query};
+pub use resource::{Resource, Rng};
 pub use world::World;
 
 #[cfg(test)]
@@ -31,6 +31,13 @@
 //! - **Manager**: Coordinates save/load operations and plugin lifecycle
 //! - **Migration**: Handles version upgrades via the [`Migration`] trait
 //!
+//! # WebAssembly
+//!
+//! On `wasm32-unknown-unknown` the path-based `save`/`load` methods and the
+//! write-ahead `journal` module are not available, since browsers have no
+//! file system. Use the writer/reader APIs or the plugins in [`browser`]
+//! instead.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -48,20 +55,47 @@
 //! ```
 
 pub mod binary;
+pub mod browser;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod delta;
+pub mod diff;
 pub mod entity_kv;
 pub mod error;
+pub mod hydration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod journal;
 pub mod json;
 pub mod manager;
 pub mod metadata;
+pub mod options;
 pub mod plugin;
+pub mod scene;
+pub mod snapshot;
+pub mod toml;
+pub mod unknown;
+#[cfg(all(feature = "notify", not(target_arch = "wasm32")))]
+pub mod watch;
 
 pub use binary::BinaryPlugin;
+pub use diff::WorldDiff;
 pub use entity_kv::KeyValueEntityPlugin;
-pub use error::{PersistenceError, Result};
+pub use error::{ErrorContext, PersistenceError, PersistenceErrors, Result};
+pub use hydration::Unloaded;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use journal::JournalPlugin;
 pub use json::JsonPlugin;
 pub use manager::PersistenceManager;
-pub use metadata::{ChangeTracker, ComponentTypeInfo, WorldMetadata};
+pub use metadata::{
+    ChangeSet, ChangeTracker, ComponentLayout, ComponentTypeInfo, MetadataExtension, WorldMetadata,
+};
+pub use options::{ChecksumPolicy, LoadOptions, MissingComponentType, UnknownComponent};
 pub use plugin::{
     ComponentData, DeltaPersistencePlugin, EntityChange, EntityData, EntityPersistencePlugin,
-    Migration, PersistencePlugin, SerializableComponent,
+    Migration, PersistencePlugin, SaveInfo, SerializableComponent,
 };
+pub use scene::{Scene, SceneEntity, SceneParams};
+pub use snapshot::WorldSnapshot;
+pub use toml::TomlPlugin;
+#[cfg(all(feature = "notify", not(target_arch = "wasm32")))]
+pub use watch::{ReloadEvent, WorldWatcher, watch};
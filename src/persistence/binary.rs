@@ -32,6 +32,11 @@
 //! 3. Entity data with components
 //! 4. Footer with checksum
 //!
+//! Component payloads are the JSON of the type's codec registered with
+//! [`ComponentRegistry::register_json`](crate::component::registry::ComponentRegistry::register_json),
+//! which `#[derive(SerializableComponent)]` registers. Components of
+//! unregistered types are not saved.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -61,7 +66,7 @@ pub use format::{
 pub use serialize::BinarySerializer;
 
 use crate::World;
-use crate::persistence::{PersistenceError, PersistencePlugin};
+use crate::persistence::{LoadOptions, PersistenceError, PersistencePlugin, SaveInfo};
 use std::io::{Read, Write};
 
 /// Binary format persistence plugin.
@@ -120,10 +125,44 @@ impl BinaryPlugin {
         self
     }
 
+    /// Create a binary plugin that embeds a schema section.
+    ///
+    /// The section records the fields of every persistent component type
+    /// in the world, so external tools can tell what the saved JSON
+    /// payloads hold through [`read_info`](Self::read_info) and
+    /// [`SaveInfo::schema`].
+    pub fn with_schema(mut self) -> Self {
+        self.flags.set(FormatFlags::EXTENDED_METADATA);
+        self
+    }
+
+    /// Create a binary plugin that writes canonical output.
+    ///
+    /// Entities are written in [`StableId`](crate::entity::StableId) order
+    /// and component types and entity components in registered name order,
+    /// so a world always saves to the same bytes regardless of spawn order
+    /// or archetype layout. Useful for golden-file tests and scenes kept
+    /// under version control; pin the timestamp with
+    /// [`World::set_fixed_timestamp`] for fully reproducible files.
+    pub fn canonical(mut self) -> Self {
+        self.flags.set(FormatFlags::CANONICAL);
+        self
+    }
+
     /// Get the format flags.
     pub fn flags(&self) -> FormatFlags {
         self.flags
     }
+
+    /// Read the summary of a binary save, including its schema section,
+    /// without loading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header or metadata is invalid.
+    pub fn read_info(&self, reader: &mut dyn Read) -> Result<SaveInfo, PersistenceError> {
+        BinaryDeserializer::new().read_info(reader)
+    }
 }
 
 impl Default for BinaryPlugin {
@@ -143,6 +182,15 @@ impl PersistencePlugin for BinaryPlugin {
         deserializer.deserialize(reader)
     }
 
+    fn load_with_options(
+        &self,
+        reader: &mut dyn Read,
+        options: &LoadOptions,
+    ) -> Result<World, PersistenceError> {
+        let mut deserializer = BinaryDeserializer::with_options(*options);
+        deserializer.deserialize(reader)
+    }
+
     fn format_name(&self) -> &str {
         "binary"
     }
@@ -170,6 +218,36 @@ mod tests {
         assert!(plugin.flags().contains(FormatFlags::COMPRESSED_ZSTD));
     }
 
+    #[test]
+    fn test_binary_plugin_canonical_ignores_spawn_order() {
+        use crate::entity::StableId;
+
+        let build = |ids: &[u128]| {
+            let mut world = World::new();
+            world.set_fixed_timestamp(Some(1));
+            for &id in ids {
+                world
+                    .spawn_empty_with_stable_id(StableId::from_raw(id))
+                    .unwrap();
+            }
+            world
+        };
+        let mut forward = build(&[1, 2, 3]);
+        let mut backward = build(&[3, 2, 1]);
+        forward.update_metadata();
+        backward.update_metadata();
+
+        let save = |world: &World, plugin: &BinaryPlugin| {
+            let mut buffer = Vec::new();
+            plugin.save(world, &mut buffer).unwrap();
+            buffer
+        };
+        let plugin = BinaryPlugin::new();
+        assert_ne!(save(&forward, &plugin), save(&backward, &plugin));
+        let plugin = BinaryPlugin::new().canonical();
+        assert_eq!(save(&forward, &plugin), save(&backward, &plugin));
+    }
+
     #[test]
     fn test_binary_plugin_with_delta() {
         let plugin = BinaryPlugin::new().with_delta();
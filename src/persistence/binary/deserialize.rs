@@ -18,9 +18,16 @@
 //!
 //! This module handles deserializing ECS world state from the binary format.
 
-use super::format::{EntityData, Footer, Header, TypeRegistryEntry, calculate_checksum};
+use super::format::{
+    EntityData, Footer, FormatFlags, Header, MetadataBlock, TypeRegistryEntry, calculate_checksum,
+    capacity_for,
+};
 use crate::World;
-use crate::persistence::PersistenceError;
+use crate::component::registry::ComponentRegistry;
+use crate::persistence::metadata::ExtendedMetadata;
+use crate::persistence::options::{LimitedReader, SavedComponent, check_limit};
+use crate::persistence::unknown::{OpaqueComponent, UnknownComponents};
+use crate::persistence::{ChecksumPolicy, LoadOptions, PersistenceError, SaveInfo};
 use std::collections::HashMap;
 use std::io::Read;
 
@@ -31,13 +38,23 @@ use std::io::Read;
 pub struct BinaryDeserializer {
     /// Type registry mapping type IDs to names
     type_registry: HashMap<u128, TypeRegistryEntry>,
+
+    /// How strictly to load
+    options: LoadOptions,
 }
 
 impl BinaryDeserializer {
-    /// Create a new binary deserializer.
+    /// Create a new binary deserializer with [strict](LoadOptions::strict)
+    /// options.
     pub fn new() -> Self {
+        Self::with_options(LoadOptions::strict())
+    }
+
+    /// Create a binary deserializer that loads according to `options`.
+    pub fn with_options(options: LoadOptions) -> Self {
         Self {
             type_registry: HashMap::new(),
+            options,
         }
     }
 
@@ -49,14 +66,33 @@ impl BinaryDeserializer {
     /// - I/O operations fail
     /// - Format is invalid or corrupted
     /// - Version is unsupported
-    /// - Checksum validation fails
+    /// - Checksum validation fails, unless the options ignore checksums
+    /// - A component cannot be restored and the options demand strictness
+    /// - The save exceeds a size limit in the options
     pub fn deserialize(&mut self, reader: &mut dyn Read) -> Result<World, PersistenceError> {
+        // Compressed components may not inflate past the size limit either
+        #[cfg(feature = "compression")]
+        let _inflate_limit = self
+            .options
+            .max_component_bytes
+            .map(crate::persistence::compression::InflateLimitScope::new);
+
+        let mut reader = LimitedReader::new(reader, self.options.max_total_bytes);
+        let result = self.read_world(&mut reader);
+        // Running out of allowed input surfaces as a truncated stream, so
+        // report the limit instead
+        reader.check()?;
+        result
+    }
+
+    /// Reads and reconstructs the world, enforcing every limit but the
+    /// total size.
+    fn read_world(&mut self, reader: &mut dyn Read) -> Result<World, PersistenceError> {
         // Read all data into buffer for checksum validation
         let mut buffer = Vec::new();
 
         // Read header
-        let header =
-            Header::read(reader).map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        let header = Header::read(reader).map_err(|e| read_error("header", e).at_offset(0))?;
 
         // Store header bytes for checksum
         let mut header_buffer = Vec::new();
@@ -65,13 +101,29 @@ impl BinaryDeserializer {
             .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
         buffer.extend_from_slice(&header_buffer);
 
+        // Read extended metadata
+        let extended = if header.flags.contains(FormatFlags::EXTENDED_METADATA) {
+            let offset = buffer.len() as u64;
+            let block = MetadataBlock::read(reader)
+                .map_err(|e| read_error("metadata", e).at_offset(offset))?;
+            block
+                .write(&mut buffer)
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            serde_json::from_slice(&block.data).map_err(|e| {
+                PersistenceError::from_source("Failed to parse metadata", e).at_offset(offset)
+            })?
+        } else {
+            ExtendedMetadata::default()
+        };
+
         // Read type registry
         self.type_registry.clear();
         self.type_registry
-            .reserve(header.component_type_count as usize);
+            .reserve(capacity_for(header.component_type_count.into()));
         for _ in 0..header.component_type_count {
+            let offset = buffer.len() as u64;
             let entry = TypeRegistryEntry::read(reader)
-                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+                .map_err(|e| read_error("type registry entry", e).at_offset(offset))?;
 
             // Store entry bytes for checksum
             let mut entry_buffer = Vec::new();
@@ -83,11 +135,19 @@ impl BinaryDeserializer {
             self.type_registry.insert(entry.type_id, entry);
         }
 
-        // Read entity data - pre-allocate for better performance
-        let mut entities = Vec::with_capacity(header.entity_count as usize);
-        for _ in 0..header.entity_count {
-            let entity = EntityData::read(reader)
-                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        check_limit(
+            "max_entities",
+            self.options.max_entities.map(|max| max as u64),
+            header.entity_count,
+        )?;
+
+        // Read entity data - pre-allocate for better performance, but no
+        // more than a corrupt count could make us waste
+        let mut entities = Vec::with_capacity(capacity_for(header.entity_count));
+        for index in 0..header.entity_count {
+            let offset = buffer.len() as u64;
+            let entity = EntityData::read_limited(reader, self.options.max_component_bytes)
+                .map_err(|e| read_error("entity", e).at_offset(offset).at_entity(index))?;
 
             // Store entity bytes for checksum
             let mut entity_buffer = Vec::new();
@@ -100,12 +160,13 @@ impl BinaryDeserializer {
         }
 
         // Read footer
-        let footer =
-            Footer::read(reader).map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        let offset = buffer.len() as u64;
+        let footer = Footer::read(reader).map_err(|e| read_error("footer", e).at_offset(offset))?;
 
         // Validate checksum
         let calculated_checksum = calculate_checksum(&buffer);
-        if calculated_checksum != footer.checksum {
+        if calculated_checksum != footer.checksum && self.options.checksum == ChecksumPolicy::Verify
+        {
             return Err(PersistenceError::ChecksumMismatch {
                 expected: footer.checksum,
                 actual: calculated_checksum,
@@ -113,7 +174,39 @@ impl BinaryDeserializer {
         }
 
         // Reconstruct world
-        self.reconstruct_world(header, entities)
+        let mut world = self.reconstruct_world(header, entities)?;
+        world.metadata_mut().apply_extended(extended);
+        world.restore_resources()?;
+        world.metadata_mut().entity_count = world.len();
+        Ok(world)
+    }
+
+    /// Read the summary of a save without loading it.
+    ///
+    /// Only the header and extended metadata are read, so the checksum is
+    /// not validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header or metadata is invalid.
+    pub fn read_info(&mut self, reader: &mut dyn Read) -> Result<SaveInfo, PersistenceError> {
+        let header = Header::read(reader).map_err(|e| read_error("header", e).at_offset(0))?;
+
+        let extended: ExtendedMetadata = if header.flags.contains(FormatFlags::EXTENDED_METADATA) {
+            let block = MetadataBlock::read(reader).map_err(|e| read_error("metadata", e))?;
+            serde_json::from_slice(&block.data)
+                .map_err(|e| PersistenceError::from_source("Failed to parse metadata", e))?
+        } else {
+            ExtendedMetadata::default()
+        };
+
+        // Fills the compression statistics when the feature adds them
+        #[allow(clippy::needless_update)]
+        Ok(SaveInfo {
+            entities: header.entity_count as usize,
+            schema: extended.schema,
+            ..SaveInfo::default()
+        })
     }
 
     /// Reconstruct a world from deserialized data.
@@ -125,19 +218,22 @@ impl BinaryDeserializer {
         let mut world = World::new();
 
         // Restore entities
-        for entity_data in entities {
+        for (index, entity_data) in entities.into_iter().enumerate() {
+            let mut unknown = UnknownComponents::default();
+
             // Convert u128 back to StableId
             let stable_id = self.u128_to_stable_id(entity_data.stable_id);
 
             // Allocate entity with the stable ID
-            let _entity = world.entities_mut().spawn_with_id(stable_id).map_err(|e| {
-                PersistenceError::Deserialization(format!("Failed to allocate entity: {:?}", e))
+            let entity = world.entities_mut().spawn_with_id(stable_id).map_err(|e| {
+                PersistenceError::from_source("Failed to allocate entity", e)
+                    .at_entity(index as u64)
             })?;
 
             // Restore components
             for component_data in entity_data.components {
                 // Look up component type in registry
-                let _type_entry =
+                let type_entry =
                     self.type_registry
                         .get(&component_data.type_id)
                         .ok_or_else(|| {
@@ -145,12 +241,37 @@ impl BinaryDeserializer {
                                 "Unknown component type ID: {}",
                                 component_data.type_id
                             ))
+                            .at_entity(index as u64)
                         })?;
+                let name = &type_entry.type_name;
+                let context = |e: PersistenceError| e.at_entity(index as u64).in_component(name);
 
-                // TODO: Deserialize and insert component
-                // This requires a component deserialization registry
-                // For now, we just validate that the type exists
+                match self.options.resolve_component(name).map_err(context)? {
+                    SavedComponent::Decode => {
+                        let value = serde_json::from_slice(&component_data.data).map_err(|e| {
+                            context(PersistenceError::from_source("Invalid component data", e))
+                        })?;
+                        ComponentRegistry::global()
+                            .insert_json(name, &mut world, entity, value)
+                            .map_err(context)?;
+                    }
+                    SavedComponent::InsertDefault(insert) => {
+                        insert(&mut world, entity);
+                    }
+                    SavedComponent::Preserve => unknown.push(OpaqueComponent {
+                        name: name.clone(),
+                        version: type_entry.type_version,
+                        data: component_data.data,
+                    }),
+                    SavedComponent::Skip => {}
+                }
+            }
+            if !unknown.is_empty() {
+                world.insert(entity, unknown);
             }
+
+            // Fill in requirements added since the save was written
+            world.insert_missing_requirements(entity);
         }
 
         Ok(world)
@@ -162,6 +283,15 @@ impl BinaryDeserializer {
     }
 }
 
+/// Wraps a failure to read one section of the stream, passing through
+/// errors the format readers raised themselves.
+fn read_error(section: &str, error: std::io::Error) -> PersistenceError {
+    match error.downcast::<PersistenceError>() {
+        Ok(error) => error,
+        Err(error) => PersistenceError::from_source(format!("Failed to read {}", section), error),
+    }
+}
+
 impl Default for BinaryDeserializer {
     fn default() -> Self {
         Self::new()
@@ -245,6 +375,300 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_truncated_entity_reports_offset_and_index() {
+        let mut world = World::new();
+        world.spawn_empty();
+        world.spawn_empty();
+        let serializer = BinarySerializer::new(FormatFlags::NONE);
+
+        let mut buffer = Vec::new();
+        serializer.serialize(&world, &mut buffer).unwrap();
+        // Cut into the second entity (stable ID + component count each)
+        let second_entity = buffer.len() - 8 - 20;
+        buffer.truncate(second_entity + 4);
+
+        let mut deserializer = BinaryDeserializer::new();
+        let error = deserializer
+            .deserialize(&mut Cursor::new(buffer))
+            .err()
+            .unwrap();
+
+        let context = error.context().unwrap();
+        assert_eq!(context.offset, Some(second_entity as u64));
+        assert_eq!(context.entity_index, Some(1));
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    /// Builds a file with one entity carrying one component named `name`.
+    fn file_with_component(name: &str, data: &[u8]) -> Vec<u8> {
+        use super::super::format::ComponentData;
+
+        let mut body = Vec::new();
+        Header::new(1, 1).write(&mut body).unwrap();
+        TypeRegistryEntry::new(7, name.to_string(), 1)
+            .write(&mut body)
+            .unwrap();
+        let mut entity = EntityData::new(1);
+        entity.add_component(ComponentData::new(7, data.to_vec()));
+        entity.write(&mut body).unwrap();
+        let checksum = calculate_checksum(&body);
+        Footer::new(checksum).write(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn test_load_options_unknown_component() {
+        let file = file_with_component("pecs.test.Unregistered", b"{}");
+
+        let strict = BinaryDeserializer::new().deserialize(&mut file.as_slice());
+        let error = strict.err().unwrap();
+        assert!(matches!(
+            error.root(),
+            PersistenceError::UnknownComponentType(name) if name == "pecs.test.Unregistered"
+        ));
+        assert_eq!(
+            error.context().unwrap().component.as_deref(),
+            Some("pecs.test.Unregistered")
+        );
+
+        let options = LoadOptions {
+            unknown_component: crate::persistence::UnknownComponent::Skip,
+            ..LoadOptions::strict()
+        };
+        let world = BinaryDeserializer::with_options(options)
+            .deserialize(&mut file.as_slice())
+            .unwrap();
+        assert_eq!(world.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_components_survive_a_round_trip() {
+        use crate::persistence::unknown::UnknownComponents;
+
+        let file = file_with_component("pecs.test.FromNewerBuild", br#"{"mana":3}"#);
+        let mut world = BinaryDeserializer::with_options(LoadOptions::lenient())
+            .deserialize(&mut file.as_slice())
+            .unwrap();
+
+        let mut saved = Vec::new();
+        world.save_binary(&mut saved).unwrap();
+
+        let options = LoadOptions {
+            unknown_component: crate::persistence::UnknownComponent::Preserve,
+            ..LoadOptions::strict()
+        };
+        let reloaded = BinaryDeserializer::with_options(options)
+            .deserialize(&mut saved.as_slice())
+            .unwrap();
+        let (entity, _) = reloaded.iter_entities().next().unwrap();
+        let unknown = reloaded.get::<UnknownComponents>(entity).unwrap();
+        assert_eq!(unknown.len(), 1);
+        let preserved = unknown.get("pecs.test.FromNewerBuild").unwrap();
+        assert_eq!(preserved.data, br#"{"mana":3}"#);
+    }
+
+    #[test]
+    fn test_load_options_missing_codec_and_checksum() {
+        use crate::component::Component;
+        use crate::component::registry::ComponentRegistry;
+
+        #[derive(Debug, PartialEq)]
+        struct Stamina(u32);
+        impl Component for Stamina {}
+
+        let registry = ComponentRegistry::global();
+        registry.register_name::<Stamina>("pecs.test.Stamina");
+        registry.register_default(|| Stamina(50));
+
+        let mut file = file_with_component("pecs.test.Stamina", b"{}");
+        assert!(
+            BinaryDeserializer::new()
+                .deserialize(&mut file.as_slice())
+                .is_err()
+        );
+
+        // Lenient loading fills in the default and ignores the corruption
+        let last = file.len() - 1;
+        file[last] ^= 0xFF;
+        let world = BinaryDeserializer::with_options(LoadOptions::lenient())
+            .deserialize(&mut file.as_slice())
+            .unwrap();
+        let (entity, _) = world.iter_entities().next().unwrap();
+        assert_eq!(world.get::<Stamina>(entity), Some(&Stamina(50)));
+    }
+
+    #[test]
+    fn test_absurd_counts_fail_without_allocating() {
+        // A header claiming u64::MAX entities followed by nothing
+        let mut file = Vec::new();
+        Header::new(u64::MAX, 0).write(&mut file).unwrap();
+        assert!(
+            BinaryDeserializer::new()
+                .deserialize(&mut file.as_slice())
+                .is_err()
+        );
+
+        // A type name claiming 4 GiB
+        let mut file = Vec::new();
+        Header::new(0, 1).write(&mut file).unwrap();
+        file.extend_from_slice(&7u128.to_le_bytes());
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+        let error = BinaryDeserializer::new()
+            .deserialize(&mut file.as_slice())
+            .err()
+            .unwrap();
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+
+        // A component claiming 4 GiB of data
+        let mut file = file_with_component("pecs.test.Huge", b"{}");
+        let data_len = file.len() - 8 - 2 - 4;
+        file[data_len..data_len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(
+            BinaryDeserializer::new()
+                .deserialize(&mut file.as_slice())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let mut world = World::new();
+        for _ in 0..3 {
+            world.spawn_empty();
+        }
+        let mut file = Vec::new();
+        world.save_binary(&mut file).unwrap();
+
+        let limited = |options: LoadOptions, file: &[u8]| {
+            BinaryDeserializer::with_options(options).deserialize(&mut &file[..])
+        };
+        let exceeded = |result: Result<World, PersistenceError>| match result.err().unwrap().root()
+        {
+            &PersistenceError::LimitExceeded { limit, max, actual } => (limit, max, actual),
+            other => panic!("unexpected error: {}", other),
+        };
+
+        // Limits that fit load normally
+        let fits = LoadOptions {
+            max_entities: Some(3),
+            max_total_bytes: Some(file.len() as u64),
+            ..LoadOptions::strict()
+        };
+        assert_eq!(limited(fits, &file).unwrap().len(), 3);
+
+        let options = LoadOptions {
+            max_entities: Some(2),
+            ..LoadOptions::strict()
+        };
+        assert_eq!(exceeded(limited(options, &file)), ("max_entities", 2, 3));
+
+        let options = LoadOptions {
+            max_total_bytes: Some(file.len() as u64 - 1),
+            ..LoadOptions::strict()
+        };
+        let (limit, max, _) = exceeded(limited(options, &file));
+        assert_eq!((limit, max), ("max_total_bytes", file.len() as u64 - 1));
+
+        // An oversized component is refused from its length alone
+        let file = file_with_component("pecs.test.Limited", &[0; 64]);
+        let options = LoadOptions {
+            max_component_bytes: Some(63),
+            ..LoadOptions::lenient()
+        };
+        assert_eq!(
+            exceeded(limited(options, &file)),
+            ("max_component_bytes", 63, 64)
+        );
+        let data_len = file.len() - 8 - 64 - 4;
+        let mut huge = file.clone();
+        huge[data_len..data_len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        huge.truncate(data_len + 4);
+        assert_eq!(
+            exceeded(limited(options, &huge)),
+            ("max_component_bytes", 63, u32::MAX as u64)
+        );
+    }
+
+    #[test]
+    fn test_corrupt_files_return_errors() {
+        use crate::component::Component;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Mana(u32, String);
+        impl Component for Mana {}
+
+        ComponentRegistry::global().register_json::<Mana>("pecs.test.Mana");
+        let mut world = World::new();
+        for i in 0..3 {
+            world.spawn().with(Mana(i, "blue".to_string())).id();
+        }
+        world
+            .metadata_mut()
+            .custom
+            .insert("map".to_string(), "dust".to_string());
+        let mut file = Vec::new();
+        world.save_binary(&mut file).unwrap();
+
+        // Every truncation and every flipped byte must fail cleanly or load,
+        // never panic
+        for len in 0..file.len() {
+            let _ = BinaryDeserializer::with_options(LoadOptions::lenient())
+                .deserialize(&mut &file[..len]);
+        }
+        for index in 0..file.len() {
+            for flip in [0x01, 0x80, 0xFF] {
+                let mut corrupt = file.clone();
+                corrupt[index] ^= flip;
+                let _ = BinaryDeserializer::with_options(LoadOptions::lenient())
+                    .deserialize(&mut corrupt.as_slice());
+                let _ = BinaryDeserializer::new().read_info(&mut corrupt.as_slice());
+            }
+        }
+    }
+
+    #[test]
+    fn test_old_saves_gain_new_requirements() {
+        use crate::component::Component;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Lamp(u8);
+        impl Component for Lamp {}
+
+        #[derive(Debug, PartialEq)]
+        struct Light(f32);
+        impl Component for Light {}
+
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Lamp>("pecs.test.Lamp");
+        let mut world = World::new();
+        let lamp = world.spawn().with(Lamp(2)).id();
+        let stable_id = world.get_stable_id(lamp).unwrap();
+        let mut file = Vec::new();
+        world.save_binary(&mut file).unwrap();
+
+        // A later version makes every lamp give light
+        registry.register_default(|| Light(1.0));
+        registry.register_requires::<Lamp, Light>();
+
+        let loaded = BinaryDeserializer::new()
+            .deserialize(&mut file.as_slice())
+            .unwrap();
+        let lamp = loaded.get_entity_id(stable_id).unwrap();
+        assert_eq!(loaded.get::<Lamp>(lamp), Some(&Lamp(2)));
+        assert_eq!(loaded.get::<Light>(lamp), Some(&Light(1.0)));
+    }
+
     #[test]
     fn test_roundtrip_empty_world() {
         // Create and serialize
@@ -262,4 +686,51 @@ mod tests {
         // Verify
         assert_eq!(world.len(), loaded_world.len());
     }
+
+    #[test]
+    fn test_roundtrip_metadata_extensions() {
+        use crate::persistence::MetadataExtension;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct GameRules {
+            max_players: u32,
+            friendly_fire: bool,
+        }
+
+        impl MetadataExtension for GameRules {
+            const NAME: &'static str = "game_rules";
+        }
+
+        let mut world = World::new();
+        world.spawn_empty();
+        let rules = GameRules {
+            max_players: 8,
+            friendly_fire: false,
+        };
+        world.metadata_mut().insert_ext(rules).unwrap();
+        world
+            .metadata_mut()
+            .custom
+            .insert("map".to_string(), "dust".to_string());
+
+        let serializer = BinarySerializer::new(FormatFlags::NONE);
+        let mut buffer = Vec::new();
+        serializer.serialize(&world, &mut buffer).unwrap();
+
+        let header = Header::read(&mut buffer.as_slice()).unwrap();
+        assert!(header.flags.contains(FormatFlags::EXTENDED_METADATA));
+
+        let mut deserializer = BinaryDeserializer::new();
+        let loaded = deserializer.deserialize(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(
+            loaded.metadata().get_ext::<GameRules>().unwrap(),
+            Some(GameRules {
+                max_players: 8,
+                friendly_fire: false,
+            })
+        );
+        assert_eq!(loaded.metadata().custom.get("map").unwrap(), "dust");
+    }
 }
@@ -30,6 +30,10 @@
 //! - Entity count: u64 (8 bytes)
 //! - Component type count: u32 (4 bytes)
 //!
+//! [Extended Metadata] (only if the EXTENDED_METADATA flag is set)
+//! - Length: u32 (4 bytes)
+//! - Custom metadata, typed extensions and schema section: JSON (UTF-8)
+//!
 //! [Type Registry]
 //! - For each component type:
 //!   - Type ID: u128 (16 bytes)
@@ -54,6 +58,9 @@
 //!
 //! - Version 1: Initial format specification
 
+#[cfg(doc)]
+use crate::persistence::PersistenceError;
+use crate::persistence::options::check_limit;
 use std::io::{self, Read, Write};
 
 /// Magic bytes identifying a PECS binary file: "PECS"
@@ -65,6 +72,16 @@ pub const FORMAT_VERSION: u32 = 1;
 /// Minimum supported format version for backward compatibility
 pub const MIN_SUPPORTED_VERSION: u32 = 1;
 
+/// Longest component type name accepted when reading a type registry
+pub const MAX_TYPE_NAME_LEN: usize = 4096;
+
+/// Largest number of items or bytes preallocated for a count read from the
+/// file.
+///
+/// Counts in a corrupt or hostile file can claim far more data than the
+/// file holds; buffers beyond this size grow only as data actually arrives.
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
 /// Format flags for optional features
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FormatFlags(u32);
@@ -82,9 +99,14 @@ impl FormatFlags {
     /// Contains delta/incremental data only
     pub const DELTA: Self = Self(1 << 2);
 
-    /// Contains extended metadata
+    /// Contains extended metadata. When set by the writer, the metadata
+    /// also embeds the component schema section
     pub const EXTENDED_METADATA: Self = Self(1 << 3);
 
+    /// Entities are sorted by stable ID, and types and components by
+    /// registered name, so equal worlds produce equal files
+    pub const CANONICAL: Self = Self(1 << 4);
+
     /// Create flags from raw value
     pub const fn from_bits(bits: u32) -> Self {
         Self(bits)
@@ -259,10 +281,18 @@ impl TypeRegistryEntry {
         let mut name_len_bytes = [0u8; 4];
         reader.read_exact(&mut name_len_bytes)?;
         let name_len = u32::from_le_bytes(name_len_bytes) as usize;
+        if name_len > MAX_TYPE_NAME_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Type name length {} exceeds the maximum of {}",
+                    name_len, MAX_TYPE_NAME_LEN
+                ),
+            ));
+        }
 
         // Read type name
-        let mut name_bytes = vec![0u8; name_len];
-        reader.read_exact(&mut name_bytes)?;
+        let name_bytes = read_bytes(reader, name_len)?;
         let type_name = String::from_utf8(name_bytes)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
@@ -321,6 +351,17 @@ impl EntityData {
 
     /// Read entity data from a reader
     pub fn read(reader: &mut dyn Read) -> io::Result<Self> {
+        Self::read_limited(reader, None)
+    }
+
+    /// Read entity data, failing before reading any component whose data
+    /// is longer than `max_component_bytes`.
+    ///
+    /// The error wraps a [`PersistenceError::LimitExceeded`].
+    pub fn read_limited(
+        reader: &mut dyn Read,
+        max_component_bytes: Option<usize>,
+    ) -> io::Result<Self> {
         // Read stable ID
         let mut stable_id_bytes = [0u8; 16];
         reader.read_exact(&mut stable_id_bytes)?;
@@ -332,9 +373,9 @@ impl EntityData {
         let component_count = u32::from_le_bytes(count_bytes) as usize;
 
         // Read components
-        let mut components = Vec::with_capacity(component_count);
+        let mut components = Vec::with_capacity(component_count.min(MAX_PREALLOCATION));
         for _ in 0..component_count {
-            components.push(ComponentData::read(reader)?);
+            components.push(ComponentData::read_limited(reader, max_component_bytes)?);
         }
 
         Ok(Self {
@@ -376,6 +417,14 @@ impl ComponentData {
 
     /// Read component data from a reader
     pub fn read(reader: &mut dyn Read) -> io::Result<Self> {
+        Self::read_limited(reader, None)
+    }
+
+    /// Read component data, failing before reading data longer than
+    /// `max_bytes`.
+    ///
+    /// The error wraps a [`PersistenceError::LimitExceeded`].
+    pub fn read_limited(reader: &mut dyn Read, max_bytes: Option<usize>) -> io::Result<Self> {
         // Read type ID
         let mut type_id_bytes = [0u8; 16];
         reader.read_exact(&mut type_id_bytes)?;
@@ -385,10 +434,15 @@ impl ComponentData {
         let mut len_bytes = [0u8; 4];
         reader.read_exact(&mut len_bytes)?;
         let data_len = u32::from_le_bytes(len_bytes) as usize;
+        check_limit(
+            "max_component_bytes",
+            max_bytes.map(|max| max as u64),
+            data_len as u64,
+        )
+        .map_err(io::Error::other)?;
 
         // Read data
-        let mut data = vec![0u8; data_len];
-        reader.read_exact(&mut data)?;
+        let data = read_bytes(reader, data_len)?;
 
         Ok(Self { type_id, data })
     }
@@ -425,6 +479,71 @@ impl Footer {
     }
 }
 
+/// Extended metadata block, present when [`FormatFlags::EXTENDED_METADATA`] is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataBlock {
+    /// JSON-encoded custom metadata and typed extensions
+    pub data: Vec<u8>,
+}
+
+impl MetadataBlock {
+    /// Create a new metadata block
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    /// Write the metadata block to a writer
+    pub fn write(&self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(&(self.data.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    /// Read a metadata block from a reader
+    pub fn read(reader: &mut dyn Read) -> io::Result<Self> {
+        let mut len_bytes = [0u8; 4];
+        reader.read_exact(&mut len_bytes)?;
+        let len = u32::from_le_bytes(len_bytes) as usize;
+
+        let data = read_bytes(reader, len)?;
+        Ok(Self { data })
+    }
+}
+
+/// Preallocates at most [`MAX_PREALLOCATION`] items for a count read from
+/// the file.
+pub(crate) fn capacity_for(count: u64) -> usize {
+    usize::try_from(count).map_or(MAX_PREALLOCATION, |count| count.min(MAX_PREALLOCATION))
+}
+
+/// Reads exactly `len` bytes, growing the buffer as data arrives rather
+/// than allocating `len` bytes up front.
+fn read_bytes(reader: &mut dyn Read, len: usize) -> io::Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("Expected {} bytes, found {}", len, data.len()),
+        ));
+    }
+    Ok(data)
+}
+
+/// Derives the binary type ID written for a registered component type
+/// from its name.
+///
+/// The IDs fit in 64 bits and are stable across builds and platforms.
+pub(crate) fn registered_type_id(name: &str) -> u128 {
+    // FNV-1a
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in name.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash as u128
+}
+
 /// Calculate CRC64 checksum for data integrity using a lookup table
 pub fn calculate_checksum(data: &[u8]) -> u64 {
     // Use a lookup table for faster CRC64 calculation
@@ -19,11 +19,16 @@
 //! This module handles serializing ECS world state into the binary format.
 
 use super::format::{
-    EntityData, Footer, FormatFlags, Header, TypeRegistryEntry, calculate_checksum,
+    ComponentData, EntityData, Footer, FormatFlags, Header, MetadataBlock, TypeRegistryEntry,
+    calculate_checksum, registered_type_id,
 };
 use crate::World;
-use crate::persistence::{PersistenceError, WorldMetadata};
-use std::any::TypeId;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::StableId;
+use crate::persistence::json::schema;
+use crate::persistence::unknown::{UnknownComponents, opaque_type_id};
+use crate::persistence::{ComponentLayout, PersistenceError};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 
 /// Binary serializer for world state.
@@ -53,11 +58,13 @@ impl BinarySerializer {
         // Get world metadata
         let metadata = world.metadata();
 
-        // Build type registry from metadata
-        let type_registry = self.build_type_registry(metadata)?;
+        // Collect entity data and the component types it uses
+        let (mut type_registry, mut entity_data) = self.collect_entity_data(world)?;
+        Self::write_back_unknown(world, &mut type_registry, &mut entity_data);
 
-        // Collect entity data
-        let entity_data = self.collect_entity_data(world)?;
+        if self.flags.contains(FormatFlags::CANONICAL) {
+            Self::canonicalize(&mut type_registry, &mut entity_data);
+        }
 
         // Pre-allocate buffer with estimated size to reduce allocations
         // Estimate: header + type registry + entity data
@@ -67,10 +74,27 @@ impl BinarySerializer {
             + Footer::FOOTER_SIZE;
         let mut buffer = Vec::with_capacity(estimated_size);
 
+        // Encode custom metadata, typed extensions and the requested schema,
+        // if any
+        let mut extended = metadata.extended();
+        if self.flags.contains(FormatFlags::EXTENDED_METADATA) {
+            extended.schema = self.build_schema(world);
+        }
+        let mut flags = self.flags;
+        let metadata_block = if extended.is_empty() {
+            flags.clear(FormatFlags::EXTENDED_METADATA);
+            None
+        } else {
+            flags.set(FormatFlags::EXTENDED_METADATA);
+            let data = serde_json::to_vec(&extended)
+                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+            Some(MetadataBlock::new(data))
+        };
+
         // Write header
         let header = Header {
             version: super::FORMAT_VERSION,
-            flags: self.flags,
+            flags,
             entity_count: entity_data.len() as u64,
             component_type_count: type_registry.len() as u32,
         };
@@ -78,6 +102,13 @@ impl BinarySerializer {
             .write(&mut buffer)
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
 
+        // Write extended metadata
+        if let Some(block) = metadata_block {
+            block
+                .write(&mut buffer)
+                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        }
+
         // Write type registry
         for entry in &type_registry {
             entry
@@ -107,79 +138,127 @@ impl BinarySerializer {
         Ok(())
     }
 
-    /// Build type registry from world metadata.
-    fn build_type_registry(
-        &self,
-        metadata: &WorldMetadata,
-    ) -> Result<Vec<TypeRegistryEntry>, PersistenceError> {
-        let mut registry = Vec::new();
-
-        for type_info in &metadata.component_types {
-            // Convert TypeId to u128 for serialization
-            // Note: TypeId doesn't have a stable representation, so we use a hash
-            let type_id_hash = self.type_id_to_u128(type_info.type_id);
-
-            let entry = TypeRegistryEntry::new(
-                type_id_hash,
-                type_info.type_name.clone(),
-                type_info.version,
-            );
-            registry.push(entry);
+    /// Add the components preserved by an earlier lenient load, and their
+    /// types, to the entity data.
+    fn write_back_unknown(
+        world: &World,
+        type_registry: &mut Vec<TypeRegistryEntry>,
+        entity_data: &mut [EntityData],
+    ) {
+        let mut types = BTreeSet::new();
+        for entity in entity_data.iter_mut() {
+            let unknown = world
+                .get_entity_id(StableId::from_u128(entity.stable_id))
+                .and_then(|id| world.get::<UnknownComponents>(id));
+            for component in unknown.into_iter().flat_map(UnknownComponents::iter) {
+                let type_id = opaque_type_id(&component.name);
+                if types.insert(type_id) {
+                    type_registry.push(TypeRegistryEntry::new(
+                        type_id,
+                        component.name.clone(),
+                        component.version,
+                    ));
+                }
+                entity.add_component(ComponentData::new(type_id, component.data.clone()));
+            }
         }
-
-        Ok(registry)
     }
 
-    /// Collect entity data from the world.
-    fn collect_entity_data(&self, world: &World) -> Result<Vec<EntityData>, PersistenceError> {
-        let mut entities = Vec::new();
+    /// Sort types and components by name and entities by stable ID.
+    fn canonicalize(type_registry: &mut [TypeRegistryEntry], entity_data: &mut [EntityData]) {
+        type_registry.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+        let names: BTreeMap<u128, &str> = type_registry
+            .iter()
+            .map(|entry| (entry.type_id, entry.type_name.as_str()))
+            .collect();
 
-        // Iterate over all entities with their stable IDs
-        for (_entity, stable_id) in world.iter_entities() {
-            let stable_id_u128 = self.stable_id_to_u128(stable_id);
-            let entity_data = EntityData::new(stable_id_u128);
+        entity_data.sort_by_key(|entity| entity.stable_id);
+        for entity in entity_data.iter_mut() {
+            entity
+                .components
+                .sort_by_key(|component| names.get(&component.type_id).copied());
+        }
+    }
 
-            // Get all components for this entity
-            // Note: This is a placeholder - actual implementation will need
-            // to iterate through archetypes and serialize components
-            // For now, we'll collect what we can from the world
+    /// Build the layouts of the persistent component types in the world,
+    /// sorted by name.
+    fn build_schema(&self, world: &World) -> Vec<ComponentLayout> {
+        let registry = ComponentRegistry::global();
+        let mut layouts = BTreeMap::new();
+        for info in world.archetypes().flat_map(|a| a.component_info()) {
+            let schema = registry.schema_of(info.type_id().type_id());
+            if schema.as_ref().is_some_and(|schema| schema.transient) {
+                continue;
+            }
+            let layout = match schema {
+                Some(schema) => ComponentLayout {
+                    name: schema.name,
+                    version: schema.version,
+                    size: info.size(),
+                    align: info.alignment(),
+                    fields: schema.fields,
+                },
+                None => ComponentLayout {
+                    name: info.type_name().to_string(),
+                    version: 1,
+                    size: info.size(),
+                    align: info.alignment(),
+                    fields: Vec::new(),
+                },
+            };
+            layouts.insert(layout.name.clone(), layout);
+        }
+        layouts.into_values().collect()
+    }
 
-            // TODO: Implement component iteration and serialization
-            // This requires access to the archetype system to get component data
+    /// Collect the components of every entity, and the types they use.
+    ///
+    /// Components are written as the JSON of the codec registered with
+    /// [`ComponentRegistry::register_json`]. Types without one, and
+    /// transient types, are left out.
+    fn collect_entity_data(
+        &self,
+        world: &World,
+    ) -> Result<(Vec<TypeRegistryEntry>, Vec<EntityData>), PersistenceError> {
+        let registry = ComponentRegistry::global();
+        let registered = schema::registered_types();
+        let type_ids: Vec<u128> = registered
+            .iter()
+            .map(|ty| registered_type_id(&ty.name))
+            .collect();
+        let mut used = vec![false; registered.len()];
+        let mut entities = Vec::new();
 
+        // Iterate over all entities with their stable IDs
+        for (entity, stable_id) in world.iter_entities() {
+            let mut entity_data = EntityData::new(self.stable_id_to_u128(stable_id));
+            for (index, ty) in registered.iter().enumerate() {
+                let Some(value) = registry.get_json(&ty.name, world, entity) else {
+                    continue;
+                };
+                let data = serde_json::to_vec(&value).map_err(|e| {
+                    PersistenceError::from_source(format!("Failed to encode {}", ty.name), e)
+                })?;
+                entity_data.add_component(ComponentData::new(type_ids[index], data));
+                used[index] = true;
+            }
             entities.push(entity_data);
         }
 
-        Ok(entities)
-    }
-
-    /// Convert TypeId to u128 for serialization.
-    ///
-    /// Note: TypeId doesn't have a stable representation across compilations,
-    /// so we use the type name hash instead for persistence.
-    fn type_id_to_u128(&self, type_id: TypeId) -> u128 {
-        // Use a simple hash of the TypeId's debug representation
-        // In a real implementation, we'd use the type name from metadata
-        let hash = self.hash_type_id(type_id);
-        hash as u128
+        let type_registry = registered
+            .into_iter()
+            .zip(type_ids)
+            .zip(used)
+            .filter(|(_, used)| *used)
+            .map(|((ty, type_id), _)| TypeRegistryEntry::new(type_id, ty.name, ty.version))
+            .collect();
+        Ok((type_registry, entities))
     }
 
     /// Convert StableId to u128.
     fn stable_id_to_u128(&self, stable_id: crate::entity::StableId) -> u128 {
         stable_id.as_u128()
     }
-
-    /// Hash a TypeId for serialization.
-    fn hash_type_id(&self, type_id: TypeId) -> u64 {
-        // Simple hash implementation
-        // In production, we'd use a proper hash function
-        let debug_str = format!("{:?}", type_id);
-        let mut hash: u64 = 0;
-        for byte in debug_str.bytes() {
-            hash = hash.wrapping_mul(31).wrapping_add(byte as u64);
-        }
-        hash
-    }
 }
 
 #[cfg(test)]
@@ -208,25 +287,48 @@ mod tests {
     }
 
     #[test]
-    fn test_type_id_to_u128_consistency() {
-        let serializer = BinarySerializer::new(FormatFlags::NONE);
-        let type_id = TypeId::of::<i32>();
+    fn test_registered_type_id_consistency() {
+        let id1 = registered_type_id("game::Health");
+        let id2 = registered_type_id("game::Health");
+
+        // Same name should produce same ID, and never an opaque one
+        assert_eq!(id1, id2);
+        assert_ne!(id1, opaque_type_id("game::Health"));
+        assert!(id1 <= u64::MAX as u128);
+    }
 
-        let hash1 = serializer.type_id_to_u128(type_id);
-        let hash2 = serializer.type_id_to_u128(type_id);
+    #[test]
+    fn test_different_names_produce_different_type_ids() {
+        let id1 = registered_type_id("game::Health");
+        let id2 = registered_type_id("game::Mana");
 
-        // Same TypeId should produce same hash
-        assert_eq!(hash1, hash2);
+        // Different names should produce different IDs
+        assert_ne!(id1, id2);
     }
 
     #[test]
-    fn test_different_type_ids_produce_different_hashes() {
-        let serializer = BinarySerializer::new(FormatFlags::NONE);
+    fn test_serialize_writes_component_payloads() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Gold(u32);
+        impl crate::Component for Gold {}
 
-        let hash1 = serializer.type_id_to_u128(TypeId::of::<i32>());
-        let hash2 = serializer.type_id_to_u128(TypeId::of::<f32>());
+        ComponentRegistry::global().register_json::<Gold>("binary::Gold");
 
-        // Different TypeIds should produce different hashes
-        assert_ne!(hash1, hash2);
+        let mut world = World::new();
+        let entity = world.spawn().with(Gold(250)).id();
+        world.spawn_empty();
+
+        let mut buffer = Vec::new();
+        BinarySerializer::new(FormatFlags::NONE)
+            .serialize(&world, &mut buffer)
+            .unwrap();
+
+        let loaded = crate::persistence::binary::BinaryDeserializer::new()
+            .deserialize(&mut buffer.as_slice())
+            .unwrap();
+        assert_eq!(loaded.len(), 2);
+        let stable_id = world.get_stable_id(entity).unwrap();
+        let restored = loaded.get_entity_id(stable_id).unwrap();
+        assert_eq!(loaded.get::<Gold>(restored), Some(&Gold(250)));
     }
 }
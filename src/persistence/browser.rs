@@ -0,0 +1,320 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Browser storage persistence plugins.
+//!
+//! Browser games cannot write files, so these plugins serialize the world
+//! into an in-memory buffer and hand the bytes to a [`BrowserStorage`]
+//! backend keyed by a save-slot name:
+//!
+//! - [`LocalStoragePlugin`] stores compact JSON, since `localStorage` only
+//!   holds strings.
+//! - [`IndexedDbPlugin`] stores the binary format, which IndexedDB can keep
+//!   as a blob.
+//!
+//! The crate does not depend on `web-sys`; applications implement
+//! [`BrowserStorage`] over `window.localStorage` or an IndexedDB object store
+//! with their own bindings. [`MemoryStorage`] is provided for tests and for
+//! native builds that share code with the web build.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//! use pecs::persistence::browser::{IndexedDbPlugin, MemoryStorage};
+//! use std::sync::Arc;
+//!
+//! let storage = Arc::new(MemoryStorage::new());
+//! let plugin = IndexedDbPlugin::new(storage.clone(), "slot-1");
+//!
+//! let mut world = World::new();
+//! world.spawn_empty();
+//! plugin.save_world(&world).unwrap();
+//!
+//! let loaded = plugin.load_world().unwrap();
+//! assert_eq!(loaded.len(), 1);
+//! ```
+
+use super::{BinaryPlugin, JsonPlugin, PersistenceError, PersistencePlugin, Result};
+use crate::World;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::{Arc, RwLock};
+
+/// A key-value byte store provided by the browser.
+///
+/// Implementations on `wasm32-unknown-unknown` typically wrap `web-sys`
+/// handles. Browsers are single-threaded, so such wrappers may implement
+/// `Send` and `Sync` manually.
+pub trait BrowserStorage: Send + Sync {
+    /// Returns the bytes stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn set(&self, key: &str, value: &[u8]) -> Result<()>;
+
+    /// Removes the value stored under `key`.
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// A [`BrowserStorage`] backed by a `HashMap`.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of stored keys.
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Returns `true` if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().unwrap().is_empty()
+    }
+}
+
+impl BrowserStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.read().unwrap().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.entries.write().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Shared behavior of the browser plugins: serialize with `format` into a
+/// buffer and keep the buffer in `storage` under `key`.
+struct SlotStore<P> {
+    storage: Arc<dyn BrowserStorage>,
+    key: String,
+    format: P,
+}
+
+impl<P: PersistencePlugin> SlotStore<P> {
+    fn save(&self, world: &World, writer: &mut dyn Write) -> Result<()> {
+        let mut buffer = Vec::new();
+        self.format.save(world, &mut buffer)?;
+        self.storage.set(&self.key, &buffer)?;
+        writer.write_all(&buffer).map_err(PersistenceError::Io)
+    }
+
+    fn load(&self, reader: &mut dyn Read) -> Result<World> {
+        match self.storage.get(&self.key)? {
+            Some(bytes) => self.format.load(&mut bytes.as_slice()),
+            None => self.format.load(reader),
+        }
+    }
+
+    fn load_world(&self) -> Result<World> {
+        let bytes = self.storage.get(&self.key)?.ok_or_else(|| {
+            PersistenceError::PluginError(format!("no save stored under key '{}'", self.key))
+        })?;
+        self.format.load(&mut bytes.as_slice())
+    }
+}
+
+/// Persists worlds as JSON text in `localStorage`.
+///
+/// When used through [`PersistencePlugin`], `save` also writes the JSON to
+/// the supplied writer (pass [`std::io::sink`] to discard it), and `load`
+/// reads from the storage slot, falling back to the reader when the slot is
+/// empty.
+pub struct LocalStoragePlugin {
+    inner: SlotStore<JsonPlugin>,
+}
+
+impl LocalStoragePlugin {
+    /// Creates a plugin that saves to `key` in `storage`.
+    pub fn new(storage: Arc<dyn BrowserStorage>, key: impl Into<String>) -> Self {
+        Self {
+            inner: SlotStore {
+                storage,
+                key: key.into(),
+                format: JsonPlugin::compact(),
+            },
+        }
+    }
+
+    /// Returns the storage key used for the save slot.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Saves `world` to the storage slot.
+    pub fn save_world(&self, world: &World) -> Result<()> {
+        self.inner.save(world, &mut std::io::sink())
+    }
+
+    /// Loads the world stored in the slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::PluginError`] if the slot is empty.
+    pub fn load_world(&self) -> Result<World> {
+        self.inner.load_world()
+    }
+
+    /// Deletes the save slot.
+    pub fn delete(&self) -> Result<()> {
+        self.inner.storage.remove(&self.inner.key)
+    }
+}
+
+impl PersistencePlugin for LocalStoragePlugin {
+    fn save(&self, world: &World, writer: &mut dyn Write) -> Result<()> {
+        self.inner.save(world, writer)
+    }
+
+    fn load(&self, reader: &mut dyn Read) -> Result<World> {
+        self.inner.load(reader)
+    }
+
+    fn format_name(&self) -> &str {
+        "localstorage"
+    }
+
+    fn format_version(&self) -> u32 {
+        self.inner.format.format_version()
+    }
+}
+
+/// Persists worlds in the binary format in an IndexedDB object store.
+///
+/// When used through [`PersistencePlugin`], `save` also writes the bytes to
+/// the supplied writer (pass [`std::io::sink`] to discard them), and `load`
+/// reads from the storage slot, falling back to the reader when the slot is
+/// empty.
+pub struct IndexedDbPlugin {
+    inner: SlotStore<BinaryPlugin>,
+}
+
+impl IndexedDbPlugin {
+    /// Creates a plugin that saves to `key` in `storage`.
+    pub fn new(storage: Arc<dyn BrowserStorage>, key: impl Into<String>) -> Self {
+        Self {
+            inner: SlotStore {
+                storage,
+                key: key.into(),
+                format: BinaryPlugin::new(),
+            },
+        }
+    }
+
+    /// Returns the storage key used for the save slot.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Saves `world` to the storage slot.
+    pub fn save_world(&self, world: &World) -> Result<()> {
+        self.inner.save(world, &mut std::io::sink())
+    }
+
+    /// Loads the world stored in the slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::PluginError`] if the slot is empty.
+    pub fn load_world(&self) -> Result<World> {
+        self.inner.load_world()
+    }
+
+    /// Deletes the save slot.
+    pub fn delete(&self) -> Result<()> {
+        self.inner.storage.remove(&self.inner.key)
+    }
+}
+
+impl PersistencePlugin for IndexedDbPlugin {
+    fn save(&self, world: &World, writer: &mut dyn Write) -> Result<()> {
+        self.inner.save(world, writer)
+    }
+
+    fn load(&self, reader: &mut dyn Read) -> Result<World> {
+        self.inner.load(reader)
+    }
+
+    fn format_name(&self) -> &str {
+        "indexeddb"
+    }
+
+    fn format_version(&self) -> u32 {
+        self.inner.format.format_version()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_storage_round_trip() {
+        let storage = Arc::new(MemoryStorage::new());
+        let plugin = LocalStoragePlugin::new(storage.clone(), "slot");
+
+        let mut world = World::new();
+        world.spawn_empty();
+        world.spawn_empty();
+
+        let mut mirror = Vec::new();
+        plugin.save(&world, &mut mirror).unwrap();
+        let stored = storage.get("slot").unwrap().unwrap();
+        assert_eq!(stored, mirror);
+        assert!(std::str::from_utf8(&stored).is_ok());
+
+        let loaded = plugin.load(&mut std::io::empty()).unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        plugin.delete().unwrap();
+        assert!(storage.is_empty());
+        assert!(matches!(
+            plugin.load_world(),
+            Err(PersistenceError::PluginError(_))
+        ));
+    }
+
+    #[test]
+    fn indexed_db_falls_back_to_reader() {
+        let storage = Arc::new(MemoryStorage::new());
+        let plugin = IndexedDbPlugin::new(storage, "slot");
+
+        let mut world = World::new();
+        world.spawn_empty();
+        let mut bytes = Vec::new();
+        BinaryPlugin::new().save(&world, &mut bytes).unwrap();
+
+        let loaded = plugin.load(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(plugin.format_name(), "indexeddb");
+    }
+}
@@ -0,0 +1,535 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Transparent compression for large component payloads.
+//!
+//! Wrapping a component in [`CompressedComponent`] makes its serialized
+//! form deflate-compressed whenever the payload reaches the
+//! [compression threshold](compression_threshold). Smaller payloads are
+//! written exactly as the inner component would be, and both forms are
+//! read back transparently, so the threshold can change between saves.
+//!
+//! Each [`PersistenceManager`](crate::persistence::PersistenceManager) has
+//! its own threshold, set with
+//! [`set_compression_threshold`](crate::persistence::PersistenceManager::set_compression_threshold)
+//! and applied to the saves it runs. Code that serializes components
+//! directly can pick one with [`with_compression_threshold`].
+//!
+//! In human-readable formats such as JSON, compressed payloads are stored
+//! as base64 text next to their uncompressed length:
+//!
+//! ```json
+//! { "$deflate": "eJzt0DEBAAAAwqD1T20ND6AAAA...", "len": 65536 }
+//! ```
+//!
+//! Binary formats that are not self-describing, such as bincode, get an
+//! enum that tags the payload as plain or deflated instead.
+//!
+//! A payload never inflates past its recorded length, nor past the
+//! [inflate limit](inflate_limit) of [`MAX_INFLATED_BYTES`] unless a load
+//! sets a smaller
+//! [`max_component_bytes`](crate::persistence::LoadOptions::max_component_bytes).
+//!
+//! World and entity saves report how much was compressed in
+//! [`SaveInfo::compression`](crate::persistence::SaveInfo::compression).
+//!
+//! Requires the `compression` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::component::registry::ComponentRegistry;
+//! use pecs::persistence::compression::CompressedComponent;
+//! use pecs::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct VoxelChunk { voxels: Vec<u8> }
+//!
+//! ComponentRegistry::global()
+//!     .register_json::<CompressedComponent<VoxelChunk>>("VoxelChunk");
+//!
+//! let mut world = World::new();
+//! let chunk = world
+//!     .spawn()
+//!     .with(CompressedComponent(VoxelChunk { voxels: vec![0; 64 * 1024] }))
+//!     .id();
+//! assert_eq!(world.get::<CompressedComponent<VoxelChunk>>(chunk).unwrap().voxels.len(), 65536);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::ops::{Deref, DerefMut};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::component::Component;
+
+/// Payloads of at least this many bytes are compressed by default.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 4096;
+
+/// Compressed payloads never inflate past this many bytes by default.
+pub const MAX_INFLATED_BYTES: usize = 64 * 1024 * 1024;
+
+/// Deflate level used for compressed payloads (0-10).
+const COMPRESSION_LEVEL: u8 = 6;
+
+thread_local! {
+    /// Threshold of the innermost [`with_compression_threshold`] on this
+    /// thread.
+    static THRESHOLD: Cell<usize> = const { Cell::new(DEFAULT_COMPRESSION_THRESHOLD) };
+
+    /// Inflate limit of the innermost [`InflateLimitScope`] on this thread.
+    static INFLATE_LIMIT: Cell<usize> = const { Cell::new(MAX_INFLATED_BYTES) };
+
+    /// Statistics for the innermost [`CompressionStats::capture`] on this
+    /// thread, if any.
+    static CAPTURE: RefCell<Option<CompressionStats>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with [`CompressedComponent`]s on the current thread compressed
+/// from a payload size of `bytes` of serialized JSON.
+///
+/// Outside of any call, and outside of saves run by a
+/// [`PersistenceManager`](crate::persistence::PersistenceManager), the
+/// threshold is [`DEFAULT_COMPRESSION_THRESHOLD`].
+///
+/// # Examples
+///
+/// ```
+/// use pecs::persistence::compression::{CompressedComponent, with_compression_threshold};
+///
+/// let payload = CompressedComponent(vec![0u8; 64]);
+/// let json = with_compression_threshold(16, || serde_json::to_value(&payload)).unwrap();
+/// assert!(json.get("$deflate").is_some());
+/// ```
+pub fn with_compression_threshold<R>(bytes: usize, f: impl FnOnce() -> R) -> R {
+    let _scope = ThresholdScope::new(bytes);
+    f()
+}
+
+/// Returns the compression threshold in effect on the current thread.
+pub fn compression_threshold() -> usize {
+    THRESHOLD.with(Cell::get)
+}
+
+/// Keeps a compression threshold in effect on the current thread until
+/// dropped.
+pub(crate) struct ThresholdScope {
+    previous: usize,
+}
+
+impl ThresholdScope {
+    pub(crate) fn new(bytes: usize) -> Self {
+        Self {
+            previous: THRESHOLD.with(|threshold| threshold.replace(bytes)),
+        }
+    }
+}
+
+impl Drop for ThresholdScope {
+    fn drop(&mut self) {
+        THRESHOLD.with(|threshold| threshold.set(self.previous));
+    }
+}
+
+/// Returns the largest number of bytes a compressed payload may inflate to
+/// on the current thread.
+///
+/// This is [`MAX_INFLATED_BYTES`], or less while a load with a smaller
+/// [`max_component_bytes`](crate::persistence::LoadOptions::max_component_bytes)
+/// runs.
+pub fn inflate_limit() -> usize {
+    INFLATE_LIMIT.with(Cell::get)
+}
+
+/// Keeps an inflate limit in effect on the current thread until dropped.
+pub(crate) struct InflateLimitScope {
+    previous: usize,
+}
+
+impl InflateLimitScope {
+    /// Lowers the limit to `bytes`; it is never raised above the limit
+    /// already in effect.
+    pub(crate) fn new(bytes: usize) -> Self {
+        Self {
+            previous: INFLATE_LIMIT.with(|limit| limit.replace(bytes.min(limit.get()))),
+        }
+    }
+}
+
+impl Drop for InflateLimitScope {
+    fn drop(&mut self) {
+        INFLATE_LIMIT.with(|limit| limit.set(self.previous));
+    }
+}
+
+/// Wrapper that compresses a component's payload when it is persisted.
+///
+/// The wrapper dereferences to the inner value, so systems can use it
+/// almost like the component itself. Register the wrapper type (not the
+/// inner one) with
+/// [`ComponentRegistry::register_json`](crate::component::registry::ComponentRegistry::register_json).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CompressedComponent<T>(pub T);
+
+impl<T> CompressedComponent<T> {
+    /// Wraps a value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for CompressedComponent<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CompressedComponent<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> Component for CompressedComponent<T> {}
+
+/// A compressed payload as it is stored in human-readable formats.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Deflated {
+    /// Base64 of the compressed payload, under the key that marks it
+    #[serde(rename = "$deflate")]
+    data: String,
+    /// Uncompressed length, used to bound decompression
+    len: usize,
+}
+
+/// A payload as it is read from human-readable formats, which tell the two
+/// forms apart by the `$deflate` key.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Stored<T> {
+    Deflated(Deflated),
+    Plain(T),
+}
+
+/// A payload as it is stored in formats that are not self-describing.
+#[derive(Serialize, Deserialize)]
+enum Packed<T> {
+    Plain(T),
+    Deflated {
+        data: Vec<u8>,
+        /// Uncompressed length, used to bound decompression
+        len: usize,
+    },
+}
+
+impl<T: Serialize> Serialize for CompressedComponent<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let human_readable = serializer.is_human_readable();
+        let json = serde_json::to_vec(&self.0).map_err(S::Error::custom)?;
+        if json.len() < compression_threshold() {
+            record(json.len(), json.len(), false);
+            return if human_readable {
+                self.0.serialize(serializer)
+            } else {
+                Packed::Plain(&self.0).serialize(serializer)
+            };
+        }
+
+        let compressed = miniz_oxide::deflate::compress_to_vec(&json, COMPRESSION_LEVEL);
+        record(json.len(), compressed.len(), true);
+        if human_readable {
+            Deflated {
+                data: BASE64.encode(compressed),
+                len: json.len(),
+            }
+            .serialize(serializer)
+        } else {
+            Packed::<&T>::Deflated {
+                data: compressed,
+                len: json.len(),
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for CompressedComponent<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (compressed, len) = if deserializer.is_human_readable() {
+            match Stored::deserialize(deserializer)? {
+                Stored::Plain(value) => return Ok(Self(value)),
+                Stored::Deflated(deflated) => (
+                    BASE64.decode(deflated.data).map_err(D::Error::custom)?,
+                    deflated.len,
+                ),
+            }
+        } else {
+            match Packed::deserialize(deserializer)? {
+                Packed::Plain(value) => return Ok(Self(value)),
+                Packed::Deflated { data, len } => (data, len),
+            }
+        };
+
+        let limit = inflate_limit();
+        if len > limit {
+            return Err(D::Error::custom(format!(
+                "compressed component inflates to {} bytes, over the limit of {}",
+                len, limit
+            )));
+        }
+        let json = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, len)
+            .map_err(|e| D::Error::custom(format!("corrupt compressed component: {}", e)))?;
+        serde_json::from_slice(&json)
+            .map(Self)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Counts of payloads written through [`CompressedComponent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Wrapped components serialized
+    pub components: usize,
+    /// How many of them were compressed
+    pub compressed: usize,
+    /// Serialized size before compression, in bytes
+    pub raw_bytes: u64,
+    /// Size after compression (before base64 encoding), in bytes
+    pub stored_bytes: u64,
+}
+
+impl CompressionStats {
+    /// Runs `f` and returns the compression statistics for payloads it
+    /// serialized on the current thread.
+    ///
+    /// Captures nest; an outer capture also counts the inner one's
+    /// payloads.
+    pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Self) {
+        let outer = CAPTURE.with(|capture| capture.borrow_mut().replace(Self::default()));
+        let result = f();
+        let stats = CAPTURE
+            .with(|capture| std::mem::replace(&mut *capture.borrow_mut(), outer))
+            .unwrap_or_default();
+        CAPTURE.with(|capture| {
+            if let Some(outer) = capture.borrow_mut().as_mut() {
+                outer.merge(&stats);
+            }
+        });
+        (result, stats)
+    }
+
+    /// Returns stored bytes divided by raw bytes, or 1.0 if nothing was
+    /// written.
+    pub fn ratio(&self) -> f64 {
+        if self.raw_bytes == 0 {
+            1.0
+        } else {
+            self.stored_bytes as f64 / self.raw_bytes as f64
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.components += other.components;
+        self.compressed += other.compressed;
+        self.raw_bytes += other.raw_bytes;
+        self.stored_bytes += other.stored_bytes;
+    }
+}
+
+fn record(raw: usize, stored: usize, compressed: bool) {
+    CAPTURE.with(|capture| {
+        if let Some(stats) = capture.borrow_mut().as_mut() {
+            stats.components += 1;
+            stats.compressed += usize::from(compressed);
+            stats.raw_bytes += raw as u64;
+            stats.stored_bytes += stored as u64;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Key marking a compressed payload.
+    const DEFLATE_KEY: &str = "$deflate";
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Chunk {
+        voxels: Vec<u8>,
+    }
+
+    #[test]
+    fn large_payloads_round_trip_compressed() {
+        let small = CompressedComponent(Chunk { voxels: vec![1; 8] });
+        let large = CompressedComponent(Chunk {
+            voxels: vec![7; 64 * 1024],
+        });
+
+        let ((small_json, large_json), stats) = CompressionStats::capture(|| {
+            (
+                serde_json::to_value(&small).unwrap(),
+                serde_json::to_value(&large).unwrap(),
+            )
+        });
+
+        // Small payloads are stored as the plain component
+        assert_eq!(small_json, serde_json::to_value(&small.0).unwrap());
+        assert!(large_json.get(DEFLATE_KEY).is_some());
+        assert!(large_json.to_string().len() < 4096);
+
+        assert_eq!(stats.components, 2);
+        assert_eq!(stats.compressed, 1);
+        assert!(stats.ratio() < 0.1);
+
+        let restored: CompressedComponent<Chunk> = serde_json::from_value(large_json).unwrap();
+        assert_eq!(restored, large);
+        let restored: CompressedComponent<Chunk> = serde_json::from_value(small_json).unwrap();
+        assert_eq!(restored, small);
+    }
+
+    #[test]
+    fn thresholds_are_scoped() {
+        let payload = CompressedComponent(Chunk {
+            voxels: vec![1; 64],
+        });
+        let compressed = |value: serde_json::Value| value.get(DEFLATE_KEY).is_some();
+
+        assert!(!compressed(serde_json::to_value(&payload).unwrap()));
+        let json = with_compression_threshold(16, || {
+            // Nested scopes restore the outer threshold
+            with_compression_threshold(usize::MAX, || {
+                assert_eq!(compression_threshold(), usize::MAX);
+            });
+            serde_json::to_value(&payload).unwrap()
+        });
+        assert!(compressed(json));
+        assert_eq!(compression_threshold(), DEFAULT_COMPRESSION_THRESHOLD);
+    }
+
+    #[test]
+    fn binary_formats_round_trip_without_deserialize_any() {
+        let small = CompressedComponent(Chunk { voxels: vec![1; 8] });
+        let large = CompressedComponent(Chunk {
+            voxels: vec![7; 64 * 1024],
+        });
+
+        for payload in [small, large] {
+            let bytes = bincode::serialize(&payload).unwrap();
+            let restored: CompressedComponent<Chunk> = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(restored, payload);
+        }
+    }
+
+    #[test]
+    fn inflation_is_capped() {
+        let payload = CompressedComponent(Chunk {
+            voxels: vec![5; 64 * 1024],
+        });
+        let json = serde_json::to_value(&payload).unwrap();
+
+        let result = {
+            let _limit = InflateLimitScope::new(1024);
+            serde_json::from_value::<CompressedComponent<Chunk>>(json.clone())
+        };
+        assert!(result.unwrap_err().to_string().contains("over the limit"));
+        assert_eq!(inflate_limit(), MAX_INFLATED_BYTES);
+
+        // A recorded length smaller than the payload cannot inflate it
+        // past that length either
+        let mut forged = json;
+        forged["len"] = 16.into();
+        let result = serde_json::from_value::<CompressedComponent<Chunk>>(forged);
+        assert!(result.unwrap_err().to_string().contains("corrupt"));
+    }
+
+    #[test]
+    fn world_saves_report_compression() {
+        use crate::World;
+        use crate::component::registry::ComponentRegistry;
+
+        ComponentRegistry::global()
+            .register_json::<CompressedComponent<Chunk>>("compression::Chunk");
+
+        let mut world = World::new();
+        world
+            .spawn()
+            .with(CompressedComponent(Chunk {
+                voxels: vec![9; 32 * 1024],
+            }))
+            .id();
+
+        let info = world.save_json(&mut Vec::new()).unwrap();
+        assert_eq!(info.entities, 1);
+        assert_eq!(info.compression.components, 1);
+        assert_eq!(info.compression.compressed, 1);
+    }
+
+    #[test]
+    fn entity_saves_report_compression() {
+        use crate::World;
+        use crate::component::registry::ComponentRegistry;
+        use crate::persistence::{EntityPersistencePlugin, KeyValueEntityPlugin};
+
+        ComponentRegistry::global()
+            .register_json::<CompressedComponent<Chunk>>("compression::Chunk");
+
+        let plugin = KeyValueEntityPlugin::new();
+        let mut world = World::new();
+        world
+            .persistence()
+            .register_entity_plugin("kv", Box::new(plugin.clone()));
+        let chunk = world
+            .spawn()
+            .with(CompressedComponent(Chunk {
+                voxels: vec![3; 32 * 1024],
+            }))
+            .id();
+
+        let info = world.save_entities(&[chunk], "kv").unwrap();
+        assert_eq!(info.entities, 1);
+        assert_eq!(info.compression.compressed, 1);
+        assert!(info.compression.stored_bytes < info.compression.raw_bytes);
+
+        // Each world's manager applies its own threshold
+        world.persistence().set_compression_threshold(1024 * 1024);
+        let info = world.save_entities(&[chunk], "kv").unwrap();
+        assert_eq!(info.compression.compressed, 0);
+
+        let mut restored = World::new();
+        let stable_id = world.get_stable_id(chunk).unwrap();
+        let loaded = plugin.load_entity(&mut restored, stable_id).unwrap();
+        let voxels = &restored
+            .get::<CompressedComponent<Chunk>>(loaded)
+            .unwrap()
+            .voxels;
+        assert_eq!(voxels.len(), 32 * 1024);
+        assert!(voxels.iter().all(|&v| v == 3));
+    }
+}
@@ -0,0 +1,407 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Component-level deltas for delta saves and replication.
+//!
+//! A [`DeltaEncoder`] remembers the last value it encoded for every
+//! JSON-registered component of an entity and produces an [`EntityDelta`]
+//! containing only what changed since. Components with a registered
+//! [`DeltaCodec`](crate::component::registry::DeltaCodec) are sent as
+//! patches; other components are sent whole, and only when their value
+//! differs from the last one sent.
+//!
+//! Deltas are serializable, so they can be written to a delta plugin or
+//! sent over the network, and are applied with [`EntityDelta::apply`].
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::component::registry::ComponentRegistry;
+//! use pecs::persistence::delta::{ComponentChange, DeltaEncoder};
+//! use pecs::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Health(u32);
+//! impl Component for Health {}
+//!
+//! ComponentRegistry::global().register_json::<Health>("Health");
+//!
+//! let mut server = World::new();
+//! let entity = server.spawn().with(Health(10)).id();
+//! let mut encoder = DeltaEncoder::new();
+//!
+//! // The first delta carries the full entity
+//! let delta = encoder.encode(&server, entity).unwrap().unwrap();
+//! let mut client = World::new();
+//! let replica = delta.apply(&mut client).unwrap();
+//!
+//! // Later deltas carry only changes
+//! assert!(encoder.encode(&server, entity).unwrap().unwrap().is_empty());
+//! server.get_mut::<Health>(entity).unwrap().0 = 4;
+//! let delta = encoder.encode(&server, entity).unwrap().unwrap();
+//! assert!(matches!(delta.components[0].change, ComponentChange::Full(_)));
+//!
+//! delta.apply(&mut client).unwrap();
+//! assert_eq!(client.get::<Health>(replica), Some(&Health(4)));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::World;
+use crate::component::registry::{ComponentRegistry, DeltaBase};
+use crate::entity::{EntityId, StableId};
+use crate::persistence::{PersistenceError, Result};
+
+/// How one component changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ComponentChange {
+    /// The complete new value
+    Full(serde_json::Value),
+    /// A patch produced by the component's delta codec
+    Patch(serde_json::Value),
+    /// The component was removed
+    Removed,
+}
+
+/// A change to one named component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDelta {
+    /// The component's JSON registration name
+    pub name: String,
+    /// What changed
+    pub change: ComponentChange,
+}
+
+/// The component changes of one entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityDelta {
+    /// The entity the changes belong to
+    pub stable_id: StableId,
+    /// Changed components, sorted by name
+    pub components: Vec<ComponentDelta>,
+}
+
+impl EntityDelta {
+    /// Returns true if no component changed.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Applies the changes to the entity with the same stable ID, spawning
+    /// it first if it doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// The entity the changes were applied to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component name is not registered, or a value
+    /// or patch does not match its component. Changes before the failing
+    /// one stay applied.
+    pub fn apply(&self, world: &mut World) -> Result<EntityId> {
+        let entity = match world.get_entity_by_stable_id(self.stable_id) {
+            Some(entity) => entity,
+            None => world
+                .spawn_empty_with_stable_id(self.stable_id)
+                .map_err(|e| PersistenceError::EntityIdConflict(e.to_string()))?,
+        };
+        for component in &self.components {
+            apply_change(world, entity, &component.name, component.change.clone())?;
+        }
+        Ok(entity)
+    }
+}
+
+/// Applies one component change to an entity.
+pub(crate) fn apply_change(
+    world: &mut World,
+    entity: EntityId,
+    name: &str,
+    change: ComponentChange,
+) -> Result<()> {
+    let registry = ComponentRegistry::global();
+    match change {
+        ComponentChange::Full(value) => {
+            registry.insert_json(name, world, entity, value)?;
+        }
+        ComponentChange::Patch(patch) => {
+            if !registry.apply_delta(name, world, entity, patch)? {
+                return Err(PersistenceError::Custom(format!(
+                    "cannot patch `{}`: entity {} does not have it",
+                    name, entity
+                )));
+            }
+        }
+        ComponentChange::Removed => {
+            registry.remove_json(name, world, entity)?;
+        }
+    }
+    Ok(())
+}
+
+/// The last value encoded for a component.
+enum Base {
+    /// Serialized value, for components without a codec
+    Json(serde_json::Value),
+    /// Copy of the component, for components with a codec
+    Codec(DeltaBase),
+}
+
+/// Produces [`EntityDelta`]s relative to the last encoded state.
+///
+/// Keep one encoder per destination (a delta store, or a network peer),
+/// since each remembers what it has already sent.
+#[derive(Default)]
+pub struct DeltaEncoder {
+    bases: HashMap<EntityId, HashMap<String, Base>>,
+}
+
+impl fmt::Debug for DeltaEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeltaEncoder")
+            .field("entities", &self.bases.len())
+            .finish()
+    }
+}
+
+impl DeltaEncoder {
+    /// Creates an encoder that has sent nothing yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encodes the changes to an entity since it was last encoded.
+    ///
+    /// The first time an entity is encoded, every JSON-registered
+    /// component it has is sent in full.
+    ///
+    /// # Returns
+    ///
+    /// The delta, or `None` if the entity doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a patch fails to serialize.
+    pub fn encode(&mut self, world: &World, entity: EntityId) -> Result<Option<EntityDelta>> {
+        let Some(stable_id) = world.get_stable_id(entity) else {
+            self.bases.remove(&entity);
+            return Ok(None);
+        };
+
+        let registry = ComponentRegistry::global();
+        let bases = self.bases.entry(entity).or_default();
+        let mut components = Vec::new();
+        for name in registry.json_names() {
+            let change = if registry.has_delta(&name) {
+                let Some(current) = registry.delta_snapshot(&name, world, entity) else {
+                    if bases.remove(&name).is_some() {
+                        components.push(ComponentDelta {
+                            name,
+                            change: ComponentChange::Removed,
+                        });
+                    }
+                    continue;
+                };
+                let patch = match bases.get(&name) {
+                    Some(Base::Codec(base)) => registry.diff_delta(&name, base, world, entity),
+                    _ => None,
+                };
+                let change = match patch {
+                    Some(patch) => ComponentChange::Patch(
+                        patch.map_err(|e| PersistenceError::Serialization(e.to_string()))?,
+                    ),
+                    None => match registry.get_json(&name, world, entity) {
+                        Some(value) => ComponentChange::Full(value),
+                        None => continue,
+                    },
+                };
+                bases.insert(name.clone(), Base::Codec(current));
+                change
+            } else {
+                let Some(value) = registry.get_json(&name, world, entity) else {
+                    if bases.remove(&name).is_some() {
+                        components.push(ComponentDelta {
+                            name,
+                            change: ComponentChange::Removed,
+                        });
+                    }
+                    continue;
+                };
+                if matches!(bases.get(&name), Some(Base::Json(base)) if *base == value) {
+                    continue;
+                }
+                bases.insert(name.clone(), Base::Json(value.clone()));
+                ComponentChange::Full(value)
+            };
+            components.push(ComponentDelta { name, change });
+        }
+
+        Ok(Some(EntityDelta {
+            stable_id,
+            components,
+        }))
+    }
+
+    /// Forgets what was sent for an entity, so its next delta is sent in
+    /// full.
+    pub fn forget(&mut self, entity: EntityId) {
+        self.bases.remove(&entity);
+    }
+
+    /// Forgets everything, as for a new destination.
+    pub fn clear(&mut self) {
+        self.bases.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::component::registry::DeltaCodec;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Chunk {
+        cells: Vec<u8>,
+    }
+    impl Component for Chunk {}
+
+    impl DeltaCodec for Chunk {
+        type Patch = Vec<(usize, u8)>;
+
+        fn diff(old: &Self, new: &Self) -> Option<Self::Patch> {
+            (old.cells.len() == new.cells.len()).then(|| {
+                old.cells
+                    .iter()
+                    .zip(&new.cells)
+                    .enumerate()
+                    .filter(|(_, (a, b))| a != b)
+                    .map(|(i, (_, &b))| (i, b))
+                    .collect()
+            })
+        }
+
+        fn apply(base: &mut Self, patch: Self::Patch) {
+            for (i, cell) in patch {
+                base.cells[i] = cell;
+            }
+        }
+    }
+
+    #[test]
+    fn codec_components_are_sent_as_patches() {
+        ComponentRegistry::global().register_delta::<Chunk>("delta::Chunk");
+
+        let mut source = World::new();
+        let entity = source
+            .spawn()
+            .with(Chunk {
+                cells: vec![0; 64 * 1024],
+            })
+            .id();
+        let mut encoder = DeltaEncoder::new();
+        let mut replica = World::new();
+
+        let full = encoder.encode(&source, entity).unwrap().unwrap();
+        assert!(matches!(
+            full.components[0].change,
+            ComponentChange::Full(_)
+        ));
+        let copy = full.apply(&mut replica).unwrap();
+
+        source.get_mut::<Chunk>(entity).unwrap().cells[1234] = 9;
+        let delta = encoder.encode(&source, entity).unwrap().unwrap();
+        assert_eq!(
+            delta.components[0].change,
+            ComponentChange::Patch(serde_json::json!([[1234, 9]]))
+        );
+        assert!(serde_json::to_vec(&delta).unwrap().len() < 256);
+
+        delta.apply(&mut replica).unwrap();
+        assert_eq!(replica.get::<Chunk>(copy), source.get::<Chunk>(entity));
+
+        // Growing the chunk cannot be patched, so it is sent whole
+        source.get_mut::<Chunk>(entity).unwrap().cells.push(1);
+        let delta = encoder.encode(&source, entity).unwrap().unwrap();
+        assert!(matches!(
+            delta.components[0].change,
+            ComponentChange::Full(_)
+        ));
+
+        source.remove::<Chunk>(entity);
+        let delta = encoder.encode(&source, entity).unwrap().unwrap();
+        assert_eq!(delta.components[0].change, ComponentChange::Removed);
+        delta.apply(&mut replica).unwrap();
+        assert!(!replica.has::<Chunk>(copy));
+    }
+
+    #[test]
+    fn delta_saves_write_patches() {
+        use crate::persistence::{DeltaPersistencePlugin, EntityChange};
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct Recorder(Arc<Mutex<Vec<Vec<EntityChange>>>>);
+        impl DeltaPersistencePlugin for Recorder {
+            fn save_changes(&self, changes: &[EntityChange]) -> Result<()> {
+                self.0.lock().unwrap().push(changes.to_vec());
+                Ok(())
+            }
+            fn load_changes(&self, _since: u64) -> Result<Vec<EntityChange>> {
+                Ok(Vec::new())
+            }
+        }
+
+        ComponentRegistry::global().register_delta::<Chunk>("delta::Chunk");
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::new();
+        world
+            .persistence()
+            .register_delta_plugin("recorder", Box::new(Recorder(saved.clone())));
+
+        let entity = world
+            .spawn()
+            .with(Chunk {
+                cells: vec![0; 4096],
+            })
+            .id();
+        world.save_delta("recorder").unwrap();
+        world.get_mut::<Chunk>(entity).unwrap().cells[7] = 1;
+        world.save_delta("recorder").unwrap();
+
+        let saved = saved.lock().unwrap();
+        assert_eq!(saved.len(), 2);
+        let EntityChange::Modified {
+            added_or_modified, ..
+        } = &saved[1][0]
+        else {
+            panic!("expected a modification, got {:?}", saved[1]);
+        };
+        assert_eq!(added_or_modified.len(), 1);
+        let change: ComponentChange = serde_json::from_slice(&added_or_modified[0].data).unwrap();
+        assert_eq!(change, ComponentChange::Patch(serde_json::json!([[7, 1]])));
+
+        // Applying the change to the source world is a no-op patch
+        saved[1][0].apply(&mut world).unwrap();
+        assert_eq!(world.get::<Chunk>(entity).unwrap().cells[7], 1);
+    }
+}
@@ -0,0 +1,267 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Differences between two worlds.
+//!
+//! A [`WorldDiff`] records which stable IDs exist in one world but not the
+//! other, and which component values differ between them. It is what the
+//! hot-reload watcher produces, and can be applied to a running world to
+//! bring its entities and their components in line with a freshly loaded
+//! save.
+//!
+//! Only components registered with
+//! [`ComponentRegistry::register_json`](crate::component::registry::ComponentRegistry::register_json)
+//! are compared, by their JSON values.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::StableId;
+use crate::persistence::Result;
+use crate::persistence::json::schema;
+
+/// The JSON values of the registered components of every entity, by stable
+/// ID and registered name.
+pub(crate) type WorldState = BTreeMap<StableId, BTreeMap<String, Value>>;
+
+/// The entities spawned and despawned, and the component values changed,
+/// between two versions of a world.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+/// use pecs::persistence::WorldDiff;
+///
+/// let mut running = World::new();
+/// let mut edited = World::new();
+/// edited.spawn_empty();
+///
+/// let diff = WorldDiff::between(&running, &edited);
+/// assert_eq!(diff.spawned.len(), 1);
+///
+/// diff.apply(&mut running).unwrap();
+/// assert_eq!(running.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldDiff {
+    /// Stable IDs present in the new world only, in ascending order
+    pub spawned: Vec<StableId>,
+    /// Stable IDs present in the old world only, in ascending order
+    pub despawned: Vec<StableId>,
+    /// Component values of the new world that differ from the old one, by
+    /// stable ID and registered name. `None` marks a component the entity
+    /// no longer has. Spawned entities list all their components
+    pub components: BTreeMap<StableId, BTreeMap<String, Option<Value>>>,
+}
+
+impl WorldDiff {
+    /// Computes the difference from `old` to `new`.
+    pub fn between(old: &World, new: &World) -> Self {
+        Self::from_states(&capture(old), &capture(new))
+    }
+
+    /// Computes the difference between two captured world states.
+    pub(crate) fn from_states(old: &WorldState, new: &WorldState) -> Self {
+        let empty = BTreeMap::new();
+        let mut components = BTreeMap::new();
+        for (stable_id, values) in new {
+            let previous = old.get(stable_id).unwrap_or(&empty);
+            let mut changed: BTreeMap<String, Option<Value>> = values
+                .iter()
+                .filter(|&(name, value)| previous.get(name) != Some(value))
+                .map(|(name, value)| (name.clone(), Some(value.clone())))
+                .collect();
+            for name in previous.keys() {
+                if !values.contains_key(name) {
+                    changed.insert(name.clone(), None);
+                }
+            }
+            if !changed.is_empty() {
+                components.insert(*stable_id, changed);
+            }
+        }
+
+        Self {
+            spawned: new
+                .keys()
+                .filter(|stable_id| !old.contains_key(stable_id))
+                .copied()
+                .collect(),
+            despawned: old
+                .keys()
+                .filter(|stable_id| !new.contains_key(stable_id))
+                .copied()
+                .collect(),
+            components,
+        }
+    }
+
+    /// Returns true if both worlds contain the same entities with the same
+    /// component values.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty() && self.despawned.is_empty() && self.components.is_empty()
+    }
+
+    /// Applies the difference to `world`.
+    ///
+    /// Spawns an entity for every spawned stable ID that is not already
+    /// alive, despawns every despawned stable ID that is, and inserts or
+    /// removes the changed components of live entities. Returns the number
+    /// of entities changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a changed component's name is not registered for
+    /// JSON or its value does not match the component type. Changes before
+    /// it have already been applied.
+    pub fn apply(&self, world: &mut World) -> Result<usize> {
+        let mut changed = 0;
+        for &stable_id in &self.despawned {
+            if let Some(entity) = world.get_entity_id(stable_id)
+                && world.despawn(entity)
+            {
+                changed += 1;
+            }
+        }
+        let mut spawned = Vec::new();
+        for &stable_id in &self.spawned {
+            if world.spawn_empty_with_stable_id(stable_id).is_ok() {
+                spawned.push(stable_id);
+            }
+        }
+        changed += spawned.len();
+
+        let registry = ComponentRegistry::global();
+        for (stable_id, values) in &self.components {
+            let Some(entity) = world.get_entity_id(*stable_id) else {
+                continue;
+            };
+            let mut updated = false;
+            for (name, value) in values {
+                updated |= match value {
+                    Some(value) => registry.insert_json(name, world, entity, value.clone())?,
+                    None => registry.remove_json(name, world, entity)?,
+                };
+            }
+            world.insert_missing_requirements(entity);
+            if updated && !spawned.contains(stable_id) {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+}
+
+/// Captures the registered component values of every entity in `world`.
+pub(crate) fn capture(world: &World) -> WorldState {
+    let registry = ComponentRegistry::global();
+    let registered = schema::registered_types();
+    world
+        .iter_entities()
+        .map(|(entity, stable_id)| {
+            let values = registered
+                .iter()
+                .filter_map(|ty| {
+                    let value = registry.get_json(&ty.name, world, entity)?;
+                    Some((ty.name.clone(), value))
+                })
+                .collect();
+            (stable_id, values)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_and_apply() {
+        let mut old = World::new();
+        let kept = old.spawn_empty();
+        let removed = old.spawn_empty();
+        let kept_id = old.get_stable_id(kept).unwrap();
+        let removed_id = old.get_stable_id(removed).unwrap();
+
+        let mut new = World::new();
+        new.spawn_empty_with_stable_id(kept_id).unwrap();
+        let added = new.spawn_empty();
+        let added_id = new.get_stable_id(added).unwrap();
+
+        let diff = WorldDiff::between(&old, &new);
+        assert_eq!(diff.spawned, vec![added_id]);
+        assert_eq!(diff.despawned, vec![removed_id]);
+        assert!(WorldDiff::between(&new, &new).is_empty());
+
+        assert_eq!(diff.apply(&mut old).unwrap(), 2);
+        assert!(old.get_entity_id(added_id).is_some());
+        assert!(old.get_entity_id(removed_id).is_none());
+        assert!(WorldDiff::between(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn diff_carries_component_values() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Speed(f32);
+        impl crate::Component for Speed {}
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Label(String);
+        impl crate::Component for Label {}
+
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Speed>("diff::Speed");
+        registry.register_json::<Label>("diff::Label");
+
+        let mut old = World::new();
+        let edited = old
+            .spawn()
+            .with(Speed(1.0))
+            .with(Label("cart".to_string()))
+            .id();
+        let edited_id = old.get_stable_id(edited).unwrap();
+
+        // The new version edits the speed in place, drops the label and
+        // adds an entity
+        let mut new = World::new();
+        let entity = new.spawn_empty_with_stable_id(edited_id).unwrap();
+        new.insert(entity, Speed(2.5));
+        let added = new.spawn().with(Label("crate".to_string())).id();
+        let added_id = new.get_stable_id(added).unwrap();
+
+        let diff = WorldDiff::between(&old, &new);
+        assert!(diff.despawned.is_empty());
+        assert_eq!(diff.spawned, vec![added_id]);
+        assert_eq!(
+            diff.components[&edited_id],
+            BTreeMap::from([
+                ("diff::Label".to_string(), None),
+                ("diff::Speed".to_string(), Some(serde_json::json!(2.5))),
+            ])
+        );
+
+        assert_eq!(diff.apply(&mut old).unwrap(), 2);
+        assert_eq!(old.get::<Speed>(edited), Some(&Speed(2.5)));
+        assert!(!old.has::<Label>(edited));
+        let added = old.get_entity_id(added_id).unwrap();
+        assert_eq!(old.get::<Label>(added), Some(&Label("crate".to_string())));
+        assert!(WorldDiff::between(&old, &new).is_empty());
+    }
+}
@@ -14,24 +14,55 @@
 // limitations under the License.
 //
 
-//! In-memory key-value entity persistence plugin.
+//! Key-value entity persistence plugin.
 //!
-//! This module provides a simple in-memory implementation of the
-//! `EntityPersistencePlugin` trait, useful for testing and as a reference
-//! implementation.
+//! This module provides a simple implementation of the
+//! `EntityPersistencePlugin` trait, useful for testing, as a reference
+//! implementation, and as a lightweight entity store. Entities live in
+//! memory; with [`KeyValueEntityPlugin::with_path`] every write is also
+//! appended to a log file that is replayed on startup and compacted
+//! periodically.
+//!
+//! Components are stored through their JSON registration (see
+//! [`ComponentRegistry::register_json`]); components of unregistered types
+//! are not saved.
+
+use std::collections::{BTreeSet, HashMap};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::fs::{self, File, OpenOptions};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::io::{BufRead, BufReader, BufWriter, Write};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
 
 use crate::World;
+use crate::component::registry::ComponentRegistry;
 use crate::entity::{EntityId, StableId};
-use crate::persistence::{EntityData, EntityPersistencePlugin, PersistenceError, Result};
+use crate::persistence::{
+    ComponentData, EntityData, EntityPersistencePlugin, PersistenceError, Result,
+};
+
+/// Name of the log file inside a plugin's directory.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+const LOG_FILE: &str = "entities.log";
+
+/// Log records are compacted once there are this many times more of them
+/// than stored entities.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+const COMPACTION_RATIO: usize = 4;
 
-/// In-memory key-value store for entity persistence.
+/// The log is never compacted below this many records.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+const COMPACTION_MIN_RECORDS: usize = 1024;
+
+/// Key-value store for entity persistence.
 ///
-/// This plugin stores entities in memory using a HashMap, making it suitable
-/// for testing, caching, or as a reference implementation. Data is lost when
-/// the plugin is dropped.
+/// By default entities are kept in memory only and are lost when the
+/// plugin is dropped. Use [`with_path`](Self::with_path) to back the store
+/// with an append-only log on disk.
 ///
 /// # Thread Safety
 ///
@@ -40,22 +71,30 @@ use crate::persistence::{EntityData, EntityPersistencePlugin, PersistenceError,
 ///
 /// # Examples
 ///
-/// ```rust,ignore
-/// use pecs::persistence::KeyValueEntityPlugin;
-/// use pecs::World;
+/// ```
+/// use pecs::component::registry::ComponentRegistry;
+/// use pecs::persistence::{EntityPersistencePlugin, KeyValueEntityPlugin};
+/// use pecs::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// ComponentRegistry::global().register_json::<Position>("Position");
 ///
 /// let plugin = KeyValueEntityPlugin::new();
 /// let mut world = World::new();
 ///
-/// // Spawn an entity
+/// // Spawn and save an entity
 /// let entity = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
 /// let stable_id = world.get_stable_id(entity).unwrap();
+/// plugin.save_entity(&world, entity).unwrap();
 ///
-/// // Save it
-/// plugin.save_entity(&world, entity)?;
-///
-/// // Load it back
-/// let loaded_entity = plugin.load_entity(&mut world, stable_id)?;
+/// // Load it into another world
+/// let mut other = World::new();
+/// let loaded = plugin.load_entity(&mut other, stable_id).unwrap();
+/// assert_eq!(other.get::<Position>(loaded), Some(&Position { x: 1.0, y: 2.0 }));
 /// ```
 #[derive(Clone)]
 pub struct KeyValueEntityPlugin {
@@ -65,6 +104,175 @@ pub struct KeyValueEntityPlugin {
     /// use the trait methods instead.
     #[doc(hidden)]
     pub storage: Arc<RwLock<HashMap<StableId, EntityData>>>,
+
+    /// Secondary index from component name to the entities storing it
+    index: Arc<RwLock<ComponentIndex>>,
+
+    /// Log file backing the store, if any
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    log: Option<Arc<Mutex<EntityLog>>>,
+}
+
+/// Maps component names to the stable IDs of stored entities that have them,
+/// in stable ID order.
+#[derive(Debug, Default)]
+struct ComponentIndex {
+    by_name: HashMap<String, BTreeSet<StableId>>,
+}
+
+impl ComponentIndex {
+    fn build(storage: &HashMap<StableId, EntityData>) -> Self {
+        let mut index = Self::default();
+        for data in storage.values() {
+            index.insert(data);
+        }
+        index
+    }
+
+    fn insert(&mut self, data: &EntityData) {
+        for component in &data.components {
+            self.by_name
+                .entry(component.type_name.clone())
+                .or_default()
+                .insert(data.stable_id);
+        }
+    }
+
+    fn remove(&mut self, data: &EntityData) {
+        for component in &data.components {
+            if let Some(ids) = self.by_name.get_mut(&component.type_name) {
+                ids.remove(&data.stable_id);
+                if ids.is_empty() {
+                    self.by_name.remove(&component.type_name);
+                }
+            }
+        }
+    }
+
+    fn find(&self, name: &str) -> Vec<StableId> {
+        self.by_name
+            .get(name)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// One line of the on-disk log.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum LogRecord {
+    Put {
+        stable_id: StableId,
+        timestamp: u64,
+        components: Vec<LoggedComponent>,
+    },
+    Delete {
+        stable_id: StableId,
+    },
+    Clear,
+}
+
+/// A component as stored in the log.
+#[derive(Debug, Serialize, Deserialize)]
+struct LoggedComponent {
+    name: String,
+    value: serde_json::Value,
+}
+
+impl LogRecord {
+    fn put(data: &EntityData) -> Result<Self> {
+        let components = data
+            .components
+            .iter()
+            .map(|component| {
+                Ok(LoggedComponent {
+                    name: component.type_name.clone(),
+                    value: serde_json::from_slice(&component.data)
+                        .map_err(|e| PersistenceError::Serialization(e.to_string()))?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::Put {
+            stable_id: data.stable_id,
+            timestamp: data.timestamp,
+            components,
+        })
+    }
+
+    /// Applies the record to an in-memory store.
+    fn apply(self, storage: &mut HashMap<StableId, EntityData>) {
+        match self {
+            Self::Put {
+                stable_id,
+                timestamp,
+                components,
+            } => {
+                let registry = ComponentRegistry::global();
+                let components = components
+                    .into_iter()
+                    .filter_map(|component| {
+                        let info = registry.info_by_name(&component.name)?;
+                        Some(ComponentData {
+                            type_id: info.type_id().type_id(),
+                            type_name: component.name,
+                            data: serde_json::to_vec(&component.value).ok()?,
+                        })
+                    })
+                    .collect();
+                storage.insert(stable_id, EntityData::new(stable_id, components, timestamp));
+            }
+            Self::Delete { stable_id } => {
+                storage.remove(&stable_id);
+            }
+            Self::Clear => storage.clear(),
+        }
+    }
+}
+
+/// The append-only log behind a file-backed plugin.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+struct EntityLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Records in the file, for deciding when to compact
+    records: usize,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl EntityLog {
+    fn append(&mut self, record: &LogRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Rewrites the log to hold one record per stored entity.
+    fn compact(&mut self, storage: &HashMap<StableId, EntityData>) -> Result<()> {
+        let temp = self.path.with_extension("log.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&temp)?);
+            let mut entities: Vec<_> = storage.values().collect();
+            entities.sort_by_key(|data| data.stable_id);
+            for data in entities {
+                serde_json::to_writer(&mut writer, &LogRecord::put(data)?)
+                    .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        fs::rename(&temp, &self.path)?;
+        self.writer = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        self.records = storage.len();
+        Ok(())
+    }
+
+    fn should_compact(&self, stored: usize) -> bool {
+        self.records >= COMPACTION_MIN_RECORDS && self.records > stored * COMPACTION_RATIO
+    }
 }
 
 impl KeyValueEntityPlugin {
@@ -78,9 +286,7 @@ impl KeyValueEntityPlugin {
     /// let plugin = KeyValueEntityPlugin::new();
     /// ```
     pub fn new() -> Self {
-        Self {
-            storage: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::with_capacity(0)
     }
 
     /// Creates a new key-value entity plugin with pre-allocated capacity.
@@ -99,6 +305,87 @@ impl KeyValueEntityPlugin {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             storage: Arc::new(RwLock::new(HashMap::with_capacity(capacity))),
+            index: Arc::new(RwLock::new(ComponentIndex::default())),
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            log: None,
+        }
+    }
+
+    /// Opens a file-backed plugin storing its log in `dir`.
+    ///
+    /// The directory is created if needed, and entities already logged
+    /// there are loaded. Every save and delete is appended to the log
+    /// before returning; the log is compacted automatically once it has
+    /// grown well past the number of stored entities, or on demand with
+    /// [`compact`](Self::compact).
+    ///
+    /// Logged components whose JSON registration is missing when the log
+    /// is read are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or log cannot be created or read,
+    /// or if the log is corrupt.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pecs::persistence::KeyValueEntityPlugin;
+    ///
+    /// let plugin = KeyValueEntityPlugin::with_path("saves/entities").unwrap();
+    /// println!("{} entities on disk", plugin.len());
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_path(dir: impl AsRef<Path>) -> Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        let path = dir.as_ref().join(LOG_FILE);
+
+        let mut storage = HashMap::new();
+        let mut records = 0;
+        if path.exists() {
+            for (number, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: LogRecord = serde_json::from_str(&line).map_err(|e| {
+                    PersistenceError::InvalidFormat(format!(
+                        "{}:{}: {}",
+                        path.display(),
+                        number + 1,
+                        e
+                    ))
+                })?;
+                record.apply(&mut storage);
+                records += 1;
+            }
+        }
+
+        let writer = BufWriter::new(OpenOptions::new().create(true).append(true).open(&path)?);
+        Ok(Self {
+            index: Arc::new(RwLock::new(ComponentIndex::build(&storage))),
+            storage: Arc::new(RwLock::new(storage)),
+            log: Some(Arc::new(Mutex::new(EntityLog {
+                path,
+                writer,
+                records,
+            }))),
+        })
+    }
+
+    /// Rewrites the log so it holds exactly one record per stored entity.
+    ///
+    /// Does nothing for in-memory plugins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be rewritten.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn compact(&self) -> Result<()> {
+        let storage = self.storage.read().unwrap();
+        match &self.log {
+            Some(log) => log.lock().unwrap().compact(&storage),
+            None => Ok(()),
         }
     }
 
@@ -128,12 +415,70 @@ impl KeyValueEntityPlugin {
     /// assert!(plugin.is_empty());
     /// ```
     pub fn clear(&self) {
-        self.storage.write().unwrap().clear();
+        let mut storage = self.storage.write().unwrap();
+        storage.clear();
+        *self.index.write().unwrap() = ComponentIndex::default();
+        // Best effort: the store is cleared in memory either way
+        let _ = self.write_log(&storage, [LogRecord::Clear]);
     }
 
-    /// Returns a list of all stored stable IDs.
+    /// Returns a list of all stored stable IDs, sorted.
     pub fn list_entities(&self) -> Vec<StableId> {
-        self.storage.read().unwrap().keys().copied().collect()
+        let mut ids: Vec<_> = self.storage.read().unwrap().keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Captures an entity's JSON-registered components.
+    fn capture(world: &World, entity: EntityId) -> Result<EntityData> {
+        let stable_id = world
+            .get_stable_id(entity)
+            .ok_or(PersistenceError::EntityNotFound(entity))?;
+
+        let registry = ComponentRegistry::global();
+        let mut components = Vec::new();
+        for name in registry.json_names() {
+            let Some(value) = registry.get_json(&name, world, entity) else {
+                continue;
+            };
+            let Some(info) = registry.info_by_name(&name) else {
+                continue;
+            };
+            components.push(ComponentData {
+                type_id: info.type_id().type_id(),
+                type_name: name,
+                data: serde_json::to_vec(&value)
+                    .map_err(|e| PersistenceError::Serialization(e.to_string()))?,
+            });
+        }
+
+        Ok(EntityData::new(
+            stable_id,
+            components,
+            EntityData::current_timestamp(),
+        ))
+    }
+
+    /// Appends records to the log, if this plugin has one, compacting it
+    /// when it has grown too long. `storage` is the already-updated store.
+    fn write_log(
+        &self,
+        storage: &HashMap<StableId, EntityData>,
+        records: impl IntoIterator<Item = LogRecord>,
+    ) -> Result<()> {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        if let Some(log) = &self.log {
+            let mut log = log.lock().unwrap();
+            for record in records {
+                log.append(&record)?;
+            }
+            if log.should_compact(storage.len()) {
+                log.compact(storage)?;
+            }
+        }
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        let _ = (storage, records.into_iter());
+        Ok(())
     }
 }
 
@@ -145,24 +490,12 @@ impl Default for KeyValueEntityPlugin {
 
 impl EntityPersistencePlugin for KeyValueEntityPlugin {
     fn save_entity(&self, world: &World, entity: EntityId) -> Result<()> {
-        // Get the stable ID for this entity
-        let stable_id = world
-            .get_stable_id(entity)
-            .ok_or(PersistenceError::EntityNotFound(entity))?;
-
-        // TODO: Collect component data from the world
-        // For now, we'll create an empty entity data
-        let entity_data = EntityData::new(stable_id, Vec::new(), EntityData::current_timestamp());
-
-        // Store in the HashMap
-        self.storage.write().unwrap().insert(stable_id, entity_data);
-
-        Ok(())
+        self.save_entities(world, &[entity])
     }
 
     fn load_entity(&self, world: &mut World, stable_id: StableId) -> Result<EntityId> {
         // Get the entity data from storage
-        let _entity_data = self
+        let entity_data = self
             .storage
             .read()
             .unwrap()
@@ -172,25 +505,30 @@ impl EntityPersistencePlugin for KeyValueEntityPlugin {
                 PersistenceError::Custom(format!("Entity with stable ID {} not found", stable_id))
             })?;
 
-        // Check if entity already exists in world
-        if let Some(entity_id) = world.get_entity_by_stable_id(stable_id) {
-            // Entity exists, update it
-            // TODO: Update components
-            Ok(entity_id)
-        } else {
-            // Create new entity with the stable ID
-            let entity_id = world
+        // Update the entity if it already exists in the world
+        let entity_id = match world.get_entity_by_stable_id(stable_id) {
+            Some(entity_id) => entity_id,
+            None => world
                 .spawn_empty_with_stable_id(stable_id)
-                .map_err(|e| PersistenceError::Custom(format!("Failed to spawn entity: {}", e)))?;
-
-            // TODO: Restore components from entity_data
-
-            Ok(entity_id)
+                .map_err(|e| PersistenceError::Custom(format!("Failed to spawn entity: {}", e)))?,
+        };
+
+        let registry = ComponentRegistry::global();
+        for component in entity_data.components {
+            let value = serde_json::from_slice(&component.data)
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            registry.insert_json(&component.type_name, world, entity_id, value)?;
         }
+
+        Ok(entity_id)
     }
 
     fn delete_entity(&self, stable_id: StableId) -> Result<()> {
-        self.storage.write().unwrap().remove(&stable_id);
+        let mut storage = self.storage.write().unwrap();
+        if let Some(removed) = storage.remove(&stable_id) {
+            self.index.write().unwrap().remove(&removed);
+            self.write_log(&storage, [LogRecord::Delete { stable_id }])?;
+        }
         Ok(())
     }
 
@@ -198,23 +536,33 @@ impl EntityPersistencePlugin for KeyValueEntityPlugin {
         Ok(self.storage.read().unwrap().contains_key(&stable_id))
     }
 
+    fn find_entities_with(&self, component_type_name: &str) -> Result<Vec<StableId>> {
+        Ok(self.index.read().unwrap().find(component_type_name))
+    }
+
     fn save_entities(&self, world: &World, entities: &[EntityId]) -> Result<()> {
+        // Capture everything before taking the lock
+        let captured = entities
+            .iter()
+            .map(|&entity| Self::capture(world, entity))
+            .collect::<Result<Vec<_>>>()?;
+        let records = captured
+            .iter()
+            .map(LogRecord::put)
+            .collect::<Result<Vec<_>>>()?;
+
         // Batch operation - acquire write lock once
         let mut storage = self.storage.write().unwrap();
-
-        for &entity in entities {
-            let stable_id = world
-                .get_stable_id(entity)
-                .ok_or(PersistenceError::EntityNotFound(entity))?;
-
-            // TODO: Collect component data
-            let entity_data =
-                EntityData::new(stable_id, Vec::new(), EntityData::current_timestamp());
-
-            storage.insert(stable_id, entity_data);
+        let mut index = self.index.write().unwrap();
+        for entity_data in captured {
+            if let Some(previous) = storage.get(&entity_data.stable_id) {
+                index.remove(previous);
+            }
+            index.insert(&entity_data);
+            storage.insert(entity_data.stable_id, entity_data);
         }
-
-        Ok(())
+        drop(index);
+        self.write_log(&storage, records)
     }
 
     fn load_entities(&self, world: &mut World, stable_ids: &[StableId]) -> Result<Vec<EntityId>> {
@@ -228,6 +576,10 @@ impl EntityPersistencePlugin for KeyValueEntityPlugin {
     }
 
     fn backend_name(&self) -> &str {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        if self.log.is_some() {
+            return "key_value_file";
+        }
         "key_value_memory"
     }
 
@@ -324,6 +676,131 @@ mod tests {
         assert!(entities.contains(&id1));
         assert!(entities.contains(&id2));
     }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Level(u32);
+    impl crate::Component for Level {}
+
+    fn log_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pecs_entity_kv_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn file_backed_store_survives_reopen() {
+        ComponentRegistry::global().register_json::<Level>("entity_kv::Level");
+        let dir = log_dir("reopen");
+
+        let mut world = World::new();
+        let kept = world.spawn().with(Level(3)).id();
+        let dropped = world.spawn().with(Level(9)).id();
+        let kept_id = world.get_stable_id(kept).unwrap();
+        let dropped_id = world.get_stable_id(dropped).unwrap();
+
+        {
+            let plugin = KeyValueEntityPlugin::with_path(&dir).unwrap();
+            assert_eq!(plugin.backend_name(), "key_value_file");
+            plugin.save_entities(&world, &[kept, dropped]).unwrap();
+            plugin.delete_entity(dropped_id).unwrap();
+        }
+
+        let plugin = KeyValueEntityPlugin::with_path(&dir).unwrap();
+        assert_eq!(plugin.list_entities(), vec![kept_id]);
+        assert_eq!(
+            plugin.find_entities_with("entity_kv::Level").unwrap(),
+            vec![kept_id]
+        );
+
+        let mut restored = World::new();
+        let entity = plugin.load_entity(&mut restored, kept_id).unwrap();
+        assert_eq!(restored.get::<Level>(entity), Some(&Level(3)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Shop;
+    impl crate::Component for Shop {}
+
+    #[test]
+    fn find_entities_with_uses_component_index() {
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Level>("entity_kv::Level");
+        registry.register_json::<Shop>("entity_kv::Shop");
+
+        let mut world = World::new();
+        let shop = world.spawn().with(Level(1)).with(Shop).id();
+        let house = world.spawn().with(Level(2)).id();
+        let shop_id = world.get_stable_id(shop).unwrap();
+        let house_id = world.get_stable_id(house).unwrap();
+
+        let plugin = KeyValueEntityPlugin::new();
+        plugin.save_entities(&world, &[shop, house]).unwrap();
+        assert_eq!(
+            plugin.find_entities_with("entity_kv::Shop").unwrap(),
+            vec![shop_id]
+        );
+        assert_eq!(
+            plugin.find_entities_with("entity_kv::Level").unwrap().len(),
+            2
+        );
+
+        // Re-saving replaces the indexed components
+        world.remove::<Shop>(shop);
+        plugin.save_entity(&world, shop).unwrap();
+        assert!(
+            plugin
+                .find_entities_with("entity_kv::Shop")
+                .unwrap()
+                .is_empty()
+        );
+
+        plugin.delete_entity(house_id).unwrap();
+        assert_eq!(
+            plugin.find_entities_with("entity_kv::Level").unwrap(),
+            vec![shop_id]
+        );
+    }
+
+    #[test]
+    fn compaction_keeps_latest_records() {
+        ComponentRegistry::global().register_json::<Level>("entity_kv::Level");
+        let dir = log_dir("compact");
+
+        let mut world = World::new();
+        let entity = world.spawn().with(Level(0)).id();
+        let stable_id = world.get_stable_id(entity).unwrap();
+
+        let plugin = KeyValueEntityPlugin::with_path(&dir).unwrap();
+        for level in 1..=COMPACTION_MIN_RECORDS as u32 + 10 {
+            world.get_mut::<Level>(entity).unwrap().0 = level;
+            plugin.save_entity(&world, entity).unwrap();
+        }
+
+        // Automatic compaction has kept the log short
+        let lines = || {
+            fs::read_to_string(dir.join(LOG_FILE))
+                .unwrap()
+                .lines()
+                .count()
+        };
+        assert!(lines() < COMPACTION_MIN_RECORDS);
+
+        plugin.compact().unwrap();
+        assert_eq!(lines(), 1);
+        drop(plugin);
+
+        let plugin = KeyValueEntityPlugin::with_path(&dir).unwrap();
+        let mut restored = World::new();
+        let entity = plugin.load_entity(&mut restored, stable_id).unwrap();
+        assert_eq!(
+            restored.get::<Level>(entity),
+            Some(&Level(COMPACTION_MIN_RECORDS as u32 + 10))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
 
 // Made with Bob
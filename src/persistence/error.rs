@@ -18,6 +18,12 @@
 //!
 //! This module provides comprehensive error types for persistence operations,
 //! with detailed error messages and context information to aid in debugging.
+//!
+//! Errors raised while reading a file can be wrapped with an
+//! [`ErrorContext`] recording where they happened: the path, the byte
+//! offset, the entity and the component type. Errors from other libraries
+//! are kept as [`source`](std::error::Error::source)s that can be
+//! downcast to their original type.
 
 use std::fmt;
 use std::io;
@@ -110,6 +116,88 @@ pub enum PersistenceError {
         /// Actual checksum.
         actual: u64,
     },
+
+    /// A save exceeds a size limit set in
+    /// [`LoadOptions`](crate::persistence::LoadOptions).
+    ///
+    /// Loading stops as soon as the excess is seen, so `actual` may be a
+    /// lower bound rather than the full size.
+    LimitExceeded {
+        /// Name of the exceeded option, such as `"max_entities"`.
+        limit: &'static str,
+        /// The configured limit.
+        max: u64,
+        /// The size found in the save.
+        actual: u64,
+    },
+
+    /// An error from another library.
+    ///
+    /// The original error is returned by
+    /// [`source`](std::error::Error::source) and can be downcast to its type.
+    Source {
+        /// What was being done when the error occurred.
+        message: String,
+        /// The original error.
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+
+    /// Another error, annotated with where it occurred.
+    ///
+    /// Use [`root`](PersistenceError::root) to inspect the underlying error
+    /// and [`context`](PersistenceError::context) for the location.
+    Context {
+        /// The underlying error.
+        error: Box<PersistenceError>,
+        /// Where the error occurred.
+        context: ErrorContext,
+    },
+}
+
+/// Where a persistence error occurred.
+///
+/// Every field is optional; readers fill in what they know. Displayed as a
+/// comma-separated list, such as
+/// `world.pecs, offset 1024, entity #3, component 'Position'`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The file being read or written.
+    pub path: Option<PathBuf>,
+    /// Byte offset into the stream where the failing item starts.
+    pub offset: Option<u64>,
+    /// Position of the failing entity in the stream, counting from zero.
+    pub entity_index: Option<u64>,
+    /// Name of the component type being processed.
+    pub component: Option<String>,
+}
+
+impl ErrorContext {
+    /// Returns `true` if no location is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.path.is_none()
+            && self.offset.is_none()
+            && self.entity_index.is_none()
+            && self.component.is_none()
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(path) = &self.path {
+            parts.push(path.display().to_string());
+        }
+        if let Some(offset) = self.offset {
+            parts.push(format!("offset {}", offset));
+        }
+        if let Some(index) = self.entity_index {
+            parts.push(format!("entity #{}", index));
+        }
+        if let Some(component) = &self.component {
+            parts.push(format!("component '{}'", component));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
 }
 
 impl PersistenceError {
@@ -165,7 +253,99 @@ impl PersistenceError {
         Self::InvalidFormat(msg.into())
     }
 
-    /// Add context to an I/O error with file path information.
+    /// Wrap an error from another library, keeping it as the source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pecs::persistence::PersistenceError;
+    /// use std::error::Error;
+    ///
+    /// let parse = serde_json::from_str::<u32>("oops").unwrap_err();
+    /// let error = PersistenceError::from_source("Invalid metadata", parse);
+    /// assert!(error.source().unwrap().is::<serde_json::Error>());
+    /// ```
+    pub fn from_source(
+        message: impl Into<String>,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::Source {
+            message: message.into(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Returns the error beneath any [`Context`](Self::Context) wrappers.
+    pub fn root(&self) -> &PersistenceError {
+        match self {
+            Self::Context { error, .. } => error.root(),
+            other => other,
+        }
+    }
+
+    /// Returns where the error occurred, if recorded.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Applies `update` to this error's context, adding one if needed.
+    fn annotate(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        match self {
+            Self::Context { error, mut context } => {
+                update(&mut context);
+                Self::Context { error, context }
+            }
+            error => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                Self::Context {
+                    error: Box::new(error),
+                    context,
+                }
+            }
+        }
+    }
+
+    /// Record the byte offset where the failing item starts.
+    ///
+    /// Offsets recorded closer to the failure win over later ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pecs::persistence::PersistenceError;
+    ///
+    /// let error = PersistenceError::invalid_format("Bad component length")
+    ///     .at_offset(1024)
+    ///     .at_entity(3)
+    ///     .in_component("Position");
+    /// assert_eq!(error.context().unwrap().offset, Some(1024));
+    /// assert!(error.root().is_corruption());
+    /// ```
+    pub fn at_offset(self, offset: u64) -> Self {
+        self.annotate(|context| {
+            context.offset.get_or_insert(offset);
+        })
+    }
+
+    /// Record the position of the failing entity in the stream.
+    pub fn at_entity(self, index: u64) -> Self {
+        self.annotate(|context| {
+            context.entity_index.get_or_insert(index);
+        })
+    }
+
+    /// Record the name of the component type being processed.
+    pub fn in_component(self, name: impl Into<String>) -> Self {
+        self.annotate(|context| {
+            context.component.get_or_insert_with(|| name.into());
+        })
+    }
+
+    /// Record the file path being read or written.
     ///
     /// This is useful for providing more detailed error messages that include
     /// the file path that caused the error.
@@ -182,14 +362,9 @@ impl PersistenceError {
     ///     .map_err(|e| PersistenceError::from(e).with_path(path));
     /// ```
     pub fn with_path(self, path: impl Into<PathBuf>) -> Self {
-        match self {
-            Self::Io(err) => {
-                let path_buf = path.into();
-                let msg = format!("{} (file: {})", err, path_buf.display());
-                Self::Io(io::Error::new(err.kind(), msg))
-            }
-            other => other,
-        }
+        self.annotate(|context| {
+            context.path.get_or_insert_with(|| path.into());
+        })
     }
 
     /// Get a suggestion for how to resolve this error, if available.
@@ -212,7 +387,7 @@ impl PersistenceError {
     /// }
     /// ```
     pub fn suggestion(&self) -> Option<&str> {
-        match self {
+        match self.root() {
             Self::VersionMismatch { .. } => Some(
                 "Try using a migration to convert the data to the current version, or re-save the world with the current version",
             ),
@@ -228,6 +403,9 @@ impl PersistenceError {
             Self::InvalidFormat(_) => {
                 Some("Ensure the file is a valid PECS persistence file and hasn't been corrupted")
             }
+            Self::LimitExceeded { .. } => {
+                Some("Raise the limit in LoadOptions if the save is trusted, otherwise reject it")
+            }
             _ => None,
         }
     }
@@ -238,7 +416,7 @@ impl PersistenceError {
     /// issue can be fixed by the user.
     pub fn is_recoverable(&self) -> bool {
         matches!(
-            self,
+            self.root(),
             Self::Io(_) | Self::PluginNotFound(_) | Self::UnknownComponentType(_)
         )
     }
@@ -247,7 +425,10 @@ impl PersistenceError {
     ///
     /// Returns `true` if the error suggests the data file is corrupted.
     pub fn is_corruption(&self) -> bool {
-        matches!(self, Self::ChecksumMismatch { .. } | Self::InvalidFormat(_))
+        matches!(
+            self.root(),
+            Self::ChecksumMismatch { .. } | Self::InvalidFormat(_)
+        )
     }
 }
 
@@ -344,6 +525,28 @@ impl fmt::Display for PersistenceError {
                 }
                 Ok(())
             }
+            Self::LimitExceeded { limit, max, actual } => {
+                write!(
+                    f,
+                    "Load limit exceeded: {} is {}, but the save needs at least {}",
+                    limit, max, actual
+                )?;
+                if let Some(suggestion) = self.suggestion() {
+                    write!(f, "\nSuggestion: {}", suggestion)?;
+                }
+                Ok(())
+            }
+            Self::Source { message, source } => {
+                write!(f, "{}: {}", message, source)?;
+                Ok(())
+            }
+            Self::Context { error, context } => {
+                if context.is_empty() {
+                    write!(f, "{}", error)
+                } else {
+                    write!(f, "{}: {}", context, error)
+                }
+            }
         }
     }
 }
@@ -352,6 +555,8 @@ impl std::error::Error for PersistenceError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Io(err) => Some(err),
+            Self::Source { source, .. } => Some(source.as_ref()),
+            Self::Context { error, .. } => error.source(),
             _ => None,
         }
     }
@@ -362,3 +567,82 @@ impl From<io::Error> for PersistenceError {
         Self::Io(err)
     }
 }
+
+/// Errors from persistence work that ran deferred, such as the commands
+/// recorded by [`CommandBuffer::save_world`](crate::CommandBuffer::save_world).
+///
+/// Stored as a world resource, which is created when the first error is
+/// reported. Drain it after applying commands to see what failed.
+///
+/// # Example
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::persistence::PersistenceErrors;
+///
+/// let mut world = World::new();
+/// let entity = world.spawn_empty();
+/// world.commands().save_entity(entity, "missing");
+/// world.apply_commands();
+///
+/// let errors = world.resource_mut::<PersistenceErrors>().unwrap();
+/// assert_eq!(errors.drain().count(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct PersistenceErrors {
+    errors: Vec<PersistenceError>,
+}
+
+impl PersistenceErrors {
+    /// Records an error.
+    pub fn push(&mut self, error: PersistenceError) {
+        self.errors.push(error);
+    }
+
+    /// Returns the recorded errors, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &PersistenceError> {
+        self.errors.iter()
+    }
+
+    /// Removes and returns all recorded errors.
+    pub fn drain(&mut self) -> impl Iterator<Item = PersistenceError> + '_ {
+        self.errors.drain(..)
+    }
+
+    /// Returns the number of recorded errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn context_wraps_once_and_keeps_innermost_values() {
+        let error = PersistenceError::from(io::Error::from(io::ErrorKind::UnexpectedEof))
+            .at_offset(20)
+            .at_entity(2)
+            .at_offset(0)
+            .with_path("world.pecs");
+
+        let context = error.context().unwrap();
+        assert_eq!(context.offset, Some(20));
+        assert_eq!(context.entity_index, Some(2));
+        assert!(matches!(error.root(), PersistenceError::Io(_)));
+        assert!(error.is_recoverable());
+        assert!(error.source().unwrap().is::<io::Error>());
+        assert!(
+            error
+                .to_string()
+                .starts_with("world.pecs, offset 20, entity #2: I/O error")
+        );
+    }
+}
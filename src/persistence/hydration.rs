@@ -0,0 +1,127 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Lazy entity hydration.
+//!
+//! Large worlds do not need every entity in memory. An entity can exist as
+//! a proxy carrying only its [`StableId`] and an [`Unloaded`] component;
+//! its real components stay in an
+//! [`EntityPersistencePlugin`](crate::persistence::EntityPersistencePlugin)
+//! until something needs them. [`World::unload_entity`] and
+//! [`World::unload_where`] turn live entities into proxies.
+//!
+//! [`World::unload_entity`]: crate::World::unload_entity
+//! [`World::unload_where`]: crate::World::unload_where
+//!
+//! Proxies are hydrated either directly, with [`World::hydrate`](crate::World::hydrate), or by
+//! marking them while iterating and hydrating every marked proxy at a
+//! convenient point:
+//!
+//! ```
+//! use pecs::component::registry::ComponentRegistry;
+//! use pecs::persistence::{EntityPersistencePlugin, KeyValueEntityPlugin, Unloaded};
+//! use pecs::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Shop { gold: u32 }
+//! impl Component for Shop {}
+//!
+//! ComponentRegistry::global().register_json::<Shop>("Shop");
+//!
+//! // Persist an entity from some earlier session
+//! let plugin = KeyValueEntityPlugin::new();
+//! let mut old = World::new();
+//! let shop = old.spawn().with(Shop { gold: 40 }).id();
+//! let stable_id = old.get_stable_id(shop).unwrap();
+//! plugin.save_entity(&old, shop).unwrap();
+//!
+//! // The live world only holds a proxy
+//! let mut world = World::new();
+//! world.persistence().register_entity_plugin("kv", Box::new(plugin));
+//! let proxy = world.spawn_unloaded(stable_id).unwrap();
+//!
+//! // A system touches the proxy...
+//! for unloaded in world.query::<&Unloaded>() {
+//!     unloaded.request();
+//! }
+//!
+//! // ...and it is loaded before the next frame
+//! world.hydrate_requested().unwrap();
+//! assert_eq!(world.get::<Shop>(proxy), Some(&Shop { gold: 40 }));
+//! assert!(!world.has::<Unloaded>(proxy));
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::component::Component;
+use crate::entity::StableId;
+
+/// Placeholder component for an entity whose components are still in
+/// storage.
+///
+/// The proxy entity keeps its stable ID, so references to it stay valid.
+/// Hydrating it loads the stored components through the entity plugin and
+/// removes this marker, moving the entity to its real archetype.
+#[derive(Debug)]
+pub struct Unloaded {
+    stable_id: StableId,
+    requested: AtomicBool,
+}
+
+impl Unloaded {
+    /// Creates a proxy marker for the entity stored under `stable_id`.
+    pub fn new(stable_id: StableId) -> Self {
+        Self {
+            stable_id,
+            requested: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the stable ID the entity is stored under.
+    pub fn stable_id(&self) -> StableId {
+        self.stable_id
+    }
+
+    /// Asks for the entity to be loaded at the next
+    /// [`World::hydrate_requested`](crate::World::hydrate_requested).
+    ///
+    /// Only needs shared access, so it can be called from read-only
+    /// queries.
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if hydration has been requested.
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::Relaxed)
+    }
+}
+
+impl Component for Unloaded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_marks_proxy() {
+        let unloaded = Unloaded::new(StableId::new());
+        assert!(!unloaded.is_requested());
+        unloaded.request();
+        assert!(unloaded.is_requested());
+    }
+}
@@ -0,0 +1,484 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Write-ahead change journal.
+//!
+//! A [`JournalPlugin`] keeps a world durable between explicit saves. Each
+//! [`commit`](JournalPlugin::commit) appends the entities spawned, changed
+//! or despawned since the previous commit to a log file and syncs it to
+//! disk; every so often the log is replaced by a full snapshot of the world
+//! so it stays short. After a crash, [`World::recover`] rebuilds the world
+//! as of the last completed commit.
+//!
+//! Changes are read from the world's
+//! [`ChangeTracker`](crate::persistence::ChangeTracker), so everything that
+//! marks an entity changed is journaled: spawns, despawns, inserts,
+//! removals, [`get_mut`](World::get_mut) and applied commands. Components
+//! are journaled as JSON, so only component types registered with
+//! [`ComponentRegistry::register_json`] survive recovery. Resources and
+//! world metadata are not journaled.
+//!
+//! Mutations made through `&mut T` query items do not mark the entity
+//! changed and are not journaled until something else does; mark such
+//! entities with
+//! [`ChangeTracker::track_modified`](crate::persistence::ChangeTracker::track_modified)
+//! before committing. A journal needs change tracking, so
+//! [`create`](JournalPlugin::create) and [`commit`](JournalPlugin::commit)
+//! fail while it is turned off with
+//! [`World::set_change_tracking`].
+//!
+//! The log holds one JSON record per line. A crash in the middle of a
+//! commit leaves an unterminated last line, which recovery ignores.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use pecs::World;
+//! use pecs::persistence::JournalPlugin;
+//!
+//! // Pick up where the last run left off
+//! let mut world = World::recover("world.wal").unwrap_or_else(|_| World::new());
+//! let mut journal = JournalPlugin::create("world.wal", &mut world).unwrap();
+//!
+//! loop {
+//!     // ... run systems that record commands ...
+//!     journal.apply_commands(&mut world).unwrap();
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::{EntityId, StableId};
+use crate::persistence::{PersistenceError, Result};
+
+/// Commits between automatic snapshots unless configured otherwise.
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 1000;
+
+/// One line of the journal.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JournalRecord {
+    /// The complete world, replacing everything before it
+    Snapshot { entities: Vec<JournaledEntity> },
+    /// The changes of one commit
+    Commit {
+        /// Entities spawned or changed, with all their components
+        written: Vec<JournaledEntity>,
+        /// Entities despawned
+        despawned: Vec<StableId>,
+    },
+}
+
+/// An entity and its JSON-registered components.
+#[derive(Debug, Serialize, Deserialize)]
+struct JournaledEntity {
+    stable_id: StableId,
+    components: BTreeMap<String, serde_json::Value>,
+}
+
+impl JournaledEntity {
+    fn capture(world: &World, entity: EntityId, stable_id: StableId, names: &[String]) -> Self {
+        let registry = ComponentRegistry::global();
+        let components = names
+            .iter()
+            .filter_map(|name| Some((name.clone(), registry.get_json(name, world, entity)?)))
+            .collect();
+        Self {
+            stable_id,
+            components,
+        }
+    }
+
+    /// Spawns the entity into `world`, replacing any entity with the same
+    /// stable ID.
+    fn restore(self, world: &mut World) -> Result<()> {
+        if let Some(existing) = world.get_entity_by_stable_id(self.stable_id) {
+            world.despawn(existing);
+        }
+        let entity = world
+            .spawn_empty_with_stable_id(self.stable_id)
+            .map_err(|e| {
+                PersistenceError::EntityIdConflict(format!("{}: {:?}", self.stable_id, e))
+            })?;
+        let registry = ComponentRegistry::global();
+        for (name, value) in self.components {
+            registry.insert_json(&name, world, entity, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Appends a world's changes to a write-ahead log file.
+///
+/// See the [module documentation](self) for what is journaled.
+pub struct JournalPlugin {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Change tracker epoch the next commit reads from
+    epoch: u32,
+    /// Stable IDs of the journaled entities, for logging their despawns
+    journaled: HashMap<EntityId, StableId>,
+    snapshot_interval: u64,
+    commits_since_snapshot: u64,
+    sync: bool,
+}
+
+impl JournalPlugin {
+    /// Starts a journal at `path` holding a snapshot of `world`.
+    ///
+    /// An existing file is replaced, so recover from it first if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the world's change tracking is turned off, or
+    /// if the file cannot be written.
+    pub fn create(path: impl AsRef<Path>, world: &mut World) -> Result<Self> {
+        require_change_tracking(world)?;
+        let path = path.as_ref().to_path_buf();
+        let writer = BufWriter::new(File::create(&path)?);
+        let mut journal = Self {
+            path,
+            writer,
+            epoch: 0,
+            journaled: HashMap::new(),
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+            commits_since_snapshot: 0,
+            sync: true,
+        };
+        journal.snapshot(world)?;
+        Ok(journal)
+    }
+
+    /// Replaces the log with a snapshot every `commits` commits. Zero
+    /// disables automatic snapshots.
+    pub fn with_snapshot_interval(mut self, commits: u64) -> Self {
+        self.snapshot_interval = commits;
+        self
+    }
+
+    /// Whether each commit waits for the log to reach the disk. On by
+    /// default; turning it off trades durability for speed, as a crash of
+    /// the operating system may then lose recent commits.
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Returns the path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Applies the world's pending commands and commits the result.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities journaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be written. The commands stay
+    /// applied.
+    pub fn apply_commands(&mut self, world: &mut World) -> Result<usize> {
+        world.apply_commands();
+        self.commit(world)
+    }
+
+    /// Appends every change since the previous commit to the log.
+    ///
+    /// Spawned and changed entities are written with all their components;
+    /// entities spawned and despawned in between are left out. Writes a
+    /// snapshot instead once the snapshot interval is reached.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities journaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the world's change tracking is turned off, since
+    /// its changes would be lost, or if the log cannot be written.
+    pub fn commit(&mut self, world: &mut World) -> Result<usize> {
+        require_change_tracking(world)?;
+        let tracker = world.persistence().change_tracker_mut();
+        let changes = tracker.changes_since(self.epoch);
+        self.epoch = tracker.advance();
+
+        if self.snapshot_interval > 0 && self.commits_since_snapshot + 1 >= self.snapshot_interval {
+            self.snapshot(world)?;
+            return Ok(world.len());
+        }
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        let names = ComponentRegistry::global().json_names();
+        let mut written = Vec::new();
+        let mut despawned = Vec::new();
+        for entity in changes.created.into_iter().chain(changes.modified) {
+            let Some(stable_id) = world.get_stable_id(entity) else {
+                continue;
+            };
+            // A remapped entity leaves its old stable ID behind
+            if let Some(previous) = self.journaled.insert(entity, stable_id)
+                && previous != stable_id
+            {
+                despawned.push(previous);
+            }
+            written.push(JournaledEntity::capture(world, entity, stable_id, &names));
+        }
+        for entity in changes.deleted {
+            if let Some(stable_id) = self.journaled.remove(&entity) {
+                despawned.push(stable_id);
+            }
+        }
+
+        let count = written.len() + despawned.len();
+        self.append(&JournalRecord::Commit { written, despawned })?;
+        self.commits_since_snapshot += 1;
+        Ok(count)
+    }
+
+    /// Replaces the log with a snapshot of `world`.
+    ///
+    /// Call this after an explicit save to keep the log short.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be written. The previous log
+    /// is kept intact in that case.
+    pub fn snapshot(&mut self, world: &mut World) -> Result<()> {
+        self.epoch = world.persistence().change_tracker_mut().advance();
+
+        let names = ComponentRegistry::global().json_names();
+        self.journaled.clear();
+        let mut entities = Vec::with_capacity(world.len());
+        for (entity, stable_id) in world.iter_entities() {
+            self.journaled.insert(entity, stable_id);
+            entities.push(JournaledEntity::capture(world, entity, stable_id, &names));
+        }
+        entities.sort_by_key(|entity| entity.stable_id);
+
+        // Write beside the log and rename over it, so a crash leaves
+        // either the old log or the new one
+        let temp = self.path.with_extension("wal.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&temp)?);
+            write_record(&mut writer, &JournalRecord::Snapshot { entities })?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        fs::rename(&temp, &self.path)?;
+        self.writer = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        self.commits_since_snapshot = 0;
+        Ok(())
+    }
+
+    fn append(&mut self, record: &JournalRecord) -> Result<()> {
+        write_record(&mut self.writer, record)?;
+        self.writer.flush()?;
+        if self.sync {
+            self.writer.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+/// Fails if `world` does not record the changes a journal reads.
+fn require_change_tracking(world: &World) -> Result<()> {
+    if world.is_change_tracking() {
+        Ok(())
+    } else {
+        Err(PersistenceError::PluginError(
+            "the journal needs change tracking, which is turned off".to_string(),
+        ))
+    }
+}
+
+fn write_record(writer: &mut dyn Write, record: &JournalRecord) -> Result<()> {
+    serde_json::to_writer(&mut *writer, record)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Rebuilds a world from the journal at `path`.
+///
+/// Backs [`World::recover`].
+pub(crate) fn recover(path: &Path) -> Result<World> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    // Only newline-terminated records were completely written
+    let complete = contents.rfind('\n').map_or("", |end| &contents[..end]);
+
+    let mut world = World::new();
+    for (number, line) in complete.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: JournalRecord = serde_json::from_str(line).map_err(|e| {
+            PersistenceError::InvalidFormat(format!("{}:{}: {}", path.display(), number + 1, e))
+        })?;
+        match record {
+            JournalRecord::Snapshot { entities } => {
+                world = World::new();
+                for entity in entities {
+                    entity.restore(&mut world)?;
+                }
+            }
+            JournalRecord::Commit { written, despawned } => {
+                for stable_id in despawned {
+                    if let Some(entity) = world.get_entity_by_stable_id(stable_id) {
+                        world.despawn(entity);
+                    }
+                }
+                for entity in written {
+                    entity.restore(&mut world)?;
+                }
+            }
+        }
+    }
+    Ok(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::replay::state_hash;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Gold(u32);
+    impl Component for Gold {}
+
+    fn journal_path(name: &str) -> PathBuf {
+        ComponentRegistry::global().register_json::<Gold>("journal::Gold");
+        std::env::temp_dir().join(format!("pecs_journal_{}.wal", name))
+    }
+
+    fn line_count(path: &Path) -> usize {
+        fs::read_to_string(path).unwrap().lines().count()
+    }
+
+    #[test]
+    fn recovery_replays_commits_and_skips_torn_tail() {
+        let path = journal_path("replay");
+        let mut world = World::new();
+        let a = world.spawn().with(Gold(1)).id();
+        let b = world.spawn().with(Gold(2)).id();
+        let mut journal = JournalPlugin::create(&path, &mut world)
+            .unwrap()
+            .with_snapshot_interval(0);
+
+        let c = world.spawn().with(Gold(3)).id();
+        world.get_mut::<Gold>(a).unwrap().0 = 10;
+        world.despawn(b);
+        let temporary = world.spawn().with(Gold(4)).id();
+        world.despawn(temporary);
+        assert_eq!(journal.commit(&mut world).unwrap(), 3);
+        assert_eq!(journal.commit(&mut world).unwrap(), 0);
+
+        world.commands().insert(c, Gold(30));
+        world.commands().remove::<Gold>(a);
+        assert_eq!(journal.apply_commands(&mut world).unwrap(), 2);
+        assert_eq!(line_count(&path), 3);
+
+        // A commit cut short by a crash
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"kind":"commit","written":[{"#).unwrap();
+        drop(file);
+
+        let recovered = World::recover(&path).unwrap();
+        assert_eq!(recovered.len(), 2);
+        assert_eq!(state_hash(&recovered), state_hash(&world));
+        let c = recovered
+            .get_entity_by_stable_id(world.get_stable_id(c).unwrap())
+            .unwrap();
+        assert_eq!(recovered.get::<Gold>(c), Some(&Gold(30)));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn journaling_requires_change_tracking() {
+        let path = journal_path("untracked");
+        let mut world = World::new();
+        world.set_change_tracking(false);
+        assert!(matches!(
+            JournalPlugin::create(&path, &mut world),
+            Err(PersistenceError::PluginError(_))
+        ));
+
+        world.set_change_tracking(true);
+        let mut journal = JournalPlugin::create(&path, &mut world).unwrap();
+        world.set_change_tracking(false);
+        world.spawn().with(Gold(1));
+        assert!(matches!(
+            journal.commit(&mut world),
+            Err(PersistenceError::PluginError(_))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn snapshots_compact_the_log() {
+        let path = journal_path("snapshot");
+        let mut world = World::new();
+        let mut journal = JournalPlugin::create(&path, &mut world)
+            .unwrap()
+            .with_snapshot_interval(3);
+
+        for amount in 0..5 {
+            world.spawn().with(Gold(amount)).id();
+            journal.commit(&mut world).unwrap();
+        }
+        // The third commit became a snapshot, followed by two commits
+        assert_eq!(line_count(&path), 3);
+        assert_eq!(
+            state_hash(&World::recover(&path).unwrap()),
+            state_hash(&world)
+        );
+
+        let entity = world.spawn().with(Gold(99)).id();
+        journal.snapshot(&mut world).unwrap();
+        assert_eq!(line_count(&path), 1);
+        world.despawn(entity);
+        journal.commit(&mut world).unwrap();
+        assert_eq!(World::recover(&path).unwrap().len(), 5);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_records_fail_recovery() {
+        let path = journal_path("corrupt");
+        let mut world = World::new();
+        JournalPlugin::create(&path, &mut world).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"not a record\n").unwrap();
+        drop(file);
+
+        let error = World::recover(&path).err().unwrap();
+        assert!(matches!(error, PersistenceError::InvalidFormat(_)));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -48,10 +48,20 @@
 //! ```
 
 mod deserialize;
+pub(crate) mod schema;
 mod serialize;
 
+/// Value of the `$format` field identifying a world document.
+const FORMAT: &str = "pecs.world";
+
+/// Current version of the JSON world format.
+///
+/// Version 1 documents, which had a plain `version` field and unversioned
+/// component values, are upgraded on load.
+const VERSION: u32 = 2;
+
 use crate::World;
-use crate::persistence::{PersistencePlugin, Result};
+use crate::persistence::{PersistenceError, PersistencePlugin, Result};
 use std::io::{Read, Write};
 
 /// JSON persistence plugin.
@@ -76,6 +86,8 @@ pub struct JsonPlugin {
     pretty: bool,
     /// Include schema information
     include_schema: bool,
+    /// Always pretty-print, for reproducible output
+    canonical: bool,
 }
 
 impl JsonPlugin {
@@ -92,6 +104,34 @@ impl JsonPlugin {
         Self {
             pretty: true,
             include_schema: true,
+            canonical: false,
+        }
+    }
+
+    /// Creates a JSON plugin that writes canonical output.
+    ///
+    /// Every JSON save writes entities in [`StableId`](crate::entity::StableId)
+    /// order and components in registered name order, so a world always
+    /// saves to the same text regardless of spawn order or archetype layout.
+    /// Canonical output is also always pretty-printed, even after
+    /// [`with_pretty(false)`](Self::with_pretty), so each entity and
+    /// component sits on its own lines and diffs stay small. Useful for
+    /// golden-file tests and scenes kept under version control; pin the
+    /// timestamp with [`World::set_fixed_timestamp`] for fully reproducible
+    /// files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::JsonPlugin;
+    ///
+    /// let plugin = JsonPlugin::canonical();
+    /// assert!(plugin.is_canonical());
+    /// ```
+    pub fn canonical() -> Self {
+        Self {
+            canonical: true,
+            ..Self::new()
         }
     }
 
@@ -108,6 +148,7 @@ impl JsonPlugin {
         Self {
             pretty: false,
             include_schema: true,
+            canonical: false,
         }
     }
 
@@ -148,6 +189,73 @@ impl JsonPlugin {
     pub fn includes_schema(&self) -> bool {
         self.include_schema
     }
+
+    /// Returns whether output is canonical.
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Returns a JSON Schema describing world documents.
+    ///
+    /// The schema covers the document envelope and the components
+    /// registered with
+    /// [`register_json`](crate::component::registry::ComponentRegistry::register_json);
+    /// unregistered components are rejected. Component values are described
+    /// from the field layouts `#[derive(Component)]` records, and accept any
+    /// value when no layout was recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::JsonPlugin;
+    ///
+    /// let schema = JsonPlugin::json_schema();
+    /// assert_eq!(schema["type"], "object");
+    /// ```
+    pub fn json_schema() -> serde_json::Value {
+        schema::world_schema(&schema::registered_types())
+    }
+
+    /// Checks a JSON world document without loading it.
+    ///
+    /// The document is checked against [`json_schema`](Self::json_schema),
+    /// and its entity count and stable IDs are verified, so a document that
+    /// passes can be loaded with the current component registrations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::InvalidFormat`] naming the JSON pointer of
+    /// the first offending value, [`PersistenceError::VersionMismatch`] for
+    /// an unsupported format version, or
+    /// [`PersistenceError::EntityIdConflict`] for a duplicate stable ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::JsonPlugin;
+    ///
+    /// let plugin = JsonPlugin::new();
+    /// let document = r#"{
+    ///     "$format": "pecs.world",
+    ///     "$version": 2,
+    ///     "entity_count": 0,
+    ///     "entities": []
+    /// }"#;
+    /// assert!(plugin.validate(&mut document.as_bytes()).is_ok());
+    ///
+    /// let document = r#"{
+    ///     "$format": "pecs.world",
+    ///     "$version": 2,
+    ///     "entity_count": 1,
+    ///     "entities": []
+    /// }"#;
+    /// assert!(plugin.validate(&mut document.as_bytes()).is_err());
+    /// ```
+    pub fn validate(&self, reader: &mut dyn Read) -> Result<()> {
+        let document: serde_json::Value = serde_json::from_reader(reader)
+            .map_err(|e| PersistenceError::from_source("Invalid JSON world", e))?;
+        schema::validate_document(document)
+    }
 }
 
 impl Default for JsonPlugin {
@@ -158,7 +266,12 @@ impl Default for JsonPlugin {
 
 impl PersistencePlugin for JsonPlugin {
     fn save(&self, world: &World, writer: &mut dyn Write) -> Result<()> {
-        serialize::serialize(world, writer, self.pretty, self.include_schema)
+        serialize::serialize(
+            world,
+            writer,
+            self.pretty || self.canonical,
+            self.include_schema,
+        )
     }
 
     fn load(&self, reader: &mut dyn Read) -> Result<World> {
@@ -170,12 +283,11 @@ impl PersistencePlugin for JsonPlugin {
     }
 
     fn format_version(&self) -> u32 {
-        1
+        VERSION
     }
 
     fn can_load_version(&self, version: u32) -> bool {
-        // Support version 1 only for now
-        version == 1
+        (1..=VERSION).contains(&version)
     }
 }
 
@@ -187,7 +299,7 @@ mod tests {
     fn test_json_plugin_creation() {
         let plugin = JsonPlugin::new();
         assert_eq!(plugin.format_name(), "json");
-        assert_eq!(plugin.format_version(), 1);
+        assert_eq!(plugin.format_version(), 2);
         assert!(plugin.is_pretty());
         assert!(plugin.includes_schema());
     }
@@ -218,11 +330,82 @@ mod tests {
         assert!(plugin.is_pretty());
     }
 
+    #[test]
+    fn test_json_schema_round_trip() {
+        use crate::component::Component;
+        use crate::component::registry::{ComponentRegistry, ComponentSchema, FieldLayout};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Health {
+            current: u32,
+        }
+        impl Component for Health {}
+
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Health>("json::Health");
+        registry.register_schema::<Health>(
+            ComponentSchema::new("json::Health", 2)
+                .with_fields(vec![FieldLayout::new("current", "u32", 0, 4)]),
+        );
+
+        let mut world = World::new();
+        let entity = world.spawn().with(Health { current: 7 }).id();
+        let plugin = JsonPlugin::new();
+        let mut buffer = Vec::new();
+        plugin.save(&world, &mut buffer).unwrap();
+
+        let document: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        let types = document["types"].as_array().unwrap();
+        assert_eq!(types.len(), 1);
+        assert_eq!(types[0]["name"], "json::Health");
+        assert_eq!(types[0]["version"], 2);
+        assert_eq!(
+            document["entities"][0]["components"]["json::Health"]["version"],
+            2
+        );
+        assert_eq!(
+            types[0]["schema"]["properties"]["current"]["type"],
+            "integer"
+        );
+        assert_eq!(
+            JsonPlugin::json_schema()["properties"]["entities"]["items"]["properties"]["components"]
+                ["properties"]["json::Health"]["properties"]["value"],
+            types[0]["schema"]
+        );
+
+        plugin.validate(&mut buffer.as_slice()).unwrap();
+        let loaded = plugin.load(&mut buffer.as_slice()).unwrap();
+        let stable_id = world.get_stable_id(entity).unwrap();
+        let restored = loaded.get_entity_by_stable_id(stable_id).unwrap();
+        assert_eq!(loaded.get::<Health>(restored), Some(&Health { current: 7 }));
+
+        let mut tampered = document.clone();
+        tampered["entities"][0]["components"]["json::Health"]["value"]["current"] = "full".into();
+        let err = plugin
+            .validate(&mut tampered.to_string().as_bytes())
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("/entities/0/components/json::Health/value/current"),
+            "{}",
+            err
+        );
+
+        let mut unknown = document;
+        unknown["entities"][0]["components"]["json::Mana"] = 3.into();
+        assert!(matches!(
+            plugin.validate(&mut unknown.to_string().as_bytes()),
+            Err(PersistenceError::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn test_json_plugin_version_compatibility() {
         let plugin = JsonPlugin::new();
         assert!(plugin.can_load_version(1));
-        assert!(!plugin.can_load_version(2));
+        assert!(plugin.can_load_version(2));
+        assert!(!plugin.can_load_version(3));
         assert!(!plugin.can_load_version(0));
     }
 }
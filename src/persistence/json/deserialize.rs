@@ -16,16 +16,23 @@
 
 //! JSON deserialization implementation.
 
+use super::{FORMAT, VERSION};
 use crate::World;
+use crate::component::registry::ComponentRegistry;
 use crate::entity::StableId;
+use crate::persistence::metadata::ExtendedMetadata;
 use crate::persistence::{PersistenceError, Result};
 use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
 use std::io::Read;
 
 /// JSON format for world deserialization.
 #[derive(Debug, Deserialize)]
 struct JsonWorld {
     /// Format version
+    #[serde(rename = "$version")]
+    #[allow(dead_code)]
     version: u32,
     /// Timestamp when saved
     #[serde(default)]
@@ -33,6 +40,9 @@ struct JsonWorld {
     timestamp: String,
     /// Number of entities
     entity_count: usize,
+    /// Custom metadata and typed extensions (optional)
+    #[serde(default)]
+    metadata: ExtendedMetadata,
     /// Component type information (optional)
     #[serde(default)]
     #[allow(dead_code)]
@@ -49,6 +59,9 @@ struct TypeInfo {
     name: String,
     /// Type version
     version: u32,
+    /// JSON Schema of the component's value
+    #[serde(default)]
+    schema: Option<serde_json::Value>,
 }
 
 /// Entity data in JSON format.
@@ -56,10 +69,19 @@ struct TypeInfo {
 struct EntityData {
     /// Stable ID as string (UUID format)
     id: String,
-    /// Component data (placeholder - will be empty for now)
+    /// Components keyed by registered JSON name
     #[serde(default)]
+    components: BTreeMap<String, ComponentEntry>,
+}
+
+/// A component value with the schema version it was written at.
+#[derive(Debug, Deserialize)]
+struct ComponentEntry {
+    /// Schema version of the component type
     #[allow(dead_code)]
-    components: serde_json::Map<String, serde_json::Value>,
+    version: u32,
+    /// Component value
+    value: Value,
 }
 
 /// Deserialize a world from JSON format.
@@ -78,17 +100,12 @@ pub(super) fn deserialize(reader: &mut dyn Read) -> Result<World> {
         .read_to_string(&mut json_data)
         .map_err(PersistenceError::Io)?;
 
-    // Parse JSON
-    let json_world: JsonWorld = serde_json::from_str(&json_data)
-        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
-
-    // Validate version
-    if json_world.version != 1 {
-        return Err(PersistenceError::VersionMismatch {
-            found: json_world.version,
-            expected: 1,
-        });
-    }
+    // Parse JSON, bringing older documents up to the current version
+    let mut document: Value = serde_json::from_str(&json_data)
+        .map_err(|e| PersistenceError::from_source("Invalid JSON world", e))?;
+    upgrade(&mut document)?;
+    let json_world: JsonWorld = serde_json::from_value(document)
+        .map_err(|e| PersistenceError::from_source("Invalid JSON world", e))?;
 
     // Validate entity count
     if json_world.entities.len() != json_world.entity_count {
@@ -101,49 +118,116 @@ pub(super) fn deserialize(reader: &mut dyn Read) -> Result<World> {
 
     // Create new world
     let mut world = World::new();
+    world.metadata_mut().apply_extended(json_world.metadata);
+    world.restore_resources()?;
+    world.metadata_mut().entity_count = json_world.entity_count;
 
     // Restore entities
-    for entity_data in json_world.entities {
+    let registry = ComponentRegistry::global();
+    for (index, entity_data) in json_world.entities.into_iter().enumerate() {
         // Parse stable ID
-        let stable_id = parse_stable_id(&entity_data.id)?;
+        let stable_id = parse_stable_id(&entity_data.id).map_err(|e| e.at_entity(index as u64))?;
 
         // Spawn entity with stable ID
-        let _entity = world.entities_mut().spawn_with_id(stable_id).map_err(|e| {
-            PersistenceError::Deserialization(format!("Failed to allocate entity: {:?}", e))
+        let entity = world.entities_mut().spawn_with_id(stable_id).map_err(|e| {
+            PersistenceError::from_source("Failed to allocate entity", e).at_entity(index as u64)
         })?;
 
-        // TODO: Restore components when component serialization is implemented
-        // For now, we just create empty entities
+        for (name, component) in entity_data.components {
+            registry
+                .insert_json(&name, &mut world, entity, component.value)
+                .map_err(|e| e.at_entity(index as u64).in_component(&name))?;
+        }
+
+        // Fill in requirements added since the save was written
+        world.insert_missing_requirements(entity);
     }
 
     Ok(world)
 }
 
-/// Parse a stable ID from string format.
+/// Checks the envelope of a parsed world document and upgrades it to the
+/// current format version in place.
 ///
-/// The string should be in UUID format (e.g., "550e8400-e29b-41d4-a716-446655440000").
-fn parse_stable_id(id_str: &str) -> Result<StableId> {
-    // Remove hyphens and parse as hex
-    let hex_str = id_str.replace('-', "");
-
-    if hex_str.len() != 32 {
-        return Err(PersistenceError::Deserialization(format!(
-            "Invalid stable ID format: {}",
-            id_str
+/// Version 1 documents had a plain `version` field, no `$format` and bare
+/// component values; their components take the version listed in `types`,
+/// or 1.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::InvalidFormat`] if the document is not a
+/// world document, or [`PersistenceError::VersionMismatch`] if it was
+/// written by a newer version.
+pub(super) fn upgrade(document: &mut Value) -> Result<()> {
+    let object = document.as_object_mut().ok_or_else(|| {
+        PersistenceError::InvalidFormat("World document must be an object".to_string())
+    })?;
+    if let Some(format) = object.get("$format")
+        && format != FORMAT
+    {
+        return Err(PersistenceError::InvalidFormat(format!(
+            "Not a world document: $format is {}",
+            format
         )));
     }
 
-    // Parse high and low parts
-    let high_str = &hex_str[0..16];
-    let low_str = &hex_str[16..32];
-
-    let high = u64::from_str_radix(high_str, 16)
-        .map_err(|e| PersistenceError::Deserialization(format!("Invalid stable ID high: {}", e)))?;
-
-    let low = u64::from_str_radix(low_str, 16)
-        .map_err(|e| PersistenceError::Deserialization(format!("Invalid stable ID low: {}", e)))?;
+    let version: u64 = object
+        .get("$version")
+        .or_else(|| object.get("version"))
+        .ok_or_else(|| PersistenceError::InvalidFormat("Missing $version".to_string()))?
+        .as_u64()
+        .ok_or_else(|| {
+            PersistenceError::InvalidFormat("$version must be an integer".to_string())
+        })?;
+    match u32::try_from(version).unwrap_or(u32::MAX) {
+        1 => {
+            let versions: BTreeMap<String, Value> = object
+                .get("types")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|ty| {
+                    Some((
+                        ty.get("name")?.as_str()?.to_string(),
+                        ty.get("version")?.clone(),
+                    ))
+                })
+                .collect();
+            for entity in object
+                .get_mut("entities")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten()
+            {
+                let Some(components) = entity.get_mut("components").and_then(Value::as_object_mut)
+                else {
+                    continue;
+                };
+                for (name, value) in components.iter_mut() {
+                    let version = versions.get(name).cloned().unwrap_or_else(|| json!(1));
+                    *value = json!({ "version": version, "value": value.take() });
+                }
+            }
+            object.remove("version");
+            object.insert("$format".to_string(), json!(FORMAT));
+            object.insert("$version".to_string(), json!(VERSION));
+            Ok(())
+        }
+        VERSION => Ok(()),
+        found => Err(PersistenceError::VersionMismatch {
+            found,
+            expected: VERSION,
+        }),
+    }
+}
 
-    Ok(StableId::from_u128(((high as u128) << 64) | (low as u128)))
+/// Parse a stable ID from string format.
+///
+/// The string should be in UUID format (e.g., "550e8400-e29b-41d4-a716-446655440000").
+fn parse_stable_id(id_str: &str) -> Result<StableId> {
+    id_str.parse().map_err(|_| {
+        PersistenceError::Deserialization(format!("Invalid stable ID format: {}", id_str))
+    })
 }
 
 #[cfg(test)]
@@ -190,6 +274,50 @@ mod tests {
         assert_eq!(world.len(), 2);
     }
 
+    #[test]
+    fn test_deserialize_fills_in_new_requirements() {
+        use crate::component::registry::ComponentRegistry;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Sprite(u32);
+        impl crate::Component for Sprite {}
+
+        #[derive(Debug, PartialEq)]
+        struct Anchor(i32);
+        impl crate::Component for Anchor {}
+
+        // The save predates `Sprite` requiring `Anchor`
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Sprite>("json::Sprite");
+        registry.register_default(|| Anchor(-1));
+        registry.register_requires::<Sprite, Anchor>();
+        let json = r#"{
+            "version": 1,
+            "entity_count": 2,
+            "entities": [
+                {
+                    "id": "550e8400-e29b-41d4-a716-446655440000",
+                    "components": { "json::Sprite": 3 }
+                },
+                {
+                    "id": "550e8400-e29b-41d4-a716-446655440001",
+                    "components": {}
+                }
+            ]
+        }"#;
+
+        let world = deserialize(&mut json.as_bytes()).unwrap();
+        let sprite = world
+            .get_entity_id(parse_stable_id("550e8400-e29b-41d4-a716-446655440000").unwrap())
+            .unwrap();
+        let other = world
+            .get_entity_id(parse_stable_id("550e8400-e29b-41d4-a716-446655440001").unwrap())
+            .unwrap();
+        assert_eq!(world.get::<Sprite>(sprite), Some(&Sprite(3)));
+        assert_eq!(world.get::<Anchor>(sprite), Some(&Anchor(-1)));
+        assert!(!world.has::<Anchor>(other));
+    }
+
     #[test]
     fn test_deserialize_invalid_version() {
         let json = r#"{
@@ -206,7 +334,7 @@ mod tests {
         match result {
             Err(PersistenceError::VersionMismatch { found, expected }) => {
                 assert_eq!(found, 999);
-                assert_eq!(expected, 1);
+                assert_eq!(expected, VERSION);
             }
             _ => panic!("Expected VersionMismatch error"),
         }
@@ -287,4 +415,57 @@ mod tests {
 
         assert_eq!(world.len(), 0);
     }
+
+    #[test]
+    fn test_upgrade_version_1() {
+        let mut document = json!({
+            "version": 1,
+            "entity_count": 1,
+            "types": [{ "name": "Health", "version": 3 }],
+            "entities": [{
+                "id": "550e8400-e29b-41d4-a716-446655440000",
+                "components": { "Health": { "current": 7 }, "Tag": null }
+            }]
+        });
+        upgrade(&mut document).unwrap();
+
+        assert_eq!(document["$format"], FORMAT);
+        assert_eq!(document["$version"], VERSION);
+        assert!(document.get("version").is_none());
+        let components = &document["entities"][0]["components"];
+        assert_eq!(
+            components["Health"],
+            json!({ "version": 3, "value": { "current": 7 } })
+        );
+        assert_eq!(components["Tag"], json!({ "version": 1, "value": null }));
+    }
+
+    #[test]
+    fn test_upgrade_rejects_other_formats() {
+        let mut document = json!({ "$format": "pecs.scene", "$version": 2 });
+        assert!(matches!(
+            upgrade(&mut document),
+            Err(PersistenceError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_with_metadata_extensions() {
+        let json = r#"{
+            "version": 1,
+            "timestamp": "2026-02-13T00:00:00Z",
+            "entity_count": 0,
+            "metadata": {
+                "custom": { "map": "dust" },
+                "extensions": { "game_rules": { "max_players": 8 } }
+            },
+            "entities": []
+        }"#;
+
+        let mut cursor = Cursor::new(json.as_bytes());
+        let world = deserialize(&mut cursor).unwrap();
+
+        assert_eq!(world.metadata().custom.get("map").unwrap(), "dust");
+        assert_eq!(world.metadata().extensions["game_rules"]["max_players"], 8);
+    }
 }
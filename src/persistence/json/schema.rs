@@ -0,0 +1,364 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! JSON Schema emission and document validation.
+//!
+//! The schema is derived from the components registered with
+//! [`ComponentRegistry::register_json`]. Component value schemas come from
+//! the field layouts recorded by `#[derive(Component)]`, so they assume the
+//! serde representation follows the Rust fields; components without recorded
+//! fields accept any value.
+
+use super::{FORMAT, VERSION, deserialize};
+use crate::component::registry::{ComponentRegistry, FieldLayout};
+use crate::entity::StableId;
+use crate::persistence::{PersistenceError, Result};
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+
+/// URI of the JSON Schema dialect the emitted schemas use.
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A JSON-registered component type, as listed in a document's `types`.
+#[derive(Debug, Clone)]
+pub(crate) struct RegisteredType {
+    /// Registered JSON name
+    pub name: String,
+    /// Schema version, 1 if the type has no registered schema
+    pub version: u32,
+    /// JSON Schema of the component's value
+    pub schema: Value,
+}
+
+/// Returns the JSON-registered component types, sorted by name.
+///
+/// Transient components and aliases (every name but the first registered
+/// for a type) are left out.
+pub(crate) fn registered_types() -> Vec<RegisteredType> {
+    let registry = ComponentRegistry::global();
+    registry
+        .json_names()
+        .into_iter()
+        .filter_map(|name| {
+            let info = registry.info_by_name(&name)?;
+            if registry.json_name_of(info.type_id()).as_deref() != Some(name.as_str()) {
+                return None;
+            }
+            let (version, schema) = match registry.schema_of(info.type_id().type_id()) {
+                Some(schema) if schema.transient => return None,
+                Some(schema) => (schema.version, fields_schema(&schema.fields)),
+                None => (1, json!({})),
+            };
+            Some(RegisteredType {
+                name,
+                version,
+                schema,
+            })
+        })
+        .collect()
+}
+
+/// Builds the JSON Schema of a world document holding `types`.
+pub(super) fn world_schema(types: &[RegisteredType]) -> Value {
+    let components: Map<String, Value> = types
+        .iter()
+        .map(|ty| {
+            let entry = json!({
+                "type": "object",
+                "required": ["version", "value"],
+                "properties": {
+                    "version": { "type": "integer", "minimum": 0 },
+                    "value": ty.schema
+                }
+            });
+            (ty.name.clone(), entry)
+        })
+        .collect();
+
+    json!({
+        "$schema": DIALECT,
+        "title": "pecs world",
+        "type": "object",
+        "required": ["$format", "$version", "entity_count", "entities"],
+        "properties": {
+            "$format": { "const": FORMAT },
+            "$version": { "const": VERSION },
+            "timestamp": { "type": "string" },
+            "entity_count": { "type": "integer", "minimum": 0 },
+            "metadata": { "type": "object" },
+            "types": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "version"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "version": { "type": "integer", "minimum": 0 },
+                        "schema": { "type": "object" }
+                    }
+                }
+            },
+            "entities": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "format": "uuid" },
+                        "components": {
+                            "type": "object",
+                            "properties": components,
+                            "additionalProperties": false
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Builds the value schema of a component from its field layouts.
+fn fields_schema(fields: &[FieldLayout]) -> Value {
+    if fields.is_empty() {
+        return json!({});
+    }
+
+    // Tuple structs record their field indices as names
+    let tuple = fields
+        .iter()
+        .all(|field| field.name.parse::<usize>().is_ok());
+    match fields {
+        [field] if tuple => type_schema(&field.type_name),
+        _ if tuple => json!({
+            "type": "array",
+            "prefixItems": fields.iter().map(|field| type_schema(&field.type_name)).collect::<Vec<_>>(),
+            "minItems": fields.len(),
+            "maxItems": fields.len()
+        }),
+        _ => json!({
+            "type": "object",
+            "required": fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>(),
+            "properties": fields
+                .iter()
+                .map(|field| (field.name.clone(), type_schema(&field.type_name)))
+                .collect::<Map<_, _>>()
+        }),
+    }
+}
+
+/// Maps a Rust type name to the schema of its default serde representation.
+fn type_schema(type_name: &str) -> Value {
+    match type_name {
+        "bool" => json!({ "type": "boolean" }),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => {
+            json!({ "type": "integer", "minimum": 0 })
+        }
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => json!({ "type": "integer" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "char" | "&str" | "alloc::string::String" => json!({ "type": "string" }),
+        _ if type_name.starts_with("alloc::vec::Vec<") => json!({ "type": "array" }),
+        _ => json!({}),
+    }
+}
+
+/// Checks a parsed world document before it is loaded.
+///
+/// The document is upgraded to the current format version and checked
+/// against [`world_schema`] of the registered types, then for the
+/// invariants a schema can't express: the entity count and unique,
+/// well-formed stable IDs.
+pub(super) fn validate_document(mut document: Value) -> Result<()> {
+    deserialize::upgrade(&mut document)?;
+    let schema = world_schema(&registered_types());
+    check(&document, &schema, "")?;
+
+    let entities = document["entities"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice);
+    if document["entity_count"].as_u64() != Some(entities.len() as u64) {
+        return Err(invalid(
+            "/entity_count",
+            format!("expected {} entities", entities.len()),
+        ));
+    }
+
+    let mut seen = HashSet::with_capacity(entities.len());
+    for (index, entity) in entities.iter().enumerate() {
+        let id = entity["id"].as_str().unwrap_or_default();
+        let stable_id: StableId = id.parse().map_err(|_| {
+            invalid(
+                &format!("/entities/{}/id", index),
+                format!("invalid stable ID {:?}", id),
+            )
+        })?;
+        if !seen.insert(stable_id) {
+            return Err(PersistenceError::EntityIdConflict(format!(
+                "/entities/{}/id: duplicate stable ID {}",
+                index, stable_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks `value` against the subset of JSON Schema [`world_schema`] emits.
+fn check(value: &Value, schema: &Value, path: &str) -> Result<()> {
+    if let Some(expected) = schema.get("const")
+        && value != expected
+    {
+        return Err(invalid(path, format!("expected {}", expected)));
+    }
+
+    if let Some(ty) = schema.get("type").and_then(Value::as_str) {
+        let matches = match ty {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "boolean" => value.is_boolean(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            _ => true,
+        };
+        if !matches {
+            return Err(invalid(path, format!("expected {}", ty)));
+        }
+    }
+
+    if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+        && value.as_f64().is_some_and(|number| number < minimum)
+    {
+        return Err(invalid(path, format!("must be at least {}", minimum)));
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(required) {
+                return Err(invalid(path, format!("missing field {:?}", required)));
+            }
+        }
+        for (key, field) in object {
+            let field_path = format!("{}/{}", path, escape(key));
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(field_schema) => check(field, field_schema, &field_path)?,
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(invalid(&field_path, "unknown component".to_string()));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let Some(array) = value.as_array() {
+        let length = array.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+            && length < min
+        {
+            return Err(invalid(path, format!("expected at least {} items", min)));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+            && length > max
+        {
+            return Err(invalid(path, format!("expected at most {} items", max)));
+        }
+        let prefix = schema
+            .get("prefixItems")
+            .and_then(Value::as_array)
+            .map_or(&[][..], Vec::as_slice);
+        for (index, item) in array.iter().enumerate() {
+            let item_schema = prefix.get(index).or_else(|| schema.get("items"));
+            if let Some(item_schema) = item_schema {
+                check(item, item_schema, &format!("{}/{}", path, index))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a validation error for the value at JSON pointer `path`.
+fn invalid(path: &str, message: String) -> PersistenceError {
+    let path = if path.is_empty() { "/" } else { path };
+    PersistenceError::InvalidFormat(format!("{}: {}", path, message))
+}
+
+/// Escapes an object key for use in a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_schema() {
+        let named = [
+            FieldLayout::new("x", "f32", 0, 4),
+            FieldLayout::new("name", "alloc::string::String", 8, 24),
+        ];
+        let schema = fields_schema(&named);
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["x"]["type"], "number");
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+
+        let newtype = [FieldLayout::new("0", "u32", 0, 4)];
+        assert_eq!(
+            fields_schema(&newtype),
+            json!({ "type": "integer", "minimum": 0 })
+        );
+        assert_eq!(fields_schema(&[]), json!({}));
+    }
+
+    #[test]
+    fn test_check_reports_pointer() {
+        let schema = fields_schema(&[FieldLayout::new("hp", "u32", 0, 4)]);
+        check(&json!({ "hp": 3 }), &schema, "").unwrap();
+
+        let err = check(&json!({ "hp": -1 }), &schema, "/c").unwrap_err();
+        assert!(err.to_string().contains("/c/hp"), "{}", err);
+        let err = check(&json!({}), &schema, "/c").unwrap_err();
+        assert!(err.to_string().contains("missing field \"hp\""), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_document_checks_ids() {
+        let id = StableId::from_raw(1).to_string();
+        let document = json!({
+            "$format": FORMAT,
+            "$version": VERSION,
+            "entity_count": 2,
+            "entities": [{ "id": id }, { "id": id }]
+        });
+        assert!(matches!(
+            validate_document(document),
+            Err(PersistenceError::EntityIdConflict(_))
+        ));
+
+        let document = json!({ "$version": 9, "entity_count": 0, "entities": [] });
+        assert!(matches!(
+            validate_document(document),
+            Err(PersistenceError::VersionMismatch { found: 9, .. })
+        ));
+    }
+}
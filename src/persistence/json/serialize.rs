@@ -16,20 +16,31 @@
 
 //! JSON serialization implementation.
 
+use super::{FORMAT, VERSION, schema};
 use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::persistence::metadata::ExtendedMetadata;
 use crate::persistence::{PersistenceError, Result};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::io::Write;
 
 /// JSON format for world serialization.
 #[derive(Debug, Serialize)]
 struct JsonWorld {
+    /// Format identifier
+    #[serde(rename = "$format")]
+    format: &'static str,
     /// Format version
+    #[serde(rename = "$version")]
     version: u32,
     /// Timestamp when saved
     timestamp: String,
     /// Number of entities
     entity_count: usize,
+    /// Custom metadata and typed extensions
+    #[serde(skip_serializing_if = "ExtendedMetadata::is_empty")]
+    metadata: ExtendedMetadata,
     /// Component type information (if schema is included)
     #[serde(skip_serializing_if = "Option::is_none")]
     types: Option<Vec<TypeInfo>>,
@@ -44,6 +55,8 @@ struct TypeInfo {
     name: String,
     /// Type version
     version: u32,
+    /// JSON Schema of the component's value
+    schema: serde_json::Value,
 }
 
 /// Entity data in JSON format.
@@ -51,8 +64,17 @@ struct TypeInfo {
 struct EntityData {
     /// Stable ID as string (UUID format)
     id: String,
-    /// Component data (placeholder - will be empty for now)
-    components: serde_json::Map<String, serde_json::Value>,
+    /// Components keyed by registered JSON name
+    components: BTreeMap<String, ComponentEntry>,
+}
+
+/// A component value with the schema version it was written at.
+#[derive(Debug, Serialize)]
+struct ComponentEntry {
+    /// Schema version of the component type
+    version: u32,
+    /// Component value
+    value: serde_json::Value,
 }
 
 /// Serialize a world to JSON format.
@@ -73,35 +95,55 @@ pub(super) fn serialize(
     pretty: bool,
     include_schema: bool,
 ) -> Result<()> {
-    // Get current timestamp
-    let timestamp = chrono::Utc::now().to_rfc3339();
-
-    // Collect entity data
+    // Use the metadata timestamp so saves of the same world are identical
+    let timestamp = chrono::DateTime::from_timestamp(world.metadata().timestamp as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339();
+
+    // Collect entity data. Entities are sorted by ID and components by
+    // name so a saved file only changes where the world did
+    let registry = ComponentRegistry::global();
+    let registered = schema::registered_types();
+    let mut used = vec![false; registered.len()];
     let mut entities = Vec::new();
-    for (_entity, stable_id) in world.iter_entities() {
+    for (entity, stable_id) in world.iter_entities() {
         let id = format!("{}", stable_id);
 
-        // For now, we don't have component data serialization
-        // This will be a placeholder until we implement component serialization
-        let components = serde_json::Map::new();
+        // Components without a JSON registration can't be written
+        let mut components = BTreeMap::new();
+        for (index, ty) in registered.iter().enumerate() {
+            if let Some(value) = registry.get_json(&ty.name, world, entity) {
+                let version = ty.version;
+                components.insert(ty.name.clone(), ComponentEntry { version, value });
+                used[index] = true;
+            }
+        }
 
         entities.push(EntityData { id, components });
     }
-
-    // Build type information if requested
-    let types = if include_schema {
-        // For now, return empty type list
-        // This will be populated when we implement component type tracking
-        Some(Vec::new())
-    } else {
-        None
-    };
+    entities.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+    // Describe the component types the entities use
+    let types = include_schema.then(|| {
+        registered
+            .into_iter()
+            .zip(used)
+            .filter(|(_, used)| *used)
+            .map(|(ty, _)| TypeInfo {
+                name: ty.name,
+                version: ty.version,
+                schema: ty.schema,
+            })
+            .collect()
+    });
 
     // Create JSON world structure
     let json_world = JsonWorld {
-        version: 1,
+        format: FORMAT,
+        version: VERSION,
         timestamp,
         entity_count: entities.len(),
+        metadata: world.metadata().extended(),
         types,
         entities,
     };
@@ -136,7 +178,8 @@ mod tests {
         let json_str = String::from_utf8(buffer).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
-        assert_eq!(parsed["version"], 1);
+        assert_eq!(parsed["$format"], FORMAT);
+        assert_eq!(parsed["$version"], VERSION);
         assert_eq!(parsed["entity_count"], 0);
         assert!(parsed["entities"].is_array());
     }
@@ -197,6 +240,33 @@ mod tests {
         assert!(parsed.get("types").is_none());
     }
 
+    #[test]
+    fn test_serialize_sorts_entities() {
+        use crate::entity::StableId;
+
+        let mut world = World::new();
+        for id in [3, 1, 2] {
+            world
+                .spawn_empty_with_stable_id(StableId::from_raw(id))
+                .unwrap();
+        }
+
+        let mut buffer = Vec::new();
+        serialize(&world, &mut buffer, false, false).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+
+        let ids: Vec<_> = parsed["entities"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entity| entity["id"].as_str().unwrap().to_string())
+            .collect();
+        let expected: Vec<_> = [1, 2, 3]
+            .map(|id| StableId::from_raw(id).to_string())
+            .to_vec();
+        assert_eq!(ids, expected);
+    }
+
     #[test]
     fn test_serialize_entity_ids() {
         let mut world = World::new();
@@ -16,15 +16,22 @@
 
 //! Persistence manager for coordinating save/load operations.
 
-use std::collections::HashMap;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::fs::File;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::path::Path;
 
 use crate::World;
+use crate::component::registry::ComponentRegistry;
 use crate::entity::{EntityId, StableId};
+use crate::persistence::delta::{ComponentChange, DeltaEncoder};
+#[cfg(feature = "default-plugins")]
+use crate::persistence::{BinaryPlugin, JsonPlugin, TomlPlugin};
 use crate::persistence::{
-    ChangeTracker, DeltaPersistencePlugin, EntityChange, EntityPersistencePlugin, Migration,
-    PersistenceError, PersistencePlugin, Result,
+    ChangeTracker, ComponentData, DeltaPersistencePlugin, EntityChange, EntityPersistencePlugin,
+    LoadOptions, Migration, PersistenceError, PersistencePlugin, Result, SaveInfo,
 };
 
 /// Manages persistence operations and plugin lifecycle.
@@ -54,14 +61,14 @@ use crate::persistence::{
 /// let loaded = manager.load_with("world.pecs", "custom")?;
 /// ```
 pub struct PersistenceManager {
-    /// Registered persistence plugins by name
-    plugins: HashMap<String, Box<dyn PersistencePlugin>>,
+    /// Registered persistence plugins by name, sorted so listings are stable
+    plugins: BTreeMap<String, Box<dyn PersistencePlugin>>,
 
     /// Registered delta persistence plugins by name
-    delta_plugins: HashMap<String, Box<dyn DeltaPersistencePlugin>>,
+    delta_plugins: BTreeMap<String, Box<dyn DeltaPersistencePlugin>>,
 
     /// Registered entity persistence plugins by name
-    entity_plugins: HashMap<String, Box<dyn EntityPersistencePlugin>>,
+    entity_plugins: BTreeMap<String, Box<dyn EntityPersistencePlugin>>,
 
     /// Registered migrations by version range
     migrations: Vec<Box<dyn Migration>>,
@@ -72,8 +79,24 @@ pub struct PersistenceManager {
     /// Default entity plugin name
     default_entity_plugin: Option<String>,
 
+    /// Plugin names by lowercase file extension, used by path-based save/load
+    extensions: BTreeMap<String, String>,
+
     /// Change tracker for delta persistence
     change_tracker: ChangeTracker,
+
+    /// Component values written by the last delta save
+    delta_encoder: DeltaEncoder,
+
+    /// Payload size from which compressed components are compressed
+    #[cfg(feature = "compression")]
+    compression_threshold: usize,
+}
+
+/// Keeps a manager's save settings in effect on the current thread.
+struct SaveScope {
+    #[cfg(feature = "compression")]
+    _threshold: crate::persistence::compression::ThresholdScope,
 }
 
 impl PersistenceManager {
@@ -88,14 +111,59 @@ impl PersistenceManager {
     /// ```
     pub fn new() -> Self {
         Self {
-            plugins: HashMap::new(),
-            delta_plugins: HashMap::new(),
-            entity_plugins: HashMap::new(),
+            plugins: BTreeMap::new(),
+            delta_plugins: BTreeMap::new(),
+            entity_plugins: BTreeMap::new(),
             migrations: Vec::new(),
             default_plugin: None,
             default_entity_plugin: None,
+            extensions: BTreeMap::new(),
             change_tracker: ChangeTracker::new(),
+            delta_encoder: DeltaEncoder::new(),
+            #[cfg(feature = "compression")]
+            compression_threshold: crate::persistence::compression::DEFAULT_COMPRESSION_THRESHOLD,
+        }
+    }
+
+    /// Creates a persistence manager with the built-in formats registered.
+    ///
+    /// With the `default-plugins` feature (enabled by default) this registers
+    /// [`BinaryPlugin`] as `"binary"` for `.pecs` and `.bin` files and
+    /// [`JsonPlugin`] as `"json"` for `.json` files and [`TomlPlugin`] as
+    /// `"toml"` for `.toml` scene files, with `"binary"` as the default. Without the feature it is equivalent to [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::PersistenceManager;
+    ///
+    /// let manager = PersistenceManager::with_default_plugins();
+    /// assert_eq!(manager.default_plugin(), Some("binary"));
+    /// assert_eq!(manager.plugin_for_extension("json"), Some("json"));
+    /// ```
+    pub fn with_default_plugins() -> Self {
+        #[cfg(feature = "default-plugins")]
+        {
+            let mut manager = Self::new();
+            manager.register_plugin("binary", Box::new(BinaryPlugin::new()));
+            manager.register_plugin("json", Box::new(JsonPlugin::new()));
+            manager.register_plugin("toml", Box::new(TomlPlugin::new()));
+            manager
+                .extensions
+                .insert("pecs".to_string(), "binary".to_string());
+            manager
+                .extensions
+                .insert("bin".to_string(), "binary".to_string());
+            manager
+                .extensions
+                .insert("json".to_string(), "json".to_string());
+            manager
+                .extensions
+                .insert("toml".to_string(), "toml".to_string());
+            manager
         }
+        #[cfg(not(feature = "default-plugins"))]
+        Self::new()
     }
 
     /// Registers a persistence plugin.
@@ -179,6 +247,54 @@ impl PersistenceManager {
         self.migrations.push(migration);
     }
 
+    /// Associates a file extension with a registered plugin.
+    ///
+    /// Path-based [`save`](Self::save) and [`load`](Self::load) pick the plugin
+    /// by the file's extension (case-insensitive) and fall back to the default
+    /// plugin for unknown extensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not registered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// manager.register_plugin("yaml", Box::new(YamlPlugin));
+    /// manager.register_extension("yml", "yaml")?;
+    /// manager.save(&world, "world.yml")?;
+    /// ```
+    pub fn register_extension(
+        &mut self,
+        extension: impl Into<String>,
+        plugin_name: impl Into<String>,
+    ) -> Result<()> {
+        let plugin_name = plugin_name.into();
+        if !self.plugins.contains_key(&plugin_name) {
+            return Err(PersistenceError::PluginNotFound(plugin_name));
+        }
+        self.extensions
+            .insert(extension.into().to_ascii_lowercase(), plugin_name);
+        Ok(())
+    }
+
+    /// Returns the plugin registered for a file extension, if any.
+    pub fn plugin_for_extension(&self, extension: &str) -> Option<&str> {
+        self.extensions
+            .get(&extension.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Selects the plugin for a path: by extension first, then the default.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn plugin_for_path(&self, path: &Path) -> Result<&str> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| self.plugin_for_extension(extension))
+            .or(self.default_plugin.as_deref())
+            .ok_or_else(|| PersistenceError::PluginNotFound("default".to_string()))
+    }
+
     /// Sets the default plugin to use for save/load operations.
     ///
     /// # Arguments
@@ -215,7 +331,9 @@ impl PersistenceManager {
         Ok(())
     }
 
-    /// Saves a world to a file using the default plugin.
+    /// Saves a world to a file, choosing the plugin by file extension.
+    ///
+    /// Falls back to the default plugin if the extension is not registered.
     ///
     /// # Arguments
     ///
@@ -225,20 +343,19 @@ impl PersistenceManager {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - No default plugin is registered
+    /// - No plugin matches the extension and no default plugin is registered
     /// - File cannot be created
     /// - Serialization fails
     ///
     /// # Examples
     ///
     /// ```rust,ignore
-    /// manager.save(&world, "world.pecs")?;
+    /// let info = manager.save(&world, "world.pecs")?;
+    /// println!("saved {} entities", info.entities);
     /// ```
-    pub fn save(&self, world: &World, path: impl AsRef<Path>) -> Result<()> {
-        let plugin_name = self
-            .default_plugin
-            .as_ref()
-            .ok_or_else(|| PersistenceError::PluginNotFound("default".to_string()))?;
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save(&self, world: &World, path: impl AsRef<Path>) -> Result<SaveInfo> {
+        let plugin_name = self.plugin_for_path(path.as_ref())?;
         self.save_with(world, path, plugin_name)
     }
 
@@ -250,6 +367,11 @@ impl PersistenceManager {
     /// * `path` - Path to save to
     /// * `plugin_name` - Name of the plugin to use
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`] summarizing the save, including compression
+    /// statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -262,23 +384,28 @@ impl PersistenceManager {
     /// ```rust,ignore
     /// manager.save_with(&world, "world.json", "json")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn save_with(
         &self,
         world: &World,
         path: impl AsRef<Path>,
         plugin_name: &str,
-    ) -> Result<()> {
+    ) -> Result<SaveInfo> {
         let plugin = self
             .plugins
             .get(plugin_name)
             .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
 
-        let mut file = File::create(path.as_ref()).map_err(PersistenceError::Io)?;
+        let mut file = File::create(path.as_ref())
+            .map_err(|e| PersistenceError::Io(e).with_path(path.as_ref()))?;
 
-        plugin.save(world, &mut file)
+        self.run_save(world, || plugin.save(world, &mut file))
+            .map_err(|e| e.with_path(path.as_ref()))
     }
 
-    /// Loads a world from a file using the default plugin.
+    /// Loads a world from a file, choosing the plugin by file extension.
+    ///
+    /// Falls back to the default plugin if the extension is not registered.
     ///
     /// # Arguments
     ///
@@ -287,7 +414,7 @@ impl PersistenceManager {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - No default plugin is registered
+    /// - No plugin matches the extension and no default plugin is registered
     /// - File cannot be opened
     /// - Deserialization fails
     ///
@@ -296,11 +423,9 @@ impl PersistenceManager {
     /// ```rust,ignore
     /// let world = manager.load("world.pecs")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn load(&self, path: impl AsRef<Path>) -> Result<World> {
-        let plugin_name = self
-            .default_plugin
-            .as_ref()
-            .ok_or_else(|| PersistenceError::PluginNotFound("default".to_string()))?;
+        let plugin_name = self.plugin_for_path(path.as_ref())?;
         self.load_with(path, plugin_name)
     }
 
@@ -323,15 +448,50 @@ impl PersistenceManager {
     /// ```rust,ignore
     /// let world = manager.load_with("world.json", "json")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn load_with(&self, path: impl AsRef<Path>, plugin_name: &str) -> Result<World> {
+        self.load_file(path.as_ref(), plugin_name, &LoadOptions::default())
+    }
+
+    /// Loads a world from a file, choosing the plugin by file extension and
+    /// loading as strictly as `options` demands.
+    ///
+    /// Plugins that do not support an option ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No plugin matches the extension and no default plugin is registered
+    /// - File cannot be opened
+    /// - Deserialization fails or `options` rejects the data
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let world = manager.load_with_options("world.pecs", &LoadOptions::lenient())?;
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: &LoadOptions,
+    ) -> Result<World> {
+        let plugin_name = self.plugin_for_path(path.as_ref())?;
+        self.load_file(path.as_ref(), plugin_name, options)
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn load_file(&self, path: &Path, plugin_name: &str, options: &LoadOptions) -> Result<World> {
         let plugin = self
             .plugins
             .get(plugin_name)
             .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
 
-        let mut file = File::open(path.as_ref()).map_err(PersistenceError::Io)?;
+        let mut file = File::open(path).map_err(|e| PersistenceError::Io(e).with_path(path))?;
 
-        let mut world = plugin.load(&mut file)?;
+        let mut world = plugin
+            .load_with_options(&mut file, options)
+            .map_err(|e| e.with_path(path))?;
 
         // Apply migrations if needed
         self.apply_migrations(&mut world)?;
@@ -339,6 +499,49 @@ impl PersistenceManager {
         Ok(world)
     }
 
+    /// Converts a save file from one registered format to another.
+    ///
+    /// The source is loaded with `src_plugin` (applying any registered
+    /// migrations) and written with `dst_plugin`. The intermediate world is
+    /// dropped before returning, so callers never need to hold it. The
+    /// destination file is only created once the source has loaded
+    /// successfully.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_path` - Path of the file to convert
+    /// * `src_plugin` - Name of the plugin that reads the source
+    /// * `dst_path` - Path to write the converted file to
+    /// * `dst_plugin` - Name of the plugin that writes the destination
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Either plugin is not registered
+    /// - The source cannot be read or deserialized
+    /// - The destination cannot be created or serialized
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// manager.convert("world.pecs", "binary", "world.json", "json")?;
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn convert(
+        &self,
+        src_path: impl AsRef<Path>,
+        src_plugin: &str,
+        dst_path: impl AsRef<Path>,
+        dst_plugin: &str,
+    ) -> Result<()> {
+        if !self.plugins.contains_key(dst_plugin) {
+            return Err(PersistenceError::PluginNotFound(dst_plugin.to_string()));
+        }
+        let world = self.load_with(src_path, src_plugin)?;
+        self.save_with(&world, dst_path, dst_plugin)?;
+        Ok(())
+    }
+
     /// Saves a world to a writer using the default plugin.
     ///
     /// # Arguments
@@ -346,12 +549,21 @@ impl PersistenceManager {
     /// * `world` - The world to save
     /// * `writer` - Writer to save to
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`] summarizing the save, including compression
+    /// statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - No default plugin is registered
     /// - Serialization fails
-    pub fn save_to_writer(&self, world: &World, writer: &mut dyn std::io::Write) -> Result<()> {
+    pub fn save_to_writer(
+        &self,
+        world: &World,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<SaveInfo> {
         let plugin_name = self
             .default_plugin
             .as_ref()
@@ -367,6 +579,11 @@ impl PersistenceManager {
     /// * `writer` - Writer to save to
     /// * `plugin_name` - Name of the plugin to use
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`] summarizing the save, including compression
+    /// statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -377,13 +594,13 @@ impl PersistenceManager {
         world: &World,
         writer: &mut dyn std::io::Write,
         plugin_name: &str,
-    ) -> Result<()> {
+    ) -> Result<SaveInfo> {
         let plugin = self
             .plugins
             .get(plugin_name)
             .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
 
-        plugin.save(world, writer)
+        self.run_save(world, || plugin.save(world, writer))
     }
 
     /// Loads a world from a reader using the default plugin.
@@ -456,19 +673,48 @@ impl PersistenceManager {
     /// manager.save_delta("database")?;
     /// ```
     pub fn save_delta(&mut self, plugin_name: &str) -> Result<()> {
-        let plugin = self
+        self.save_changes(None, plugin_name)
+    }
+
+    /// Saves the changes since the last checkpoint, including component
+    /// data.
+    ///
+    /// Created and modified entities carry one [`ComponentData`] per changed
+    /// JSON-registered component, whose `data` is a serialized
+    /// [`ComponentChange`](crate::persistence::delta::ComponentChange).
+    /// Components with a [`DeltaCodec`](crate::component::registry::DeltaCodec)
+    /// are written as patches against the previous delta save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not registered, a component fails
+    /// to serialize, or saving the changes fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// manager.save_world_delta(&world, "database")?;
+    /// ```
+    pub fn save_world_delta(&mut self, world: &World, plugin_name: &str) -> Result<()> {
+        self.save_changes(Some(world), plugin_name)
+    }
+
+    fn save_changes(&mut self, world: Option<&World>, plugin_name: &str) -> Result<()> {
+        let timestamp = self
             .delta_plugins
             .get(plugin_name)
-            .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
+            .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?
+            .current_timestamp();
 
         if !self.change_tracker.has_changes() {
             return Ok(());
         }
 
         // Convert tracked changes to EntityChange format
-        let changes = self.collect_changes(plugin.as_ref())?;
+        let _scope = self.save_scope();
+        let changes = self.collect_changes(timestamp, world)?;
 
-        plugin.save_changes(&changes)?;
+        self.delta_plugins[plugin_name].save_changes(&changes)?;
         self.change_tracker.checkpoint();
 
         Ok(())
@@ -520,6 +766,74 @@ impl PersistenceManager {
         &mut self.change_tracker
     }
 
+    /// Sets the payload size, in bytes of serialized JSON, from which
+    /// [`CompressedComponent`](crate::persistence::compression::CompressedComponent)s
+    /// are compressed in saves run by this manager.
+    ///
+    /// Requires the `compression` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::PersistenceManager;
+    /// use pecs::persistence::compression::DEFAULT_COMPRESSION_THRESHOLD;
+    ///
+    /// let mut manager = PersistenceManager::new();
+    /// assert_eq!(manager.compression_threshold(), DEFAULT_COMPRESSION_THRESHOLD);
+    /// manager.set_compression_threshold(64 * 1024);
+    /// assert_eq!(manager.compression_threshold(), 64 * 1024);
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn set_compression_threshold(&mut self, bytes: usize) {
+        self.compression_threshold = bytes;
+    }
+
+    /// Returns the compression threshold of this manager's saves.
+    #[cfg(feature = "compression")]
+    pub fn compression_threshold(&self) -> usize {
+        self.compression_threshold
+    }
+
+    /// Puts this manager's save settings in effect on the current thread
+    /// until the returned scope is dropped.
+    fn save_scope(&self) -> SaveScope {
+        SaveScope {
+            #[cfg(feature = "compression")]
+            _threshold: crate::persistence::compression::ThresholdScope::new(
+                self.compression_threshold,
+            ),
+        }
+    }
+
+    /// Runs `save` with this manager's save settings and summarizes the
+    /// save of `world`.
+    pub(crate) fn run_save(
+        &self,
+        world: &World,
+        save: impl FnOnce() -> Result<()>,
+    ) -> Result<SaveInfo> {
+        let _scope = self.save_scope();
+        #[cfg(feature = "compression")]
+        {
+            let (result, compression) =
+                crate::persistence::compression::CompressionStats::capture(save);
+            result?;
+            Ok(SaveInfo {
+                entities: world.len(),
+                compression,
+                ..SaveInfo::default()
+            })
+        }
+        #[cfg(not(feature = "compression"))]
+        {
+            save()?;
+            Ok(SaveInfo {
+                entities: world.len(),
+                ..SaveInfo::default()
+            })
+        }
+    }
+
     /// Applies all necessary migrations to bring a world to the current version.
     ///
     /// Migrations are applied in order from the world's current version to the
@@ -578,44 +892,88 @@ impl PersistenceManager {
         Ok(())
     }
 
-    /// Collects changes from the change tracker and converts them to EntityChange format.
-    fn collect_changes(&self, plugin: &dyn DeltaPersistencePlugin) -> Result<Vec<EntityChange>> {
-        let timestamp = plugin.current_timestamp();
+    /// Collects changes from the change tracker and converts them to
+    /// EntityChange format, with component data if `world` is given.
+    fn collect_changes(
+        &mut self,
+        timestamp: u64,
+        world: Option<&World>,
+    ) -> Result<Vec<EntityChange>> {
         let mut changes = Vec::new();
 
         // Convert created entities
-        for &entity in self.change_tracker.created() {
+        for entity in self.change_tracker.created() {
+            let (components, _) = self.encode_components(world, entity)?;
             changes.push(EntityChange::Created {
                 entity,
-                components: Vec::new(), // TODO: Collect actual component data
+                components,
                 timestamp,
             });
         }
 
         // Convert modified entities
-        for &entity in self.change_tracker.modified() {
+        for entity in self.change_tracker.modified() {
+            let (added_or_modified, removed) = self.encode_components(world, entity)?;
             changes.push(EntityChange::Modified {
                 entity,
-                added_or_modified: Vec::new(), // TODO: Collect actual component data
-                removed: Vec::new(),
+                added_or_modified,
+                removed,
                 timestamp,
             });
         }
 
         // Convert deleted entities
-        for &entity in self.change_tracker.deleted() {
+        for entity in self.change_tracker.deleted() {
+            self.delta_encoder.forget(entity);
             changes.push(EntityChange::Deleted { entity, timestamp });
         }
 
         Ok(changes)
     }
 
-    /// Lists all registered plugin names.
+    /// Encodes an entity's component changes since the last delta save.
+    ///
+    /// Returns the changes and the types of the removed components.
+    fn encode_components(
+        &mut self,
+        world: Option<&World>,
+        entity: EntityId,
+    ) -> Result<(Vec<ComponentData>, Vec<TypeId>)> {
+        let Some(delta) = world
+            .map(|world| self.delta_encoder.encode(world, entity))
+            .transpose()?
+            .flatten()
+        else {
+            return Ok((Vec::new(), Vec::new()));
+        };
+
+        let registry = ComponentRegistry::global();
+        let mut components = Vec::with_capacity(delta.components.len());
+        let mut removed = Vec::new();
+        for component in delta.components {
+            let type_id = registry
+                .info_by_name(&component.name)
+                .map(|info| info.type_id().type_id())
+                .ok_or_else(|| PersistenceError::UnknownComponentType(component.name.clone()))?;
+            if component.change == ComponentChange::Removed {
+                removed.push(type_id);
+            }
+            components.push(ComponentData {
+                type_id,
+                data: serde_json::to_vec(&component.change)
+                    .map_err(|e| PersistenceError::Serialization(e.to_string()))?,
+                type_name: component.name,
+            });
+        }
+        Ok((components, removed))
+    }
+
+    /// Lists all registered plugin names, sorted.
     pub fn list_plugins(&self) -> Vec<&str> {
         self.plugins.keys().map(|s| s.as_str()).collect()
     }
 
-    /// Lists all registered delta plugin names.
+    /// Lists all registered delta plugin names, sorted.
     pub fn list_delta_plugins(&self) -> Vec<&str> {
         self.delta_plugins.keys().map(|s| s.as_str()).collect()
     }
@@ -683,9 +1041,62 @@ impl PersistenceManager {
             .get(plugin_name)
             .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
 
+        let _scope = self.save_scope();
         plugin.save_entity(world, entity)
     }
 
+    /// Saves several entities in one batch using a named entity plugin.
+    ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`] summarizing the save, including compression
+    /// statistics with the `compression` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not registered or saving fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let info = manager.save_entities_with(&world, &[a, b], "redis")?;
+    /// println!("saved {} entities", info.entities);
+    /// ```
+    pub fn save_entities_with(
+        &self,
+        world: &World,
+        entities: &[EntityId],
+        plugin_name: &str,
+    ) -> Result<SaveInfo> {
+        let plugin = self
+            .entity_plugins
+            .get(plugin_name)
+            .ok_or_else(|| PersistenceError::PluginNotFound(plugin_name.to_string()))?;
+
+        let _scope = self.save_scope();
+        #[cfg(feature = "compression")]
+        {
+            let (result, compression) =
+                crate::persistence::compression::CompressionStats::capture(|| {
+                    plugin.save_entities(world, entities)
+                });
+            result?;
+            Ok(SaveInfo {
+                entities: entities.len(),
+                compression,
+                ..SaveInfo::default()
+            })
+        }
+        #[cfg(not(feature = "compression"))]
+        {
+            plugin.save_entities(world, entities)?;
+            Ok(SaveInfo {
+                entities: entities.len(),
+                ..SaveInfo::default()
+            })
+        }
+    }
+
     /// Loads a specific entity using the default entity plugin.
     ///
     /// # Arguments
@@ -871,7 +1282,7 @@ impl PersistenceManager {
         plugin.entity_exists(stable_id)
     }
 
-    /// Lists all registered entity plugin names.
+    /// Lists all registered entity plugin names, sorted.
     pub fn list_entity_plugins(&self) -> Vec<&str> {
         self.entity_plugins.keys().map(|s| s.as_str()).collect()
     }
@@ -912,4 +1323,55 @@ mod tests {
         assert!(manager.change_tracker().has_changes());
         assert_eq!(manager.change_tracker().created().len(), 1);
     }
+
+    #[test]
+    #[cfg(feature = "default-plugins")]
+    fn default_plugins_select_by_extension() {
+        let mut manager = PersistenceManager::with_default_plugins();
+        assert_eq!(manager.default_plugin(), Some("binary"));
+        assert_eq!(manager.plugin_for_extension("PECS"), Some("binary"));
+        assert!(manager.register_extension("yml", "yaml").is_err());
+        manager.register_extension("save", "json").unwrap();
+
+        let mut world = World::new();
+        world.spawn_empty();
+
+        let path = std::env::temp_dir().join("pecs_manager_extension.save");
+        manager.save(&world, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.trim_start().starts_with('{'));
+        assert_eq!(manager.load(&path).unwrap().len(), 1);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn convert_between_plugins() {
+        use crate::persistence::{BinaryPlugin, JsonPlugin};
+
+        let mut manager = PersistenceManager::new();
+        manager.register_plugin("binary", Box::new(BinaryPlugin::new()));
+        manager.register_plugin("json", Box::new(JsonPlugin::new()));
+
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        let stable_id = world.get_stable_id(entity).unwrap();
+
+        let dir = std::env::temp_dir();
+        let src = dir.join("pecs_manager_convert.pecs");
+        let dst = dir.join("pecs_manager_convert.json");
+        manager.save_with(&world, &src, "binary").unwrap();
+
+        manager.convert(&src, "binary", &dst, "json").unwrap();
+        let converted = manager.load_with(&dst, "json").unwrap();
+        assert_eq!(converted.len(), 1);
+        assert!(converted.get_entity_id(stable_id).is_some());
+
+        assert!(matches!(
+            manager.convert(&src, "binary", &dst, "yaml"),
+            Err(PersistenceError::PluginNotFound(_))
+        ));
+
+        let _ = std::fs::remove_file(src);
+        let _ = std::fs::remove_file(dst);
+    }
 }
@@ -17,10 +17,14 @@
 //! Metadata tracking for world persistence.
 
 use std::any::TypeId;
-use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{BTreeMap, HashMap};
 
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::component::registry::FieldLayout;
 use crate::entity::EntityId;
+use crate::persistence::{PersistenceError, Result};
 
 /// Metadata about the world state.
 #[derive(Debug, Clone)]
@@ -29,7 +33,95 @@ pub struct WorldMetadata {
     pub timestamp: u64,
     pub entity_count: usize,
     pub component_types: Vec<ComponentTypeInfo>,
-    pub custom: HashMap<String, String>,
+    /// Free-form key/value pairs, kept sorted so saves are reproducible
+    pub custom: BTreeMap<String, String>,
+    /// Number of times the world has been saved, including by earlier sessions
+    pub save_count: u64,
+    /// Typed extensions stored as serde values, keyed by
+    /// [`MetadataExtension::NAME`].
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// A typed world-level setting that is saved alongside the world.
+///
+/// Extensions are stored in [`WorldMetadata`] under [`NAME`](Self::NAME) and
+/// written into the header of both the binary and JSON formats, so settings
+/// such as game rules survive a save/load round trip.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::persistence::{MetadataExtension, WorldMetadata};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct GameRules {
+///     max_players: u32,
+/// }
+///
+/// impl MetadataExtension for GameRules {
+///     const NAME: &'static str = "game_rules";
+/// }
+///
+/// let mut metadata = WorldMetadata::new(1, 0, Vec::new());
+/// metadata.insert_ext(GameRules { max_players: 4 }).unwrap();
+/// assert_eq!(
+///     metadata.get_ext::<GameRules>().unwrap(),
+///     Some(GameRules { max_players: 4 })
+/// );
+/// ```
+pub trait MetadataExtension: Serialize + DeserializeOwned + 'static {
+    /// Key the extension is stored under. Must be stable across releases.
+    const NAME: &'static str;
+}
+
+/// The part of [`WorldMetadata`] that persistence formats write verbatim.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ExtendedMetadata {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub save_count: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Component layouts, written only on request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema: Vec<ComponentLayout>,
+}
+
+impl ExtendedMetadata {
+    /// Returns true if there is nothing to write.
+    pub fn is_empty(&self) -> bool {
+        self.timestamp == 0
+            && self.save_count == 0
+            && self.custom.is_empty()
+            && self.extensions.is_empty()
+            && self.schema.is_empty()
+    }
+}
+
+/// The layout of a component type, as embedded in a save's schema section.
+///
+/// See [`SaveInfo::schema`](crate::persistence::SaveInfo::schema).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentLayout {
+    /// Schema name of the component, or its Rust type name if it has no
+    /// registered schema
+    pub name: String,
+    /// Schema version of the component
+    pub version: u32,
+    /// Size of the component in bytes
+    pub size: usize,
+    /// Alignment of the component in bytes
+    pub align: usize,
+    /// Field layouts, empty if the component did not record them
+    pub fields: Vec<FieldLayout>,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
 }
 
 impl WorldMetadata {
@@ -39,15 +131,80 @@ impl WorldMetadata {
             timestamp: Self::current_timestamp(),
             entity_count,
             component_types,
-            custom: HashMap::new(),
+            custom: BTreeMap::new(),
+            save_count: 0,
+            extensions: BTreeMap::new(),
         }
     }
 
+    /// Stores a typed extension, replacing any previous value of that type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::Serialization`] if the value cannot be
+    /// converted to a serde value.
+    pub fn insert_ext<T: MetadataExtension>(&mut self, value: T) -> Result<()> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| PersistenceError::Serialization(format!("{}: {}", T::NAME, e)))?;
+        self.extensions.insert(T::NAME.to_string(), value);
+        Ok(())
+    }
+
+    /// Returns a copy of the typed extension, or `None` if it is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::Deserialization`] if the stored value does
+    /// not match `T`, e.g. because it was written by an older version.
+    pub fn get_ext<T: MetadataExtension>(&self) -> Result<Option<T>> {
+        self.extensions
+            .get(T::NAME)
+            .map(|value| {
+                T::deserialize(value)
+                    .map_err(|e| PersistenceError::Deserialization(format!("{}: {}", T::NAME, e)))
+            })
+            .transpose()
+    }
+
+    /// Returns true if an extension of type `T` is set.
+    pub fn has_ext<T: MetadataExtension>(&self) -> bool {
+        self.extensions.contains_key(T::NAME)
+    }
+
+    /// Removes the typed extension, returning true if it was set.
+    pub fn remove_ext<T: MetadataExtension>(&mut self) -> bool {
+        self.extensions.remove(T::NAME).is_some()
+    }
+
+    /// Collects the custom map and extensions for writing.
+    pub(crate) fn extended(&self) -> ExtendedMetadata {
+        ExtendedMetadata {
+            timestamp: self.timestamp,
+            save_count: self.save_count,
+            custom: self.custom.clone(),
+            extensions: self.extensions.clone(),
+            schema: Vec::new(),
+        }
+    }
+
+    /// Restores the save bookkeeping, custom map and extensions read from a
+    /// save.
+    pub(crate) fn apply_extended(&mut self, extended: ExtendedMetadata) {
+        if extended.timestamp != 0 {
+            self.timestamp = extended.timestamp;
+        }
+        self.save_count = extended.save_count;
+        self.custom.extend(extended.custom);
+        self.extensions.extend(extended.extensions);
+    }
+
+    /// Returns the current Unix time in seconds.
+    ///
+    /// Goes through `chrono` rather than `std::time::SystemTime`, which
+    /// panics on `wasm32-unknown-unknown`; chrono reads the JavaScript clock
+    /// there instead.
     pub fn current_timestamp() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        chrono::Utc::now().timestamp().max(0) as u64
     }
 }
 
@@ -61,68 +218,357 @@ pub struct ComponentTypeInfo {
 }
 
 /// Change tracker for delta persistence.
+///
+/// Every change is stamped with the tracker's current epoch, a `u32` tick
+/// that the application advances (typically once per frame) with
+/// [`advance`](Self::advance). Each consumer remembers the epoch it last
+/// read and asks for [`changes_since`](Self::changes_since) that epoch, so
+/// autosave, replication and UI code can each consume changes at their own
+/// pace without clearing them for the others.
+///
+/// Only an entity's latest stamp per kind of change is kept, so memory grows
+/// with the number of distinct entities changed, not the number of changes.
+/// Records older than every consumer's epoch can be dropped with
+/// [`prune_before`](Self::prune_before), or marked droppable in O(1) with
+/// [`acknowledge`](Self::acknowledge); acknowledged records are dropped
+/// whenever the table has doubled since it was last pruned.
+///
+/// [`created`](Self::created), [`modified`](Self::modified) and
+/// [`deleted`](Self::deleted) report changes since the last
+/// [`checkpoint`](Self::checkpoint), for the single-consumer delta plugins.
+///
+/// # Example
+///
+/// ```
+/// use pecs::prelude::*;
+///
+/// let mut world = World::new();
+/// let autosave_seen = world.persistence().change_tracker_mut().advance();
+///
+/// let entity = world.spawn_empty();
+/// let replication_seen = world.persistence().change_tracker_mut().advance();
+/// world.despawn(entity);
+///
+/// let tracker = world.persistence().change_tracker();
+/// assert_eq!(tracker.changes_since(autosave_seen).deleted, vec![entity]);
+/// assert_eq!(tracker.changes_since(replication_seen).deleted, vec![entity]);
+/// assert!(tracker.changes_since(tracker.epoch() + 1).is_empty());
+/// ```
 #[derive(Debug, Default)]
 pub struct ChangeTracker {
-    created: Vec<EntityId>,
-    modified: Vec<EntityId>,
-    deleted: Vec<EntityId>,
+    records: HashMap<EntityId, ChangeRecord>,
+    epoch: u32,
+    checkpoint_epoch: u32,
+    /// Oldest epoch any consumer still needs; older records can go
+    acknowledged: u32,
+    /// Epoch of the latest recorded change
+    latest_change: Option<u32>,
+    /// Record count at which the table is pruned next
+    prune_at: usize,
+    sequence: u64,
     last_checkpoint: u64,
     enabled: bool,
 }
 
+/// The latest change stamps of one entity.
+#[derive(Debug, Default, Clone, Copy)]
+struct ChangeRecord {
+    created: Option<u32>,
+    modified: Option<u32>,
+    deleted: Option<u32>,
+    /// Epoch of the entity's latest change
+    latest: u32,
+    /// Order of the entity's latest change, for stable output
+    sequence: u64,
+}
+
+/// Entities changed since an epoch, as returned by
+/// [`ChangeTracker::changes_since`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    /// Entities created since the epoch (and still alive)
+    pub created: Vec<EntityId>,
+    /// Entities that existed before the epoch and were modified since
+    pub modified: Vec<EntityId>,
+    /// Entities deleted since the epoch
+    pub deleted: Vec<EntityId>,
+}
+
+impl ChangeSet {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.modified.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Returns `true` if `stamp` is at or after `since`, allowing for the epoch
+/// counter wrapping around.
+fn at_or_after(stamp: u32, since: u32) -> bool {
+    stamp.wrapping_sub(since) <= u32::MAX / 2
+}
+
+/// Record count below which the tracker never prunes on its own.
+const MIN_PRUNE_AT: usize = 1024;
+
 impl ChangeTracker {
     pub fn new() -> Self {
         Self {
-            created: Vec::new(),
-            modified: Vec::new(),
-            deleted: Vec::new(),
+            records: HashMap::new(),
+            epoch: 0,
+            checkpoint_epoch: 0,
+            acknowledged: 0,
+            latest_change: None,
+            prune_at: MIN_PRUNE_AT,
+            sequence: 0,
             last_checkpoint: WorldMetadata::current_timestamp(),
             enabled: true,
         }
     }
 
+    /// Turns change recording on or off.
+    ///
+    /// While disabled, the `track_*` methods return without touching the
+    /// record table. Changes made in that window are never reported, so
+    /// consumers should take a fresh baseline after re-enabling.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if changes are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the current epoch, which new changes are stamped with.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Starts a new epoch and returns it.
+    ///
+    /// A consumer that stores the returned value and later passes it to
+    /// [`changes_since`](Self::changes_since) sees every change made after
+    /// this call.
+    pub fn advance(&mut self) -> u32 {
+        self.epoch = self.epoch.wrapping_add(1);
+        self.epoch
+    }
+
+    fn record(&mut self, entity: EntityId) -> &mut ChangeRecord {
+        if self.records.len() >= self.prune_at && !self.records.contains_key(&entity) {
+            self.retain_acknowledged();
+            self.prune_at = (self.records.len() * 2).max(MIN_PRUNE_AT);
+        }
+        self.sequence += 1;
+        let sequence = self.sequence;
+        self.latest_change = Some(self.epoch);
+        let record = self.records.entry(entity).or_default();
+        record.latest = self.epoch;
+        record.sequence = sequence;
+        record
+    }
+
     pub fn track_created(&mut self, entity: EntityId) {
-        if self.enabled && !self.created.contains(&entity) {
-            self.created.push(entity);
+        if self.enabled {
+            let epoch = self.epoch;
+            let record = self.record(entity);
+            record.created = Some(epoch);
+            record.deleted = None;
         }
     }
 
     pub fn track_modified(&mut self, entity: EntityId) {
-        if self.enabled && !self.created.contains(&entity) && !self.modified.contains(&entity) {
-            self.modified.push(entity);
+        if self.enabled {
+            let epoch = self.epoch;
+            self.record(entity).modified = Some(epoch);
         }
     }
 
     pub fn track_deleted(&mut self, entity: EntityId) {
         if self.enabled {
-            self.created.retain(|&e| e != entity);
-            self.modified.retain(|&e| e != entity);
-            if !self.deleted.contains(&entity) {
-                self.deleted.push(entity);
+            let epoch = self.epoch;
+            self.record(entity).deleted = Some(epoch);
+        }
+    }
+
+    /// Returns the entities changed at or after `epoch`.
+    ///
+    /// An entity appears in at most one list: deletion wins over creation
+    /// and modification, and creation over modification.
+    pub fn changes_since(&self, epoch: u32) -> ChangeSet {
+        let mut records: Vec<_> = self
+            .records
+            .iter()
+            .filter(|(_, record)| at_or_after(record.latest, epoch))
+            .collect();
+        records.sort_by_key(|(_, record)| record.sequence);
+
+        let within = |stamp: Option<u32>| stamp.is_some_and(|stamp| at_or_after(stamp, epoch));
+        let mut changes = ChangeSet::default();
+        for (&entity, record) in records {
+            if within(record.deleted) {
+                changes.deleted.push(entity);
+            } else if record.deleted.is_some() {
+                continue;
+            } else if within(record.created) {
+                changes.created.push(entity);
+            } else if within(record.modified) {
+                changes.modified.push(entity);
             }
         }
+        changes
+    }
+
+    /// Drops the records of entities whose latest change is older than
+    /// `epoch`.
+    ///
+    /// Call this with the oldest epoch any consumer still needs.
+    pub fn prune_before(&mut self, epoch: u32) {
+        self.acknowledge(epoch);
+        self.retain_acknowledged();
+    }
+
+    /// Declares that no consumer needs changes older than `epoch`.
+    ///
+    /// Unlike [`prune_before`](Self::prune_before) this does not touch the
+    /// record table; the records are dropped the next time it is pruned.
+    pub fn acknowledge(&mut self, epoch: u32) {
+        self.acknowledged = epoch;
+    }
+
+    fn retain_acknowledged(&mut self) {
+        let acknowledged = self.acknowledged;
+        self.records
+            .retain(|_, record| at_or_after(record.latest, acknowledged));
     }
 
-    pub fn created(&self) -> &[EntityId] {
-        &self.created
+    /// Entities created since the last checkpoint.
+    pub fn created(&self) -> Vec<EntityId> {
+        self.changes_since(self.checkpoint_epoch).created
     }
 
-    pub fn modified(&self) -> &[EntityId] {
-        &self.modified
+    /// Entities modified since the last checkpoint.
+    pub fn modified(&self) -> Vec<EntityId> {
+        self.changes_since(self.checkpoint_epoch).modified
     }
 
-    pub fn deleted(&self) -> &[EntityId] {
-        &self.deleted
+    /// Entities deleted since the last checkpoint.
+    pub fn deleted(&self) -> Vec<EntityId> {
+        self.changes_since(self.checkpoint_epoch).deleted
     }
 
+    /// Returns `true` if anything changed since the last checkpoint.
     pub fn has_changes(&self) -> bool {
-        !self.created.is_empty() || !self.modified.is_empty() || !self.deleted.is_empty()
+        self.latest_change
+            .is_some_and(|epoch| at_or_after(epoch, self.checkpoint_epoch))
     }
 
+    /// Marks everything tracked so far as consumed by the checkpoint
+    /// readers ([`created`](Self::created) and friends).
+    ///
+    /// Starts a new epoch, and acknowledges the previous checkpoint's epoch,
+    /// so records only older than it are eventually dropped. Consumers using
+    /// [`changes_since`](Self::changes_since) should read at least once per
+    /// checkpoint interval.
     pub fn checkpoint(&mut self) {
-        self.created.clear();
-        self.modified.clear();
-        self.deleted.clear();
+        let previous = self.checkpoint_epoch;
+        self.checkpoint_epoch = self.advance();
+        self.acknowledge(previous);
         self.last_checkpoint = WorldMetadata::current_timestamp();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumers_read_changes_independently() {
+        let mut tracker = ChangeTracker::new();
+        let a = EntityId::new(1, 1);
+        let b = EntityId::new(2, 1);
+        let c = EntityId::new(3, 1);
+
+        tracker.track_created(a);
+        tracker.track_created(b);
+        let first = tracker.advance();
+        tracker.track_modified(a);
+        tracker.track_created(c);
+        tracker.track_deleted(b);
+        let second = tracker.advance();
+        tracker.track_modified(c);
+
+        let all = tracker.changes_since(0);
+        assert_eq!(all.created, vec![a, c]);
+        assert!(all.modified.is_empty());
+        assert_eq!(all.deleted, vec![b]);
+
+        let since_first = tracker.changes_since(first);
+        assert_eq!(since_first.created, vec![c]);
+        assert_eq!(since_first.modified, vec![a]);
+        assert_eq!(since_first.deleted, vec![b]);
+
+        let since_second = tracker.changes_since(second);
+        assert_eq!(since_second.modified, vec![c]);
+        assert!(since_second.created.is_empty() && since_second.deleted.is_empty());
+
+        // Only `c` changed at or after `second`
+        tracker.prune_before(second);
+        let remaining = tracker.changes_since(0);
+        assert_eq!(remaining.created, vec![c]);
+        assert!(remaining.modified.is_empty() && remaining.deleted.is_empty());
+    }
+
+    #[test]
+    fn checkpoint_resets_checkpoint_readers_only() {
+        let mut tracker = ChangeTracker::new();
+        let a = EntityId::new(1, 1);
+        let start = tracker.epoch();
+
+        tracker.track_created(a);
+        assert!(tracker.has_changes());
+        assert_eq!(tracker.created(), vec![a]);
+
+        tracker.checkpoint();
+        assert!(!tracker.has_changes());
+        assert_eq!(tracker.changes_since(start).created, vec![a]);
+
+        tracker.track_modified(a);
+        assert_eq!(tracker.modified(), vec![a]);
+    }
+
+    #[test]
+    fn acknowledged_records_are_pruned_as_the_table_grows() {
+        let mut tracker = ChangeTracker::new();
+        let entity = |index: usize| EntityId::new(index as u32, 1);
+
+        for index in 0..MIN_PRUNE_AT {
+            tracker.track_created(entity(index));
+        }
+        let kept = tracker.advance();
+        tracker.acknowledge(kept);
+        assert_eq!(tracker.records.len(), MIN_PRUNE_AT);
+
+        for index in MIN_PRUNE_AT..MIN_PRUNE_AT + 10 {
+            tracker.track_created(entity(index));
+        }
+        assert_eq!(tracker.records.len(), 10);
+        assert_eq!(tracker.changes_since(kept).created.len(), 10);
+        assert!(tracker.has_changes());
+    }
+
+    #[test]
+    fn epochs_wrap_around() {
+        let mut tracker = ChangeTracker::new();
+        tracker.epoch = u32::MAX;
+        let a = EntityId::new(1, 1);
+        tracker.track_created(a);
+        let next = tracker.advance();
+        assert_eq!(next, 0);
+        tracker.track_modified(a);
+
+        assert_eq!(tracker.changes_since(u32::MAX).created, vec![a]);
+        assert!(tracker.changes_since(next).created.is_empty());
+        assert_eq!(tracker.changes_since(next).modified, vec![a]);
+    }
+}
@@ -0,0 +1,247 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Options controlling how strictly saves are loaded.
+//!
+//! A [`LoadOptions`] value tells the loaders what to do when a save refers
+//! to component types this program cannot restore, and whether to trust a
+//! file whose checksum does not match. [`LoadOptions::strict`], the
+//! default, fails on anything it cannot restore; [`LoadOptions::lenient`]
+//! loads what it can, which suits servers receiving saves from newer
+//! clients.
+//!
+//! The `max_*` limits bound the resources a load may use, for servers that
+//! accept saves from untrusted clients. A save over a limit fails with
+//! [`PersistenceError::LimitExceeded`] as soon as the excess is seen,
+//! before it is allocated. The binary format enforces every limit; formats
+//! that cannot enforce one ignore it.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//! use pecs::persistence::{LoadOptions, UnknownComponent};
+//!
+//! let mut buffer = Vec::new();
+//! World::new().save_binary(&mut buffer).unwrap();
+//!
+//! let options = LoadOptions {
+//!     unknown_component: UnknownComponent::Skip,
+//!     ..LoadOptions::strict()
+//! };
+//! let world = World::load_binary_with_options(&mut buffer.as_slice(), &options).unwrap();
+//! assert!(world.is_empty());
+//!
+//! // Accept at most 10,000 entities in at most 1 MiB from a client
+//! let options = LoadOptions {
+//!     max_entities: Some(10_000),
+//!     max_component_bytes: Some(4096),
+//!     max_total_bytes: Some(1 << 20),
+//!     ..LoadOptions::strict()
+//! };
+//! let world = World::load_binary_with_options(&mut buffer.as_slice(), &options).unwrap();
+//! assert!(world.is_empty());
+//! ```
+
+use crate::component::registry::{ComponentRegistry, InsertDefaultFn};
+use crate::persistence::{PersistenceError, Result};
+use std::io::Read;
+
+/// What to do with a saved component whose type name is not registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownComponent {
+    /// Drop the component and keep loading
+    Skip,
+    /// Keep the raw bytes in an
+    /// [`UnknownComponents`](crate::persistence::unknown::UnknownComponents)
+    /// component on the entity, so saving writes them back out. Formats
+    /// that cannot carry raw payloads skip the component instead
+    Preserve,
+    /// Fail with [`PersistenceError::UnknownComponentType`]
+    #[default]
+    Error,
+}
+
+/// What to do with a saved component whose type is registered, but without
+/// a codec to decode the saved value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingComponentType {
+    /// Insert the type's registered default value instead, failing if no
+    /// default is registered
+    Default,
+    /// Drop the component and keep loading
+    Skip,
+    /// Fail with [`PersistenceError::Deserialization`]
+    #[default]
+    Error,
+}
+
+/// Whether to verify the checksum of formats that carry one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumPolicy {
+    /// Fail with [`PersistenceError::ChecksumMismatch`] on a mismatch
+    #[default]
+    Verify,
+    /// Load the data even if the checksum does not match
+    Ignore,
+}
+
+/// How strictly a save is loaded.
+///
+/// Passed to [`World::load_with_options`](crate::World::load_with_options)
+/// and the other `*_with_options` load functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoadOptions {
+    /// Handling of components whose type name is not registered
+    pub unknown_component: UnknownComponent,
+    /// Handling of registered component types that cannot be decoded
+    pub missing_component_type: MissingComponentType,
+    /// Handling of checksum mismatches
+    pub checksum: ChecksumPolicy,
+    /// Largest number of entities a save may contain, or `None` for no
+    /// limit
+    pub max_entities: Option<usize>,
+    /// Largest encoded size of a single component value in bytes, or
+    /// `None` for no limit. With the `compression` feature this also caps
+    /// how far a compressed component may inflate
+    pub max_component_bytes: Option<usize>,
+    /// Largest number of bytes read from the input, or `None` for no limit
+    pub max_total_bytes: Option<u64>,
+}
+
+/// How a loader should restore one saved component.
+pub(crate) enum SavedComponent {
+    /// Decode the saved value through the registered JSON codec
+    Decode,
+    /// Insert the registered default value instead
+    InsertDefault(InsertDefaultFn),
+    /// Keep the raw payload for writing back out
+    Preserve,
+    /// Leave the component out
+    Skip,
+}
+
+impl LoadOptions {
+    /// Fails on anything that cannot be restored exactly. This is the
+    /// default.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Loads as much as possible: unknown components are preserved,
+    /// undecodable ones replaced by their defaults, and checksums ignored.
+    /// No size limits are set.
+    pub fn lenient() -> Self {
+        Self {
+            unknown_component: UnknownComponent::Preserve,
+            missing_component_type: MissingComponentType::Default,
+            checksum: ChecksumPolicy::Ignore,
+            ..Self::default()
+        }
+    }
+
+    /// Decides how to restore a saved component of the type named `name`.
+    pub(crate) fn resolve_component(&self, name: &str) -> Result<SavedComponent> {
+        let registry = ComponentRegistry::global();
+        if registry.has_json(name) {
+            return Ok(SavedComponent::Decode);
+        }
+
+        let type_id = registry.type_by_schema_name(name).or_else(|| {
+            registry
+                .info_by_name(name)
+                .map(|info| info.type_id().type_id())
+        });
+        let Some(type_id) = type_id else {
+            return match self.unknown_component {
+                UnknownComponent::Skip => Ok(SavedComponent::Skip),
+                UnknownComponent::Preserve => Ok(SavedComponent::Preserve),
+                UnknownComponent::Error => {
+                    Err(PersistenceError::UnknownComponentType(name.to_string()))
+                }
+            };
+        };
+
+        match self.missing_component_type {
+            MissingComponentType::Skip => Ok(SavedComponent::Skip),
+            MissingComponentType::Default => registry
+                .default_inserter_for(type_id)
+                .map(SavedComponent::InsertDefault)
+                .ok_or_else(|| {
+                    PersistenceError::Deserialization(format!(
+                        "No codec or default registered for component '{}'",
+                        name
+                    ))
+                }),
+            MissingComponentType::Error => Err(PersistenceError::Deserialization(format!(
+                "No codec registered for component '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+/// Fails with [`PersistenceError::LimitExceeded`] if `actual` is over `max`.
+pub(crate) fn check_limit(limit: &'static str, max: Option<u64>, actual: u64) -> Result<()> {
+    match max {
+        Some(max) if actual > max => Err(PersistenceError::LimitExceeded { limit, max, actual }),
+        _ => Ok(()),
+    }
+}
+
+/// A reader that stops after one byte more than `max` bytes, so a caller
+/// can tell an input that fits from one that goes over.
+pub(crate) struct LimitedReader<'a> {
+    inner: &'a mut dyn Read,
+    max: Option<u64>,
+    read: u64,
+}
+
+impl<'a> LimitedReader<'a> {
+    /// Wraps `inner`, allowing `max` bytes or any number if `None`.
+    pub(crate) fn new(inner: &'a mut dyn Read, max: Option<u64>) -> Self {
+        Self {
+            inner,
+            max,
+            read: 0,
+        }
+    }
+
+    /// Fails with [`PersistenceError::LimitExceeded`] if more than the
+    /// allowed number of bytes were read.
+    pub(crate) fn check(&self) -> Result<()> {
+        check_limit("max_total_bytes", self.max, self.read)
+    }
+}
+
+impl Read for LimitedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = match self.max {
+            Some(max) => {
+                let remaining = max.saturating_add(1).saturating_sub(self.read);
+                buf.len()
+                    .min(usize::try_from(remaining).unwrap_or(usize::MAX))
+            }
+            None => buf.len(),
+        };
+        if len == 0 {
+            return Ok(0);
+        }
+        let read = self.inner.read(&mut buf[..len])?;
+        self.read += read as u64;
+        Ok(read)
+    }
+}
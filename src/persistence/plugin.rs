@@ -18,7 +18,9 @@
 
 use crate::World;
 use crate::entity::{EntityId, StableId};
-use crate::persistence::Result;
+use crate::persistence::{LoadOptions, PersistenceError, Result};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::io::{Read, Write};
 
 /// Trait for implementing custom persistence formats.
@@ -84,6 +86,20 @@ pub trait PersistencePlugin: Send + Sync {
     /// Returns an error if deserialization fails.
     fn load(&self, reader: &mut dyn Read) -> Result<World>;
 
+    /// Deserialize a world, applying `options` where the format supports
+    /// them.
+    ///
+    /// The default ignores `options` and calls [`load`](Self::load).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization fails or `options` rejects the
+    /// data.
+    fn load_with_options(&self, reader: &mut dyn Read, options: &LoadOptions) -> Result<World> {
+        let _ = options;
+        self.load(reader)
+    }
+
     /// Get the name of this format.
     ///
     /// This is used for plugin registration and identification.
@@ -202,11 +218,7 @@ pub trait DeltaPersistencePlugin: Send + Sync {
     /// This is used to track when changes were made. Default implementation
     /// uses system time.
     fn current_timestamp(&self) -> u64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        crate::persistence::WorldMetadata::current_timestamp()
     }
 }
 
@@ -250,6 +262,10 @@ pub enum EntityChange {
 impl EntityChange {
     /// Apply this change to a world.
     ///
+    /// Component data written by
+    /// [`PersistenceManager::save_world_delta`](crate::persistence::PersistenceManager::save_world_delta)
+    /// is applied to the entity if it is alive in `world`.
+    ///
     /// # Arguments
     ///
     /// * `world` - The world to apply the change to
@@ -260,21 +276,30 @@ impl EntityChange {
     pub fn apply(&self, world: &mut World) -> Result<()> {
         match self {
             EntityChange::Created {
-                entity, components, ..
-            } => {
-                // Create entity with components
-                // This will be implemented when we integrate with World
-                let _ = (entity, components);
-                Ok(())
+                entity,
+                components: changed,
+                ..
             }
-            EntityChange::Modified {
+            | EntityChange::Modified {
                 entity,
-                added_or_modified,
-                removed,
+                added_or_modified: changed,
                 ..
             } => {
-                // Modify entity components
-                let _ = (entity, added_or_modified, removed);
+                // Component data is only applied to entities that exist;
+                // removals are carried in `changed` as well
+                if !world.is_alive(*entity) {
+                    return Ok(());
+                }
+                for component in changed {
+                    let change = serde_json::from_slice(&component.data)
+                        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+                    crate::persistence::delta::apply_change(
+                        world,
+                        *entity,
+                        &component.type_name,
+                        change,
+                    )?;
+                }
                 Ok(())
             }
             EntityChange::Deleted { entity, .. } => {
@@ -319,9 +344,29 @@ pub struct ComponentData {
 
 /// Trait for components that can be persisted.
 ///
-/// This trait is automatically implemented for all types that implement
-/// the `Component` trait. Users can customize persistence behavior by
-/// implementing this trait manually.
+/// Implement it with `#[derive(SerializableComponent)]`, which encodes the
+/// component through serde (see [`serialize_serde`]) and takes the version
+/// and transient flag from the `#[component(...)]` attribute. The derive
+/// also registers the type with
+/// [`ComponentRegistry::register_json`](crate::component::registry::ComponentRegistry::register_json),
+/// through which the JSON and binary world formats save components. Users
+/// can customize persistence behavior by implementing this trait manually.
+///
+/// ```
+/// use pecs::persistence::SerializableComponent;
+/// use pecs::{Component, SerializableComponent};
+///
+/// #[derive(Component, SerializableComponent, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+/// #[component(version = 2)]
+/// struct Health {
+///     current: u32,
+/// }
+///
+/// let mut bytes = Vec::new();
+/// Health { current: 7 }.serialize(&mut bytes).unwrap();
+/// assert_eq!(Health::deserialize(&mut bytes.as_slice()).unwrap(), Health { current: 7 });
+/// assert_eq!(Health::VERSION, 2);
+/// ```
 ///
 /// # Transient Components
 ///
@@ -347,6 +392,9 @@ pub struct ComponentData {
 /// }
 /// ```
 pub trait SerializableComponent {
+    /// Version of the serialized representation, for migrations.
+    const VERSION: u32 = 1;
+
     /// Serialize this component to the given writer.
     ///
     /// # Arguments
@@ -380,6 +428,27 @@ pub trait SerializableComponent {
     }
 }
 
+/// Writes `value` with the serde-based encoding used by
+/// `#[derive(SerializableComponent)]`.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::Serialization`](crate::persistence::PersistenceError::Serialization)
+/// if the value cannot be encoded or written.
+pub fn serialize_serde<T: Serialize>(value: &T, writer: &mut dyn Write) -> Result<()> {
+    serde_json::to_writer(writer, value).map_err(|e| PersistenceError::Serialization(e.to_string()))
+}
+
+/// Reads a value written by [`serialize_serde`].
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::Deserialization`](crate::persistence::PersistenceError::Deserialization)
+/// if the data cannot be read or does not match `T`.
+pub fn deserialize_serde<T: DeserializeOwned>(reader: &mut dyn Read) -> Result<T> {
+    serde_json::from_reader(reader).map_err(|e| PersistenceError::Deserialization(e.to_string()))
+}
+
 /// Trait for version migrations.
 ///
 /// Migrations allow upgrading saved data from older versions to newer versions.
@@ -578,6 +647,43 @@ pub trait EntityPersistencePlugin: Send + Sync {
     /// ```
     fn entity_exists(&self, stable_id: StableId) -> Result<bool>;
 
+    /// Find every stored entity that has a given component.
+    ///
+    /// `component_type_name` is the name the component is stored under,
+    /// such as its [`ComponentRegistry::register_json`] name. Nothing is
+    /// loaded; pass the result (or a filtered part of it) to
+    /// [`load_entities`](Self::load_entities) to bring entities in lazily.
+    /// The order of the returned IDs is unspecified.
+    ///
+    /// The default implementation reports that the backend does not support
+    /// component queries. Backends should answer from a secondary index
+    /// rather than scanning the whole store.
+    ///
+    /// [`ComponentRegistry::register_json`]: crate::component::registry::ComponentRegistry::register_json
+    ///
+    /// # Arguments
+    ///
+    /// * `component_type_name` - The stored name of the component
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage operation fails or the backend does
+    /// not support component queries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let shops = plugin.find_entities_with("Shop")?;
+    /// let loaded = plugin.load_entities(&mut world, &shops)?;
+    /// ```
+    fn find_entities_with(&self, component_type_name: &str) -> Result<Vec<StableId>> {
+        Err(PersistenceError::PluginError(format!(
+            "{} backend does not support finding entities by component (`{}`)",
+            self.backend_name(),
+            component_type_name
+        )))
+    }
+
     /// Save multiple entities in a batch operation.
     ///
     /// Default implementation calls `save_entity` for each entity.
@@ -636,6 +742,60 @@ pub trait EntityPersistencePlugin: Send + Sync {
     }
 }
 
+/// Summary of a save.
+///
+/// Returned by world and entity saves, and read back from binary saves by
+/// [`BinaryPlugin::read_info`](crate::persistence::binary::BinaryPlugin::read_info).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveInfo {
+    /// Number of entities saved.
+    pub entities: usize,
+    /// Payloads written through
+    /// [`CompressedComponent`](crate::persistence::compression::CompressedComponent)
+    /// during the save.
+    #[cfg(feature = "compression")]
+    pub compression: crate::persistence::compression::CompressionStats,
+    /// Component layouts embedded in the save
+    pub(crate) schema: Vec<crate::persistence::ComponentLayout>,
+}
+
+impl SaveInfo {
+    /// Returns the component layouts embedded in the save, sorted by name.
+    ///
+    /// Empty unless the save was written with a schema section, see
+    /// [`BinaryPlugin::with_schema`](crate::persistence::binary::BinaryPlugin::with_schema).
+    /// Each payload is the component's serde JSON: by default an object
+    /// keyed by the field names listed here, or an array of the fields in
+    /// order for tuple structs. The layouts' sizes and offsets describe the
+    /// Rust types in memory, not the payloads.
+    pub fn schema(&self) -> &[crate::persistence::ComponentLayout] {
+        &self.schema
+    }
+
+    /// Renders the schema as a Markdown reference of the save's component
+    /// payloads.
+    pub fn schema_docs(&self) -> String {
+        use std::fmt::Write;
+
+        let mut docs = String::from("# Component Schema\n");
+        for layout in &self.schema {
+            let _ = write!(
+                docs,
+                "\n## {} (version {})\n\nEncoded as serde JSON.\n",
+                layout.name, layout.version
+            );
+            if layout.fields.is_empty() {
+                continue;
+            }
+            docs.push_str("\n| Field | Type |\n|---|---|\n");
+            for field in &layout.fields {
+                let _ = writeln!(docs, "| {} | `{}` |", field.name, field.type_name);
+            }
+        }
+        docs
+    }
+}
+
 /// Serialized entity data for entity-specific persistence.
 ///
 /// This structure contains all the information needed to persist and restore
@@ -668,10 +828,6 @@ impl EntityData {
 
     /// Get the current timestamp.
     pub fn current_timestamp() -> u64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        crate::persistence::WorldMetadata::current_timestamp()
     }
 }
@@ -0,0 +1,642 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Hand-authored scenes.
+//!
+//! A [`Scene`] is a list of labelled entities, each with components keyed by
+//! their [`register_json`](ComponentRegistry::register_json) name. Scenes
+//! are written in a TOML subset meant for people rather than tools: each
+//! entity is a table named by its label, and other entities are referenced
+//! as `"@label"` instead of by UUID.
+//!
+//! ```toml
+//! [player]
+//! Position = { x = 0.0, y = 0.0 }
+//!
+//! [player.Health]
+//! current = 100
+//!
+//! [sword]
+//! id = "550e8400-e29b-41d4-a716-446655440000"  # optional fixed stable ID
+//! Owner = "@player"
+//! Marker = {}
+//! ```
+//!
+//! An `id` key fixes an entity's stable ID; entities without one get a
+//! fresh ID when spawned. Strings starting with `@` anywhere in a component
+//! value are references, resolved to the stable ID of the labelled entity
+//! when the scene is spawned; write `@@` for a literal leading `@`. Marker
+//! components that deserialize from `null` are written as empty tables.
+//!
+//! Scenes double as prefabs: strings such as `"{spawn_x}"` are placeholders
+//! filled in from [`SceneParams`] by [`Scene::spawn_with_params`].
+
+mod params;
+
+pub use params::SceneParams;
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::{EntityId, StableId};
+use crate::persistence::json::schema::registered_types;
+use crate::persistence::toml::{parse, write};
+use crate::persistence::{PersistenceError, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Key of the optional stable ID in an entity table.
+const ID_KEY: &str = "id";
+
+/// A set of labelled entities that can be spawned into a world.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::component::registry::ComponentRegistry;
+/// use pecs::persistence::Scene;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Owner(StableId);
+/// impl Component for Owner {}
+///
+/// ComponentRegistry::global().register_json::<Owner>("Owner");
+///
+/// let scene = Scene::from_toml(r#"
+///     [player]
+///
+///     [sword]
+///     Owner = "@player"
+/// "#).unwrap();
+///
+/// let mut world = World::new();
+/// let spawned = scene.spawn(&mut world).unwrap();
+/// let player = world.get_stable_id(spawned["player"]).unwrap();
+/// assert_eq!(world.get::<Owner>(spawned["sword"]), Some(&Owner(player)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scene {
+    /// Entities in authoring order
+    entities: Vec<SceneEntity>,
+}
+
+/// One labelled entity of a [`Scene`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneEntity {
+    /// Label, unique within the scene
+    label: String,
+    /// Fixed stable ID, if any
+    id: Option<StableId>,
+    /// Component values keyed by registered JSON name
+    components: BTreeMap<String, Value>,
+}
+
+impl SceneEntity {
+    /// Creates an entity with no components.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            id: None,
+            components: BTreeMap::new(),
+        }
+    }
+
+    /// Fixes the stable ID the entity is spawned with.
+    pub fn with_id(mut self, id: StableId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Adds a component value under its registered JSON name.
+    pub fn with_component(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.components.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the entity's label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the fixed stable ID, if any.
+    pub fn id(&self) -> Option<StableId> {
+        self.id
+    }
+
+    /// Returns the component values, keyed by registered JSON name.
+    pub fn components(&self) -> &BTreeMap<String, Value> {
+        &self.components
+    }
+}
+
+impl Scene {
+    /// Creates an empty scene.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a scene from TOML.
+    ///
+    /// Entities are kept in the order they appear in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::InvalidFormat`] if the text is not valid
+    /// in the supported TOML subset, a top-level value is not a table, or
+    /// an `id` is not a stable ID.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let (mut document, order) = parse::parse(text)?;
+        let mut scene = Self::new();
+        for label in order {
+            let Some(Value::Object(table)) = document.remove(&label) else {
+                return Err(PersistenceError::InvalidFormat(format!(
+                    "{}: expected an entity table",
+                    label
+                )));
+            };
+            let mut entity = SceneEntity::new(label);
+            for (name, value) in table {
+                if name != ID_KEY {
+                    entity.components.insert(name, value);
+                    continue;
+                }
+                let id = value
+                    .as_str()
+                    .and_then(|id| id.parse().ok())
+                    .ok_or_else(|| {
+                        PersistenceError::InvalidFormat(format!(
+                            "{}.{}: invalid stable ID {}",
+                            entity.label, ID_KEY, value
+                        ))
+                    })?;
+                entity.id = Some(id);
+            }
+            scene.entities.push(entity);
+        }
+        Ok(scene)
+    }
+
+    /// Writes the scene as TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::Serialization`] if a component value
+    /// can't be represented in TOML, such as an array holding `null`.
+    pub fn to_toml(&self) -> Result<String> {
+        let mut document = Map::new();
+        let mut out = String::new();
+        for entity in &self.entities {
+            let mut table = Map::new();
+            if let Some(id) = entity.id {
+                table.insert(ID_KEY.to_string(), Value::String(id.to_string()));
+            }
+            for (name, value) in &entity.components {
+                let value = match value {
+                    Value::Null => Value::Object(Map::new()),
+                    value => value.clone(),
+                };
+                table.insert(name.clone(), value);
+            }
+
+            // Written one entity at a time to keep authoring order
+            document.insert(entity.label.clone(), Value::Object(table));
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&write::write(&document, 2)?);
+            document.clear();
+        }
+        Ok(out)
+    }
+
+    /// Captures the JSON-registered components of every entity in `world`.
+    ///
+    /// Entities are labelled `entity_0`, `entity_1`, ... in stable ID
+    /// order, keep their stable IDs, and component values holding the
+    /// stable ID of another captured entity are turned into references.
+    pub fn from_world(world: &World) -> Self {
+        let registry = ComponentRegistry::global();
+        let types = registered_types();
+        let mut handles: Vec<_> = world.iter_entities().collect();
+        handles.sort_unstable_by_key(|(_, stable_id)| *stable_id);
+
+        let labels: HashMap<String, String> = handles
+            .iter()
+            .enumerate()
+            .map(|(index, (_, stable_id))| (stable_id.to_string(), format!("entity_{}", index)))
+            .collect();
+
+        let mut scene = Self::new();
+        for (entity, stable_id) in handles {
+            let mut scene_entity =
+                SceneEntity::new(labels[&stable_id.to_string()].clone()).with_id(stable_id);
+            for ty in &types {
+                if let Some(mut value) = registry.get_json(&ty.name, world, entity) {
+                    to_references(&mut value, &labels);
+                    scene_entity.components.insert(ty.name.clone(), value);
+                }
+            }
+            scene.entities.push(scene_entity);
+        }
+        scene
+    }
+
+    /// Adds an entity to the scene.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::InvalidFormat`] if the label is already
+    /// used.
+    pub fn push(&mut self, entity: SceneEntity) -> Result<()> {
+        if self.get(&entity.label).is_some() {
+            return Err(PersistenceError::InvalidFormat(format!(
+                "duplicate scene label {}",
+                entity.label
+            )));
+        }
+        self.entities.push(entity);
+        Ok(())
+    }
+
+    /// Returns the entity with `label`.
+    pub fn get(&self, label: &str) -> Option<&SceneEntity> {
+        self.entities.iter().find(|entity| entity.label == label)
+    }
+
+    /// Returns the entities in authoring order.
+    pub fn entities(&self) -> &[SceneEntity] {
+        &self.entities
+    }
+
+    /// Returns the number of entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns whether the scene has no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Spawns the scene's entities into `world`.
+    ///
+    /// All entities are allocated first so references between them resolve
+    /// regardless of order. If anything fails, the entities spawned so far
+    /// are despawned again.
+    ///
+    /// # Returns
+    ///
+    /// The spawned entities by label.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::EntityIdConflict`] if a fixed stable ID
+    /// is already in use, [`PersistenceError::InvalidFormat`] for a
+    /// reference to an unknown label, or the error of a component that
+    /// fails to insert.
+    pub fn spawn(&self, world: &mut World) -> Result<HashMap<String, EntityId>> {
+        self.spawn_checked(world, None)
+    }
+
+    /// Spawns the scene's entities into `world`, filling in placeholders
+    /// from `params` first.
+    ///
+    /// Placeholders are substituted before references are resolved, so a
+    /// parameter can itself be a `"@label"` reference. Spawning the same
+    /// scene with different parameters makes data-driven spawners possible
+    /// without custom deserialization per prefab; give prefab entities no
+    /// fixed `id` so each spawn gets fresh stable IDs.
+    ///
+    /// # Errors
+    ///
+    /// As [`spawn`](Self::spawn), and [`PersistenceError::InvalidFormat`]
+    /// for a placeholder with no parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    /// use pecs::persistence::{Scene, SceneParams};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// struct Spawn { x: f32, name: String }
+    /// impl Component for Spawn {}
+    ///
+    /// ComponentRegistry::global().register_json::<Spawn>("Spawn");
+    ///
+    /// let prefab = Scene::from_toml(r#"
+    ///     [wolf.Spawn]
+    ///     x = "{spawn_x}"
+    ///     name = "wolf_{index}"
+    /// "#).unwrap();
+    /// assert_eq!(prefab.parameters().len(), 2);
+    ///
+    /// let mut world = World::new();
+    /// let params = SceneParams::new().with("spawn_x", 4.0).with("index", 2);
+    /// let spawned = prefab.spawn_with_params(&mut world, &params).unwrap();
+    /// assert_eq!(
+    ///     world.get::<Spawn>(spawned["wolf"]),
+    ///     Some(&Spawn { x: 4.0, name: "wolf_2".to_string() })
+    /// );
+    /// ```
+    pub fn spawn_with_params(
+        &self,
+        world: &mut World,
+        params: &SceneParams,
+    ) -> Result<HashMap<String, EntityId>> {
+        self.spawn_checked(world, Some(params))
+    }
+
+    /// Returns the names of the placeholders used in component values.
+    pub fn parameters(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for value in self
+            .entities
+            .iter()
+            .flat_map(|entity| entity.components.values())
+        {
+            params::collect_placeholders(value, &mut names);
+        }
+        names
+    }
+
+    /// Spawns the entities, despawning them again on failure.
+    fn spawn_checked(
+        &self,
+        world: &mut World,
+        params: Option<&SceneParams>,
+    ) -> Result<HashMap<String, EntityId>> {
+        let mut spawned = HashMap::with_capacity(self.entities.len());
+        if let Err(e) = self.spawn_into(world, params, &mut spawned) {
+            for entity in spawned.into_values() {
+                world.despawn(entity);
+            }
+            return Err(e);
+        }
+        Ok(spawned)
+    }
+
+    fn spawn_into(
+        &self,
+        world: &mut World,
+        params: Option<&SceneParams>,
+        spawned: &mut HashMap<String, EntityId>,
+    ) -> Result<()> {
+        let mut stable_ids = HashMap::with_capacity(self.entities.len());
+        for entity in &self.entities {
+            let id = match entity.id {
+                Some(stable_id) => world.spawn_empty_with_stable_id(stable_id).map_err(|e| {
+                    PersistenceError::EntityIdConflict(format!("{}: {}", entity.label, e))
+                })?,
+                None => world.spawn_empty(),
+            };
+            spawned.insert(entity.label.clone(), id);
+            let stable_id = world
+                .get_stable_id(id)
+                .ok_or(PersistenceError::EntityNotFound(id))?;
+            stable_ids.insert(entity.label.as_str(), stable_id);
+        }
+
+        let registry = ComponentRegistry::global();
+        for entity in &self.entities {
+            let id = spawned[&entity.label];
+            for (name, value) in &entity.components {
+                // Name the component by its path in the scene document
+                let context =
+                    |e: PersistenceError| e.in_component(format!("{}.{}", entity.label, name));
+                let mut value = value.clone();
+                if let Some(params) = params {
+                    params.substitute(&mut value).map_err(context)?;
+                }
+                resolve_references(&mut value, &stable_ids).map_err(context)?;
+                insert_component(registry, name, world, id, value).map_err(context)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Inserts a component, retrying an empty table as `null` for markers.
+fn insert_component(
+    registry: &ComponentRegistry,
+    name: &str,
+    world: &mut World,
+    entity: EntityId,
+    value: Value,
+) -> Result<()> {
+    let marker = value.as_object().is_some_and(Map::is_empty);
+    match registry.insert_json(name, world, entity, value) {
+        Err(PersistenceError::Deserialization(_)) if marker => {
+            registry.insert_json(name, world, entity, Value::Null)?;
+        }
+        result => {
+            result?;
+        }
+    }
+    Ok(())
+}
+
+/// Replaces `"@label"` strings in `value` with stable IDs.
+fn resolve_references(value: &mut Value, stable_ids: &HashMap<&str, StableId>) -> Result<()> {
+    match value {
+        Value::String(text) => {
+            if let Some(reference) = text.strip_prefix('@') {
+                *text = match reference.strip_prefix('@') {
+                    Some(_) => reference.to_string(),
+                    None => stable_ids
+                        .get(reference)
+                        .ok_or_else(|| {
+                            PersistenceError::InvalidFormat(format!(
+                                "reference to unknown entity @{}",
+                                reference
+                            ))
+                        })?
+                        .to_string(),
+                };
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_references(item, stable_ids)?;
+            }
+        }
+        Value::Object(object) => {
+            for item in object.values_mut() {
+                resolve_references(item, stable_ids)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces stable ID strings of captured entities in `value` with
+/// references, and escapes other strings starting with `@`.
+fn to_references(value: &mut Value, labels: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            if let Some(label) = labels.get(text.as_str()) {
+                *text = format!("@{}", label);
+            } else if text.starts_with('@') {
+                text.insert(0, '@');
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| to_references(item, labels)),
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|item| to_references(item, labels)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Follows {
+        target: StableId,
+        tag: String,
+    }
+    impl Component for Follows {}
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Leader;
+    impl Component for Leader {}
+
+    fn register() {
+        let registry = ComponentRegistry::global();
+        registry.register_json::<Follows>("scene::Follows");
+        registry.register_json::<Leader>("scene::Leader");
+    }
+
+    #[test]
+    fn test_scene_spawn_resolves_references() {
+        register();
+        let scene = Scene::from_toml(
+            r#"
+            [wolf]
+            "scene::Follows" = { target = "@alpha", tag = "@@pack" }
+
+            [alpha]
+            "scene::Leader" = {}
+            "#,
+        )
+        .unwrap();
+        let labels: Vec<_> = scene.entities().iter().map(SceneEntity::label).collect();
+        assert_eq!(labels, ["wolf", "alpha"]);
+
+        let mut world = World::new();
+        let spawned = scene.spawn(&mut world).unwrap();
+        let alpha = world.get_stable_id(spawned["alpha"]).unwrap();
+        assert_eq!(world.get::<Leader>(spawned["alpha"]), Some(&Leader));
+        assert_eq!(
+            world.get::<Follows>(spawned["wolf"]),
+            Some(&Follows {
+                target: alpha,
+                tag: "@pack".to_string()
+            })
+        );
+
+        // Captured scenes turn the stable ID back into a reference
+        let text = Scene::from_world(&world).to_toml().unwrap();
+        let alpha_label = Scene::from_toml(&text)
+            .unwrap()
+            .entities()
+            .iter()
+            .find(|entity| entity.id() == Some(alpha))
+            .map(|entity| entity.label().to_string())
+            .unwrap();
+        assert!(
+            text.contains(&format!("target = \"@{}\"", alpha_label)),
+            "{}",
+            text
+        );
+        assert!(text.contains("tag = \"@@pack\""), "{}", text);
+        assert!(text.contains("\"scene::Leader\" = {}"), "{}", text);
+    }
+
+    #[test]
+    fn test_scene_spawn_failure_despawns() {
+        register();
+        let scene = Scene::from_toml(
+            r#"
+            [a]
+            [b]
+            "scene::Follows" = { target = "@missing", tag = "" }
+            "#,
+        )
+        .unwrap();
+
+        let mut world = World::new();
+        let error = scene.spawn(&mut world).unwrap_err();
+        assert!(error.to_string().contains("@missing"), "{}", error);
+        assert_eq!(
+            error.context().unwrap().component.as_deref(),
+            Some("b.scene::Follows")
+        );
+        assert!(world.is_empty());
+    }
+
+    #[test]
+    fn test_scene_spawn_with_params() {
+        register();
+        let prefab = Scene::from_toml(
+            r#"
+            [alpha]
+            [wolf]
+            "scene::Follows" = { target = "{leader}", tag = "pack_{pack}" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            prefab.parameters().into_iter().collect::<Vec<_>>(),
+            ["leader", "pack"]
+        );
+
+        let mut world = World::new();
+        let params = SceneParams::new().with("leader", "@alpha").with("pack", 7);
+        let spawned = prefab.spawn_with_params(&mut world, &params).unwrap();
+        let alpha = world.get_stable_id(spawned["alpha"]).unwrap();
+        assert_eq!(
+            world.get::<Follows>(spawned["wolf"]),
+            Some(&Follows {
+                target: alpha,
+                tag: "pack_7".to_string()
+            })
+        );
+
+        // Without params the placeholders are left as written
+        let error = prefab.spawn(&mut world).unwrap_err();
+        assert!(matches!(error.root(), PersistenceError::Deserialization(_)));
+        assert_eq!(world.len(), 2);
+    }
+
+    #[test]
+    fn test_scene_push_rejects_duplicate_labels() {
+        let mut scene = Scene::new();
+        scene.push(SceneEntity::new("a")).unwrap();
+        assert!(scene.push(SceneEntity::new("a")).is_err());
+        assert_eq!(scene.len(), 1);
+    }
+}
@@ -0,0 +1,228 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Parameters substituted into scene component values.
+
+use crate::persistence::{PersistenceError, Result};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Named values substituted into a [`Scene`](super::Scene) when it is
+/// spawned with [`spawn_with_params`](super::Scene::spawn_with_params).
+///
+/// A string that is exactly one placeholder, such as `"{spawn_x}"`, is
+/// replaced by the parameter's value, keeping its JSON type, so numbers stay
+/// numbers. Placeholders inside longer strings, such as `"wolf_{index}"`,
+/// are replaced by the parameter's text. Write `{{` and `}}` for literal
+/// braces. Placeholder names are made of ASCII letters, digits and `_`;
+/// other braces are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::persistence::SceneParams;
+///
+/// let params = SceneParams::new().with("spawn_x", 10.0).with("name", "wolf");
+/// assert_eq!(params.get("name"), Some(&"wolf".into()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneParams {
+    values: BTreeMap<String, Value>,
+}
+
+impl SceneParams {
+    /// Creates an empty parameter set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a parameter, returning the set for chaining.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets a parameter, replacing any earlier value.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Returns the value of a parameter.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Replaces the placeholders in the strings of `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::InvalidFormat`] for a placeholder with no
+    /// parameter.
+    pub(super) fn substitute(&self, value: &mut Value) -> Result<()> {
+        match value {
+            Value::String(text) => {
+                if let Some(name) = whole_placeholder(text) {
+                    *value = self.lookup(name)?.clone();
+                } else {
+                    let mut result = Ok(());
+                    let replaced = interpolate(text, |name| match self.lookup(name) {
+                        Ok(Value::String(text)) => text.clone(),
+                        Ok(value) => value.to_string(),
+                        Err(e) => {
+                            result = Err(e);
+                            String::new()
+                        }
+                    });
+                    result?;
+                    *text = replaced;
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.substitute(item)?;
+                }
+            }
+            Value::Object(object) => {
+                for item in object.values_mut() {
+                    self.substitute(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, name: &str) -> Result<&Value> {
+        self.values.get(name).ok_or_else(|| {
+            PersistenceError::InvalidFormat(format!("missing scene parameter {{{}}}", name))
+        })
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for SceneParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = Self::new();
+        for (name, value) in iter {
+            params.set(name, value);
+        }
+        params
+    }
+}
+
+/// Adds the placeholder names used in the strings of `value` to `names`.
+pub(super) fn collect_placeholders(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => {
+            interpolate(text, |name| {
+                names.insert(name.to_string());
+                String::new()
+            });
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_placeholders(item, names)),
+        Value::Object(object) => object
+            .values()
+            .for_each(|item| collect_placeholders(item, names)),
+        _ => {}
+    }
+}
+
+/// Returns the placeholder name if `text` is exactly one placeholder.
+fn whole_placeholder(text: &str) -> Option<&str> {
+    let name = text.strip_prefix('{')?.strip_suffix('}')?;
+    is_name(name).then_some(name)
+}
+
+/// Replaces each placeholder in `text` with `replace(name)` and unescapes
+/// doubled braces.
+fn interpolate(text: &str, mut replace: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}']) {
+        out.push_str(&rest[..index]);
+        let tail = &rest[index..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail
+            .strip_prefix('{')
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]))
+            .filter(|name| is_name(name));
+        match placeholder {
+            Some(name) => {
+                out.push_str(&replace(name));
+                rest = &tail[name.len() + 2..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_substitute() {
+        let params = SceneParams::new()
+            .with("x", 1.5)
+            .with("name", "wolf")
+            .with("index", 3);
+        let mut value = json!({
+            "x": "{x}",
+            "label": "{name}_{index}",
+            "raw": "{{x}} {not a placeholder}",
+            "list": ["{index}"]
+        });
+        params.substitute(&mut value).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "x": 1.5,
+                "label": "wolf_3",
+                "raw": "{x} {not a placeholder}",
+                "list": [3]
+            })
+        );
+
+        let mut missing = json!("{y}");
+        let error = params.substitute(&mut missing).unwrap_err();
+        assert!(error.to_string().contains("{y}"), "{}", error);
+    }
+
+    #[test]
+    fn test_collect_placeholders() {
+        let mut names = BTreeSet::new();
+        collect_placeholders(
+            &json!({ "a": "{x}", "b": ["{y}-{x}", "{{z}}"] }),
+            &mut names,
+        );
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), ["x", "y"]);
+    }
+}
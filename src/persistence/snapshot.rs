@@ -0,0 +1,238 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! In-memory world snapshots.
+//!
+//! A [`WorldSnapshot`] captures every entity and its JSON-registered
+//! components (see [`ComponentRegistry::register_json`]) and can restore
+//! them into a world later. Components of unregistered types are not
+//! captured. Snapshots back the world's time-travel history; see
+//! [`World::enable_history`].
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::entity::StableId;
+use crate::persistence::{PersistenceError, Result};
+use std::collections::VecDeque;
+
+/// The entities and JSON-registered components of a world at one point in
+/// time.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::component::Component;
+/// use pecs::component::registry::ComponentRegistry;
+/// use pecs::persistence::WorldSnapshot;
+/// use pecs::World;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Health(u32);
+/// impl Component for Health {}
+///
+/// ComponentRegistry::global().register_json::<Health>("Health");
+///
+/// let mut world = World::new();
+/// let entity = world.spawn().with(Health(10)).id();
+/// let stable_id = world.get_stable_id(entity).unwrap();
+/// let snapshot = WorldSnapshot::capture(&world);
+///
+/// world.insert(entity, Health(0));
+/// snapshot.restore(&mut world).unwrap();
+///
+/// let entity = world.get_entity_by_stable_id(stable_id).unwrap();
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldSnapshot {
+    /// Entities sorted by stable ID, each with components sorted by name
+    entities: Vec<(StableId, Vec<(String, serde_json::Value)>)>,
+}
+
+impl WorldSnapshot {
+    /// Captures the current state of `world`.
+    pub fn capture(world: &World) -> Self {
+        let registry = ComponentRegistry::global();
+        let names = registry.json_names();
+        let mut entities: Vec<_> = world
+            .iter_entities()
+            .map(|(entity, stable_id)| {
+                let components = names
+                    .iter()
+                    .filter_map(|name| {
+                        registry
+                            .get_json(name, world, entity)
+                            .map(|value| (name.clone(), value))
+                    })
+                    .collect();
+                (stable_id, components)
+            })
+            .collect();
+        entities.sort_by_key(|(stable_id, _)| *stable_id);
+        Self { entities }
+    }
+
+    /// Replaces the entities of `world` with the captured ones.
+    ///
+    /// Resources, indexes and metadata are kept. Entity handles change;
+    /// look entities up again by stable ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a captured component can no longer be inserted,
+    /// e.g. because its JSON registration changed.
+    pub fn restore(&self, world: &mut World) -> Result<()> {
+        let registry = ComponentRegistry::global();
+        world.reset_entities();
+        for (stable_id, components) in &self.entities {
+            let entity = world.spawn_empty_with_stable_id(*stable_id).map_err(|e| {
+                PersistenceError::EntityIdConflict(format!("{}: {:?}", stable_id, e))
+            })?;
+            for (name, value) in components {
+                registry.insert_json(name, world, entity, value.clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the captured components of an entity.
+    pub fn components(&self, entity: StableId) -> Option<&[(String, serde_json::Value)]> {
+        self.entities
+            .binary_search_by_key(&entity, |(stable_id, _)| *stable_id)
+            .ok()
+            .map(|index| self.entities[index].1.as_slice())
+    }
+
+    /// Returns the number of captured entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns true if no entities were captured.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// A bounded ring of per-tick snapshots with a cursor for stepping through
+/// them.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    capacity: usize,
+    snapshots: VecDeque<WorldSnapshot>,
+    /// Index of the snapshot matching the world's current state
+    cursor: usize,
+}
+
+impl History {
+    pub(crate) fn new(capacity: usize, initial: WorldSnapshot) -> Self {
+        let mut snapshots = VecDeque::with_capacity(capacity);
+        snapshots.push_back(initial);
+        Self {
+            capacity,
+            snapshots,
+            cursor: 0,
+        }
+    }
+
+    /// Records a new tick, discarding any ticks ahead of the cursor.
+    pub(crate) fn push(&mut self, snapshot: WorldSnapshot) {
+        self.snapshots.truncate(self.cursor + 1);
+        self.snapshots.push_back(snapshot);
+        if self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.cursor = self.snapshots.len() - 1;
+    }
+
+    /// Moves the cursor back up to `ticks`, returning how far it moved.
+    pub(crate) fn rewind(&mut self, ticks: usize) -> usize {
+        let moved = ticks.min(self.cursor);
+        self.cursor -= moved;
+        moved
+    }
+
+    /// Moves the cursor forward one tick if possible.
+    pub(crate) fn step_forward(&mut self) -> bool {
+        if self.cursor + 1 < self.snapshots.len() {
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The snapshot at the cursor.
+    pub(crate) fn current(&self) -> &WorldSnapshot {
+        &self.snapshots[self.cursor]
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.cursor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entities: usize) -> WorldSnapshot {
+        WorldSnapshot {
+            entities: (0..entities)
+                .map(|index| (StableId::from_u128(index as u128 + 1), Vec::new()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn history_is_bounded_and_branches() {
+        let mut history = History::new(3, snapshot(0));
+        for entities in 1..=4 {
+            history.push(snapshot(entities));
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.current().len(), 4);
+
+        assert_eq!(history.rewind(5), 2);
+        assert_eq!(history.current().len(), 2);
+        assert!(history.step_forward());
+        assert_eq!(history.current().len(), 3);
+
+        history.push(snapshot(7));
+        assert_eq!(history.len(), 3);
+        assert!(!history.step_forward());
+        assert_eq!(history.current().len(), 7);
+    }
+
+    #[test]
+    fn restore_replaces_entities() {
+        let mut world = World::new();
+        let kept = world.spawn_empty();
+        let kept = world.get_stable_id(kept).unwrap();
+        let captured = WorldSnapshot::capture(&world);
+        world.spawn_empty();
+
+        captured.restore(&mut world).unwrap();
+        assert_eq!(world.len(), 1);
+        assert!(world.get_entity_by_stable_id(kept).is_some());
+        assert_eq!(captured.components(kept), Some(&[][..]));
+    }
+}
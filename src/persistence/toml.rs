@@ -0,0 +1,107 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! TOML scene persistence plugin.
+//!
+//! Saves and loads worlds as [`Scene`] documents, for content authored and
+//! reviewed by hand. Only entities and their JSON-registered components are
+//! stored; world metadata and resources are not. See [`scene`](super::scene)
+//! for the format.
+
+pub(crate) mod parse;
+pub(crate) mod write;
+
+use crate::World;
+use crate::persistence::{PersistenceError, PersistencePlugin, Result, Scene};
+use std::io::{Read, Write};
+
+/// TOML scene persistence plugin.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::persistence::{PersistencePlugin, TomlPlugin};
+///
+/// let mut world = World::new();
+/// world.spawn_empty();
+///
+/// let plugin = TomlPlugin::new();
+/// let mut buffer = Vec::new();
+/// plugin.save(&world, &mut buffer).unwrap();
+///
+/// let loaded = plugin.load(&mut buffer.as_slice()).unwrap();
+/// assert_eq!(loaded.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TomlPlugin;
+
+impl TomlPlugin {
+    /// Creates a new TOML plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PersistencePlugin for TomlPlugin {
+    fn save(&self, world: &World, writer: &mut dyn Write) -> Result<()> {
+        let text = Scene::from_world(world).to_toml()?;
+        writer
+            .write_all(text.as_bytes())
+            .map_err(PersistenceError::Io)
+    }
+
+    fn load(&self, reader: &mut dyn Read) -> Result<World> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(PersistenceError::Io)?;
+
+        let mut world = World::new();
+        Scene::from_toml(&text)?.spawn(&mut world)?;
+        Ok(world)
+    }
+
+    fn format_name(&self) -> &str {
+        "toml"
+    }
+
+    fn format_version(&self) -> u32 {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_plugin_keeps_stable_ids() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        let stable_id = world.get_stable_id(entity).unwrap();
+
+        let plugin = TomlPlugin::new();
+        let mut buffer = Vec::new();
+        plugin.save(&world, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.starts_with("[entity_0]\nid = "), "{}", text);
+
+        let loaded = plugin.load(&mut text.as_bytes()).unwrap();
+        assert!(loaded.get_entity_by_stable_id(stable_id).is_some());
+        assert_eq!(plugin.format_name(), "toml");
+    }
+}
@@ -0,0 +1,434 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Parser for the TOML subset used by scene files.
+//!
+//! Supported: comments, `[table]` headers with dotted and quoted keys,
+//! `key = value` pairs with dotted keys, basic and literal strings,
+//! integers (decimal, `0x`, `0o` and `0b`), floats, booleans, arrays and
+//! inline tables. Multi-line strings, dates and arrays of tables are
+//! rejected.
+
+use crate::persistence::{PersistenceError, Result};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Parses a TOML document into a JSON object.
+///
+/// Also returns the top-level keys in the order they first appear, since
+/// the object itself is sorted.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::InvalidFormat`] naming the line of the first
+/// syntax error, duplicate key or unsupported construct.
+pub(crate) fn parse(input: &str) -> Result<(Map<String, Value>, Vec<String>)> {
+    Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        line: 1,
+    }
+    .document()
+}
+
+/// A recursive-descent parser over the characters of a document.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn document(mut self) -> Result<(Map<String, Value>, Vec<String>)> {
+        let mut root = Map::new();
+        let mut order = Vec::new();
+        let mut table: Vec<String> = Vec::new();
+        let mut headers = HashSet::new();
+
+        loop {
+            self.skip_blank_lines();
+            let Some(c) = self.peek() else {
+                return Ok((root, order));
+            };
+            if c == '[' {
+                self.pos += 1;
+                if self.peek() == Some('[') {
+                    return Err(self.error("arrays of tables are not supported"));
+                }
+                self.skip_spaces();
+                table = self.dotted_key()?;
+                self.skip_spaces();
+                self.expect(']')?;
+                if !headers.insert(table.clone()) {
+                    return Err(self.error(format!("table [{}] defined twice", table.join("."))));
+                }
+                if !root.contains_key(&table[0]) {
+                    order.push(table[0].clone());
+                }
+                self.table_at(&mut root, &table)?;
+            } else {
+                let key = self.dotted_key()?;
+                self.skip_spaces();
+                self.expect('=')?;
+                self.skip_spaces();
+                let value = self.value()?;
+                let (last, parents) = key.split_last().expect("keys are never empty");
+                let path: Vec<String> = table.iter().chain(parents).cloned().collect();
+                let first = path.first().unwrap_or(last);
+                if !root.contains_key(first) {
+                    order.push(first.clone());
+                }
+                let target = self.table_at(&mut root, &path)?;
+                if target.insert(last.clone(), value).is_some() {
+                    return Err(self.error(format!("duplicate key {:?}", last)));
+                }
+            }
+            self.end_of_line()?;
+        }
+    }
+
+    /// Returns the table at `path`, creating missing tables on the way.
+    fn table_at<'m>(
+        &self,
+        root: &'m mut Map<String, Value>,
+        path: &[String],
+    ) -> Result<&'m mut Map<String, Value>> {
+        let mut table = root;
+        for key in path {
+            table = table
+                .entry(key.clone())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .ok_or_else(|| self.error(format!("{:?} is not a table", key)))?;
+        }
+        Ok(table)
+    }
+
+    fn dotted_key(&mut self) -> Result<Vec<String>> {
+        let mut keys = vec![self.key()?];
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(keys);
+            }
+            self.pos += 1;
+            self.skip_spaces();
+            keys.push(self.key()?);
+        }
+    }
+
+    fn key(&mut self) -> Result<String> {
+        match self.peek() {
+            Some('"') => self.basic_string(),
+            Some('\'') => self.literal_string(),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(self.error("expected a key"));
+                }
+                Ok(self.chars[start..self.pos].iter().collect())
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some('"') => self.basic_string().map(Value::String),
+            Some('\'') => self.literal_string().map(Value::String),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some(_) => self.scalar(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        if self.peek() == Some('"') && self.chars.get(self.pos + 1) == Some(&'"') {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('u') => self.unicode_escape(4)?,
+                        Some('U') => self.unicode_escape(8)?,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    text.push(escaped);
+                }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self, digits: usize) -> Result<char> {
+        let end = self.pos + digits;
+        let hex: String = self
+            .chars
+            .get(self.pos..end)
+            .unwrap_or_default()
+            .iter()
+            .collect();
+        self.pos = end.min(self.chars.len());
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .filter(|_| hex.len() == digits)
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn literal_string(&mut self) -> Result<String> {
+        self.expect('\'')?;
+        if self.peek() == Some('\'') && self.chars.get(self.pos + 1) == Some(&'\'') {
+            return Err(self.error("multi-line strings are not supported"));
+        }
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(text),
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank_lines();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value> {
+        self.expect('{')?;
+        let mut table = Map::new();
+        self.skip_spaces();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Object(table));
+        }
+        loop {
+            self.skip_spaces();
+            let key = self.dotted_key()?;
+            self.skip_spaces();
+            self.expect('=')?;
+            self.skip_spaces();
+            let value = self.value()?;
+            let (last, parents) = key.split_last().expect("keys are never empty");
+            let target = self.table_at(&mut table, parents)?;
+            if target.insert(last.clone(), value).is_some() {
+                return Err(self.error(format!("duplicate key {:?}", last)));
+            }
+            self.skip_spaces();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(table)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// Parses a boolean or number.
+    fn scalar(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | '_'))
+        {
+            self.pos += 1;
+        }
+        let token: String = self.chars[start..self.pos].iter().collect();
+        let digits = token.replace('_', "");
+        let (sign, unsigned) = match digits.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, digits.strip_prefix('+').unwrap_or(&digits)),
+        };
+        let radix = match unsigned.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            Some("0b") => Some(2),
+            _ => None,
+        };
+
+        let value = match token.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ if token.is_empty() || token.contains("__") => None,
+            _ => match radix {
+                Some(radix) if sign > 0 => i64::from_str_radix(&unsigned[2..], radix)
+                    .ok()
+                    .map(Value::from),
+                Some(_) => None,
+                None if digits.contains(['.', 'e', 'E']) => digits
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number),
+                None => digits.parse::<i64>().ok().map(Value::from),
+            },
+        };
+        value.ok_or_else(|| self.error(format!("unsupported value {:?}", token)))
+    }
+
+    /// Skips spaces, tabs, comments, newlines and blank lines.
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('\n') => {
+                    self.pos += 1;
+                    self.line += 1;
+                }
+                Some('\r') if self.chars.get(self.pos + 1) == Some(&'\n') => self.pos += 1,
+                _ => return,
+            }
+        }
+    }
+
+    /// Skips spaces, tabs and a trailing comment.
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+        if self.peek() == Some('#') {
+            while self.peek().is_some_and(|c| c != '\n') {
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<()> {
+        self.skip_spaces();
+        if self.peek() == Some('\r') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            None | Some('\n') => Ok(()),
+            Some(_) => Err(self.error("expected end of line")),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", expected)))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> PersistenceError {
+        PersistenceError::InvalidFormat(format!("line {}: {}", self.line, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_tables_and_values() {
+        let (document, order) = parse(
+            r#"
+            # A scene
+            [player]
+            id = "550e8400-e29b-41d4-a716-446655440000"
+            Tags = ["hero", 'literal\n', ]
+            Position = { x = 1.5, y = -2 } # inline
+
+            [player."game::Health"]
+            current = 1_000
+            flags.alive = true
+            mask = 0xff
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(order, ["player"]);
+        assert_eq!(
+            Value::Object(document),
+            json!({
+                "player": {
+                    "id": "550e8400-e29b-41d4-a716-446655440000",
+                    "Tags": ["hero", "literal\\n"],
+                    "Position": { "x": 1.5, "y": -2 },
+                    "game::Health": {
+                        "current": 1000,
+                        "flags": { "alive": true },
+                        "mask": 255
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let error = parse("[a]\nx = 1\nx = 2\n").unwrap_err();
+        assert!(error.to_string().contains("line 3"), "{}", error);
+
+        for unsupported in [
+            "[[a]]",
+            "a = 1979-05-27",
+            "a = \"\"\"text\"\"\"",
+            "a = nan",
+            "[a]\n[a]",
+        ] {
+            assert!(parse(unsupported).is_err(), "{}", unsupported);
+        }
+    }
+}
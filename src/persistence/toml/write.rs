@@ -0,0 +1,186 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Writer for the TOML subset used by scene files.
+
+use crate::persistence::{PersistenceError, Result};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// Writes a JSON object as a TOML document.
+///
+/// Objects nested up to `depth` levels below the root become `[table]`
+/// sections; deeper objects are written as inline tables. `null` object
+/// fields are left out, since TOML has no null, and an empty object at the
+/// table level is written inline so it still appears in the document.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::Serialization`] for values TOML can't
+/// represent, such as `null` array items.
+pub(crate) fn write(document: &Map<String, Value>, depth: usize) -> Result<String> {
+    let mut out = String::new();
+    write_table(&mut out, &mut Vec::new(), document, depth)?;
+    Ok(out)
+}
+
+fn write_table(
+    out: &mut String,
+    path: &mut Vec<String>,
+    table: &Map<String, Value>,
+    depth: usize,
+) -> Result<()> {
+    let is_section =
+        |value: &Value| depth > 0 && value.as_object().is_some_and(|object| !object.is_empty());
+
+    // Plain keys must come before any sub-table header
+    for (key, value) in table {
+        if value.is_null() || is_section(value) {
+            continue;
+        }
+        let _ = write!(out, "{} = ", key_text(key));
+        write_value(out, value)?;
+        out.push('\n');
+    }
+
+    for (key, value) in table {
+        let Some(object) = value.as_object().filter(|_| is_section(value)) else {
+            continue;
+        };
+        path.push(key_text(key));
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "[{}]", path.join("."));
+        write_table(out, path, object, depth - 1)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => {
+            return Err(PersistenceError::Serialization(
+                "TOML can't represent null array items".to_string(),
+            ));
+        }
+        Value::Bool(flag) => {
+            let _ = write!(out, "{}", flag);
+        }
+        Value::Number(number) => match number.as_f64().filter(|_| number.is_f64()) {
+            // Debug keeps the decimal point TOML needs to read it back as a float
+            Some(float) => {
+                let _ = write!(out, "{:?}", float);
+            }
+            None => {
+                let _ = write!(out, "{}", number);
+            }
+        },
+        Value::String(text) => out.push_str(&string_text(text)),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(object) => {
+            out.push('{');
+            let mut first = true;
+            for (key, value) in object.iter().filter(|(_, value)| !value.is_null()) {
+                out.push_str(if first { " " } else { ", " });
+                first = false;
+                let _ = write!(out, "{} = ", key_text(key));
+                write_value(out, value)?;
+            }
+            out.push_str(if first { "}" } else { " }" });
+        }
+    }
+    Ok(())
+}
+
+/// Returns `key` bare if TOML allows it, quoted otherwise.
+fn key_text(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        string_text(key)
+    }
+}
+
+/// Returns `text` as a basic string.
+fn string_text(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::toml::parse::parse;
+    use serde_json::json;
+
+    #[test]
+    fn test_write_round_trips() {
+        let document = json!({
+            "player": {
+                "id": "550e8400-e29b-41d4-a716-446655440000",
+                "Marker": {},
+                "game::Health": { "current": 10, "regen": 0.5, "buff": null },
+                "Path": { "points": [{ "x": 1.0 }, { "x": 2.0 }] },
+                "Name": "quote \" and \\ slash"
+            }
+        });
+        let Value::Object(document) = document else {
+            unreachable!()
+        };
+
+        let text = write(&document, 2).unwrap();
+        assert!(text.contains("[player.\"game::Health\"]"), "{}", text);
+        assert!(text.contains("Marker = {}"), "{}", text);
+        assert!(text.contains("x = 1.0"), "{}", text);
+
+        let mut expected = document.clone();
+        expected["player"]["game::Health"]
+            .as_object_mut()
+            .unwrap()
+            .remove("buff");
+        assert_eq!(parse(&text).unwrap().0, expected);
+    }
+}
@@ -0,0 +1,129 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Passthrough storage for components this build does not know.
+//!
+//! When a save is loaded with
+//! [`UnknownComponent::Preserve`](crate::persistence::UnknownComponent::Preserve),
+//! components whose type is not registered are kept as raw bytes in an
+//! [`UnknownComponents`] component on their entity, and the binary format
+//! writes them back out on save. A stripped-down tool can then edit a save
+//! without destroying the data it does not understand.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//! use pecs::persistence::unknown::{OpaqueComponent, UnknownComponents};
+//!
+//! let mut world = World::new();
+//! let entity = world.spawn_empty();
+//! let mut unknown = UnknownComponents::default();
+//! unknown.push(OpaqueComponent {
+//!     name: "game::Mana".to_string(),
+//!     version: 1,
+//!     data: br#"{"current":3}"#.to_vec(),
+//! });
+//! world.insert(entity, unknown);
+//!
+//! let mut buffer = Vec::new();
+//! world.save_binary(&mut buffer).unwrap();
+//! ```
+
+use crate::component::Component;
+use crate::component::registry::{ComponentRegistration, ComponentRegistry, ComponentSchema};
+use crate::persistence::binary::format::registered_type_id;
+
+/// A saved component kept as raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpaqueComponent {
+    /// The type name recorded in the save
+    pub name: String,
+    /// The type version recorded in the save
+    pub version: u32,
+    /// The component payload, exactly as saved
+    pub data: Vec<u8>,
+}
+
+/// Components of an entity that this build could not decode, preserved for
+/// writing back out.
+///
+/// The type itself is transient: it never appears in a save under its own
+/// name, only as the components it holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownComponents {
+    components: Vec<OpaqueComponent>,
+}
+
+impl Component for UnknownComponents {}
+
+const _: () = {
+    fn register(registry: &ComponentRegistry) {
+        registry.register_schema::<UnknownComponents>(
+            ComponentSchema::new("pecs::UnknownComponents", 1).with_transient(true),
+        );
+    }
+
+    inventory::submit! {
+        ComponentRegistration::new(register)
+    }
+};
+
+impl UnknownComponents {
+    /// Adds a preserved component.
+    pub fn push(&mut self, component: OpaqueComponent) {
+        self.components.push(component);
+    }
+
+    /// Returns the preserved component with the given type name.
+    pub fn get(&self, name: &str) -> Option<&OpaqueComponent> {
+        self.components
+            .iter()
+            .find(|component| component.name == name)
+    }
+
+    /// Removes and returns the preserved component with the given type name.
+    pub fn remove(&mut self, name: &str) -> Option<OpaqueComponent> {
+        let index = self
+            .components
+            .iter()
+            .position(|component| component.name == name)?;
+        Some(self.components.remove(index))
+    }
+
+    /// Returns the preserved components, in load order.
+    pub fn iter(&self) -> impl Iterator<Item = &OpaqueComponent> {
+        self.components.iter()
+    }
+
+    /// Returns the number of preserved components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns `true` if nothing is preserved.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Derives the binary type ID written for a preserved component type.
+///
+/// The top bit is set so the IDs never collide with those of
+/// [registered types](registered_type_id), which fit in 64 bits.
+pub(crate) fn opaque_type_id(name: &str) -> u128 {
+    (1 << 127) | registered_type_id(name)
+}
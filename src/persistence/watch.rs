@@ -0,0 +1,303 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Hot-reload support for save and scene files.
+//!
+//! [`watch`] monitors a file on disk and reloads it with a persistence
+//! plugin whenever it changes, producing a [`WorldDiff`] against the
+//! previously loaded version. Diffs can be applied to a running world,
+//! which enables live editing: save the scene in an editor and the game
+//! picks up new entities and edited component values without restarting.
+//!
+//! Requires the `notify` feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use pecs::World;
+//! use pecs::persistence::{JsonPlugin, watch};
+//!
+//! let mut world = World::load("scene.json")?;
+//! let watcher = watch("scene.json", Box::new(JsonPlugin::new()))?;
+//!
+//! loop {
+//!     // Once per frame
+//!     watcher.apply_pending(&mut world)?;
+//!     # break;
+//! }
+//! # Ok::<(), pecs::persistence::PersistenceError>(())
+//! ```
+
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::World;
+use crate::persistence::diff::{WorldState, capture};
+use crate::persistence::{PersistenceError, PersistencePlugin, Result, WorldDiff};
+
+/// A change observed by a [`WorldWatcher`].
+#[derive(Debug)]
+pub enum ReloadEvent {
+    /// The file was reloaded and its entities or their component values
+    /// differ from the last version
+    Changed(WorldDiff),
+    /// The file changed but could not be loaded, e.g. mid-write
+    Failed(PersistenceError),
+}
+
+/// Watches a save file and reports changes as [`ReloadEvent`]s.
+///
+/// Created by [`watch`]. Watching stops when the watcher is dropped.
+/// Iterating blocks until the next event.
+pub struct WorldWatcher {
+    path: PathBuf,
+    events: Receiver<ReloadEvent>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WorldWatcher {
+    /// Returns the watched path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the next event if one is pending, without blocking.
+    pub fn try_next(&self) -> Option<ReloadEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    pub fn next_timeout(&self, timeout: Duration) -> Option<ReloadEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// Applies every pending diff to `world`.
+    ///
+    /// Returns the number of entities spawned, despawned or updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failed reload or diff. Diffs received
+    /// before it have already been applied; later events stay queued.
+    pub fn apply_pending(&self, world: &mut World) -> Result<usize> {
+        let mut changed = 0;
+        while let Some(event) = self.try_next() {
+            match event {
+                ReloadEvent::Changed(diff) => changed += diff.apply(world)?,
+                ReloadEvent::Failed(error) => return Err(error),
+            }
+        }
+        Ok(changed)
+    }
+}
+
+impl Iterator for WorldWatcher {
+    type Item = ReloadEvent;
+
+    fn next(&mut self) -> Option<ReloadEvent> {
+        self.events.recv().ok()
+    }
+}
+
+impl std::fmt::Debug for WorldWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldWatcher")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Starts watching `path`, reloading it with `plugin` on every change.
+///
+/// The file is loaded once up front (if it exists) to establish the
+/// baseline that later diffs are computed against. The containing directory
+/// is watched rather than the file itself, so editors that save by
+/// replacing the file are handled. Reloads that leave the entities and
+/// their component values unchanged produce no event.
+///
+/// # Errors
+///
+/// Returns an error if the initial load fails or the file system watcher
+/// cannot be started.
+pub fn watch(path: impl AsRef<Path>, plugin: Box<dyn PersistencePlugin>) -> Result<WorldWatcher> {
+    let path = path.as_ref().to_path_buf();
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| PersistenceError::Custom(format!("not a file: {}", path.display())))?
+        .to_os_string();
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let mut previous = match load(&path, plugin.as_ref())? {
+        Some(world) => capture(&world),
+        None => WorldState::new(),
+    };
+
+    let (sender, events) = channel();
+    let reload_path = path.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(error) => {
+                let _ = sender.send(ReloadEvent::Failed(watch_error(error)));
+                return;
+            }
+        };
+        let relevant = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event
+                .paths
+                .iter()
+                .any(|changed| changed.file_name() == Some(file_name.as_os_str()));
+        if relevant {
+            reload(&reload_path, plugin.as_ref(), &mut previous, &sender);
+        }
+    })
+    .map_err(watch_error)?;
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+
+    Ok(WorldWatcher {
+        path,
+        events,
+        _watcher: watcher,
+    })
+}
+
+/// Reloads the file and sends the diff against `previous`, if any.
+fn reload(
+    path: &Path,
+    plugin: &dyn PersistencePlugin,
+    previous: &mut WorldState,
+    sender: &Sender<ReloadEvent>,
+) {
+    match load(path, plugin) {
+        Ok(Some(world)) => {
+            let current = capture(&world);
+            let diff = WorldDiff::from_states(previous, &current);
+            if !diff.is_empty() {
+                *previous = current;
+                let _ = sender.send(ReloadEvent::Changed(diff));
+            }
+        }
+        Ok(None) => {}
+        Err(error) => {
+            let _ = sender.send(ReloadEvent::Failed(error));
+        }
+    }
+}
+
+/// Loads the file, returning `None` if it does not exist.
+fn load(path: &Path, plugin: &dyn PersistencePlugin) -> Result<Option<World>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(PersistenceError::Io(error)),
+    };
+    plugin.load(&mut file).map(Some)
+}
+
+fn watch_error(error: notify::Error) -> PersistenceError {
+    PersistenceError::Custom(format!("file watcher failed: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::JsonPlugin;
+
+    #[test]
+    fn reload_reports_new_entities() {
+        let directory = std::env::temp_dir().join("pecs_watch_test");
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("scene.json");
+
+        let mut world = World::new();
+        world.spawn_empty();
+        world.save_json(&mut File::create(&path).unwrap()).unwrap();
+
+        let watcher = watch(&path, Box::new(JsonPlugin::new())).unwrap();
+        let mut running = World::load_json(&mut File::open(&path).unwrap()).unwrap();
+
+        let added = world.spawn_empty();
+        let added_id = world.get_stable_id(added).unwrap();
+        world.save_json(&mut File::create(&path).unwrap()).unwrap();
+
+        // Writes may be reported in several events; wait for the diff
+        let mut diff = None;
+        while let Some(event) = watcher.next_timeout(Duration::from_secs(5)) {
+            if let ReloadEvent::Changed(changed) = event {
+                diff = Some(changed);
+                break;
+            }
+        }
+        let diff = diff.expect("no reload event");
+        assert_eq!(diff.spawned, vec![added_id]);
+
+        diff.apply(&mut running).unwrap();
+        assert_eq!(running.len(), 2);
+        let _ = std::fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn reload_reports_edited_values() {
+        use crate::component::registry::ComponentRegistry;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Brightness(u8);
+        impl crate::Component for Brightness {}
+
+        ComponentRegistry::global().register_json::<Brightness>("watch::Brightness");
+
+        let directory = std::env::temp_dir().join("pecs_watch_edit_test");
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("scene.json");
+
+        let mut world = World::new();
+        let lamp = world.spawn().with(Brightness(10)).id();
+        world.save_json(&mut File::create(&path).unwrap()).unwrap();
+
+        let watcher = watch(&path, Box::new(JsonPlugin::new())).unwrap();
+        let mut running = World::load_json(&mut File::open(&path).unwrap()).unwrap();
+
+        // Only the value changes; the entity set stays the same
+        world.get_mut::<Brightness>(lamp).unwrap().0 = 90;
+        world.save_json(&mut File::create(&path).unwrap()).unwrap();
+
+        let mut diff = None;
+        while let Some(event) = watcher.next_timeout(Duration::from_secs(5)) {
+            if let ReloadEvent::Changed(changed) = event {
+                diff = Some(changed);
+                break;
+            }
+        }
+        let diff = diff.expect("no reload event");
+        assert!(diff.spawned.is_empty() && diff.despawned.is_empty());
+
+        assert_eq!(diff.apply(&mut running).unwrap(), 1);
+        let stable_id = world.get_stable_id(lamp).unwrap();
+        let lamp = running.get_entity_id(stable_id).unwrap();
+        assert_eq!(running.get::<Brightness>(lamp), Some(&Brightness(90)));
+        let _ = std::fs::remove_dir_all(directory);
+    }
+}
@@ -42,14 +42,19 @@
 //! }
 //! ```
 
+pub mod access;
 pub mod fetch;
 pub mod filter;
 pub mod iter;
+pub mod plan;
 mod query_impl;
 
-use crate::entity::EntityId;
+use crate::entity::{EntityId, EntityManager, StableId};
 use std::marker::PhantomData;
 
+pub use access::Access;
+pub use plan::{ArchetypeMatch, ArchetypePlan, QueryPlan};
+
 /// A query that fetches data from the world.
 ///
 /// Queries are type-safe and composable, allowing you to specify exactly
@@ -69,6 +74,39 @@ pub trait Query {
     type Filter: for<'a> Filter<'a>;
 }
 
+/// World data available to fetches besides the archetype being iterated.
+///
+/// Queries created through [`World`](crate::World) carry the world's entity
+/// manager, which backs `&StableId` fetches. Iterators created directly
+/// from an archetype manager have an empty context.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchContext<'a> {
+    entities: Option<&'a EntityManager>,
+}
+
+impl<'a> FetchContext<'a> {
+    /// Creates a context backed by an entity manager.
+    pub fn new(entities: &'a EntityManager) -> Self {
+        Self {
+            entities: Some(entities),
+        }
+    }
+
+    /// Borrows the stable ID of an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context has no entity manager, or the entity has no
+    /// stable ID.
+    #[inline]
+    pub fn stable_id(&self, entity: EntityId) -> &'a StableId {
+        self.entities
+            .expect("`&StableId` queries need a query created through `World`")
+            .stable_id_ref(entity)
+            .expect("queried entity must have a stable ID")
+    }
+}
+
 /// Trait for fetching component data from archetypes.
 ///
 /// This trait is implemented for various component access patterns,
@@ -82,6 +120,9 @@ pub trait Fetch<'a> {
 
     /// Fetches data for a specific entity.
     ///
+    /// `context` gives access to world data outside the archetype, such as
+    /// stable IDs.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that:
@@ -91,9 +132,41 @@ pub trait Fetch<'a> {
     unsafe fn fetch(
         archetype: &'a crate::component::archetype::Archetype,
         entity: EntityId,
+        context: FetchContext<'a>,
     ) -> Self::Item;
+
+    /// Records the component types this fetch reads and writes.
+    ///
+    /// The default records nothing, which is correct for fetches that do
+    /// not touch component data. Query iterators check the recorded access
+    /// on construction and panic if a component is written alongside
+    /// another access to it, so fetches that write must record it here.
+    fn access(_access: &mut Access) {}
+
+    /// Adds the names of the components this fetch needs but `archetype`
+    /// lacks to `missing`, for [`QueryPlan`].
+    ///
+    /// The default names the whole fetch if it does not match.
+    fn missing_components(
+        archetype: &crate::component::archetype::Archetype,
+        missing: &mut Vec<String>,
+    ) {
+        if !Self::matches_archetype(archetype) {
+            missing.push(plan::short_type_name(std::any::type_name::<Self>()));
+        }
+    }
 }
 
+/// Marker for fetches that never hand out mutable references.
+///
+/// Several items of a read-only fetch can be held at once, which is what
+/// lets [`Combinations`](iter::Combinations) implement `Iterator` for them.
+///
+/// # Safety
+///
+/// Implementors must not produce mutable access to component data.
+pub unsafe trait ReadOnlyFetch: for<'a> Fetch<'a> {}
+
 /// Trait for filtering which entities to include in a query.
 ///
 /// Filters allow you to narrow down query results based on component
@@ -101,6 +174,68 @@ pub trait Fetch<'a> {
 pub trait Filter<'a> {
     /// Checks if an entity passes this filter.
     fn matches(archetype: &crate::component::archetype::Archetype, entity: EntityId) -> bool;
+
+    /// Returns `true` if this filter asks for
+    /// [`Disabled`](crate::component::Disabled) entities, which queries
+    /// skip otherwise.
+    ///
+    /// The default is `false`. [`Including<Disabled>`](filter::Including)
+    /// and [`With<Disabled>`](filter::With) return `true`, and combinators
+    /// return `true` if any of their parts does.
+    fn includes_disabled() -> bool {
+        false
+    }
+
+    /// Checks if an entity passes this filter, adding the name of each part
+    /// that rejects it to `rejected`, for [`QueryPlan`].
+    ///
+    /// The default names the whole filter. Combinators report their
+    /// rejecting parts instead.
+    fn explain(
+        archetype: &crate::component::archetype::Archetype,
+        entity: EntityId,
+        rejected: &mut Vec<String>,
+    ) -> bool {
+        let matches = Self::matches(archetype, entity);
+        if !matches {
+            rejected.push(plan::short_type_name(std::any::type_name::<Self>()));
+        }
+        matches
+    }
+}
+
+/// A query over alternative components.
+///
+/// `AnyOf<(&A, &B, &C)>` matches entities that have at least one of the
+/// listed components and yields a tuple of `Option`s, with `Some` for each
+/// component the entity has. This suits systems that handle a family of
+/// alternative component types, such as several kinds of renderable.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::query::AnyOf;
+///
+/// struct Sprite(u32);
+/// impl Component for Sprite {}
+/// struct Mesh(u32);
+/// impl Component for Mesh {}
+///
+/// let mut world = World::new();
+/// world.spawn().with(Sprite(1)).id();
+/// world.spawn().with(Mesh(2)).id();
+/// world.spawn_empty();
+///
+/// let mut drawn = 0;
+/// for (sprite, mesh) in world.query::<AnyOf<(&Sprite, &Mesh)>>() {
+///     assert!(sprite.is_some() || mesh.is_some());
+///     drawn += 1;
+/// }
+/// assert_eq!(drawn, 2);
+/// ```
+pub struct AnyOf<T> {
+    _phantom: PhantomData<T>,
 }
 
 /// A query builder that allows composing queries with filters.
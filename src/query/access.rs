@@ -0,0 +1,307 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Component access descriptions for queries.
+//!
+//! An [`Access`] lists the component types a query reads and writes. Code
+//! that runs several queries at once, such as a system scheduler, uses it to
+//! check that no component is borrowed mutably twice. Query iterators use
+//! it to reject a single query that does so, such as
+//! `(&mut Position, &Position)`.
+
+use crate::component::{Component, ComponentTypeId};
+
+/// The component types a query reads and writes.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::component::Component;
+/// use pecs::query::Access;
+///
+/// struct Position;
+/// impl Component for Position {}
+/// struct Velocity;
+/// impl Component for Velocity {}
+///
+/// let movement = Access::of::<(&mut Position, &Velocity)>();
+/// let render = Access::of::<&Position>();
+/// let physics = Access::of::<&mut Velocity>();
+///
+/// assert!(movement.is_compatible(&Access::of::<&Velocity>()));
+/// assert!(!movement.is_compatible(&render));
+/// assert_eq!(movement.conflicts(&physics).len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Access {
+    reads: Vec<AccessEntry>,
+    writes: Vec<AccessEntry>,
+    aliased: Vec<AccessEntry>,
+}
+
+/// A component type and its name, kept for error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AccessEntry {
+    component_type: ComponentTypeId,
+    name: &'static str,
+}
+
+impl Access {
+    /// Creates an empty access set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the access of query `Q`.
+    pub fn of<Q: super::Query>() -> Self {
+        let mut access = Self::new();
+        <Q::Fetch as super::Fetch<'static>>::access(&mut access);
+        access
+    }
+
+    /// Records a shared read of component type `T`.
+    ///
+    /// Reading a type that is already written marks it aliased.
+    pub fn add_read<T: Component>(&mut self) {
+        let entry = Self::entry::<T>();
+        if self.writes.contains(&entry) {
+            Self::push(&mut self.aliased, entry);
+        }
+        Self::push(&mut self.reads, entry);
+    }
+
+    /// Records an exclusive write of component type `T`.
+    ///
+    /// Writing a type that is already read or written marks it aliased.
+    pub fn add_write<T: Component>(&mut self) {
+        let entry = Self::entry::<T>();
+        if self.writes.contains(&entry) || self.reads.contains(&entry) {
+            Self::push(&mut self.aliased, entry);
+        }
+        Self::push(&mut self.writes, entry);
+    }
+
+    /// Adds everything `other` accesses.
+    ///
+    /// Only the aliasing within `other` carries over; overlap between
+    /// `self` and `other` is what [`conflicts`](Self::conflicts) reports.
+    pub fn extend(&mut self, other: &Access) {
+        for &read in &other.reads {
+            Self::push(&mut self.reads, read);
+        }
+        for &write in &other.writes {
+            Self::push(&mut self.writes, write);
+        }
+        for &alias in &other.aliased {
+            Self::push(&mut self.aliased, alias);
+        }
+    }
+
+    /// Component types read.
+    pub fn reads(&self) -> impl Iterator<Item = ComponentTypeId> + '_ {
+        self.reads.iter().map(|entry| entry.component_type)
+    }
+
+    /// Component types written.
+    pub fn writes(&self) -> impl Iterator<Item = ComponentTypeId> + '_ {
+        self.writes.iter().map(|entry| entry.component_type)
+    }
+
+    /// Returns true if nothing is read or written.
+    pub fn is_empty(&self) -> bool {
+        self.reads.is_empty() && self.writes.is_empty()
+    }
+
+    /// Returns the names of the component types that `self` and `other`
+    /// cannot access at the same time: anything one side writes and the
+    /// other touches.
+    pub fn conflicts(&self, other: &Access) -> Vec<&'static str> {
+        let mut conflicts = Vec::new();
+        for write in &self.writes {
+            if other.writes.contains(write) || other.reads.contains(write) {
+                conflicts.push(write.name);
+            }
+        }
+        for write in &other.writes {
+            if self.reads.contains(write) && !conflicts.contains(&write.name) {
+                conflicts.push(write.name);
+            }
+        }
+        conflicts
+    }
+
+    /// Returns the names of the component types that were recorded as
+    /// written alongside another read or write of the same type.
+    ///
+    /// A query with aliased access would hand out a mutable reference and
+    /// another reference to the same component at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::Component;
+    /// use pecs::query::Access;
+    ///
+    /// struct Position;
+    /// impl Component for Position {}
+    ///
+    /// assert!(Access::of::<(&Position, &Position)>().aliased().is_empty());
+    /// assert_eq!(Access::of::<(&mut Position, &Position)>().aliased().len(), 1);
+    /// ```
+    pub fn aliased(&self) -> Vec<&'static str> {
+        self.aliased.iter().map(|entry| entry.name).collect()
+    }
+
+    /// Panics if fetch `F` borrows a component mutably alongside another
+    /// access to it.
+    pub(crate) fn assert_unaliased<F: for<'a> super::Fetch<'a>>() {
+        let mut access = Self::new();
+        <F as super::Fetch<'static>>::access(&mut access);
+        if let Some(alias) = access.aliased.first() {
+            panic!(
+                "query accesses `{}` mutably more than once or both mutably and \
+                 immutably; a component may only be borrowed mutably once per query",
+                alias.name
+            );
+        }
+    }
+
+    /// Returns true if `self` and `other` can be held at the same time.
+    pub fn is_compatible(&self, other: &Access) -> bool {
+        self.conflicts(other).is_empty()
+    }
+
+    /// Returns the names of the component types `other` accesses beyond
+    /// what `self` grants: reads of types `self` neither reads nor writes,
+    /// and writes of types `self` does not write.
+    pub fn uncovered(&self, other: &Access) -> Vec<&'static str> {
+        let mut uncovered = Vec::new();
+        for read in &other.reads {
+            if !self.reads.contains(read) && !self.writes.contains(read) {
+                uncovered.push(read.name);
+            }
+        }
+        for write in &other.writes {
+            if !self.writes.contains(write) && !uncovered.contains(&write.name) {
+                uncovered.push(write.name);
+            }
+        }
+        uncovered
+    }
+
+    /// Returns true if everything `other` accesses is granted by `self`.
+    pub fn allows(&self, other: &Access) -> bool {
+        self.uncovered(other).is_empty()
+    }
+
+    /// Returns true if `self` reads or writes component type `T`.
+    pub fn can_read<T: Component>(&self) -> bool {
+        let entry = Self::entry::<T>();
+        self.reads.contains(&entry) || self.writes.contains(&entry)
+    }
+
+    /// Returns true if `self` writes component type `T`.
+    pub fn can_write<T: Component>(&self) -> bool {
+        self.writes.contains(&Self::entry::<T>())
+    }
+
+    fn entry<T: Component>() -> AccessEntry {
+        AccessEntry {
+            component_type: ComponentTypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+
+    fn push(entries: &mut Vec<AccessEntry>, entry: AccessEntry) {
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+
+    struct A;
+    impl Component for A {}
+    struct B;
+    impl Component for B {}
+
+    #[test]
+    fn reads_share_writes_conflict() {
+        let read_a = Access::of::<&A>();
+        let write_a = Access::of::<&mut A>();
+        let optional_a = Access::of::<Option<&A>>();
+
+        assert!(read_a.is_compatible(&read_a));
+        assert!(read_a.is_compatible(&optional_a));
+        assert!(!write_a.is_compatible(&write_a));
+        assert_eq!(
+            write_a.conflicts(&optional_a),
+            vec![std::any::type_name::<A>()]
+        );
+        assert!(write_a.is_compatible(&Access::of::<(&B, crate::EntityId)>()));
+
+        let mut combined = Access::new();
+        combined.extend(&read_a);
+        combined.extend(&Access::of::<&mut B>());
+        assert_eq!(
+            combined.reads().collect::<Vec<_>>(),
+            vec![ComponentTypeId::of::<A>()]
+        );
+        assert_eq!(
+            combined.writes().collect::<Vec<_>>(),
+            vec![ComponentTypeId::of::<B>()]
+        );
+        assert!(Access::of::<crate::EntityId>().is_empty());
+    }
+
+    #[test]
+    fn writes_cover_reads() {
+        let write_a = Access::of::<(&mut A, &B)>();
+
+        assert!(write_a.allows(&Access::of::<&A>()));
+        assert!(write_a.allows(&Access::of::<(&mut A, Option<&B>)>()));
+        assert!(write_a.can_read::<B>());
+        assert!(!write_a.can_write::<B>());
+        assert_eq!(
+            write_a.uncovered(&Access::of::<&mut B>()),
+            vec![std::any::type_name::<B>()]
+        );
+        assert!(Access::new().allows(&Access::of::<crate::EntityId>()));
+    }
+
+    #[test]
+    fn aliasing_within_one_query() {
+        let name = std::any::type_name::<A>();
+
+        assert!(Access::of::<(&A, &A, Option<&A>)>().aliased().is_empty());
+        assert!(Access::of::<(&mut A, &B)>().aliased().is_empty());
+        assert_eq!(Access::of::<(&mut A, &A)>().aliased(), vec![name]);
+        assert_eq!(Access::of::<(&A, &mut A)>().aliased(), vec![name]);
+        assert_eq!(Access::of::<(&mut A, &mut A)>().aliased(), vec![name]);
+        assert_eq!(
+            Access::of::<(&mut A, (&B, Option<&A>))>().aliased(),
+            vec![name]
+        );
+
+        let mut combined = Access::of::<&mut A>();
+        combined.extend(&Access::of::<&A>());
+        assert!(combined.aliased().is_empty());
+    }
+}
@@ -26,9 +26,10 @@
 //! - Archetype matching is optimized with inline hints
 //! - Unsafe operations are carefully documented and optimized
 
-use super::Fetch;
+use super::plan::short_type_name;
+use super::{Access, Fetch, FetchContext, ReadOnlyFetch};
 use crate::component::{Component, archetype::Archetype};
-use crate::entity::EntityId;
+use crate::entity::{EntityId, StableId};
 use std::marker::PhantomData;
 
 /// Fetch implementation for immutable component references.
@@ -43,6 +44,9 @@ pub struct FetchRead<T: Component> {
     _phantom: PhantomData<T>,
 }
 
+// SAFETY: Only shared references are produced
+unsafe impl<T: Component> ReadOnlyFetch for FetchRead<T> {}
+
 impl<'a, T: Component> Fetch<'a> for FetchRead<T> {
     type Item = &'a T;
 
@@ -52,7 +56,11 @@ impl<'a, T: Component> Fetch<'a> for FetchRead<T> {
     }
 
     #[inline(always)]
-    unsafe fn fetch(archetype: &'a Archetype, entity: EntityId) -> Self::Item {
+    unsafe fn fetch(
+        archetype: &'a Archetype,
+        entity: EntityId,
+        _context: FetchContext<'a>,
+    ) -> Self::Item {
         // SAFETY: Caller ensures entity exists and archetype matches
         // The archetype must have this component type (verified by matches_archetype)
         unsafe {
@@ -61,6 +69,16 @@ impl<'a, T: Component> Fetch<'a> for FetchRead<T> {
                 .expect("Entity must have component in matching archetype")
         }
     }
+
+    fn access(access: &mut Access) {
+        access.add_read::<T>();
+    }
+
+    fn missing_components(archetype: &Archetype, missing: &mut Vec<String>) {
+        if !archetype.has_component::<T>() {
+            missing.push(short_type_name(std::any::type_name::<T>()));
+        }
+    }
 }
 
 /// Fetch implementation for mutable component references.
@@ -84,7 +102,11 @@ impl<'a, T: Component> Fetch<'a> for FetchWrite<T> {
     }
 
     #[inline(always)]
-    unsafe fn fetch(archetype: &'a Archetype, entity: EntityId) -> Self::Item {
+    unsafe fn fetch(
+        archetype: &'a Archetype,
+        entity: EntityId,
+        _context: FetchContext<'a>,
+    ) -> Self::Item {
         // SAFETY: Caller ensures entity exists, archetype matches, and access is exclusive
         // The archetype must have this component type (verified by matches_archetype)
         unsafe {
@@ -94,6 +116,16 @@ impl<'a, T: Component> Fetch<'a> for FetchWrite<T> {
             &mut *(ptr as *mut T)
         }
     }
+
+    fn access(access: &mut Access) {
+        access.add_write::<T>();
+    }
+
+    fn missing_components(archetype: &Archetype, missing: &mut Vec<String>) {
+        if !archetype.has_component::<T>() {
+            missing.push(short_type_name(std::any::type_name::<T>()));
+        }
+    }
 }
 
 /// Fetch implementation for optional component references.
@@ -108,6 +140,9 @@ pub struct FetchOptional<T: Component> {
     _phantom: PhantomData<T>,
 }
 
+// SAFETY: Only shared references are produced
+unsafe impl<T: Component> ReadOnlyFetch for FetchOptional<T> {}
+
 impl<'a, T: Component> Fetch<'a> for FetchOptional<T> {
     type Item = Option<&'a T>;
 
@@ -118,10 +153,18 @@ impl<'a, T: Component> Fetch<'a> for FetchOptional<T> {
     }
 
     #[inline(always)]
-    unsafe fn fetch(archetype: &'a Archetype, entity: EntityId) -> Self::Item {
+    unsafe fn fetch(
+        archetype: &'a Archetype,
+        entity: EntityId,
+        _context: FetchContext<'a>,
+    ) -> Self::Item {
         // SAFETY: Caller ensures entity exists
         unsafe { archetype.get_component::<T>(entity) }
     }
+
+    fn access(access: &mut Access) {
+        access.add_read::<T>();
+    }
 }
 
 /// Fetch implementation for entity IDs.
@@ -134,6 +177,9 @@ impl<'a, T: Component> Fetch<'a> for FetchOptional<T> {
 /// without any memory access.
 pub struct FetchEntity;
 
+// SAFETY: No component data is accessed
+unsafe impl ReadOnlyFetch for FetchEntity {}
+
 impl<'a> Fetch<'a> for FetchEntity {
     type Item = EntityId;
 
@@ -144,11 +190,43 @@ impl<'a> Fetch<'a> for FetchEntity {
     }
 
     #[inline(always)]
-    unsafe fn fetch(_archetype: &'a Archetype, entity: EntityId) -> Self::Item {
+    unsafe fn fetch(
+        _archetype: &'a Archetype,
+        entity: EntityId,
+        _context: FetchContext<'a>,
+    ) -> Self::Item {
         entity
     }
 }
 
+/// Fetch implementation for stable IDs.
+///
+/// This allows including `&StableId` in query results. The ID is borrowed
+/// from the entity allocator's mapping, which is indexed by entity, so no
+/// hash lookup is needed.
+pub struct FetchStableId;
+
+// SAFETY: No component data is accessed
+unsafe impl ReadOnlyFetch for FetchStableId {}
+
+impl<'a> Fetch<'a> for FetchStableId {
+    type Item = &'a StableId;
+
+    #[inline(always)]
+    fn matches_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    unsafe fn fetch(
+        _archetype: &'a Archetype,
+        entity: EntityId,
+        context: FetchContext<'a>,
+    ) -> Self::Item {
+        context.stable_id(entity)
+    }
+}
+
 // Macro to implement Fetch for tuples
 macro_rules! impl_fetch_tuple {
     ($($T:ident),*) => {
@@ -160,13 +238,28 @@ macro_rules! impl_fetch_tuple {
                 $($T::matches_archetype(archetype))&&*
             }
 
-            unsafe fn fetch(archetype: &'a Archetype, entity: EntityId) -> Self::Item {
+            unsafe fn fetch(
+                archetype: &'a Archetype,
+                entity: EntityId,
+                context: FetchContext<'a>,
+            ) -> Self::Item {
                 // SAFETY: Caller ensures all safety requirements
                 unsafe {
-                    ($($T::fetch(archetype, entity),)*)
+                    ($($T::fetch(archetype, entity, context),)*)
                 }
             }
+
+            fn access(access: &mut Access) {
+                $($T::access(access);)*
+            }
+
+            fn missing_components(archetype: &Archetype, missing: &mut Vec<String>) {
+                $($T::missing_components(archetype, missing);)*
+            }
         }
+
+        // SAFETY: Every element is read-only
+        unsafe impl<$($T: ReadOnlyFetch),*> ReadOnlyFetch for ($($T,)*) {}
     };
 }
 
@@ -180,6 +273,71 @@ impl_fetch_tuple!(A, B, C, D, E, F);
 impl_fetch_tuple!(A, B, C, D, E, F, G);
 impl_fetch_tuple!(A, B, C, D, E, F, G, H);
 
+/// Fetch implementation for [`AnyOf`](super::AnyOf) queries.
+///
+/// Matches archetypes where at least one element matches, and yields a
+/// tuple with `Some` for every element that matches.
+///
+/// # Performance
+///
+/// Each element's archetype check runs once per entity, so this is a
+/// little slower than a plain tuple fetch.
+pub struct FetchAnyOf<T> {
+    _phantom: PhantomData<T>,
+}
+
+// Macro to implement Fetch for any-of tuples
+macro_rules! impl_fetch_any_of {
+    ($($T:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<'a, $($T: Fetch<'a>),*> Fetch<'a> for FetchAnyOf<($($T,)*)> {
+            type Item = ($(Option<$T::Item>,)*);
+
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                $($T::matches_archetype(archetype))||*
+            }
+
+            unsafe fn fetch(
+                archetype: &'a Archetype,
+                entity: EntityId,
+                context: FetchContext<'a>,
+            ) -> Self::Item {
+                // SAFETY: Caller ensures all safety requirements, and each
+                // element is only fetched from an archetype it matches
+                unsafe {
+                    ($($T::matches_archetype(archetype)
+                        .then(|| $T::fetch(archetype, entity, context)),)*)
+                }
+            }
+
+            fn access(access: &mut Access) {
+                $($T::access(access);)*
+            }
+
+            fn missing_components(archetype: &Archetype, missing: &mut Vec<String>) {
+                if Self::matches_archetype(archetype) {
+                    return;
+                }
+                let mut any = Vec::new();
+                $($T::missing_components(archetype, &mut any);)*
+                missing.push(format!("any of {}", any.join(", ")));
+            }
+        }
+
+        // SAFETY: Every element is read-only
+        unsafe impl<$($T: ReadOnlyFetch),*> ReadOnlyFetch for FetchAnyOf<($($T,)*)> {}
+    };
+}
+
+impl_fetch_any_of!(A);
+impl_fetch_any_of!(A, B);
+impl_fetch_any_of!(A, B, C);
+impl_fetch_any_of!(A, B, C, D);
+impl_fetch_any_of!(A, B, C, D, E);
+impl_fetch_any_of!(A, B, C, D, E, F);
+impl_fetch_any_of!(A, B, C, D, E, F, G);
+impl_fetch_any_of!(A, B, C, D, E, F, G, H);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +382,12 @@ mod tests {
         _test_fetch::<FetchEntity>();
     }
 
+    #[test]
+    fn fetch_stable_id_type_check() {
+        fn _test_fetch<F: super::ReadOnlyFetch>() {}
+        _test_fetch::<FetchStableId>();
+    }
+
     #[test]
     fn fetch_tuple_type_check() {
         fn _test_fetch<F: for<'a> Fetch<'a>>() {}
@@ -26,10 +26,16 @@
 //! - Entity-level: Custom filters can check individual entities
 //!
 //! The query iterator uses archetype-level filtering to skip non-matching archetypes entirely.
+//!
+//! # Disabled Entities
+//!
+//! Queries skip entities with the [`Disabled`] marker unless the filter
+//! includes them, see [`Including`].
 
 use super::Filter;
-use crate::component::{Component, archetype::Archetype};
+use crate::component::{Component, Disabled, archetype::Archetype};
 use crate::entity::EntityId;
+use std::any::TypeId;
 use std::marker::PhantomData;
 
 /// A filter that requires an entity to have a specific component.
@@ -57,6 +63,50 @@ impl<'a, T: Component> Filter<'a> for With<T> {
     fn matches(archetype: &Archetype, _entity: EntityId) -> bool {
         archetype.has_component::<T>()
     }
+
+    fn includes_disabled() -> bool {
+        TypeId::of::<T>() == TypeId::of::<Disabled>()
+    }
+}
+
+/// A filter that lets a query see entities it would skip by default.
+///
+/// `Including<Disabled>` makes a query visit [`Disabled`] entities as well
+/// as enabled ones. It matches every entity, so it only widens a query.
+/// Other component types have no default exclusion, which makes
+/// `Including<T>` a no-op for them.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::component::Disabled;
+/// use pecs::query::filter::Including;
+///
+/// struct Health(u32);
+/// impl Component for Health {}
+///
+/// let mut world = World::new();
+/// let sleeping = world.spawn().with(Health(5)).id();
+/// world.spawn().with(Health(9)).id();
+/// world.set_enabled(sleeping, false);
+///
+/// assert_eq!(world.query::<&Health>().count(), 1);
+/// assert_eq!(world.query_filtered::<&Health, Including<Disabled>>().count(), 2);
+/// ```
+pub struct Including<T: Component> {
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Component> Filter<'a> for Including<T> {
+    #[inline(always)]
+    fn matches(_archetype: &Archetype, _entity: EntityId) -> bool {
+        true
+    }
+
+    fn includes_disabled() -> bool {
+        TypeId::of::<T>() == TypeId::of::<Disabled>()
+    }
 }
 
 /// A filter that requires an entity to NOT have a specific component.
@@ -105,21 +155,50 @@ impl<'a, A: Filter<'a>, B: Filter<'a>> Filter<'a> for And<A, B> {
     fn matches(archetype: &Archetype, entity: EntityId) -> bool {
         A::matches(archetype, entity) && B::matches(archetype, entity)
     }
-}
 
-/// A filter that combines multiple filters with OR logic.
-///
-/// At least one filter must match for the entity to be included.
-pub struct Or<A, B> {
-    _phantom: PhantomData<(A, B)>,
-}
+    fn explain(archetype: &Archetype, entity: EntityId, rejected: &mut Vec<String>) -> bool {
+        let a = A::explain(archetype, entity, rejected);
+        B::explain(archetype, entity, rejected) && a
+    }
 
-impl<'a, A: Filter<'a>, B: Filter<'a>> Filter<'a> for Or<A, B> {
-    fn matches(archetype: &Archetype, entity: EntityId) -> bool {
-        A::matches(archetype, entity) || B::matches(archetype, entity)
+    fn includes_disabled() -> bool {
+        A::includes_disabled() || B::includes_disabled()
     }
 }
 
+/// A filter that combines a tuple of filters with OR logic.
+///
+/// At least one filter must match for the entity to be included. Combine
+/// it with tuples (AND) and [`Not`] to express complex conditions in a
+/// single query.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::query::filter::{Not, Or, With};
+///
+/// struct Enemy;
+/// impl Component for Enemy {}
+/// struct Neutral;
+/// impl Component for Neutral {}
+/// struct Frozen;
+/// impl Component for Frozen {}
+///
+/// let mut world = World::new();
+/// let enemy = world.spawn().with(Enemy).id();
+/// world.spawn().with(Neutral).with(Frozen).id();
+///
+/// // (With<Enemy> or With<Neutral>) and not With<Frozen>
+/// let targets: Vec<EntityId> = world
+///     .query_filtered::<EntityId, (Or<(With<Enemy>, With<Neutral>)>, Not<With<Frozen>>)>()
+///     .collect();
+/// assert_eq!(targets, vec![enemy]);
+/// ```
+pub struct Or<T> {
+    _phantom: PhantomData<T>,
+}
+
 /// A filter that inverts another filter.
 ///
 /// Matches when the inner filter does NOT match.
@@ -141,6 +220,21 @@ macro_rules! impl_filter_tuple {
             fn matches(archetype: &Archetype, entity: EntityId) -> bool {
                 $($T::matches(archetype, entity))&&*
             }
+
+            fn explain(
+                archetype: &Archetype,
+                entity: EntityId,
+                rejected: &mut Vec<String>,
+            ) -> bool {
+                // Every part is checked so all rejecting parts are reported
+                let mut matches = true;
+                $(matches &= $T::explain(archetype, entity, rejected);)*
+                matches
+            }
+
+            fn includes_disabled() -> bool {
+                $($T::includes_disabled())||*
+            }
         }
     };
 }
@@ -155,6 +249,31 @@ impl_filter_tuple!(A, B, C, D, E, F);
 impl_filter_tuple!(A, B, C, D, E, F, G);
 impl_filter_tuple!(A, B, C, D, E, F, G, H);
 
+// Macro to implement Filter for Or over tuples
+macro_rules! impl_or_filter_tuple {
+    ($($T:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<'a, $($T: Filter<'a>),*> Filter<'a> for Or<($($T,)*)> {
+            fn matches(archetype: &Archetype, entity: EntityId) -> bool {
+                $($T::matches(archetype, entity))||*
+            }
+
+            fn includes_disabled() -> bool {
+                $($T::includes_disabled())||*
+            }
+        }
+    };
+}
+
+impl_or_filter_tuple!(A);
+impl_or_filter_tuple!(A, B);
+impl_or_filter_tuple!(A, B, C);
+impl_or_filter_tuple!(A, B, C, D);
+impl_or_filter_tuple!(A, B, C, D, E);
+impl_or_filter_tuple!(A, B, C, D, E, F);
+impl_or_filter_tuple!(A, B, C, D, E, F, G);
+impl_or_filter_tuple!(A, B, C, D, E, F, G, H);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,7 +321,8 @@ mod tests {
     #[test]
     fn or_filter_type_check() {
         fn _test_filter<F: for<'a> Filter<'a>>() {}
-        _test_filter::<Or<With<Position>, With<Velocity>>>();
+        _test_filter::<Or<(With<Position>, With<Velocity>)>>();
+        _test_filter::<Or<(With<Position>, With<Velocity>, Without<Dead>)>>();
     }
 
     #[test]
@@ -228,4 +348,28 @@ mod tests {
 
         assert!(<() as Filter>::matches(&archetype, entity));
     }
+
+    #[test]
+    fn or_and_not_combine() {
+        use crate::component::archetype::{Archetype, ArchetypeId};
+        use crate::component::{ComponentInfo, ComponentSet, ComponentTypeId};
+
+        let archetype = Archetype::new(
+            ArchetypeId::new(1),
+            ComponentSet::from_types(vec![ComponentTypeId::of::<Velocity>()]),
+            vec![ComponentInfo::of::<Velocity>()],
+        );
+        let entity = EntityId::new(0, 1);
+
+        assert!(<Or<(With<Position>, With<Velocity>)> as Filter>::matches(
+            &archetype, entity
+        ));
+        assert!(!<Or<(With<Position>, With<Dead>)> as Filter>::matches(
+            &archetype, entity
+        ));
+        assert!(<Not<With<Dead>> as Filter>::matches(&archetype, entity));
+        assert!(
+            !<(Or<(With<Velocity>,)>, Not<With<Velocity>>) as Filter>::matches(&archetype, entity)
+        );
+    }
 }
@@ -18,12 +18,122 @@
 //!
 //! This module provides iterators for traversing query results across
 //! multiple archetypes efficiently.
+//!
+//! # Iteration Order
+//!
+//! By default archetypes are visited in creation order and entities in row
+//! order. Both are a pure function of the operations applied to the world, so
+//! two worlds driven by the same command sequence iterate identically.
+//!
+//! Iterators created with `new_deterministic` (used by worlds with
+//! [`World::set_deterministic_iteration`](crate::World::set_deterministic_iteration)
+//! enabled) go further and yield entities in ascending [`EntityId`] order,
+//! which is independent of archetype layout and row history. This is the
+//! order lockstep simulations should rely on.
+//!
+//! Any other order can be obtained with the `sort_*` adapters, which collect
+//! the matched rows and yield them in a caller-defined order.
 
-use super::{Fetch, Filter};
+use super::{Access, Fetch, FetchContext, Filter, ReadOnlyFetch};
+use crate::component::Disabled;
 use crate::component::archetype::{Archetype, ArchetypeManager};
-use crate::entity::EntityId;
+use crate::entity::{EntityId, EntityManager};
+use std::cmp::Ordering;
 use std::marker::PhantomData;
 
+/// Rows of matching archetypes, sorted by entity ID.
+type OrderedRows<'w> = std::vec::IntoIter<(&'w Archetype, EntityId)>;
+
+/// Checks whether a query visits an archetype: it must match the fetch,
+/// and hold no [`Disabled`] entities unless the filter includes them.
+#[inline]
+fn visits<F, Fil>(archetype: &Archetype) -> bool
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    F::matches_archetype(archetype)
+        && (Fil::includes_disabled() || !archetype.has_component::<Disabled>())
+}
+
+/// Collects the rows of all archetypes the query visits, ordered by entity
+/// ID.
+fn ordered_rows<'w, F, Fil>(archetype_manager: &'w ArchetypeManager) -> OrderedRows<'w>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    // Archetype 0 is skipped to match the unordered iterators
+    let mut rows: Vec<_> = archetype_manager
+        .iter()
+        .skip(1)
+        .filter(|archetype| visits::<F, Fil>(archetype))
+        .flat_map(|archetype| archetype.entities().iter().map(move |&e| (archetype, e)))
+        .collect();
+    rows.sort_unstable_by_key(|(_, entity)| (entity.index(), entity.generation()));
+    rows.into_iter()
+}
+
+/// Adds sorting adapters to a query iterator.
+macro_rules! impl_sort_adapters {
+    ($iter:ident) => {
+        impl<'w, F, Fil> $iter<'w, F, Fil>
+        where
+            F: for<'a> Fetch<'a>,
+            Fil: for<'a> Filter<'a>,
+        {
+            /// Collects the matched items and yields them ordered by `compare`.
+            ///
+            /// The sort is stable, so items that compare equal keep their
+            /// iteration order.
+            pub fn sort_by(
+                self,
+                compare: impl FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering,
+            ) -> std::vec::IntoIter<<Self as Iterator>::Item> {
+                let mut items: Vec<_> = self.collect();
+                items.sort_by(compare);
+                items.into_iter()
+            }
+
+            /// Collects the matched items and yields them ordered by the key
+            /// returned from `key`, such as a draw-order component.
+            ///
+            /// The sort is stable, so items with equal keys keep their
+            /// iteration order.
+            pub fn sort_by_key<K: Ord>(
+                self,
+                key: impl FnMut(&<Self as Iterator>::Item) -> K,
+            ) -> std::vec::IntoIter<<Self as Iterator>::Item> {
+                let mut items: Vec<_> = self.collect();
+                items.sort_by_key(key);
+                items.into_iter()
+            }
+
+            /// Like [`sort_by`](Self::sort_by), but does not preserve the order
+            /// of equal items.
+            pub fn sort_unstable_by(
+                self,
+                compare: impl FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering,
+            ) -> std::vec::IntoIter<<Self as Iterator>::Item> {
+                let mut items: Vec<_> = self.collect();
+                items.sort_unstable_by(compare);
+                items.into_iter()
+            }
+
+            /// Like [`sort_by_key`](Self::sort_by_key), but does not preserve
+            /// the order of items with equal keys.
+            pub fn sort_unstable_by_key<K: Ord>(
+                self,
+                key: impl FnMut(&<Self as Iterator>::Item) -> K,
+            ) -> std::vec::IntoIter<<Self as Iterator>::Item> {
+                let mut items: Vec<_> = self.collect();
+                items.sort_unstable_by_key(key);
+                items.into_iter()
+            }
+        }
+    };
+}
+
 /// An iterator over query results.
 ///
 /// This iterator traverses all archetypes that match the query's fetch
@@ -50,34 +160,287 @@ pub struct QueryIter<'w, F, Fil = ()> {
     /// Cached entity slice from current archetype (better cache locality)
     current_entities: &'w [EntityId],
 
+    /// Pre-sorted rows when iterating in entity order
+    ordered: Option<OrderedRows<'w>>,
+
+    /// World data passed to fetches
+    context: FetchContext<'w>,
+
     /// Phantom data for fetch and filter types
     _phantom: PhantomData<(F, Fil)>,
 }
 
-impl<'w, F, Fil> QueryIter<'w, F, Fil> {
+impl<'w, F, Fil> QueryIter<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+{
     /// Creates a new query iterator.
     ///
     /// # Arguments
     ///
     /// * `archetype_manager` - The archetype manager to iterate over
+    ///
+    /// # Panics
+    ///
+    /// Panics if `F` borrows a component mutably alongside another access
+    /// to it, as `(&mut Position, &Position)` does.
     pub fn new(archetype_manager: &'w ArchetypeManager) -> Self {
+        Access::assert_unaliased::<F>();
         Self {
             archetype_manager,
             archetype_index: 0,
             entity_index: 0,
             current_archetype: None,
             current_entities: &[],
+            ordered: None,
+            context: FetchContext::default(),
             _phantom: PhantomData,
         }
     }
+}
+
+impl<'w, F, Fil> QueryIter<'w, F, Fil> {
+    /// Attaches the entity manager of the world being queried, which
+    /// `&StableId` fetches read from.
+    pub fn with_entities(mut self, entities: &'w EntityManager) -> Self {
+        self.context = FetchContext::new(entities);
+        self
+    }
 
     /// Resets the iterator to the beginning.
+    ///
+    /// Deterministic iterators fall back to unordered iteration after a reset.
     pub fn reset(&mut self) {
         self.archetype_index = 0;
         self.entity_index = 0;
         self.current_archetype = None;
         self.current_entities = &[];
+        self.ordered = None;
+    }
+}
+
+impl<'w, F, Fil> QueryIter<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    /// Creates a query iterator that yields entities in ascending ID order.
+    ///
+    /// The matched rows are collected up front, so this costs an allocation
+    /// and a sort per query.
+    pub fn new_deterministic(archetype_manager: &'w ArchetypeManager) -> Self {
+        let mut iter = Self::new(archetype_manager);
+        iter.ordered = Some(ordered_rows::<F, Fil>(archetype_manager));
+        iter
+    }
+}
+
+impl_sort_adapters!(QueryIter);
+
+impl<'w, F, Fil> QueryIter<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    /// Yields every unordered combination of `K` distinct matches.
+    ///
+    /// Each combination appears once, with its items in iteration order.
+    /// Read-only queries can use the result as an `Iterator`; queries with
+    /// `&mut` components step through it with
+    /// [`fetch_next`](Combinations::fetch_next), which only allows one
+    /// combination to be held at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::component::Component;
+    /// use pecs::World;
+    ///
+    /// struct Mass(f32);
+    /// impl Component for Mass {}
+    ///
+    /// struct Force(f32);
+    /// impl Component for Force {}
+    ///
+    /// let mut world = World::new();
+    /// for mass in [1.0, 2.0, 3.0] {
+    ///     world.spawn().with(Mass(mass)).with(Force(0.0)).id();
+    /// }
+    ///
+    /// let pairs = world.query::<&Mass>().iter_combinations::<2>().count();
+    /// assert_eq!(pairs, 3);
+    ///
+    /// let mut bodies = world.query::<(&Mass, &mut Force)>().iter_combinations::<2>();
+    /// while let Some([(a, fa), (b, fb)]) = bodies.fetch_next() {
+    ///     let force = a.0 * b.0;
+    ///     fa.0 += force;
+    ///     fb.0 -= force;
+    /// }
+    /// ```
+    pub fn iter_combinations<const K: usize>(self) -> Combinations<'w, F, Fil, K> {
+        let context = self.context;
+        Combinations::new(self.into_rows(), context)
+    }
+
+    /// Collects the rows this iterator has left to visit.
+    fn into_rows(self) -> Vec<(&'w Archetype, EntityId)> {
+        if let Some(ordered) = self.ordered {
+            return ordered
+                .filter(|(archetype, entity)| Fil::matches(archetype, *entity))
+                .collect();
+        }
+        let mut rows = Vec::new();
+        if let Some(archetype) = self.current_archetype {
+            rows.extend(
+                self.current_entities[self.entity_index..]
+                    .iter()
+                    .filter(|&&entity| Fil::matches(archetype, entity))
+                    .map(|&entity| (archetype, entity)),
+            );
+        }
+        for archetype in self
+            .archetype_manager
+            .iter()
+            .skip(self.archetype_index + 1)
+            .filter(|archetype| visits::<F, Fil>(archetype))
+        {
+            rows.extend(
+                archetype
+                    .entities()
+                    .iter()
+                    .filter(|&&entity| Fil::matches(archetype, entity))
+                    .map(|&entity| (archetype, entity)),
+            );
+        }
+        rows
+    }
+}
+
+/// Unordered combinations of `K` distinct query matches.
+///
+/// Created by [`QueryIter::iter_combinations`].
+pub struct Combinations<'w, F, Fil, const K: usize> {
+    /// Matched rows, in iteration order
+    rows: Vec<(&'w Archetype, EntityId)>,
+
+    /// Row indices of the next combination, strictly increasing
+    indices: [usize; K],
+
+    /// Whether every combination has been yielded
+    done: bool,
+
+    /// World data passed to fetches
+    context: FetchContext<'w>,
+
+    /// Phantom data for fetch and filter types
+    _phantom: PhantomData<(F, Fil)>,
+}
+
+impl<'w, F, Fil, const K: usize> Combinations<'w, F, Fil, K>
+where
+    F: for<'a> Fetch<'a>,
+{
+    fn new(rows: Vec<(&'w Archetype, EntityId)>, context: FetchContext<'w>) -> Self {
+        Self {
+            done: K == 0 || K > rows.len(),
+            rows,
+            context,
+            indices: std::array::from_fn(|i| i),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the next combination.
+    ///
+    /// The items borrow the iterator, so this works for queries with `&mut`
+    /// components: the combination must be dropped before the next call.
+    pub fn fetch_next(&mut self) -> Option<[<F as Fetch<'_>>::Item; K]> {
+        let indices = self.advance()?;
+        // SAFETY: The indices are distinct, so each row is a different entity
+        // and mutable items never alias; the archetypes matched `F`
+        Some(std::array::from_fn(|i| {
+            let (archetype, entity) = self.rows[indices[i]];
+            unsafe { F::fetch(archetype, entity, self.context) }
+        }))
+    }
+
+    /// Returns the number of combinations not yet yielded.
+    pub fn remaining(&self) -> usize {
+        if self.done {
+            return 0;
+        }
+        // Count the combinations lexicographically at or after `indices`
+        let n = self.rows.len();
+        let mut remaining = 0;
+        for (position, &index) in self.indices.iter().enumerate() {
+            let slots = K - position - 1;
+            let start = if position == 0 {
+                0
+            } else {
+                self.indices[position - 1] + 1
+            };
+            for skipped in start..index {
+                remaining += binomial(n - skipped - 1, slots);
+            }
+        }
+        binomial(n, K) - remaining
+    }
+
+    /// Returns the current indices and steps to the next combination.
+    fn advance(&mut self) -> Option<[usize; K]> {
+        if self.done {
+            return None;
+        }
+        let current = self.indices;
+        let n = self.rows.len();
+        match (0..K).rev().find(|&i| self.indices[i] < n - K + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..K {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(current)
+    }
+}
+
+impl<'w, F, Fil, const K: usize> Iterator for Combinations<'w, F, Fil, K>
+where
+    F: ReadOnlyFetch,
+{
+    type Item = [<F as Fetch<'w>>::Item; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.advance()?;
+        // SAFETY: Read-only items may alias; the archetypes matched `F`
+        Some(std::array::from_fn(|i| {
+            let (archetype, entity) = self.rows[indices[i]];
+            unsafe { F::fetch(archetype, entity, self.context) }
+        }))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+/// Number of ways to choose `k` of `n` items, saturating on overflow.
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: usize = 1;
+    for i in 0..k {
+        result = match result.checked_mul(n - i) {
+            Some(product) => product / (i + 1),
+            None => return usize::MAX,
+        };
+    }
+    result
 }
 
 impl<'w, F, Fil> Iterator for QueryIter<'w, F, Fil>
@@ -88,6 +451,16 @@ where
     type Item = <F as Fetch<'w>>::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ordered) = &mut self.ordered {
+            for (archetype, entity) in ordered.by_ref() {
+                if Fil::matches(archetype, entity) {
+                    // SAFETY: The archetype matched the fetch when rows were collected
+                    return Some(unsafe { F::fetch(archetype, entity, self.context) });
+                }
+            }
+            return None;
+        }
+
         loop {
             // Fast path: iterate within current archetype
             if self.entity_index < self.current_entities.len() {
@@ -104,7 +477,7 @@ where
                 }
 
                 // Fetch the data for this entity
-                let item = unsafe { F::fetch(archetype, entity) };
+                let item = unsafe { F::fetch(archetype, entity, self.context) };
                 return Some(item);
             }
 
@@ -119,7 +492,7 @@ where
                 let archetype = self.archetype_manager.get_archetype(archetype_id)?;
 
                 // Check if this archetype matches our fetch requirements
-                if F::matches_archetype(archetype) {
+                if visits::<F, Fil>(archetype) {
                     // Cache the archetype and its entities for fast iteration
                     self.current_archetype = Some(archetype);
                     self.current_entities = archetype.entities();
@@ -133,6 +506,95 @@ where
     }
 }
 
+/// An iterator over the query results within a single archetype.
+///
+/// Created by [`Archetype::iter`] and
+/// [`World::query_archetype`](crate::World::query_archetype). Yields nothing
+/// if the archetype does not match the query.
+pub struct ArchetypeIter<'w, F, Fil = ()> {
+    /// The archetype, or `None` if it does not match
+    archetype: Option<&'w Archetype>,
+
+    /// Next row to visit
+    row: usize,
+
+    /// World data passed to fetches
+    context: FetchContext<'w>,
+
+    /// Phantom data for fetch and filter types
+    _phantom: PhantomData<(F, Fil)>,
+}
+
+impl<'w, F, Fil> ArchetypeIter<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    /// Creates an iterator over one archetype.
+    ///
+    /// # Safety
+    ///
+    /// For the lifetime of the iterator, no other reference may exist to a
+    /// component `F` writes, and no mutable reference to a component it
+    /// reads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `F` borrows a component mutably alongside another access
+    /// to it.
+    pub(crate) unsafe fn new(archetype: &'w Archetype, context: FetchContext<'w>) -> Self {
+        Access::assert_unaliased::<F>();
+        Self {
+            archetype: visits::<F, Fil>(archetype).then_some(archetype),
+            row: 0,
+            context,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates an iterator that yields nothing.
+    ///
+    /// Panics like [`new`](Self::new) so an aliasing query fails the same
+    /// way whether or not its archetype exists.
+    pub(crate) fn empty() -> Self {
+        Access::assert_unaliased::<F>();
+        Self {
+            archetype: None,
+            row: 0,
+            context: FetchContext::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'w, F, Fil> Iterator for ArchetypeIter<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    type Item = <F as Fetch<'w>>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let archetype = self.archetype?;
+        while let Some(entity) = archetype.get_entity(self.row) {
+            self.row += 1;
+            if Fil::matches(archetype, entity) {
+                // SAFETY: The archetype matched the fetch and the caller of
+                // `new` guaranteed the access
+                return Some(unsafe { F::fetch(archetype, entity, self.context) });
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .archetype
+            .map_or(0, |archetype| archetype.len() - self.row);
+        (0, Some(remaining))
+    }
+}
+
 // Note: Parallel query iteration will be added in a future update
 // when the `parallel` feature is implemented.
 
@@ -161,32 +623,76 @@ pub struct QueryIterWithEntity<'w, F, Fil = ()> {
     /// Cached entity slice from current archetype
     current_entities: &'w [EntityId],
 
+    /// Pre-sorted rows when iterating in entity order
+    ordered: Option<OrderedRows<'w>>,
+
+    /// World data passed to fetches
+    context: FetchContext<'w>,
+
     /// Phantom data for fetch and filter types
     _phantom: PhantomData<(F, Fil)>,
 }
 
-impl<'w, F, Fil> QueryIterWithEntity<'w, F, Fil> {
+impl<'w, F, Fil> QueryIterWithEntity<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+{
     /// Creates a new query iterator with entity IDs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `F` borrows a component mutably alongside another access
+    /// to it.
     pub fn new(archetype_manager: &'w ArchetypeManager) -> Self {
+        Access::assert_unaliased::<F>();
         Self {
             archetype_manager,
             archetype_index: 0,
             entity_index: 0,
             current_archetype: None,
             current_entities: &[],
+            ordered: None,
+            context: FetchContext::default(),
             _phantom: PhantomData,
         }
     }
+}
+
+impl<'w, F, Fil> QueryIterWithEntity<'w, F, Fil> {
+    /// Attaches the entity manager of the world being queried, which
+    /// `&StableId` fetches read from.
+    pub fn with_entities(mut self, entities: &'w EntityManager) -> Self {
+        self.context = FetchContext::new(entities);
+        self
+    }
 
     /// Resets the iterator to the beginning.
+    ///
+    /// Deterministic iterators fall back to unordered iteration after a reset.
     pub fn reset(&mut self) {
         self.archetype_index = 0;
         self.entity_index = 0;
         self.current_archetype = None;
         self.current_entities = &[];
+        self.ordered = None;
+    }
+}
+
+impl<'w, F, Fil> QueryIterWithEntity<'w, F, Fil>
+where
+    F: for<'a> Fetch<'a>,
+    Fil: for<'a> Filter<'a>,
+{
+    /// Creates a query iterator that yields entities in ascending ID order.
+    pub fn new_deterministic(archetype_manager: &'w ArchetypeManager) -> Self {
+        let mut iter = Self::new(archetype_manager);
+        iter.ordered = Some(ordered_rows::<F, Fil>(archetype_manager));
+        iter
     }
 }
 
+impl_sort_adapters!(QueryIterWithEntity);
+
 impl<'w, F, Fil> Iterator for QueryIterWithEntity<'w, F, Fil>
 where
     F: for<'a> Fetch<'a>,
@@ -195,6 +701,16 @@ where
     type Item = (EntityId, <F as Fetch<'w>>::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ordered) = &mut self.ordered {
+            for (archetype, entity) in ordered.by_ref() {
+                if Fil::matches(archetype, entity) {
+                    // SAFETY: The archetype matched the fetch when rows were collected
+                    return Some((entity, unsafe { F::fetch(archetype, entity, self.context) }));
+                }
+            }
+            return None;
+        }
+
         loop {
             // Fast path: iterate within current archetype
             if self.entity_index < self.current_entities.len() {
@@ -211,7 +727,7 @@ where
 
                 // Fetch the data for this entity
                 // SAFETY: We've verified the archetype matches and the entity exists
-                let item = unsafe { F::fetch(archetype, entity) };
+                let item = unsafe { F::fetch(archetype, entity, self.context) };
                 return Some((entity, item));
             }
 
@@ -226,7 +742,7 @@ where
                 let archetype = self.archetype_manager.get_archetype(archetype_id)?;
 
                 // Check if this archetype matches our fetch requirements
-                if F::matches_archetype(archetype) {
+                if visits::<F, Fil>(archetype) {
                     // Cache the archetype and its entities for fast iteration
                     self.current_archetype = Some(archetype);
                     self.current_entities = archetype.entities();
@@ -243,13 +759,14 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::query::fetch::FetchEntity;
 
     #[test]
     fn query_iter_creation() {
         use crate::component::archetype::ArchetypeManager;
 
         let manager = ArchetypeManager::new();
-        let _iter: QueryIter<()> = QueryIter::new(&manager);
+        let _iter: QueryIter<FetchEntity> = QueryIter::new(&manager);
     }
 
     #[test]
@@ -257,7 +774,7 @@ mod tests {
         use crate::component::archetype::ArchetypeManager;
 
         let manager = ArchetypeManager::new();
-        let mut iter: QueryIter<()> = QueryIter::new(&manager);
+        let mut iter: QueryIter<FetchEntity> = QueryIter::new(&manager);
 
         iter.archetype_index = 5;
         iter.entity_index = 10;
@@ -272,6 +789,47 @@ mod tests {
         use crate::component::archetype::ArchetypeManager;
 
         let manager = ArchetypeManager::new();
-        let _iter: QueryIterWithEntity<()> = QueryIterWithEntity::new(&manager);
+        let _iter: QueryIterWithEntity<FetchEntity> = QueryIterWithEntity::new(&manager);
+    }
+
+    #[test]
+    fn combinations_are_unique_and_counted() {
+        use crate::World;
+        use crate::component::Component;
+
+        struct Value(u32);
+        impl Component for Value {}
+        struct Tag;
+        impl Component for Tag {}
+
+        let mut world = World::new();
+        for value in 0..5 {
+            let entity = world.spawn().with(Value(value)).id();
+            if value % 2 == 0 {
+                world.insert(entity, Tag);
+            }
+        }
+
+        let mut pairs = world.query::<&Value>().iter_combinations::<2>();
+        assert_eq!(pairs.size_hint(), (10, Some(10)));
+        pairs.next();
+        assert_eq!(pairs.remaining(), 9);
+        let mut seen: Vec<(u32, u32)> = pairs.map(|[a, b]| (a.0.min(b.0), a.0.max(b.0))).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 9);
+
+        assert_eq!(world.query::<&Value>().iter_combinations::<3>().count(), 10);
+        assert_eq!(world.query::<&Value>().iter_combinations::<6>().count(), 0);
+        assert_eq!(world.query::<&Value>().iter_combinations::<0>().count(), 0);
+
+        let mut tagged = world.query::<(&mut Value, &Tag)>().iter_combinations::<2>();
+        while let Some([(a, _), (b, _)]) = tagged.fetch_next() {
+            a.0 += 10;
+            b.0 += 10;
+        }
+        let mut values: Vec<u32> = world.query::<&Value>().map(|value| value.0).collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 3, 20, 22, 24]);
     }
 }
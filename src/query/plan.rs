@@ -0,0 +1,310 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Query dry runs.
+//!
+//! [`World::explain_query`](crate::World::explain_query) walks the
+//! archetypes a query would visit without fetching anything, and records
+//! for each one whether it matched and, if not, which components or filters
+//! ruled it out. Print the resulting [`QueryPlan`] to find out why a query
+//! returns nothing.
+
+use super::{Fetch, Filter};
+use crate::component::Disabled;
+use crate::component::archetype::{ArchetypeId, ArchetypeManager};
+use crate::entity::EntityId;
+use std::fmt;
+
+/// The outcome of a query dry run, one entry per archetype.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+/// use pecs::query::filter::Without;
+///
+/// struct Position(f32);
+/// impl Component for Position {}
+/// struct Dead;
+/// impl Component for Dead {}
+///
+/// let mut world = World::new();
+/// world.spawn().with(Position(1.0)).with(Dead).id();
+///
+/// let plan = world.explain_query::<&Position, Without<Dead>>();
+/// assert_eq!(plan.matched_entities(), 0);
+/// println!("{}", plan);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlan {
+    /// Type name of the query
+    query: String,
+    /// Type name of the filter
+    filter: String,
+    /// One entry per archetype, in archetype order
+    archetypes: Vec<ArchetypePlan>,
+}
+
+/// How a query treats one archetype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypePlan {
+    /// The archetype
+    pub id: ArchetypeId,
+    /// Component type names of the archetype, sorted
+    pub components: Vec<String>,
+    /// Number of entities in the archetype
+    pub entities: usize,
+    /// Number of those entities the query would yield
+    pub matched: usize,
+    /// Whether the archetype is visited, and why not
+    pub outcome: ArchetypeMatch,
+}
+
+/// Whether a query visits an archetype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchetypeMatch {
+    /// The query visits the archetype
+    Matched,
+    /// The archetype of entities without components, which queries skip
+    Skipped,
+    /// The query fetches components the archetype lacks
+    MissingComponents(Vec<String>),
+    /// The filters rejected the archetype's entities, or its entities are
+    /// `Disabled`
+    Filtered(Vec<String>),
+}
+
+impl QueryPlan {
+    /// Dry-runs query `F` with filter `Fil` over `archetypes`.
+    pub(crate) fn explain<F, Fil>(archetypes: &ArchetypeManager, query: &str, filter: &str) -> Self
+    where
+        F: for<'a> Fetch<'a>,
+        Fil: for<'a> Filter<'a>,
+    {
+        let archetypes = archetypes
+            .iter()
+            .map(|archetype| {
+                let mut components: Vec<String> = archetype
+                    .component_info()
+                    .iter()
+                    .map(|info| short_type_name(info.type_name()))
+                    .collect();
+                components.sort();
+                let entities = archetype.entities();
+
+                let mut missing = Vec::new();
+                let mut matched = 0;
+                let outcome = if archetype.id().index() == 0 {
+                    ArchetypeMatch::Skipped
+                } else if !F::matches_archetype(archetype) {
+                    F::missing_components(archetype, &mut missing);
+                    ArchetypeMatch::MissingComponents(missing)
+                } else {
+                    matched = entities
+                        .iter()
+                        .filter(|&&entity| Fil::matches(archetype, entity))
+                        .count();
+
+                    // Explain with the first rejected entity, or a placeholder
+                    // for empty archetypes, since filters mostly check only the
+                    // archetype
+                    let mut rejected = Vec::new();
+                    let probe = entities
+                        .iter()
+                        .copied()
+                        .find(|&entity| !Fil::matches(archetype, entity))
+                        .unwrap_or(EntityId::new(0, 1));
+                    if matched == 0 {
+                        Fil::explain(archetype, probe, &mut rejected);
+                    }
+                    if !Fil::includes_disabled() && archetype.has_component::<Disabled>() {
+                        matched = 0;
+                        rejected.push("Disabled".to_string());
+                    }
+                    if rejected.is_empty() {
+                        ArchetypeMatch::Matched
+                    } else {
+                        ArchetypeMatch::Filtered(rejected)
+                    }
+                };
+
+                ArchetypePlan {
+                    id: archetype.id(),
+                    components,
+                    entities: entities.len(),
+                    matched,
+                    outcome,
+                }
+            })
+            .collect();
+
+        Self {
+            query: short_type_name(query),
+            filter: short_type_name(filter),
+            archetypes,
+        }
+    }
+
+    /// Returns the entry of every archetype, in archetype order.
+    pub fn archetypes(&self) -> &[ArchetypePlan] {
+        &self.archetypes
+    }
+
+    /// Returns the entries of the archetypes the query visits.
+    pub fn matched_archetypes(&self) -> impl Iterator<Item = &ArchetypePlan> {
+        self.archetypes
+            .iter()
+            .filter(|plan| plan.outcome == ArchetypeMatch::Matched)
+    }
+
+    /// Returns the number of entities the query would yield.
+    pub fn matched_entities(&self) -> usize {
+        self.archetypes.iter().map(|plan| plan.matched).sum()
+    }
+
+    /// Returns the entry of an archetype.
+    pub fn archetype(&self, id: ArchetypeId) -> Option<&ArchetypePlan> {
+        self.archetypes.iter().find(|plan| plan.id == id)
+    }
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "query {} filtered by {}", self.query, self.filter)?;
+        for plan in &self.archetypes {
+            write!(
+                f,
+                "  archetype {} [{}], {} entities: ",
+                plan.id.index(),
+                plan.components.join(", "),
+                plan.entities
+            )?;
+            match &plan.outcome {
+                ArchetypeMatch::Matched => writeln!(f, "matched {}", plan.matched)?,
+                ArchetypeMatch::Skipped => {
+                    writeln!(f, "skipped, entities without components are not queried")?
+                }
+                ArchetypeMatch::MissingComponents(missing) => {
+                    writeln!(f, "missing {}", missing.join(", "))?
+                }
+                ArchetypeMatch::Filtered(rejected) => {
+                    writeln!(f, "excluded by {}", rejected.join(", "))?
+                }
+            }
+        }
+        write!(
+            f,
+            "{} entities in {} of {} archetypes",
+            self.matched_entities(),
+            self.matched_archetypes().count(),
+            self.archetypes.len()
+        )
+    }
+}
+
+/// Strips module paths from a type name, so
+/// `pecs::query::filter::With<game::Dead>` becomes `With<Dead>`.
+pub(crate) fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for (index, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            continue;
+        }
+        short.push_str(last_segment(&name[segment_start..index]));
+        short.push(c);
+        segment_start = index + c.len_utf8();
+    }
+    short.push_str(last_segment(&name[segment_start..]));
+    short
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+    use crate::component::Component;
+    use crate::query::filter::{With, Without};
+
+    struct Position;
+    impl Component for Position {}
+    struct Frozen;
+    impl Component for Frozen {}
+    struct Hidden;
+    impl Component for Hidden {}
+
+    #[test]
+    fn explain_reports_every_rejecting_filter() {
+        let mut world = World::new();
+        world.spawn_empty();
+        world.spawn().with(Position).with(Frozen).with(Hidden).id();
+        world.spawn().with(Position).id();
+        world.spawn().with(Position).id();
+
+        let plan = world.explain_query::<&Position, (Without<Frozen>, Without<Hidden>)>();
+        assert_eq!(plan.matched_entities(), 2);
+        assert_eq!(
+            plan.archetype(ArchetypeId::new(0)).unwrap().outcome,
+            ArchetypeMatch::Skipped
+        );
+        let filtered: Vec<_> = plan
+            .archetypes()
+            .iter()
+            .filter_map(|archetype| match &archetype.outcome {
+                ArchetypeMatch::Filtered(rejected) => Some(rejected.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(filtered, [["Without<Frozen>", "Without<Hidden>"]]);
+
+        let text = plan.to_string();
+        assert!(
+            text.starts_with("query &Position filtered by (Without<Frozen>, Without<Hidden>)"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("[Position], 2 entities: matched 2"),
+            "{}",
+            text
+        );
+        assert!(
+            text.ends_with("2 entities in 1 of 3 archetypes"),
+            "{}",
+            text
+        );
+
+        let plan = world.explain_query::<(&Position, &Frozen), With<Hidden>>();
+        assert!(plan.archetypes().iter().any(|archetype| archetype.outcome
+            == ArchetypeMatch::MissingComponents(vec!["Frozen".to_string()])));
+    }
+
+    #[test]
+    fn short_type_names() {
+        assert_eq!(
+            short_type_name("pecs::query::filter::With<game::Dead>"),
+            "With<Dead>"
+        );
+        assert_eq!(
+            short_type_name("(&game::Position, core::option::Option<&mut a::B>)"),
+            "(&Position, Option<&mut B>)"
+        );
+    }
+}
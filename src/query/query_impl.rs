@@ -19,10 +19,10 @@
 //! This module provides implementations of the `Query` trait for various
 //! component access patterns, enabling type-safe queries over entities.
 
-use super::Query;
-use super::fetch::{FetchEntity, FetchOptional, FetchRead, FetchWrite};
+use super::fetch::{FetchAnyOf, FetchEntity, FetchOptional, FetchRead, FetchStableId, FetchWrite};
+use super::{AnyOf, Query};
 use crate::component::Component;
-use crate::entity::EntityId;
+use crate::entity::{EntityId, StableId};
 
 // ============================================================================
 // Single Component Queries
@@ -122,6 +122,35 @@ impl Query for EntityId {
     type Filter = ();
 }
 
+/// Query implementation for stable IDs.
+///
+/// Yields each entity's stable ID, borrowed from the entity allocator, so
+/// persistence and replication code can iterate with stable identity
+/// without a per-entity lookup.
+///
+/// # Examples
+///
+/// ```
+/// use pecs::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct Position { x: f32, y: f32 }
+/// impl Component for Position {}
+///
+/// let mut world = World::new();
+/// let entity = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
+/// let expected = world.get_stable_id(entity).unwrap();
+/// for (stable_id, pos) in world.query::<(&StableId, &Position)>() {
+///     assert_eq!(*stable_id, expected);
+///     println!("{}: ({}, {})", stable_id, pos.x, pos.y);
+/// }
+/// ```
+impl Query for &StableId {
+    type Item<'a> = &'a StableId;
+    type Fetch = FetchStableId;
+    type Filter = ();
+}
+
 // ============================================================================
 // Tuple Query Implementations
 // ============================================================================
@@ -148,6 +177,31 @@ impl_query_tuple!(A, B, C, D, E, F);
 impl_query_tuple!(A, B, C, D, E, F, G);
 impl_query_tuple!(A, B, C, D, E, F, G, H);
 
+// ============================================================================
+// Any-Of Query Implementations
+// ============================================================================
+
+// Macro to implement Query for any-of tuples
+macro_rules! impl_query_any_of {
+    ($($T:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($T: Query),*> Query for AnyOf<($($T,)*)> {
+            type Item<'a> = ($(Option<$T::Item<'a>>,)*);
+            type Fetch = FetchAnyOf<($($T::Fetch,)*)>;
+            type Filter = ();
+        }
+    };
+}
+
+impl_query_any_of!(A);
+impl_query_any_of!(A, B);
+impl_query_any_of!(A, B, C);
+impl_query_any_of!(A, B, C, D);
+impl_query_any_of!(A, B, C, D, E);
+impl_query_any_of!(A, B, C, D, E, F);
+impl_query_any_of!(A, B, C, D, E, F, G);
+impl_query_any_of!(A, B, C, D, E, F, G, H);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +251,7 @@ mod tests {
         _test::<(&Position, &Velocity)>();
         _test::<(&mut Position, &Velocity)>();
         _test::<(EntityId, &Position)>();
+        _test::<(&StableId, &Position)>();
     }
 
     #[test]
@@ -205,4 +260,12 @@ mod tests {
         _test::<(EntityId, &Position, &Velocity)>();
         _test::<(&mut Position, &Velocity, Option<&Position>)>();
     }
+
+    #[test]
+    fn query_any_of() {
+        fn _test<Q: Query>() {}
+        _test::<AnyOf<(&Position, &Velocity)>>();
+        _test::<AnyOf<(&mut Position, &Velocity)>>();
+        _test::<(EntityId, AnyOf<(&Position,)>)>();
+    }
 }
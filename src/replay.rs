@@ -0,0 +1,630 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Deterministic replay recording.
+//!
+//! A [`Recorder`] logs every mutation made through its [`RecordingWorld`]
+//! layer, including commands applied with
+//! [`RecordingWorld::apply_commands`], stamped with the tick it happened on.
+//! The resulting [`ReplayLog`] can be written to a file and replayed onto a
+//! fresh world. On `wasm32-unknown-unknown`, which has no file system, use
+//! [`ReplayLog::write`] and [`ReplayLog::read`] instead of the path-based
+//! `save` and `load`. Periodic snapshots of the world's state hash are stored in
+//! the log, and replay stops with [`ReplayError::Divergence`] as soon as the
+//! replayed world stops matching them.
+//!
+//! Components are recorded as JSON, so every component type that is
+//! inserted or removed must be registered with
+//! [`ComponentRegistry::register_json`]. Mutations the recorder cannot
+//! describe, such as unregistered components or custom commands, are noted
+//! in the log and make it fail to replay.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::component::Component;
+//! use pecs::component::registry::ComponentRegistry;
+//! use pecs::replay::Recorder;
+//! use pecs::World;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Score(u32);
+//! impl Component for Score {}
+//!
+//! ComponentRegistry::global().register_json::<Score>("Score");
+//!
+//! let mut world = World::new();
+//! let mut recorder = Recorder::new().with_snapshot_interval(1);
+//! for points in 0..3 {
+//!     let mut recording = recorder.record(&mut world);
+//!     let player = recording.spawn();
+//!     recording.insert(player, Score(points));
+//!     recorder.end_tick(&world);
+//! }
+//!
+//! let log = recorder.into_log();
+//! let mut replayed = World::new();
+//! log.replay(&mut replayed).unwrap();
+//! assert_eq!(replayed.len(), 3);
+//! ```
+
+use crate::World;
+use crate::component::registry::ComponentRegistry;
+use crate::component::{Component, ComponentTypeId};
+use crate::entity::{EntityId, StableId};
+use crate::persistence::{PersistenceError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::ops::Deref;
+
+/// A recorded world mutation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ReplayOp {
+    /// An empty entity was spawned.
+    Spawn {
+        /// The new entity.
+        entity: StableId,
+    },
+    /// An entity was despawned.
+    Despawn {
+        /// The despawned entity.
+        entity: StableId,
+    },
+    /// A component was inserted or replaced.
+    Insert {
+        /// The entity.
+        entity: StableId,
+        /// The component's registered JSON name.
+        component: String,
+        /// The component value.
+        value: serde_json::Value,
+    },
+    /// A component was removed.
+    Remove {
+        /// The entity.
+        entity: StableId,
+        /// The component's registered JSON name.
+        component: String,
+    },
+    /// The world's state hash at this point.
+    Snapshot {
+        /// Number of live entities.
+        entities: usize,
+        /// The [`state_hash`] of the world.
+        hash: u64,
+    },
+    /// A mutation the recorder could not describe.
+    Unrecorded {
+        /// What was applied.
+        description: String,
+    },
+}
+
+/// A mutation and the tick it happened on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// The tick, counted from zero.
+    pub tick: u64,
+    /// The mutation.
+    #[serde(flatten)]
+    pub op: ReplayOp,
+}
+
+/// Errors raised while replaying a log.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The replayed world's state hash no longer matches the snapshot.
+    Divergence {
+        /// Tick of the failing snapshot.
+        tick: u64,
+        /// Hash recorded in the log.
+        expected: u64,
+        /// Hash of the replayed world.
+        actual: u64,
+    },
+    /// The log contains a mutation that was not recorded.
+    Unrecorded {
+        /// Tick of the mutation.
+        tick: u64,
+        /// What was applied.
+        description: String,
+    },
+    /// An event refers to an entity that does not exist in the replayed
+    /// world.
+    UnknownEntity {
+        /// Tick of the event.
+        tick: u64,
+        /// The missing entity.
+        entity: StableId,
+    },
+    /// A component could not be applied.
+    Persistence(PersistenceError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Divergence {
+                tick,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Replay diverged at tick {}: expected state hash {:016x}, got {:016x}",
+                tick, expected, actual
+            ),
+            Self::Unrecorded { tick, description } => write!(
+                f,
+                "Replay cannot continue at tick {}: `{}` was not recorded",
+                tick, description
+            ),
+            Self::UnknownEntity { tick, entity } => {
+                write!(
+                    f,
+                    "Replay refers to unknown entity {} at tick {}",
+                    entity, tick
+                )
+            }
+            Self::Persistence(err) => write!(f, "Replay failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Persistence(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<PersistenceError> for ReplayError {
+    fn from(err: PersistenceError) -> Self {
+        Self::Persistence(err)
+    }
+}
+
+/// A recorded sequence of world mutations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayLog {
+    events: Vec<ReplayEvent>,
+}
+
+impl ReplayLog {
+    /// Returns the recorded events in order.
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// Returns true if every mutation was recorded, so the log can be
+    /// replayed.
+    pub fn is_replayable(&self) -> bool {
+        !self
+            .events
+            .iter()
+            .any(|event| matches!(event.op, ReplayOp::Unrecorded { .. }))
+    }
+
+    /// Applies the log to `world`, checking every snapshot.
+    ///
+    /// `world` is normally empty; replaying onto a world with other
+    /// registered components fails at the first snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error at the first event that cannot be applied or the
+    /// first snapshot that does not match.
+    pub fn replay(&self, world: &mut World) -> std::result::Result<(), ReplayError> {
+        let registry = ComponentRegistry::global();
+        for event in &self.events {
+            let tick = event.tick;
+            let lookup = |world: &World, entity: StableId| {
+                world
+                    .get_entity_by_stable_id(entity)
+                    .ok_or(ReplayError::UnknownEntity { tick, entity })
+            };
+            match &event.op {
+                ReplayOp::Spawn { entity } => {
+                    world.spawn_empty_with_stable_id(*entity).map_err(|err| {
+                        PersistenceError::EntityIdConflict(format!("{}: {:?}", entity, err))
+                    })?;
+                }
+                ReplayOp::Despawn { entity } => {
+                    let id = lookup(world, *entity)?;
+                    world.despawn(id);
+                }
+                ReplayOp::Insert {
+                    entity,
+                    component,
+                    value,
+                } => {
+                    let id = lookup(world, *entity)?;
+                    registry.insert_json(component, world, id, value.clone())?;
+                }
+                ReplayOp::Remove { entity, component } => {
+                    let id = lookup(world, *entity)?;
+                    registry.remove_json(component, world, id)?;
+                }
+                ReplayOp::Snapshot { hash, .. } => {
+                    let actual = state_hash(world);
+                    if actual != *hash {
+                        return Err(ReplayError::Divergence {
+                            tick,
+                            expected: *hash,
+                            actual,
+                        });
+                    }
+                }
+                ReplayOp::Unrecorded { description } => {
+                    return Err(ReplayError::Unrecorded {
+                        tick,
+                        description: description.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the log as JSON lines, one event per line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write(&self, writer: &mut dyn Write) -> Result<()> {
+        for event in &self.events {
+            serde_json::to_writer(&mut *writer, event)
+                .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Reads a log written by [`write`](Self::write).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or a line is not a valid event.
+    pub fn read(reader: &mut dyn Read) -> Result<Self> {
+        let mut events = Vec::new();
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line).map_err(|e| {
+                PersistenceError::Deserialization(format!("line {}: {}", number + 1, e))
+            })?;
+            events.push(event);
+        }
+        Ok(Self { events })
+    }
+
+    /// Writes the log to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a log from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is malformed.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::read(&mut std::fs::File::open(path)?)
+    }
+}
+
+/// Records world mutations into a [`ReplayLog`].
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    log: ReplayLog,
+    tick: u64,
+    snapshot_interval: u64,
+}
+
+impl Recorder {
+    /// Creates a recorder at tick zero that takes no automatic snapshots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a snapshot at the end of every `ticks` ticks. Zero disables
+    /// automatic snapshots.
+    pub fn with_snapshot_interval(mut self, ticks: u64) -> Self {
+        self.snapshot_interval = ticks;
+        self
+    }
+
+    /// Returns a layer over `world` whose mutations are recorded.
+    pub fn record<'a>(&'a mut self, world: &'a mut World) -> RecordingWorld<'a> {
+        RecordingWorld {
+            world,
+            recorder: self,
+        }
+    }
+
+    /// Records a snapshot of `world` at the current tick.
+    pub fn snapshot(&mut self, world: &World) {
+        self.push(ReplayOp::Snapshot {
+            entities: world.len(),
+            hash: state_hash(world),
+        });
+    }
+
+    /// Ends the current tick, taking a snapshot if one is due.
+    pub fn end_tick(&mut self, world: &World) {
+        if self.snapshot_interval > 0 && (self.tick + 1).is_multiple_of(self.snapshot_interval) {
+            self.snapshot(world);
+        }
+        self.tick += 1;
+    }
+
+    /// Returns the current tick.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the log recorded so far.
+    pub fn log(&self) -> &ReplayLog {
+        &self.log
+    }
+
+    /// Consumes the recorder, returning its log.
+    pub fn into_log(self) -> ReplayLog {
+        self.log
+    }
+
+    fn push(&mut self, op: ReplayOp) {
+        self.log.events.push(ReplayEvent {
+            tick: self.tick,
+            op,
+        });
+    }
+}
+
+/// A layer over a world that records every mutation made through it.
+///
+/// Reads go straight to the world through `Deref`.
+pub struct RecordingWorld<'a> {
+    world: &'a mut World,
+    recorder: &'a mut Recorder,
+}
+
+impl RecordingWorld<'_> {
+    /// Spawns an empty entity.
+    pub fn spawn(&mut self) -> EntityId {
+        let entity = self.world.spawn_empty();
+        if let Some(stable_id) = self.world.get_stable_id(entity) {
+            self.recorder.push(ReplayOp::Spawn { entity: stable_id });
+        }
+        entity
+    }
+
+    /// Despawns an entity. Returns false if it was not alive.
+    pub fn despawn(&mut self, entity: EntityId) -> bool {
+        let Some(stable_id) = self.world.get_stable_id(entity) else {
+            return false;
+        };
+        let despawned = self.world.despawn(entity);
+        if despawned {
+            self.recorder.push(ReplayOp::Despawn { entity: stable_id });
+        }
+        despawned
+    }
+
+    /// Inserts or replaces a component. Returns false if the entity is not
+    /// alive.
+    pub fn insert<T: Component>(&mut self, entity: EntityId, component: T) -> bool {
+        let Some(stable_id) = self.world.get_stable_id(entity) else {
+            return false;
+        };
+        if !self.world.insert(entity, component) {
+            return false;
+        }
+        let registry = ComponentRegistry::global();
+        let recorded = registry
+            .json_name_of(ComponentTypeId::of::<T>())
+            .and_then(|name| Some((registry.get_json(&name, self.world, entity)?, name)));
+        match recorded {
+            Some((value, component)) => self.recorder.push(ReplayOp::Insert {
+                entity: stable_id,
+                component,
+                value,
+            }),
+            None => self.note_unrecorded(format!("insert {}", std::any::type_name::<T>())),
+        }
+        true
+    }
+
+    /// Removes a component, returning it.
+    pub fn remove<T: Component>(&mut self, entity: EntityId) -> Option<T> {
+        let stable_id = self.world.get_stable_id(entity)?;
+        let component = self.world.remove::<T>(entity)?;
+        match ComponentRegistry::global().json_name_of(ComponentTypeId::of::<T>()) {
+            Some(name) => self.recorder.push(ReplayOp::Remove {
+                entity: stable_id,
+                component: name,
+            }),
+            None => self.note_unrecorded(format!("remove {}", std::any::type_name::<T>())),
+        }
+        Some(component)
+    }
+
+    /// Applies and records the world's pending commands.
+    pub fn apply_commands(&mut self) {
+        let mut commands = std::mem::take(self.world.commands());
+        commands.apply_recorded(self);
+        *self.world.commands() = commands;
+    }
+
+    /// Notes a mutation that cannot be replayed.
+    pub(crate) fn note_unrecorded(&mut self, description: String) {
+        self.recorder.push(ReplayOp::Unrecorded { description });
+    }
+
+    /// Returns the underlying world for applying unrecorded commands.
+    pub(crate) fn world_mut(&mut self) -> &mut World {
+        self.world
+    }
+}
+
+impl Deref for RecordingWorld<'_> {
+    type Target = World;
+
+    fn deref(&self) -> &World {
+        self.world
+    }
+}
+
+/// Hashes the entities and JSON-registered components of a world.
+///
+/// The hash depends only on stable IDs and component values, not on
+/// iteration order or entity handles, so two worlds built by the same
+/// mutations hash the same.
+pub fn state_hash(world: &World) -> u64 {
+    let registry = ComponentRegistry::global();
+    let names = registry.json_names();
+    let mut entries: Vec<(u128, &str, String)> = Vec::new();
+    for (entity, stable_id) in world.iter_entities() {
+        entries.push((stable_id.as_u128(), "", String::new()));
+        for name in &names {
+            if let Some(value) = registry.get_json(name, world, entity) {
+                entries.push((stable_id.as_u128(), name, value.to_string()));
+            }
+        }
+    }
+    entries.sort();
+
+    // FNV-1a, which is stable across platforms and releases
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    for (stable_id, name, value) in &entries {
+        feed(&stable_id.to_le_bytes());
+        feed(name.as_bytes());
+        feed(&[0]);
+        feed(value.as_bytes());
+        feed(&[0]);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter(u32);
+    impl Component for Counter {}
+
+    struct Opaque;
+    impl Component for Opaque {}
+
+    fn register() {
+        ComponentRegistry::global().register_json::<Counter>("replay::Counter");
+    }
+
+    fn record_session() -> (World, ReplayLog) {
+        register();
+        let mut world = World::new();
+        let mut recorder = Recorder::new().with_snapshot_interval(2);
+        let mut entities = Vec::new();
+        for tick in 0..5u32 {
+            let mut recording = recorder.record(&mut world);
+            let entity = recording.spawn();
+            recording.insert(entity, Counter(tick));
+            entities.push(entity);
+            if tick == 2 {
+                recording.despawn(entities[0]);
+                recording.remove::<Counter>(entities[1]);
+            }
+            recording
+                .world
+                .commands()
+                .insert(entity, Counter(tick * 10));
+            recording.world.commands().spawn();
+            recording.apply_commands();
+            recorder.end_tick(&world);
+        }
+        (world, recorder.into_log())
+    }
+
+    #[test]
+    fn replay_reproduces_world() {
+        let (world, log) = record_session();
+        assert!(log.is_replayable());
+        assert_eq!(log.events().last().unwrap().tick, 4);
+
+        let mut file = Vec::new();
+        log.write(&mut file).unwrap();
+        let log = ReplayLog::read(&mut file.as_slice()).unwrap();
+
+        let mut replayed = World::new();
+        log.replay(&mut replayed).unwrap();
+        assert_eq!(replayed.len(), world.len());
+        assert_eq!(state_hash(&replayed), state_hash(&world));
+    }
+
+    #[test]
+    fn divergence_is_detected() {
+        let (_, log) = record_session();
+
+        let mut world = World::new();
+        world.spawn().with(Counter(7)).id();
+        let err = log.replay(&mut world).unwrap_err();
+        assert!(matches!(err, ReplayError::Divergence { tick: 1, .. }));
+    }
+
+    #[test]
+    fn unrecorded_mutations_fail_replay() {
+        struct Custom;
+        impl Command for Custom {
+            unsafe fn apply(self: Box<Self>, _world: *mut World) {}
+        }
+
+        let mut world = World::new();
+        let mut recorder = Recorder::new();
+        let mut recording = recorder.record(&mut world);
+        let entity = recording.spawn();
+        recording.insert(entity, Opaque);
+        recording.world.commands().push(Custom);
+        recording.apply_commands();
+
+        let log = recorder.into_log();
+        assert!(!log.is_replayable());
+        let err = log.replay(&mut World::new()).unwrap_err();
+        assert!(matches!(err, ReplayError::Unrecorded { tick: 0, .. }));
+    }
+}
@@ -0,0 +1,136 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! World-level singleton data.
+//!
+//! Resources are values that exist once per world rather than once per
+//! entity: a frame clock, game settings, a random number generator. Any
+//! `Send + Sync + 'static` type can be a resource; there is at most one
+//! resource of each type.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::World;
+//!
+//! struct Time {
+//!     delta: f32,
+//! }
+//!
+//! let mut world = World::new();
+//! world.insert_resource(Time { delta: 0.016 });
+//!
+//! world.resource_mut::<Time>().unwrap().delta = 0.033;
+//! assert_eq!(world.resource::<Time>().unwrap().delta, 0.033);
+//! ```
+
+pub mod rng;
+
+pub use rng::Rng;
+
+use std::any::{Any, TypeId};
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+
+/// Marker for types that can be stored as world resources.
+///
+/// Implemented automatically for every `Send + Sync + 'static` type.
+pub trait Resource: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> Resource for T {}
+
+/// A resource value that can be borrowed mutably through a shared
+/// reference by callers that guarantee exclusivity.
+struct ResourceCell<R>(UnsafeCell<R>);
+
+// SAFETY: `R` is Send + Sync; the cell is only mutated through `&mut` or by
+// callers of the unsafe accessors, who guarantee exclusive access.
+unsafe impl<R: Resource> Send for ResourceCell<R> {}
+// SAFETY: As above
+unsafe impl<R: Resource> Sync for ResourceCell<R> {}
+
+/// Type-keyed storage for resources.
+#[derive(Default)]
+pub(crate) struct Resources {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Resources {
+    pub fn insert<R: Resource>(&mut self, value: R) -> Option<R> {
+        self.values
+            .insert(
+                TypeId::of::<R>(),
+                Box::new(ResourceCell(UnsafeCell::new(value))),
+            )
+            .map(Self::unwrap)
+    }
+
+    pub fn remove<R: Resource>(&mut self) -> Option<R> {
+        self.values.remove(&TypeId::of::<R>()).map(Self::unwrap)
+    }
+
+    pub fn contains<R: Resource>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<R>())
+    }
+
+    pub fn get<R: Resource>(&self) -> Option<&R> {
+        // SAFETY: Shared access through `&self`; writers need `&mut self`
+        // or the unsafe accessor, whose callers rule out this borrow
+        self.cell::<R>().map(|cell| unsafe { &*cell.0.get() })
+    }
+
+    pub fn get_mut<R: Resource>(&mut self) -> Option<&mut R> {
+        self.values
+            .get_mut(&TypeId::of::<R>())
+            .and_then(|value| value.downcast_mut::<ResourceCell<R>>())
+            .map(|cell| cell.0.get_mut())
+    }
+
+    /// # Safety
+    ///
+    /// No other reference to the resource may exist while the returned
+    /// borrow is alive.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_unchecked_mut<R: Resource>(&self) -> Option<&mut R> {
+        // SAFETY: Guaranteed by the caller
+        self.cell::<R>().map(|cell| unsafe { &mut *cell.0.get() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn cell<R: Resource>(&self) -> Option<&ResourceCell<R>> {
+        self.values
+            .get(&TypeId::of::<R>())
+            .and_then(|value| value.downcast_ref::<ResourceCell<R>>())
+    }
+
+    fn unwrap<R: Resource>(value: Box<dyn Any + Send + Sync>) -> R {
+        let cell = value
+            .downcast::<ResourceCell<R>>()
+            .expect("resource stored under the wrong type");
+        cell.0.into_inner()
+    }
+}
+
+impl std::fmt::Debug for Resources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resources")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
@@ -0,0 +1,199 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Deterministic random numbers.
+//!
+//! [`Rng`] is a seedable xoshiro256** generator meant to be stored as a
+//! world resource. When present, its state is written to the save's
+//! metadata and restored on load, so a loaded world continues the exact
+//! random sequence the saved world would have produced.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::{Rng, World};
+//!
+//! let mut world = World::new();
+//! world.insert_resource(Rng::seed_from_u64(7));
+//!
+//! let roll = world.resource_mut::<Rng>().unwrap().range(1..7);
+//! assert!((1..7).contains(&roll));
+//! ```
+
+use crate::persistence::MetadataExtension;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// A seedable, serializable pseudo-random number generator.
+///
+/// The same seed always produces the same sequence on every platform.
+/// This generator is not suitable for cryptography.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl MetadataExtension for Rng {
+    const NAME: &'static str = "pecs.rng";
+}
+
+impl Rng {
+    /// Creates a generator from a 64-bit seed.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        // Expand the seed with SplitMix64, as recommended for xoshiro
+        let mut seed = seed;
+        let mut next = || {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Self {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+
+    /// Returns the next 32 random bits.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a float uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a float uniformly distributed in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns an integer uniformly distributed in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            range.start < range.end,
+            "cannot sample empty range {range:?}"
+        );
+        let span = range.end - range.start;
+        // Lemire's multiply-and-reject method, without modulo bias
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(span);
+            if (product as u64) >= threshold {
+                return range.start + (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Returns true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Shuffles `slice` in place.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.range(0..i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a random element of `slice`, or `None` if it is empty.
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            None
+        } else {
+            slice.get(self.range(0..slice.len() as u64) as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+
+    #[test]
+    fn seeded_sequences_repeat() {
+        let mut a = Rng::seed_from_u64(42);
+        let mut b = Rng::seed_from_u64(42);
+        let mut c = Rng::seed_from_u64(43);
+
+        let sequence: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        assert_eq!(sequence, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+
+        for _ in 0..1000 {
+            assert!((10..20).contains(&a.range(10..20)));
+            assert!((0.0..1.0).contains(&a.next_f64()));
+            assert!((0.0..1.0).contains(&a.next_f32()));
+        }
+
+        let mut items = [1, 2, 3, 4, 5];
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        assert!(a.choose::<u8>(&[]).is_none());
+    }
+
+    #[test]
+    fn state_survives_save_and_load() {
+        let mut world = World::new();
+        let mut rng = Rng::seed_from_u64(9);
+        rng.next_u64();
+        world.insert_resource(rng);
+
+        let mut binary = Vec::new();
+        world.save_binary(&mut binary).unwrap();
+        let mut json = Vec::new();
+        world.save_json(&mut json).unwrap();
+
+        let expected: Vec<u64> = {
+            let rng = world.resource_mut::<Rng>().unwrap();
+            (0..4).map(|_| rng.next_u64()).collect()
+        };
+        for mut loaded in [
+            World::load_binary(&mut binary.as_slice()).unwrap(),
+            World::load_json(&mut json.as_slice()).unwrap(),
+        ] {
+            let rng = loaded.resource_mut::<Rng>().unwrap();
+            assert_eq!((0..4).map(|_| rng.next_u64()).collect::<Vec<_>>(), expected);
+        }
+
+        world.remove_resource::<Rng>();
+        world.save_binary(&mut Vec::new()).unwrap();
+        assert!(!world.metadata().has_ext::<Rng>());
+    }
+}
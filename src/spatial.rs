@@ -0,0 +1,336 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Uniform-grid spatial partitioning.
+//!
+//! This module is available with the `spatial` feature. A [`SpatialGrid`]
+//! buckets entities into cubic cells based on a position read from a
+//! registered component, so range queries only visit nearby cells instead of
+//! every entity.
+//!
+//! The grid is kept in sync with the same change detection that drives
+//! [`ComponentIndex`](crate::component::index::ComponentIndex): inserting,
+//! removing or mutably borrowing the position component marks the entity
+//! dirty, and dirty entities are re-bucketed the next time the grid is read
+//! through [`World::spatial`](crate::World::spatial). Mutations made through
+//! query iterators are picked up by
+//! [`World::rebuild_spatial`](crate::World::rebuild_spatial).
+//!
+//! Two-dimensional games can return `0.0` for the third coordinate.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::prelude::*;
+//!
+//! #[derive(Debug)]
+//! struct Position { x: f32, y: f32 }
+//! impl Component for Position {}
+//!
+//! let mut world = World::new();
+//! world.register_spatial(8.0, |p: &Position| [p.x, p.y, 0.0]);
+//!
+//! let near = world.spawn().with(Position { x: 1.0, y: 1.0 }).id();
+//! let far = world.spawn().with(Position { x: 100.0, y: 100.0 }).id();
+//!
+//! let found: Vec<_> = world.spatial().query_radius([0.0, 0.0, 0.0], 5.0).collect();
+//! assert_eq!(found, vec![near]);
+//!
+//! let found: Vec<_> = world
+//!     .spatial()
+//!     .query_aabb([50.0, 50.0, -1.0], [150.0, 150.0, 1.0])
+//!     .collect();
+//! assert_eq!(found, vec![far]);
+//! ```
+
+use crate::component::archetype::ArchetypeManager;
+use crate::component::index::AnyComponentIndex;
+use crate::component::{Component, ComponentTypeId};
+use crate::entity::EntityId;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+/// A point in world space.
+pub type Point = [f32; 3];
+
+/// Integer coordinates of a grid cell.
+type Cell = [i32; 3];
+
+/// Reads an entity's position out of archetype storage.
+type PositionFn = Box<dyn Fn(&ArchetypeManager, EntityId) -> Option<Point> + Send + Sync>;
+
+/// A uniform grid of entity positions.
+///
+/// Created with [`World::register_spatial`](crate::World::register_spatial).
+pub struct SpatialGrid {
+    /// Edge length of each cubic cell
+    cell_size: f32,
+
+    /// Component type the positions are read from
+    component_type: ComponentTypeId,
+
+    /// Extracts a position for an entity
+    position_of: PositionFn,
+
+    /// Entities bucketed by cell
+    cells: HashMap<Cell, Vec<EntityId>>,
+
+    /// Current position of every tracked entity
+    positions: HashMap<EntityId, Point>,
+
+    /// Entities whose position must be re-read
+    dirty: HashSet<EntityId>,
+}
+
+impl SpatialGrid {
+    /// Creates an empty grid reading positions from component `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` is not positive and finite.
+    pub(crate) fn new<T: Component>(cell_size: f32, position: fn(&T) -> Point) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "cell size must be positive and finite"
+        );
+        Self {
+            cell_size,
+            component_type: ComponentTypeId::of::<T>(),
+            position_of: Box::new(move |archetypes, entity| {
+                let location = archetypes.get_entity_location(entity)?;
+                let archetype = archetypes.get_archetype(location.archetype_id)?;
+                // SAFETY: get_component checks the entity's row and the column type
+                unsafe { archetype.get_component::<T>(entity) }.map(position)
+            }),
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Returns the edge length of each grid cell.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the number of tracked entities.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if no entities are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the last known position of an entity.
+    pub fn position(&self, entity: EntityId) -> Option<Point> {
+        self.positions.get(&entity).copied()
+    }
+
+    /// Returns the entities whose position lies within the box `[min, max]`.
+    ///
+    /// Bounds are inclusive on every axis.
+    pub fn query_aabb(&self, min: Point, max: Point) -> impl Iterator<Item = EntityId> + '_ {
+        self.candidates(min, max)
+            .filter(move |(_, p)| (0..3).all(|axis| p[axis] >= min[axis] && p[axis] <= max[axis]))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Returns the entities within `radius` of `center`.
+    pub fn query_radius(&self, center: Point, radius: f32) -> impl Iterator<Item = EntityId> + '_ {
+        let min = [center[0] - radius, center[1] - radius, center[2] - radius];
+        let max = [center[0] + radius, center[1] + radius, center[2] + radius];
+        let radius_squared = radius * radius;
+        self.candidates(min, max)
+            .filter(move |(_, p)| {
+                let d = [p[0] - center[0], p[1] - center[1], p[2] - center[2]];
+                d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= radius_squared
+            })
+            .map(|(entity, _)| entity)
+    }
+
+    /// Yields every entity in the cells overlapping `[min, max]`.
+    ///
+    /// Large ranges walk the occupied cells instead of every cell in range.
+    fn candidates(
+        &self,
+        min: Point,
+        max: Point,
+    ) -> Box<dyn Iterator<Item = (EntityId, Point)> + '_> {
+        let lo = self.cell_of(min);
+        let hi = self.cell_of(max);
+        let span = (0..3)
+            .map(|axis| (hi[axis] as i64 - lo[axis] as i64 + 1).max(0) as u64)
+            .fold(1u64, u64::saturating_mul);
+
+        let with_point = |&entity: &EntityId| (entity, self.positions[&entity]);
+        if span > self.cells.len() as u64 {
+            Box::new(
+                self.cells
+                    .iter()
+                    .filter(move |(cell, _)| (0..3).all(|a| cell[a] >= lo[a] && cell[a] <= hi[a]))
+                    .flat_map(|(_, bucket)| bucket)
+                    .map(with_point),
+            )
+        } else {
+            Box::new(
+                (lo[0]..=hi[0])
+                    .flat_map(move |x| {
+                        (lo[1]..=hi[1]).flat_map(move |y| (lo[2]..=hi[2]).map(move |z| [x, y, z]))
+                    })
+                    .filter_map(|cell| self.cells.get(&cell))
+                    .flatten()
+                    .map(with_point),
+            )
+        }
+    }
+
+    fn cell_of(&self, point: Point) -> Cell {
+        point.map(|v| (v / self.cell_size).floor() as i32)
+    }
+
+    /// Re-reads the position of every entity that has the component.
+    pub(crate) fn rebuild(&mut self, archetypes: &ArchetypeManager) {
+        self.clear();
+        for archetype in archetypes.iter() {
+            if !archetype.has_component_by_id(self.component_type) {
+                continue;
+            }
+            for &entity in archetype.entities() {
+                if let Some(point) = (self.position_of)(archetypes, entity) {
+                    self.insert_point(entity, point);
+                }
+            }
+        }
+    }
+
+    fn refresh_entity(&mut self, archetypes: &ArchetypeManager, entity: EntityId) {
+        let point = (self.position_of)(archetypes, entity);
+        if self.positions.get(&entity) == point.as_ref() {
+            return;
+        }
+        self.remove_point(entity);
+        if let Some(point) = point {
+            self.insert_point(entity, point);
+        }
+    }
+
+    fn insert_point(&mut self, entity: EntityId, point: Point) {
+        self.cells
+            .entry(self.cell_of(point))
+            .or_default()
+            .push(entity);
+        self.positions.insert(entity, point);
+    }
+
+    fn remove_point(&mut self, entity: EntityId) {
+        let Some(point) = self.positions.remove(&entity) else {
+            return;
+        };
+        let cell = self.cell_of(point);
+        if let Some(bucket) = self.cells.get_mut(&cell) {
+            bucket.retain(|&e| e != entity);
+            if bucket.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+}
+
+impl AnyComponentIndex for SpatialGrid {
+    fn mark_dirty(&mut self, entity: EntityId) {
+        self.dirty.insert(entity);
+    }
+
+    fn refresh(&mut self, archetypes: &ArchetypeManager) {
+        for entity in std::mem::take(&mut self.dirty) {
+            self.refresh_entity(archetypes, entity);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+        self.positions.clear();
+        self.dirty.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::World;
+    use crate::component::Component;
+
+    #[derive(Debug)]
+    struct Pos(f32, f32, f32);
+    impl Component for Pos {}
+
+    fn pos(p: &Pos) -> [f32; 3] {
+        [p.0, p.1, p.2]
+    }
+
+    #[test]
+    fn queries_respect_exact_bounds() {
+        let mut world = World::new();
+        world.register_spatial(4.0, pos);
+        let a = world.spawn().with(Pos(1.0, 1.0, 1.0)).id();
+        let b = world.spawn().with(Pos(3.9, 0.0, 0.0)).id();
+        let c = world.spawn().with(Pos(-5.0, -5.0, 0.0)).id();
+
+        let mut found: Vec<_> = world.spatial().query_radius([0.0; 3], 2.0).collect();
+        found.sort_by_key(|e| e.index());
+        assert_eq!(found, vec![a]);
+
+        let mut found: Vec<_> = world
+            .spatial()
+            .query_aabb([-6.0, -6.0, -1.0], [4.0, 0.5, 0.5])
+            .collect();
+        found.sort_by_key(|e| e.index());
+        assert_eq!(found, vec![b, c]);
+    }
+
+    #[test]
+    fn grid_follows_moves_and_despawns() {
+        let mut world = World::new();
+        let a = world.spawn().with(Pos(0.0, 0.0, 0.0)).id();
+        world.register_spatial(1.0, pos);
+        assert_eq!(world.spatial().len(), 1);
+
+        world.get_mut::<Pos>(a).unwrap().0 = 10.0;
+        assert_eq!(world.spatial().query_radius([0.0; 3], 1.0).count(), 0);
+        assert_eq!(world.spatial().position(a), Some([10.0, 0.0, 0.0]));
+
+        for p in world.query::<&mut Pos>() {
+            p.0 = 20.0;
+        }
+        world.rebuild_spatial();
+        assert_eq!(
+            world.spatial().query_radius([20.0, 0.0, 0.0], 0.5).count(),
+            1
+        );
+
+        world.despawn(a);
+        assert!(world.spatial().is_empty());
+    }
+}
@@ -47,11 +47,37 @@
 //! }
 //! ```
 
+pub mod builder;
+mod graph;
+mod revision;
+pub mod sub_world;
+pub mod transaction;
+mod validate;
+
+pub use builder::WorldBuilder;
+pub use sub_world::{AccessConflict, SubWorld};
+pub use transaction::Transaction;
+pub use validate::ConsistencyError;
+
+use revision::Revisions;
+
+use crate::bundle::Bundle;
 use crate::command::CommandBuffer;
-use crate::component::archetype::{ArchetypeId, ArchetypeManager};
-use crate::component::{Component, ComponentInfo, ComponentSet, ComponentTypeId};
-use crate::entity::{EntityId, EntityManager, StableId};
-use crate::persistence::{PersistenceManager, WorldMetadata};
+use crate::component::archetype::{Archetype, ArchetypeId, ArchetypeManager};
+use crate::component::index::{AnyComponentIndex, ComponentIndex};
+use crate::component::memory::{Alloc, MemoryError};
+use crate::component::registry::ComponentRegistry;
+use crate::component::{
+    Component, ComponentInfo, ComponentSet, ComponentTypeId, Disabled, InsertError,
+};
+use crate::entity::{EntityId, EntityManager, StableId, StableIdGenerator};
+use crate::persistence::snapshot::{History, WorldSnapshot};
+use crate::persistence::{ComponentTypeInfo, PersistenceManager, WorldMetadata};
+use crate::resource::{Resource, Resources, Rng};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
 
 /// The main ECS world.
 ///
@@ -64,9 +90,39 @@ use crate::persistence::{PersistenceManager, WorldMetadata};
 ///
 /// # Thread Safety
 ///
-/// `World` is not `Send` or `Sync` by default, as it's designed for
-/// single-threaded use. For parallel system execution, use command buffers
-/// to record operations from multiple threads, then apply them to the world.
+/// `World` is `Send` and `Sync`: it can be built on a background thread
+/// (for example by a loader) and moved to the main thread, or shared as
+/// `Arc<RwLock<World>>`. Mutation needs `&mut World`, so shared access is
+/// read-only; the few `&self` methods with interior effects, such as
+/// [`reserve_entity`](Self::reserve_entity), are synchronized internally.
+///
+/// For parallel work on one world, use command buffers to record
+/// operations from multiple threads and apply them later, or
+/// [`split`](Self::split) the world into disjoint [`SubWorld`]s.
+///
+/// ```
+/// use pecs::prelude::*;
+/// use std::sync::{Arc, RwLock};
+///
+/// #[derive(Debug)]
+/// struct Position(f32);
+/// impl Component for Position {}
+///
+/// let loaded = std::thread::spawn(|| {
+///     let mut world = World::new();
+///     world.spawn().with(Position(1.0)).id();
+///     world
+/// })
+/// .join()
+/// .unwrap();
+///
+/// let shared = Arc::new(RwLock::new(loaded));
+/// let reader = Arc::clone(&shared);
+/// let count = std::thread::spawn(move || reader.read().unwrap().len())
+///     .join()
+///     .unwrap();
+/// assert_eq!(count, 1);
+/// ```
 pub struct World {
     /// Entity management
     entities: EntityManager,
@@ -82,6 +138,24 @@ pub struct World {
 
     /// World metadata for persistence
     metadata: WorldMetadata,
+
+    /// Whether queries iterate in entity ID order
+    deterministic_iteration: bool,
+
+    /// Timestamp written by saves instead of the current time, if pinned
+    fixed_timestamp: Option<u64>,
+
+    /// Registered component value indexes, keyed by index type
+    indexes: HashMap<TypeId, Box<dyn AnyComponentIndex>>,
+
+    /// World-level singleton values
+    resources: Resources,
+
+    /// Per-tick snapshots for rewinding, when enabled
+    history: Option<History>,
+
+    /// Revision counters of changed components
+    revisions: Revisions,
 }
 
 impl World {
@@ -99,8 +173,14 @@ impl World {
             entities: EntityManager::new(),
             archetypes: ArchetypeManager::new(),
             commands: CommandBuffer::new(),
-            persistence: PersistenceManager::new(),
+            persistence: PersistenceManager::with_default_plugins(),
             metadata: WorldMetadata::new(1, 0, Vec::new()),
+            deterministic_iteration: false,
+            fixed_timestamp: None,
+            indexes: HashMap::new(),
+            resources: Resources::default(),
+            history: None,
+            revisions: Revisions::default(),
         }
     }
 
@@ -122,11 +202,78 @@ impl World {
             entities: EntityManager::with_capacity(entity_capacity),
             archetypes: ArchetypeManager::new(),
             commands: CommandBuffer::with_capacity(entity_capacity),
-            persistence: PersistenceManager::new(),
+            persistence: PersistenceManager::with_default_plugins(),
             metadata: WorldMetadata::new(1, 0, Vec::new()),
+            deterministic_iteration: false,
+            fixed_timestamp: None,
+            indexes: HashMap::new(),
+            resources: Resources::default(),
+            history: None,
+            revisions: Revisions::default(),
+        }
+    }
+
+    /// Creates a new empty world whose component columns allocate from
+    /// `allocator`.
+    ///
+    /// Use this to place component data in an arena or a tracked heap.
+    /// Entity tables and indexes still use the global allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::memory::{Alloc, Global};
+    /// use std::alloc::Layout;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// #[derive(Default)]
+    /// struct Counting(AtomicUsize);
+    ///
+    /// unsafe impl Alloc for Counting {
+    ///     fn allocate(&self, layout: Layout) -> *mut u8 {
+    ///         self.0.fetch_add(1, Ordering::Relaxed);
+    ///         Global.allocate(layout)
+    ///     }
+    ///
+    ///     unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+    ///         unsafe { Global.deallocate(ptr, layout) }
+    ///     }
+    /// }
+    ///
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let allocator = Arc::new(Counting::default());
+    /// let mut world = World::with_allocator(allocator.clone());
+    /// world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+    /// assert!(allocator.0.load(Ordering::Relaxed) > 0);
+    /// ```
+    pub fn with_allocator(allocator: Arc<dyn Alloc>) -> Self {
+        Self {
+            archetypes: ArchetypeManager::with_allocator(allocator),
+            ..Self::new()
         }
     }
 
+    /// Returns a builder for configuring a world before it is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let world = World::builder()
+    ///     .capacity(1000)
+    ///     .change_tracking(false)
+    ///     .build();
+    /// assert!(!world.is_change_tracking());
+    /// ```
+    pub fn builder() -> WorldBuilder {
+        WorldBuilder::new()
+    }
+
     /// Spawns a new entity, returning an entity builder.
     ///
     /// The entity builder allows you to add components before the entity
@@ -147,6 +294,7 @@ impl World {
     ///     .id();
     /// ```
     pub fn spawn(&mut self) -> EntityBuilder<'_> {
+        self.flush_reserved();
         let (entity_id, stable_id) = self.entities.spawn_with_stable_id();
 
         // Track entity creation for persistence
@@ -176,12 +324,20 @@ impl World {
     /// let entity = world.spawn_empty();
     /// ```
     pub fn spawn_empty(&mut self) -> EntityId {
+        self.flush_reserved();
         let entity_id = self.entities.spawn();
 
         // Add to empty archetype
         let empty_archetype_id = ArchetypeId::new(0);
         if let Some(archetype) = self.archetypes.get_archetype_mut(empty_archetype_id) {
-            archetype.allocate_row(entity_id);
+            let row = archetype.allocate_row(entity_id);
+            self.archetypes.set_entity_location(
+                entity_id,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: empty_archetype_id,
+                    row,
+                },
+            );
         }
 
         // Track entity creation for persistence
@@ -192,6 +348,31 @@ impl World {
         entity_id
     }
 
+    /// Spawns `count` entities without components.
+    ///
+    /// Equivalent to calling [`spawn_empty`](Self::spawn_empty) `count`
+    /// times, but reserves entity slots and archetype rows once up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let entities = world.spawn_empty_batch(100);
+    /// assert_eq!(entities.len(), 100);
+    /// assert_eq!(world.len(), 100);
+    /// ```
+    pub fn spawn_empty_batch(&mut self, count: usize) -> Vec<EntityId> {
+        self.flush_reserved();
+        self.entities.reserve(count);
+        if let Some(archetype) = self.archetypes.get_archetype_mut(ArchetypeId::new(0)) {
+            // The empty archetype has no columns, so this cannot fail
+            let _ = archetype.try_reserve(count);
+        }
+        (0..count).map(|_| self.spawn_empty()).collect()
+    }
+
     /// Spawns an entity with a specific stable ID.
     ///
     /// This is useful for deserialization or when you need to restore entities
@@ -228,6 +409,7 @@ impl World {
         &mut self,
         stable_id: StableId,
     ) -> Result<EntityBuilder<'_>, crate::entity::EntityError> {
+        self.flush_reserved();
         let entity_id = self.entities.spawn_with_id(stable_id)?;
 
         // Track entity creation for persistence
@@ -243,6 +425,41 @@ impl World {
         })
     }
 
+    /// Spawns a sub-entity whose stable ID is derived from its parent's.
+    ///
+    /// The stable ID is [`StableId::child_of`] the parent's stable ID and
+    /// `ordinal`, so rebuilding the parent reproduces its sub-entities' IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EntityError::InvalidEntity`](crate::entity::EntityError::InvalidEntity)
+    /// if the parent is not alive, or
+    /// [`EntityError::DuplicateStableId`](crate::entity::EntityError::DuplicateStableId)
+    /// if the parent already has a sub-entity with that ordinal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// let mut world = World::new();
+    /// let ship = world.spawn_empty();
+    /// let turret = world.spawn_child_of(ship, 0).unwrap().id();
+    ///
+    /// let ship_id = world.get_stable_id(ship).unwrap();
+    /// assert_eq!(world.get_stable_id(turret), Some(StableId::child_of(ship_id, 0)));
+    /// ```
+    pub fn spawn_child_of(
+        &mut self,
+        parent: EntityId,
+        ordinal: u64,
+    ) -> Result<EntityBuilder<'_>, crate::entity::EntityError> {
+        let parent = self
+            .get_stable_id(parent)
+            .ok_or(crate::entity::EntityError::InvalidEntity)?;
+        self.spawn_with_stable_id(StableId::child_of(parent, ordinal))
+    }
+
     /// Spawns an empty entity with a specific stable ID.
     ///
     /// This is faster than using the builder if you don't need to add
@@ -270,12 +487,20 @@ impl World {
         &mut self,
         stable_id: StableId,
     ) -> Result<EntityId, crate::entity::EntityError> {
+        self.flush_reserved();
         let entity_id = self.entities.spawn_with_id(stable_id)?;
 
         // Add to empty archetype
         let empty_archetype_id = ArchetypeId::new(0);
         if let Some(archetype) = self.archetypes.get_archetype_mut(empty_archetype_id) {
-            archetype.allocate_row(entity_id);
+            let row = archetype.allocate_row(entity_id);
+            self.archetypes.set_entity_location(
+                entity_id,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: empty_archetype_id,
+                    row,
+                },
+            );
         }
 
         // Track entity creation for persistence
@@ -307,24 +532,303 @@ impl World {
     /// assert!(!world.is_alive(entity));
     /// ```
     pub fn despawn(&mut self, entity: EntityId) -> bool {
+        self.flush_reserved();
         if !self.entities.is_alive(entity) {
             return false;
         }
 
         // Track entity deletion for persistence
-        self.persistence.change_tracker_mut().track_deleted(entity);
+        self.track_deleted(entity);
 
         // Remove from archetype
         if let Some(location) = self.archetypes.get_entity_location(entity)
             && let Some(archetype) = self.archetypes.get_archetype_mut(location.archetype_id)
+            && let Some(row) = archetype.get_entity_row(entity)
+            && let Some(moved) = archetype.remove_entity(entity)
         {
-            archetype.remove_entity(entity);
+            // The archetype's last entity took over the vacated row
+            self.archetypes.set_entity_location(
+                moved,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: location.archetype_id,
+                    row,
+                },
+            );
         }
 
         // Remove from entity manager
         self.entities.despawn(entity)
     }
 
+    /// Despawns many entities at once.
+    ///
+    /// Targets are grouped by archetype and each archetype swap-removes its
+    /// rows in one pass, fixing up the locations of the entities moved into
+    /// the vacated rows once. This is much faster than calling
+    /// [`despawn`](Self::despawn) in a loop for mass cleanups. Dead and
+    /// duplicate entities are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities that were alive and have been despawned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// let mut world = World::new();
+    /// let bullets: Vec<_> = (0..10).map(|_| world.spawn_empty()).collect();
+    /// let survivor = world.spawn_empty();
+    ///
+    /// assert_eq!(world.despawn_many(bullets.iter().copied()), 10);
+    /// assert_eq!(world.despawn_many(bullets), 0);
+    /// assert!(world.is_alive(survivor));
+    /// ```
+    pub fn despawn_many(&mut self, entities: impl IntoIterator<Item = EntityId>) -> usize {
+        self.flush_reserved();
+        let mut targets = Vec::new();
+        let mut despawned = 0;
+
+        for entity in entities {
+            if !self.entities.is_alive(entity) {
+                continue;
+            }
+            self.track_deleted(entity);
+            if let Some(location) = self.archetypes.remove_entity_location(entity) {
+                targets.push((location.archetype_id, entity));
+            }
+            self.entities.despawn(entity);
+            despawned += 1;
+        }
+
+        targets.sort_unstable_by_key(|(archetype_id, _)| archetype_id.index());
+        let mut doomed = Vec::new();
+        for group in targets.chunk_by(|a, b| a.0 == b.0) {
+            let archetype_id = group[0].0;
+            doomed.clear();
+            doomed.extend(group.iter().map(|&(_, entity)| entity));
+            let Some(archetype) = self.archetypes.get_archetype_mut(archetype_id) else {
+                continue;
+            };
+            for (moved, row) in archetype.remove_entities(&doomed) {
+                self.archetypes.set_entity_location(
+                    moved,
+                    crate::component::archetype::EntityLocation { archetype_id, row },
+                );
+            }
+        }
+
+        despawned
+    }
+
+    /// Spawns a copy of an entity with all of its cloneable components.
+    ///
+    /// Components are cloned through their [`ComponentInfo`] vtable, so
+    /// only types with a registered `Clone` impl (`#[component(clone)]` or
+    /// [`ComponentRegistry::register_clone`]) are copied; other components
+    /// are left out. The copy gets a fresh [`StableId`].
+    ///
+    /// # Returns
+    ///
+    /// The new entity, or `None` if `entity` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component, Debug, Clone, PartialEq)]
+    /// #[component(clone)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let template = world.spawn().with(Health(30)).id();
+    ///
+    /// let enemy = world.clone_entity(template).unwrap();
+    /// assert_eq!(world.get::<Health>(enemy), Some(&Health(30)));
+    /// assert_ne!(world.get_stable_id(enemy), world.get_stable_id(template));
+    /// ```
+    pub fn clone_entity(&mut self, entity: EntityId) -> Option<EntityId> {
+        self.flush_reserved();
+        if !self.is_alive(entity) {
+            return None;
+        }
+
+        // Clone the components out first, as the copy may land in the same
+        // archetype
+        let mut cloned = Vec::new();
+        let mut source_id = None;
+        if let Some(location) = self.archetypes.get_entity_location(entity)
+            && let Some(archetype) = self.archetypes.get_archetype(location.archetype_id)
+            && let Some(row) = archetype.get_entity_row(entity)
+        {
+            source_id = Some(location.archetype_id);
+            for info in archetype.component_info() {
+                if let Some(storage) = archetype.get_storage(info.type_id()) {
+                    // SAFETY: The row holds an initialized value of this type
+                    cloned.extend(unsafe { ClonedComponent::new(info, storage.get(row)) });
+                }
+            }
+        }
+
+        let copy = self.entities.spawn();
+        self.persistence.change_tracker_mut().track_created(copy);
+        let Some(source_id) = source_id else {
+            return Some(copy);
+        };
+
+        let all_cloned = self
+            .archetypes
+            .get_archetype(source_id)
+            .is_some_and(|archetype| archetype.component_info().len() == cloned.len());
+        let target_id = if all_cloned {
+            source_id
+        } else {
+            let types = ComponentSet::from_types(cloned.iter().map(|c| c.info.type_id()).collect());
+            let infos = cloned.iter().map(|c| c.info.clone()).collect();
+            self.archetypes.get_or_create_archetype(types, infos)
+        };
+
+        if let Some(archetype) = self.archetypes.get_archetype_mut(target_id) {
+            let row = archetype.allocate_row(copy);
+            for component in &cloned {
+                // SAFETY: The buffer holds a freshly cloned value of this
+                // type, which is moved into the archetype
+                unsafe {
+                    archetype.set_component(row, component.info.type_id(), component.ptr);
+                }
+                self.revisions.bump(copy, component.info.type_id());
+            }
+            self.archetypes.set_entity_location(
+                copy,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: target_id,
+                    row,
+                },
+            );
+        }
+
+        self.track_modified(copy);
+        Some(copy)
+    }
+
+    /// Despawns every entity for which `keep` returns `false`.
+    ///
+    /// The closure sees each entity through a read-only [`EntityView`].
+    /// Entities are visited archetype by archetype, so the components being
+    /// inspected are read sequentially; the despawns happen after all
+    /// entities have been visited.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities despawned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Health(i32);
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let alive = world.spawn().with(Health(5)).id();
+    /// let dead = world.spawn().with(Health(0)).id();
+    ///
+    /// let culled = world.retain(|_, view| view.get::<Health>().is_none_or(|h| h.0 > 0));
+    /// assert_eq!(culled, 1);
+    /// assert!(world.is_alive(alive));
+    /// assert!(!world.is_alive(dead));
+    /// ```
+    pub fn retain(&mut self, mut keep: impl FnMut(EntityId, EntityView<'_>) -> bool) -> usize {
+        self.flush_reserved();
+        let mut seen = HashSet::new();
+        let mut doomed = Vec::new();
+
+        for archetype in self.archetypes.iter() {
+            for (row, &entity) in archetype.entities().iter().enumerate() {
+                if !self.entities.is_alive(entity) {
+                    continue;
+                }
+                // Skip stale rows left behind by entities that have moved on
+                if let Some(location) = self.archetypes.get_entity_location(entity)
+                    && location.archetype_id != archetype.id()
+                {
+                    continue;
+                }
+                if !seen.insert(entity) {
+                    continue;
+                }
+                let view = EntityView {
+                    entity,
+                    row: Some((archetype, row)),
+                };
+                if !keep(entity, view) {
+                    doomed.push(entity);
+                }
+            }
+        }
+
+        // Entities that never entered an archetype have no components
+        for (entity, _) in self.entities.iter() {
+            if !seen.contains(&entity) && !keep(entity, EntityView { entity, row: None }) {
+                doomed.push(entity);
+            }
+        }
+
+        self.despawn_many(doomed)
+    }
+
+    /// Reserves an entity ID without exclusive access to the world.
+    ///
+    /// The ID is valid immediately and can be handed to a [`CommandBuffer`]
+    /// to attach components. The entity itself is created, empty, the next
+    /// time the world is structurally modified (for example by
+    /// [`apply_commands`](Self::apply_commands)); until then it is not alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.reserve_entity();
+    /// assert!(!world.is_alive(entity));
+    ///
+    /// world.apply_commands();
+    /// assert!(world.is_alive(entity));
+    /// ```
+    pub fn reserve_entity(&self) -> EntityId {
+        self.entities.reserve_entity()
+    }
+
+    /// Creates all entities reserved through [`reserve_entity`](Self::reserve_entity).
+    fn flush_reserved(&mut self) {
+        if self.entities.pending_reservations() == 0 {
+            return;
+        }
+
+        let empty_archetype_id = ArchetypeId::new(0);
+        for (entity_id, _) in self.entities.flush_reserved() {
+            if let Some(archetype) = self.archetypes.get_archetype_mut(empty_archetype_id) {
+                let row = archetype.allocate_row(entity_id);
+                self.archetypes.set_entity_location(
+                    entity_id,
+                    crate::component::archetype::EntityLocation {
+                        archetype_id: empty_archetype_id,
+                        row,
+                    },
+                );
+            }
+
+            self.persistence
+                .change_tracker_mut()
+                .track_created(entity_id);
+        }
+    }
+
     /// Checks if an entity is alive.
     ///
     /// # Examples
@@ -390,6 +894,72 @@ impl World {
         self.get_entity_id(stable_id)
     }
 
+    /// Gets the ephemeral IDs for many stable IDs at once, in input order.
+    ///
+    /// See [`EntityManager::get_entity_ids`].
+    pub fn get_entity_ids(&self, stable_ids: &[StableId]) -> Vec<Option<EntityId>> {
+        self.entities.get_entity_ids(stable_ids)
+    }
+
+    /// Gets the stable IDs for many entities at once, in input order.
+    ///
+    /// See [`EntityManager::get_stable_ids`].
+    pub fn get_stable_ids(&self, entities: &[EntityId]) -> Vec<Option<StableId>> {
+        self.entities.get_stable_ids(entities)
+    }
+
+    /// Remaps an entity to a new stable ID.
+    ///
+    /// Observers registered with
+    /// [`on_stable_id_remap`](Self::on_stable_id_remap) are notified, and
+    /// the entity is marked modified for persistence.
+    ///
+    /// # Returns
+    ///
+    /// The entity's previous stable ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entity doesn't exist or `new_stable_id` is
+    /// already in use.
+    pub fn remap_stable_id(
+        &mut self,
+        entity: EntityId,
+        new_stable_id: StableId,
+    ) -> Result<StableId, crate::entity::EntityError> {
+        let old = self.entities.remap_stable_id(entity, new_stable_id)?;
+        self.track_modified(entity);
+        Ok(old)
+    }
+
+    /// Registers a callback notified with the old and new stable ID
+    /// whenever an entity in this world is remapped, so external indexes
+    /// keyed by stable ID can be updated.
+    ///
+    /// See [`EntityManager::on_stable_id_remap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut world = World::new();
+    /// let sessions = Arc::new(Mutex::new(Vec::new()));
+    /// let observed = Arc::clone(&sessions);
+    /// world.on_stable_id_remap(move |remap| observed.lock().unwrap().push(remap.new));
+    ///
+    /// let entity = world.spawn_empty();
+    /// world.remap_stable_id(entity, StableId::from_raw(42)).unwrap();
+    /// assert_eq!(*sessions.lock().unwrap(), vec![StableId::from_raw(42)]);
+    /// ```
+    pub fn on_stable_id_remap(
+        &mut self,
+        observer: impl FnMut(&crate::entity::StableIdRemap) + Send + Sync + 'static,
+    ) -> crate::entity::RemapObserverId {
+        self.entities.on_stable_id_remap(observer)
+    }
+
     /// Returns the number of alive entities.
     ///
     /// # Examples
@@ -422,6 +992,10 @@ impl World {
 
     /// Clears all entities and components from the world.
     ///
+    /// Registered archetypes are kept (emptied, not discarded), so refilling a
+    /// cleared world does not pay archetype creation costs again. Resources
+    /// are kept as well.
+    ///
     /// # Examples
     ///
     /// ```
@@ -434,24 +1008,283 @@ impl World {
     /// ```
     pub fn clear(&mut self) {
         self.entities.clear();
-        self.archetypes = ArchetypeManager::new();
-        self.persistence = PersistenceManager::new();
+        self.archetypes.clear();
+        self.persistence = PersistenceManager::with_default_plugins();
         self.metadata = WorldMetadata::new(1, 0, Vec::new());
+        for index in self.indexes.values_mut() {
+            index.clear();
+        }
+        self.revisions.clear();
     }
 
-    /// Returns a reference to the command buffer.
+    /// Removes every entity and component, keeping resources, indexes,
+    /// metadata and history. Used to restore snapshots.
+    ///
+    /// Entities are freed one by one rather than cleared, so their slots
+    /// move on to a new generation and old handles stay dead. Each removal
+    /// is recorded as a despawn for change tracking consumers.
+    pub(crate) fn reset_entities(&mut self) {
+        self.flush_reserved();
+        let entities: Vec<_> = self.entities.iter().map(|(entity, _)| entity).collect();
+        for entity in entities {
+            self.entities.despawn(entity);
+            self.persistence.change_tracker_mut().track_deleted(entity);
+        }
+        self.archetypes.clear();
+        for index in self.indexes.values_mut() {
+            index.clear();
+        }
+        self.revisions.clear();
+    }
+
+    /// Starts keeping the last `capacity` ticks of history for
+    /// [`rewind`](Self::rewind) and [`step_forward`](Self::step_forward).
     ///
-    /// Commands recorded in the buffer can be applied later using
-    /// [`apply_commands`](Self::apply_commands).
+    /// The current state becomes the first tick; call
+    /// [`record_tick`](Self::record_tick) at the end of each simulation tick
+    /// to add more. Only components registered with
+    /// [`ComponentRegistry::register_json`] are captured. Enabling again
+    /// discards the previous history.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
     ///
     /// # Examples
     ///
     /// ```
+    /// use pecs::component::Component;
+    /// use pecs::component::registry::ComponentRegistry;
     /// use pecs::World;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// struct Position(i32);
+    /// impl Component for Position {}
+    ///
+    /// ComponentRegistry::global().register_json::<Position>("Position");
     ///
     /// let mut world = World::new();
-    /// world.commands().spawn();
-    /// ```
+    /// let entity = world.spawn().with(Position(0)).id();
+    /// let stable_id = world.get_stable_id(entity).unwrap();
+    /// world.enable_history(16);
+    ///
+    /// for step in 1..=3 {
+    ///     let entity = world.get_entity_by_stable_id(stable_id).unwrap();
+    ///     world.insert(entity, Position(step));
+    ///     world.record_tick();
+    /// }
+    ///
+    /// assert_eq!(world.rewind(2).unwrap(), 2);
+    /// let entity = world.get_entity_by_stable_id(stable_id).unwrap();
+    /// assert_eq!(world.get::<Position>(entity), Some(&Position(1)));
+    ///
+    /// assert!(world.step_forward().unwrap());
+    /// let entity = world.get_entity_by_stable_id(stable_id).unwrap();
+    /// assert_eq!(world.get::<Position>(entity), Some(&Position(2)));
+    /// ```
+    pub fn enable_history(&mut self, capacity: usize) {
+        assert!(capacity > 0, "history capacity must be non-zero");
+        self.history = Some(History::new(capacity, WorldSnapshot::capture(self)));
+    }
+
+    /// Stops keeping history and frees the stored snapshots.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Records the current state as the next tick of history.
+    ///
+    /// Ticks ahead of a previous [`rewind`](Self::rewind) are discarded.
+    /// Does nothing if history is not enabled.
+    pub fn record_tick(&mut self) {
+        if self.history.is_some() {
+            let snapshot = WorldSnapshot::capture(self);
+            if let Some(history) = &mut self.history {
+                history.push(snapshot);
+            }
+        }
+    }
+
+    /// Restores the state from up to `ticks` ticks ago, returning how many
+    /// ticks were actually rewound.
+    ///
+    /// Entity handles change on restore; look entities up again by stable
+    /// ID. Returns `Ok(0)` if history is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be restored.
+    pub fn rewind(&mut self, ticks: usize) -> crate::persistence::Result<usize> {
+        let Some(mut history) = self.history.take() else {
+            return Ok(0);
+        };
+        let moved = history.rewind(ticks);
+        let result = if moved > 0 {
+            history.current().restore(self)
+        } else {
+            Ok(())
+        };
+        self.history = Some(history);
+        result.map(|()| moved)
+    }
+
+    /// Restores the state one tick after the current one, if a previous
+    /// [`rewind`](Self::rewind) left one. Returns whether it moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be restored.
+    pub fn step_forward(&mut self) -> crate::persistence::Result<bool> {
+        let Some(mut history) = self.history.take() else {
+            return Ok(false);
+        };
+        let moved = history.step_forward();
+        let result = if moved {
+            history.current().restore(self)
+        } else {
+            Ok(())
+        };
+        self.history = Some(history);
+        result.map(|()| moved)
+    }
+
+    /// Returns the index of the current tick and the number of ticks held
+    /// in history, or `None` if history is not enabled.
+    pub fn history_position(&self) -> Option<(usize, usize)> {
+        self.history
+            .as_ref()
+            .map(|history| (history.position(), history.len()))
+    }
+
+    /// Compacts world storage after large despawn waves.
+    ///
+    /// Removes stale archetype rows, rewrites entity locations so every row
+    /// is dense, and releases spare capacity held by component columns and
+    /// the entity allocator. Long-running servers can call this periodically
+    /// to return memory after load peaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let mut world = World::with_capacity(10_000);
+    /// let entities: Vec<_> = (0..1000).map(|_| world.spawn_empty()).collect();
+    /// for entity in entities {
+    ///     world.despawn(entity);
+    /// }
+    ///
+    /// let stats = world.compact();
+    /// assert!(stats.bytes_reclaimed > 0);
+    /// ```
+    pub fn compact(&mut self) -> CompactionStats {
+        self.flush_reserved();
+
+        let entities = &self.entities;
+        let (rows_removed, archetype_bytes) =
+            self.archetypes.compact(|entity| entities.is_alive(entity));
+        let entity_bytes = self.entities.shrink_to_fit();
+
+        CompactionStats {
+            rows_removed,
+            bytes_reclaimed: archetype_bytes + entity_bytes,
+        }
+    }
+
+    /// Returns the tracker that component columns report their allocations
+    /// to.
+    ///
+    /// Set a budget on it to make column growth fail instead of allocating
+    /// past a limit; see [`memory`](crate::component::memory).
+    pub fn memory(&self) -> &crate::component::memory::MemoryTracker {
+        self.archetypes.memory()
+    }
+
+    /// Reports entity counts and component memory per archetype and per
+    /// component type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+    ///
+    /// let stats = world.stats();
+    /// assert_eq!(stats.entities, 1);
+    /// assert_eq!(stats.components.len(), 1);
+    /// assert!(stats.components[0].type_name.ends_with("Position"));
+    /// assert_eq!(stats.allocated_bytes, stats.components[0].bytes);
+    /// ```
+    pub fn stats(&self) -> WorldStats {
+        let memory = self.archetypes.memory();
+        let mut names = HashMap::new();
+        let mut columns = Vec::new();
+        let archetypes = self
+            .archetypes
+            .iter()
+            .map(|archetype| {
+                for info in archetype.component_info() {
+                    names.insert(info.type_id(), info.type_name());
+                    if let Some(storage) = archetype.get_storage(info.type_id()) {
+                        columns.push(ColumnStats {
+                            archetype: archetype.id(),
+                            type_id: info.type_id(),
+                            type_name: info.type_name(),
+                            len: storage.len(),
+                            capacity: storage.capacity(),
+                            bytes: storage.allocated_bytes(),
+                        });
+                    }
+                }
+                ArchetypeStats {
+                    id: archetype.id(),
+                    entities: archetype.len(),
+                    bytes: archetype.allocated_bytes(),
+                }
+            })
+            .collect();
+
+        let mut components: Vec<ComponentStats> = memory
+            .allocated_by_type()
+            .into_iter()
+            .map(|(type_id, bytes)| ComponentStats {
+                type_id,
+                type_name: names.get(&type_id).copied().unwrap_or("<unknown>"),
+                bytes,
+            })
+            .collect();
+        components.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.type_name.cmp(b.type_name)));
+
+        WorldStats {
+            entities: self.len(),
+            retired_slots: self.entities.retired_slots(),
+            allocated_bytes: memory.allocated(),
+            budget: memory.budget(),
+            archetypes,
+            components,
+            columns,
+        }
+    }
+
+    /// Returns a reference to the command buffer.
+    ///
+    /// Commands recorded in the buffer can be applied later using
+    /// [`apply_commands`](Self::apply_commands).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let mut world = World::new();
+    /// world.commands().spawn();
+    /// ```
     pub fn commands(&mut self) -> &mut CommandBuffer {
         &mut self.commands
     }
@@ -469,6 +1302,8 @@ impl World {
     /// assert_eq!(world.len(), 1);
     /// ```
     pub fn apply_commands(&mut self) {
+        self.flush_reserved();
+
         // Take the command buffer temporarily to avoid borrow checker issues
         let mut commands = std::mem::take(&mut self.commands);
         commands.apply(self);
@@ -492,6 +1327,284 @@ impl World {
         &mut self.persistence
     }
 
+    /// Saves one entity through a registered entity persistence plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entity plugin is registered under
+    /// `plugin_name`, or if the plugin fails to save the entity.
+    pub fn save_entity(
+        &self,
+        entity: EntityId,
+        plugin_name: &str,
+    ) -> crate::persistence::Result<()> {
+        self.persistence.save_entity_with(self, entity, plugin_name)
+    }
+
+    /// Saves the changes since the last delta save through a registered
+    /// delta persistence plugin, including component data.
+    ///
+    /// See
+    /// [`PersistenceManager::save_world_delta`](crate::persistence::PersistenceManager::save_world_delta).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no delta plugin is registered under
+    /// `plugin_name`, or if encoding or saving the changes fails.
+    pub fn save_delta(&mut self, plugin_name: &str) -> crate::persistence::Result<()> {
+        // Take the manager temporarily so it can read the world
+        let mut persistence = std::mem::take(&mut self.persistence);
+        let result = persistence.save_world_delta(self, plugin_name);
+        self.persistence = persistence;
+        result
+    }
+
+    /// Saves several entities in one batch through a registered entity
+    /// persistence plugin.
+    ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entity plugin is registered under
+    /// `plugin_name`, or if the plugin fails to save the entities.
+    pub fn save_entities(
+        &self,
+        entities: &[EntityId],
+        plugin_name: &str,
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
+        self.persistence
+            .save_entities_with(self, entities, plugin_name)
+    }
+
+    /// Spawns a proxy for an entity whose components are in storage.
+    ///
+    /// The entity gets `stable_id` and an
+    /// [`Unloaded`](crate::persistence::Unloaded) marker, and nothing else
+    /// until it is hydrated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `stable_id` is already in use.
+    pub fn spawn_unloaded(
+        &mut self,
+        stable_id: StableId,
+    ) -> Result<EntityId, crate::entity::EntityError> {
+        let entity = self.spawn_empty_with_stable_id(stable_id)?;
+        self.insert(entity, crate::persistence::Unloaded::new(stable_id));
+        Ok(entity)
+    }
+
+    /// Loads an entity's components through the default entity plugin.
+    ///
+    /// See [`hydrate_with`](Self::hydrate_with).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no default entity plugin is registered or the
+    /// plugin fails to load the entity.
+    pub fn hydrate(&mut self, stable_id: StableId) -> crate::persistence::Result<EntityId> {
+        let plugin_name = self
+            .persistence
+            .default_entity_plugin()
+            .ok_or_else(|| {
+                crate::persistence::PersistenceError::PluginNotFound(
+                    "default entity plugin".to_string(),
+                )
+            })?
+            .to_string();
+        self.hydrate_with(stable_id, &plugin_name)
+    }
+
+    /// Loads an entity's components through a named entity plugin.
+    ///
+    /// If the entity is a proxy, its stored components are added and its
+    /// [`Unloaded`](crate::persistence::Unloaded) marker removed. If it is
+    /// not in the world at all it is loaded as a new entity. Entities that
+    /// are already loaded are returned as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entity plugin is registered under
+    /// `plugin_name` or the plugin fails to load the entity. A proxy stays
+    /// unloaded on failure.
+    pub fn hydrate_with(
+        &mut self,
+        stable_id: StableId,
+        plugin_name: &str,
+    ) -> crate::persistence::Result<EntityId> {
+        let proxy = self.get_entity_by_stable_id(stable_id);
+        if let Some(entity) = proxy
+            && !self.has::<crate::persistence::Unloaded>(entity)
+        {
+            return Ok(entity);
+        }
+
+        // Take the manager temporarily so the plugin can borrow the world.
+        // Changes made meanwhile bypass its tracker, so track them here.
+        let persistence = std::mem::take(&mut self.persistence);
+        let loaded = persistence.load_entity_with(self, stable_id, plugin_name);
+        self.persistence = persistence;
+
+        let entity = loaded?;
+        self.remove::<crate::persistence::Unloaded>(entity);
+        match proxy {
+            Some(_) => self.track_modified(entity),
+            None => self.persistence.change_tracker_mut().track_created(entity),
+        }
+        Ok(entity)
+    }
+
+    /// Hydrates every proxy whose hydration was requested with
+    /// [`Unloaded::request`](crate::persistence::Unloaded::request), using
+    /// the default entity plugin.
+    ///
+    /// # Returns
+    ///
+    /// The hydrated entities.
+    ///
+    /// # Errors
+    ///
+    /// Stops at and returns the first load error. Proxies not yet hydrated
+    /// keep their request and are retried on the next call.
+    pub fn hydrate_requested(&mut self) -> crate::persistence::Result<Vec<EntityId>> {
+        let requested: Vec<StableId> = self
+            .query::<&crate::persistence::Unloaded>()
+            .filter(|unloaded| unloaded.is_requested())
+            .map(|unloaded| unloaded.stable_id())
+            .collect();
+
+        requested
+            .into_iter()
+            .map(|stable_id| self.hydrate(stable_id))
+            .collect()
+    }
+
+    /// Saves an entity to an entity plugin and removes it from the world.
+    ///
+    /// With `keep_proxy`, an [`Unloaded`](crate::persistence::Unloaded)
+    /// proxy with the same stable ID takes its place, ready to be brought
+    /// back with [`hydrate`](Self::hydrate). The proxy is a new entity, so
+    /// the old `EntityId` becomes stale either way.
+    ///
+    /// Proxies themselves are never saved, which would overwrite the stored
+    /// entity: unloading one despawns it, or keeps it as it is with
+    /// `keep_proxy`.
+    ///
+    /// # Returns
+    ///
+    /// The proxy entity, if one was kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entity doesn't exist, no entity plugin is
+    /// registered under `plugin_name`, or saving fails. The entity is left
+    /// untouched on error.
+    pub fn unload_entity(
+        &mut self,
+        entity: EntityId,
+        plugin_name: &str,
+        keep_proxy: bool,
+    ) -> crate::persistence::Result<Option<EntityId>> {
+        let stable_id = self
+            .get_stable_id(entity)
+            .ok_or(crate::persistence::PersistenceError::EntityNotFound(entity))?;
+
+        if !self.has::<crate::persistence::Unloaded>(entity) {
+            self.persistence
+                .save_entity_with(self, entity, plugin_name)?;
+        } else if keep_proxy {
+            return Ok(Some(entity));
+        }
+
+        self.despawn(entity);
+        if !keep_proxy {
+            return Ok(None);
+        }
+        self.spawn_unloaded(stable_id)
+            .map(Some)
+            .map_err(|e| crate::persistence::PersistenceError::EntityIdConflict(e.to_string()))
+    }
+
+    /// Unloads every entity matching the query filter `F`.
+    ///
+    /// Matching entities are saved in one batch, then removed as in
+    /// [`unload_entity`](Self::unload_entity). Existing proxies are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The stable IDs of the unloaded entities.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entity plugin is registered under
+    /// `plugin_name` or saving fails, in which case no entity is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::persistence::KeyValueEntityPlugin;
+    /// use pecs::prelude::*;
+    /// use pecs::query::filter::With;
+    ///
+    /// struct Distant;
+    /// impl Component for Distant {}
+    /// struct Position(f32);
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .persistence()
+    ///     .register_entity_plugin("kv", Box::new(KeyValueEntityPlugin::new()));
+    /// world.spawn().with(Position(900.0)).with(Distant).id();
+    /// let near = world.spawn().with(Position(1.0)).id();
+    ///
+    /// let unloaded = world.unload_where::<With<Distant>>("kv", true).unwrap();
+    /// assert_eq!(unloaded.len(), 1);
+    /// assert!(world.is_alive(near));
+    /// ```
+    pub fn unload_where<F>(
+        &mut self,
+        plugin_name: &str,
+        keep_proxy: bool,
+    ) -> crate::persistence::Result<Vec<StableId>>
+    where
+        F: for<'a> crate::query::Filter<'a>,
+    {
+        let entities: Vec<EntityId> = self
+            .query_filtered::<EntityId, (
+                F,
+                crate::query::filter::Without<crate::persistence::Unloaded>,
+            )>()
+            .collect();
+        self.persistence
+            .save_entities_with(self, &entities, plugin_name)?;
+
+        let mut unloaded = Vec::with_capacity(entities.len());
+        for entity in entities {
+            let Some(stable_id) = self.get_stable_id(entity) else {
+                continue;
+            };
+            self.despawn(entity);
+            if keep_proxy {
+                self.spawn_unloaded(stable_id).map_err(|e| {
+                    crate::persistence::PersistenceError::EntityIdConflict(e.to_string())
+                })?;
+            }
+            unloaded.push(stable_id);
+        }
+        Ok(unloaded)
+    }
+
+    /// Records a failed deferred persistence operation in the
+    /// [`PersistenceErrors`](crate::persistence::PersistenceErrors) resource.
+    pub(crate) fn report_persistence_error(&mut self, error: crate::persistence::PersistenceError) {
+        self.resource_or_insert_with(crate::persistence::PersistenceErrors::default)
+            .push(error);
+    }
+
     /// Returns a reference to the world metadata.
     ///
     /// Metadata includes version information, timestamps, and component
@@ -524,78 +1637,459 @@ impl World {
         &mut self.metadata
     }
 
-    /// Returns an iterator over all entities with their stable IDs.
+    /// Brings the persisted fields of the metadata up to date.
     ///
-    /// This is useful for persistence operations that need to serialize
-    /// entities with their stable identifiers.
+    /// Sets the entity count, the timestamp and the list of component types
+    /// currently stored in the world. Types with a registered
+    /// [`ComponentSchema`](crate::component::registry::ComponentSchema) are
+    /// listed under their schema name and version, and transient types are
+    /// left out. Every `World` save method calls this
+    /// first; call it yourself before handing the world to a plugin or
+    /// [`PersistenceManager`] directly.
     ///
     /// # Examples
     ///
     /// ```
     /// use pecs::World;
+    /// use pecs::component::Component;
+    ///
+    /// struct Health(u32);
+    /// impl Component for Health {}
     ///
     /// let mut world = World::new();
-    /// world.spawn_empty();
+    /// world.spawn().with(Health(10)).id();
+    /// world.update_metadata();
     ///
-    /// for (entity, stable_id) in world.iter_entities() {
-    ///     println!("Entity {:?} has stable ID {}", entity, stable_id);
-    /// }
+    /// assert_eq!(world.metadata().entity_count, 1);
+    /// assert_eq!(world.metadata().component_types.len(), 1);
     /// ```
-    pub fn iter_entities(&self) -> impl Iterator<Item = (EntityId, StableId)> + '_ {
-        self.entities.iter()
+    pub fn update_metadata(&mut self) {
+        let mut component_types: Vec<ComponentTypeInfo> = Vec::new();
+        let mut seen = HashSet::new();
+        let registry = ComponentRegistry::global();
+        for archetype in self.archetypes.iter() {
+            for info in archetype.component_info() {
+                if !seen.insert(info.type_id()) {
+                    continue;
+                }
+                let type_id = info.type_id().type_id();
+                let (type_name, version) = match registry.schema_of(type_id) {
+                    Some(schema) if schema.transient => continue,
+                    Some(schema) => (schema.name, schema.version),
+                    None => (info.type_name().to_string(), 1),
+                };
+                component_types.push(ComponentTypeInfo {
+                    type_id,
+                    type_name,
+                    version,
+                    size: info.size(),
+                });
+            }
+        }
+        component_types.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+
+        self.metadata.entity_count = self.len();
+        self.metadata.timestamp = self
+            .fixed_timestamp
+            .unwrap_or_else(WorldMetadata::current_timestamp);
+        self.metadata.component_types = component_types;
     }
 
-    /// Returns a mutable reference to the entity manager.
-    ///
-    /// This is primarily for internal use by persistence systems.
-    #[doc(hidden)]
-    pub fn entities_mut(&mut self) -> &mut EntityManager {
-        &mut self.entities
+    /// Updates the metadata and bumps the save counter ahead of a save.
+    ///
+    /// Resources that persist with the world, such as [`Rng`], are written
+    /// into the metadata here.
+    fn prepare_save(&mut self) -> crate::persistence::Result<()> {
+        self.update_metadata();
+        self.metadata.save_count += 1;
+        match self.resources.get::<Rng>() {
+            Some(rng) => self.metadata.insert_ext(rng.clone())?,
+            None => {
+                self.metadata.remove_ext::<Rng>();
+            }
+        }
+        Ok(())
     }
 
-    /// Inserts a component into an entity.
-    ///
-    /// If the entity already has this component type, it will be replaced.
-    /// This operation may move the entity to a different archetype.
-    ///
-    /// # Arguments
-    ///
-    /// * `entity` - The entity to add the component to
-    /// * `component` - The component to add
-    ///
-    /// # Returns
-    ///
-    /// `true` if successful, `false` if the entity doesn't exist.
+    /// Restores the resources written by `prepare_save` after a load.
+    pub(crate) fn restore_resources(&mut self) -> crate::persistence::Result<()> {
+        if let Some(rng) = self.metadata.get_ext::<Rng>()? {
+            self.insert_resource(rng);
+        }
+        Ok(())
+    }
+
+    /// Inserts a resource, returning the previous value of that type.
     ///
     /// # Examples
     ///
     /// ```
-    /// use pecs::prelude::*;
+    /// use pecs::World;
     ///
-    /// #[derive(Debug)]
-    /// struct Position { x: f32, y: f32 }
-    /// impl Component for Position {}
+    /// struct Gravity(f32);
     ///
     /// let mut world = World::new();
-    /// let entity = world.spawn_empty();
-    /// assert!(world.insert(entity, Position { x: 1.0, y: 2.0 }));
+    /// assert!(world.insert_resource(Gravity(9.8)).is_none());
+    /// assert_eq!(world.insert_resource(Gravity(1.6)).unwrap().0, 9.8);
     /// ```
-    pub fn insert<T: Component>(&mut self, entity: EntityId, component: T) -> bool {
-        if !self.is_alive(entity) {
-            return false;
-        }
+    pub fn insert_resource<R: Resource>(&mut self, value: R) -> Option<R> {
+        self.resources.insert(value)
+    }
 
-        let component_type_id = ComponentTypeId::of::<T>();
+    /// Removes a resource and returns it.
+    pub fn remove_resource<R: Resource>(&mut self) -> Option<R> {
+        self.resources.remove::<R>()
+    }
 
-        // Get current archetype location
-        let current_location = self.archetypes.get_entity_location(entity);
+    /// Returns true if a resource of type `R` exists.
+    pub fn contains_resource<R: Resource>(&self) -> bool {
+        self.resources.contains::<R>()
+    }
 
-        if let Some(location) = current_location {
-            // Entity exists in an archetype
-            let current_archetype_id = location.archetype_id;
+    /// Returns a reference to a resource.
+    pub fn resource<R: Resource>(&self) -> Option<&R> {
+        self.resources.get::<R>()
+    }
 
-            // Check if entity already has this component
-            let has_component = self
+    /// Returns a mutable reference to a resource.
+    pub fn resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
+        self.resources.get_mut::<R>()
+    }
+
+    /// Returns a mutable reference to a resource, inserting it first if it
+    /// does not exist.
+    pub fn resource_or_insert_with<R: Resource>(&mut self, create: impl FnOnce() -> R) -> &mut R {
+        if !self.resources.contains::<R>() {
+            self.resources.insert(create());
+        }
+        self.resources
+            .get_mut::<R>()
+            .expect("resource was just inserted")
+    }
+
+    /// Returns a mutable reference to a resource through a shared reference.
+    ///
+    /// # Safety
+    ///
+    /// No other reference to the resource may exist while the returned
+    /// borrow is alive.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn resource_unchecked_mut<R: Resource>(&self) -> Option<&mut R> {
+        // SAFETY: Guaranteed by the caller
+        unsafe { self.resources.get_unchecked_mut::<R>() }
+    }
+
+    /// Returns the number of resources in the world.
+    pub fn resource_count(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns an iterator over all entities with their stable IDs.
+    ///
+    /// This is useful for persistence operations that need to serialize
+    /// entities with their stable identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_empty();
+    ///
+    /// for (entity, stable_id) in world.iter_entities() {
+    ///     println!("Entity {:?} has stable ID {}", entity, stable_id);
+    /// }
+    /// ```
+    pub fn iter_entities(&self) -> impl Iterator<Item = (EntityId, StableId)> + '_ {
+        self.entities.iter()
+    }
+
+    /// Returns an iterator over all entities with their stable IDs,
+    /// archetypes and component counts.
+    ///
+    /// Entities are yielded in the same order as
+    /// [`iter_entities`](Self::iter_entities). Each summary is read from the
+    /// world's dense location table, so persistence and debug tools can
+    /// stream entity overviews without looking up each entity themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_empty();
+    /// world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+    ///
+    /// let counts: Vec<_> = world
+    ///     .iter_entities_detailed()
+    ///     .map(|summary| summary.component_count)
+    ///     .collect();
+    /// assert_eq!(counts, vec![0, 1]);
+    /// ```
+    pub fn iter_entities_detailed(&self) -> impl Iterator<Item = EntitySummary> + '_ {
+        self.entities.iter().map(|(entity, stable_id)| {
+            let archetype = self
+                .archetypes
+                .get_entity_location(entity)
+                .and_then(|location| self.archetypes.get_archetype(location.archetype_id));
+            EntitySummary {
+                entity,
+                stable_id,
+                archetype: archetype.map_or(ArchetypeId::new(0), Archetype::id),
+                component_count: archetype.map_or(0, |archetype| archetype.component_types().len()),
+            }
+        })
+    }
+
+    /// Returns a read-only view of an entity's components, or `None` if the
+    /// entity is not alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Health(u32);
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Health(3)).id();
+    ///
+    /// let view = world.entity_view(entity).unwrap();
+    /// assert_eq!(view.get::<Health>(), Some(&Health(3)));
+    /// ```
+    pub fn entity_view(&self, entity: EntityId) -> Option<EntityView<'_>> {
+        if !self.entities.is_alive(entity) {
+            return None;
+        }
+        let row = self
+            .archetypes
+            .get_entity_location(entity)
+            .and_then(|location| {
+                let archetype = self.archetypes.get_archetype(location.archetype_id)?;
+                Some((archetype, location.row))
+            });
+        Some(EntityView { entity, row })
+    }
+
+    /// Returns a mutable reference to the entity manager.
+    ///
+    /// This is primarily for internal use by persistence systems.
+    #[doc(hidden)]
+    pub fn entities_mut(&mut self) -> &mut EntityManager {
+        &mut self.entities
+    }
+
+    /// Inserts a component into an entity.
+    ///
+    /// If the entity already has this component type, it will be replaced.
+    /// This operation may move the entity to a different archetype.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity to add the component to
+    /// * `component` - The component to add
+    ///
+    /// # Returns
+    ///
+    /// `true` if successful, `false` if the entity doesn't exist, a
+    /// [required component](ComponentRegistry::register_requires) is
+    /// missing and has no registered default, or the component does not
+    /// fit in the memory budget. The component is dropped on failure. Use
+    /// [`try_insert`](Self::try_insert) to tell these apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    /// assert!(world.insert(entity, Position { x: 1.0, y: 2.0 }));
+    /// ```
+    pub fn insert<T: Component>(&mut self, entity: EntityId, component: T) -> bool {
+        self.try_insert(entity, component).is_ok()
+    }
+
+    /// Inserts a component into an entity, reporting why it failed.
+    ///
+    /// Like [`insert`](Self::insert), this adds the registered default of
+    /// every [required component](ComponentRegistry::register_requires) the
+    /// entity is missing. If one of them has no default, nothing is
+    /// inserted and the component is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsertError::InvalidEntity`] if the entity doesn't exist,
+    /// [`InsertError::MissingRequirement`] if a required component is
+    /// missing and has no registered default, or [`InsertError::OverBudget`]
+    /// if the component storage cannot grow within the
+    /// [memory budget](crate::component::memory::MemoryTracker::set_budget).
+    /// On error the component is dropped and the entity keeps the
+    /// components it had, except for exclusive rivals a component replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::InsertError;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// struct Body;
+    /// impl Component for Body {}
+    ///
+    /// struct Collider;
+    /// impl Component for Collider {}
+    ///
+    /// ComponentRegistry::global().register_requires::<Collider, Body>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    /// assert!(matches!(
+    ///     world.try_insert(entity, Collider),
+    ///     Err(InsertError::MissingRequirement { .. })
+    /// ));
+    ///
+    /// world.insert(entity, Body);
+    /// assert_eq!(world.try_insert(entity, Collider), Ok(()));
+    /// ```
+    pub fn try_insert<T: Component>(
+        &mut self,
+        entity: EntityId,
+        component: T,
+    ) -> Result<(), InsertError> {
+        self.flush_reserved();
+        if !self.is_alive(entity) {
+            return Err(InsertError::InvalidEntity);
+        }
+        if self.has::<T>(entity) {
+            return self.try_insert_component(entity, component);
+        }
+
+        let required =
+            self.missing_requirements(entity, &[(TypeId::of::<T>(), std::any::type_name::<T>())])?;
+        if required.is_empty() {
+            return self.try_insert_component(entity, component);
+        }
+
+        let before = self.component_types_of(entity);
+        self.try_insert_component(entity, component)?;
+        for insert in required {
+            if let Err(error) = insert(self, entity) {
+                // Take back what this call added, so the entity is never
+                // left with a component but without its requirements
+                let mut added = self.component_types_of(entity);
+                for type_id in before.iter() {
+                    added.remove(type_id);
+                }
+                drop(self.detach_components(entity, &added));
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the required components an entity would be missing after
+    /// gaining the `added` types, following requirements transitively.
+    ///
+    /// Returns the inserters of their registered defaults, which must not
+    /// resolve requirements again: the closure is already complete.
+    pub(crate) fn missing_requirements(
+        &self,
+        entity: EntityId,
+        added: &[(TypeId, &'static str)],
+    ) -> Result<Vec<crate::component::registry::InsertRequirementFn>, InsertError> {
+        let registry = ComponentRegistry::global();
+        let archetype = self
+            .archetypes
+            .get_entity_location(entity)
+            .and_then(|location| self.archetypes.get_archetype(location.archetype_id));
+        let present = |type_id: TypeId| {
+            archetype.is_some_and(|archetype| {
+                archetype
+                    .component_info()
+                    .iter()
+                    .any(|info| info.type_id().type_id() == type_id)
+            })
+        };
+
+        let mut seen: HashSet<TypeId> = added.iter().map(|&(type_id, _)| type_id).collect();
+        let mut pending = added.to_vec();
+        let mut inserters = Vec::new();
+        while let Some((type_id, type_name)) = pending.pop() {
+            for requirement in registry.requirements_of(type_id) {
+                if present(requirement.type_id) || !seen.insert(requirement.type_id) {
+                    continue;
+                }
+                let insert = registry.requirement_inserter(requirement.type_id).ok_or(
+                    InsertError::MissingRequirement {
+                        component: type_name,
+                        required: requirement.type_name,
+                    },
+                )?;
+                inserters.push(insert);
+                pending.push((requirement.type_id, requirement.type_name));
+            }
+        }
+        Ok(inserters)
+    }
+
+    /// Inserts a component without resolving its requirements.
+    ///
+    /// Used where the requirements are resolved for a whole group of
+    /// components at once, such as bundles.
+    pub(crate) fn insert_component<T: Component>(
+        &mut self,
+        entity: EntityId,
+        component: T,
+    ) -> bool {
+        self.try_insert_component(entity, component).is_ok()
+    }
+
+    /// Inserts one component of a bundle, panicking if the memory budget
+    /// is exceeded: spawning and inserting bundles cannot report errors.
+    pub(crate) fn insert_bundle_component<T: Component>(&mut self, entity: EntityId, component: T) {
+        if let Err(InsertError::OverBudget(error)) = self.try_insert_component(entity, component) {
+            panic!("{}: {}", std::any::type_name::<T>(), error);
+        }
+    }
+
+    /// Inserts a component without resolving its requirements, reporting
+    /// why it failed.
+    ///
+    /// Target columns are grown before the entity moves, so running out of
+    /// budget leaves the entity unchanged and drops the component.
+    pub(crate) fn try_insert_component<T: Component>(
+        &mut self,
+        entity: EntityId,
+        component: T,
+    ) -> Result<(), InsertError> {
+        self.flush_reserved();
+        if !self.is_alive(entity) {
+            return Err(InsertError::InvalidEntity);
+        }
+
+        let component_type_id = ComponentTypeId::of::<T>();
+
+        // Get current archetype location
+        let current_location = self.archetypes.get_entity_location(entity);
+
+        if let Some(location) = current_location {
+            // Entity exists in an archetype
+            let current_archetype_id = location.archetype_id;
+
+            // Check if entity already has this component
+            let has_component = self
                 .archetypes
                 .get_archetype(current_archetype_id)
                 .map(|a| a.has_component::<T>())
@@ -613,72 +2107,60 @@ impl World {
                 }
 
                 // Track component modification for persistence
-                self.persistence.change_tracker_mut().track_modified(entity);
-                return true;
+                self.track_modified(entity);
+                self.revisions.bump(entity, component_type_id);
+                return Ok(());
             }
 
-            // Need to move to new archetype with added component
-            // First, collect all existing component types and their info
-            let (mut new_component_types, mut component_info) = self
-                .archetypes
-                .get_archetype(current_archetype_id)
-                .map(|a| {
-                    let types = a.component_types().clone();
-                    let mut infos = Vec::new();
-                    // Collect ComponentInfo for existing types
-                    for type_id in types.iter() {
-                        if let Some(storage) = a.get_storage(type_id) {
-                            infos.push(storage.info().clone());
-                        }
-                    }
-                    (types, infos)
-                })
-                .unwrap_or_default();
+            // Inserting a member of an exclusive group removes the others
+            // in the same move
+            let rivals = ComponentRegistry::global().exclusive_with(component_type_id);
+            if !rivals.is_empty()
+                && self
+                    .archetypes
+                    .get_archetype(current_archetype_id)
+                    .is_some_and(|archetype| !archetype.component_types().is_disjoint(&rivals))
+            {
+                self.replace_exclusive(entity, current_archetype_id, &rivals, component)?;
+                self.track_modified(entity);
+                self.revisions.bump(entity, component_type_id);
+                return Ok(());
+            }
 
-            // Add the new component type
-            new_component_types.insert(component_type_id);
-            component_info.push(crate::component::ComponentInfo::of::<T>());
+            // Need to move to new archetype with added component
+            let target_archetype_id = self.archetypes.get_or_create_add_target(
+                current_archetype_id,
+                &crate::component::ComponentInfo::of::<T>(),
+            );
 
-            // Get or create target archetype
-            let target_archetype_id = self
-                .archetypes
-                .get_or_create_archetype(new_component_types, component_info);
+            self.reserve_row(target_archetype_id)?;
 
             // Prepare component data for the new component
             let component_ptr = &component as *const T as *const u8;
             let component_data = vec![(component_type_id, component_ptr)];
 
             // Move entity to new archetype (this copies existing components)
-            let target_row = unsafe {
-                self.archetypes.move_entity_between_archetypes(
+            let moved = unsafe {
+                self.move_entity(
                     entity,
                     current_archetype_id,
                     target_archetype_id,
                     &component_data,
                 )
             };
-
-            // Update entity location
-            if let Some(row) = target_row {
-                self.archetypes.set_entity_location(
-                    entity,
-                    crate::component::archetype::EntityLocation {
-                        archetype_id: target_archetype_id,
-                        row,
-                    },
-                );
+            if moved.is_none() {
+                // Nothing was moved, so the component is still ours to drop
+                return Err(InsertError::InvalidEntity);
             }
 
             std::mem::forget(component); // Component was moved
         } else {
             // Entity not in any archetype yet, add to new archetype
-            let mut component_types = ComponentSet::new();
-            component_types.insert(component_type_id);
-
-            let component_info = vec![crate::component::ComponentInfo::of::<T>()];
-            let archetype_id = self
-                .archetypes
-                .get_or_create_archetype(component_types, component_info);
+            let archetype_id = self.archetypes.get_or_create_add_target(
+                ArchetypeId::new(0),
+                &crate::component::ComponentInfo::of::<T>(),
+            );
+            self.reserve_row(archetype_id)?;
 
             if let Some(archetype) = self.archetypes.get_archetype_mut(archetype_id) {
                 let row = archetype.allocate_row(entity);
@@ -698,11 +2180,552 @@ impl World {
         }
 
         // Track component modification for persistence
-        self.persistence.change_tracker_mut().track_modified(entity);
+        self.track_modified(entity);
+        self.revisions.bump(entity, component_type_id);
+
+        Ok(())
+    }
+
+    /// Grows every column of an archetype for one more row, so an entity
+    /// can move in without allocating.
+    fn reserve_row(&mut self, archetype_id: ArchetypeId) -> Result<(), MemoryError> {
+        match self.archetypes.get_archetype_mut(archetype_id) {
+            Some(archetype) => archetype.try_reserve(1),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves an entity to the archetype without the rivals of `T` and with
+    /// `T`, dropping the rival components.
+    fn replace_exclusive<T: Component>(
+        &mut self,
+        entity: EntityId,
+        source_id: ArchetypeId,
+        rivals: &ComponentSet,
+        component: T,
+    ) -> Result<(), InsertError> {
+        let Some(source) = self.archetypes.get_archetype(source_id) else {
+            return Err(InsertError::InvalidEntity);
+        };
+
+        let mut target_types = source.component_types().clone();
+        let mut target_info = Vec::with_capacity(source.component_info().len());
+        let mut dropped = Vec::new();
+        for info in source.component_info() {
+            if rivals.contains(info.type_id()) {
+                target_types.remove(info.type_id());
+                dropped.push(info.clone());
+            } else {
+                target_info.push(info.clone());
+            }
+        }
+        target_types.insert(ComponentTypeId::of::<T>());
+        target_info.push(ComponentInfo::of::<T>());
+
+        let target_id = self
+            .archetypes
+            .get_or_create_archetype(target_types, target_info);
+        self.reserve_row(target_id)?;
+
+        // The move below only copies components the target has, so the
+        // rivals are detached first and dropped once the entity has settled:
+        // a panicking drop then cannot leave a dropped value in a live row
+        let source = self
+            .archetypes
+            .get_archetype(source_id)
+            .ok_or(InsertError::InvalidEntity)?;
+        let row = source
+            .get_entity_row(entity)
+            .ok_or(InsertError::InvalidEntity)?;
+        let detached: Vec<DetachedComponent> = dropped
+            .iter()
+            .filter_map(|info| {
+                let storage = source.get_storage(info.type_id())?;
+                // SAFETY: The row holds an initialized component of this type,
+                // which the move below discards without dropping
+                Some(unsafe { DetachedComponent::new(info, storage.get(row)) })
+            })
+            .collect();
+
+        let component_data = [(
+            ComponentTypeId::of::<T>(),
+            &component as *const T as *const u8,
+        )];
+        // SAFETY: The entity is in the source archetype and the target has
+        // exactly its remaining components plus `T`
+        let moved = unsafe { self.move_entity(entity, source_id, target_id, &component_data) };
+        if moved.is_none() {
+            // The rivals are still in their row, so only the copies go
+            for component in detached {
+                component.release();
+            }
+            return Err(InsertError::InvalidEntity);
+        }
+        std::mem::forget(component); // Component was moved
+
+        drop(detached);
+        Ok(())
+    }
+
+    /// Moves an entity between archetypes and records its new location.
+    ///
+    /// The move either fails before touching either archetype or runs to
+    /// completion, and nothing between it and the location update can
+    /// panic, so a panic never leaves the entity's location pointing at a
+    /// row it no longer owns.
+    ///
+    /// # Safety
+    ///
+    /// See [`ArchetypeManager::move_entity_between_archetypes`].
+    unsafe fn move_entity(
+        &mut self,
+        entity: EntityId,
+        source_id: ArchetypeId,
+        target_id: ArchetypeId,
+        component_data: &[(ComponentTypeId, *const u8)],
+    ) -> Option<usize> {
+        // The source's last entity is swapped into the vacated row
+        let vacated = self.archetypes.get_archetype(source_id).and_then(|source| {
+            let row = source.get_entity_row(entity)?;
+            let last = source.get_entity(source.len() - 1)?;
+            (source_id != target_id && last != entity).then_some((last, row))
+        });
+
+        // SAFETY: Upheld by the caller
+        let row = unsafe {
+            self.archetypes.move_entity_between_archetypes(
+                entity,
+                source_id,
+                target_id,
+                component_data,
+            )
+        }?;
+        self.archetypes.set_entity_location(
+            entity,
+            crate::component::archetype::EntityLocation {
+                archetype_id: target_id,
+                row,
+            },
+        );
+        if let Some((moved, row)) = vacated {
+            self.archetypes.set_entity_location(
+                moved,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: source_id,
+                    row,
+                },
+            );
+        }
+        Some(row)
+    }
+
+    /// Inserts a component into many entities at once.
+    ///
+    /// Entities are grouped by their current archetype so the archetype
+    /// transition is resolved once per group rather than once per entity.
+    /// Components with requirements or an exclusive group are inserted one
+    /// entity at a time, as by [`insert`](Self::insert).
+    /// Entities that already have the component get it replaced; entities
+    /// that are not alive, or whose component does not fit in the memory
+    /// budget, are skipped and their component is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - Pairs of target entity and component to insert
+    ///
+    /// # Returns
+    ///
+    /// The number of components that were inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Selected;
+    /// impl Component for Selected {}
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..100).map(|_| world.spawn_empty()).collect();
+    ///
+    /// let inserted = world.insert_batch(entities.iter().map(|&e| (e, Selected)));
+    /// assert_eq!(inserted, 100);
+    /// assert!(world.has::<Selected>(entities[0]));
+    /// ```
+    pub fn insert_batch<T: Component>(
+        &mut self,
+        batch: impl IntoIterator<Item = (EntityId, T)>,
+    ) -> usize {
+        self.insert_batch_with(batch, false).unwrap_or_default()
+    }
+
+    /// Inserts a component into many entities at once, failing instead of
+    /// skipping components that do not fit in the memory budget.
+    ///
+    /// Storage for the whole batch is reserved before anything is inserted,
+    /// so running out of budget leaves every entity unchanged. Components
+    /// with requirements or an exclusive group are inserted one entity at a
+    /// time, as by [`try_insert`](Self::try_insert), and stop at the first
+    /// failure. Entities that are not alive are skipped, as by
+    /// [`insert_batch`](Self::insert_batch).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsertError::OverBudget`] if the component storage cannot
+    /// grow within the memory budget, or any error of
+    /// [`try_insert`](Self::try_insert) for components inserted one at a
+    /// time. The components not yet inserted are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::InsertError;
+    ///
+    /// struct Chunk([u8; 1024]);
+    /// impl Component for Chunk {}
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..100).map(|_| world.spawn_empty()).collect();
+    /// world.memory().set_budget(Some(16 * 1024));
+    ///
+    /// let result = world.try_insert_batch(entities.iter().map(|&e| (e, Chunk([0; 1024]))));
+    /// assert!(matches!(result, Err(InsertError::OverBudget(_))));
+    /// assert!(!world.has::<Chunk>(entities[0]));
+    /// ```
+    pub fn try_insert_batch<T: Component>(
+        &mut self,
+        batch: impl IntoIterator<Item = (EntityId, T)>,
+    ) -> Result<usize, InsertError> {
+        self.insert_batch_with(batch, true)
+    }
+
+    /// Shared body of [`insert_batch`](Self::insert_batch) and
+    /// [`try_insert_batch`](Self::try_insert_batch). With `strict` unset,
+    /// failures are skipped and no error is returned.
+    fn insert_batch_with<T: Component>(
+        &mut self,
+        batch: impl IntoIterator<Item = (EntityId, T)>,
+        strict: bool,
+    ) -> Result<usize, InsertError> {
+        self.flush_reserved();
+
+        let info = ComponentInfo::of::<T>();
+        let component_type_id = info.type_id();
+
+        // Requirements and exclusive groups make the transition depend on
+        // each entity's other components
+        let registry = ComponentRegistry::global();
+        if !registry.requirements::<T>().is_empty()
+            || !registry.exclusive_with(component_type_id).is_empty()
+        {
+            let mut inserted = 0;
+            for (entity, component) in batch {
+                match self.try_insert(entity, component) {
+                    Ok(()) => inserted += 1,
+                    Err(InsertError::InvalidEntity) => {}
+                    Err(error) if strict => return Err(error),
+                    Err(_) => {}
+                }
+            }
+            return Ok(inserted);
+        }
+
+        // Group by source archetype so each transition is resolved once
+        let mut sources = Vec::new();
+        let mut groups: Vec<Vec<(EntityId, T)>> = Vec::new();
+        let mut group_index: HashMap<Option<ArchetypeId>, usize> = HashMap::new();
+        for (entity, component) in batch {
+            if !self.is_alive(entity) {
+                continue;
+            }
+            let source = self
+                .archetypes
+                .get_entity_location(entity)
+                .map(|location| location.archetype_id);
+            let index = *group_index.entry(source).or_insert_with(|| {
+                sources.push(source);
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push((entity, component));
+        }
+
+        // Grow each target's columns once for all the entities moving in;
+        // if that fails, the per-entity reservations below report it
+        let mut targets = Vec::with_capacity(sources.len());
+        let mut arriving: HashMap<ArchetypeId, usize> = HashMap::new();
+        for (source, items) in sources.iter().zip(&groups) {
+            let source_id = source.unwrap_or(ArchetypeId::new(0));
+            let target_id = self.archetypes.get_or_create_add_target(source_id, &info);
+            if source.is_none() || target_id != source_id {
+                *arriving.entry(target_id).or_default() += items.len();
+            }
+            targets.push(target_id);
+        }
+        for (&target_id, &count) in &arriving {
+            if let Some(archetype) = self.archetypes.get_archetype_mut(target_id) {
+                let reserved = archetype.try_reserve(count);
+                if strict {
+                    reserved?;
+                }
+            }
+        }
+
+        let mut inserted = 0;
+        for ((source, target_id), items) in sources.into_iter().zip(targets).zip(groups) {
+            let source_id = source.unwrap_or(ArchetypeId::new(0));
+            for (entity, component) in items {
+                let current = self
+                    .archetypes
+                    .get_entity_location(entity)
+                    .map(|location| location.archetype_id);
+                if current != source {
+                    // Moved by an earlier item in this batch (duplicate entity)
+                    match self.try_insert(entity, component) {
+                        Ok(()) => inserted += 1,
+                        Err(error) if strict => return Err(error),
+                        Err(_) => {}
+                    }
+                    continue;
+                }
+
+                if source.is_some() && target_id == source_id {
+                    // Already has the component, replace in place
+                    if let Some(archetype) = self.archetypes.get_archetype_mut(source_id) {
+                        unsafe {
+                            if let Some(existing) = archetype.get_component_mut::<T>(entity) {
+                                *existing = component;
+                            }
+                        }
+                    }
+                } else {
+                    match self.reserve_row(target_id) {
+                        Ok(()) => {}
+                        Err(error) if strict => return Err(error.into()),
+                        Err(_) => continue,
+                    }
+                    if source.is_some() {
+                        let component_data =
+                            [(component_type_id, &component as *const T as *const u8)];
+                        let moved = unsafe {
+                            self.move_entity(entity, source_id, target_id, &component_data)
+                        };
+                        if moved.is_none() {
+                            continue;
+                        }
+                        std::mem::forget(component); // Component was moved
+                    } else if let Some(archetype) = self.archetypes.get_archetype_mut(target_id) {
+                        let row = archetype.allocate_row(entity);
+                        unsafe {
+                            archetype.set_component(
+                                row,
+                                component_type_id,
+                                &component as *const T as *const u8,
+                            );
+                        }
+                        self.archetypes.set_entity_location(
+                            entity,
+                            crate::component::archetype::EntityLocation {
+                                archetype_id: target_id,
+                                row,
+                            },
+                        );
+                        std::mem::forget(component); // Component was moved
+                    } else {
+                        continue;
+                    }
+                }
+
+                self.track_modified(entity);
+                self.revisions.bump(entity, component_type_id);
+                inserted += 1;
+            }
+        }
+
+        Ok(inserted)
+    }
+
+    /// Inserts a type-erased component into an entity.
+    ///
+    /// This is the runtime-typed form of [`insert`](Self::insert), used by
+    /// bindings that only know a component through its [`ComponentInfo`].
+    /// The bytes at `component` are moved into the world; the caller must
+    /// not drop or reuse them afterwards. An existing component of the same
+    /// type is dropped and replaced. Requirements and exclusive groups are
+    /// not applied.
+    ///
+    /// # Returns
+    ///
+    /// `true` if successful, `false` if the entity doesn't exist or the
+    /// component storage cannot grow within the memory budget. The value
+    /// is not moved when `false` is returned, so the caller still owns it.
+    ///
+    /// # Safety
+    ///
+    /// `component` must point to a valid, properly aligned value of the type
+    /// described by `info`.
+    pub unsafe fn insert_raw(
+        &mut self,
+        entity: EntityId,
+        info: &ComponentInfo,
+        component: *const u8,
+    ) -> bool {
+        self.flush_reserved();
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        let component_type_id = info.type_id();
+        let location = self.archetypes.get_entity_location(entity);
+        let source_id = location
+            .map(|location| location.archetype_id)
+            .unwrap_or(ArchetypeId::new(0));
+        let target_id = self.archetypes.get_or_create_add_target(source_id, info);
+
+        if location.is_some() && target_id == source_id {
+            // Already has the component, replace in place
+            if let Some(archetype) = self.archetypes.get_archetype_mut(source_id)
+                && let Some(row) = archetype.get_entity_row(entity)
+                && let Some(storage) = archetype.get_storage_mut(component_type_id)
+            {
+                // SAFETY: The row holds an initialized value of this type, and
+                // the caller guarantees `component` is a valid value of it
+                unsafe {
+                    let existing = storage.get_mut(row);
+                    // Writes the new value even if the old one's drop panics
+                    let _write = WriteOnDrop {
+                        dst: existing,
+                        src: component,
+                        size: info.size(),
+                    };
+                    info.drop(existing);
+                }
+            }
+        } else if location.is_some() {
+            if self.reserve_row(target_id).is_err() {
+                return false;
+            }
+            let component_data = [(component_type_id, component)];
+            // SAFETY: The caller guarantees `component` is valid for the type
+            let moved = unsafe { self.move_entity(entity, source_id, target_id, &component_data) };
+            if moved.is_none() {
+                return false;
+            }
+        } else if self.reserve_row(target_id).is_err() {
+            return false;
+        } else if let Some(archetype) = self.archetypes.get_archetype_mut(target_id) {
+            let row = archetype.allocate_row(entity);
+            // SAFETY: The caller guarantees `component` is valid for the type
+            unsafe {
+                archetype.set_component(row, component_type_id, component);
+            }
+            self.archetypes.set_entity_location(
+                entity,
+                crate::component::archetype::EntityLocation {
+                    archetype_id: target_id,
+                    row,
+                },
+            );
+        }
 
+        self.track_modified(entity);
+        self.revisions.bump(entity, component_type_id);
         true
     }
 
+    /// Inserts the registered default value of a component into an entity.
+    ///
+    /// Defaults are registered through
+    /// [`ComponentRegistry::register_default`](crate::component::registry::ComponentRegistry::register_default).
+    ///
+    /// # Returns
+    ///
+    /// `true` if successful, `false` if the entity doesn't exist or no
+    /// default is registered for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Health(u32);
+    /// impl Component for Health {}
+    ///
+    /// ComponentRegistry::global().register_default(|| Health(100));
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    /// assert!(world.insert_default::<Health>(entity));
+    /// assert_eq!(world.get::<Health>(entity), Some(&Health(100)));
+    /// ```
+    pub fn insert_default<T: Component>(&mut self, entity: EntityId) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        match ComponentRegistry::global().default_value::<T>() {
+            Some(component) => self.insert(entity, component),
+            None => false,
+        }
+    }
+
+    /// Inserts the registered default value of a component identified at runtime.
+    ///
+    /// This is the type-erased form of [`insert_default`](Self::insert_default),
+    /// for callers that only know the component type at runtime.
+    ///
+    /// # Returns
+    ///
+    /// `true` if successful, `false` if the entity doesn't exist or no
+    /// default is registered for the type.
+    pub fn insert_default_by_id(&mut self, entity: EntityId, type_id: ComponentTypeId) -> bool {
+        match ComponentRegistry::global().default_inserter(type_id) {
+            Some(insert) => insert(self, entity),
+            None => false,
+        }
+    }
+
+    /// Inserts the registered defaults of the
+    /// [required components](ComponentRegistry::register_requires) an entity
+    /// is missing.
+    ///
+    /// Loaders restore components one at a time without resolving
+    /// requirements, then call this, so an entity saved before one of its
+    /// types gained a requirement loads with the required component's
+    /// default. Requirements without a registered default are left out.
+    ///
+    /// # Returns
+    ///
+    /// The number of components inserted.
+    pub(crate) fn insert_missing_requirements(&mut self, entity: EntityId) -> usize {
+        let registry = ComponentRegistry::global();
+        let mut pending: Vec<TypeId> = self
+            .component_types_of(entity)
+            .iter()
+            .map(ComponentTypeId::type_id)
+            .collect();
+        let mut seen: HashSet<TypeId> = pending.iter().copied().collect();
+        let mut inserted = 0;
+        while let Some(type_id) = pending.pop() {
+            for requirement in registry.requirements_of(type_id) {
+                if !seen.insert(requirement.type_id) {
+                    continue;
+                }
+                let Some(insert) = registry.requirement_inserter(requirement.type_id) else {
+                    continue;
+                };
+                if insert(self, entity).is_ok() {
+                    inserted += 1;
+                    pending.push(requirement.type_id);
+                }
+            }
+        }
+        inserted
+    }
+
     /// Removes a component from an entity.
     ///
     /// This operation may move the entity to a different archetype.
@@ -758,67 +2781,124 @@ impl World {
             .get_archetype(current_archetype_id)?
             .get_entity_row(entity)?;
 
-        // Collect remaining component types (all except the one being removed)
-        let (new_component_types, component_info) = self
-            .archetypes
-            .get_archetype(current_archetype_id)
-            .map(|a| {
-                let mut types = a.component_types().clone();
-                types.remove(component_type_id);
-
-                let mut infos = Vec::new();
-                // Collect ComponentInfo for remaining types
-                for type_id in types.iter() {
-                    if let Some(storage) = a.get_storage(type_id) {
-                        infos.push(storage.info().clone());
-                    }
-                }
-                (types, infos)
-            })
-            .unwrap_or_default();
-
         // Read the component value before moving (but after we know the row)
         // We need to do this before move_entity_between_archetypes because that will
         // remove the entity from the source archetype
+        // The copy stays in a ManuallyDrop until the move is done, so if the
+        // move panics the column still owns the one live value
         let component_value = unsafe {
             let archetype = self.archetypes.get_archetype(current_archetype_id)?;
             let storage = archetype.get_storage(component_type_id)?;
             let ptr = storage.get(row) as *const T;
-            std::ptr::read(ptr)
+            std::mem::ManuallyDrop::new(std::ptr::read(ptr))
         };
 
-        // Get or create target archetype (may be empty archetype)
+        // Follow the remove edge to the target archetype (may be empty archetype)
         let target_archetype_id = self
             .archetypes
-            .get_or_create_archetype(new_component_types, component_info);
+            .get_or_create_remove_target(current_archetype_id, component_type_id);
 
         // Move entity to new archetype (this copies remaining components)
         // Note: The component we're removing won't be copied because the target
         // archetype doesn't have that component type
-        let target_row = unsafe {
-            self.archetypes.move_entity_between_archetypes(
+        unsafe {
+            self.move_entity(
                 entity,
                 current_archetype_id,
                 target_archetype_id,
                 &[], // No new components to add
-            )
-        };
-
-        // Update entity location
-        if let Some(row) = target_row {
-            self.archetypes.set_entity_location(
-                entity,
-                crate::component::archetype::EntityLocation {
-                    archetype_id: target_archetype_id,
-                    row,
-                },
             );
         }
 
-        // Track component modification for persistence
-        self.persistence.change_tracker_mut().track_modified(entity);
+        // Track component modification for persistence
+        self.track_modified(entity);
+
+        Some(std::mem::ManuallyDrop::into_inner(component_value))
+    }
+
+    /// Removes a bundle of components from an entity and returns them.
+    ///
+    /// All components are removed in a single archetype transition. If the
+    /// entity doesn't have every component in the bundle, nothing is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity to take components from
+    ///
+    /// # Returns
+    ///
+    /// The removed components, or `None` if the entity doesn't exist or is
+    /// missing any of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bundle contains the same component type more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Idle { since: u32 }
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Patrol { waypoint: u32 }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_bundle((Idle { since: 3 }, Patrol { waypoint: 7 }));
+    ///
+    /// let (idle, patrol) = world.take::<(Idle, Patrol)>(entity).unwrap();
+    /// assert_eq!(idle, Idle { since: 3 });
+    /// assert_eq!(patrol, Patrol { waypoint: 7 });
+    /// assert!(!world.has::<Idle>(entity));
+    /// ```
+    pub fn take<B: Bundle>(&mut self, entity: EntityId) -> Option<B> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+
+        let bundle_info = B::component_info();
+        let bundle_types: ComponentSet = bundle_info.iter().map(|info| info.type_id()).collect();
+        assert_eq!(
+            bundle_types.len(),
+            bundle_info.len(),
+            "bundle contains duplicate component types"
+        );
+
+        let location = self.archetypes.get_entity_location(entity)?;
+        let source = self.archetypes.get_archetype(location.archetype_id)?;
+        if !bundle_types.is_subset(source.component_types()) {
+            return None;
+        }
+        let row = source.get_entity_row(entity)?;
+
+        // Remaining components determine the target archetype
+        let mut target_types = source.component_types().clone();
+        for type_id in bundle_types.iter() {
+            target_types.remove(type_id);
+        }
+        let target_info = source
+            .component_info()
+            .iter()
+            .filter(|info| !bundle_types.contains(info.type_id()))
+            .cloned()
+            .collect();
+
+        // SAFETY: All bundle components exist in the row; the entity is moved
+        // below without copying or dropping them, so ownership is ours once
+        // the move succeeds. Until then the copy must not be dropped
+        let bundle = std::mem::ManuallyDrop::new(unsafe { B::take_from_archetype(source, row) });
+
+        let target_id = self
+            .archetypes
+            .get_or_create_archetype(target_types, target_info);
+        unsafe {
+            self.move_entity(entity, location.archetype_id, target_id, &[]);
+        }
 
-        Some(component_value)
+        self.track_modified(entity);
+        Some(std::mem::ManuallyDrop::into_inner(bundle))
     }
 
     /// Gets an immutable reference to a component on an entity.
@@ -892,11 +2972,14 @@ impl World {
         }
 
         let location = self.archetypes.get_entity_location(entity)?;
-        let archetype = self.archetypes.get_archetype_mut(location.archetype_id)?;
 
         // Track component modification for persistence
-        self.persistence.change_tracker_mut().track_modified(entity);
+        self.track_modified(entity);
 
+        let archetype = self.archetypes.get_archetype_mut(location.archetype_id)?;
+        if archetype.has_component::<T>() {
+            self.revisions.bump(entity, ComponentTypeId::of::<T>());
+        }
         unsafe { archetype.get_component_mut::<T>(entity) }
     }
 
@@ -908,34 +2991,559 @@ impl World {
     ///
     /// # Returns
     ///
-    /// `true` if the entity has the component, `false` otherwise.
+    /// `true` if the entity has the component, `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn_empty();
+    ///
+    /// assert!(!world.has::<Position>(entity));
+    /// world.insert(entity, Position { x: 1.0, y: 2.0 });
+    /// assert!(world.has::<Position>(entity));
+    /// ```
+    pub fn has<T: Component>(&self, entity: EntityId) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.archetypes
+            .get_entity_location(entity)
+            .and_then(|location| self.archetypes.get_archetype(location.archetype_id))
+            .map(|archetype| archetype.has_component::<T>())
+            .unwrap_or(false)
+    }
+
+    /// Enables or disables an entity.
+    ///
+    /// Disabling adds the [`Disabled`] marker, which queries skip unless
+    /// their filter includes it with
+    /// [`Including<Disabled>`](crate::query::filter::Including). The
+    /// entity keeps its components and stays reachable through
+    /// [`get`](Self::get) and friends.
+    ///
+    /// # Returns
+    ///
+    /// `true` if successful, `false` if the entity doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
+    ///
+    /// world.set_enabled(entity, false);
+    /// assert!(!world.is_enabled(entity));
+    /// assert_eq!(world.query::<&Position>().count(), 0);
+    /// assert!(world.get::<Position>(entity).is_some());
+    ///
+    /// world.set_enabled(entity, true);
+    /// assert_eq!(world.query::<&Position>().count(), 1);
+    /// ```
+    pub fn set_enabled(&mut self, entity: EntityId, enabled: bool) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        if enabled {
+            self.remove::<Disabled>(entity);
+        } else if !self.has::<Disabled>(entity) {
+            self.insert(entity, Disabled);
+        }
+        true
+    }
+
+    /// Returns `true` if the entity exists and is not [`Disabled`].
+    pub fn is_enabled(&self, entity: EntityId) -> bool {
+        self.is_alive(entity) && !self.has::<Disabled>(entity)
+    }
+
+    /// Registers a [`ComponentIndex`] keyed by `extractor(&T)`.
+    ///
+    /// The index is built from the current entities immediately and kept up
+    /// to date as components are inserted, removed or mutably borrowed.
+    /// Registering the same `T` and `K` again replaces the extractor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Team(u8);
+    /// impl Component for Team {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_index(|team: &Team| team.0);
+    ///
+    /// let red = world.spawn().with(Team(1)).id();
+    /// assert_eq!(world.index::<Team, u8>().get(&1), &[red]);
+    /// ```
+    pub fn register_index<T, K>(&mut self, extractor: fn(&T) -> K)
+    where
+        T: Component,
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+    {
+        let mut index = ComponentIndex::new(extractor);
+        index.rebuild(&self.archetypes);
+        self.indexes
+            .insert(TypeId::of::<ComponentIndex<T, K>>(), Box::new(index));
+    }
+
+    /// Returns the index registered for `T` and `K`, bringing it up to date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no such index was registered with
+    /// [`register_index`](Self::register_index).
+    pub fn index<T, K>(&mut self) -> &ComponentIndex<T, K>
+    where
+        T: Component,
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+    {
+        let index = self
+            .indexes
+            .get_mut(&TypeId::of::<ComponentIndex<T, K>>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "no index registered for {} keyed by {}",
+                    std::any::type_name::<T>(),
+                    std::any::type_name::<K>()
+                )
+            });
+        index.refresh(&self.archetypes);
+        index
+            .as_any()
+            .downcast_ref()
+            .expect("index stored under mismatched type")
+    }
+
+    /// Rebuilds the index for `T` and `K` from scratch.
+    ///
+    /// Needed after mutating `T` through query iterators, which the index
+    /// does not observe. Does nothing if no such index is registered.
+    pub fn rebuild_index<T, K>(&mut self)
+    where
+        T: Component,
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+    {
+        if let Some(index) = self
+            .indexes
+            .get_mut(&TypeId::of::<ComponentIndex<T, K>>())
+            .and_then(|index| index.as_any_mut().downcast_mut::<ComponentIndex<T, K>>())
+        {
+            index.rebuild(&self.archetypes);
+        }
+    }
+
+    /// Registers a [`SpatialGrid`](crate::spatial::SpatialGrid) over the
+    /// positions returned by `position`.
+    ///
+    /// The grid is built from the current entities immediately. Registering
+    /// again replaces the previous grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` is not positive and finite.
+    #[cfg(feature = "spatial")]
+    pub fn register_spatial<T: Component>(&mut self, cell_size: f32, position: fn(&T) -> [f32; 3]) {
+        let mut grid = crate::spatial::SpatialGrid::new(cell_size, position);
+        grid.rebuild(&self.archetypes);
+        self.indexes
+            .insert(TypeId::of::<crate::spatial::SpatialGrid>(), Box::new(grid));
+    }
+
+    /// Returns the spatial grid, bringing it up to date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no grid was registered with
+    /// [`register_spatial`](Self::register_spatial).
+    #[cfg(feature = "spatial")]
+    pub fn spatial(&mut self) -> &crate::spatial::SpatialGrid {
+        let grid = self
+            .indexes
+            .get_mut(&TypeId::of::<crate::spatial::SpatialGrid>())
+            .expect("no spatial grid registered");
+        grid.refresh(&self.archetypes);
+        grid.as_any()
+            .downcast_ref()
+            .expect("spatial grid stored under mismatched type")
+    }
+
+    /// Rebuilds the spatial grid from scratch.
+    ///
+    /// Needed after moving entities through query iterators, which the grid
+    /// does not observe. Does nothing if no grid is registered.
+    #[cfg(feature = "spatial")]
+    pub fn rebuild_spatial(&mut self) {
+        if let Some(grid) = self
+            .indexes
+            .get_mut(&TypeId::of::<crate::spatial::SpatialGrid>())
+            .and_then(|grid| {
+                grid.as_any_mut()
+                    .downcast_mut::<crate::spatial::SpatialGrid>()
+            })
+        {
+            grid.rebuild(&self.archetypes);
+        }
+    }
+
+    /// Records a component change for persistence and component indexes.
+    fn track_modified(&mut self, entity: EntityId) {
+        self.persistence.change_tracker_mut().track_modified(entity);
+        self.mark_indexes_dirty(entity);
+    }
+
+    /// Records a despawn for persistence and component indexes.
+    fn track_deleted(&mut self, entity: EntityId) {
+        self.persistence.change_tracker_mut().track_deleted(entity);
+        self.revisions.forget(entity);
+        self.mark_indexes_dirty(entity);
+    }
+
+    fn mark_indexes_dirty(&mut self, entity: EntityId) {
+        for index in self.indexes.values_mut() {
+            index.mark_dirty(entity);
+        }
+    }
+
+    /// Returns raw component columns for every archetype containing all of
+    /// `component_types`.
+    ///
+    /// This is a low-level API for FFI and scripting bindings: each item
+    /// exposes type-erased column pointers plus a row count, so a foreign
+    /// caller can process an archetype in one call. Empty archetypes are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::ComponentTypeId;
+    ///
+    /// #[derive(Debug)]
+    /// struct Health(u32);
+    /// impl Component for Health {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Health(10)).id();
+    /// world.spawn().with(Health(20)).id();
+    ///
+    /// let types = [ComponentTypeId::of::<Health>()];
+    /// for slices in world.iter_archetype_columns(&types) {
+    ///     let column = slices.columns()[0];
+    ///     let health = column.ptr as *mut Health;
+    ///     for row in 0..slices.len() {
+    ///         // SAFETY: The column holds `slices.len()` Health values
+    ///         unsafe { (*health.add(row)).0 += 1 };
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_archetype_columns<'w>(
+        &'w mut self,
+        component_types: &'w [ComponentTypeId],
+    ) -> impl Iterator<Item = crate::component::archetype::ColumnSlices<'w>> + 'w {
+        self.flush_reserved();
+        self.archetypes
+            .iter_mut()
+            .filter(|archetype| !archetype.is_empty())
+            .filter_map(move |archetype| archetype.column_slices(component_types))
+    }
+
+    /// Returns every archetype in the world, in creation order.
+    ///
+    /// Archetype 0 holds entities without components.
+    pub fn archetypes(&self) -> impl Iterator<Item = &Archetype> {
+        self.archetypes.iter()
+    }
+
+    /// Returns an archetype by ID.
+    pub fn archetype(&self, id: ArchetypeId) -> Option<&Archetype> {
+        self.archetypes.get_archetype(id)
+    }
+
+    /// Returns the archetype an entity is stored in.
+    pub fn archetype_of(&self, entity: EntityId) -> Option<ArchetypeId> {
+        if !self.entities.is_alive(entity) {
+            return None;
+        }
+        self.archetypes
+            .get_entity_location(entity)
+            .map(|location| location.archetype_id)
+    }
+
+    /// Creates the archetype of a bundle ahead of time.
+    ///
+    /// Creates the bundle's archetype along with the intermediate archetypes
+    /// and transition edges [`spawn_bundle`](Self::spawn_bundle) walks, so
+    /// the first spawn of the bundle during a latency-sensitive frame does
+    /// not pay for archetype creation and hash insertion. Calling it again
+    /// is cheap and returns the same archetype.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position { x: f32, y: f32 }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity { x: f32, y: f32 }
+    ///
+    /// let mut world = World::new();
+    /// let archetype = world.register_archetype::<(Position, Velocity)>();
+    ///
+    /// let entity = world.spawn_bundle((
+    ///     Position { x: 0.0, y: 0.0 },
+    ///     Velocity { x: 1.0, y: 0.0 },
+    /// ));
+    /// assert_eq!(world.archetype_of(entity), Some(archetype));
+    /// ```
+    pub fn register_archetype<B: Bundle>(&mut self) -> ArchetypeId {
+        self.bundle_path::<B>()
+            .last()
+            .copied()
+            .unwrap_or(ArchetypeId::new(0))
+    }
+
+    /// Creates and returns the archetypes `spawn_bundle` moves an entity
+    /// through, ending with the bundle's archetype.
+    fn bundle_path<B: Bundle>(&mut self) -> Vec<ArchetypeId> {
+        let mut source = ArchetypeId::new(0);
+        B::component_info()
+            .iter()
+            .map(|info| {
+                source = self.archetypes.get_or_create_add_target(source, info);
+                source
+            })
+            .collect()
+    }
+
+    /// Reserves room for `additional` more entities with bundle `B`.
+    ///
+    /// Creates the bundle's archetype if needed, like
+    /// [`register_archetype`](Self::register_archetype), and grows its
+    /// component columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MemoryError`] if
+    /// the columns cannot grow, e.g. because that would exceed the
+    /// [memory budget](Self::memory).
+    pub fn try_reserve<B: Bundle>(&mut self, additional: usize) -> Result<(), MemoryError> {
+        let path = self.bundle_path::<B>();
+        for (step, &id) in path.iter().enumerate() {
+            // Entities only pass through the intermediate archetypes
+            let rows = if step + 1 == path.len() {
+                additional
+            } else {
+                1
+            };
+            if let Some(archetype) = self.archetypes.get_archetype_mut(id) {
+                archetype.try_reserve(rows)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawns an entity with a bundle, returning an error instead of
+    /// panicking if its components do not fit in the
+    /// [memory budget](Self::memory).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MemoryError`] if
+    /// the component columns cannot grow. No entity is spawned then.
+    pub fn try_spawn_bundle<B: Bundle>(&mut self, bundle: B) -> Result<EntityId, MemoryError> {
+        self.try_reserve::<B>(1)?;
+        Ok(self.spawn_bundle(bundle))
+    }
+
+    /// Iterates the live entities of one archetype, in row order.
+    ///
+    /// Yields nothing if there is no archetype with that ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Turret;
+    /// impl Component for Turret {}
+    ///
+    /// let mut world = World::new();
+    /// let a = world.spawn().with(Turret).id();
+    /// let b = world.spawn().with(Turret).id();
+    ///
+    /// let id = world.archetype_of(a).unwrap();
+    /// let entities: Vec<_> = world.iter_archetype(id).collect();
+    /// assert_eq!(entities, vec![a, b]);
+    /// ```
+    pub fn iter_archetype(&self, id: ArchetypeId) -> impl Iterator<Item = EntityId> + '_ {
+        self.archetypes
+            .get_archetype(id)
+            .map(|archetype| archetype.entities())
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|&entity| self.entities.is_alive(entity))
+    }
+
+    /// Executes a query over the entities of one archetype, in row order.
+    ///
+    /// This is for archetype-specialized systems that process a known
+    /// layout. Yields nothing if there is no archetype with that ID or it
+    /// does not match the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Heat(f32);
+    /// impl Component for Heat {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Heat(10.0)).id();
+    /// let id = world.archetype_of(entity).unwrap();
+    ///
+    /// for heat in world.query_archetype::<&mut Heat>(id) {
+    ///     heat.0 *= 0.5;
+    /// }
+    /// assert_eq!(world.get::<Heat>(entity).unwrap().0, 5.0);
+    /// ```
+    pub fn query_archetype<Q>(
+        &mut self,
+        id: ArchetypeId,
+    ) -> crate::query::iter::ArchetypeIter<'_, Q::Fetch, Q::Filter>
+    where
+        Q: crate::query::Query,
+    {
+        self.flush_reserved();
+        let context = crate::query::FetchContext::new(&self.entities);
+        match self.archetypes.get_archetype(id) {
+            // SAFETY: `self` is borrowed mutably for the iterator's lifetime
+            Some(archetype) => unsafe {
+                crate::query::iter::ArchetypeIter::new(archetype, context)
+            },
+            None => crate::query::iter::ArchetypeIter::empty(),
+        }
+    }
+
+    /// Guarantees that queries yield entities in ascending [`EntityId`] order.
+    ///
+    /// Without this, queries visit archetypes in creation order, which is
+    /// reproducible for identical operation sequences but changes with
+    /// archetype layout. Lockstep simulations that must agree across peers or
+    /// across save/load should enable it. Ordered iteration collects and sorts
+    /// the matched rows, so it is slower than the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.set_deterministic_iteration(true);
+    /// assert!(world.is_deterministic_iteration());
+    /// ```
+    pub fn set_deterministic_iteration(&mut self, enabled: bool) {
+        self.deterministic_iteration = enabled;
+    }
+
+    /// Returns `true` if queries iterate in entity ID order.
+    pub fn is_deterministic_iteration(&self) -> bool {
+        self.deterministic_iteration
+    }
+
+    /// Pins the Unix timestamp that saves record, or restores the current
+    /// time with `None`.
+    ///
+    /// Saves are otherwise a pure function of the world's contents, so with
+    /// a pinned timestamp two runs that build the same world write
+    /// byte-identical files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// let mut world = World::new();
+    /// world.set_fixed_timestamp(Some(1_700_000_000));
+    /// world.update_metadata();
+    /// assert_eq!(world.metadata().timestamp, 1_700_000_000);
+    /// ```
+    pub fn set_fixed_timestamp(&mut self, timestamp: Option<u64>) {
+        self.fixed_timestamp = timestamp;
+    }
+
+    /// Replaces the source of stable IDs for entities spawned from now on.
+    ///
+    /// Entities spawned with an explicit stable ID, including loaded ones,
+    /// are unaffected. See [`crate::entity::generator`] for the built-in
+    /// schemes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::World;
+    /// use pecs::entity::generator::SequentialIds;
+    /// use pecs::entity::id::StableId;
+    ///
+    /// let mut world = World::new();
+    /// world.set_id_generator(SequentialIds::starting_at(1000));
+    /// let entity = world.spawn_empty();
+    /// assert_eq!(world.get_stable_id(entity), Some(StableId::from_raw(1000)));
+    /// ```
+    pub fn set_id_generator(&mut self, generator: impl StableIdGenerator + 'static) {
+        self.entities.set_id_generator(Box::new(generator));
+    }
+
+    /// Turns entity change tracking on or off.
+    ///
+    /// Tracking is on by default and feeds incremental saves and
+    /// [`ChangeTracker::changes_since`](crate::persistence::ChangeTracker::changes_since).
+    /// Every spawn, insert, removal and `get_mut` records the entity in a
+    /// hash table, so worlds that never persist incrementally can turn it
+    /// off to skip that cost. Changes made while tracking is off are never
+    /// reported.
     ///
     /// # Examples
     ///
     /// ```
     /// use pecs::prelude::*;
     ///
-    /// #[derive(Debug)]
-    /// struct Position { x: f32, y: f32 }
-    /// impl Component for Position {}
-    ///
     /// let mut world = World::new();
-    /// let entity = world.spawn_empty();
-    ///
-    /// assert!(!world.has::<Position>(entity));
-    /// world.insert(entity, Position { x: 1.0, y: 2.0 });
-    /// assert!(world.has::<Position>(entity));
+    /// world.set_change_tracking(false);
+    /// world.spawn_empty();
+    /// assert!(!world.is_change_tracking());
+    /// assert!(!world.persistence().change_tracker().has_changes());
     /// ```
-    pub fn has<T: Component>(&self, entity: EntityId) -> bool {
-        if !self.is_alive(entity) {
-            return false;
-        }
+    pub fn set_change_tracking(&mut self, enabled: bool) {
+        self.persistence.change_tracker_mut().set_enabled(enabled);
+    }
 
-        self.archetypes
-            .get_entity_location(entity)
-            .and_then(|location| self.archetypes.get_archetype(location.archetype_id))
-            .map(|archetype| archetype.has_component::<T>())
-            .unwrap_or(false)
+    /// Returns `true` if entity changes are being tracked.
+    pub fn is_change_tracking(&self) -> bool {
+        self.persistence.change_tracker().is_enabled()
     }
 
     /// Executes a query over all entities in the world.
@@ -947,6 +3555,12 @@ impl World {
     ///
     /// * `Q` - The query type (e.g., `&Position`, `(&mut Position, &Velocity)`)
     ///
+    /// # Panics
+    ///
+    /// Panics if `Q` borrows a component mutably alongside another access to
+    /// it, such as `(&mut Position, &Position)` or
+    /// `(&mut Position, &mut Position)`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -976,7 +3590,12 @@ impl World {
     where
         Q: crate::query::Query,
     {
-        crate::query::iter::QueryIter::new(&self.archetypes)
+        let iter = if self.deterministic_iteration {
+            crate::query::iter::QueryIter::new_deterministic(&self.archetypes)
+        } else {
+            crate::query::iter::QueryIter::new(&self.archetypes)
+        };
+        iter.with_entities(&self.entities)
     }
 
     /// Executes a filtered query over all entities in the world.
@@ -1003,7 +3622,82 @@ impl World {
         Q: crate::query::Query,
         F: for<'a> crate::query::Filter<'a>,
     {
-        crate::query::iter::QueryIter::new(&self.archetypes)
+        let iter = if self.deterministic_iteration {
+            crate::query::iter::QueryIter::new_deterministic(&self.archetypes)
+        } else {
+            crate::query::iter::QueryIter::new(&self.archetypes)
+        };
+        iter.with_entities(&self.entities)
+    }
+
+    /// Dry-runs a filtered query and reports how it treats each archetype.
+    ///
+    /// Nothing is fetched. The returned [`QueryPlan`](crate::query::QueryPlan)
+    /// lists every archetype with its components and entity count, whether
+    /// the query visits it, and otherwise which fetched components it lacks
+    /// or which filters excluded it. Print it to debug a query that returns
+    /// nothing. Use `()` as `F` for an unfiltered query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::query::ArchetypeMatch;
+    /// use pecs::query::filter::Without;
+    ///
+    /// struct Position(f32);
+    /// impl Component for Position {}
+    /// struct Velocity(f32);
+    /// impl Component for Velocity {}
+    /// struct Frozen;
+    /// impl Component for Frozen {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Position(0.0)).id();
+    /// world.spawn().with(Position(0.0)).with(Velocity(1.0)).with(Frozen).id();
+    ///
+    /// let plan = world.explain_query::<(&mut Position, &Velocity), Without<Frozen>>();
+    /// assert_eq!(plan.matched_entities(), 0);
+    /// let outcomes: Vec<_> = plan.archetypes().iter().map(|a| &a.outcome).collect();
+    /// assert!(outcomes.contains(&&ArchetypeMatch::MissingComponents(vec!["Velocity".into()])));
+    /// assert!(outcomes.contains(&&ArchetypeMatch::Filtered(vec!["Without<Frozen>".into()])));
+    /// ```
+    pub fn explain_query<Q, F>(&self) -> crate::query::QueryPlan
+    where
+        Q: crate::query::Query,
+        F: for<'a> crate::query::Filter<'a>,
+    {
+        crate::query::QueryPlan::explain::<Q::Fetch, F>(
+            &self.archetypes,
+            std::any::type_name::<Q>(),
+            std::any::type_name::<F>(),
+        )
+    }
+
+    /// Executes a filtered query through a shared reference.
+    ///
+    /// This is the building block for code that holds several queries and
+    /// resources at once, such as system schedulers. Use
+    /// [`Access`](crate::query::Access) to check that the queries held
+    /// together do not conflict.
+    ///
+    /// # Safety
+    ///
+    /// While the iterator or any item it yields is alive, no other
+    /// reference may exist to a component the query writes, and no mutable
+    /// reference may exist to a component it reads. No structural change
+    /// (spawn, despawn, insert, remove) may happen either.
+    pub unsafe fn query_unchecked<Q, F>(&self) -> crate::query::iter::QueryIter<'_, Q::Fetch, F>
+    where
+        Q: crate::query::Query,
+        F: for<'a> crate::query::Filter<'a>,
+    {
+        let iter = if self.deterministic_iteration {
+            crate::query::iter::QueryIter::new_deterministic(&self.archetypes)
+        } else {
+            crate::query::iter::QueryIter::new(&self.archetypes)
+        };
+        iter.with_entities(&self.entities)
     }
 
     /// Saves the world to a file using the default persistence plugin.
@@ -1012,6 +3706,11 @@ impl World {
     ///
     /// * `path` - Path to save the world to
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save,
+    /// including compression statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -1024,15 +3723,16 @@ impl World {
     /// ```rust,ignore
     /// use pecs::World;
     ///
-    /// let world = World::new();
-    /// world.save("world.pecs")?;
+    /// let mut world = World::new();
+    /// let info = world.save("world.pecs")?;
+    /// println!("saved {} entities", info.entities);
     /// ```
-    pub fn save(&self, path: impl AsRef<std::path::Path>) -> crate::persistence::Result<()> {
-        // Update metadata before saving
-        let mut metadata = self.metadata.clone();
-        metadata.entity_count = self.len();
-        metadata.timestamp = WorldMetadata::current_timestamp();
-
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
+        self.prepare_save()?;
         self.persistence.save(self, path)
     }
 
@@ -1043,6 +3743,11 @@ impl World {
     /// * `path` - Path to save the world to
     /// * `plugin_name` - Name of the plugin to use
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save,
+    /// including compression statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -1055,18 +3760,25 @@ impl World {
     /// ```rust,ignore
     /// use pecs::World;
     ///
-    /// let world = World::new();
+    /// let mut world = World::new();
     /// world.save_with("world.json", "json")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn save_with(
-        &self,
+        &mut self,
         path: impl AsRef<std::path::Path>,
         plugin_name: &str,
-    ) -> crate::persistence::Result<()> {
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
+        self.prepare_save()?;
         self.persistence.save_with(self, path, plugin_name)
     }
 
-    /// Loads a world from a file using the default persistence plugin.
+    /// Loads a world from a file using the built-in persistence plugins.
+    ///
+    /// The plugin is chosen by file extension. Only the plugins registered by
+    /// [`PersistenceManager::with_default_plugins`] are available; to load
+    /// with custom plugins or migrations use
+    /// [`load_with_manager`](Self::load_with_manager).
     ///
     /// # Arguments
     ///
@@ -1086,11 +3798,69 @@ impl World {
     ///
     /// let world = World::load("world.pecs")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn load(path: impl AsRef<std::path::Path>) -> crate::persistence::Result<Self> {
-        let persistence = PersistenceManager::new();
+        let persistence = PersistenceManager::with_default_plugins();
         persistence.load(path)
     }
 
+    /// Loads a world from a file, as strictly as `options` demands.
+    ///
+    /// Like [`load`](Self::load), the plugin is chosen by file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No default plugin is registered
+    /// - File cannot be opened
+    /// - Deserialization fails or `options` rejects the data
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pecs::World;
+    /// use pecs::persistence::LoadOptions;
+    ///
+    /// // Keep serving even if a newer client saved unknown components
+    /// let world = World::load_with_options("world.pecs", &LoadOptions::lenient())?;
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load_with_options(
+        path: impl AsRef<std::path::Path>,
+        options: &crate::persistence::LoadOptions,
+    ) -> crate::persistence::Result<Self> {
+        let persistence = PersistenceManager::with_default_plugins();
+        persistence.load_with_options(path, options)
+    }
+
+    /// Rebuilds a world from a [`JournalPlugin`](crate::persistence::JournalPlugin)
+    /// log after a crash.
+    ///
+    /// The world matches its state at the last completed commit, except for
+    /// resources, metadata and components without a JSON registration,
+    /// which are not journaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be read, a complete record is
+    /// malformed, or a journaled component can no longer be inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pecs::World;
+    /// use pecs::persistence::JournalPlugin;
+    ///
+    /// let mut world = World::recover("world.wal")?;
+    /// // Start a fresh journal from the recovered state
+    /// let mut journal = JournalPlugin::create("world.wal", &mut world)?;
+    /// # Ok::<(), pecs::persistence::PersistenceError>(())
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn recover(journal_path: impl AsRef<std::path::Path>) -> crate::persistence::Result<Self> {
+        crate::persistence::journal::recover(journal_path.as_ref())
+    }
+
     /// Loads a world from a file using a specific persistence plugin.
     ///
     /// # Arguments
@@ -1112,20 +3882,87 @@ impl World {
     ///
     /// let world = World::load_with("world.json", "json")?;
     /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn load_with(
         path: impl AsRef<std::path::Path>,
         plugin_name: &str,
     ) -> crate::persistence::Result<Self> {
-        let persistence = PersistenceManager::new();
+        let persistence = PersistenceManager::with_default_plugins();
         persistence.load_with(path, plugin_name)
     }
 
+    /// Loads a world from a file using a caller-provided persistence manager.
+    ///
+    /// Unlike [`load`](Self::load), this honours every plugin and migration
+    /// registered on `manager`. The plugin is chosen by file extension,
+    /// falling back to the manager's default plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No plugin matches the extension and no default plugin is registered
+    /// - File cannot be opened
+    /// - Deserialization or a migration fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use pecs::World;
+    /// use pecs::persistence::PersistenceManager;
+    ///
+    /// let mut manager = PersistenceManager::with_default_plugins();
+    /// manager.register_plugin("yaml", Box::new(YamlPlugin));
+    /// manager.register_extension("yml", "yaml")?;
+    /// manager.register_migration(Box::new(MigrationV1ToV2));
+    ///
+    /// let world = World::load_with_manager(&manager, "world.yml")?;
+    /// ```
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load_with_manager(
+        manager: &PersistenceManager,
+        path: impl AsRef<std::path::Path>,
+    ) -> crate::persistence::Result<Self> {
+        manager.load(path)
+    }
+
+    /// Saves the world to a file using a caller-provided persistence manager.
+    ///
+    /// The counterpart of [`load_with_manager`](Self::load_with_manager):
+    /// metadata is updated as in [`save`](Self::save), then the world is
+    /// written with the plugin `manager` selects for `path`.
+    ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save,
+    /// including compression statistics with the `compression` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No plugin matches the extension and no default plugin is registered
+    /// - File cannot be created
+    /// - Serialization fails
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save_with_manager(
+        &mut self,
+        manager: &PersistenceManager,
+        path: impl AsRef<std::path::Path>,
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
+        self.prepare_save()?;
+        manager.save(self, path)
+    }
+
     /// Saves the world to a writer using binary format.
     ///
     /// # Arguments
     ///
     /// * `writer` - Writer to save to
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save,
+    /// including compression statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if serialization fails.
@@ -1135,16 +3972,21 @@ impl World {
     /// ```rust,ignore
     /// use pecs::World;
     ///
-    /// let world = World::new();
+    /// let mut world = World::new();
     /// let mut buffer = Vec::new();
     /// world.save_binary(&mut buffer)?;
     /// ```
-    pub fn save_binary(&self, writer: &mut dyn std::io::Write) -> crate::persistence::Result<()> {
+    pub fn save_binary(
+        &mut self,
+        writer: &mut dyn std::io::Write,
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
         use crate::persistence::binary::BinarySerializer;
         use crate::persistence::binary::format::FormatFlags;
 
+        self.prepare_save()?;
         let serializer = BinarySerializer::new(FormatFlags::NONE);
-        serializer.serialize(self, writer)
+        self.persistence
+            .run_save(self, || serializer.serialize(self, writer))
     }
 
     /// Loads a world from a reader using binary format.
@@ -1174,12 +4016,34 @@ impl World {
         deserializer.deserialize(reader)
     }
 
+    /// Loads a world from a reader using binary format, as strictly as
+    /// `options` demands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization fails or `options` rejects the
+    /// data.
+    pub fn load_binary_with_options(
+        reader: &mut dyn std::io::Read,
+        options: &crate::persistence::LoadOptions,
+    ) -> crate::persistence::Result<Self> {
+        use crate::persistence::binary::BinaryDeserializer;
+
+        let mut deserializer = BinaryDeserializer::with_options(*options);
+        deserializer.deserialize(reader)
+    }
+
     /// Saves the world to a writer using JSON format.
     ///
     /// # Arguments
     ///
     /// * `writer` - Writer to save to
     ///
+    /// # Returns
+    ///
+    /// A [`SaveInfo`](crate::persistence::SaveInfo) summarizing the save,
+    /// including compression statistics with the `compression` feature.
+    ///
     /// # Errors
     ///
     /// Returns an error if serialization fails.
@@ -1189,15 +4053,20 @@ impl World {
     /// ```rust,ignore
     /// use pecs::World;
     ///
-    /// let world = World::new();
+    /// let mut world = World::new();
     /// let mut buffer = Vec::new();
     /// world.save_json(&mut buffer)?;
     /// ```
-    pub fn save_json(&self, writer: &mut dyn std::io::Write) -> crate::persistence::Result<()> {
+    pub fn save_json(
+        &mut self,
+        writer: &mut dyn std::io::Write,
+    ) -> crate::persistence::Result<crate::persistence::SaveInfo> {
         use crate::persistence::{JsonPlugin, PersistencePlugin};
 
+        self.prepare_save()?;
         let plugin = JsonPlugin::new();
-        plugin.save(self, writer)
+        self.persistence
+            .run_save(self, || plugin.save(self, writer))
     }
 
     /// Loads a world from a reader using JSON format.
@@ -1223,14 +4092,324 @@ impl World {
     pub fn load_json(reader: &mut dyn std::io::Read) -> crate::persistence::Result<Self> {
         use crate::persistence::{JsonPlugin, PersistencePlugin};
 
-        let plugin = JsonPlugin::new();
-        plugin.load(reader)
+        let plugin = JsonPlugin::new();
+        plugin.load(reader)
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A component value cloned into its own allocation, waiting to be moved
+/// into an archetype by [`World::clone_entity`].
+struct ClonedComponent {
+    info: ComponentInfo,
+    ptr: *mut u8,
+    layout: std::alloc::Layout,
+}
+
+impl ClonedComponent {
+    /// Clones the component at `src`, or returns `None` if its type is not
+    /// cloneable.
+    ///
+    /// # Safety
+    ///
+    /// `src` must point to a valid instance of the type described by `info`.
+    unsafe fn new(info: &ComponentInfo, src: *const u8) -> Option<Self> {
+        if !info.is_cloneable() {
+            return None;
+        }
+        let layout = std::alloc::Layout::from_size_align(info.size(), info.alignment()).ok()?;
+        let ptr = alloc_component(layout);
+        let component = Self {
+            info: info.clone(),
+            ptr,
+            layout,
+        };
+        // SAFETY: The caller guarantees `src`, and `ptr` is a fresh
+        // allocation with the type's layout
+        unsafe { info.clone_into(src, ptr) };
+        Some(component)
+    }
+}
+
+impl Drop for ClonedComponent {
+    fn drop(&mut self) {
+        // The value itself has been moved into an archetype; only the
+        // allocation is released
+        if self.layout.size() != 0 {
+            // SAFETY: Allocated in `new` with this layout
+            unsafe { std::alloc::dealloc(self.ptr, self.layout) };
+        }
+    }
+}
+
+/// A component value moved out of an archetype row into its own
+/// allocation, dropped together with it.
+struct DetachedComponent {
+    info: ComponentInfo,
+    ptr: *mut u8,
+    layout: std::alloc::Layout,
+}
+
+impl DetachedComponent {
+    /// Moves the component at `src` out into a new allocation.
+    ///
+    /// # Safety
+    ///
+    /// `src` must point to a valid instance of the type described by `info`,
+    /// which the caller must not drop or read as a value afterwards.
+    unsafe fn new(info: &ComponentInfo, src: *const u8) -> Self {
+        let layout = std::alloc::Layout::from_size_align(info.size(), info.alignment())
+            .expect("invalid layout");
+        let ptr = alloc_component(layout);
+        // SAFETY: The caller guarantees `src`, and `ptr` is a fresh
+        // allocation with the type's layout
+        unsafe { std::ptr::copy_nonoverlapping(src, ptr, layout.size()) };
+        Self {
+            info: info.clone(),
+            ptr,
+            layout,
+        }
+    }
+
+    /// Frees the allocation without dropping the value, which is owned
+    /// elsewhere.
+    fn release(self) {
+        let component = std::mem::ManuallyDrop::new(self);
+        if component.layout.size() != 0 {
+            // SAFETY: Allocated in `new` with this layout
+            unsafe { std::alloc::dealloc(component.ptr, component.layout) };
+        }
+    }
+}
+
+impl Drop for DetachedComponent {
+    fn drop(&mut self) {
+        // Release the allocation even if the value's drop panics
+        let _release = DeallocOnDrop {
+            ptr: self.ptr,
+            layout: self.layout,
+        };
+        // SAFETY: The value was moved here in `new` and is dropped only here
+        unsafe { self.info.drop(self.ptr) };
+    }
+}
+
+/// Allocates room for one component, or a dangling pointer for zero-sized
+/// types.
+fn alloc_component(layout: std::alloc::Layout) -> *mut u8 {
+    if layout.size() == 0 {
+        return std::ptr::without_provenance_mut(layout.align());
+    }
+    // SAFETY: The layout has a non-zero size
+    let ptr = unsafe { std::alloc::alloc(layout) };
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// Frees an allocation from [`alloc_component`] when dropped.
+struct DeallocOnDrop {
+    ptr: *mut u8,
+    layout: std::alloc::Layout,
+}
+
+impl Drop for DeallocOnDrop {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: Allocated by `alloc_component` with this layout
+            unsafe { std::alloc::dealloc(self.ptr, self.layout) };
+        }
+    }
+}
+
+/// Copies a component into a row when dropped, so the row is refilled even
+/// if dropping its previous value panics.
+struct WriteOnDrop {
+    dst: *mut u8,
+    src: *const u8,
+    size: usize,
+}
+
+impl Drop for WriteOnDrop {
+    fn drop(&mut self) {
+        // SAFETY: Set up by the caller with valid, non-overlapping pointers
+        unsafe { std::ptr::copy_nonoverlapping(self.src, self.dst, self.size) };
+    }
+}
+
+/// Statistics returned by [`World::compact`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionStats {
+    /// Number of stale archetype rows that were removed
+    pub rows_removed: usize,
+
+    /// Approximate number of bytes returned to the allocator
+    pub bytes_reclaimed: usize,
+}
+
+/// Statistics returned by [`World::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldStats {
+    /// Number of live entities
+    pub entities: usize,
+
+    /// Number of entity slots retired after exhausting their generation
+    /// counter
+    pub retired_slots: usize,
+
+    /// Bytes allocated by component columns
+    pub allocated_bytes: usize,
+
+    /// Memory budget for component columns, if set
+    pub budget: Option<usize>,
+
+    /// Per-archetype statistics, in archetype order
+    pub archetypes: Vec<ArchetypeStats>,
+
+    /// Per-component-type statistics, largest first
+    pub components: Vec<ComponentStats>,
+
+    /// Per-column statistics, in archetype order
+    pub columns: Vec<ColumnStats>,
+}
+
+/// Statistics for one archetype, part of [`WorldStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchetypeStats {
+    /// The archetype
+    pub id: ArchetypeId,
+
+    /// Number of rows in the archetype
+    pub entities: usize,
+
+    /// Bytes allocated by the archetype's component columns
+    pub bytes: usize,
+}
+
+/// Statistics for one component type, part of [`WorldStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentStats {
+    /// The component type
+    pub type_id: ComponentTypeId,
+
+    /// Name of the component type
+    pub type_name: &'static str,
+
+    /// Bytes allocated by columns of this type, across all archetypes
+    pub bytes: usize,
+}
+
+/// Statistics for one component column, part of [`WorldStats`].
+///
+/// A column whose `capacity` is far above its `len` holds memory left over
+/// from a peak load, which [`World::compact`] or
+/// [`Archetype::shrink_to_fit`] give back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnStats {
+    /// The archetype owning the column
+    pub archetype: ArchetypeId,
+
+    /// The component type stored in the column
+    pub type_id: ComponentTypeId,
+
+    /// Name of the component type
+    pub type_name: &'static str,
+
+    /// Number of components in the column
+    pub len: usize,
+
+    /// Number of components the column has room for
+    pub capacity: usize,
+
+    /// Bytes allocated by the column
+    pub bytes: usize,
+}
+
+impl ColumnStats {
+    /// Returns the bytes allocated for rows the column doesn't use.
+    pub fn unused_bytes(&self) -> usize {
+        match self.capacity {
+            0 => 0,
+            capacity => self.bytes / capacity * (capacity - self.len),
+        }
+    }
+}
+
+/// Summary of one entity, yielded by [`World::iter_entities_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntitySummary {
+    /// The entity
+    pub entity: EntityId,
+
+    /// The entity's stable ID
+    pub stable_id: StableId,
+
+    /// The archetype holding the entity's components
+    pub archetype: ArchetypeId,
+
+    /// Number of components on the entity
+    pub component_count: usize,
+}
+
+/// A read-only view of one entity's components.
+///
+/// Passed to the closure of [`World::retain`] and returned by
+/// [`World::entity_view`].
+#[derive(Clone, Copy)]
+pub struct EntityView<'w> {
+    entity: EntityId,
+    row: Option<(&'w Archetype, usize)>,
+}
+
+impl<'w> EntityView<'w> {
+    /// Returns the entity being viewed.
+    pub fn id(&self) -> EntityId {
+        self.entity
+    }
+
+    /// Returns `true` if the entity has component `T`.
+    pub fn has<T: Component>(&self) -> bool {
+        self.row
+            .is_some_and(|(archetype, _)| archetype.has_component::<T>())
+    }
+
+    /// Gets a reference to component `T`, if the entity has it.
+    pub fn get<T: Component>(&self) -> Option<&'w T> {
+        let (archetype, row) = self.row?;
+        let storage = archetype.get_storage(ComponentTypeId::of::<T>())?;
+        // SAFETY: `row` is this entity's row, holding an initialized `T`
+        unsafe { Some(&*(storage.get(row) as *const T)) }
+    }
+
+    /// Calls `f` with the metadata of each of the entity's components and a
+    /// pointer to its value, valid for `'w`.
+    pub(crate) fn for_each_component(&self, mut f: impl FnMut(&'w ComponentInfo, *const u8)) {
+        let Some((archetype, row)) = self.row else {
+            return;
+        };
+        for info in archetype.component_info() {
+            if let Some(storage) = archetype.get_storage(info.type_id()) {
+                // SAFETY: `row` is this entity's row in the archetype
+                f(info, unsafe { storage.get(row) });
+            }
+        }
     }
 }
 
-impl Default for World {
-    fn default() -> Self {
-        Self::new()
+impl std::fmt::Debug for EntityView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut components = f.debug_list();
+        self.for_each_component(|info, ptr| {
+            // SAFETY: `ptr` points to an initialized value of this type
+            components.entry(&unsafe { info.debug(ptr) });
+        });
+        components.finish()
     }
 }
 
@@ -1263,6 +4442,12 @@ impl<'w> EntityBuilder<'w> {
     ///     .id();
     /// ```
     pub fn with<T: Component>(mut self, component: T) -> Self {
+        // A later member of an exclusive group replaces an earlier one
+        let rivals = ComponentRegistry::global().exclusive_with(ComponentTypeId::of::<T>());
+        if !rivals.is_empty() {
+            self.components
+                .retain(|(type_id, _, _)| !rivals.contains(*type_id));
+        }
         self.components.push((
             ComponentTypeId::of::<T>(),
             ComponentInfo::of::<T>(),
@@ -1271,8 +4456,50 @@ impl<'w> EntityBuilder<'w> {
         self
     }
 
+    /// Adds the registered default value of a component to the entity being built.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default constructor is registered for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    /// use pecs::component::registry::ComponentRegistry;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Health(u32);
+    /// impl Component for Health {}
+    ///
+    /// ComponentRegistry::global().register_default(|| Health(100));
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with_default::<Health>().id();
+    /// assert_eq!(world.get::<Health>(entity), Some(&Health(100)));
+    /// ```
+    pub fn with_default<T: Component>(self) -> Self {
+        let component = ComponentRegistry::global()
+            .default_value::<T>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "no default registered for component {}",
+                    std::any::type_name::<T>()
+                )
+            });
+        self.with(component)
+    }
+
     /// Finishes building the entity and returns its ID.
     ///
+    /// [Required components](ComponentRegistry::register_requires) that were
+    /// not added are filled in with their registered defaults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required component was not added and has no registered
+    /// default.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1305,6 +4532,16 @@ impl<'w> EntityBuilder<'w> {
             return self.entity_id;
         }
 
+        let added: Vec<_> = self
+            .components
+            .iter()
+            .map(|(_, info, _)| (info.type_id().type_id(), info.type_name()))
+            .collect();
+        let required = self
+            .world
+            .missing_requirements(self.entity_id, &added)
+            .unwrap_or_else(|error| panic!("{}", error));
+
         // Create component set and collect component info
         let mut component_types = ComponentSet::new();
         let mut component_info = Vec::new();
@@ -1337,6 +4574,7 @@ impl<'w> EntityBuilder<'w> {
                     // Don't drop the box - ownership transferred to archetype
                     // The component_ptr points to heap memory that will be managed by the archetype
                 }
+                self.world.revisions.bump(self.entity_id, type_id);
             }
 
             // Set entity location
@@ -1346,6 +4584,10 @@ impl<'w> EntityBuilder<'w> {
             );
         }
 
+        self.world
+            .insert_bundle_requirements(self.entity_id, required);
+
+        self.world.mark_indexes_dirty(self.entity_id);
         self.entity_id
     }
 }
@@ -1354,6 +4596,13 @@ impl<'w> EntityBuilder<'w> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn world_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<World>();
+        assert_send_sync::<CommandBuffer>();
+    }
+
     #[test]
     fn create_world() {
         let world = World::new();
@@ -1406,6 +4655,211 @@ mod tests {
         assert!(!world.despawn(entity));
     }
 
+    #[test]
+    fn despawn_many_keeps_survivor_locations() {
+        #[derive(Debug, PartialEq)]
+        struct Tag(u32);
+        impl Component for Tag {}
+
+        let mut world = World::new();
+        let entities: Vec<_> = (0..8).map(|i| world.spawn().with(Tag(i)).id()).collect();
+        let doomed = [entities[0], entities[2], entities[7], entities[2]];
+
+        assert_eq!(world.despawn_many(doomed), 3);
+        assert_eq!(world.len(), 5);
+        for (i, &entity) in entities.iter().enumerate() {
+            if doomed.contains(&entity) {
+                assert!(!world.is_alive(entity));
+            } else {
+                let view = world.entity_view(entity).unwrap();
+                assert_eq!(view.get::<Tag>(), Some(&Tag(i as u32)));
+            }
+        }
+    }
+
+    #[test]
+    fn rewinding_keeps_old_handles_dead() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Name(String);
+        impl Component for Name {}
+        ComponentRegistry::global().register_json::<Name>("rewinding_keeps_old_handles_dead::Name");
+
+        let mut world = World::new();
+        let x = world.spawn().with(Name("x".into())).id();
+        world.despawn(x);
+        let y = world.spawn().with(Name("y".into())).id();
+        let y_stable = world.get_stable_id(y).unwrap();
+
+        world.enable_history(4);
+        world.record_tick();
+        let since = world.persistence().change_tracker_mut().advance();
+        assert_eq!(world.rewind(1).unwrap(), 1);
+
+        assert!(!world.is_alive(x));
+        assert_eq!(world.get::<Name>(x), None);
+        assert!(!world.is_alive(y));
+        let restored = world.get_entity_by_stable_id(y_stable).unwrap();
+        assert_ne!(restored, x);
+        assert_eq!(world.get::<Name>(restored), Some(&Name("y".into())));
+
+        let changes = world.persistence().change_tracker().changes_since(since);
+        assert_eq!(changes.deleted, vec![y]);
+        assert_eq!(changes.created, vec![restored]);
+    }
+
+    #[test]
+    fn clone_entity_copies_cloneable_components() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Name(String);
+        impl Component for Name {}
+
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+        impl Component for Handle {}
+
+        ComponentRegistry::global().register_clone::<Name>();
+
+        let mut world = World::new();
+        let original = world.spawn().with(Name("orc".to_string())).id();
+        let copy = world.clone_entity(original).unwrap();
+        assert_eq!(world.get::<Name>(copy), Some(&Name("orc".to_string())));
+
+        // Non-cloneable components are left out
+        world.insert(original, Handle(7));
+        let partial = world.clone_entity(original).unwrap();
+        assert_eq!(world.get::<Name>(partial), Some(&Name("orc".to_string())));
+        assert!(!world.has::<Handle>(partial));
+        assert_eq!(world.get::<Handle>(original), Some(&Handle(7)));
+
+        world.despawn(original);
+        assert_eq!(world.clone_entity(original), None);
+        assert_eq!(world.get::<Name>(copy), Some(&Name("orc".to_string())));
+    }
+
+    #[test]
+    fn retain_culls_across_archetypes() {
+        #[derive(Debug)]
+        struct Health(i32);
+        impl Component for Health {}
+
+        #[derive(Debug)]
+        struct Shield;
+        impl Component for Shield {}
+
+        let mut world = World::new();
+        let bare = world.spawn_empty();
+        let weak = world.spawn().with(Health(0)).id();
+        let strong = world.spawn().with(Health(9)).id();
+        let shielded = world.spawn().with(Health(0)).with(Shield).id();
+
+        let mut visited = Vec::new();
+        let culled = world.retain(|entity, view| {
+            visited.push(entity);
+            assert_eq!(view.id(), entity);
+            view.has::<Shield>() || view.get::<Health>().is_some_and(|h| h.0 > 0)
+        });
+
+        assert_eq!(culled, 2);
+        assert_eq!(visited.len(), 4);
+        for entity in [bare, weak, strong, shielded] {
+            assert!(visited.contains(&entity));
+        }
+        assert!(!world.is_alive(bare));
+        assert!(!world.is_alive(weak));
+        assert!(world.is_alive(strong));
+        assert!(world.is_alive(shielded));
+        assert_eq!(world.retain(|_, _| true), 0);
+    }
+
+    #[test]
+    fn hydrate_replaces_proxy_with_stored_components() {
+        use crate::persistence::{EntityPersistencePlugin, KeyValueEntityPlugin, Unloaded};
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Gold(u32);
+        impl Component for Gold {}
+        ComponentRegistry::global().register_json::<Gold>("world::hydrate::Gold");
+
+        let plugin = KeyValueEntityPlugin::new();
+        let mut source = World::new();
+        let stored = source.spawn().with(Gold(7)).id();
+        let stored_id = source.get_stable_id(stored).unwrap();
+        plugin.save_entity(&source, stored).unwrap();
+
+        let mut world = World::new();
+        let missing = world.spawn_unloaded(StableId::new()).unwrap();
+        let proxy = world.spawn_unloaded(stored_id).unwrap();
+        assert!(world.hydrate(stored_id).is_err());
+
+        world
+            .persistence()
+            .register_entity_plugin("kv", Box::new(plugin));
+        assert_eq!(world.hydrate(stored_id).unwrap(), proxy);
+        assert_eq!(world.get::<Gold>(proxy), Some(&Gold(7)));
+        assert!(!world.has::<Unloaded>(proxy));
+
+        // Already loaded entities are left alone
+        assert_eq!(world.hydrate(stored_id).unwrap(), proxy);
+
+        // A failed load keeps the proxy
+        let missing_id = world.get_stable_id(missing).unwrap();
+        assert!(world.hydrate(missing_id).is_err());
+        assert!(world.has::<Unloaded>(missing));
+    }
+
+    #[test]
+    fn unload_and_hydrate_round_trip() {
+        use crate::persistence::{EntityPersistencePlugin, KeyValueEntityPlugin, Unloaded};
+        use crate::query::filter::With;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Stock(u32);
+        impl Component for Stock {}
+        struct Far;
+        impl Component for Far {}
+        ComponentRegistry::global().register_json::<Stock>("world::unload::Stock");
+
+        let plugin = KeyValueEntityPlugin::new();
+        let mut world = World::new();
+        world
+            .persistence()
+            .register_entity_plugin("kv", Box::new(plugin.clone()));
+
+        let near = world.spawn().with(Stock(1)).id();
+        let far = world.spawn().with(Stock(2)).with(Far).id();
+        let far_id = world.get_stable_id(far).unwrap();
+        let gone = world.spawn().with(Stock(3)).id();
+        let gone_id = world.get_stable_id(gone).unwrap();
+
+        assert_eq!(world.unload_entity(gone, "kv", false).unwrap(), None);
+        assert!(!world.is_alive(gone));
+        assert_eq!(world.get_entity_by_stable_id(gone_id), None);
+
+        assert_eq!(
+            world.unload_where::<With<Far>>("kv", true).unwrap(),
+            vec![far_id]
+        );
+        assert!(!world.is_alive(far));
+        assert!(world.is_alive(near));
+        assert_eq!(plugin.len(), 2);
+
+        // Unloading the proxy again keeps it without overwriting storage
+        let proxy = world.get_entity_by_stable_id(far_id).unwrap();
+        assert!(world.has::<Unloaded>(proxy));
+        assert_eq!(world.unload_entity(proxy, "kv", true).unwrap(), Some(proxy));
+        assert!(
+            plugin
+                .find_entities_with("world::unload::Stock")
+                .unwrap()
+                .contains(&far_id)
+        );
+
+        world.hydrate_with(far_id, "kv").unwrap();
+        assert_eq!(world.get::<Stock>(proxy), Some(&Stock(2)));
+        let gone = world.hydrate_with(gone_id, "kv").unwrap();
+        assert_eq!(world.get::<Stock>(gone), Some(&Stock(3)));
+    }
+
     #[test]
     fn stable_id_lookup() {
         let mut world = World::new();
@@ -1426,6 +4880,101 @@ mod tests {
         assert!(world.is_empty());
     }
 
+    #[test]
+    fn reserve_entity_flushed_by_commands() {
+        let mut world = World::new();
+        let existing = world.spawn_empty();
+
+        let reserved = world.reserve_entity();
+        assert_ne!(reserved, existing);
+        assert!(!world.is_alive(reserved));
+
+        world
+            .commands()
+            .insert(reserved, Position { x: 1.0, y: 2.0 });
+        world.apply_commands();
+
+        assert!(world.is_alive(reserved));
+        assert_eq!(world.len(), 2);
+        assert_eq!(world.get::<Position>(reserved).unwrap().y, 2.0);
+    }
+
+    #[test]
+    fn compact_world_after_despawn() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..100)
+            .map(|i| world.spawn().with(TestComponent { value: i }).id())
+            .collect();
+        for &entity in &entities[..90] {
+            world.despawn(entity);
+        }
+
+        let stats = world.compact();
+        assert!(stats.bytes_reclaimed > 0);
+
+        for (i, &entity) in entities[90..].iter().enumerate() {
+            assert_eq!(
+                world.get::<TestComponent>(entity).unwrap().value,
+                90 + i as i32
+            );
+        }
+        assert_eq!(world.query::<&TestComponent>().count(), 10);
+    }
+
+    #[test]
+    fn insert_batch_across_archetypes() {
+        let mut world = World::new();
+        let plain: Vec<_> = (0..10).map(|_| world.spawn_empty()).collect();
+        let moving: Vec<_> = (0..10)
+            .map(|_| world.spawn().with(Velocity { x: 1.0, y: 0.0 }).id())
+            .collect();
+        let dead = world.spawn_empty();
+        world.despawn(dead);
+
+        let batch = plain
+            .iter()
+            .chain(&moving)
+            .chain(std::iter::once(&dead))
+            .enumerate()
+            .map(|(i, &e)| (e, TestComponent { value: i as i32 }));
+        assert_eq!(world.insert_batch(batch), 20);
+
+        assert_eq!(world.get::<TestComponent>(plain[3]).unwrap().value, 3);
+        assert_eq!(world.get::<TestComponent>(moving[3]).unwrap().value, 13);
+        assert!(world.has::<Velocity>(moving[3]));
+        assert_eq!(world.query::<&TestComponent>().count(), 20);
+    }
+
+    #[test]
+    fn insert_batch_replaces_and_handles_duplicates() {
+        let mut world = World::new();
+        let entity = world.spawn().with(TestComponent { value: 1 }).id();
+        let other = world.spawn_empty();
+
+        let batch = vec![
+            (entity, TestComponent { value: 2 }),
+            (other, TestComponent { value: 3 }),
+            (other, TestComponent { value: 4 }),
+        ];
+        assert_eq!(world.insert_batch(batch), 3);
+
+        assert_eq!(world.get::<TestComponent>(entity).unwrap().value, 2);
+        assert_eq!(world.get::<TestComponent>(other).unwrap().value, 4);
+    }
+
+    #[test]
+    fn clear_world_resets_locations() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
+
+        world.clear();
+        assert!(world.get::<Position>(entity).is_none());
+
+        let entity = world.spawn().with(Position { x: 3.0, y: 4.0 }).id();
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 3.0);
+        assert_eq!(world.query::<&Position>().count(), 1);
+    }
+
     #[test]
     fn command_buffer_integration() {
         let mut world = World::new();
@@ -1644,6 +5193,248 @@ mod tests {
         assert!(!world.has::<TestComponent>(entity));
     }
 
+    #[test]
+    fn insert_raw_adds_and_replaces() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+        let info = ComponentInfo::of::<TestComponent>();
+
+        for value in [1, 2] {
+            let component = std::mem::ManuallyDrop::new(TestComponent { value });
+            let ptr = &*component as *const TestComponent as *const u8;
+            assert!(unsafe { world.insert_raw(entity, &info, ptr) });
+            assert_eq!(world.get::<TestComponent>(entity).unwrap().value, value);
+        }
+        assert!(world.has::<Position>(entity));
+    }
+
+    #[test]
+    fn insert_default_components() {
+        #[derive(Debug, PartialEq)]
+        struct Armor(u32);
+        impl Component for Armor {}
+
+        #[derive(Debug)]
+        struct Unregistered;
+        impl Component for Unregistered {}
+
+        ComponentRegistry::global().register_default(|| Armor(10));
+
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        assert!(world.insert_default::<Armor>(entity));
+        assert_eq!(world.get::<Armor>(entity), Some(&Armor(10)));
+        assert!(!world.insert_default::<Unregistered>(entity));
+
+        let other = world.spawn_empty();
+        assert!(world.insert_default_by_id(other, ComponentTypeId::of::<Armor>()));
+        assert_eq!(world.get::<Armor>(other), Some(&Armor(10)));
+        assert!(!world.insert_default_by_id(other, ComponentTypeId::of::<Unregistered>()));
+
+        let built = world.spawn().with_default::<Armor>().id();
+        assert_eq!(world.get::<Armor>(built), Some(&Armor(10)));
+
+        world.despawn(entity);
+        assert!(!world.insert_default::<Armor>(entity));
+    }
+
+    #[test]
+    fn query_sorted_by_key() {
+        let mut world = World::new();
+        for value in [3, 1, 2] {
+            world.spawn().with(TestComponent { value }).id();
+        }
+
+        let values: Vec<i32> = world
+            .query::<&TestComponent>()
+            .sort_by_key(|c| c.value)
+            .map(|c| c.value)
+            .collect();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let values: Vec<i32> = world
+            .query::<&TestComponent>()
+            .sort_unstable_by(|a, b| b.value.cmp(&a.value))
+            .map(|c| c.value)
+            .collect();
+        assert_eq!(values, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn deterministic_iteration_follows_entity_order() {
+        let mut world = World::new();
+        let a = world.spawn().with(TestComponent { value: 0 }).id();
+        world.spawn().with(TestComponent { value: 1 }).id();
+        world.spawn().with(TestComponent { value: 2 }).id();
+
+        // Move the first entity into a newer archetype
+        world.insert(a, Position { x: 0.0, y: 0.0 });
+
+        let unordered: Vec<i32> = world.query::<&TestComponent>().map(|c| c.value).collect();
+        assert_eq!(unordered, vec![2, 1, 0]);
+
+        world.set_deterministic_iteration(true);
+        let ordered: Vec<i32> = world.query::<&TestComponent>().map(|c| c.value).collect();
+        assert_eq!(ordered, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn iter_archetype_columns_exposes_rows() {
+        let mut world = World::new();
+        let a = world.spawn().with(TestComponent { value: 1 }).id();
+        let b = world
+            .spawn()
+            .with(TestComponent { value: 2 })
+            .with(Position { x: 0.0, y: 0.0 })
+            .id();
+        world.spawn().with(Position { x: 1.0, y: 1.0 }).id();
+
+        let types = [ComponentTypeId::of::<TestComponent>()];
+        let mut seen = Vec::new();
+        for slices in world.iter_archetype_columns(&types) {
+            let column = slices.columns()[0];
+            assert_eq!(column.size, std::mem::size_of::<TestComponent>());
+            let values = column.ptr as *mut TestComponent;
+            for (row, &entity) in slices.entities().iter().enumerate() {
+                unsafe { (*values.add(row)).value *= 10 };
+                seen.push(entity);
+            }
+        }
+
+        assert_eq!(seen.len(), 2);
+        assert_eq!(world.get::<TestComponent>(a).unwrap().value, 10);
+        assert_eq!(world.get::<TestComponent>(b).unwrap().value, 20);
+    }
+
+    #[test]
+    fn query_archetype_visits_only_that_archetype() {
+        let mut world = World::new();
+        let a = world.spawn().with(TestComponent { value: 1 }).id();
+        let b = world
+            .spawn()
+            .with(TestComponent { value: 2 })
+            .with(Position { x: 0.0, y: 0.0 })
+            .id();
+        let c = world.spawn().with(TestComponent { value: 3 }).id();
+
+        let id = world.archetype_of(a).unwrap();
+        assert_ne!(Some(id), world.archetype_of(b));
+        assert_eq!(world.iter_archetype(id).collect::<Vec<_>>(), vec![a, c]);
+
+        for component in world.query_archetype::<&mut TestComponent>(id) {
+            component.value *= 10;
+        }
+        assert_eq!(world.get::<TestComponent>(a).unwrap().value, 10);
+        assert_eq!(world.get::<TestComponent>(b).unwrap().value, 2);
+        assert_eq!(world.get::<TestComponent>(c).unwrap().value, 30);
+
+        let archetype = world.archetype(id).unwrap();
+        assert_eq!(archetype.iter::<&TestComponent>().count(), 2);
+        assert_eq!(archetype.iter::<&Position>().count(), 0);
+        assert_eq!(world.query_archetype::<&Position>(id).count(), 0);
+
+        assert!(world.despawn(c));
+        assert_eq!(world.iter_archetype(id).collect::<Vec<_>>(), vec![a]);
+    }
+
+    #[test]
+    #[should_panic(expected = "borrowed mutably once per query")]
+    fn query_aliasing_mutable_and_shared_panics() {
+        let mut world = World::new();
+        world.spawn().with(TestComponent { value: 1 });
+        world
+            .query::<(&mut TestComponent, &TestComponent)>()
+            .count();
+    }
+
+    #[test]
+    #[should_panic(expected = "borrowed mutably once per query")]
+    fn query_aliasing_two_mutable_panics_without_matches() {
+        let mut world = World::new();
+        world
+            .query_filtered::<(&mut TestComponent, &mut TestComponent), ()>()
+            .count();
+    }
+
+    #[test]
+    fn detailed_iteration_reports_archetype_and_component_count() {
+        let mut world = World::new();
+        let a = world.spawn_bundle((TestComponent { value: 1 },));
+        let b = world.spawn_bundle((TestComponent { value: 2 }, Position { x: 0.0, y: 0.0 }));
+        world.remove::<Position>(b);
+
+        let summaries: Vec<_> = world.iter_entities_detailed().collect();
+        assert_eq!(summaries.len(), 2);
+        for (summary, (entity, stable_id)) in summaries.iter().zip(world.iter_entities()) {
+            assert_eq!((summary.entity, summary.stable_id), (entity, stable_id));
+            assert_eq!(summary.component_count, 1);
+        }
+        assert_eq!(summaries[0].entity, a);
+        assert_eq!(summaries[0].archetype, summaries[1].archetype);
+    }
+
+    #[test]
+    fn stats_track_memory_and_budget() {
+        let mut world = World::new();
+        world.spawn_bundle((TestComponent { value: 1 },));
+        world.spawn_bundle((TestComponent { value: 2 }, Position { x: 0.0, y: 0.0 }));
+
+        let stats = world.stats();
+        assert_eq!(stats.entities, 2);
+        assert_eq!(stats.budget, None);
+        assert_eq!(stats.components.len(), 2);
+        assert_eq!(
+            stats.allocated_bytes,
+            stats.archetypes.iter().map(|a| a.bytes).sum::<usize>()
+        );
+        assert_eq!(
+            stats.allocated_bytes,
+            stats.components.iter().map(|c| c.bytes).sum::<usize>()
+        );
+
+        assert_eq!(stats.columns.len(), 3);
+        assert_eq!(
+            stats.allocated_bytes,
+            stats.columns.iter().map(|c| c.bytes).sum::<usize>()
+        );
+        assert!(stats.columns.iter().all(|c| c.len == 1 && c.capacity >= 1));
+
+        world.memory().set_budget(Some(stats.allocated_bytes));
+        assert!(world.try_reserve::<(Position,)>(1_000).is_err());
+
+        // A new archetype's columns are allocated against the budget too
+        assert!(
+            world
+                .try_spawn_bundle((Position { x: 1.0, y: 1.0 },))
+                .is_err()
+        );
+        assert_eq!(world.len(), 2);
+
+        world.memory().set_budget(None);
+        assert!(
+            world
+                .try_spawn_bundle((Position { x: 1.0, y: 1.0 },))
+                .is_ok()
+        );
+        assert_eq!(world.stats().entities, 3);
+    }
+
+    #[test]
+    fn column_stats_show_memory_reclaimed_by_compact() {
+        let mut world = World::new();
+        let entities = world.spawn_batch((0..1_000).map(|value| TestComponent { value }));
+        world.despawn_many(entities.into_iter().skip(10));
+
+        let column = world.stats().columns[0];
+        assert_eq!((column.len, column.capacity), (10, 1_000));
+        assert!(column.unused_bytes() > 0);
+
+        world.compact();
+        let column = world.stats().columns[0];
+        assert_eq!((column.len, column.capacity), (10, 10));
+        assert_eq!(column.unused_bytes(), 0);
+    }
+
     #[test]
     fn spawn_with_stable_id() {
         let mut world = World::new();
@@ -1708,4 +5499,242 @@ mod tests {
         let result = world.spawn_empty_with_stable_id(stable_id);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn save_writes_back_metadata() {
+        let mut world = World::new();
+        world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+        world.spawn_empty();
+
+        let mut buffer = Vec::new();
+        world.save_binary(&mut buffer).unwrap();
+        assert_eq!(world.metadata().save_count, 1);
+        assert_eq!(world.metadata().entity_count, 2);
+        assert!(world.metadata().timestamp > 0);
+        assert!(
+            world
+                .metadata()
+                .component_types
+                .iter()
+                .any(|info| info.type_name.ends_with("Position"))
+        );
+
+        world.save_json(&mut Vec::new()).unwrap();
+        assert_eq!(world.metadata().save_count, 2);
+
+        let loaded = World::load_binary(&mut buffer.as_slice()).unwrap();
+        assert_eq!(loaded.metadata().save_count, 1);
+        assert_eq!(loaded.metadata().entity_count, 2);
+        assert_eq!(loaded.metadata().timestamp, world.metadata().timestamp);
+    }
+
+    #[test]
+    fn load_with_manager_applies_migrations() {
+        use crate::persistence::Migration;
+
+        struct TagMigration;
+
+        impl Migration for TagMigration {
+            fn source_version(&self) -> u32 {
+                1
+            }
+
+            fn target_version(&self) -> u32 {
+                2
+            }
+
+            fn migrate(&self, world: &mut World) -> crate::persistence::Result<()> {
+                world
+                    .metadata_mut()
+                    .custom
+                    .insert("migrated".to_string(), "yes".to_string());
+                Ok(())
+            }
+        }
+
+        let mut manager = PersistenceManager::with_default_plugins();
+        manager.register_migration(Box::new(TagMigration));
+
+        let mut world = World::new();
+        world.spawn_empty();
+        let path = std::env::temp_dir().join("pecs_world_load_with_manager.json");
+        world.save_with_manager(&manager, &path).unwrap();
+
+        let loaded = World::load_with_manager(&manager, &path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.metadata().version, 2);
+        assert_eq!(loaded.metadata().custom.get("migrated").unwrap(), "yes");
+        assert_eq!(loaded.metadata().save_count, 1);
+
+        // The built-in loader knows nothing of the migration
+        let plain = World::load(&path).unwrap();
+        assert_eq!(plain.metadata().version, 1);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn resources() {
+        struct Score(u32);
+
+        let mut world = World::new();
+        assert!(!world.contains_resource::<Score>());
+        assert!(world.resource::<Score>().is_none());
+
+        world.insert_resource(Score(1));
+        world.resource_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(world.resource::<Score>().unwrap().0, 2);
+        assert_eq!(world.resource_count(), 1);
+
+        world.clear();
+        assert_eq!(world.resource_or_insert_with(|| Score(0)).0, 2);
+        assert_eq!(world.remove_resource::<Score>().unwrap().0, 2);
+        assert_eq!(world.resource_or_insert_with(|| Score(7)).0, 7);
+    }
+
+    #[test]
+    fn exclusive_group_replaces_rivals() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Idle(Arc<AtomicUsize>);
+        impl Component for Idle {}
+        impl Drop for Idle {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        struct Walking(f32);
+        impl Component for Walking {}
+        struct Health(u32);
+        impl Component for Health {}
+
+        ComponentRegistry::global().register_exclusive::<(Idle, Walking)>();
+        let drops = Arc::new(AtomicUsize::new(0));
+
+        let mut world = World::new();
+        let entity = world
+            .spawn()
+            .with(Health(10))
+            .with(Idle(drops.clone()))
+            .id();
+        world.insert(entity, Walking(2.0));
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        assert!(!world.has::<Idle>(entity));
+        assert_eq!(world.get::<Walking>(entity).unwrap().0, 2.0);
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 10);
+
+        world.insert_batch([(entity, Idle(drops.clone()))]);
+        assert!(!world.has::<Walking>(entity));
+        assert!(world.has::<Idle>(entity));
+
+        // The later member wins in builders and bundles
+        let built = world
+            .spawn()
+            .with(Walking(1.0))
+            .with(Idle(drops.clone()))
+            .id();
+        assert!(world.has::<Idle>(built) && !world.has::<Walking>(built));
+        let bundled = world.spawn_bundle((Idle(drops.clone()), Walking(3.0)));
+        assert!(world.has::<Walking>(bundled) && !world.has::<Idle>(bundled));
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn over_budget_move_leaves_entity_in_place() {
+        use crate::component::memory::MemoryError;
+
+        #[allow(dead_code)]
+        struct Held(Arc<()>);
+        impl Component for Held {}
+
+        let mut world = World::new();
+        let entity = world.spawn().with(Position { x: 1.0, y: 2.0 }).id();
+
+        // Fill the target archetype up to its column capacity
+        let full = world.spawn_bundle((Position { x: 0.0, y: 0.0 }, Velocity { x: 0.0, y: 0.0 }));
+        let target = world.archetype_of(full).unwrap();
+        let capacity = world
+            .archetype(target)
+            .unwrap()
+            .get_storage(ComponentTypeId::of::<Velocity>())
+            .unwrap()
+            .capacity();
+        while world.archetype(target).unwrap().len() < capacity {
+            world.spawn_bundle((Position { x: 0.0, y: 0.0 }, Velocity { x: 0.0, y: 0.0 }));
+        }
+
+        world.memory().set_budget(Some(world.memory().allocated()));
+        assert!(matches!(
+            world.try_insert(entity, Velocity { x: 1.0, y: 1.0 }),
+            Err(InsertError::OverBudget(MemoryError::BudgetExceeded { .. }))
+        ));
+        assert!(!world.insert(entity, Velocity { x: 1.0, y: 1.0 }));
+        assert!(matches!(
+            world.try_insert_batch([(entity, Velocity { x: 1.0, y: 1.0 })]),
+            Err(InsertError::OverBudget(_))
+        ));
+        assert_eq!(
+            world.insert_batch([(entity, Velocity { x: 1.0, y: 1.0 })]),
+            0
+        );
+
+        // A component that cannot be stored is dropped, not leaked
+        let held = Arc::new(());
+        assert!(matches!(
+            world.try_insert(entity, Held(held.clone())),
+            Err(InsertError::OverBudget(_))
+        ));
+        assert_eq!(Arc::strong_count(&held), 1);
+        world.memory().set_budget(None);
+
+        assert!(!world.has::<Velocity>(entity));
+        assert_eq!(world.archetype(target).unwrap().len(), capacity);
+        let view = world.entity_view(entity).unwrap();
+        assert_eq!(view.get::<Position>().map(|p| (p.x, p.y)), Some((1.0, 2.0)));
+
+        assert!(world.insert(entity, Velocity { x: 1.0, y: 1.0 }));
+        assert!(world.has::<Velocity>(entity));
+    }
+
+    #[test]
+    fn panicking_drop_during_replace_keeps_world_consistent() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        struct Fragile(bool);
+        impl Component for Fragile {}
+        impl Drop for Fragile {
+            fn drop(&mut self) {
+                if self.0 {
+                    panic!("fragile component dropped");
+                }
+            }
+        }
+        struct Sturdy(u32);
+        impl Component for Sturdy {}
+
+        ComponentRegistry::global().register_exclusive::<(Fragile, Sturdy)>();
+
+        let mut world = World::new();
+        let doomed = world
+            .spawn()
+            .with(Position { x: 1.0, y: 1.0 })
+            .with(Fragile(true))
+            .id();
+        let neighbour = world
+            .spawn()
+            .with(Position { x: 2.0, y: 2.0 })
+            .with(Fragile(false))
+            .id();
+
+        let result = catch_unwind(AssertUnwindSafe(|| world.insert(doomed, Sturdy(7))));
+        assert!(result.is_err());
+
+        // The replacement finished before the rival was dropped
+        assert!(!world.has::<Fragile>(doomed));
+        assert_eq!(world.get::<Sturdy>(doomed).map(|s| s.0), Some(7));
+        assert_eq!(world.get::<Position>(doomed).map(|p| p.x), Some(1.0));
+        let view = world.entity_view(neighbour).unwrap();
+        assert_eq!(view.get::<Position>().map(|p| p.x), Some(2.0));
+        assert!(view.get::<Fragile>().is_some());
+    }
 }
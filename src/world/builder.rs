@@ -0,0 +1,168 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Configuration for new worlds.
+//!
+//! [`WorldBuilder`] gathers the settings that [`World`]'s constructors and
+//! setters otherwise spread out, so a world can be created fully
+//! configured in one expression.
+
+use super::World;
+use crate::command::CommandBuffer;
+use crate::component::archetype::ArchetypeManager;
+use crate::component::memory::Alloc;
+use crate::entity::{EntityManager, StableIdGenerator};
+use std::sync::Arc;
+
+/// Builder for a [`World`], created by [`World::builder`].
+///
+/// # Examples
+///
+/// ```
+/// use pecs::World;
+///
+/// let world = World::builder()
+///     .capacity(256)
+///     .deterministic_iteration(true)
+///     .change_tracking(false)
+///     .build();
+/// assert!(world.is_deterministic_iteration());
+/// assert!(!world.is_change_tracking());
+/// ```
+#[must_use]
+pub struct WorldBuilder {
+    capacity: usize,
+    allocator: Option<Arc<dyn Alloc>>,
+    change_tracking: bool,
+    revision_tracking: bool,
+    deterministic_iteration: bool,
+    fixed_timestamp: Option<u64>,
+    id_generator: Option<Box<dyn StableIdGenerator>>,
+}
+
+impl WorldBuilder {
+    /// Creates a builder with the same settings as [`World::new`].
+    pub fn new() -> Self {
+        Self {
+            capacity: 0,
+            allocator: None,
+            change_tracking: true,
+            revision_tracking: true,
+            deterministic_iteration: false,
+            fixed_timestamp: None,
+            id_generator: None,
+        }
+    }
+
+    /// Pre-allocates entity slots, as [`World::with_capacity`] does.
+    pub fn capacity(mut self, entity_capacity: usize) -> Self {
+        self.capacity = entity_capacity;
+        self
+    }
+
+    /// Allocates component columns from `allocator`, as
+    /// [`World::with_allocator`] does.
+    pub fn allocator(mut self, allocator: Arc<dyn Alloc>) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Sets whether entity changes are tracked; see
+    /// [`World::set_change_tracking`].
+    pub fn change_tracking(mut self, enabled: bool) -> Self {
+        self.change_tracking = enabled;
+        self
+    }
+
+    /// Sets whether component revisions are counted; see
+    /// [`World::set_revision_tracking`].
+    pub fn revision_tracking(mut self, enabled: bool) -> Self {
+        self.revision_tracking = enabled;
+        self
+    }
+
+    /// Sets whether queries iterate in entity ID order; see
+    /// [`World::set_deterministic_iteration`].
+    pub fn deterministic_iteration(mut self, enabled: bool) -> Self {
+        self.deterministic_iteration = enabled;
+        self
+    }
+
+    /// Pins the timestamp that saves record; see
+    /// [`World::set_fixed_timestamp`].
+    pub fn fixed_timestamp(mut self, timestamp: u64) -> Self {
+        self.fixed_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the source of stable IDs for spawned entities; see
+    /// [`World::set_id_generator`].
+    pub fn id_generator(mut self, generator: impl StableIdGenerator + 'static) -> Self {
+        self.id_generator = Some(Box::new(generator));
+        self
+    }
+
+    /// Creates the world.
+    pub fn build(self) -> World {
+        let archetypes = match self.allocator {
+            Some(allocator) => ArchetypeManager::with_allocator(allocator),
+            None => ArchetypeManager::new(),
+        };
+        let mut world = World {
+            entities: EntityManager::with_capacity(self.capacity),
+            archetypes,
+            commands: CommandBuffer::with_capacity(self.capacity),
+            deterministic_iteration: self.deterministic_iteration,
+            fixed_timestamp: self.fixed_timestamp,
+            ..World::new()
+        };
+        world.set_change_tracking(self.change_tracking);
+        world.set_revision_tracking(self.revision_tracking);
+        if let Some(generator) = self.id_generator {
+            world.entities.set_id_generator(generator);
+        }
+        world
+    }
+}
+
+impl Default for WorldBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+
+    struct Health(#[allow(dead_code)] u32);
+    impl Component for Health {}
+
+    #[test]
+    fn disabled_tracking_records_nothing() {
+        let mut world = World::builder().change_tracking(false).build();
+        let entity = world.spawn().with(Health(10)).id();
+        world.insert(entity, Health(5));
+        world.get_mut::<Health>(entity).unwrap().0 = 1;
+        world.despawn(entity);
+        assert!(!world.persistence().change_tracker().has_changes());
+
+        world.set_change_tracking(true);
+        let entity = world.spawn_empty();
+        assert_eq!(world.persistence().change_tracker().created(), vec![entity]);
+    }
+}
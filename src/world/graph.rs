@@ -0,0 +1,210 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Archetype graph export.
+//!
+//! Renders the archetypes of a world and the add/remove transitions
+//! between them for Graphviz or Mermaid, to see at a glance which
+//! component combinations exist and how entities move between them.
+
+use super::World;
+use crate::component::archetype::{Archetype, ArchetypeId};
+use crate::query::plan::short_type_name;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// A transition between two archetypes that differ by one component.
+struct Transition {
+    /// Archetype without the component
+    from: ArchetypeId,
+    /// Archetype with the component
+    to: ArchetypeId,
+    /// Short type name of the component
+    component: String,
+}
+
+impl World {
+    /// Renders the archetype graph in Graphviz DOT format.
+    ///
+    /// Each archetype is a node labelled with its components and entity
+    /// count. Each edge points from an archetype to the one an entity moves
+    /// to when the labelled component is added, and is followed backwards
+    /// when it is removed. Only transitions that have happened are known,
+    /// so archetypes created directly from a bundle may have no edges.
+    /// Useful for spotting archetype explosion: many sparsely populated
+    /// nodes fanning out from the same components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Position;
+    /// impl Component for Position {}
+    /// struct Velocity;
+    /// impl Component for Velocity {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position).id();
+    /// world.insert(entity, Velocity);
+    ///
+    /// let dot = world.archetype_graph_dot();
+    /// assert!(dot.starts_with("digraph archetypes {"));
+    /// assert!(dot.contains("label=\"Velocity\""));
+    /// ```
+    pub fn archetype_graph_dot(&self) -> String {
+        let mut out = String::from("digraph archetypes {\n    node [shape=box];\n");
+        for archetype in self.archetypes.iter() {
+            let _ = writeln!(
+                out,
+                "    a{} [label=\"{}\\n{}\"];",
+                archetype.id().index(),
+                node_components(archetype),
+                entity_count(archetype)
+            );
+        }
+        for transition in self.archetype_transitions() {
+            let _ = writeln!(
+                out,
+                "    a{} -> a{} [label=\"{}\"];",
+                transition.from.index(),
+                transition.to.index(),
+                transition.component
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the archetype graph as a Mermaid flowchart.
+    ///
+    /// Nodes and edges are the same as for
+    /// [`archetype_graph_dot`](Self::archetype_graph_dot); Mermaid renders
+    /// inline in Markdown, which suits issue reports and docs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// struct Position;
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// world.spawn().with(Position).id();
+    ///
+    /// let mermaid = world.archetype_graph_mermaid();
+    /// assert!(mermaid.starts_with("flowchart LR"));
+    /// assert!(mermaid.contains("{Position}<br/>1 entity"));
+    /// ```
+    pub fn archetype_graph_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for archetype in self.archetypes.iter() {
+            let _ = writeln!(
+                out,
+                "    a{}[\"{}<br/>{}\"]",
+                archetype.id().index(),
+                node_components(archetype),
+                entity_count(archetype)
+            );
+        }
+        for transition in self.archetype_transitions() {
+            let _ = writeln!(
+                out,
+                "    a{} -->|{}| a{}",
+                transition.from.index(),
+                transition.component,
+                transition.to.index()
+            );
+        }
+        out
+    }
+
+    /// Collects the cached add/remove transitions, one per archetype pair,
+    /// in archetype order.
+    fn archetype_transitions(&self) -> Vec<Transition> {
+        let mut pairs = BTreeSet::new();
+        for archetype in self.archetypes.iter() {
+            let id = archetype.id().index();
+            let edges = archetype.edges();
+            pairs.extend(edges.add_targets().map(|to| (id, to.index())));
+            pairs.extend(edges.remove_targets().map(|from| (from.index(), id)));
+        }
+
+        pairs
+            .into_iter()
+            .filter_map(|(from, to)| {
+                let from = self.archetypes.get_archetype(ArchetypeId::new(from))?;
+                let to = self.archetypes.get_archetype(ArchetypeId::new(to))?;
+                let added = to
+                    .component_info()
+                    .iter()
+                    .find(|info| !from.component_types().contains(info.type_id()))?;
+                Some(Transition {
+                    from: from.id(),
+                    to: to.id(),
+                    component: short_type_name(added.type_name()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the sorted component names of an archetype in braces.
+fn node_components(archetype: &Archetype) -> String {
+    let mut names: Vec<_> = archetype
+        .component_info()
+        .iter()
+        .map(|info| short_type_name(info.type_name()))
+        .collect();
+    names.sort();
+    format!("{{{}}}", names.join(", "))
+}
+
+fn entity_count(archetype: &Archetype) -> String {
+    match archetype.len() {
+        1 => "1 entity".to_string(),
+        count => format!("{} entities", count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::World;
+    use crate::component::Component;
+
+    struct Position;
+    impl Component for Position {}
+    struct Velocity;
+    impl Component for Velocity {}
+
+    #[test]
+    fn graph_has_one_edge_per_transition() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Position).id();
+        world.insert(entity, Velocity);
+        world.remove::<Velocity>(entity);
+        world.insert(entity, Velocity);
+
+        let dot = world.archetype_graph_dot();
+        assert_eq!(dot.matches("->").count(), 1, "{}", dot);
+        assert!(dot.contains("{Position, Velocity}\\n1 entity"), "{}", dot);
+        assert!(dot.contains("{Position}\\n0 entities"), "{}", dot);
+
+        let mermaid = world.archetype_graph_mermaid();
+        assert_eq!(mermaid.matches("-->|Velocity|").count(), 1, "{}", mermaid);
+    }
+}
@@ -0,0 +1,246 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Per-component revision counters.
+//!
+//! Every component instance has a revision that is incremented whenever the
+//! component is added (by spawning or inserting), replaced or borrowed
+//! through [`World::get_mut`]. Caches derived from a component, such as
+//! render extraction or navigation meshes, remember the revision they were
+//! built from and rebuild once [`World::component_revision`] reports a
+//! different one.
+//!
+//! Counting costs a hash lookup per mutation. Worlds that do not need
+//! revisions can turn them off with [`World::set_revision_tracking`] or
+//! [`WorldBuilder::revision_tracking`](super::WorldBuilder::revision_tracking).
+
+use super::World;
+use crate::component::{Component, ComponentTypeId};
+use crate::entity::EntityId;
+use std::collections::HashMap;
+
+/// The revisions of components that have been written. Components without
+/// an entry are at revision zero.
+#[derive(Debug)]
+pub(crate) struct Revisions {
+    by_entity: HashMap<EntityId, Vec<(ComponentTypeId, u64)>>,
+    enabled: bool,
+}
+
+impl Default for Revisions {
+    fn default() -> Self {
+        Self {
+            by_entity: HashMap::new(),
+            enabled: true,
+        }
+    }
+}
+
+impl Revisions {
+    /// Increments the revision of one component.
+    #[inline]
+    pub(crate) fn bump(&mut self, entity: EntityId, type_id: ComponentTypeId) {
+        if !self.enabled {
+            return;
+        }
+        let revisions = self.by_entity.entry(entity).or_default();
+        match revisions.iter_mut().find(|(id, _)| *id == type_id) {
+            Some((_, revision)) => *revision = revision.wrapping_add(1),
+            None => revisions.push((type_id, 1)),
+        }
+    }
+
+    /// Returns the revision of one component.
+    pub(crate) fn get(&self, entity: EntityId, type_id: ComponentTypeId) -> u64 {
+        self.by_entity
+            .get(&entity)
+            .and_then(|revisions| revisions.iter().find(|(id, _)| *id == type_id))
+            .map_or(0, |&(_, revision)| revision)
+    }
+
+    /// Drops the revisions of a despawned entity.
+    #[inline]
+    pub(crate) fn forget(&mut self, entity: EntityId) {
+        if self.enabled {
+            self.by_entity.remove(&entity);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.by_entity.clear();
+    }
+}
+
+impl World {
+    /// Returns the revision of an entity's `T` component, or `None` if the
+    /// entity is not alive, has no `T`, or revision tracking is off.
+    ///
+    /// The revision is incremented every time `T` is added to the entity,
+    /// whether by spawning or by [`insert`](Self::insert), and by every
+    /// [`get_mut`](Self::get_mut) of it, whether or not the value actually
+    /// changed. A component is therefore at revision 1 once it is added. Removing a component keeps its count, so removing
+    /// and inserting it again still yields a new revision. Changes made
+    /// through queries or [`SubWorld`](super::SubWorld)s are not counted;
+    /// report them with [`bump_revision`](Self::bump_revision).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Mesh(Vec<f32>);
+    /// impl Component for Mesh {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Mesh(vec![0.0])).id();
+    /// let built_from = world.component_revision::<Mesh>(entity);
+    ///
+    /// world.get_mut::<Mesh>(entity).unwrap().0.push(1.0);
+    /// assert_ne!(world.component_revision::<Mesh>(entity), built_from);
+    /// ```
+    pub fn component_revision<T: Component>(&self, entity: EntityId) -> Option<u64> {
+        (self.revisions.enabled && self.has::<T>(entity))
+            .then(|| self.revisions.get(entity, ComponentTypeId::of::<T>()))
+    }
+
+    /// Turns component revision counting on or off.
+    ///
+    /// Counting is on by default. While it is off, mutations skip the
+    /// revision table and [`component_revision`](Self::component_revision)
+    /// returns `None`. Turning it off discards the counts so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Mesh(Vec<f32>);
+    ///
+    /// let mut world = World::builder().revision_tracking(false).build();
+    /// let entity = world.spawn().with(Mesh(Vec::new())).id();
+    /// assert!(!world.is_revision_tracking());
+    /// assert_eq!(world.component_revision::<Mesh>(entity), None);
+    /// ```
+    pub fn set_revision_tracking(&mut self, enabled: bool) {
+        if !enabled {
+            self.revisions.clear();
+        }
+        self.revisions.enabled = enabled;
+    }
+
+    /// Returns `true` if component revisions are being counted.
+    pub fn is_revision_tracking(&self) -> bool {
+        self.revisions.enabled
+    }
+
+    /// Increments the revision of an entity's `T` component, for changes
+    /// made where the world cannot see them, such as through a query.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the entity is not alive, has no `T`, or revision tracking
+    /// is off.
+    pub fn bump_revision<T: Component>(&mut self, entity: EntityId) -> bool {
+        if !self.revisions.enabled || !self.has::<T>(entity) {
+            return false;
+        }
+        self.revisions.bump(entity, ComponentTypeId::of::<T>());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Transform(f32);
+    impl Component for Transform {}
+
+    #[derive(Debug, PartialEq)]
+    struct Tint(u8);
+    impl Component for Tint {}
+
+    #[test]
+    fn revisions_count_changes_per_component() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Transform(0.0)).id();
+        let other = world.spawn().with(Transform(0.0)).id();
+        assert_eq!(world.component_revision::<Transform>(entity), Some(1));
+        assert_eq!(world.component_revision::<Tint>(entity), None);
+
+        world.get_mut::<Transform>(entity).unwrap().0 = 1.0;
+        world.insert(entity, Transform(2.0));
+        world.insert(entity, Tint(1));
+        assert_eq!(world.component_revision::<Transform>(entity), Some(3));
+        assert_eq!(world.component_revision::<Tint>(entity), Some(1));
+        assert_eq!(world.component_revision::<Transform>(other), Some(1));
+
+        // Removing and inserting again still moves the revision on
+        world.remove::<Tint>(entity);
+        assert_eq!(world.component_revision::<Tint>(entity), None);
+        world.insert_batch([(entity, Tint(2)), (other, Tint(2))]);
+        assert_eq!(world.component_revision::<Tint>(entity), Some(2));
+        assert_eq!(world.component_revision::<Tint>(other), Some(1));
+
+        for transform in world.query::<&mut Transform>() {
+            transform.0 += 1.0;
+        }
+        assert!(world.bump_revision::<Transform>(other));
+        let empty = world.spawn_empty();
+        assert!(!world.bump_revision::<Tint>(empty));
+        assert_eq!(world.component_revision::<Transform>(other), Some(2));
+
+        world.despawn(entity);
+        assert_eq!(world.component_revision::<Transform>(entity), None);
+        assert!(!world.revisions.by_entity.contains_key(&entity));
+    }
+
+    #[test]
+    fn spawned_and_inserted_components_start_alike() {
+        crate::component::registry::ComponentRegistry::global().register_clone::<Transform>();
+        let mut world = World::new();
+        let built = world.spawn().with(Transform(0.0)).id();
+        let bundled = world.spawn_bundle((Transform(0.0),));
+        let inserted = world.spawn_empty();
+        world.insert(inserted, Transform(0.0));
+        let cloned = world.clone_entity(built).unwrap();
+
+        for entity in [built, bundled, inserted, cloned] {
+            assert_eq!(world.component_revision::<Transform>(entity), Some(1));
+        }
+    }
+
+    #[test]
+    fn revision_tracking_can_be_turned_off() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Transform(0.0)).id();
+        world.set_revision_tracking(false);
+        assert!(world.revisions.by_entity.is_empty());
+
+        world.insert(entity, Tint(1));
+        world.get_mut::<Transform>(entity).unwrap().0 = 1.0;
+        assert!(!world.bump_revision::<Transform>(entity));
+        assert!(world.revisions.by_entity.is_empty());
+        assert_eq!(world.component_revision::<Transform>(entity), None);
+
+        world.set_revision_tracking(true);
+        world.get_mut::<Transform>(entity).unwrap().0 = 2.0;
+        assert_eq!(world.component_revision::<Transform>(entity), Some(1));
+    }
+}
@@ -0,0 +1,323 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Disjoint views into a world.
+//!
+//! A [`SubWorld`] is a view of a [`World`] restricted to the component types
+//! named by an [`Access`]. [`World::split`] hands out several sub-worlds at
+//! once after checking that their accesses do not conflict, so code blocks
+//! that touch disjoint components can run on separate threads without a
+//! scheduler.
+//!
+//! # Examples
+//!
+//! ```
+//! use pecs::prelude::*;
+//! use pecs::query::Access;
+//!
+//! #[derive(Debug)]
+//! struct Position(f32);
+//! impl Component for Position {}
+//!
+//! #[derive(Debug)]
+//! struct Health(u32);
+//! impl Component for Health {}
+//!
+//! let mut world = World::new();
+//! world.spawn().with(Position(0.0)).with(Health(10)).id();
+//!
+//! let [mut movement, mut combat] = world
+//!     .split([Access::of::<&mut Position>(), Access::of::<&mut Health>()])
+//!     .unwrap();
+//!
+//! std::thread::scope(|scope| {
+//!     scope.spawn(move || {
+//!         for position in movement.query::<&mut Position>() {
+//!             position.0 += 1.0;
+//!         }
+//!     });
+//!     scope.spawn(move || {
+//!         for health in combat.query::<&mut Health>() {
+//!             health.0 -= 1;
+//!         }
+//!     });
+//! });
+//! ```
+
+use super::World;
+use crate::component::Component;
+use crate::component::archetype::ArchetypeManager;
+use crate::entity::{EntityId, EntityManager};
+use crate::query::iter::QueryIter;
+use crate::query::{Access, Filter, Query};
+use std::fmt;
+
+/// A view of a world limited to the components of an [`Access`].
+///
+/// Created by [`World::split`]. Structural changes (spawning, despawning,
+/// inserting or removing components) are not possible through a sub-world,
+/// and changes made through it are not reported to the persistence change
+/// tracker.
+pub struct SubWorld<'w> {
+    entities: &'w EntityManager,
+    archetypes: &'w ArchetypeManager,
+    deterministic_iteration: bool,
+    access: Access,
+}
+
+impl<'w> SubWorld<'w> {
+    fn new(world: &'w World, access: Access) -> Self {
+        Self {
+            entities: &world.entities,
+            archetypes: &world.archetypes,
+            deterministic_iteration: world.deterministic_iteration,
+            access,
+        }
+    }
+
+    /// Returns the component access this view grants.
+    pub fn access(&self) -> &Access {
+        &self.access
+    }
+
+    /// Checks if an entity is alive.
+    pub fn is_alive(&self, entity: EntityId) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Checks if an entity has a component.
+    ///
+    /// Presence is structural, so this works for any component type.
+    pub fn has<T: Component>(&self, entity: EntityId) -> bool {
+        self.is_alive(entity)
+            && self
+                .archetypes
+                .get_entity_location(entity)
+                .and_then(|location| self.archetypes.get_archetype(location.archetype_id))
+                .is_some_and(|archetype| archetype.has_component::<T>())
+    }
+
+    /// Gets a reference to a component of an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this view does not grant access to `T`.
+    pub fn get<T: Component>(&self, entity: EntityId) -> Option<&T> {
+        self.check(
+            self.access.can_read::<T>(),
+            "read",
+            std::any::type_name::<T>(),
+        );
+        // SAFETY: `split` only hands out views whose accesses don't conflict,
+        // and this one grants reading `T`
+        unsafe { self.component_ptr::<T>(entity).map(|ptr| &*ptr) }
+    }
+
+    /// Gets a mutable reference to a component of an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this view does not grant writing `T`.
+    pub fn get_mut<T: Component>(&mut self, entity: EntityId) -> Option<&mut T> {
+        self.check(
+            self.access.can_write::<T>(),
+            "write",
+            std::any::type_name::<T>(),
+        );
+        // SAFETY: `split` only hands out views whose accesses don't conflict,
+        // this one grants writing `T`, and `&mut self` keeps it unique
+        unsafe { self.component_ptr::<T>(entity).map(|ptr| &mut *ptr) }
+    }
+
+    /// Executes a query over the components this view grants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses components this view does not grant.
+    pub fn query<Q: Query>(&mut self) -> QueryIter<'_, Q::Fetch, ()> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Executes a filtered query over the components this view grants.
+    ///
+    /// Filters only test component presence, so they may name any type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses components this view does not grant.
+    pub fn query_filtered<Q, F>(&mut self) -> QueryIter<'_, Q::Fetch, F>
+    where
+        Q: Query,
+        F: for<'a> Filter<'a>,
+    {
+        let uncovered = self.access.uncovered(&Access::of::<Q>());
+        if !uncovered.is_empty() {
+            panic!(
+                "query `{}` accesses components outside this SubWorld: {}",
+                std::any::type_name::<Q>(),
+                uncovered.join(", ")
+            );
+        }
+        let iter = if self.deterministic_iteration {
+            QueryIter::new_deterministic(self.archetypes)
+        } else {
+            QueryIter::new(self.archetypes)
+        };
+        iter.with_entities(self.entities)
+    }
+
+    /// Returns a pointer to a component of an entity.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold the access needed for how the pointer is used.
+    unsafe fn component_ptr<T: Component>(&self, entity: EntityId) -> Option<*mut T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let location = self.archetypes.get_entity_location(entity)?;
+        let archetype = self.archetypes.get_archetype(location.archetype_id)?;
+        // SAFETY: The entity lives in this archetype
+        unsafe { archetype.get_component_ptr::<T>(entity) }.map(|ptr| ptr as *mut T)
+    }
+
+    fn check(&self, allowed: bool, kind: &str, name: &str) {
+        if !allowed {
+            panic!("SubWorld does not grant {} access to `{}`", kind, name);
+        }
+    }
+}
+
+impl fmt::Debug for SubWorld<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubWorld")
+            .field("access", &self.access)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error returned by [`World::split`] when two requested accesses conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessConflict {
+    /// Positions of the two conflicting accesses
+    pub indices: (usize, usize),
+    /// Names of the component types both accesses claim
+    pub components: Vec<&'static str>,
+}
+
+impl fmt::Display for AccessConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "accesses {} and {} conflict on: {}",
+            self.indices.0,
+            self.indices.1,
+            self.components.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for AccessConflict {}
+
+impl World {
+    /// Splits the world into views over disjoint component sets.
+    ///
+    /// Each [`Access`] becomes one [`SubWorld`]. The views borrow the world
+    /// mutably as a whole, so no structural changes can happen while they
+    /// exist, but they can be used at the same time, including from
+    /// different threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AccessConflict`] if any two accesses conflict, i.e. one
+    /// writes a component type the other reads or writes.
+    pub fn split<const N: usize>(
+        &mut self,
+        accesses: [Access; N],
+    ) -> Result<[SubWorld<'_>; N], AccessConflict> {
+        for i in 0..N {
+            for j in i + 1..N {
+                let components = accesses[i].conflicts(&accesses[j]);
+                if !components.is_empty() {
+                    return Err(AccessConflict {
+                        indices: (i, j),
+                        components,
+                    });
+                }
+            }
+        }
+        self.flush_reserved();
+        let world: &World = self;
+        Ok(accesses.map(|access| SubWorld::new(world, access)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Position(i32);
+    impl Component for Position {}
+
+    #[derive(Debug, PartialEq)]
+    struct Velocity(i32);
+    impl Component for Velocity {}
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn split_grants_disjoint_access() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Position(0)).with(Velocity(2)).id();
+
+        let [mut movers, readers] = world
+            .split([Access::of::<&mut Position>(), Access::of::<&Velocity>()])
+            .unwrap();
+        assert_send(&movers);
+
+        let velocity = readers.get::<Velocity>(entity).unwrap();
+        movers.get_mut::<Position>(entity).unwrap().0 += velocity.0;
+        for position in movers.query::<&mut Position>() {
+            position.0 *= 10;
+        }
+        assert!(readers.has::<Position>(entity));
+
+        assert_eq!(world.get::<Position>(entity), Some(&Position(20)));
+    }
+
+    #[test]
+    fn split_rejects_conflicts() {
+        let mut world = World::new();
+        let error = world
+            .split([
+                Access::of::<&Velocity>(),
+                Access::of::<&Position>(),
+                Access::of::<&mut Position>(),
+            ])
+            .unwrap_err();
+        assert_eq!(error.indices, (1, 2));
+        assert_eq!(error.components, vec![std::any::type_name::<Position>()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside this SubWorld")]
+    fn query_outside_access_panics() {
+        let mut world = World::new();
+        let [mut view] = world.split([Access::of::<&Position>()]).unwrap();
+        view.query::<&mut Position>().count();
+    }
+}
@@ -0,0 +1,406 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! All-or-nothing world mutation.
+//!
+//! [`World::transaction`] runs a closure against a [`Transaction`], which
+//! applies mutations to the world immediately, so later reads in the
+//! closure see them, and records how to undo each one. If the closure
+//! returns an error or panics, the undo log is replayed in reverse and the
+//! world is left as it was; otherwise the mutations are kept.
+
+use super::{DetachedComponent, World};
+use crate::component::registry::ComponentRegistry;
+use crate::component::{Component, ComponentSet, ComponentTypeId};
+use crate::entity::EntityId;
+use std::ops::Deref;
+
+/// Reverts one mutation.
+type Undo = Box<dyn FnOnce(&mut World)>;
+
+/// A set of world mutations that is kept or reverted as a whole.
+///
+/// Created by [`World::transaction`]. Reads go straight to the world
+/// through `Deref`. Mutations must go through the transaction to be
+/// reverted: [`remove`](Self::remove) and [`get_mut`](Self::get_mut) keep a
+/// clone of the old value, so they need `T: Clone`.
+pub struct Transaction<'w> {
+    world: &'w mut World,
+    undo: Vec<Undo>,
+    /// Entities to despawn on commit
+    despawned: Vec<EntityId>,
+}
+
+impl<'w> Transaction<'w> {
+    fn new(world: &'w mut World) -> Self {
+        Self {
+            world,
+            undo: Vec::new(),
+            despawned: Vec::new(),
+        }
+    }
+
+    /// Spawns an empty entity, which is despawned again on rollback.
+    pub fn spawn(&mut self) -> EntityId {
+        let entity = self.world.spawn_empty();
+        self.undo.push(Box::new(move |world| {
+            world.despawn(entity);
+        }));
+        entity
+    }
+
+    /// Despawns an entity when the transaction commits.
+    ///
+    /// The entity stays alive until then, so its handle remains valid if
+    /// the transaction rolls back.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entity is alive.
+    pub fn despawn(&mut self, entity: EntityId) -> bool {
+        if !self.world.is_alive(entity) {
+            return false;
+        }
+        self.despawned.push(entity);
+        true
+    }
+
+    /// Inserts or replaces a component, as by [`World::insert`].
+    ///
+    /// On rollback the old value is put back, or the component is removed
+    /// together with any required components it brought, and any members
+    /// of its exclusive group it displaced are restored.
+    pub fn insert<T: Component>(&mut self, entity: EntityId, component: T) -> bool {
+        if let Some(slot) = self.world.get_mut::<T>(entity) {
+            let previous = std::mem::replace(slot, component);
+            self.undo.push(Box::new(move |world| {
+                if let Some(slot) = world.get_mut::<T>(entity) {
+                    *slot = previous;
+                }
+            }));
+            return true;
+        }
+        if !self.world.is_alive(entity) {
+            return false;
+        }
+
+        // Take out the rivals the insert would drop, to restore them later
+        let rivals = ComponentRegistry::global().exclusive_with(ComponentTypeId::of::<T>());
+        let displaced = self.world.detach_components(entity, &rivals);
+        let before = self.world.component_types_of(entity);
+        if !self.world.insert(entity, component) {
+            self.world.reattach_components(entity, displaced);
+            return false;
+        }
+        let added: ComponentSet = self
+            .world
+            .component_types_of(entity)
+            .iter()
+            .filter(|type_id| !before.contains(*type_id))
+            .collect();
+        self.undo.push(Box::new(move |world| {
+            drop(world.detach_components(entity, &added));
+            world.reattach_components(entity, displaced);
+        }));
+        true
+    }
+
+    /// Removes a component, returning it. A clone is inserted back on
+    /// rollback.
+    pub fn remove<T: Component + Clone>(&mut self, entity: EntityId) -> Option<T> {
+        let component = self.world.remove::<T>(entity)?;
+        let previous = component.clone();
+        self.undo.push(Box::new(move |world| {
+            world.insert_component(entity, previous);
+        }));
+        Some(component)
+    }
+
+    /// Returns a mutable reference to a component. A clone of the current
+    /// value is written back on rollback.
+    pub fn get_mut<T: Component + Clone>(&mut self, entity: EntityId) -> Option<&mut T> {
+        let previous = self.world.get::<T>(entity)?.clone();
+        self.undo.push(Box::new(move |world| {
+            if let Some(slot) = world.get_mut::<T>(entity) {
+                *slot = previous;
+            }
+        }));
+        self.world.get_mut::<T>(entity)
+    }
+
+    /// Keeps every mutation and applies the pending despawns.
+    fn commit(mut self) {
+        self.undo.clear();
+        let despawned = std::mem::take(&mut self.despawned);
+        self.world.despawn_many(despawned);
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = World;
+
+    fn deref(&self) -> &World {
+        self.world
+    }
+}
+
+impl Drop for Transaction<'_> {
+    /// Rolls back whatever has not been committed.
+    fn drop(&mut self) {
+        while let Some(undo) = self.undo.pop() {
+            undo(self.world);
+        }
+    }
+}
+
+impl World {
+    /// Runs `f` as a transaction: if it returns `Err` or panics, every
+    /// mutation it made through the [`Transaction`] is rolled back.
+    ///
+    /// Mutations take effect immediately, so `f` reads its own writes.
+    /// Entity handles stay valid across a rollback, as despawns are
+    /// deferred until commit. Mutations made by other means, such as
+    /// applying commands, are not reverted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error `f` returned, after rolling back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Gold(u32);
+    /// impl Component for Gold {}
+    ///
+    /// let mut world = World::new();
+    /// let buyer = world.spawn().with(Gold(5)).id();
+    /// let seller = world.spawn().with(Gold(0)).id();
+    ///
+    /// let trade = world.transaction(|tx| {
+    ///     tx.get_mut::<Gold>(seller).unwrap().0 += 10;
+    ///     let gold = tx.get_mut::<Gold>(buyer).unwrap();
+    ///     gold.0 = gold.0.checked_sub(10).ok_or("not enough gold")?;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(trade, Err("not enough gold"));
+    /// assert_eq!(world.get::<Gold>(seller), Some(&Gold(0)));
+    /// ```
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut Transaction<'_>) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let mut transaction = Transaction::new(self);
+        let value = f(&mut transaction)?;
+        transaction.commit();
+        Ok(value)
+    }
+
+    /// Returns the component types of an entity.
+    pub(super) fn component_types_of(&self, entity: EntityId) -> ComponentSet {
+        self.archetypes
+            .get_entity_location(entity)
+            .and_then(|location| self.archetypes.get_archetype(location.archetype_id))
+            .map(|archetype| archetype.component_types().clone())
+            .unwrap_or_default()
+    }
+
+    /// Moves the components of `types` out of an entity without dropping
+    /// them.
+    pub(super) fn detach_components(
+        &mut self,
+        entity: EntityId,
+        types: &ComponentSet,
+    ) -> Vec<DetachedComponent> {
+        let Some(source_id) = self
+            .archetypes
+            .get_entity_location(entity)
+            .map(|location| location.archetype_id)
+        else {
+            return Vec::new();
+        };
+        let Some(source) = self.archetypes.get_archetype(source_id) else {
+            return Vec::new();
+        };
+        let Some(row) = source.get_entity_row(entity) else {
+            return Vec::new();
+        };
+        if source.component_types().is_disjoint(types) {
+            return Vec::new();
+        }
+
+        let mut target_types = source.component_types().clone();
+        let mut target_info = Vec::new();
+        let mut detached = Vec::new();
+        for info in source.component_info() {
+            if !types.contains(info.type_id()) {
+                target_info.push(info.clone());
+                continue;
+            }
+            target_types.remove(info.type_id());
+            if let Some(storage) = source.get_storage(info.type_id()) {
+                // SAFETY: The row holds an initialized component of this
+                // type, which the move below discards without dropping
+                detached.push(unsafe { DetachedComponent::new(info, storage.get(row)) });
+            }
+        }
+
+        let target_id = self
+            .archetypes
+            .get_or_create_archetype(target_types, target_info);
+        // SAFETY: The entity is in the source archetype and the target has
+        // exactly its remaining components
+        unsafe {
+            self.move_entity(entity, source_id, target_id, &[]);
+        }
+        self.track_modified(entity);
+        detached
+    }
+
+    /// Moves detached components back into an entity, dropping them if it
+    /// is no longer alive.
+    fn reattach_components(&mut self, entity: EntityId, components: Vec<DetachedComponent>) {
+        for component in components {
+            // SAFETY: The allocation holds a valid value of the type, which
+            // is moved into the world and then only deallocated
+            if unsafe { self.insert_raw(entity, &component.info, component.ptr) } {
+                component.release();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Gold(u32);
+    impl Component for Gold {}
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Sword;
+    impl Component for Sword {}
+
+    #[test]
+    fn error_rolls_back_every_mutation() {
+        #[derive(Debug)]
+        struct Sheathed(Arc<AtomicUsize>);
+        impl Component for Sheathed {}
+        impl Drop for Sheathed {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        #[derive(Debug, PartialEq)]
+        struct Drawn;
+        impl Component for Drawn {}
+        #[derive(Debug, PartialEq)]
+        struct Stance(u8);
+        impl Component for Stance {}
+
+        let registry = ComponentRegistry::global();
+        registry.register_exclusive::<(Sheathed, Drawn)>();
+        registry.register_default(|| Stance(1));
+        registry.register_requires::<Drawn, Stance>();
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut world = World::new();
+        let buyer = world
+            .spawn()
+            .with(Gold(5))
+            .with(Sheathed(drops.clone()))
+            .id();
+        let seller = world.spawn().with(Gold(0)).with(Sword).id();
+
+        let result: Result<(), &str> = world.transaction(|tx| {
+            let sword = tx.remove::<Sword>(seller).unwrap();
+            tx.insert(buyer, sword);
+            tx.insert(buyer, Drawn);
+            tx.insert(seller, Gold(10));
+            let receipt = tx.spawn();
+            tx.insert(receipt, Gold(10));
+            assert!(tx.despawn(seller));
+            assert!(tx.has::<Stance>(buyer));
+            Err("buyer cannot pay")
+        });
+
+        assert_eq!(result, Err("buyer cannot pay"));
+        assert_eq!(world.len(), 2);
+        assert_eq!(world.get::<Gold>(seller), Some(&Gold(0)));
+        assert!(world.has::<Sword>(seller));
+        assert!(!world.has::<Sword>(buyer));
+        assert!(!world.has::<Drawn>(buyer));
+        assert!(!world.has::<Stance>(buyer));
+        assert_eq!(
+            world
+                .get::<Sheathed>(buyer)
+                .map(|s| Arc::ptr_eq(&s.0, &drops)),
+            Some(true)
+        );
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(world.validate(), vec![]);
+    }
+
+    #[test]
+    fn commit_keeps_mutations_and_applies_despawns() {
+        let mut world = World::new();
+        let buyer = world.spawn().with(Gold(15)).id();
+        let seller = world.spawn().with(Gold(0)).with(Sword).id();
+
+        let price = world.transaction(|tx| {
+            let price = 10;
+            tx.get_mut::<Gold>(buyer).unwrap().0 -= price;
+            tx.get_mut::<Gold>(seller).unwrap().0 += price;
+            let sword = tx.remove::<Sword>(seller).unwrap();
+            tx.insert(buyer, sword);
+            tx.despawn(seller);
+            // Despawns wait for the commit
+            assert!(tx.is_alive(seller));
+            Ok::<_, ()>(price)
+        });
+
+        assert_eq!(price, Ok(10));
+        assert_eq!(world.get::<Gold>(buyer), Some(&Gold(5)));
+        assert!(world.has::<Sword>(buyer));
+        assert!(!world.is_alive(seller));
+    }
+
+    #[test]
+    fn panic_rolls_back() {
+        let mut world = World::new();
+        let entity = world.spawn().with(Gold(1)).id();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.transaction(|tx| {
+                tx.get_mut::<Gold>(entity).unwrap().0 = 100;
+                tx.spawn();
+                panic!("mid-trade");
+                #[allow(unreachable_code)]
+                Ok::<(), ()>(())
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(world.len(), 1);
+        assert_eq!(world.get::<Gold>(entity), Some(&Gold(1)));
+    }
+}
@@ -0,0 +1,352 @@
+//
+// Copyright 2026 Hans W. Uhlig. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! World consistency checking.
+//!
+//! A world keeps the same facts in several places: the allocator knows
+//! which entities are alive, the archetype manager maps each entity to a
+//! location, and each archetype has an entity list, a row index and one
+//! column per component. [`World::validate`] cross-checks them, which is
+//! useful in debug builds and tests after complex operations such as
+//! merge, load or compact.
+
+use super::World;
+use crate::component::archetype::{ArchetypeId, EntityLocation};
+use crate::entity::EntityId;
+
+/// An inconsistency found by [`World::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyError {
+    /// An archetype row holds an entity that is not alive.
+    DeadEntity {
+        /// The archetype holding the row
+        archetype: ArchetypeId,
+        /// The row
+        row: usize,
+        /// The dead entity
+        entity: EntityId,
+    },
+    /// An archetype's row index disagrees with its entity list.
+    RowIndexMismatch {
+        /// The archetype
+        archetype: ArchetypeId,
+        /// The row the entity is listed at
+        row: usize,
+        /// The entity
+        entity: EntityId,
+        /// The row the index maps the entity to, if any
+        indexed: Option<usize>,
+    },
+    /// An entity's recorded location is not the row that holds it.
+    LocationMismatch {
+        /// The entity
+        entity: EntityId,
+        /// The row holding the entity
+        actual: EntityLocation,
+        /// The recorded location, if any
+        recorded: Option<EntityLocation>,
+    },
+    /// A live entity's recorded location points at a row that doesn't hold
+    /// it.
+    DanglingLocation {
+        /// The entity
+        entity: EntityId,
+        /// The recorded location
+        location: EntityLocation,
+    },
+    /// A component column's length differs from the archetype's row count.
+    ColumnLength {
+        /// The archetype
+        archetype: ArchetypeId,
+        /// Name of the column's component type
+        component: &'static str,
+        /// Number of components in the column
+        len: usize,
+        /// Number of rows in the archetype
+        rows: usize,
+    },
+    /// An archetype has no column for one of its component types.
+    MissingColumn {
+        /// The archetype
+        archetype: ArchetypeId,
+        /// Name of the component type
+        component: &'static str,
+    },
+}
+
+impl std::fmt::Display for ConsistencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsistencyError::DeadEntity {
+                archetype,
+                row,
+                entity,
+            } => write!(
+                f,
+                "Archetype {} row {} holds dead entity {}",
+                archetype.index(),
+                row,
+                entity
+            ),
+            ConsistencyError::RowIndexMismatch {
+                archetype,
+                row,
+                entity,
+                indexed,
+            } => write!(
+                f,
+                "Archetype {} lists entity {} at row {} but indexes it at {:?}",
+                archetype.index(),
+                entity,
+                row,
+                indexed
+            ),
+            ConsistencyError::LocationMismatch {
+                entity,
+                actual,
+                recorded,
+            } => write!(
+                f,
+                "Entity {} is in archetype {} row {} but its location is {:?}",
+                entity,
+                actual.archetype_id.index(),
+                actual.row,
+                recorded
+            ),
+            ConsistencyError::DanglingLocation { entity, location } => write!(
+                f,
+                "Entity {} is located at archetype {} row {}, which doesn't hold it",
+                entity,
+                location.archetype_id.index(),
+                location.row
+            ),
+            ConsistencyError::ColumnLength {
+                archetype,
+                component,
+                len,
+                rows,
+            } => write!(
+                f,
+                "Archetype {} column {} has {} components for {} rows",
+                archetype.index(),
+                component,
+                len,
+                rows
+            ),
+            ConsistencyError::MissingColumn {
+                archetype,
+                component,
+            } => write!(
+                f,
+                "Archetype {} has no column for {}",
+                archetype.index(),
+                component
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConsistencyError {}
+
+impl World {
+    /// Cross-checks entity aliveness, entity locations, archetype row
+    /// indexes and column lengths.
+    ///
+    /// Returns every inconsistency found, or an empty list for a healthy
+    /// world. The check visits every row of every archetype, so it is meant
+    /// for debug builds and tests rather than every frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pecs::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct Position { x: f32, y: f32 }
+    /// impl Component for Position {}
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().with(Position { x: 0.0, y: 0.0 }).id();
+    /// world.spawn_empty();
+    /// world.despawn(entity);
+    /// world.compact();
+    ///
+    /// assert_eq!(world.validate(), vec![]);
+    /// ```
+    pub fn validate(&self) -> Vec<ConsistencyError> {
+        let mut errors = Vec::new();
+
+        for archetype in self.archetypes.iter() {
+            let archetype_id = archetype.id();
+            let rows = archetype.len();
+
+            for info in archetype.component_info() {
+                match archetype.get_storage(info.type_id()) {
+                    Some(storage) if storage.len() != rows => {
+                        errors.push(ConsistencyError::ColumnLength {
+                            archetype: archetype_id,
+                            component: info.type_name(),
+                            len: storage.len(),
+                            rows,
+                        });
+                    }
+                    Some(_) => {}
+                    None => errors.push(ConsistencyError::MissingColumn {
+                        archetype: archetype_id,
+                        component: info.type_name(),
+                    }),
+                }
+            }
+
+            for (row, &entity) in archetype.entities().iter().enumerate() {
+                let indexed = archetype.get_entity_row(entity);
+                if indexed != Some(row) {
+                    errors.push(ConsistencyError::RowIndexMismatch {
+                        archetype: archetype_id,
+                        row,
+                        entity,
+                        indexed,
+                    });
+                }
+
+                if !self.entities.is_alive(entity) {
+                    errors.push(ConsistencyError::DeadEntity {
+                        archetype: archetype_id,
+                        row,
+                        entity,
+                    });
+                    continue;
+                }
+
+                let actual = EntityLocation { archetype_id, row };
+                let recorded = self.archetypes.get_entity_location(entity);
+                if recorded != Some(actual) {
+                    errors.push(ConsistencyError::LocationMismatch {
+                        entity,
+                        actual,
+                        recorded,
+                    });
+                }
+            }
+        }
+
+        for (entity, _) in self.entities.iter() {
+            let Some(location) = self.archetypes.get_entity_location(entity) else {
+                continue;
+            };
+            let holds_entity = self
+                .archetypes
+                .get_archetype(location.archetype_id)
+                .and_then(|archetype| archetype.get_entity(location.row))
+                == Some(entity);
+            if !holds_entity {
+                errors.push(ConsistencyError::DanglingLocation { entity, location });
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{Component, ComponentTypeId};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Health(u32);
+    impl Component for Health {}
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Armor(u32);
+    impl Component for Armor {}
+
+    #[test]
+    fn complex_operations_keep_world_consistent() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..50)
+            .map(|i| world.spawn().with(Health(i)).with(Armor(i)).id())
+            .collect();
+        world.spawn_empty_batch(10);
+        for &entity in entities.iter().step_by(3) {
+            world.remove::<Armor>(entity);
+        }
+        world.despawn_many(entities.iter().copied().step_by(4));
+        world.despawn(entities[1]);
+        world.insert_batch(entities.iter().map(|&entity| (entity, Armor(0))));
+        assert_eq!(world.validate(), vec![]);
+
+        world.compact();
+        assert_eq!(world.validate(), vec![]);
+    }
+
+    #[test]
+    fn reports_corrupted_location_and_column() {
+        let mut world = World::new();
+        let first = world.spawn().with(Health(1)).id();
+        let second = world.spawn().with(Health(2)).id();
+        let archetype = world.archetype_of(first).unwrap();
+
+        // Point the first entity at the second's row
+        world.archetypes.set_entity_location(
+            first,
+            EntityLocation {
+                archetype_id: archetype,
+                row: 1,
+            },
+        );
+        let errors = world.validate();
+        assert!(errors.contains(&ConsistencyError::LocationMismatch {
+            entity: first,
+            actual: EntityLocation {
+                archetype_id: archetype,
+                row: 0,
+            },
+            recorded: Some(EntityLocation {
+                archetype_id: archetype,
+                row: 1,
+            }),
+        }));
+        assert!(errors.contains(&ConsistencyError::DanglingLocation {
+            entity: first,
+            location: EntityLocation {
+                archetype_id: archetype,
+                row: 1,
+            },
+        }));
+        assert!(world.get::<Health>(second).is_some());
+
+        // Drop a component without removing its row
+        let storage = world
+            .archetypes
+            .get_archetype_mut(archetype)
+            .unwrap()
+            .get_storage_mut(ComponentTypeId::of::<Health>())
+            .unwrap();
+        let mut removed = std::mem::MaybeUninit::<Health>::uninit();
+        unsafe { storage.swap_remove(1, removed.as_mut_ptr() as *mut u8) };
+        assert!(world.validate().iter().any(|error| matches!(
+            error,
+            ConsistencyError::ColumnLength {
+                len: 1,
+                rows: 2,
+                ..
+            }
+        )));
+    }
+}
@@ -0,0 +1,87 @@
+//! Regression checks built on the benchmark scenarios in `pecs::bench_util`.
+//!
+//! The scenario tests always run and check that each workload does what the
+//! benchmarks assume. The timing tests are ignored by default because they
+//! depend on the machine; run them with
+//! `cargo test --release --test bench_regression_tests -- --ignored`.
+
+use pecs::World;
+use pecs::bench_util::{self, FRAGMENTS, measure};
+use std::time::Duration;
+
+const ENTITIES: usize = 10_000;
+
+#[test]
+fn scenarios_visit_every_entity() {
+    let mut world = World::new();
+    bench_util::spawn_moving(&mut world, ENTITIES);
+    assert_eq!(bench_util::iterate_two(&mut world), ENTITIES);
+    assert_eq!(bench_util::iterate_four(&mut world), ENTITIES);
+    assert!(bench_util::iterate_one(&mut world) > 0.0);
+
+    let mut fragmented = World::new();
+    bench_util::spawn_fragmented(&mut fragmented, ENTITIES);
+    assert_eq!(bench_util::iterate_two(&mut fragmented), ENTITIES);
+    assert!(fragmented.stats().archetypes.len() > FRAGMENTS);
+}
+
+#[test]
+fn toggle_scale_restores_archetypes() {
+    let mut world = World::new();
+    let entities = bench_util::spawn_fragmented(&mut world, 100);
+    let before: Vec<_> = entities.iter().map(|&e| world.archetype_of(e)).collect();
+
+    bench_util::toggle_scale(&mut world, &entities);
+    let after: Vec<_> = entities.iter().map(|&e| world.archetype_of(e)).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn modify_and_detect_counts_modified_entities() {
+    let mut world = World::new();
+    let entities = bench_util::spawn_moving(&mut world, 100);
+    assert_eq!(bench_util::modify_and_detect(&mut world, &entities, 10), 10);
+}
+
+#[test]
+#[ignore = "timing; run with --release -- --ignored"]
+fn query_iteration_within_budget() {
+    let mut world = World::new();
+    bench_util::spawn_moving(&mut world, ENTITIES);
+
+    let budget = Duration::from_millis(20);
+    measure("iterate_one", 50, || bench_util::iterate_one(&mut world)).assert_at_most(budget);
+    measure("iterate_two", 50, || bench_util::iterate_two(&mut world)).assert_at_most(budget);
+    measure("iterate_four", 50, || bench_util::iterate_four(&mut world)).assert_at_most(budget);
+
+    let mut fragmented = World::new();
+    bench_util::spawn_fragmented(&mut fragmented, ENTITIES);
+    measure("iterate_fragmented", 50, || {
+        bench_util::iterate_two(&mut fragmented)
+    })
+    .assert_at_most(budget);
+}
+
+#[test]
+#[ignore = "timing; run with --release -- --ignored"]
+fn archetype_transitions_within_budget() {
+    let mut world = World::new();
+    let entities = bench_util::spawn_fragmented(&mut world, 1_000);
+
+    measure("toggle_scale", 20, || {
+        bench_util::toggle_scale(&mut world, &entities)
+    })
+    .assert_at_most(Duration::from_millis(5));
+}
+
+#[test]
+#[ignore = "timing; run with --release -- --ignored"]
+fn change_detection_within_budget() {
+    let mut world = World::new();
+    let entities = bench_util::spawn_moving(&mut world, ENTITIES);
+
+    measure("modify_and_detect", 20, || {
+        bench_util::modify_and_detect(&mut world, &entities, 1)
+    })
+    .assert_at_most(Duration::from_millis(10));
+}
@@ -140,3 +140,308 @@ fn test_derive_macro_insert_remove() {
 }
 
 // Made with Bob
+
+#[derive(Component)]
+#[component(name = "game::Armor", version = 3)]
+#[allow(dead_code)]
+struct Armor(u32);
+
+#[derive(Component)]
+#[component(transient)]
+#[allow(dead_code)]
+struct RenderCache(Vec<u8>);
+
+#[test]
+fn test_derive_macro_schema() {
+    use pecs::component::registry::ComponentRegistry;
+
+    let registry = ComponentRegistry::global();
+
+    let armor = registry.schema::<Armor>().unwrap();
+    assert_eq!(armor.name, "game::Armor");
+    assert_eq!(armor.version, 3);
+    assert!(!armor.transient);
+
+    let cache = registry.schema::<RenderCache>().unwrap();
+    assert_eq!(cache.name, "derive_macro_tests::RenderCache");
+    assert_eq!(cache.version, 1);
+    assert!(cache.transient);
+
+    // Persistence metadata uses the schema and skips transient types
+    let mut world = World::new();
+    world
+        .spawn()
+        .with(Armor(5))
+        .with(RenderCache(Vec::new()))
+        .id();
+    world.update_metadata();
+    let names: Vec<_> = world
+        .metadata()
+        .component_types
+        .iter()
+        .map(|info| (info.type_name.as_str(), info.version))
+        .collect();
+    assert_eq!(names, vec![("game::Armor", 3)]);
+}
+
+#[derive(Bundle)]
+struct MovementBundle {
+    position: Position,
+    velocity: Velocity,
+}
+
+#[derive(Bundle)]
+struct PlayerBundle {
+    movement: MovementBundle,
+    health: Health,
+}
+
+#[derive(Bundle)]
+struct NameTag(Name, Armor);
+
+#[test]
+fn test_derive_bundle() {
+    let mut world = World::new();
+    let player = world.spawn_bundle(PlayerBundle {
+        movement: MovementBundle {
+            position: Position { x: 1.0, y: 2.0 },
+            velocity: Velocity { x: 0.5, y: 0.0 },
+        },
+        health: Health {
+            current: 90,
+            max: 100,
+        },
+    });
+
+    assert_eq!(world.get::<Position>(player).unwrap().x, 1.0);
+    assert_eq!(world.get::<Health>(player).unwrap().current, 90);
+    assert!(world.has::<Velocity>(player));
+
+    let movement: MovementBundle = world.take(player).unwrap();
+    assert_eq!(movement.position.y, 2.0);
+    assert!(!world.has::<Position>(player));
+    assert!(world.has::<Health>(player));
+
+    assert!(world.insert_bundle(player, NameTag(Name("hero".to_string()), Armor(2))));
+    assert_eq!(world.get::<Name>(player).unwrap().0, "hero");
+}
+
+#[derive(
+    Component, SerializableComponent, serde::Serialize, serde::Deserialize, Debug, PartialEq,
+)]
+#[component(name = "game::Inventory", version = 4)]
+struct Inventory {
+    slots: Vec<u32>,
+}
+
+#[derive(Component, SerializableComponent, serde::Serialize, serde::Deserialize)]
+#[component(transient)]
+struct Scratch(u8);
+
+#[test]
+fn test_derive_serializable_component() {
+    use pecs::persistence::SerializableComponent;
+
+    let inventory = Inventory {
+        slots: vec![1, 2, 3],
+    };
+    let mut bytes = Vec::new();
+    inventory.serialize(&mut bytes).unwrap();
+    let decoded = Inventory::deserialize(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded, inventory);
+    assert_eq!(Inventory::VERSION, 4);
+    assert!(!inventory.is_transient());
+
+    assert!(Scratch(0).is_transient());
+    assert_eq!(Scratch::VERSION, 1);
+    assert!(Inventory::deserialize(&mut b"not json".as_slice()).is_err());
+}
+
+#[test]
+fn test_derive_serializable_component_registers_for_saves() {
+    use pecs::component::registry::ComponentRegistry;
+
+    assert!(
+        ComponentRegistry::global()
+            .json_names()
+            .contains(&"game::Inventory".to_string())
+    );
+
+    let mut world = World::new();
+    let entity = world
+        .spawn()
+        .with(Inventory {
+            slots: vec![4, 5, 6],
+        })
+        .id();
+    let stable_id = world.get_stable_id(entity).unwrap();
+
+    let mut buffer = Vec::new();
+    world.save_binary(&mut buffer).unwrap();
+    let loaded = World::load_binary(&mut buffer.as_slice()).unwrap();
+    let restored = loaded.get_entity_id(stable_id).unwrap();
+    assert_eq!(
+        loaded.get::<Inventory>(restored),
+        Some(&Inventory {
+            slots: vec![4, 5, 6]
+        })
+    );
+}
+
+#[test]
+fn test_derive_caches_component_type_id() {
+    use pecs::component::ComponentTypeId;
+
+    let cached = ComponentTypeId::of::<Health>();
+    assert_eq!(cached, ComponentTypeId::lookup::<Health>());
+    assert_eq!(cached, ComponentTypeId::of::<Health>());
+
+    let mut world = World::new();
+    let entity = world.spawn().with(Health { current: 1, max: 1 }).id();
+    assert!(world.has::<Health>(entity));
+    assert!(!world.has::<Position>(entity));
+}
+
+#[derive(Component, Debug, Clone, PartialEq)]
+#[component(clone, debug)]
+struct Nickname(String);
+
+#[test]
+fn test_derive_registers_clone_and_debug() {
+    use pecs::component::ComponentInfo;
+
+    let info = ComponentInfo::of::<Nickname>();
+    assert!(info.is_cloneable());
+
+    let name = Nickname("Ace".to_string());
+    let src = &name as *const Nickname as *const u8;
+    let mut copy = std::mem::MaybeUninit::<Nickname>::uninit();
+    unsafe {
+        assert!(info.clone_into(src, copy.as_mut_ptr() as *mut u8));
+        assert_eq!(copy.assume_init(), name);
+    }
+    assert_eq!(
+        format!("{:?}", unsafe { info.debug(src) }),
+        "Nickname(\"Ace\")"
+    );
+}
+
+#[test]
+fn test_derive_macro_field_layouts() {
+    use pecs::component::registry::ComponentRegistry;
+    use pecs::persistence::BinaryPlugin;
+    use pecs::persistence::PersistencePlugin;
+
+    let position = ComponentRegistry::global().schema::<Position>().unwrap();
+    let fields: Vec<_> = position
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.type_name.as_str(), field.offset))
+        .collect();
+    assert_eq!(fields, vec![("x", "f32", 0), ("y", "f32", 4)]);
+
+    let armor = ComponentRegistry::global().schema::<Armor>().unwrap();
+    assert_eq!(armor.fields[0].name, "0");
+    assert_eq!(armor.fields[0].size, 4);
+
+    // The schema section is only written on request
+    let mut world = World::new();
+    world
+        .spawn()
+        .with(Position { x: 1.0, y: 2.0 })
+        .with(Armor(5))
+        .with(RenderCache(Vec::new()))
+        .id();
+
+    let mut bytes = Vec::new();
+    let plugin = BinaryPlugin::new();
+    plugin.save(&world, &mut bytes).unwrap();
+    let info = plugin.read_info(&mut bytes.as_slice()).unwrap();
+    assert_eq!(info.entities, 1);
+    assert!(info.schema().is_empty());
+
+    let mut bytes = Vec::new();
+    let plugin = BinaryPlugin::new().with_schema();
+    plugin.save(&world, &mut bytes).unwrap();
+    let info = plugin.read_info(&mut bytes.as_slice()).unwrap();
+    let names: Vec<_> = info.schema().iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["derive_macro_tests::Position", "game::Armor"]);
+    assert_eq!(info.schema()[0].fields, position.fields);
+    assert!(info.schema_docs().contains("| y | `f32` |"));
+
+    // Saves with a schema section still load
+    let loaded = plugin.load(&mut bytes.as_slice()).unwrap();
+    assert_eq!(loaded.len(), 1);
+}
+
+#[derive(Component, Clone, Copy)]
+#[component(align = 64)]
+struct Mass(f32);
+
+#[test]
+fn test_derive_macro_column_alignment() {
+    use pecs::component::ComponentInfo;
+
+    let info = ComponentInfo::of::<Mass>();
+    assert_eq!(info.alignment(), 4);
+    assert_eq!(info.column_alignment(), 64);
+
+    let mut world = World::new();
+    for i in 0..5 {
+        world.spawn().with(Mass(i as f32)).id();
+    }
+    let types = [info.type_id()];
+    let columns: Vec<_> = world
+        .iter_archetype_columns(&types)
+        .map(|slices| slices.columns()[0])
+        .collect();
+    assert_eq!(columns.len(), 1);
+    assert_eq!(columns[0].column_alignment, 64);
+    assert_eq!(columns[0].ptr as usize % 64, 0);
+}
+
+#[derive(Component, Debug, PartialEq)]
+struct Anchor(i32);
+
+#[derive(Component)]
+#[component(requires(Anchor))]
+struct Tether;
+
+#[derive(Component)]
+#[component(requires(Tether))]
+struct Grapple;
+
+#[test]
+fn test_derive_macro_requires() {
+    use pecs::component::InsertError;
+    use pecs::component::registry::ComponentRegistry;
+
+    let registry = ComponentRegistry::global();
+    assert_eq!(registry.requirements::<Tether>().len(), 1);
+
+    // Anchor has no default yet, so nothing is inserted
+    let mut world = World::new();
+    let entity = world.spawn_empty();
+    assert!(matches!(
+        world.try_insert(entity, Grapple),
+        Err(InsertError::MissingRequirement { .. })
+    ));
+    assert!(!world.has::<Grapple>(entity));
+    assert!(!world.has::<Tether>(entity));
+
+    // A bundle may bring its own requirements
+    let bundled = world.spawn_bundle((Tether, Anchor(3)));
+    assert_eq!(world.get::<Anchor>(bundled), Some(&Anchor(3)));
+
+    // Requirements are filled in transitively
+    registry.register_default(|| Anchor(0));
+    registry.register_default(|| Tether);
+    assert!(world.insert(entity, Grapple));
+    assert!(world.has::<Tether>(entity));
+    assert_eq!(world.get::<Anchor>(entity), Some(&Anchor(0)));
+
+    // Present components are kept
+    let built = world.spawn().with(Anchor(7)).with(Grapple).id();
+    assert!(world.has::<Tether>(built));
+    assert_eq!(world.get::<Anchor>(built), Some(&Anchor(7)));
+}
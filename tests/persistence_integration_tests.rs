@@ -390,3 +390,46 @@ fn test_default_plugin_selection() {
 
     cleanup_test_file(&path);
 }
+
+/// Builds the same world on every call, with metadata inserted in a
+/// different order each time.
+fn build_reproducible_world(reverse: bool) -> World {
+    let mut world = World::new();
+    world.set_fixed_timestamp(Some(1_700_000_000));
+    for i in 1..=32u128 {
+        world
+            .spawn_with_stable_id(StableId::from_raw(i))
+            .unwrap()
+            .with(Position {
+                x: i as f32,
+                y: 0.0,
+                z: 0.0,
+            })
+            .id();
+    }
+
+    let mut keys: Vec<String> = (0..16).map(|i| format!("key{}", i)).collect();
+    if reverse {
+        keys.reverse();
+    }
+    for key in keys {
+        world.metadata_mut().custom.insert(key.clone(), key);
+    }
+    world
+}
+
+#[test]
+fn test_identical_worlds_save_identical_bytes() {
+    let mut first = build_reproducible_world(false);
+    let mut second = build_reproducible_world(true);
+
+    let (mut a, mut b) = (Vec::new(), Vec::new());
+    first.save_binary(&mut a).unwrap();
+    second.save_binary(&mut b).unwrap();
+    assert_eq!(a, b);
+
+    let (mut a, mut b) = (Vec::new(), Vec::new());
+    first.save_json(&mut a).unwrap();
+    second.save_json(&mut b).unwrap();
+    assert_eq!(a, b);
+}
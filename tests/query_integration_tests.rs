@@ -353,6 +353,112 @@ fn query_optional_component() {
     assert_eq!(count_without_vel, 1);
 }
 
+#[test]
+fn query_any_of() {
+    use pecs::query::AnyOf;
+
+    let mut world = World::new();
+    let moving = world
+        .spawn()
+        .with(Position { x: 0.0, y: 0.0 })
+        .with(Velocity { x: 1.0, y: 0.0 })
+        .id();
+    let still = world.spawn().with(Position { x: 5.0, y: 5.0 }).id();
+    let hurt = world
+        .spawn()
+        .with(Health {
+            current: 1,
+            max: 10,
+        })
+        .id();
+
+    let mut seen = Vec::new();
+    for (entity, (pos, vel)) in world.query::<(EntityId, AnyOf<(&mut Position, &Velocity)>)>() {
+        if let (Some(pos), Some(vel)) = (pos, vel) {
+            pos.x += vel.x;
+        }
+        seen.push(entity);
+    }
+
+    assert_eq!(seen.len(), 2);
+    assert!(seen.contains(&moving) && seen.contains(&still));
+    assert!(!seen.contains(&hurt));
+    assert_eq!(world.get::<Position>(moving).unwrap().x, 1.0);
+}
+
+#[test]
+fn query_stable_ids() {
+    let mut world = World::new();
+
+    let a = world.spawn().with(Position { x: 1.0, y: 1.0 }).id();
+    let b = world
+        .spawn()
+        .with(Position { x: 2.0, y: 2.0 })
+        .with(Velocity { x: 1.0, y: 0.0 })
+        .id();
+    let expected = [
+        (a, world.get_stable_id(a).unwrap()),
+        (b, world.get_stable_id(b).unwrap()),
+    ];
+
+    let mut seen = 0;
+    for (entity, stable_id, _pos) in world.query::<(EntityId, &StableId, &Position)>() {
+        assert!(expected.contains(&(entity, *stable_id)));
+        seen += 1;
+    }
+    assert_eq!(seen, 2);
+
+    // Deterministic iteration goes through the same context
+    world.set_deterministic_iteration(true);
+    let ids: Vec<StableId> = world
+        .query::<(&StableId, &Velocity)>()
+        .map(|(stable_id, _)| *stable_id)
+        .collect();
+    assert_eq!(ids, vec![expected[1].1]);
+}
+
+#[test]
+fn query_skips_disabled_entities() {
+    use pecs::component::Disabled;
+    use pecs::query::ArchetypeMatch;
+    use pecs::query::filter::{Including, With};
+
+    let mut world = World::new();
+    let active = world.spawn().with(Position { x: 1.0, y: 1.0 }).id();
+    let parked = world
+        .spawn()
+        .with(Position { x: 2.0, y: 2.0 })
+        .with(Velocity { x: 1.0, y: 0.0 })
+        .id();
+    assert!(world.set_enabled(parked, false));
+    assert!(world.set_enabled(parked, false));
+
+    let seen: Vec<EntityId> = world.query::<EntityId>().collect();
+    assert_eq!(seen, vec![active]);
+    assert_eq!(world.query::<&Velocity>().count(), 0);
+
+    world.set_deterministic_iteration(true);
+    let all: Vec<EntityId> = world
+        .query_filtered::<EntityId, Including<Disabled>>()
+        .collect();
+    assert_eq!(all, vec![active, parked]);
+    let disabled: Vec<EntityId> = world
+        .query_filtered::<EntityId, (With<Disabled>, With<Velocity>)>()
+        .collect();
+    assert_eq!(disabled, vec![parked]);
+
+    let plan = world.explain_query::<&Velocity, ()>();
+    let archetype = world.archetype_of(parked).unwrap();
+    assert_eq!(
+        plan.archetype(archetype).unwrap().outcome,
+        ArchetypeMatch::Filtered(vec!["Disabled".to_string()])
+    );
+
+    assert!(world.set_enabled(parked, true));
+    assert!(world.is_enabled(parked));
+    assert_eq!(world.query::<&Velocity>().count(), 1);
+}
+
 #[test]
 #[ignore] // Performance benchmark - run with `cargo test -- --ignored`
 fn query_performance_baseline() {